@@ -2,6 +2,7 @@ use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group,
 use std::fs::File;
 use std::io::Write;
 use tempfile::TempDir;
+use thoth::constants::DEFAULT_MAX_FILE_SIZE_MB;
 use thoth::file::loaders::{FileLoader, load_file_auto};
 
 /// Generate a temporary NDJSON file with the specified number of records
@@ -58,7 +59,8 @@ fn bench_ndjson_loading(c: &mut Criterion) {
             let file_path = create_ndjson_file(&temp_dir, size);
 
             b.iter(|| {
-                let (_detected, file) = load_file_auto(black_box(&file_path)).unwrap();
+                let (_detected, file) =
+                    load_file_auto(black_box(&file_path), DEFAULT_MAX_FILE_SIZE_MB).unwrap();
                 black_box(file.len())
             });
         });
@@ -78,7 +80,8 @@ fn bench_json_array_loading(c: &mut Criterion) {
             let file_path = create_json_array_file(&temp_dir, size);
 
             b.iter(|| {
-                let (_detected, file) = load_file_auto(black_box(&file_path)).unwrap();
+                let (_detected, file) =
+                    load_file_auto(black_box(&file_path), DEFAULT_MAX_FILE_SIZE_MB).unwrap();
                 black_box(file.len())
             });
         });
@@ -98,7 +101,8 @@ fn bench_sequential_access(c: &mut Criterion) {
             let file_path = create_ndjson_file(&temp_dir, size);
 
             b.iter(|| {
-                let (_detected, mut file) = load_file_auto(&file_path).unwrap();
+                let (_detected, mut file) =
+                    load_file_auto(&file_path, DEFAULT_MAX_FILE_SIZE_MB).unwrap();
                 let len = file.len();
                 for i in 0..len {
                     black_box(file.get(black_box(i)).unwrap());
@@ -121,7 +125,8 @@ fn bench_random_access(c: &mut Criterion) {
     group.throughput(Throughput::Elements(100));
     group.bench_function("random_1000_records", |b| {
         b.iter(|| {
-            let (_detected, mut file) = load_file_auto(&file_path).unwrap();
+            let (_detected, mut file) =
+                load_file_auto(&file_path, DEFAULT_MAX_FILE_SIZE_MB).unwrap();
             // Access 100 random positions
             for i in (0..100).map(|x| (x * 13) % size) {
                 black_box(file.get(black_box(i)).unwrap());
@@ -142,14 +147,15 @@ fn bench_raw_bytes_vs_parsed(c: &mut Criterion) {
 
     group.bench_function("raw_bytes", |b| {
         b.iter(|| {
-            let (_detected, file) = load_file_auto(&file_path).unwrap();
+            let (_detected, file) = load_file_auto(&file_path, DEFAULT_MAX_FILE_SIZE_MB).unwrap();
             black_box(file.raw_bytes(black_box(500)).unwrap())
         });
     });
 
     group.bench_function("parsed_json", |b| {
         b.iter(|| {
-            let (_detected, mut file) = load_file_auto(&file_path).unwrap();
+            let (_detected, mut file) =
+                load_file_auto(&file_path, DEFAULT_MAX_FILE_SIZE_MB).unwrap();
             black_box(file.get(black_box(500)).unwrap())
         });
     });
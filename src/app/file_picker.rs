@@ -1,14 +1,28 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use rfd::FileDialog;
 
 use crate::{PLUGIN_MANAGER, plugin::Capability};
 
 fn supported_files(plugins_enabled: bool) -> Vec<(String, Vec<String>)> {
-    let mut all_supported_file_types = vec![(
-        "JSON".to_string(),
-        vec!["json".to_string(), "ndjson".to_string()],
-    )];
+    let mut all_supported_file_types = vec![
+        (
+            "JSON".to_string(),
+            vec!["json".to_string(), "ndjson".to_string()],
+        ),
+        (
+            "CSV".to_string(),
+            vec!["csv".to_string(), "tsv".to_string()],
+        ),
+        (
+            "YAML".to_string(),
+            vec!["yaml".to_string(), "yml".to_string()],
+        ),
+        ("TOML".to_string(), vec!["toml".to_string()]),
+        ("XML".to_string(), vec!["xml".to_string()]),
+        ("Parquet".to_string(), vec!["parquet".to_string()]),
+        ("Gzip".to_string(), vec!["gz".to_string()]),
+    ];
 
     if plugins_enabled && let Some(Some(plugin_manager)) = PLUGIN_MANAGER.get() {
         plugin_manager
@@ -36,3 +50,92 @@ pub fn pick_file(plugins_enabled: bool) -> Option<PathBuf> {
 
     fd.pick_file()
 }
+
+/// Pick a JSON Schema file to validate the open file against
+pub fn pick_schema_file() -> Option<PathBuf> {
+    FileDialog::new()
+        .set_title("Load JSON Schema")
+        .add_filter("JSON Schema", &["json"])
+        .pick_file()
+}
+
+/// Pick where to write a find-and-replace result, suggesting a file next to `source`
+pub fn pick_replace_output(source: &Path) -> Option<PathBuf> {
+    let suggested = crate::search::replace::ReplacePreview::default_output_path(source);
+    let mut fd = FileDialog::new().set_title("Save Replaced File");
+    if let Some(dir) = suggested.parent() {
+        fd = fd.set_directory(dir);
+    }
+    if let Some(name) = suggested.file_name() {
+        fd = fd.set_file_name(name.to_string_lossy());
+    }
+    fd.save_file()
+}
+
+/// Pick where to export bookmarks
+pub fn pick_bookmarks_export() -> Option<PathBuf> {
+    FileDialog::new()
+        .set_title("Export Bookmarks")
+        .add_filter("JSON", &["json"])
+        .set_file_name("thoth-bookmarks.json")
+        .save_file()
+}
+
+/// Pick where to export the tree as HTML, suggesting a name after `source`
+pub fn pick_html_export(source: &Path) -> Option<PathBuf> {
+    let stem = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "export".to_string());
+    FileDialog::new()
+        .set_title("Export View as HTML")
+        .add_filter("HTML", &["html", "htm"])
+        .set_file_name(format!("{}.html", stem))
+        .save_file()
+}
+
+/// Pick where to export records as `format`, suggesting a name after `source`
+pub fn pick_records_export(
+    source: &Path,
+    format: crate::file::export::ExportFormat,
+) -> Option<PathBuf> {
+    let stem = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "export".to_string());
+    let (filter, extensions): (&str, &[&str]) = match format.extension() {
+        "ndjson" => ("NDJSON", &["ndjson", "jsonl"]),
+        _ => ("JSON", &["json"]),
+    };
+    FileDialog::new()
+        .set_title("Export Records")
+        .add_filter(filter, extensions)
+        .set_file_name(format!("{}.export.{}", stem, format.extension()))
+        .save_file()
+}
+
+/// Pick a standalone theme file to load
+pub fn pick_theme_file() -> Option<PathBuf> {
+    FileDialog::new()
+        .set_title("Load Theme")
+        .add_filter("Theme", &["toml", "json"])
+        .pick_file()
+}
+
+/// Pick where to export the current theme, suggesting a file named after it
+pub fn pick_theme_export(theme_name: &str) -> Option<PathBuf> {
+    FileDialog::new()
+        .set_title("Export Theme")
+        .add_filter("TOML", &["toml"])
+        .add_filter("JSON", &["json"])
+        .set_file_name(format!("{}.toml", theme_name))
+        .save_file()
+}
+
+/// Pick a bookmarks file to import
+pub fn pick_bookmarks_import() -> Option<PathBuf> {
+    FileDialog::new()
+        .set_title("Import Bookmarks")
+        .add_filter("JSON", &["json"])
+        .pick_file()
+}
@@ -1,12 +1,13 @@
 mod file_picker;
 pub mod persistent_state;
+mod recent_files_check;
 mod search_handler;
 mod shortcut_handler;
 pub mod tab_manager;
 mod thoth_app;
 mod update_handler;
 
-pub use file_picker::pick_file;
+pub use file_picker::{pick_file, pick_theme_export, pick_theme_file};
 pub use shortcut_handler::ShortcutAction;
 pub use tab_manager::{TabEvent, TabId, TabManager, TabState, ThothTabViewer};
 pub use thoth_app::ThothApp;
@@ -1,14 +1,19 @@
 use crate::error::{Result, ThothError};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::constants::{DEFAULT_SIDEBAR_WIDTH, MAX_RECENT_FILES, MIN_SIDEBAR_WIDTH};
+use crate::platform::canonical_path_key;
+use crate::search::QueryMode;
 
 const MAX_SEARCH_HISTORY_PER_FILE: usize = 10;
 const MAX_FILES_WITH_HISTORY: usize = 20; // Keep history for at most 20 files
 const MAX_BOOKMARKS: usize = 100; // Maximum number of bookmarks
+const MAX_PINNED_FILES: usize = 20; // Maximum number of pinned recent files
+const MAX_FILES_WITH_EXPANSION: usize = 20; // Keep expansion state for at most 20 files
+const MAX_EXPANDED_PATHS_PER_FILE: usize = 10_000; // Larger sets (e.g. after expand-all) aren't kept
 
 /// What kind of content a persisted tab holds.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -38,6 +43,60 @@ pub struct PersistedTab {
     pub kind: PersistedTabKind,
 }
 
+/// Position and size of a window, in logical points
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    /// Top-left corner of the window frame
+    pub position: [f32; 2],
+    /// Size of the window's content area
+    pub size: [f32; 2],
+    /// Size of the monitor the window was on, when known
+    #[serde(default)]
+    pub monitor_size: Option<[f32; 2]>,
+}
+
+impl WindowGeometry {
+    /// Current geometry of a viewport, or None while it is minimized or
+    /// fullscreen (those aren't worth restoring)
+    pub fn from_viewport(info: &eframe::egui::ViewportInfo) -> Option<Self> {
+        if info.minimized == Some(true) || info.fullscreen == Some(true) {
+            return None;
+        }
+        let (outer, inner) = (info.outer_rect?, info.inner_rect?);
+        Some(Self {
+            position: [outer.min.x, outer.min.y],
+            size: [inner.width(), inner.height()],
+            monitor_size: info.monitor_size.map(|size| [size.x, size.y]),
+        })
+    }
+
+    /// Whether a window restored from this geometry can be trusted to be
+    /// on-screen, given the monitor it was actually placed on
+    ///
+    /// egui only reports the size of a window's monitor, not where it sits on
+    /// the desktop, so a change of monitor size is taken to mean the original
+    /// monitor is gone (e.g. disconnected) and the window landed elsewhere.
+    pub fn fits_monitor(&self, monitor_size: Option<[f32; 2]>) -> bool {
+        monitor_size.is_some() && monitor_size == self.monitor_size
+    }
+
+    /// Shrink to fit `monitor_size` and center on it
+    pub fn centered_on(&self, monitor_size: [f32; 2]) -> Self {
+        let size = [
+            self.size[0].min(monitor_size[0]),
+            self.size[1].min(monitor_size[1]),
+        ];
+        Self {
+            position: [
+                ((monitor_size[0] - size[0]) / 2.0).max(0.0),
+                ((monitor_size[1] - size[1]) / 2.0).max(0.0),
+            ],
+            size,
+            monitor_size: Some(monitor_size),
+        }
+    }
+}
+
 /// A bookmark for a specific JSON path within a file
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Bookmark {
@@ -57,10 +116,71 @@ struct SearchHistoryStore {
     histories: HashMap<String, (u64, Vec<String>)>,
 }
 
+impl SearchHistoryStore {
+    /// Remove one query from a file's history, dropping the file's entry
+    /// once its history is empty. Returns whether anything was removed.
+    fn remove_query(&mut self, file_path: &str, query: &str) -> bool {
+        let Some((_, queries)) = self.histories.get_mut(file_path) else {
+            return false;
+        };
+        let before = queries.len();
+        queries.retain(|q| q != query);
+        let removed = queries.len() != before;
+        if queries.is_empty() {
+            self.histories.remove(file_path);
+        }
+        removed
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ExpansionStore {
+    /// Maps file path to (last_accessed_timestamp, expanded node paths)
+    files: HashMap<String, (u64, Vec<String>)>,
+}
+
+impl ExpansionStore {
+    /// Record the expanded paths for a file, dropping the least recently
+    /// saved files beyond `MAX_FILES_WITH_EXPANSION`. An empty or oversized
+    /// set removes the entry instead.
+    fn set(&mut self, file_path: String, expanded: &HashSet<String>, timestamp: u64) {
+        if expanded.is_empty() || expanded.len() > MAX_EXPANDED_PATHS_PER_FILE {
+            self.files.remove(&file_path);
+            return;
+        }
+
+        let mut paths: Vec<String> = expanded.iter().cloned().collect();
+        paths.sort();
+
+        if !self.files.contains_key(&file_path) && self.files.len() >= MAX_FILES_WITH_EXPANSION {
+            // Make room by dropping the least recently saved files
+            let mut entries: Vec<_> = self
+                .files
+                .iter()
+                .map(|(k, (ts, _))| (*ts, k.clone()))
+                .collect();
+            entries.sort_by_key(|(ts, _)| std::cmp::Reverse(*ts));
+            for (_, key) in entries.into_iter().skip(MAX_FILES_WITH_EXPANSION - 1) {
+                self.files.remove(&key);
+            }
+        }
+        self.files.insert(file_path, (timestamp, paths));
+    }
+
+    fn get(&self, file_path: &str) -> Option<HashSet<String>> {
+        self.files
+            .get(file_path)
+            .map(|(_, paths)| paths.iter().cloned().collect())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PersistentState {
     #[serde(default)]
     recent_files: Vec<String>,
+    /// Files pinned above the recent list; never evicted by recency.
+    #[serde(default)]
+    pinned_files: Vec<String>,
     #[serde(default = "default_sidebar_width")]
     sidebar_width: f32,
     #[serde(default)]
@@ -73,6 +193,21 @@ pub struct PersistentState {
     /// Index into `open_tabs` of the tab that was active at last save.
     #[serde(default)]
     active_tab_index: usize,
+    /// Query mode last selected in the search panel, restored on launch.
+    #[serde(default)]
+    last_query_mode: QueryMode,
+    /// Search results are listed one row per record instead of grouped by record.
+    #[serde(default)]
+    flat_search_results: bool,
+    /// File most recently opened, reopened on launch when no tabs are restored.
+    #[serde(default)]
+    last_opened_file: Option<String>,
+    /// Main window position and size at last exit.
+    #[serde(default)]
+    window_geometry: Option<WindowGeometry>,
+    /// Settings window position and size when it was last closed.
+    #[serde(default)]
+    settings_window_geometry: Option<WindowGeometry>,
 }
 
 fn default_sidebar_width() -> f32 {
@@ -82,18 +217,29 @@ fn default_sidebar_width() -> f32 {
 impl Default for PersistentState {
     fn default() -> Self {
         // Try to load from disk, fallback to empty state on error
-        Self::load().unwrap_or(Self {
+        Self::load().unwrap_or_else(|_| Self::empty())
+    }
+}
+
+impl PersistentState {
+    /// Fresh state with nothing recorded yet (not loaded from disk)
+    fn empty() -> Self {
+        Self {
             recent_files: Vec::new(),
+            pinned_files: Vec::new(),
             sidebar_width: DEFAULT_SIDEBAR_WIDTH,
             sidebar_expanded: false,
             bookmarks: Vec::new(),
             open_tabs: Vec::new(),
             active_tab_index: 0,
-        })
+            last_query_mode: QueryMode::default(),
+            flat_search_results: false,
+            last_opened_file: None,
+            window_geometry: None,
+            settings_window_geometry: None,
+        }
     }
-}
 
-impl PersistentState {
     /// Get the path to the app state storage
     /// Returns: ~/.config/thoth/persistent_state.json on Linux/macOS
     ///          %APPDATA%/thoth/persistent_state.json on Windows
@@ -121,10 +267,11 @@ impl PersistentState {
             let contents = std::fs::read_to_string(&path).map_err(|e| ThothError::StateError {
                 reason: format!("Failed to read app state: {}", e),
             })?;
-            let app_state: PersistentState =
+            let mut app_state: PersistentState =
                 serde_json::from_str(&contents).map_err(|e| ThothError::StateError {
                     reason: format!("Failed to parse app state: {}", e),
                 })?;
+            app_state.canonicalize_paths();
             Ok(app_state)
         } else {
             // Try to migrate from old recent_files.json
@@ -155,11 +302,7 @@ impl PersistentState {
                 eprintln!("Migrating from old recent_files.json format...");
                 let new_state = PersistentState {
                     recent_files: old_data.files,
-                    sidebar_width: DEFAULT_SIDEBAR_WIDTH,
-                    sidebar_expanded: false,
-                    bookmarks: Vec::new(),
-                    open_tabs: Vec::new(),
-                    active_tab_index: 0,
+                    ..Self::empty()
                 };
 
                 // Save in new format
@@ -174,14 +317,37 @@ impl PersistentState {
         }
 
         // No migration needed or failed, return default
-        Ok(Self {
-            recent_files: Vec::new(),
-            sidebar_width: DEFAULT_SIDEBAR_WIDTH,
-            sidebar_expanded: false,
-            bookmarks: Vec::new(),
-            open_tabs: Vec::new(),
-            active_tab_index: 0,
-        })
+        Ok(Self::empty())
+    }
+
+    /// Re-key recent files and bookmarks on canonical paths, collapsing
+    /// entries that were recorded via a symlink or relative path
+    fn canonicalize_paths(&mut self) {
+        let mut seen = std::collections::HashSet::new();
+        self.recent_files = std::mem::take(&mut self.recent_files)
+            .into_iter()
+            .map(|f| canonical_path_key(&f))
+            .filter(|f| seen.insert(f.clone()))
+            .collect();
+
+        let mut seen = std::collections::HashSet::new();
+        self.pinned_files = std::mem::take(&mut self.pinned_files)
+            .into_iter()
+            .map(|f| canonical_path_key(&f))
+            .filter(|f| seen.insert(f.clone()))
+            .collect();
+        // A pinned file is listed only in the pinned section
+        self.recent_files.retain(|f| !seen.contains(f));
+
+        let mut seen = std::collections::HashSet::new();
+        self.bookmarks = std::mem::take(&mut self.bookmarks)
+            .into_iter()
+            .map(|mut b| {
+                b.file_path = canonical_path_key(&b.file_path);
+                b
+            })
+            .filter(|b| seen.insert((b.file_path.clone(), b.path.clone())))
+            .collect();
     }
 
     /// Save app state to disk
@@ -200,14 +366,28 @@ impl PersistentState {
     // Recent Files methods
 
     /// Add a file to recent files (moves to top if already exists)
+    ///
+    /// The path is stored canonicalized so a file opened via a symlink and via
+    /// its real path is a single entry.
     pub fn add_recent_file(&mut self, file_path: String, max_recent_files: usize) {
+        let file_path = canonical_path_key(&file_path);
+        self.last_opened_file = Some(file_path.clone());
+
+        // Pinned files keep their place in the pinned list
+        if self.pinned_files.contains(&file_path) {
+            return;
+        }
+
         // Remove if already exists
         self.recent_files.retain(|f| f != &file_path);
 
         // Add to front
         self.recent_files.insert(0, file_path);
+        self.truncate_recent_files(max_recent_files);
+    }
 
-        // Limit to configured max (or fallback to constant)
+    /// Limit recent files to the configured max (or fallback to constant)
+    fn truncate_recent_files(&mut self, max_recent_files: usize) {
         let limit = if max_recent_files > 0 {
             max_recent_files
         } else {
@@ -219,9 +399,11 @@ impl PersistentState {
         }
     }
 
-    /// Remove a file from recent files
+    /// Remove a file from recent files (and from the pinned list)
     pub fn remove_recent_file(&mut self, file_path: &str) {
-        self.recent_files.retain(|f| f != file_path);
+        let key = canonical_path_key(file_path);
+        self.recent_files.retain(|f| f != file_path && f != &key);
+        self.pinned_files.retain(|f| f != file_path && f != &key);
     }
 
     /// Get all recent files
@@ -229,6 +411,40 @@ impl PersistentState {
         &self.recent_files
     }
 
+    /// Pin a recent file, or unpin it if it is already pinned
+    ///
+    /// Pinning moves the file out of the recent list into the pinned list,
+    /// where truncation never evicts it. Unpinning puts it back at the top
+    /// of the recent list. Returns whether the file is now pinned.
+    pub fn toggle_pinned_file(&mut self, file_path: &str, max_recent_files: usize) -> Result<bool> {
+        let key = canonical_path_key(file_path);
+        if let Some(pos) = self.pinned_files.iter().position(|f| f == &key) {
+            self.pinned_files.remove(pos);
+            self.recent_files.insert(0, key);
+            self.truncate_recent_files(max_recent_files);
+            return Ok(false);
+        }
+
+        if self.pinned_files.len() >= MAX_PINNED_FILES {
+            return Err(ThothError::StateError {
+                reason: format!("At most {} files can be pinned", MAX_PINNED_FILES),
+            });
+        }
+        self.recent_files.retain(|f| f != file_path && f != &key);
+        self.pinned_files.push(key);
+        Ok(true)
+    }
+
+    /// Get all pinned files, in the order they were pinned
+    pub fn get_pinned_files(&self) -> &[String] {
+        &self.pinned_files
+    }
+
+    /// Get the file most recently opened, if any
+    pub fn get_last_opened_file(&self) -> Option<&str> {
+        self.last_opened_file.as_deref()
+    }
+
     // Sidebar width methods
 
     /// Set the sidebar width
@@ -253,10 +469,56 @@ impl PersistentState {
         self.sidebar_expanded
     }
 
+    // Search query mode methods
+
+    /// Set the query mode last selected in the search panel
+    pub fn set_last_query_mode(&mut self, mode: QueryMode) {
+        self.last_query_mode = mode;
+    }
+
+    /// Get the query mode last selected in the search panel
+    pub fn get_last_query_mode(&self) -> QueryMode {
+        self.last_query_mode
+    }
+
+    /// Set whether search results are listed flat instead of grouped by record
+    pub fn set_flat_search_results(&mut self, flat: bool) {
+        self.flat_search_results = flat;
+    }
+
+    /// Whether search results are listed flat instead of grouped by record
+    pub fn get_flat_search_results(&self) -> bool {
+        self.flat_search_results
+    }
+
+    // Window geometry methods
+
+    /// Remember the main window's position and size
+    pub fn set_window_geometry(&mut self, geometry: WindowGeometry) {
+        self.window_geometry = Some(geometry);
+    }
+
+    /// Main window position and size at last exit
+    pub fn get_window_geometry(&self) -> Option<WindowGeometry> {
+        self.window_geometry
+    }
+
+    /// Remember the settings window's position and size
+    pub fn set_settings_window_geometry(&mut self, geometry: WindowGeometry) {
+        self.settings_window_geometry = Some(geometry);
+    }
+
+    /// Settings window position and size when it was last closed
+    pub fn get_settings_window_geometry(&self) -> Option<WindowGeometry> {
+        self.settings_window_geometry
+    }
+
     // Bookmark methods
 
     /// Add a bookmark
     pub fn add_bookmark(&mut self, path: String, file_path: String, label: Option<String>) {
+        let file_path = canonical_path_key(&file_path);
+
         // Check if bookmark already exists for this path and file
         if self
             .bookmarks
@@ -289,8 +551,83 @@ impl PersistentState {
         }
     }
 
+    /// Set or clear (`None` / blank) a bookmark's label
+    ///
+    /// Returns false if `index` no longer points at a bookmark.
+    pub fn rename_bookmark(&mut self, index: usize, label: Option<String>) -> bool {
+        let Some(bookmark) = self.bookmarks.get_mut(index) else {
+            return false;
+        };
+        bookmark.label = label
+            .map(|label| label.trim().to_string())
+            .filter(|label| !label.is_empty());
+        true
+    }
+
+    /// Write all bookmarks to a standalone JSON file
+    pub fn export_bookmarks(&self, path: &Path) -> Result<()> {
+        let json =
+            serde_json::to_string_pretty(&self.bookmarks).map_err(|e| ThothError::StateError {
+                reason: format!("Failed to serialize bookmarks: {}", e),
+            })?;
+        fs::write(path, json).map_err(|e| ThothError::FileWriteError {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })
+    }
+
+    /// Merge bookmarks from a file written by `export_bookmarks`
+    ///
+    /// Entries already bookmarked (same path and file) and malformed entries
+    /// are skipped. If the merge goes over `MAX_BOOKMARKS`, the most recently
+    /// created bookmarks are kept. Returns how many bookmarks were added.
+    pub fn import_bookmarks(&mut self, path: &Path) -> Result<usize> {
+        let invalid = |reason: String| ThothError::FileReadError {
+            path: path.to_path_buf(),
+            reason,
+        };
+        let contents = fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&contents)
+            .map_err(|e| invalid(format!("Not a bookmarks file: {}", e)))?;
+
+        let mut seen: HashSet<(String, String)> = self
+            .bookmarks
+            .iter()
+            .map(|b| (b.file_path.clone(), b.path.clone()))
+            .collect();
+        let mut imported = Vec::new();
+        for entry in entries {
+            let Ok(mut bookmark) = serde_json::from_value::<Bookmark>(entry) else {
+                continue;
+            };
+            if bookmark.path.is_empty() || bookmark.file_path.is_empty() {
+                continue;
+            }
+            bookmark.file_path = canonical_path_key(&bookmark.file_path);
+            if seen.insert((bookmark.file_path.clone(), bookmark.path.clone())) {
+                imported.push(bookmark);
+            }
+        }
+
+        let new_keys: HashSet<(String, String)> = imported
+            .iter()
+            .map(|b| (b.file_path.clone(), b.path.clone()))
+            .collect();
+        self.bookmarks.extend(imported);
+        // Most recent first; the sort is stable so equal timestamps keep existing ones ahead
+        self.bookmarks
+            .sort_by_key(|b| std::cmp::Reverse(b.created_at));
+        self.bookmarks.truncate(MAX_BOOKMARKS);
+        Ok(self
+            .bookmarks
+            .iter()
+            .filter(|b| new_keys.contains(&(b.file_path.clone(), b.path.clone())))
+            .count())
+    }
+
     /// Toggle bookmark (add if not exists, remove if exists)
     pub fn toggle_bookmark(&mut self, path: String, file_path: String) -> bool {
+        let file_path = canonical_path_key(&file_path);
         if let Some(index) = self
             .bookmarks
             .iter()
@@ -397,7 +734,7 @@ impl PersistentState {
         let store = Self::load_history_store()?;
         Ok(store
             .histories
-            .get(file_path)
+            .get(&canonical_path_key(file_path))
             .map(|(_, queries)| queries.clone())
             .unwrap_or_default())
     }
@@ -407,6 +744,8 @@ impl PersistentState {
         if query.trim().is_empty() {
             return Ok(());
         }
+        let file_path = canonical_path_key(file_path);
+        let file_path = file_path.as_str();
 
         let mut store = Self::load_history_store().unwrap_or_else(|err| {
             eprintln!("Failed to load search history store: {}", err);
@@ -456,10 +795,85 @@ impl PersistentState {
     /// Clear search history for a specific file
     pub fn clear_search_history(file_path: &str) -> Result<()> {
         let mut store = Self::load_history_store()?;
-        store.histories.remove(file_path);
+        store.histories.remove(&canonical_path_key(file_path));
         Self::save_history_store(&store)
     }
 
+    /// Remove a single stored query from a file's search history
+    pub fn remove_search_query(file_path: &str, query: &str) -> Result<()> {
+        let mut store = Self::load_history_store()?;
+        if store.remove_query(&canonical_path_key(file_path), query) {
+            Self::save_history_store(&store)?;
+        }
+        Ok(())
+    }
+
+    // Tree expansion methods (single file with LRU for most recently used files)
+
+    /// Get the path to the expansion state storage file
+    fn expansion_storage_path() -> Result<PathBuf> {
+        let config_dir = dirs::config_dir().ok_or_else(|| ThothError::StateError {
+            reason: "Failed to get config directory".to_string(),
+        })?;
+        let thoth_config_dir = config_dir.join("thoth");
+
+        // Create directory if it doesn't exist
+        if !thoth_config_dir.exists() {
+            std::fs::create_dir_all(&thoth_config_dir).map_err(|e| ThothError::StateError {
+                reason: format!("Failed to create thoth config directory: {}", e),
+            })?;
+        }
+
+        Ok(thoth_config_dir.join("expansion_state.json"))
+    }
+
+    fn load_expansion_store() -> Result<ExpansionStore> {
+        let path = Self::expansion_storage_path()?;
+
+        if path.exists() {
+            let contents = std::fs::read_to_string(&path).map_err(|e| ThothError::StateError {
+                reason: format!("Failed to read expansion state: {}", e),
+            })?;
+            serde_json::from_str(&contents).map_err(|e| ThothError::StateError {
+                reason: format!("Failed to parse expansion state: {}", e),
+            })
+        } else {
+            Ok(ExpansionStore::default())
+        }
+    }
+
+    fn save_expansion_store(store: &ExpansionStore) -> Result<()> {
+        let path = Self::expansion_storage_path()?;
+        let json = serde_json::to_string(store).map_err(|e| ThothError::StateError {
+            reason: format!("Failed to serialize expansion state: {}", e),
+        })?;
+        std::fs::write(&path, &json).map_err(|e| ThothError::FileWriteError {
+            path: path.clone(),
+            reason: e.to_string(),
+        })?;
+        Ok(())
+    }
+
+    /// Load the tree nodes that were expanded when a file was last viewed
+    pub fn load_expansion(file_path: &str) -> Result<Option<HashSet<String>>> {
+        let store = Self::load_expansion_store()?;
+        Ok(store.get(&canonical_path_key(file_path)))
+    }
+
+    /// Remember which tree nodes are expanded for a file
+    pub fn save_expansion(file_path: &str, expanded: &HashSet<String>) -> Result<()> {
+        let mut store = Self::load_expansion_store().unwrap_or_else(|err| {
+            eprintln!("Failed to load expansion state store: {}", err);
+            ExpansionStore::default()
+        });
+        store.set(
+            canonical_path_key(file_path),
+            expanded,
+            Self::current_timestamp(),
+        );
+        Self::save_expansion_store(&store)
+    }
+
     pub fn local_plugin_dir(plugin_id: &str) -> Result<PathBuf> {
         let config_dir =
             dirs::config_dir().ok_or_else(|| "failed to locate config directory".to_string())?;
@@ -535,14 +949,7 @@ mod tests {
 
     #[test]
     fn test_add_recent_file() {
-        let mut state = PersistentState {
-            recent_files: Vec::new(),
-            sidebar_width: DEFAULT_SIDEBAR_WIDTH,
-            sidebar_expanded: false,
-            bookmarks: Vec::new(),
-            open_tabs: Vec::new(),
-            active_tab_index: 0,
-        };
+        let mut state = PersistentState::empty();
         state.add_recent_file("file1.json".to_string(), MAX_RECENT_FILES);
         state.add_recent_file("file2.json".to_string(), MAX_RECENT_FILES);
 
@@ -553,14 +960,7 @@ mod tests {
 
     #[test]
     fn test_add_duplicate_moves_to_top() {
-        let mut state = PersistentState {
-            recent_files: Vec::new(),
-            sidebar_width: DEFAULT_SIDEBAR_WIDTH,
-            sidebar_expanded: false,
-            bookmarks: Vec::new(),
-            open_tabs: Vec::new(),
-            active_tab_index: 0,
-        };
+        let mut state = PersistentState::empty();
         state.add_recent_file("file1.json".to_string(), MAX_RECENT_FILES);
         state.add_recent_file("file2.json".to_string(), MAX_RECENT_FILES);
         state.add_recent_file("file1.json".to_string(), MAX_RECENT_FILES);
@@ -572,14 +972,7 @@ mod tests {
 
     #[test]
     fn test_max_recent_files() {
-        let mut state = PersistentState {
-            recent_files: Vec::new(),
-            sidebar_width: DEFAULT_SIDEBAR_WIDTH,
-            sidebar_expanded: false,
-            bookmarks: Vec::new(),
-            open_tabs: Vec::new(),
-            active_tab_index: 0,
-        };
+        let mut state = PersistentState::empty();
         for i in 0..15 {
             state.add_recent_file(format!("file{}.json", i), MAX_RECENT_FILES);
         }
@@ -589,15 +982,110 @@ mod tests {
     }
 
     #[test]
-    fn test_remove_recent_file() {
-        let mut state = PersistentState {
-            recent_files: Vec::new(),
-            sidebar_width: DEFAULT_SIDEBAR_WIDTH,
-            sidebar_expanded: false,
-            bookmarks: Vec::new(),
-            open_tabs: Vec::new(),
-            active_tab_index: 0,
+    fn test_pinned_files_survive_truncation() {
+        let mut state = PersistentState::empty();
+        state.add_recent_file("pinned.json".to_string(), 3);
+        assert!(state.toggle_pinned_file("pinned.json", 3).unwrap());
+        assert!(state.get_recent_files().is_empty());
+
+        for i in 0..5 {
+            state.add_recent_file(format!("file{}.json", i), 3);
+        }
+        // Reopening a pinned file doesn't put it back in the recent list
+        state.add_recent_file("pinned.json".to_string(), 3);
+        assert_eq!(state.get_pinned_files(), ["pinned.json"]);
+        assert_eq!(
+            state.get_recent_files(),
+            ["file4.json", "file3.json", "file2.json"]
+        );
+
+        // Unpinning returns it to the top of the recent list
+        assert!(!state.toggle_pinned_file("pinned.json", 3).unwrap());
+        assert!(state.get_pinned_files().is_empty());
+        assert_eq!(
+            state.get_recent_files(),
+            ["pinned.json", "file4.json", "file3.json"]
+        );
+    }
+
+    #[test]
+    fn test_pinned_files_are_capped() {
+        let mut state = PersistentState::empty();
+        for i in 0..MAX_PINNED_FILES {
+            state
+                .toggle_pinned_file(&format!("file{}.json", i), 10)
+                .unwrap();
+        }
+        assert!(state.toggle_pinned_file("one_more.json", 10).is_err());
+        assert_eq!(state.get_pinned_files().len(), MAX_PINNED_FILES);
+
+        // Removing a file drops its pin too
+        state.remove_recent_file("file0.json");
+        assert_eq!(state.get_pinned_files().len(), MAX_PINNED_FILES - 1);
+    }
+
+    #[test]
+    fn test_last_opened_file() {
+        let mut state = PersistentState::empty();
+        assert_eq!(state.get_last_opened_file(), None);
+
+        state.add_recent_file("/file1.json".to_string(), 10);
+        state.add_recent_file("/file2.json".to_string(), 10);
+        assert_eq!(state.get_last_opened_file(), Some("/file2.json"));
+
+        // Older state files have no last opened file
+        let old: PersistentState = serde_json::from_str(r#"{"recent_files": []}"#).unwrap();
+        assert_eq!(old.get_last_opened_file(), None);
+    }
+
+    #[test]
+    fn test_window_geometries_are_kept_apart() {
+        let mut state = PersistentState::empty();
+        assert_eq!(state.get_window_geometry(), None);
+
+        let main = WindowGeometry {
+            position: [40.0, 30.0],
+            size: [1600.0, 900.0],
+            monitor_size: Some([1920.0, 1080.0]),
+        };
+        let settings = WindowGeometry {
+            position: [200.0, 100.0],
+            ..main
         };
+        state.set_window_geometry(main);
+        state.set_settings_window_geometry(settings);
+
+        let json = serde_json::to_string(&state).unwrap();
+        let restored: PersistentState = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.get_window_geometry(), Some(main));
+        assert_eq!(restored.get_settings_window_geometry(), Some(settings));
+    }
+
+    #[test]
+    fn test_window_geometry_moves_to_a_smaller_monitor() {
+        let geometry = WindowGeometry {
+            position: [2200.0, 100.0],
+            size: [1800.0, 1200.0],
+            monitor_size: Some([2560.0, 1440.0]),
+        };
+        assert!(geometry.fits_monitor(Some([2560.0, 1440.0])));
+        assert!(!geometry.fits_monitor(Some([1440.0, 900.0])));
+        assert!(!geometry.fits_monitor(None));
+
+        let moved = geometry.centered_on([1440.0, 900.0]);
+        assert_eq!(moved.size, [1440.0, 900.0]);
+        assert_eq!(moved.position, [0.0, 0.0]);
+
+        let small = WindowGeometry {
+            size: [800.0, 600.0],
+            ..geometry
+        };
+        assert_eq!(small.centered_on([1440.0, 900.0]).position, [320.0, 150.0]);
+    }
+
+    #[test]
+    fn test_remove_recent_file() {
+        let mut state = PersistentState::empty();
         state.add_recent_file("file1.json".to_string(), MAX_RECENT_FILES);
         state.add_recent_file("file2.json".to_string(), MAX_RECENT_FILES);
         state.remove_recent_file("file1.json");
@@ -608,14 +1096,7 @@ mod tests {
 
     #[test]
     fn test_sidebar_width() {
-        let mut state = PersistentState {
-            recent_files: Vec::new(),
-            sidebar_width: DEFAULT_SIDEBAR_WIDTH,
-            sidebar_expanded: false,
-            bookmarks: Vec::new(),
-            open_tabs: Vec::new(),
-            active_tab_index: 0,
-        };
+        let mut state = PersistentState::empty();
 
         assert_eq!(state.get_sidebar_width(), DEFAULT_SIDEBAR_WIDTH);
 
@@ -629,14 +1110,7 @@ mod tests {
 
     #[test]
     fn test_add_bookmark() {
-        let mut state = PersistentState {
-            recent_files: Vec::new(),
-            sidebar_width: DEFAULT_SIDEBAR_WIDTH,
-            sidebar_expanded: false,
-            bookmarks: Vec::new(),
-            open_tabs: Vec::new(),
-            active_tab_index: 0,
-        };
+        let mut state = PersistentState::empty();
 
         state.add_bookmark(
             "users[0].name".to_string(),
@@ -655,14 +1129,7 @@ mod tests {
 
     #[test]
     fn test_add_duplicate_bookmark() {
-        let mut state = PersistentState {
-            recent_files: Vec::new(),
-            sidebar_width: DEFAULT_SIDEBAR_WIDTH,
-            sidebar_expanded: false,
-            bookmarks: Vec::new(),
-            open_tabs: Vec::new(),
-            active_tab_index: 0,
-        };
+        let mut state = PersistentState::empty();
 
         state.add_bookmark(
             "users[0].name".to_string(),
@@ -681,14 +1148,7 @@ mod tests {
 
     #[test]
     fn test_remove_bookmark() {
-        let mut state = PersistentState {
-            recent_files: Vec::new(),
-            sidebar_width: DEFAULT_SIDEBAR_WIDTH,
-            sidebar_expanded: false,
-            bookmarks: Vec::new(),
-            open_tabs: Vec::new(),
-            active_tab_index: 0,
-        };
+        let mut state = PersistentState::empty();
 
         state.add_bookmark("path1".to_string(), "/file1.json".to_string(), None);
         state.add_bookmark("path2".to_string(), "/file2.json".to_string(), None);
@@ -700,16 +1160,99 @@ mod tests {
         assert_eq!(state.get_bookmarks()[0].path, "path1");
     }
 
+    #[test]
+    fn test_rename_bookmark() {
+        let mut state = PersistentState::empty();
+        state.add_bookmark("path1".to_string(), "/file1.json".to_string(), None);
+
+        assert!(state.rename_bookmark(0, Some("  Primary email ".to_string())));
+        assert_eq!(
+            state.get_bookmarks()[0].label,
+            Some("Primary email".to_string())
+        );
+
+        // A blank label clears it
+        assert!(state.rename_bookmark(0, Some("   ".to_string())));
+        assert_eq!(state.get_bookmarks()[0].label, None);
+
+        // Stale index
+        assert!(!state.rename_bookmark(3, Some("x".to_string())));
+    }
+
+    #[test]
+    fn test_export_import_bookmarks() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("bookmarks.json");
+
+        let mut source = PersistentState::empty();
+        source.add_bookmark("a".to_string(), "/file1.json".to_string(), None);
+        source.add_bookmark("b".to_string(), "/file1.json".to_string(), None);
+        source.rename_bookmark(0, Some("Bee".to_string()));
+        source.export_bookmarks(&file).unwrap();
+
+        let mut target = PersistentState::empty();
+        target.add_bookmark("a".to_string(), "/file1.json".to_string(), None);
+        assert_eq!(target.import_bookmarks(&file).unwrap(), 1);
+        assert_eq!(target.get_bookmarks().len(), 2);
+        let imported = target
+            .get_bookmarks()
+            .iter()
+            .find(|b| b.path == "b")
+            .unwrap();
+        assert_eq!(imported.label, Some("Bee".to_string()));
+
+        // Importing again adds nothing
+        assert_eq!(target.import_bookmarks(&file).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_import_skips_malformed_bookmarks() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("bookmarks.json");
+        fs::write(
+            &file,
+            r#"[
+                {"path": "ok", "file_path": "/f.json", "label": null, "created_at": 5},
+                {"path": "no-file", "created_at": 5},
+                {"path": "", "file_path": "/f.json", "label": null, "created_at": 5},
+                42
+            ]"#,
+        )
+        .unwrap();
+
+        let mut state = PersistentState::empty();
+        assert_eq!(state.import_bookmarks(&file).unwrap(), 1);
+        assert_eq!(state.get_bookmarks()[0].path, "ok");
+
+        fs::write(&file, r#"{"bookmarks": []}"#).unwrap();
+        assert!(state.import_bookmarks(&file).is_err());
+    }
+
+    #[test]
+    fn test_import_bookmarks_keeps_most_recent() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("bookmarks.json");
+        let old: Vec<Bookmark> = (0..MAX_BOOKMARKS)
+            .map(|i| Bookmark {
+                path: format!("old{}", i),
+                file_path: "/f.json".to_string(),
+                label: None,
+                created_at: 1,
+            })
+            .collect();
+        fs::write(&file, serde_json::to_string(&old).unwrap()).unwrap();
+
+        // Newer than everything in the file
+        let mut state = PersistentState::empty();
+        state.add_bookmark("new".to_string(), "/f.json".to_string(), None);
+        assert_eq!(state.import_bookmarks(&file).unwrap(), MAX_BOOKMARKS - 1);
+        assert_eq!(state.get_bookmarks().len(), MAX_BOOKMARKS);
+        assert_eq!(state.get_bookmarks()[0].path, "new");
+    }
+
     #[test]
     fn test_toggle_bookmark() {
-        let mut state = PersistentState {
-            recent_files: Vec::new(),
-            sidebar_width: DEFAULT_SIDEBAR_WIDTH,
-            sidebar_expanded: false,
-            bookmarks: Vec::new(),
-            open_tabs: Vec::new(),
-            active_tab_index: 0,
-        };
+        let mut state = PersistentState::empty();
 
         // Toggle on (add)
         let added = state.toggle_bookmark("path1".to_string(), "/file1.json".to_string());
@@ -724,14 +1267,7 @@ mod tests {
 
     #[test]
     fn test_max_bookmarks() {
-        let mut state = PersistentState {
-            recent_files: Vec::new(),
-            sidebar_width: DEFAULT_SIDEBAR_WIDTH,
-            sidebar_expanded: false,
-            bookmarks: Vec::new(),
-            open_tabs: Vec::new(),
-            active_tab_index: 0,
-        };
+        let mut state = PersistentState::empty();
 
         // Add more than MAX_BOOKMARKS
         for i in 0..=MAX_BOOKMARKS {
@@ -741,4 +1277,186 @@ mod tests {
         // Should be limited to MAX_BOOKMARKS
         assert_eq!(state.get_bookmarks().len(), MAX_BOOKMARKS);
     }
+
+    #[test]
+    fn test_last_query_mode_round_trip() {
+        let mut state = PersistentState::empty();
+        assert_eq!(state.get_last_query_mode(), QueryMode::Text);
+
+        state.set_last_query_mode(QueryMode::JsonPath);
+        let json = serde_json::to_string(&state).unwrap();
+        let restored: PersistentState = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.get_last_query_mode(), QueryMode::JsonPath);
+    }
+
+    #[test]
+    fn test_flat_search_results_round_trip() {
+        let mut state = PersistentState::empty();
+        assert!(!state.get_flat_search_results());
+
+        state.set_flat_search_results(true);
+        let json = serde_json::to_string(&state).unwrap();
+        let restored: PersistentState = serde_json::from_str(&json).unwrap();
+        assert!(restored.get_flat_search_results());
+    }
+
+    #[test]
+    fn test_last_query_mode_defaults_for_old_state_files() {
+        // State files written before the field existed fall back to Text
+        let json = r#"{"recent_files": [], "sidebar_width": 350.0, "sidebar_expanded": true}"#;
+        let state: PersistentState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.get_last_query_mode(), QueryMode::Text);
+    }
+
+    fn expanded(paths: &[&str]) -> HashSet<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_expansion_store_round_trip() {
+        let mut store = ExpansionStore::default();
+        store.set("/a.json".to_string(), &expanded(&["0", "0.user"]), 1);
+
+        let json = serde_json::to_string(&store).unwrap();
+        let restored: ExpansionStore = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.get("/a.json"), Some(expanded(&["0", "0.user"])));
+        assert_eq!(restored.get("/b.json"), None);
+    }
+
+    #[test]
+    fn test_expansion_store_empty_or_oversized_set_removes_entry() {
+        let mut store = ExpansionStore::default();
+        store.set("/a.json".to_string(), &expanded(&["0"]), 1);
+        store.set("/a.json".to_string(), &HashSet::new(), 2);
+        assert_eq!(store.get("/a.json"), None);
+
+        let huge: HashSet<String> = (0..=MAX_EXPANDED_PATHS_PER_FILE)
+            .map(|i| i.to_string())
+            .collect();
+        store.set("/a.json".to_string(), &huge, 3);
+        assert_eq!(store.get("/a.json"), None);
+    }
+
+    #[test]
+    fn test_expansion_store_evicts_least_recent_files() {
+        let mut store = ExpansionStore::default();
+        for i in 0..=MAX_FILES_WITH_EXPANSION {
+            store.set(format!("/file{}.json", i), &expanded(&["0"]), i as u64);
+        }
+
+        assert_eq!(store.files.len(), MAX_FILES_WITH_EXPANSION);
+        assert_eq!(store.get("/file0.json"), None);
+        assert!(
+            store
+                .get(&format!("/file{}.json", MAX_FILES_WITH_EXPANSION))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_search_history_remove_single_query() {
+        let mut store = SearchHistoryStore {
+            histories: HashMap::from([(
+                "/a.json".to_string(),
+                (1, vec!["foo".to_string(), "bar".to_string()]),
+            )]),
+        };
+
+        assert!(store.remove_query("/a.json", "foo"));
+        assert_eq!(store.histories["/a.json"].1, vec!["bar".to_string()]);
+        assert!(!store.remove_query("/a.json", "missing"));
+        assert!(!store.remove_query("/b.json", "bar"));
+
+        // Removing the last query drops the file entirely
+        assert!(store.remove_query("/a.json", "bar"));
+        assert!(store.histories.is_empty());
+    }
+
+    #[cfg(unix)]
+    mod symlinks {
+        use super::*;
+        use std::os::unix::fs::symlink;
+        use tempfile::TempDir;
+
+        /// Create `data.json` and a `link.json` symlink to it; returns (dir, real, link)
+        fn fixture() -> (TempDir, String, String) {
+            let dir = tempfile::tempdir().unwrap();
+            let real = dir.path().join("data.json");
+            fs::write(&real, "[]").unwrap();
+            let link = dir.path().join("link.json");
+            symlink(&real, &link).unwrap();
+            let real = fs::canonicalize(&real).unwrap();
+            (
+                dir,
+                real.to_str().unwrap().to_string(),
+                link.to_str().unwrap().to_string(),
+            )
+        }
+
+        #[test]
+        fn test_recent_file_via_symlink_collapses_to_real_path() {
+            let (_dir, real, link) = fixture();
+            let mut state = PersistentState::empty();
+
+            state.add_recent_file(real.clone(), MAX_RECENT_FILES);
+            state.add_recent_file(link.clone(), MAX_RECENT_FILES);
+
+            assert_eq!(state.get_recent_files(), [real.clone()]);
+
+            state.remove_recent_file(&link);
+            assert!(state.get_recent_files().is_empty());
+        }
+
+        #[test]
+        fn test_bookmark_via_symlink_matches_real_path() {
+            let (_dir, real, link) = fixture();
+            let mut state = PersistentState::empty();
+
+            state.add_bookmark("0.user".to_string(), real.clone(), None);
+            state.add_bookmark("0.user".to_string(), link.clone(), None);
+            assert_eq!(state.get_bookmarks().len(), 1);
+            assert_eq!(state.get_bookmarks()[0].file_path, real);
+
+            // Toggling through the symlink removes the bookmark made via the real path
+            assert!(!state.toggle_bookmark("0.user".to_string(), link));
+            assert!(state.get_bookmarks().is_empty());
+        }
+
+        #[test]
+        fn test_broken_symlink_falls_back_to_given_path() {
+            let (_dir, real, link) = fixture();
+            fs::remove_file(&real).unwrap();
+            let mut state = PersistentState::empty();
+
+            state.add_recent_file(link.clone(), MAX_RECENT_FILES);
+            assert_eq!(state.get_recent_files(), [link]);
+        }
+
+        #[test]
+        fn test_loaded_state_collapses_legacy_duplicates() {
+            let (_dir, real, link) = fixture();
+            let mut state = PersistentState::empty();
+            state.recent_files = vec![link.clone(), real.clone()];
+            state.bookmarks = vec![
+                Bookmark {
+                    path: "0".to_string(),
+                    file_path: link,
+                    label: None,
+                    created_at: 0,
+                },
+                Bookmark {
+                    path: "0".to_string(),
+                    file_path: real.clone(),
+                    label: None,
+                    created_at: 0,
+                },
+            ];
+
+            state.canonicalize_paths();
+
+            assert_eq!(state.get_recent_files(), [real.clone()]);
+            assert_eq!(state.get_bookmarks().len(), 1);
+            assert_eq!(state.get_bookmarks()[0].file_path, real);
+        }
+    }
 }
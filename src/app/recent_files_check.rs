@@ -0,0 +1,149 @@
+use std::collections::HashSet;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+/// Background check of which recent files still exist on disk
+///
+/// Each path is stat-ed once on a worker thread, so a slow or unresponsive
+/// network share never stalls a frame. Results are cached until
+/// [`invalidate`](Self::invalidate) is called, e.g. when the window regains
+/// focus after the user may have moved files around.
+pub struct RecentFilesCheck {
+    /// Paths with a result, missing or not
+    checked: HashSet<String>,
+    /// Paths sent to a worker that hasn't answered yet
+    pending: HashSet<String>,
+    missing: HashSet<String>,
+    tx: Sender<(String, bool)>,
+    rx: Receiver<(String, bool)>,
+}
+
+impl Default for RecentFilesCheck {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            checked: HashSet::new(),
+            pending: HashSet::new(),
+            missing: HashSet::new(),
+            tx,
+            rx,
+        }
+    }
+}
+
+impl RecentFilesCheck {
+    /// Start checking any of `paths` not checked or being checked yet; `wake`
+    /// is called from the worker once it has answered
+    pub fn request<'a>(
+        &mut self,
+        paths: impl IntoIterator<Item = &'a String>,
+        wake: impl Fn() + Send + 'static,
+    ) {
+        let batch: Vec<String> = paths
+            .into_iter()
+            .filter(|path| !self.checked.contains(*path) && !self.pending.contains(*path))
+            .cloned()
+            .collect();
+        if batch.is_empty() {
+            return;
+        }
+        self.pending.extend(batch.iter().cloned());
+
+        let tx = self.tx.clone();
+        thread::spawn(move || {
+            for path in batch {
+                let missing = is_missing(&path);
+                if tx.send((path, missing)).is_err() {
+                    return;
+                }
+            }
+            wake();
+        });
+    }
+
+    /// Take in the answers that arrived since the last poll
+    pub fn poll(&mut self) {
+        while let Ok((path, missing)) = self.rx.try_recv() {
+            // Dropped by `invalidate` while in flight; it will be asked again
+            if !self.pending.remove(&path) {
+                continue;
+            }
+            if missing {
+                self.missing.insert(path.clone());
+            } else {
+                self.missing.remove(&path);
+            }
+            self.checked.insert(path);
+        }
+    }
+
+    /// Forget every result so the next request checks the paths again
+    ///
+    /// Known-missing paths stay marked until their new answer arrives, so
+    /// the list doesn't flicker.
+    pub fn invalidate(&mut self) {
+        self.checked.clear();
+        self.pending.clear();
+    }
+
+    /// Paths found missing on their last check
+    pub fn missing(&self) -> &HashSet<String> {
+        &self.missing
+    }
+}
+
+/// Whether `path` is known not to exist
+///
+/// Only a stat, never an open. Other errors (e.g. permission denied) say
+/// nothing about whether the file is there, so they don't count as missing.
+fn is_missing(path: &str) -> bool {
+    matches!(
+        std::fs::metadata(path),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+    use tempfile::NamedTempFile;
+
+    fn wait_for(check: &mut RecentFilesCheck) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !check.pending.is_empty() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+            check.poll();
+        }
+        assert!(check.pending.is_empty(), "check didn't finish");
+    }
+
+    #[test]
+    fn test_reports_missing_files() {
+        let present = NamedTempFile::new().unwrap();
+        let present = present.path().to_str().unwrap().to_string();
+        let gone = NamedTempFile::new()
+            .unwrap()
+            .path()
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let mut check = RecentFilesCheck::default();
+        check.request([&present, &gone], || {});
+        wait_for(&mut check);
+        assert_eq!(check.missing(), &HashSet::from([gone.clone()]));
+
+        // Already answered paths aren't checked again until invalidated
+        check.request([&present, &gone], || {});
+        assert!(check.pending.is_empty());
+
+        std::fs::write(&gone, "{}").unwrap();
+        check.invalidate();
+        assert!(check.missing().contains(&gone));
+        check.request([&gone], || {});
+        wait_for(&mut check);
+        assert!(check.missing().is_empty());
+        std::fs::remove_file(&gone).unwrap();
+    }
+}
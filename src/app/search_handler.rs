@@ -1,5 +1,10 @@
 use crate::{error::ThothError, search, state};
 use eframe::egui;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, mpsc};
+use std::thread;
+use std::time::Duration;
 
 /// Handles all search-related logic
 pub struct SearchHandler;
@@ -7,34 +12,82 @@ pub struct SearchHandler;
 impl SearchHandler {
     /// Process search messages from sidebar and background search
     /// Returns (message_to_central, error_if_any)
+    ///
+    /// New searches stop after `max_results` matching records, and a `.gz`
+    /// file may decompress to at most `max_file_size_mb` megabytes.
     pub fn handle_search_messages(
         incoming_msg: Option<search::SearchMessage>,
         search_state: &mut state::SearchEngineState,
         file_path: &Option<std::path::PathBuf>,
         file_type: &crate::file::lazy_loader::FileKind,
+        max_results: usize,
+        max_file_size_mb: u64,
         ctx: &egui::Context,
     ) -> (Option<search::SearchMessage>, Option<ThothError>) {
         let mut msg_to_central: Option<search::SearchMessage> = None;
         let mut search_error: Option<ThothError> = None;
 
-        // Check if background search has completed
-        if let Some(rx) = &search_state.search_rx
-            && let Ok(done) = rx.try_recv()
-        {
-            // Check if the search encountered an error
-            if let Some(error) = &done.error {
-                search_error = Some(error.clone());
+        // Collect whatever the background scan found since the last frame
+        if let Some(rx) = &search_state.search_rx {
+            let mut changed = false;
+            let mut finished = false;
+            loop {
+                match rx.try_recv() {
+                    Ok(search::SearchUpdate::Hits(hits)) => {
+                        search_state.search.results.extend(hits);
+                        changed = true;
+                    }
+                    Ok(search::SearchUpdate::Finished(result)) => {
+                        match result {
+                            Ok(summary) => {
+                                let results = &mut search_state.search.results;
+                                results.set_total_records(summary.total_records);
+                                results.set_truncated(summary.truncated);
+                            }
+                            Err(error) => {
+                                search_error = Some(error.clone());
+                                search_state.search.error = Some(error);
+                            }
+                        }
+                        finished = true;
+                        break;
+                    }
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        finished = true;
+                        break;
+                    }
+                }
+            }
+
+            if finished {
+                search_state.search.scanning = false;
+                search_state.search_rx = None;
+                search_state.search_cancel = None;
+            } else {
+                // Keep polling while scanning
+                ctx.request_repaint_after(Duration::from_millis(100));
+            }
+            if changed || finished {
+                msg_to_central = Some(search::SearchMessage::StartSearch(
+                    search_state.search.clone(),
+                ));
             }
-            search_state.search = done.clone();
-            msg_to_central = Some(search::SearchMessage::StartSearch(done));
-            search_state.search_rx = None; // finished
         }
 
         // Handle incoming search message from sidebar
         if let Some(msg) = incoming_msg {
             match msg {
                 search::SearchMessage::StartSearch(s) => {
-                    Self::start_search(s, search_state, file_path, file_type, ctx);
+                    Self::start_search(
+                        s,
+                        search_state,
+                        file_path,
+                        file_type,
+                        max_results,
+                        max_file_size_mb,
+                        ctx,
+                    );
                     msg_to_central = Some(search::SearchMessage::StartSearch(
                         search_state.search.clone(),
                     ));
@@ -49,6 +102,177 @@ impl SearchHandler {
         (msg_to_central, search_error)
     }
 
+    /// Start finding the records a find-and-replace would change
+    pub fn start_replace_preview(
+        replace_state: &mut state::ReplaceState,
+        file_path: &Option<PathBuf>,
+        spec: search::replace::ReplaceSpec,
+        max_file_size_mb: u64,
+        ctx: &egui::Context,
+    ) {
+        let Some(source) = file_path.clone() else {
+            return;
+        };
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(search::replace::ReplacePreview::build(
+                &source,
+                &spec,
+                max_file_size_mb,
+            ));
+        });
+        replace_state.preview = None;
+        replace_state.error = None;
+        replace_state.preview_rx = Some(rx);
+        ctx.request_repaint();
+    }
+
+    /// Start writing the previewed replacements to `output`
+    pub fn start_replace_write(
+        replace_state: &mut state::ReplaceState,
+        file_path: &Option<PathBuf>,
+        output: PathBuf,
+        max_file_size_mb: u64,
+        ctx: &egui::Context,
+    ) {
+        let (Some(source), Some(preview)) = (file_path.clone(), replace_state.preview.clone())
+        else {
+            return;
+        };
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let written = preview
+                .write(&source, &output, max_file_size_mb)
+                .map(|changed| (output, changed));
+            let _ = tx.send(written);
+        });
+        replace_state.error = None;
+        replace_state.write_rx = Some(rx);
+        ctx.request_repaint();
+    }
+
+    /// Collect finished find-and-replace jobs, notifying when a write completes
+    pub fn poll_replace(replace_state: &mut state::ReplaceState, ctx: &egui::Context) {
+        if let Some(rx) = &replace_state.preview_rx {
+            match rx.try_recv() {
+                Ok(Ok(preview)) => {
+                    replace_state.preview = Some(Arc::new(preview));
+                    replace_state.preview_rx = None;
+                }
+                Ok(Err(e)) => {
+                    replace_state.error = Some(e.to_string());
+                    replace_state.preview_rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => replace_state.preview_rx = None,
+            }
+        }
+
+        if let Some(rx) = &replace_state.write_rx {
+            match rx.try_recv() {
+                Ok(Ok((output, changed))) => {
+                    crate::notification::NotificationManager::notify(
+                        crate::notification::Notification::new(
+                            &format!("Replaced values in {} record(s)", changed),
+                            &output.display().to_string(),
+                        ),
+                    );
+                    replace_state.write_rx = None;
+                }
+                Ok(Err(e)) => {
+                    crate::notification::NotificationManager::notify_error(
+                        crate::notification::Notification::new(
+                            "Find and replace failed",
+                            &crate::error::ErrorHandler::get_user_message(&e),
+                        ),
+                    );
+                    replace_state.error = Some(e.to_string());
+                    replace_state.write_rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => replace_state.write_rx = None,
+            }
+        }
+
+        // Keep polling while a job is running
+        if replace_state.preview_rx.is_some() || replace_state.write_rx.is_some() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+    }
+
+    /// Start running `search` over `files`, replacing any earlier results
+    ///
+    /// Each file stops after `max_results` matching records, and a `.gz` file
+    /// may decompress to at most `max_file_size_mb` megabytes.
+    pub fn start_recent_files_search(
+        state: &mut state::RecentFilesSearch,
+        mut search: search::Search,
+        files: Vec<PathBuf>,
+        max_results: usize,
+        max_file_size_mb: u64,
+        ctx: &egui::Context,
+    ) {
+        Self::stop_recent_files_search(state);
+
+        search.max_results = Some(max_results);
+        search.max_file_size_mb = Some(max_file_size_mb);
+        let cancel = Arc::new(AtomicBool::new(false));
+        *state = state::RecentFilesSearch {
+            query: search.query.clone(),
+            total_files: files.len(),
+            rx: Some(search.start_scanning_files(files, cancel.clone())),
+            cancel: Some(cancel),
+            ..Default::default()
+        };
+        ctx.request_repaint();
+    }
+
+    /// Collect the files searched since the last frame
+    pub fn poll_recent_files_search(state: &mut state::RecentFilesSearch, ctx: &egui::Context) {
+        let Some(rx) = &state.rx else {
+            return;
+        };
+        let mut finished = false;
+        loop {
+            match rx.try_recv() {
+                Ok(search::MultiFileUpdate::Scanning { index }) => state.current_file = index,
+                Ok(search::MultiFileUpdate::Matched(file)) => state.files.push(file),
+                Ok(search::MultiFileUpdate::Skipped { path, reason }) => {
+                    state.skipped.push((path, reason))
+                }
+                Ok(search::MultiFileUpdate::Finished(result)) => {
+                    if let Err(e) = result {
+                        state.error = Some(crate::error::ErrorHandler::get_user_message(&e));
+                    }
+                    finished = true;
+                    break;
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+
+        if finished {
+            state.rx = None;
+            state.cancel = None;
+        } else {
+            // Keep polling while searching
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+    }
+
+    /// Cancel the running search across recent files, keeping the files
+    /// already searched
+    pub fn stop_recent_files_search(state: &mut state::RecentFilesSearch) {
+        if let Some(cancel) = state.cancel.take() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        state.rx = None;
+    }
+
     // Private helper methods
 
     fn start_search(
@@ -56,20 +280,41 @@ impl SearchHandler {
         search_state: &mut state::SearchEngineState,
         file_path: &Option<std::path::PathBuf>,
         file_type: &crate::file::lazy_loader::FileKind,
+        max_results: usize,
+        max_file_size_mb: u64,
         ctx: &egui::Context,
     ) {
-        // Update search state
-        search_state.search = search.clone();
+        // A new query replaces any scan still running
+        Self::stop_search(search_state);
+
+        // Update search state; hits stream in from the background scan
+        search_state.search = search;
+        search_state.search.max_results = Some(max_results);
+        search_state.search.max_file_size_mb = Some(max_file_size_mb);
         search_state.search.scanning = true;
+        search_state.search.error = None;
+        search_state.search.results.clear();
+        search_state.current_match_index = None;
 
         // Spawn background search
-        search_state.search_rx = Some(search_state.search.start_scanning(file_path, file_type));
+        let cancel = Arc::new(AtomicBool::new(false));
+        search_state.search_rx = Some(search_state.search.start_scanning(
+            file_path,
+            file_type,
+            cancel.clone(),
+        ));
+        search_state.search_cancel = Some(cancel);
 
         // Keep UI repainting while scanning
         ctx.request_repaint();
     }
 
-    fn stop_search(search_state: &mut state::SearchEngineState) {
-        search_state.search_rx = None; // Drop pending result
+    /// Cancel the running scan, keeping the hits it already found
+    pub fn stop_search(search_state: &mut state::SearchEngineState) {
+        if let Some(cancel) = search_state.search_cancel.take() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        search_state.search_rx = None; // Drop pending updates
+        search_state.search.scanning = false;
     }
 }
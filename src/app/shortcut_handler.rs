@@ -13,6 +13,7 @@ pub enum ShortcutAction {
     FocusSearch,
     NextMatch,
     PrevMatch,
+    GoToRecord,
     NavBack,
     NavForward,
     Escape,
@@ -26,20 +27,31 @@ pub enum ShortcutAction {
     CollapseNode,
     ExpandAll,
     CollapseAll,
+    ExpandToDepth,
+    CollapseToDepth,
+    ToggleFlatView,
 
     // Clipboard
     CopyKey,
     CopyValue,
     CopyObject,
+    CopyMinified,
     CopyPath,
+    CopyLineNumber,
+    CopyVisibleRecords,
 
     // Movement
     MoveUp,
     MoveDown,
+    ExtendSelectionUp,
+    ExtendSelectionDown,
 
     // UI
     Settings,
     ToggleTheme,
+    ToggleSidebar,
+    TogglePresentation,
+    CommandPalette,
 
     // Developer
     ToggleProfiler,
@@ -81,8 +93,12 @@ impl ShortcutHandler {
             actions.push(ShortcutAction::FocusSearch);
         }
 
-        // Check more specific shortcuts (with Shift) before less specific ones
-        if ctx.input_mut(|i| i.consume_shortcut(&shortcuts.prev_match.to_keyboard_shortcut())) {
+        // Check more specific shortcuts (with Alt or Shift) before less specific ones
+        if ctx.input_mut(|i| i.consume_shortcut(&shortcuts.go_to_record.to_keyboard_shortcut())) {
+            actions.push(ShortcutAction::GoToRecord);
+        } else if ctx
+            .input_mut(|i| i.consume_shortcut(&shortcuts.prev_match.to_keyboard_shortcut()))
+        {
             actions.push(ShortcutAction::PrevMatch);
         } else if ctx
             .input_mut(|i| i.consume_shortcut(&shortcuts.next_match.to_keyboard_shortcut()))
@@ -162,6 +178,20 @@ impl ShortcutHandler {
                 actions.push(ShortcutAction::CollapseNode);
             }
 
+            // Checked before expand/collapse all: egui ignores an extra Shift when
+            // matching, so ⌘⇧→ would otherwise be consumed as ⌘→
+            if ctx.input_mut(|i| {
+                i.consume_shortcut(&shortcuts.expand_to_depth.to_keyboard_shortcut())
+            }) {
+                actions.push(ShortcutAction::ExpandToDepth);
+            }
+
+            if ctx.input_mut(|i| {
+                i.consume_shortcut(&shortcuts.collapse_to_depth.to_keyboard_shortcut())
+            }) {
+                actions.push(ShortcutAction::CollapseToDepth);
+            }
+
             if ctx.input_mut(|i| i.consume_shortcut(&shortcuts.expand_all.to_keyboard_shortcut())) {
                 actions.push(ShortcutAction::ExpandAll);
             }
@@ -171,7 +201,21 @@ impl ShortcutHandler {
                 actions.push(ShortcutAction::CollapseAll);
             }
 
+            if ctx.input_mut(|i| {
+                i.consume_shortcut(&shortcuts.toggle_flat_view.to_keyboard_shortcut())
+            }) {
+                actions.push(ShortcutAction::ToggleFlatView);
+            }
+
             // Clipboard
+            // Checked first: copy_key/copy_value/copy_object would also match it,
+            // since extra Shift/Alt modifiers are ignored when matching shortcuts
+            if ctx
+                .input_mut(|i| i.consume_shortcut(&shortcuts.copy_minified.to_keyboard_shortcut()))
+            {
+                actions.push(ShortcutAction::CopyMinified);
+            }
+
             if ctx.input_mut(|i| i.consume_shortcut(&shortcuts.copy_key.to_keyboard_shortcut())) {
                 actions.push(ShortcutAction::CopyKey);
             }
@@ -189,7 +233,32 @@ impl ShortcutHandler {
                 actions.push(ShortcutAction::CopyPath);
             }
 
-            // Movement
+            if ctx.input_mut(|i| {
+                i.consume_shortcut(&shortcuts.copy_line_number.to_keyboard_shortcut())
+            }) {
+                actions.push(ShortcutAction::CopyLineNumber);
+            }
+
+            if ctx.input_mut(|i| {
+                i.consume_shortcut(&shortcuts.copy_visible_records.to_keyboard_shortcut())
+            }) {
+                actions.push(ShortcutAction::CopyVisibleRecords);
+            }
+
+            // Movement; the Shift variants go first since egui matches
+            // Shift+Arrow against the plain arrow bindings too
+            if ctx.input_mut(|i| {
+                i.consume_shortcut(&shortcuts.extend_selection_up.to_keyboard_shortcut())
+            }) {
+                actions.push(ShortcutAction::ExtendSelectionUp);
+            }
+
+            if ctx.input_mut(|i| {
+                i.consume_shortcut(&shortcuts.extend_selection_down.to_keyboard_shortcut())
+            }) {
+                actions.push(ShortcutAction::ExtendSelectionDown);
+            }
+
             if ctx.input_mut(|i| i.consume_shortcut(&shortcuts.move_up.to_keyboard_shortcut())) {
                 actions.push(ShortcutAction::MoveUp);
             }
@@ -208,6 +277,21 @@ impl ShortcutHandler {
             actions.push(ShortcutAction::ToggleTheme);
         }
 
+        if ctx.input_mut(|i| i.consume_shortcut(&shortcuts.toggle_sidebar.to_keyboard_shortcut())) {
+            actions.push(ShortcutAction::ToggleSidebar);
+        }
+
+        if ctx.input_mut(|i| {
+            i.consume_shortcut(&shortcuts.toggle_presentation.to_keyboard_shortcut())
+        }) {
+            actions.push(ShortcutAction::TogglePresentation);
+        }
+
+        if ctx.input_mut(|i| i.consume_shortcut(&shortcuts.command_palette.to_keyboard_shortcut()))
+        {
+            actions.push(ShortcutAction::CommandPalette);
+        }
+
         // Developer
         if ctx.input_mut(|i| i.consume_shortcut(&shortcuts.toggle_profiler.to_keyboard_shortcut()))
         {
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use eframe::egui;
 use egui_dock::{DockState, tab_viewer::OnCloseResponse};
@@ -9,10 +10,12 @@ use crate::{
     components::central_panel::{CentralPanel, CentralPanelProps},
     components::traits::ContextComponent,
     error::ThothError,
+    file::detect_file_type::DetectedFileType,
     file::lazy_loader::FileKind,
+    file::schema::SchemaValidator,
     plugin::render_node::UiOutput,
     settings::Settings,
-    state::{ActivePluginPane, NavigationHistory, SearchEngineState},
+    state::{ActivePluginPane, FileSession, FileSessions, NavigationHistory, SearchEngineState},
 };
 
 pub type TabId = usize;
@@ -24,11 +27,15 @@ pub struct TabState {
     pub file_type: FileKind,
     pub error: Option<ThothError>,
     pub total_items: usize,
+    /// When the file was last reloaded after changing on disk
+    pub reloaded_at: Option<chrono::DateTime<chrono::Local>>,
     pub search_engine_state: SearchEngineState,
     pub navigation_history: NavigationHistory,
     pub pending_navigation: Option<String>,
     pub active_plugin_pane: Option<ActivePluginPane>,
     pub plugin_sidebar_output: Option<UiOutput>,
+    /// JSON Schema loaded from the sidebar for this tab's file
+    pub schema: Option<Arc<SchemaValidator>>,
     pub central_panel: CentralPanel,
     /// When `Some`, this is a Chart Studio tab that paints a chart directly
     /// (no file or plugin pane).
@@ -43,11 +50,13 @@ impl TabState {
             file_type: FileKind::default(),
             error: None,
             total_items: 0,
+            reloaded_at: None,
             search_engine_state: SearchEngineState::default(),
             navigation_history: NavigationHistory::with_capacity(nav_capacity),
             pending_navigation: None,
             active_plugin_pane: None,
             plugin_sidebar_output: None,
+            schema: None,
             central_panel: CentralPanel::default(),
             chart: None,
         }
@@ -82,6 +91,37 @@ impl TabState {
     pub fn is_empty(&self) -> bool {
         self.file_path.is_none() && self.active_plugin_pane.is_none() && self.chart.is_none()
     }
+
+    /// Where the user is in this tab's file, to restore if the file is opened
+    /// again later in the session; `None` without a file or with nothing to
+    /// restore
+    pub fn file_session(&self) -> Option<(PathBuf, FileSession)> {
+        let path = self.file_path.clone()?;
+        let search = &self.search_engine_state.search;
+        let session = FileSession {
+            query: search.query.clone(),
+            query_mode: search.query_mode,
+            match_case: search.match_case,
+            scope_path: search.scope_path.clone(),
+            sort: self.central_panel.sort().cloned(),
+            selected: self.central_panel.get_selected_path().cloned(),
+            scroll_offset: self.central_panel.scroll_offset(),
+        };
+        (session != FileSession::default()).then_some((path, session))
+    }
+}
+
+/// What waits for a tab's unsaved edits to be saved or discarded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsavedAction {
+    /// Show the file (or empty tab) the tab now asks for
+    SwitchFile,
+    /// Re-read the file as another type
+    ReopenAs(DetectedFileType),
+    /// Close the tab
+    CloseTab,
+    /// Close the window
+    Quit,
 }
 
 /// Events emitted from ThothTabViewer to ThothApp, drained after DockArea::show_inside.
@@ -96,13 +136,39 @@ pub enum TabEvent {
         tab_id: TabId,
         error: ThothError,
     },
+    /// The file a tab is opening exceeds the size limit and awaits confirmation
+    FileTooLarge {
+        tab_id: TabId,
+        path: PathBuf,
+        size_bytes: u64,
+    },
     FileClosed {
         tab_id: TabId,
+        path: PathBuf,
+    },
+    /// `action` was held back because the tab's file has unsaved edits
+    UnsavedEdits {
+        tab_id: TabId,
+        action: UnsavedAction,
+    },
+    /// Opening a file was canceled; the tab goes back to `previous`
+    OpenCanceled {
+        tab_id: TabId,
+        previous: Option<(PathBuf, FileKind)>,
     },
     FileTypeChanged {
         tab_id: TabId,
         file_type: FileKind,
     },
+    FileReloaded {
+        tab_id: TabId,
+        file_type: FileKind,
+        total_items: usize,
+    },
+    FileReloadError {
+        tab_id: TabId,
+        error: ThothError,
+    },
     ErrorCleared {
         tab_id: TabId,
     },
@@ -115,8 +181,12 @@ pub enum TabEvent {
         path: String,
     },
     TabClosed(TabId),
+    /// The `+` button in a tab bar was clicked.
+    NewTab,
     OpenFilePicker,
     OpenRecentFile(std::path::PathBuf),
+    /// Text copied from a tab's viewer, to put on the clipboard.
+    CopyText(String),
     /// A toolbar action from a chart tab (Edit / Refresh).
     ChartAction {
         tab_id: TabId,
@@ -130,6 +200,8 @@ pub struct ThothTabViewer<'a> {
     pub tabs: &'a mut HashMap<TabId, TabState>,
     pub settings: &'a Settings,
     pub persistent_state: &'a mut PersistentState,
+    /// Sessions of files closed this session; a closing tab leaves its own here
+    pub file_sessions: &'a mut FileSessions,
     pub nav_capacity: usize,
     /// Search message for the focused tab, consumed by the first matching tab::ui call.
     pub search_msg: Option<(TabId, crate::search::SearchMessage)>,
@@ -137,6 +209,8 @@ pub struct ThothTabViewer<'a> {
     pub events: Vec<TabEvent>,
     /// Current theme colors for per-tab style overrides.
     pub colors: Option<crate::theme::ThemeColors>,
+    /// Presentation mode, which shows files read-only
+    pub presentation: bool,
 }
 
 impl egui_dock::TabViewer for ThothTabViewer<'_> {
@@ -197,6 +271,20 @@ impl egui_dock::TabViewer for ThothTabViewer<'_> {
         // Copy primitive settings values before the mutable borrow of tab.
         let cache_size = self.settings.performance.cache_size;
         let syntax_highlighting = self.settings.viewer.syntax_highlighting;
+        let indent_size = self.settings.viewer.indent_size;
+        let indent_guides = self.settings.viewer.indent_guides;
+        let scroll_margin = self.settings.viewer.scroll_margin;
+        let auto_expand_depth = self.settings.viewer.auto_expand_depth;
+        let auto_reload = self.settings.viewer.auto_reload;
+        let max_file_size_mb = self.settings.performance.max_file_size_mb;
+        let show_type_badges = self.settings.viewer.show_type_badges;
+        let inline_editing = self.settings.viewer.inline_editing;
+        let number_format = self.settings.viewer.number_format;
+        let settings = self.settings;
+        let timestamp_fields = settings
+            .viewer
+            .humanize_timestamps
+            .then_some(settings.viewer.timestamp_fields.as_slice());
         let plugin_ui = tab.active_plugin_pane.as_ref().map(|p| &p.ui_output);
 
         // egui_dock already builds each tab's `ui` with a per-tab id
@@ -213,6 +301,18 @@ impl egui_dock::TabViewer for ThothTabViewer<'_> {
                 search_message: search_msg,
                 cache_size,
                 syntax_highlighting,
+                indent_size,
+                indent_guides,
+                scroll_margin,
+                auto_expand_depth,
+                auto_reload,
+                max_file_size_mb,
+                show_type_badges,
+                inline_editing,
+                presentation: self.presentation,
+                number_format,
+                timestamp_fields,
+                schema: tab.schema.as_ref(),
                 plugin_ui,
                 recent_files: &recent_files,
                 colors: self.colors,
@@ -252,8 +352,30 @@ impl egui_dock::TabViewer for ThothTabViewer<'_> {
                         error: err,
                     });
                 }
-                CentralPanelEvent::FileClosed => {
-                    self.events.push(TabEvent::FileClosed { tab_id: *tab_id });
+                CentralPanelEvent::FileTooLarge { path, size_bytes } => {
+                    self.events.push(TabEvent::FileTooLarge {
+                        tab_id: *tab_id,
+                        path,
+                        size_bytes,
+                    });
+                }
+                CentralPanelEvent::UnsavedEdits => {
+                    self.events.push(TabEvent::UnsavedEdits {
+                        tab_id: *tab_id,
+                        action: UnsavedAction::SwitchFile,
+                    });
+                }
+                CentralPanelEvent::FileClosed(path) => {
+                    self.events.push(TabEvent::FileClosed {
+                        tab_id: *tab_id,
+                        path,
+                    });
+                }
+                CentralPanelEvent::OpenCanceled { previous } => {
+                    self.events.push(TabEvent::OpenCanceled {
+                        tab_id: *tab_id,
+                        previous,
+                    });
                 }
                 CentralPanelEvent::FileTypeChanged(ft) => {
                     self.events.push(TabEvent::FileTypeChanged {
@@ -261,6 +383,22 @@ impl egui_dock::TabViewer for ThothTabViewer<'_> {
                         file_type: ft,
                     });
                 }
+                CentralPanelEvent::FileReloaded {
+                    file_type,
+                    total_items,
+                } => {
+                    self.events.push(TabEvent::FileReloaded {
+                        tab_id: *tab_id,
+                        file_type,
+                        total_items,
+                    });
+                }
+                CentralPanelEvent::FileReloadError(err) => {
+                    self.events.push(TabEvent::FileReloadError {
+                        tab_id: *tab_id,
+                        error: err,
+                    });
+                }
                 CentralPanelEvent::ErrorCleared => {
                     self.events.push(TabEvent::ErrorCleared { tab_id: *tab_id });
                 }
@@ -276,22 +414,44 @@ impl egui_dock::TabViewer for ThothTabViewer<'_> {
                 CentralPanelEvent::OpenRecentFile(path) => {
                     self.events.push(TabEvent::OpenRecentFile(path));
                 }
+                CentralPanelEvent::CopyText(text) => {
+                    self.events.push(TabEvent::CopyText(text));
+                }
             }
         }
     }
 
     fn on_close(&mut self, tab_id: &mut TabId) -> OnCloseResponse {
+        // Ask what to do with unsaved edits first; the app closes the tab after
+        if self
+            .tabs
+            .get(tab_id)
+            .is_some_and(|tab| tab.central_panel.unsaved_edits() > 0)
+        {
+            self.events.push(TabEvent::UnsavedEdits {
+                tab_id: *tab_id,
+                action: UnsavedAction::CloseTab,
+            });
+            return OnCloseResponse::Focus;
+        }
         // Notify the plugin BEFORE the pane (and its loader) is dropped.
         if let Some(tab) = self.tabs.get(tab_id)
             && let Some(pane) = tab.active_plugin_pane.as_ref()
         {
             pane.loader.on_tab_closed();
         }
+        if let Some((path, session)) = self.tabs.get(tab_id).and_then(TabState::file_session) {
+            self.file_sessions.remember(&path, session);
+        }
         self.tabs.remove(tab_id);
         self.events.push(TabEvent::TabClosed(*tab_id));
         OnCloseResponse::Close
     }
 
+    fn on_add(&mut self, _surface: egui_dock::SurfaceIndex, _node: egui_dock::NodeIndex) {
+        self.events.push(TabEvent::NewTab);
+    }
+
     fn scroll_bars(&self, _tab: &TabId) -> [bool; 2] {
         [false, false]
     }
@@ -365,6 +525,29 @@ impl TabManager {
         id
     }
 
+    /// Find an open tab showing `path`, preferring the active tab. Paths are
+    /// compared canonically so a file opened via a symlink or relative path
+    /// matches its real path.
+    pub fn find_file_tab(&mut self, path: &Path) -> Option<TabId> {
+        let target = crate::platform::canonical_path(path);
+        let shows_target = |tab: &TabState| {
+            tab.file_path
+                .as_deref()
+                .is_some_and(|p| crate::platform::canonical_path(p) == target)
+        };
+
+        if let Some(id) = self.active_tab_id()
+            && self.tabs.get(&id).is_some_and(&shows_target)
+        {
+            return Some(id);
+        }
+        self.tabs
+            .iter()
+            .filter(|(_, tab)| shows_target(tab))
+            .map(|(id, _)| *id)
+            .min()
+    }
+
     /// Get the ID of the currently focused tab, if any.
     ///
     /// Falls back to the smallest-ID tab when the dock has no focus set yet
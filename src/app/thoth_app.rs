@@ -1,18 +1,28 @@
 use eframe::{App, Frame, egui};
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
 use crate::{
     NOTIFICATION_MANAGER, PLUGIN_MANAGER,
-    app::{file_picker, pick_file, tab_manager::TabEvent},
+    app::{
+        file_picker, pick_file,
+        tab_manager::{TabEvent, UnsavedAction},
+    },
     components::{self, traits::ContextComponent},
+    error::ErrorHandler,
+    notification::{NotificationManager, Toast},
     plugin::plugin_ui_host::PluginUiHost,
     settings, state,
     theme::ThemeColorsExt,
 };
 
 use super::{
-    ShortcutAction, persistent_state::PersistentState, search_handler::SearchHandler,
-    shortcut_handler::ShortcutHandler, update_handler::UpdateHandler,
+    ShortcutAction,
+    persistent_state::{PersistentState, WindowGeometry},
+    recent_files_check::RecentFilesCheck,
+    search_handler::SearchHandler,
+    shortcut_handler::ShortcutHandler,
+    update_handler::UpdateHandler,
 };
 
 pub struct ThothApp {
@@ -52,6 +62,27 @@ pub struct ThothApp {
     /// Pending chart PNG export: `(chart rect in points, screenshot requested?)`
     /// — drives the two-frame screenshot → crop → save flow.
     chart_export: Option<(egui::Rect, bool)>,
+    /// Whether the restored window position has been checked against its monitor
+    window_geometry_checked: bool,
+    /// Records JSON held back by the large-copy warning until it's answered
+    pending_large_copy: Option<String>,
+    go_to_record: components::go_to_record::GoToRecordDialog,
+    command_palette: components::command_palette::CommandPalette,
+    clipboard_history: components::clipboard_history::ClipboardHistoryDialog,
+    export_records: components::export_records::ExportRecordsDialog,
+    /// Over-limit files waiting for the user to confirm opening them, asked
+    /// one at a time: `(tab, path, size in bytes)`
+    pending_large_files: Vec<(crate::app::tab_manager::TabId, PathBuf, u64)>,
+    /// Actions held back by a tab's unsaved edits, asked about one at a time
+    pending_unsaved: Vec<(crate::app::tab_manager::TabId, UnsavedAction)>,
+    /// Search message raised outside the sidebar, applied on the next frame
+    pending_search_message: Option<crate::search::SearchMessage>,
+    /// Sidebar events raised outside the sidebar, handled on the next frame
+    pending_sidebar_events: Vec<components::sidebar::SidebarEvent>,
+    /// Which recent and pinned files are missing from disk
+    recent_files_check: RecentFilesCheck,
+    /// Answer from the large-copy notification's actions: copy (`true`) or drop
+    large_copy_decision: Arc<Mutex<Option<bool>>>,
 }
 
 /// Build the synthetic `http-response` UiEvent delivered to a plugin when an
@@ -150,7 +181,7 @@ struct SidebarPluginRuntime {
 }
 
 impl ThothApp {
-    pub fn new(settings: settings::Settings, file_to_open: Option<PathBuf>) -> Self {
+    pub fn new(settings: settings::Settings, files_to_open: Vec<PathBuf>) -> Self {
         let persistent_state = PersistentState::default();
 
         let mut window_state = state::WindowState::default();
@@ -158,38 +189,67 @@ impl ThothApp {
             window_state.sidebar_expanded = persistent_state.get_sidebar_expanded();
         }
 
+        window_state
+            .sidebar
+            .set_search_query_mode(persistent_state.get_last_query_mode());
+        window_state
+            .sidebar
+            .set_search_flat_results(persistent_state.get_flat_search_results());
+
+        // Decompressed `.gz` copies from an earlier session are no longer in use
+        crate::file::gzip::clear_temp_copies();
+        crate::file::loaders::set_file_type_overrides(settings.viewer.file_type_overrides.clone());
+
         // Replace the default TabManager with one that uses the configured nav history size.
         let nav_capacity = settings.performance.navigation_history_size;
         window_state.tab_manager = crate::app::TabManager::new(nav_capacity);
 
-        let (pending_plugin_restores, session_restore_active_index) =
-            if let Some(path) = file_to_open {
-                // A file was passed via CLI / OS file association — open it directly,
-                // skipping session restore so the user sees exactly what they asked for.
+        let (pending_plugin_restores, session_restore_active_index) = if !files_to_open.is_empty() {
+            // Files were passed via CLI / OS file association — open each in a tab,
+            // skipping session restore so the user sees exactly what they asked for.
+            // The first one ends up focused.
+            let mut first = None;
+            for path in files_to_open {
+                let id = match window_state.tab_manager.find_file_tab(&path) {
+                    Some(id) => id,
+                    None => window_state.tab_manager.open_file(path, nav_capacity),
+                };
+                first.get_or_insert(id);
+            }
+            if let Some(id) = first {
+                window_state.tab_manager.focus_tab(id);
+            }
+            (Vec::new(), None)
+        } else {
+            // Restore the previous session (file tabs whose paths still exist, plugin tabs
+            // that can be re-instantiated). Plugin tabs that can't be opened yet (because
+            // PLUGIN_MANAGER is still initializing on a background thread) are returned
+            // here and retried via poll_pending_plugin_restores() each frame.
+            let (deferred, active_index) = Self::restore_tab_session(
+                &mut window_state.tab_manager,
+                &persistent_state,
+                &settings,
+            );
+            // Nothing came back from the session — fall back to the last opened file.
+            // Its expansion state is restored by the file viewer when it loads.
+            if deferred.is_empty()
+                && window_state.tab_manager.tabs.values().all(|t| t.is_empty())
+                && let Some(path) = Self::last_file_to_reopen(&persistent_state, &settings)
+            {
                 window_state.tab_manager.open_file(path, nav_capacity);
-                (Vec::new(), None)
+            }
+            // Switch to the previously-active tab immediately if there are no deferred
+            // plugins; otherwise defer until poll_pending_plugin_restores() finishes.
+            let restore_index = if deferred.is_empty() {
+                window_state
+                    .tab_manager
+                    .switch_to_tab_by_index(active_index);
+                None
             } else {
-                // Restore the previous session (file tabs whose paths still exist, plugin tabs
-                // that can be re-instantiated). Plugin tabs that can't be opened yet (because
-                // PLUGIN_MANAGER is still initializing on a background thread) are returned
-                // here and retried via poll_pending_plugin_restores() each frame.
-                let (deferred, active_index) = Self::restore_tab_session(
-                    &mut window_state.tab_manager,
-                    &persistent_state,
-                    &settings,
-                );
-                // Switch to the previously-active tab immediately if there are no deferred
-                // plugins; otherwise defer until poll_pending_plugin_restores() finishes.
-                let restore_index = if deferred.is_empty() {
-                    window_state
-                        .tab_manager
-                        .switch_to_tab_by_index(active_index);
-                    None
-                } else {
-                    Some(active_index)
-                };
-                (deferred, restore_index)
+                Some(active_index)
             };
+            (deferred, restore_index)
+        };
 
         Self {
             settings,
@@ -209,6 +269,18 @@ impl ThothApp {
             chart_counter: 0,
             chart_source: None,
             chart_export: None,
+            window_geometry_checked: false,
+            pending_large_copy: None,
+            go_to_record: Default::default(),
+            command_palette: Default::default(),
+            clipboard_history: Default::default(),
+            export_records: Default::default(),
+            pending_large_files: Vec::new(),
+            pending_unsaved: Vec::new(),
+            pending_search_message: None,
+            pending_sidebar_events: Vec::new(),
+            recent_files_check: Default::default(),
+            large_copy_decision: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -305,11 +377,11 @@ impl App for ThothApp {
         let _ = crate::EGUI_CTX.set(ctx.clone());
 
         if UpdateHandler::should_check_updates(&self.update_state, &self.settings) {
-            UpdateHandler::check_for_updates(&mut self.update_state);
+            UpdateHandler::check_for_updates(&mut self.update_state, &self.settings);
         }
 
         let should_show_updates =
-            UpdateHandler::handle_update_messages(&mut self.update_state, ctx);
+            UpdateHandler::handle_update_messages(&mut self.update_state, &self.settings, ctx);
 
         if should_show_updates {
             self.show_update_consent = true;
@@ -331,8 +403,17 @@ impl App for ThothApp {
         // Restore plugin tabs that were deferred at startup (PLUGIN_MANAGER not ready yet).
         self.poll_pending_plugin_restores();
 
-        // Handle OS-dispatched file opens (e.g. macOS Apple Events / Finder)
-        self.poll_os_open_requests();
+        // Switch to the theme's light or dark variant when the OS appearance changes
+        self.follow_system_theme(ctx);
+
+        // Grey out (or drop) recent files that were deleted or moved
+        self.check_recent_files(ctx);
+
+        // Handle OS-dispatched file opens (e.g. macOS Apple Events / Finder),
+        // raising the window so the user sees the file they asked for
+        if self.poll_os_open_requests() {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        }
 
         // Handle file drops
         self.handle_file_drop(ctx);
@@ -349,19 +430,43 @@ impl App for ThothApp {
 
         self.poll_plugin_panes(&ctx);
 
-        if self.settings.ui.show_toolbar {
+        // Presentation mode hides all chrome, overriding the UI settings
+        let presentation = self.window_state.presentation;
+
+        self.refresh_busy_state();
+
+        if self.settings.ui.show_toolbar && !presentation {
             self.render_toolbar(ui);
         }
 
-        if self.settings.ui.show_status_bar {
+        if self.settings.ui.show_status_bar && !presentation {
             self.render_status_bar(ui);
         }
 
+        if let Some(copy) = self
+            .large_copy_decision
+            .lock()
+            .ok()
+            .and_then(|mut d| d.take())
+        {
+            let text = self.pending_large_copy.take();
+            if copy {
+                self.clipboard_text = text;
+            }
+        }
+
         if let Some(text) = self.clipboard_text.take() {
+            self.window_state.clipboard_history.push(&text);
             ctx.copy_text(text);
+            NotificationManager::toast(Toast::success("Copied to clipboard"));
         }
 
-        let sidebar_msg = self.render_sidebar(ui);
+        let sidebar_msg = if presentation {
+            None
+        } else {
+            self.render_sidebar(ui)
+        }
+        .or_else(|| self.pending_search_message.take());
 
         // Handle search messages from sidebar against the active tab.
         let (msg_to_central, search_error) =
@@ -371,6 +476,8 @@ impl App for ThothApp {
                     &mut tab.search_engine_state,
                     &tab.file_path,
                     &tab.file_type,
+                    self.settings.performance.max_search_results,
+                    self.settings.performance.max_file_size_mb,
                     &ctx,
                 )
             } else {
@@ -383,6 +490,12 @@ impl App for ThothApp {
             tab.error = Some(error);
         }
 
+        // Collect finished find-and-replace previews and writes on every tab
+        for tab in self.window_state.tab_manager.tabs.values_mut() {
+            SearchHandler::poll_replace(&mut tab.search_engine_state.replace, &ctx);
+        }
+        SearchHandler::poll_recent_files_search(&mut self.window_state.recent_files_search, &ctx);
+
         let shortcut_actions =
             ShortcutHandler::handle_shortcuts(ui.ctx(), &self.settings.shortcuts);
         self.handle_shortcut_actions(ui.ctx(), shortcut_actions);
@@ -395,12 +508,26 @@ impl App for ThothApp {
             SettingsDialogProps {
                 update_state: Some(&self.update_state.update_status.state),
                 last_check: self.update_state.update_status.last_check,
+                check_failures: self.update_state.update_status.failures,
                 current_version: crate::update::UpdateManager::get_current_version(),
+                window_geometry: self.settings_window_geometry(&ctx),
             },
         );
 
+        if let Some(geometry) = settings_output.closed_geometry {
+            self.persistent_state.set_settings_window_geometry(geometry);
+            let _ = self.persistent_state.save();
+        }
+
         if !self.settings_dialog.open {
-            crate::theme::apply_theme(&ctx, &self.settings);
+            if self.window_state.presentation {
+                // Enlarge fonts without touching the saved settings
+                let mut presented = self.settings.clone();
+                presented.font_size *= crate::constants::PRESENTATION_FONT_SCALE;
+                crate::theme::apply_theme(&ctx, &presented);
+            } else {
+                crate::theme::apply_theme(&ctx, &self.settings);
+            }
         }
 
         if let Some(new_settings) = settings_output.new_settings {
@@ -410,7 +537,7 @@ impl App for ThothApp {
         for event in settings_output.events {
             match event {
                 SettingsDialogEvent::CheckForUpdates => {
-                    UpdateHandler::check_for_updates(&mut self.update_state);
+                    UpdateHandler::check_for_updates(&mut self.update_state, &self.settings);
                 }
                 SettingsDialogEvent::DownloadUpdate => {
                     let latest_release =
@@ -479,11 +606,19 @@ impl App for ThothApp {
 
         self.render_error_modal(&ctx);
         self.render_update_consent_modal(ui);
+        self.render_go_to_record(ui);
+        self.render_command_palette(ui);
+        self.render_clipboard_history(ui);
+        self.render_export_records(ui);
+        self.render_large_file_modal(ui);
+        self.render_unsaved_edits_modal(ui);
         self.poll_chart_export(&ctx);
 
         if let Some(new_settings) = settings::Settings::take_if_dirty(&ctx) {
             self.apply_new_settings(new_settings);
         }
+        self.guard_unsaved_edits_on_close(&ctx);
+        self.track_window_geometry(&ctx);
         self.save_settings_if_changed();
         self.save_session_if_dirty();
 
@@ -532,6 +667,9 @@ impl ThothApp {
 
         for action in actions {
             match action {
+                // Wait for a conflicting operation to finish or be canceled
+                ShortcutAction::OpenFile if self.window_state.busy.open_blocker().is_some() => {}
+                ShortcutAction::CloseTab if self.window_state.busy.close_blocker().is_some() => {}
                 ShortcutAction::OpenFile => {
                     if let Some(path) = file_picker::pick_file(self.settings.plugins.enabled) {
                         if let Some(path_str) = path.to_str() {
@@ -550,9 +688,38 @@ impl ThothApp {
                 ShortcutAction::Settings => {
                     self.open_settings_window(ctx);
                 }
-                ShortcutAction::ToggleTheme => {
-                    self.settings.dark_mode = !self.settings.dark_mode;
-                    self.settings_changed = true;
+                ShortcutAction::ToggleTheme => self.toggle_theme(),
+                ShortcutAction::ToggleSidebar => {
+                    self.window_state.sidebar_expanded = !self.window_state.sidebar_expanded;
+
+                    // Reopen on the last section; fall back if it was cleared
+                    // (e.g. a plugin sidebar that has since been closed)
+                    if self.window_state.sidebar_expanded
+                        && self.window_state.sidebar_selected_section.is_none()
+                    {
+                        self.window_state.sidebar_selected_section = Some(
+                            self.window_state
+                                .previous_sidebar_section
+                                .clone()
+                                .unwrap_or(components::sidebar::SidebarSection::RecentFiles),
+                        );
+                    }
+
+                    if self.settings.ui.remember_sidebar_state {
+                        self.persistent_state
+                            .set_sidebar_expanded(self.window_state.sidebar_expanded);
+                        let _ = self.persistent_state.save();
+                    }
+                }
+                ShortcutAction::TogglePresentation => {
+                    self.window_state.presentation = !self.window_state.presentation;
+                }
+                ShortcutAction::CommandPalette => {
+                    if self.command_palette.open {
+                        self.command_palette.open = false;
+                    } else {
+                        self.command_palette.show_dialog();
+                    }
                 }
                 ShortcutAction::ToggleProfiler => {
                     self.settings.dev.show_profiler = !self.settings.dev.show_profiler;
@@ -575,8 +742,30 @@ impl ThothApp {
                         let _ = self.persistent_state.save();
                     }
                 }
-                ShortcutAction::NextMatch => {}
-                ShortcutAction::PrevMatch => {}
+                ShortcutAction::NextMatch => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut()
+                        && let Some(record_index) = tab.search_engine_state.next_match()
+                    {
+                        tab.central_panel.navigate_to_record(record_index);
+                    }
+                }
+                ShortcutAction::PrevMatch => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut()
+                        && let Some(record_index) = tab.search_engine_state.prev_match()
+                    {
+                        tab.central_panel.navigate_to_record(record_index);
+                    }
+                }
+                ShortcutAction::GoToRecord => {
+                    if self
+                        .window_state
+                        .tab_manager
+                        .active_tab_mut()
+                        .is_some_and(|tab| tab.file_path.is_some())
+                    {
+                        self.go_to_record.show_dialog();
+                    }
+                }
                 ShortcutAction::NavBack => {
                     if let Some(tab) = self.window_state.tab_manager.active_tab_mut()
                         && let Some(path) = tab.navigation_history.back()
@@ -592,6 +781,44 @@ impl ThothApp {
                     }
                 }
                 ShortcutAction::Escape => {
+                    if self.command_palette.open {
+                        self.command_palette.open = false;
+                        continue;
+                    }
+
+                    // An in-progress expand-all takes priority over closing the sidebar
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut()
+                        && tab.central_panel.cancel_expand_all()
+                    {
+                        continue;
+                    }
+
+                    // A tree filter is cleared before closing anything too
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut()
+                        && tab.central_panel.clear_tree_filter()
+                    {
+                        continue;
+                    }
+
+                    // So does a search that is still scanning
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut()
+                        && tab.search_engine_state.search_rx.is_some()
+                    {
+                        SearchHandler::stop_search(&mut tab.search_engine_state);
+                        continue;
+                    }
+                    if self.window_state.recent_files_search.is_running() {
+                        SearchHandler::stop_recent_files_search(
+                            &mut self.window_state.recent_files_search,
+                        );
+                        continue;
+                    }
+
+                    if self.window_state.presentation {
+                        self.window_state.presentation = false;
+                        continue;
+                    }
+
                     if self.window_state.sidebar_expanded {
                         self.window_state.sidebar_expanded = false;
 
@@ -612,10 +839,18 @@ impl ThothApp {
                             Some((path, file_path))
                         });
                     if let Some((selected_path, file_path_str)) = info {
-                        self.persistent_state
+                        let added = self
+                            .persistent_state
                             .toggle_bookmark(selected_path, file_path_str);
-                        if let Err(e) = self.persistent_state.save() {
-                            eprintln!("Failed to save bookmarks: {}", e);
+                        match self.persistent_state.save() {
+                            Ok(()) if added => {
+                                NotificationManager::toast(Toast::success("Bookmark added"))
+                            }
+                            Ok(()) => NotificationManager::toast(Toast::info("Bookmark removed")),
+                            Err(e) => NotificationManager::toast(Toast::error(format!(
+                                "Failed to save bookmarks: {}",
+                                ErrorHandler::get_user_message(&e)
+                            ))),
                         }
                     }
                 }
@@ -649,6 +884,21 @@ impl ThothApp {
                         tab.central_panel.collapse_all_nodes();
                     }
                 }
+                ShortcutAction::ExpandToDepth => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
+                        tab.central_panel.expand_one_level();
+                    }
+                }
+                ShortcutAction::CollapseToDepth => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
+                        tab.central_panel.collapse_one_level();
+                    }
+                }
+                ShortcutAction::ToggleFlatView => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
+                        tab.central_panel.toggle_flat_view();
+                    }
+                }
                 ShortcutAction::MoveUp => {
                     if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
                         tab.central_panel.move_selection_up();
@@ -659,6 +909,16 @@ impl ThothApp {
                         tab.central_panel.move_selection_down();
                     }
                 }
+                ShortcutAction::ExtendSelectionUp => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
+                        tab.central_panel.extend_selection_up();
+                    }
+                }
+                ShortcutAction::ExtendSelectionDown => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
+                        tab.central_panel.extend_selection_down();
+                    }
+                }
                 ShortcutAction::CopyKey => {
                     if let Some(tab) = self.window_state.tab_manager.active_tab_mut()
                         && let Some(text) = tab.central_panel.copy_selected_key()
@@ -680,24 +940,31 @@ impl ThothApp {
                         self.clipboard_text = Some(text);
                     }
                 }
+                ShortcutAction::CopyMinified => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut()
+                        && let Some(text) = tab.central_panel.copy_selected_object_minified()
+                    {
+                        self.clipboard_text = Some(text);
+                    }
+                }
                 ShortcutAction::CopyPath => {
                     if let Some(tab) = self.window_state.tab_manager.active_tab_mut()
-                        && let Some(text) = tab.central_panel.copy_selected_path()
+                        && let Some(text) = tab
+                            .central_panel
+                            .copy_selected_path(self.settings.viewer.copy_path_format)
                     {
                         self.clipboard_text = Some(text);
                     }
                 }
-                ShortcutAction::CloseTab => {
-                    let was_empty = self.window_state.tab_manager.close_active_tab();
-                    let now_empty = self.window_state.tab_manager.tabs.is_empty();
-                    if was_empty && now_empty {
-                        // Last tab was already the welcome screen — close the window.
-                        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
-                    } else {
-                        self.window_state.tab_manager.ensure_non_empty(nav_capacity);
+                ShortcutAction::CopyLineNumber => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut()
+                        && let Some(text) = tab.central_panel.copy_selected_line_number()
+                    {
+                        self.clipboard_text = Some(text);
                     }
-                    self.session_dirty = true;
                 }
+                ShortcutAction::CopyVisibleRecords => self.copy_visible_records(),
+                ShortcutAction::CloseTab => self.close_active_tab(ctx),
                 ShortcutAction::NewTab => {
                     self.window_state.tab_manager.open_new_tab(nav_capacity);
                     // Empty tabs are not persisted, so no session_dirty needed here.
@@ -941,30 +1208,112 @@ impl ThothApp {
         }
     }
 
+    /// Switch between the theme's dark and light variants; does nothing but
+    /// explain why while the theme follows the system appearance
+    fn toggle_theme(&mut self) {
+        if self.settings.follow_system_theme {
+            NotificationManager::toast(Toast::info(
+                "The theme follows the system appearance. Turn this off in Settings → General",
+            ));
+            return;
+        }
+        let dark = !self.settings.theme.dark_mode;
+        self.settings.theme = self.settings.theme.for_mode(dark);
+        self.settings.dark_mode = dark;
+        self.settings_changed = true;
+    }
+
+    /// Match the theme to the OS appearance when the user chose to follow it
+    fn follow_system_theme(&mut self, ctx: &egui::Context) {
+        if !self.settings.follow_system_theme {
+            return;
+        }
+        let Some(system) = ctx.input(|i| i.raw.system_theme) else {
+            return;
+        };
+        let dark = system == egui::Theme::Dark;
+        if self.settings.theme.dark_mode != dark {
+            self.settings.theme = self.settings.theme.for_mode(dark);
+            self.settings.dark_mode = dark;
+            self.settings_changed = true;
+        }
+    }
+
+    /// Look for recent files that no longer exist while the list is on
+    /// screen, or always when missing ones are pruned
+    ///
+    /// The stat calls run on a worker thread. Results are cached until the
+    /// window regains focus, since files are usually moved or restored in
+    /// another app. Pinned files and files on network shares or removable
+    /// drives (which may just be unmounted) are never pruned, only greyed.
+    fn check_recent_files(&mut self, ctx: &egui::Context) {
+        let prune = self.settings.ui.prune_missing_recent_files;
+        let showing = self.window_state.sidebar_expanded
+            && self.window_state.sidebar_selected_section
+                == Some(components::sidebar::SidebarSection::RecentFiles);
+        if !prune && !showing {
+            return;
+        }
+
+        let check = &mut self.recent_files_check;
+        if ctx.input(|i| {
+            i.events
+                .iter()
+                .any(|e| matches!(e, egui::Event::WindowFocused(true)))
+        }) {
+            check.invalidate();
+        }
+        let waker = ctx.clone();
+        check.request(
+            self.persistent_state
+                .get_recent_files()
+                .iter()
+                .chain(self.persistent_state.get_pinned_files()),
+            move || waker.request_repaint(),
+        );
+        check.poll();
+
+        if !prune {
+            return;
+        }
+        let gone: Vec<String> = self
+            .persistent_state
+            .get_recent_files()
+            .iter()
+            .filter(|path| {
+                check.missing().contains(*path) && !crate::platform::is_detachable_path(path)
+            })
+            .cloned()
+            .collect();
+        if gone.is_empty() {
+            return;
+        }
+        for path in &gone {
+            self.persistent_state.remove_recent_file(path);
+        }
+        if let Err(e) = self.persistent_state.save() {
+            eprintln!("Failed to save recent files: {}", e);
+        }
+    }
+
     /// Drain OS-dispatched file open requests (e.g. macOS Apple Events) and
-    /// load the most recent one. Called once per frame from `update()`.
+    /// open each file in a tab, like files dropped on the window. Called once
+    /// per frame from `update()`; returns whether anything was requested.
     ///
     /// This mirrors the existing `poll_plugin_http_results` pattern: a
     /// platform-specific handler enqueues paths from a callback thread, and
-    /// we drain them on the UI thread each frame.
-    pub fn poll_os_open_requests(&mut self) {
+    /// we drain them on the UI thread each frame. While a load or export
+    /// blocks opening, requests stay queued until it finishes.
+    pub fn poll_os_open_requests(&mut self) -> bool {
+        if self.window_state.busy.open_blocker().is_some() {
+            return false;
+        }
         let paths = crate::platform::drain_open_requests();
-        if let Some(path) = paths.into_iter().last() {
-            // Add to recent files (same as toolbar / sidebar open-file paths)
-            if let Some(path_str) = path.to_str() {
-                self.persistent_state.add_recent_file(
-                    path_str.to_string(),
-                    self.settings.performance.max_recent_files,
-                );
-                let _ = self.persistent_state.save();
-            }
-
-            let nav_capacity = self.settings.performance.navigation_history_size;
-            self.window_state.tab_manager.open_file(path, nav_capacity);
-            if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
-                tab.error = None;
-            }
+        if paths.is_empty() {
+            return false;
         }
+        self.open_external_paths(paths);
+        true
     }
 
     /// Drive every open plugin tab once per frame, before rendering:
@@ -1126,6 +1475,25 @@ impl ThothApp {
         }
     }
 
+    /// Recompute which long-running operations are in flight for the active tab.
+    /// Runs every frame, so actions re-enable as soon as an operation finishes
+    /// or is canceled.
+    fn refresh_busy_state(&mut self) {
+        self.window_state.busy = match self.window_state.tab_manager.active_tab_mut() {
+            Some(tab) => crate::state::BusyState {
+                loading: tab.central_panel.is_opening()
+                    || tab
+                        .active_plugin_pane
+                        .as_ref()
+                        .is_some_and(|pane| pane.loader.busy() || pane.loader.has_pending_query()),
+                searching: tab.search_engine_state.search_rx.is_some(),
+                expanding: tab.central_panel.is_expanding_all(),
+                exporting: tab.search_engine_state.replace.write_rx.is_some(),
+            },
+            None => crate::state::BusyState::default(),
+        };
+    }
+
     fn render_toolbar(&mut self, ui: &mut egui::Ui) {
         #[cfg(feature = "profiling")]
         puffin::profile_function!();
@@ -1157,6 +1525,7 @@ impl ThothApp {
                 can_go_back,
                 can_go_forward,
                 plugins_enabled: self.settings.plugins.enabled,
+                busy: self.window_state.busy,
             },
         );
 
@@ -1178,23 +1547,12 @@ impl ThothApp {
                         tab.error = None;
                     }
                 }
-                components::toolbar::ToolbarEvent::CloseTab => {
-                    let was_empty = self.window_state.tab_manager.close_active_tab();
-                    let now_empty = self.window_state.tab_manager.tabs.is_empty();
-                    if was_empty && now_empty {
-                        ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
-                    } else {
-                        self.window_state.tab_manager.ensure_non_empty(nav_capacity);
-                    }
-                    self.session_dirty = true;
-                }
+                components::toolbar::ToolbarEvent::CloseTab => self.close_active_tab(ui.ctx()),
                 components::toolbar::ToolbarEvent::NewWindow => {
                     self.create_new_window();
                 }
-                components::toolbar::ToolbarEvent::ToggleTheme => {
-                    self.settings.dark_mode = !self.settings.dark_mode;
-                    self.settings_changed = true;
-                }
+                components::toolbar::ToolbarEvent::ExportHtml => self.export_view_html(ui.ctx()),
+                components::toolbar::ToolbarEvent::ToggleTheme => self.toggle_theme(),
                 components::toolbar::ToolbarEvent::OpenSettings => {
                     self.open_settings_window(ui.ctx());
                 }
@@ -1221,6 +1579,9 @@ impl ThothApp {
         for action in crate::platform::native_menu::poll_events() {
             use crate::platform::native_menu::MenuAction;
             match action {
+                // Wait for a conflicting operation to finish or be canceled
+                MenuAction::OpenFile if self.window_state.busy.open_blocker().is_some() => {}
+                MenuAction::CloseTab if self.window_state.busy.close_blocker().is_some() => {}
                 MenuAction::OpenFile => {
                     let plugins_enabled = self.settings.plugins.enabled;
                     if let Some(path) = crate::app::pick_file(plugins_enabled)
@@ -1242,16 +1603,8 @@ impl ThothApp {
                     }
                 }
                 MenuAction::NewWindow => self.create_new_window(),
-                MenuAction::CloseTab => {
-                    let was_empty = self.window_state.tab_manager.close_active_tab();
-                    let now_empty = self.window_state.tab_manager.tabs.is_empty();
-                    if was_empty && now_empty {
-                        ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
-                    } else {
-                        self.window_state.tab_manager.ensure_non_empty(nav_capacity);
-                    }
-                    self.session_dirty = true;
-                }
+                MenuAction::ExportHtml => self.export_view_html(ui.ctx()),
+                MenuAction::CloseTab => self.close_active_tab(ui.ctx()),
                 MenuAction::OpenSettings => self.open_settings_window(ui.ctx()),
             }
         }
@@ -1259,6 +1612,9 @@ impl ThothApp {
 
     fn save_settings_if_changed(&mut self) {
         if self.settings_changed {
+            crate::file::loaders::set_file_type_overrides(
+                self.settings.viewer.file_type_overrides.clone(),
+            );
             if let Err(e) = self.settings.save() {
                 eprintln!("Failed to save settings: {}", e);
             }
@@ -1332,6 +1688,20 @@ impl ThothApp {
         (deferred_plugins, active_tab_index)
     }
 
+    /// The last opened file, if `reopen_last_file` is on and it still exists
+    fn last_file_to_reopen(
+        persistent_state: &PersistentState,
+        settings: &settings::Settings,
+    ) -> Option<PathBuf> {
+        if !settings.ui.reopen_last_file {
+            return None;
+        }
+        persistent_state
+            .get_last_opened_file()
+            .map(PathBuf::from)
+            .filter(|path| path.is_file())
+    }
+
     /// Try to open a single plugin tab. Shared by initial restore and the deferred poll loop.
     /// Picks the loader by capability: data-source plugins (which need http-client) use the
     /// data-source loader; pure ui-component plugins use the ui-component loader. `state` is
@@ -1402,6 +1772,59 @@ impl ThothApp {
         }
     }
 
+    /// Remember where the main window is and save it when the window closes
+    ///
+    /// On the first frame, a window restored onto a different monitor than it
+    /// was saved on (e.g. one that has since been disconnected) is moved back
+    /// onto the current monitor.
+    fn track_window_geometry(&mut self, ctx: &egui::Context) {
+        let (geometry, close_requested) = ctx.input(|i| {
+            (
+                WindowGeometry::from_viewport(i.viewport()),
+                i.viewport().close_requested(),
+            )
+        });
+        let Some(geometry) = geometry else {
+            return;
+        };
+
+        if !self.window_geometry_checked {
+            self.window_geometry_checked = true;
+            if let Some(saved) = self.restored_window_geometry()
+                && !saved.fits_monitor(geometry.monitor_size)
+                && let Some(monitor_size) = geometry.monitor_size
+            {
+                let moved = saved.centered_on(monitor_size);
+                ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(moved.position.into()));
+                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(moved.size.into()));
+            }
+        }
+        if close_requested && self.settings.ui.restore_window_geometry {
+            self.persistent_state.set_window_geometry(geometry);
+            let _ = self.persistent_state.save();
+        }
+    }
+
+    /// Saved main window geometry, if restoring it is enabled
+    fn restored_window_geometry(&self) -> Option<WindowGeometry> {
+        self.settings
+            .ui
+            .restore_window_geometry
+            .then(|| self.persistent_state.get_window_geometry())
+            .flatten()
+    }
+
+    /// Where to reopen the settings window: its saved geometry, unless restoring
+    /// is off or it was saved on a monitor the main window isn't on now
+    fn settings_window_geometry(&self, ctx: &egui::Context) -> Option<WindowGeometry> {
+        let monitor_size = ctx.input(|i| i.viewport().monitor_size.map(|size| [size.x, size.y]));
+        self.persistent_state
+            .get_settings_window_geometry()
+            .filter(|geometry| {
+                self.settings.ui.restore_window_geometry && geometry.fits_monitor(monitor_size)
+            })
+    }
+
     /// Snapshot the current open tabs and write them to persistent_state, then save to disk.
     fn save_session_if_dirty(&mut self) {
         if !self.session_dirty {
@@ -1500,10 +1923,16 @@ impl ThothApp {
             total_items,
             error_present,
             search_scanning,
-            _search_results_len,
+            search_truncated,
             filtered_count,
+            match_position,
+            search_scope,
+            reloaded_at,
             selected_path,
             active_plugin_id,
+            cache_stats,
+            detection,
+            comments_stripped,
         ) = if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
             let search = &tab.search_engine_state.search;
             let scanning = search.scanning;
@@ -1514,6 +1943,7 @@ impl ThothApp {
             } else {
                 None
             };
+            let scope = search.scope_path.clone().filter(|_| query_non_empty);
             let sel_path = tab.central_panel.get_selected_path().cloned();
             // A plugin pane tab: (plugin_id, instance_id) drives the
             // instance-scoped status bar.
@@ -1527,10 +1957,16 @@ impl ThothApp {
                 tab.total_items,
                 tab.error.is_some(),
                 scanning,
-                results_len,
+                search.results.is_truncated(),
                 filtered,
+                tab.search_engine_state.match_position(),
+                scope,
+                tab.reloaded_at,
                 sel_path,
                 plugin_id,
+                tab.central_panel.cache_stats(),
+                tab.central_panel.detection(),
+                tab.central_panel.comments_stripped(),
             )
         } else {
             (
@@ -1539,10 +1975,16 @@ impl ThothApp {
                 0,
                 false,
                 false,
-                0,
+                false,
+                None,
+                None,
+                None,
                 None,
                 None,
                 None,
+                None,
+                None,
+                false,
             )
         };
 
@@ -1566,8 +2008,16 @@ impl ThothApp {
             components::status_bar::StatusBarProps {
                 file_path: file_path_opt.as_deref(),
                 file_type: &file_type,
+                detection,
+                comments_stripped,
                 item_count: total_items,
                 filtered_count,
+                search_truncated,
+                match_position,
+                search_scope: search_scope.as_deref(),
+                reloaded_at,
+                cache_stats,
+                resident_memory: crate::platform::resident_memory_bytes(),
                 status,
                 selected_path: selected_path.as_deref(),
                 active_plugin: active_plugin_id
@@ -1585,8 +2035,117 @@ impl ThothApp {
                         tab.central_panel.navigate_to_path(path);
                     }
                 }
+                components::status_bar::StatusBarEvent::RevealFile(path) => {
+                    let folder = path.parent().unwrap_or(&path);
+                    if let Err(e) = open::that(folder) {
+                        NotificationManager::notify_error(crate::notification::Notification::new(
+                            "Could not open folder",
+                            &format!("{}: {}", folder.display(), e),
+                        ));
+                    }
+                }
+                components::status_bar::StatusBarEvent::CopyFilePath(path) => {
+                    self.clipboard_text = Some(path.display().to_string());
+                }
+                components::status_bar::StatusBarEvent::ReopenAs(detected) => {
+                    if let Some(tab_id) = self.window_state.tab_manager.active_tab_id() {
+                        self.reopen_as(tab_id, detected);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether opening files has to wait for a running operation, telling
+    /// the user which one
+    pub(crate) fn opening_blocked(&self) -> bool {
+        let Some(blocker) = self.window_state.busy.open_blocker() else {
+            return false;
+        };
+        NotificationManager::toast(Toast::warning(format!(
+            "{} Open the file again once it's done",
+            blocker
+        )));
+        true
+    }
+
+    /// Re-read a tab's file as `detected`, asking about unsaved edits first
+    fn reopen_as(
+        &mut self,
+        tab_id: crate::app::tab_manager::TabId,
+        detected: crate::file::detect_file_type::DetectedFileType,
+    ) {
+        let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&tab_id) else {
+            return;
+        };
+        if tab.central_panel.unsaved_edits() > 0 {
+            self.ask_about_unsaved_edits(tab_id, UnsavedAction::ReopenAs(detected));
+            return;
+        }
+        match tab.central_panel.reopen_as(detected) {
+            Ok((file_type, total_items)) => {
+                tab.file_type = file_type;
+                tab.total_items = total_items;
             }
+            Err(e) => NotificationManager::notify_error(crate::notification::Notification::new(
+                &format!("Could not read file as {}", detected.label()),
+                &e.to_string(),
+            )),
+        }
+    }
+
+    /// Close the active tab, asking about unsaved edits first; closing the
+    /// last tab when it's already empty closes the window
+    fn close_active_tab(&mut self, ctx: &egui::Context) {
+        let nav_capacity = self.settings.performance.navigation_history_size;
+        if let Some(tab) = self.window_state.tab_manager.active_tab_mut()
+            && tab.central_panel.unsaved_edits() > 0
+        {
+            let tab_id = tab.id;
+            self.ask_about_unsaved_edits(tab_id, UnsavedAction::CloseTab);
+            return;
+        }
+        let was_empty = self.window_state.close_active_tab();
+        let now_empty = self.window_state.tab_manager.tabs.is_empty();
+        if was_empty && now_empty {
+            // Last tab was already the welcome screen — close the window.
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        } else {
+            self.window_state.tab_manager.ensure_non_empty(nav_capacity);
+        }
+        self.session_dirty = true;
+    }
+
+    /// Hold `action` back until the tab's unsaved edits are saved or discarded
+    fn ask_about_unsaved_edits(
+        &mut self,
+        tab_id: crate::app::tab_manager::TabId,
+        action: UnsavedAction,
+    ) {
+        if !self.pending_unsaved.contains(&(tab_id, action)) {
+            self.pending_unsaved.push((tab_id, action));
+        }
+    }
+
+    /// Keep the window open while a tab has unsaved edits and ask about them;
+    /// the window closes once none are left
+    fn guard_unsaved_edits_on_close(&mut self, ctx: &egui::Context) {
+        if !ctx.input(|i| i.viewport().close_requested()) {
+            return;
         }
+        let tab_manager = &self.window_state.tab_manager;
+        let Some(tab_id) = tab_manager.ordered_tab_ids().into_iter().find(|id| {
+            tab_manager
+                .tabs
+                .get(id)
+                .is_some_and(|tab| tab.central_panel.unsaved_edits() > 0)
+        }) else {
+            crate::file::gzip::clear_temp_copies();
+            return;
+        };
+        ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+        self.window_state.tab_manager.focus_tab(tab_id);
+        self.ask_about_unsaved_edits(tab_id, UnsavedAction::Quit);
     }
 
     /// Render the DockArea that hosts all open tabs.
@@ -1619,10 +2178,12 @@ impl ThothApp {
             tabs,
             settings: &self.settings,
             persistent_state: &mut self.persistent_state,
+            file_sessions: &mut self.window_state.file_sessions,
             nav_capacity,
             search_msg: search_message.zip(focused_id).map(|(msg, id)| (id, msg)),
             events: Vec::new(),
             colors,
+            presentation: self.window_state.presentation,
         };
 
         // Use a smaller font for tab labels (egui_dock hardcodes TextStyle::Button).
@@ -1636,6 +2197,7 @@ impl ThothApp {
                 egui_dock::DockArea::new(dock_state)
                     .style(dock_style)
                     .show_leaf_collapse_buttons(false)
+                    .show_add_buttons(true)
                     .show_inside(ui, &mut viewer);
                 viewer.events.drain(..).collect::<Vec<_>>()
             })
@@ -1663,44 +2225,122 @@ impl ThothApp {
                     );
                     let _ = self.persistent_state.save();
                 }
+                // Pick up where the user left off if the file was open earlier
+                let session = self.window_state.file_sessions.take(&path);
                 if let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&tab_id) {
                     tab.file_path = Some(path);
                     tab.file_type = file_type;
                     tab.total_items = total_items;
+                    tab.reloaded_at = None;
                     tab.active_plugin_pane = None;
                     tab.plugin_sidebar_output = None;
+                    if let Some(session) = &session {
+                        tab.central_panel.restore_session(session);
+                    }
                     if let Some(pending_path) = tab.pending_navigation.take() {
                         tab.central_panel.navigate_to_path(pending_path);
                     }
                 }
+                // Searches run against the active tab, so only its file's
+                // search is run again
+                if let Some(session) = session
+                    && !session.query.is_empty()
+                    && self.window_state.tab_manager.active_tab_id() == Some(tab_id)
+                    && let Some(msg) = crate::search::SearchMessage::create_search(
+                        session.query,
+                        session.match_case,
+                        session.query_mode,
+                        session.scope_path,
+                    )
+                {
+                    if let crate::search::SearchMessage::StartSearch(search) = &msg {
+                        self.window_state.sidebar.load_search(search);
+                    }
+                    self.pending_search_message = Some(msg);
+                }
                 self.session_dirty = true;
             }
+            TabEvent::FileTooLarge {
+                tab_id,
+                path,
+                size_bytes,
+            } => {
+                self.pending_large_files.push((tab_id, path, size_bytes));
+            }
+            TabEvent::UnsavedEdits { tab_id, action } => {
+                self.ask_about_unsaved_edits(tab_id, action);
+            }
             TabEvent::FileOpenError { tab_id, error } => {
                 if let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&tab_id) {
                     tab.error = Some(error);
                 }
             }
-            TabEvent::FileClosed { tab_id } => {
+            TabEvent::FileClosed { tab_id, path } => {
+                // A cleared file starts over the next time it's opened
+                self.window_state.file_sessions.forget(&path);
                 if let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&tab_id) {
                     tab.file_path = None;
                     tab.total_items = 0;
+                    tab.reloaded_at = None;
                 }
                 self.session_dirty = true;
             }
-            TabEvent::FileTypeChanged { tab_id, file_type } => {
+            TabEvent::OpenCanceled { tab_id, previous } => {
                 if let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&tab_id) {
-                    tab.file_type = file_type;
+                    match previous {
+                        Some((path, file_type)) => {
+                            tab.file_path = Some(path);
+                            tab.file_type = file_type;
+                        }
+                        None => tab.file_path = None,
+                    }
+                    tab.error = None;
                 }
+                self.session_dirty = true;
             }
-            TabEvent::ErrorCleared { tab_id } => {
+            TabEvent::FileTypeChanged { tab_id, file_type } => {
                 if let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&tab_id) {
-                    tab.error = None;
+                    tab.file_type = file_type;
                 }
             }
-            TabEvent::PluginUiEvent { tab_id, event } => {
-                self.dispatch_plugin_event_for(tab_id, event);
-            }
-            TabEvent::NavigationPush { tab_id, path } => {
+            TabEvent::FileReloaded {
+                tab_id,
+                file_type,
+                total_items,
+            } => {
+                if let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&tab_id) {
+                    tab.file_type = file_type;
+                    tab.total_items = total_items;
+                    tab.reloaded_at = Some(chrono::Local::now());
+                }
+            }
+            TabEvent::FileReloadError { tab_id, error } => {
+                // Non-fatal: the tab keeps showing the last good contents
+                let file_name = self
+                    .window_state
+                    .tab_manager
+                    .tabs
+                    .get(&tab_id)
+                    .map(|tab| tab.title())
+                    .unwrap_or_default();
+                crate::notification::NotificationManager::notify(
+                    crate::notification::Notification::new(
+                        &format!("Could not reload {}", file_name),
+                        &crate::error::ErrorHandler::get_user_message(&error),
+                    )
+                    .with_kind(crate::notification::NotificationKind::Warn)
+                    .with_toast(true),
+                );
+            }
+            TabEvent::ErrorCleared { tab_id } => {
+                if let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&tab_id) {
+                    tab.error = None;
+                }
+            }
+            TabEvent::PluginUiEvent { tab_id, event } => {
+                self.dispatch_plugin_event_for(tab_id, event);
+            }
+            TabEvent::NavigationPush { tab_id, path } => {
                 if let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&tab_id) {
                     tab.navigation_history.push(path);
                 }
@@ -1710,14 +2350,24 @@ impl ThothApp {
                 let _ = id;
                 self.session_dirty = true;
             }
+            TabEvent::NewTab => {
+                self.window_state.tab_manager.open_new_tab(nav_capacity);
+            }
             TabEvent::OpenFilePicker => {
                 let nav_cap = self.settings.performance.navigation_history_size;
-                if let Some(path) = pick_file(self.settings.plugins.enabled) {
+                if !self.opening_blocked()
+                    && let Some(path) = pick_file(self.settings.plugins.enabled)
+                {
                     self.window_state.tab_manager.open_file(path, nav_cap);
                 }
             }
             TabEvent::OpenRecentFile(path) => {
-                self.window_state.tab_manager.open_file(path, nav_capacity);
+                if !self.opening_blocked() {
+                    self.window_state.tab_manager.open_file(path, nav_capacity);
+                }
+            }
+            TabEvent::CopyText(text) => {
+                self.clipboard_text = Some(text);
             }
             TabEvent::ChartAction { tab_id, action } => {
                 use crate::components::chart_studio::ChartTabAction;
@@ -1761,15 +2411,42 @@ impl ThothApp {
             .unwrap_or_default();
 
         // Snapshot per-tab data we need for SidebarProps (avoids complex lifetime issues).
-        let (current_file_path, search_state_clone) =
-            if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
-                (
-                    tab.file_path.clone(),
-                    tab.search_engine_state.search.clone(),
-                )
-            } else {
-                (None, crate::search::Search::default())
-            };
+        let (
+            current_file_path,
+            search_state_clone,
+            selected_path,
+            schema_status,
+            replace_status,
+            facets,
+        ) = if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
+            (
+                tab.file_path.clone(),
+                tab.search_engine_state.search.clone(),
+                tab.central_panel.get_selected_path().cloned(),
+                components::schema_panel::SchemaStatus::new(
+                    tab.schema.as_ref(),
+                    tab.central_panel.schema_validation(),
+                ),
+                tab.search_engine_state.replace.status(),
+                tab.central_panel.facets().cloned(),
+            )
+        } else {
+            (
+                None,
+                crate::search::Search::default(),
+                None,
+                components::schema_panel::SchemaStatus::default(),
+                crate::state::ReplaceStatus::default(),
+                None,
+            )
+        };
+
+        // Each tab keeps its own search; show the active one's in the panel
+        let active_tab = self.window_state.tab_manager.active_tab_id();
+        if active_tab != self.window_state.search_panel_tab {
+            self.window_state.search_panel_tab = active_tab;
+            self.window_state.sidebar.load_search(&search_state_clone);
+        }
 
         // The mounted plugin sidebar (independent of any tab) drives the sidebar
         // panel and the icon-highlight state.
@@ -1807,6 +2484,26 @@ impl ThothApp {
                 super::persistent_state::PersistentState::load_search_history(path_str).ok()
             });
 
+        // Bookmarks store canonical paths, so compare against the canonical form.
+        let current_file_key = current_file_path
+            .as_ref()
+            .and_then(|p| p.to_str())
+            .map(crate::platform::canonical_path_key);
+
+        // Suggest continuations for the path typed in the bookmarks panel
+        let path_completion = if self.window_state.sidebar_selected_section
+            == Some(components::sidebar::SidebarSection::Bookmarks)
+            && !self.window_state.sidebar.jump_query().is_empty()
+        {
+            let query = self.window_state.sidebar.jump_query().to_string();
+            self.window_state
+                .tab_manager
+                .active_tab_mut()
+                .and_then(|tab| tab.central_panel.complete_path(&query))
+        } else {
+            None
+        };
+
         // Feed the Chart Studio its live source list + open-chart list.
         let producers = self.gather_producers();
         self.window_state.sidebar.set_chart_producers(producers);
@@ -1823,14 +2520,23 @@ impl ThothApp {
             ui,
             components::sidebar::SidebarProps {
                 recent_files: self.persistent_state.get_recent_files(),
+                pinned_files: self.persistent_state.get_pinned_files(),
+                missing_files: self.recent_files_check.missing(),
                 bookmarks: self.persistent_state.get_bookmarks(),
-                current_file_path: current_file_path.as_ref().and_then(|p| p.to_str()),
+                current_file_path: current_file_key.as_deref(),
                 expanded: self.window_state.sidebar_expanded,
                 sidebar_width: self.persistent_state.get_sidebar_width(),
                 selected_section: self.window_state.sidebar_selected_section.clone(),
                 focus_search,
                 search_state: &search_state_clone,
                 search_history: search_history.as_ref(),
+                selected_path: selected_path.as_deref(),
+                path_completion: path_completion.as_ref(),
+                schema_status: &schema_status,
+                replace_status: &replace_status,
+                recent_files_search: &self.window_state.recent_files_search,
+                facets: facets.as_ref(),
+                busy: self.window_state.busy,
                 data_source_plugins: &ds_plugins,
                 ui_component_plugins: &ui_plugins,
                 active_datasource_plugin_id: sidebar_plugin_id.as_deref(),
@@ -1845,12 +2551,15 @@ impl ThothApp {
         self.window_state.previous_sidebar_expanded = self.window_state.sidebar_expanded;
 
         let nav_capacity = self.settings.performance.navigation_history_size;
+        let pending = std::mem::take(&mut self.pending_sidebar_events);
 
-        for event in output.events {
+        for event in output.events.into_iter().chain(pending) {
             match event {
                 components::sidebar::SidebarEvent::OpenFile(file_path) => {
-                    let path = std::path::PathBuf::from(&file_path);
-                    self.window_state.tab_manager.open_file(path, nav_capacity);
+                    if !self.opening_blocked() {
+                        let path = std::path::PathBuf::from(&file_path);
+                        self.window_state.tab_manager.open_file(path, nav_capacity);
+                    }
                 }
                 components::sidebar::SidebarEvent::RemoveRecentFile(file_path) => {
                     self.persistent_state.remove_recent_file(&file_path);
@@ -1858,8 +2567,30 @@ impl ThothApp {
                         eprintln!("Failed to save recent files: {}", e);
                     }
                 }
+                components::sidebar::SidebarEvent::TogglePinRecentFile(file_path) => {
+                    match self
+                        .persistent_state
+                        .toggle_pinned_file(&file_path, self.settings.performance.max_recent_files)
+                    {
+                        Ok(_) => {
+                            if let Err(e) = self.persistent_state.save() {
+                                eprintln!("Failed to save pinned files: {}", e);
+                            }
+                        }
+                        Err(e) => {
+                            crate::notification::NotificationManager::notify_error(
+                                crate::notification::Notification::new(
+                                    "Could not pin file",
+                                    &crate::error::ErrorHandler::get_user_message(&e),
+                                ),
+                            );
+                        }
+                    }
+                }
                 components::sidebar::SidebarEvent::OpenFilePicker => {
-                    if let Some(path) = pick_file(self.settings.plugins.enabled) {
+                    if !self.opening_blocked()
+                        && let Some(path) = pick_file(self.settings.plugins.enabled)
+                    {
                         if let Some(path_str) = path.to_str() {
                             self.persistent_state.add_recent_file(
                                 path_str.to_string(),
@@ -1939,11 +2670,120 @@ impl ThothApp {
                     }
                     return Some(msg);
                 }
+                components::sidebar::SidebarEvent::SearchQueryModeChanged(mode) => {
+                    self.persistent_state.set_last_query_mode(mode);
+                    if let Err(e) = self.persistent_state.save() {
+                        eprintln!("Failed to save search query mode: {}", e);
+                    }
+                }
+                components::sidebar::SidebarEvent::SearchFlatResultsChanged(flat) => {
+                    self.persistent_state.set_flat_search_results(flat);
+                    if let Err(e) = self.persistent_state.save() {
+                        eprintln!("Failed to save search results layout: {}", e);
+                    }
+                }
+                components::sidebar::SidebarEvent::PreviewReplace(spec) => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
+                        SearchHandler::start_replace_preview(
+                            &mut tab.search_engine_state.replace,
+                            &tab.file_path,
+                            spec,
+                            self.settings.performance.max_file_size_mb,
+                            ui.ctx(),
+                        );
+                    }
+                }
+                components::sidebar::SidebarEvent::ApplyReplace => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut()
+                        && let Some(source) = tab.file_path.clone()
+                        && let Some(output) = file_picker::pick_replace_output(&source)
+                    {
+                        SearchHandler::start_replace_write(
+                            &mut tab.search_engine_state.replace,
+                            &tab.file_path,
+                            output,
+                            self.settings.performance.max_file_size_mb,
+                            ui.ctx(),
+                        );
+                    }
+                }
+                components::sidebar::SidebarEvent::ClearReplace => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
+                        tab.search_engine_state.replace = crate::state::ReplaceState::default();
+                    }
+                }
+                components::sidebar::SidebarEvent::SearchRecentFiles(search) => {
+                    // Pinned files first, as in the recent files panel
+                    let mut files: Vec<std::path::PathBuf> = Vec::new();
+                    for file in self
+                        .persistent_state
+                        .get_pinned_files()
+                        .iter()
+                        .chain(self.persistent_state.get_recent_files())
+                    {
+                        let path = std::path::PathBuf::from(file);
+                        if !files.contains(&path) {
+                            files.push(path);
+                        }
+                    }
+                    if files.is_empty() {
+                        NotificationManager::toast(Toast::info("No recent files to search"));
+                    } else {
+                        SearchHandler::start_recent_files_search(
+                            &mut self.window_state.recent_files_search,
+                            search,
+                            files,
+                            self.settings.performance.max_search_results,
+                            self.settings.performance.max_file_size_mb,
+                            ui.ctx(),
+                        );
+                    }
+                }
+                components::sidebar::SidebarEvent::StopRecentFilesSearch => {
+                    SearchHandler::stop_recent_files_search(
+                        &mut self.window_state.recent_files_search,
+                    );
+                }
+                components::sidebar::SidebarEvent::OpenRecentFilesMatch { path, record_index } => {
+                    if let Some(id) = self.window_state.tab_manager.find_file_tab(&path) {
+                        self.window_state.tab_manager.focus_tab(id);
+                        if let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&id) {
+                            tab.navigation_history.push(record_index.to_string());
+                            tab.central_panel.navigate_to_record(record_index);
+                        }
+                    } else if !self.opening_blocked() {
+                        let id = self
+                            .window_state
+                            .tab_manager
+                            .open_file(path.clone(), nav_capacity);
+                        if let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&id) {
+                            tab.error = None;
+                            tab.pending_navigation = Some(record_index.to_string());
+                        }
+                        if let Some(path_str) = path.to_str() {
+                            self.persistent_state.add_recent_file(
+                                path_str.to_string(),
+                                self.settings.performance.max_recent_files,
+                            );
+                            let _ = self.persistent_state.save();
+                        }
+                    }
+                }
                 components::sidebar::SidebarEvent::NavigateToSearchResult { record_index } => {
                     if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
+                        tab.search_engine_state
+                            .set_current_match_record(record_index);
                         tab.central_panel.navigate_to_record(record_index);
                     }
                 }
+                components::sidebar::SidebarEvent::NavigateToSearchMatch { record_index, path } => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
+                        tab.search_engine_state
+                            .set_current_match_record(record_index);
+                        tab.navigation_history.push(path.clone());
+                        tab.central_panel.navigate_to_path(path);
+                    }
+                }
                 components::sidebar::SidebarEvent::ClearSearchHistory => {
                     if let Some(tab) = self.window_state.tab_manager.active_tab_mut()
                         && let Some(file_path) = &tab.file_path
@@ -1954,19 +2794,37 @@ impl ThothApp {
                         );
                     }
                 }
+                components::sidebar::SidebarEvent::CopyVisibleRecords => {
+                    self.copy_visible_records();
+                }
+                components::sidebar::SidebarEvent::ExportVisibleRecords => {
+                    self.export_records.show_dialog();
+                }
+                components::sidebar::SidebarEvent::RemoveSearchHistoryEntry(entry) => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut()
+                        && let Some(file_path) = &tab.file_path
+                        && let Some(path_str) = file_path.to_str()
+                        && let Err(e) =
+                            super::persistent_state::PersistentState::remove_search_query(
+                                path_str, &entry,
+                            )
+                    {
+                        eprintln!("Failed to update search history: {}", e);
+                    }
+                }
                 components::sidebar::SidebarEvent::NavigateToBookmark { file_path, path } => {
-                    let current_file =
-                        self.window_state
-                            .tab_manager
-                            .active_tab_mut()
-                            .and_then(|tab| {
-                                tab.file_path
-                                    .as_ref()
-                                    .and_then(|p| p.to_str())
-                                    .map(|s| s.to_string())
-                            });
-
-                    if current_file.as_deref() != Some(file_path.as_str()) {
+                    let open_tab = self
+                        .window_state
+                        .tab_manager
+                        .find_file_tab(std::path::Path::new(&file_path));
+
+                    if let Some(id) = open_tab {
+                        self.window_state.tab_manager.focus_tab(id);
+                        if let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&id) {
+                            tab.navigation_history.push(path.clone());
+                            tab.central_panel.navigate_to_path(path);
+                        }
+                    } else if !self.opening_blocked() {
                         let path_buf = std::path::PathBuf::from(&file_path);
                         let id = self
                             .window_state
@@ -1981,25 +2839,110 @@ impl ThothApp {
                             self.settings.performance.max_recent_files,
                         );
                         let _ = self.persistent_state.save();
-                    } else {
-                        if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
-                            tab.navigation_history.push(path.clone());
-                            tab.central_panel.navigate_to_path(path);
-                        }
                     }
                 }
                 components::sidebar::SidebarEvent::RemoveBookmark(index) => {
                     self.persistent_state.remove_bookmark(index);
-                    if let Err(e) = self.persistent_state.save() {
+                    match self.persistent_state.save() {
+                        Ok(()) => NotificationManager::toast(Toast::info("Bookmark removed")),
+                        Err(e) => NotificationManager::toast(Toast::error(format!(
+                            "Failed to save bookmarks: {}",
+                            ErrorHandler::get_user_message(&e)
+                        ))),
+                    }
+                }
+                components::sidebar::SidebarEvent::RenameBookmark { index, label } => {
+                    // The list may have changed since editing started
+                    if !self.persistent_state.rename_bookmark(index, Some(label)) {
+                        NotificationManager::toast(Toast::warning(
+                            "The bookmark was removed before it was renamed",
+                        ));
+                    } else if let Err(e) = self.persistent_state.save() {
                         eprintln!("Failed to save bookmarks: {}", e);
                     }
                 }
+                components::sidebar::SidebarEvent::ExportBookmarks => {
+                    if let Some(path) = file_picker::pick_bookmarks_export() {
+                        match self.persistent_state.export_bookmarks(&path) {
+                            Ok(()) => {
+                                crate::notification::NotificationManager::notify(
+                                    crate::notification::Notification::new(
+                                        "Bookmarks exported",
+                                        &format!(
+                                            "Saved {} bookmark(s) to {}",
+                                            self.persistent_state.get_bookmarks().len(),
+                                            path.display()
+                                        ),
+                                    )
+                                    .with_kind(crate::notification::NotificationKind::Success),
+                                );
+                            }
+                            Err(e) => {
+                                crate::notification::NotificationManager::notify_error(
+                                    crate::notification::Notification::new(
+                                        "Could not export bookmarks",
+                                        &crate::error::ErrorHandler::get_user_message(&e),
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                }
+                components::sidebar::SidebarEvent::ImportBookmarks => {
+                    if let Some(path) = file_picker::pick_bookmarks_import() {
+                        match self.persistent_state.import_bookmarks(&path) {
+                            Ok(added) => {
+                                if let Err(e) = self.persistent_state.save() {
+                                    eprintln!("Failed to save bookmarks: {}", e);
+                                }
+                                crate::notification::NotificationManager::notify(
+                                    crate::notification::Notification::new(
+                                        "Bookmarks imported",
+                                        &format!("Added {} new bookmark(s)", added),
+                                    ),
+                                );
+                            }
+                            Err(e) => {
+                                crate::notification::NotificationManager::notify_error(
+                                    crate::notification::Notification::new(
+                                        "Could not import bookmarks",
+                                        &crate::error::ErrorHandler::get_user_message(&e),
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                }
                 components::sidebar::SidebarEvent::JumpToPath(path) => {
                     if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
                         tab.navigation_history.push(path.clone());
                         tab.central_panel.navigate_to_path(path);
                     }
                 }
+                components::sidebar::SidebarEvent::LoadSchema => {
+                    if let Some(path) = file_picker::pick_schema_file() {
+                        match crate::file::schema::SchemaValidator::open(&path) {
+                            Ok(validator) => {
+                                if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
+                                    tab.schema = Some(std::sync::Arc::new(validator));
+                                }
+                            }
+                            Err(e) => {
+                                crate::notification::NotificationManager::notify_error(
+                                    crate::notification::Notification::new(
+                                        "Could not load schema",
+                                        &crate::error::ErrorHandler::get_user_message(&e),
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                }
+                components::sidebar::SidebarEvent::ClearSchema => {
+                    if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
+                        tab.schema = None;
+                    }
+                }
                 components::sidebar::SidebarEvent::DataSourceQueryResult { .. } => {}
                 components::sidebar::SidebarEvent::DataSourceConsentNeeded(consent_request) => {
                     eprintln!(
@@ -2243,6 +3186,61 @@ impl ThothApp {
         self.window_state.tab_manager.focus_tab(tab_id);
     }
 
+    /// Copy the active tab's visible records as one JSON array
+    ///
+    /// Above `performance.copy_warn_size_mb` the text is held back and a
+    /// notification asks before the clipboard write.
+    fn copy_visible_records(&mut self) {
+        let Some(tab) = self.window_state.tab_manager.active_tab_mut() else {
+            return;
+        };
+        let (text, count) = match tab.central_panel.visible_records_json() {
+            Ok(records) => records,
+            Err(e) => {
+                NotificationManager::toast(Toast::error(ErrorHandler::get_user_message(&e)));
+                return;
+            }
+        };
+
+        let limit = self.settings.performance.copy_warn_size_mb * 1024 * 1024;
+        if (text.len() as u64) <= limit {
+            self.clipboard_text = Some(text);
+            return;
+        }
+
+        let size_mb = text.len() as f64 / (1024.0 * 1024.0);
+        self.pending_large_copy = Some(text);
+        let copy = self.large_copy_decision.clone();
+        let cancel = self.large_copy_decision.clone();
+        NotificationManager::notify(
+            crate::notification::Notification::new(
+                "Large copy",
+                &format!(
+                    "{} records are {:.1} MB of JSON; copying them may freeze Thoth briefly.",
+                    count, size_mb
+                ),
+            )
+            .with_id("large_copy")
+            .with_kind(crate::notification::NotificationKind::Warn)
+            .with_action(
+                "Copy anyway",
+                Arc::new(move || {
+                    if let Ok(mut decision) = copy.lock() {
+                        *decision = Some(true);
+                    }
+                }),
+            )
+            .with_action(
+                "Cancel",
+                Arc::new(move || {
+                    if let Ok(mut decision) = cancel.lock() {
+                        *decision = Some(false);
+                    }
+                }),
+            ),
+        );
+    }
+
     /// Drive the chart PNG export: request a viewport screenshot, then on the
     /// next frame crop it to the chart's rect and save via a file dialog.
     fn poll_chart_export(&mut self, ctx: &egui::Context) {
@@ -2272,12 +3270,12 @@ impl ThothApp {
         use crate::components::traits::StatefulComponent;
         use crate::error::RecoveryAction;
 
-        let error = self
+        let (error, file_path) = self
             .window_state
             .tab_manager
             .active_tab_mut()
-            .and_then(|t| t.error.as_ref())
-            .cloned();
+            .map(|t| (t.error.clone(), t.file_path.clone()))
+            .unwrap_or_default();
 
         if let Some(error) = error {
             let mut output = None;
@@ -2290,6 +3288,7 @@ impl ThothApp {
                         components::error_modal::ErrorModalProps {
                             error: &error,
                             open: true,
+                            file_path: file_path.as_deref(),
                         },
                     ));
                 });
@@ -2316,6 +3315,7 @@ impl ThothApp {
                             tab.file_path = None;
                             tab.total_items = 0;
                             tab.search_engine_state.search = crate::search::Search::default();
+                            tab.search_engine_state.current_match_index = None;
                         }
                     }
                 }
@@ -2341,6 +3341,348 @@ impl ThothApp {
         }
     }
 
+    /// Show the "Go to record #" dialog and jump to the record it returns
+    fn render_go_to_record(&mut self, ui: &mut egui::Ui) {
+        use crate::components::go_to_record::GoToRecordProps;
+        use crate::components::traits::StatefulComponent;
+
+        let Some(tab) = self.window_state.tab_manager.active_tab_mut() else {
+            self.go_to_record.open = false;
+            return;
+        };
+        let search = &tab.search_engine_state.search;
+        let request = self.go_to_record.render(
+            ui,
+            GoToRecordProps {
+                total_records: tab.total_items,
+                search_results: (!search.query.is_empty()).then_some(&search.results),
+            },
+        );
+        let Some(request) = request else {
+            return;
+        };
+
+        if request.clear_search {
+            tab.search_engine_state.current_match_index = None;
+            self.pending_search_message = self.window_state.sidebar.clear_search();
+        } else {
+            // Continue match navigation from the record jumped to
+            tab.search_engine_state.current_match_index = tab
+                .search_engine_state
+                .search
+                .results
+                .hits()
+                .iter()
+                .position(|hit| hit.record_index == request.record_index);
+        }
+        tab.navigation_history
+            .push(request.record_index.to_string());
+        tab.central_panel.navigate_to_record(request.record_index);
+    }
+
+    /// Show the command palette and run the command picked from it
+    fn render_command_palette(&mut self, ui: &mut egui::Ui) {
+        use crate::components::command_palette::{CommandPaletteProps, PaletteAction};
+        use crate::components::sidebar::SidebarEvent;
+        use crate::components::traits::StatefulComponent;
+
+        let action = self.command_palette.render(
+            ui,
+            CommandPaletteProps {
+                shortcuts: &self.settings.shortcuts,
+            },
+        );
+        let Some(action) = action else {
+            return;
+        };
+
+        let event = match action {
+            PaletteAction::Shortcut(action) => {
+                self.handle_shortcut_actions(ui.ctx(), vec![action]);
+                return;
+            }
+            PaletteAction::ShowClipboardHistory => {
+                self.clipboard_history.show_dialog();
+                return;
+            }
+            PaletteAction::ExportHtml => {
+                self.export_view_html(ui.ctx());
+                return;
+            }
+            PaletteAction::ExportRecords => {
+                self.export_records.show_dialog();
+                return;
+            }
+            PaletteAction::CheckForUpdates => {
+                UpdateHandler::check_for_updates(&mut self.update_state, &self.settings);
+                self.settings_dialog.open_updates(&self.settings);
+                return;
+            }
+            PaletteAction::ShowSection(section) => {
+                if self.window_state.sidebar_expanded
+                    && self.window_state.sidebar_selected_section.as_ref() == Some(&section)
+                {
+                    return;
+                }
+                SidebarEvent::SectionToggled(section)
+            }
+            PaletteAction::LoadSchema => SidebarEvent::LoadSchema,
+            PaletteAction::ExportBookmarks => SidebarEvent::ExportBookmarks,
+            PaletteAction::ImportBookmarks => SidebarEvent::ImportBookmarks,
+        };
+        self.pending_sidebar_events.push(event);
+        ui.ctx().request_repaint();
+    }
+
+    /// Save the active tab's tree, as currently expanded, as a standalone HTML page
+    fn export_view_html(&mut self, ctx: &egui::Context) {
+        use crate::notification::{Notification, NotificationKind, NotificationManager};
+
+        let colors = crate::theme::ThemeColors::from_ctx(ctx);
+        let Some(tab) = self.window_state.tab_manager.active_tab_mut() else {
+            return;
+        };
+        let Some(source) = tab.file_path.clone() else {
+            return;
+        };
+        let Some(html) = tab.central_panel.export_html(&colors) else {
+            NotificationManager::notify_error(Notification::new(
+                "Could not export view",
+                "Only the JSON tree view can be exported as HTML",
+            ));
+            return;
+        };
+        let Some(path) = file_picker::pick_html_export(&source) else {
+            return;
+        };
+        match std::fs::write(&path, html) {
+            Ok(()) => {
+                NotificationManager::notify(
+                    Notification::new(
+                        "View exported",
+                        &format!("Saved the view to {}", path.display()),
+                    )
+                    .with_kind(NotificationKind::Success),
+                );
+            }
+            Err(e) => {
+                NotificationManager::notify_error(Notification::new(
+                    "Could not export view",
+                    &format!("{}: {}", path.display(), e),
+                ));
+            }
+        }
+    }
+
+    /// Ask for a format, then a file, and write the active tab's visible
+    /// records to it
+    fn render_export_records(&mut self, ui: &mut egui::Ui) {
+        use crate::components::export_records::ExportRecordsProps;
+        use crate::components::traits::StatefulComponent;
+        use crate::notification::{Notification, NotificationKind};
+
+        if !self.export_records.open {
+            return;
+        }
+        let Some(tab) = self.window_state.tab_manager.active_tab_mut() else {
+            self.export_records.open = false;
+            return;
+        };
+        let (Some(source), Some(count)) = (
+            tab.file_path.clone(),
+            tab.central_panel.visible_record_count(),
+        ) else {
+            self.export_records.open = false;
+            NotificationManager::toast(Toast::error("Open a file to export its records"));
+            return;
+        };
+
+        let Some(format) = self.export_records.render(
+            ui,
+            ExportRecordsProps {
+                count,
+                filtered: tab.central_panel.is_filtered(),
+                source_format: tab.central_panel.export_source_format(),
+            },
+        ) else {
+            return;
+        };
+        let Some(path) = file_picker::pick_records_export(&source, format) else {
+            return;
+        };
+        if crate::platform::canonical_path(&path) == crate::platform::canonical_path(&source) {
+            NotificationManager::notify_error(Notification::new(
+                "Could not export records",
+                "Records are exported to a new file; choose a different path than the open file",
+            ));
+            return;
+        }
+        match tab.central_panel.export_visible_records(&path, format) {
+            Ok(written) => {
+                NotificationManager::notify(
+                    Notification::new(
+                        "Records exported",
+                        &format!(
+                            "Saved {} {} as {} to {}",
+                            written,
+                            if written == 1 { "record" } else { "records" },
+                            format.label(),
+                            path.display()
+                        ),
+                    )
+                    .with_kind(NotificationKind::Success),
+                );
+            }
+            Err(e) => {
+                NotificationManager::notify_error(Notification::new(
+                    "Could not export records",
+                    &ErrorHandler::get_user_message(&e),
+                ));
+            }
+        }
+    }
+
+    /// Show the clipboard history popup and copy the entry picked from it
+    fn render_clipboard_history(&mut self, ui: &mut egui::Ui) {
+        use crate::components::clipboard_history::{ClipboardHistoryAction, ClipboardHistoryProps};
+        use crate::components::traits::StatefulComponent;
+
+        let action = self.clipboard_history.render(
+            ui,
+            ClipboardHistoryProps {
+                history: &self.window_state.clipboard_history,
+            },
+        );
+        match action {
+            Some(ClipboardHistoryAction::Copy(text)) => {
+                self.clipboard_text = Some(text);
+                ui.ctx().request_repaint();
+            }
+            Some(ClipboardHistoryAction::Clear) => self.window_state.clipboard_history.clear(),
+            None => {}
+        }
+    }
+
+    /// Ask before loading a file over `performance.max_file_size_mb`; the tab
+    /// only loads it once the user opens it once or raises the limit
+    fn render_large_file_modal(&mut self, ui: &mut egui::Ui) {
+        use crate::components::large_file_modal::{
+            LargeFileModal, LargeFileModalProps, raised_limit_mb,
+        };
+        use crate::components::traits::StatelessComponent;
+
+        let Some((_, path, size_bytes)) = self.pending_large_files.first() else {
+            return;
+        };
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        let output = LargeFileModal::render(
+            ui,
+            LargeFileModalProps {
+                file_name: &file_name,
+                size_bytes: *size_bytes,
+                limit_mb: self.settings.performance.max_file_size_mb,
+            },
+        );
+        if !(output.open_once || output.raise_limit || output.cancel) {
+            return;
+        }
+
+        let (tab_id, path, size_bytes) = self.pending_large_files.remove(0);
+        if output.raise_limit {
+            self.settings.performance.max_file_size_mb = raised_limit_mb(size_bytes);
+            self.settings_changed = true;
+        }
+        let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&tab_id) else {
+            return;
+        };
+        if output.cancel {
+            if tab.file_path.as_ref() == Some(&path) {
+                tab.file_path = None;
+                tab.total_items = 0;
+            }
+        } else {
+            tab.central_panel.confirm_large_file(path);
+        }
+        self.session_dirty = true;
+    }
+
+    /// Ask what to do with a tab's unsaved edits, then carry out the action
+    /// that was held back for them
+    fn render_unsaved_edits_modal(&mut self, ui: &mut egui::Ui) {
+        use crate::components::traits::StatelessComponent;
+        use crate::components::unsaved_edits_modal::{UnsavedEditsModal, UnsavedEditsModalProps};
+
+        let Some(&(tab_id, action)) = self.pending_unsaved.first() else {
+            return;
+        };
+        let Some(tab) = self.window_state.tab_manager.tabs.get(&tab_id) else {
+            self.pending_unsaved.remove(0);
+            return;
+        };
+        let file_name = tab
+            .central_panel
+            .loaded_file()
+            .and_then(|(path, _)| path.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| tab.title());
+        let output = UnsavedEditsModal::render(
+            ui,
+            UnsavedEditsModalProps {
+                file_name: &file_name,
+                record_count: tab.central_panel.unsaved_edits(),
+            },
+        );
+        if !(output.save || output.discard || output.cancel) {
+            return;
+        }
+        self.pending_unsaved.remove(0);
+        let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&tab_id) else {
+            return;
+        };
+
+        let proceed = if output.save {
+            match tab.central_panel.save_edits() {
+                Ok(_) => true,
+                Err(e) => {
+                    NotificationManager::notify_error(crate::notification::Notification::new(
+                        &format!("Could not save {}", file_name),
+                        &ErrorHandler::get_user_message(&e),
+                    ));
+                    false
+                }
+            }
+        } else if output.discard {
+            tab.central_panel.discard_edits();
+            true
+        } else {
+            false
+        };
+
+        if !proceed {
+            // Show the edited file again instead of the one the tab asked for
+            if action == UnsavedAction::SwitchFile
+                && let Some((path, file_type)) = tab.central_panel.loaded_file()
+            {
+                tab.file_path = Some(path);
+                tab.file_type = file_type;
+                tab.error = None;
+            }
+            return;
+        }
+        match action {
+            // The tab opens the file it asks for on the next frame
+            UnsavedAction::SwitchFile => {}
+            UnsavedAction::ReopenAs(detected) => self.reopen_as(tab_id, detected),
+            UnsavedAction::CloseTab => {
+                self.window_state.tab_manager.focus_tab(tab_id);
+                self.close_active_tab(ui.ctx());
+            }
+            UnsavedAction::Quit => ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close),
+        }
+    }
+
     fn render_update_consent_modal(&mut self, ui: &mut egui::Ui) {
         use super::update_handler::ConsentAction;
         match UpdateHandler::render_consent_modal(ui, &self.update_state, self.show_update_consent)
@@ -2381,7 +3723,8 @@ fn save_chart_png(image: &egui::ColorImage) {
                 crate::notification::Notification::new(
                     "Chart exported",
                     &path.display().to_string(),
-                ),
+                )
+                .with_kind(crate::notification::NotificationKind::Success),
             );
         } else {
             crate::notification::NotificationManager::notify_error(
@@ -21,9 +21,17 @@ impl UpdateHandler {
         )
     }
 
-    /// Initiate update check
-    pub fn check_for_updates(update_state: &mut state::ApplicationUpdateState) {
-        update_state.update_manager.check_for_updates();
+    /// Initiate update check; it runs in the background and gives up after
+    /// `updates.check_timeout_secs`
+    pub fn check_for_updates(
+        update_state: &mut state::ApplicationUpdateState,
+        settings: &settings::Settings,
+    ) {
+        update_state
+            .update_manager
+            .check_for_updates(std::time::Duration::from_secs(
+                settings.updates.check_timeout_secs.max(1),
+            ));
         update_state.update_status.state = update::UpdateState::Checking;
         update_state.update_status.last_check = Some(chrono::Utc::now());
     }
@@ -32,13 +40,14 @@ impl UpdateHandler {
     /// Returns `true` once when a new update is detected (first time only per session).
     pub fn handle_update_messages(
         update_state: &mut state::ApplicationUpdateState,
+        settings: &settings::Settings,
         ctx: &egui::Context,
     ) -> bool {
         let mut update_detected = false;
         while let Ok(msg) = update_state.update_manager.receiver().try_recv() {
             match msg {
                 update::manager::UpdateMessage::UpdateCheckComplete(result) => {
-                    if Self::handle_check_complete(result, update_state) {
+                    if Self::handle_check_complete(result, update_state, settings) {
                         update_detected = true;
                     }
                 }
@@ -61,13 +70,24 @@ impl UpdateHandler {
     fn handle_check_complete(
         result: Result<Vec<update::ReleaseInfo>, ThothError>,
         update_state: &mut state::ApplicationUpdateState,
+        settings: &settings::Settings,
     ) -> bool {
         let mut update_detected = false;
+        let include_prereleases = settings.updates.include_prereleases;
+
+        // Failures in a row make the next automatic check back off
+        let status = &mut update_state.update_status;
+        status.failures = if result.is_ok() {
+            0
+        } else {
+            status.failures.saturating_add(1)
+        };
 
         match result {
             Ok(releases) => {
-                if update::UpdateManager::has_newer_version(&releases) {
-                    let newer_releases = update::UpdateManager::get_newer_releases(&releases);
+                if update::UpdateManager::has_newer_version(&releases, include_prereleases) {
+                    let newer_releases =
+                        update::UpdateManager::get_newer_releases(&releases, include_prereleases);
                     if let Some(latest) = newer_releases.first() {
                         update_state.update_status.state = update::UpdateState::UpdateAvailable {
                             latest_version: latest.tag_name.clone(),
@@ -1,19 +1,35 @@
 use crate::app::persistent_state::Bookmark;
 use crate::components::traits::StatefulComponent;
+use crate::helpers::PathCompletion;
 use eframe::egui;
 use thoth_plugin_sdk::components::{
-    Input, List, ListEvent, ListItem, ListItemPrefix, Separator, SidebarHeader,
+    Input, List, ListEvent, ListItem, ListItemAction, ListItemPrefix, Select, SelectOption,
+    Separator, SidebarHeader, SidebarHeaderAction, Size, Typography,
 };
 
 pub struct BookmarksProps<'a> {
     pub bookmarks: &'a [Bookmark],
     pub current_file_path: Option<&'a str>,
+    /// Suggestions for the jump-to-path text, from the active tab's data
+    pub path_completion: Option<&'a PathCompletion>,
 }
 
 #[derive(Debug, Clone)]
 pub enum BookmarksEvent {
-    NavigateToBookmark { file_path: String, path: String },
+    NavigateToBookmark {
+        file_path: String,
+        path: String,
+    },
     JumpToPath(String),
+    /// Set the label of the bookmark at `index`; an empty label clears it
+    Rename {
+        index: usize,
+        label: String,
+    },
+    /// Write all bookmarks to a file chosen by the user
+    Export,
+    /// Merge bookmarks from a file chosen by the user
+    Import,
 }
 
 pub struct BookmarksOutput {
@@ -23,6 +39,366 @@ pub struct BookmarksOutput {
 #[derive(Default)]
 pub struct Bookmarks {
     jump_input: String,
+    /// Suggestion highlighted with the arrow keys
+    jump_highlight: Option<usize>,
+    /// Whether the jump input had focus last frame
+    jump_focused: bool,
+    /// Give the jump input focus back (after a suggestion was clicked)
+    jump_refocus: bool,
+    /// Bookmark whose label is being edited inline
+    editing: Option<LabelEdit>,
+    /// Text the list is narrowed down by
+    filter: String,
+    sort: BookmarkSort,
+}
+
+/// Order the bookmarks list is shown in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum BookmarkSort {
+    /// Oldest first, the order they were added in
+    #[default]
+    Created,
+    /// Grouped by file name, then by path within each file
+    File,
+    /// Alphabetically by path
+    Path,
+}
+
+impl BookmarkSort {
+    const ALL: [BookmarkSort; 3] = [
+        BookmarkSort::Created,
+        BookmarkSort::File,
+        BookmarkSort::Path,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            BookmarkSort::Created => "Date added",
+            BookmarkSort::File => "File",
+            BookmarkSort::Path => "Path",
+        }
+    }
+}
+
+/// Indices into `bookmarks` of the ones matching `filter`, in `sort` order
+///
+/// The filter is matched case-insensitively against the path, the label and
+/// the file name; an empty filter keeps every bookmark.
+fn visible_bookmarks(bookmarks: &[Bookmark], filter: &str, sort: BookmarkSort) -> Vec<usize> {
+    let filter = filter.trim().to_lowercase();
+    let mut indices: Vec<usize> = bookmarks
+        .iter()
+        .enumerate()
+        .filter(|(_, b)| {
+            filter.is_empty()
+                || [
+                    Some(b.path.as_str()),
+                    b.label.as_deref(),
+                    Some(file_name(&b.file_path)),
+                ]
+                .into_iter()
+                .flatten()
+                .any(|text| text.to_lowercase().contains(&filter))
+        })
+        .map(|(i, _)| i)
+        .collect();
+
+    // Stable sorts, so ties keep the order the bookmarks were added in
+    match sort {
+        BookmarkSort::Created => indices.sort_by_key(|&i| bookmarks[i].created_at),
+        BookmarkSort::File => indices.sort_by(|&a, &b| {
+            let (a, b) = (&bookmarks[a], &bookmarks[b]);
+            file_name(&a.file_path)
+                .to_lowercase()
+                .cmp(&file_name(&b.file_path).to_lowercase())
+                .then_with(|| a.file_path.cmp(&b.file_path))
+                .then_with(|| a.path.cmp(&b.path))
+        }),
+        BookmarkSort::Path => indices.sort_by(|&a, &b| bookmarks[a].path.cmp(&bookmarks[b].path)),
+    }
+    indices
+}
+
+/// Last component of a bookmark's file path, or the whole path without one
+fn file_name(file_path: &str) -> &str {
+    std::path::Path::new(file_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(file_path)
+}
+
+/// Inline label editor for one bookmark row
+struct LabelEdit {
+    index: usize,
+    /// Identity of the bookmark, so the edit follows it if the list changes
+    path: String,
+    file_path: String,
+    label: String,
+    /// Focus the text field on its first frame
+    focus: bool,
+}
+
+impl LabelEdit {
+    fn new(index: usize, bookmark: &Bookmark) -> Self {
+        Self {
+            index,
+            path: bookmark.path.clone(),
+            file_path: bookmark.file_path.clone(),
+            label: bookmark.label.clone().unwrap_or_default(),
+            focus: true,
+        }
+    }
+
+    /// Show the text field; commits on Enter or clicking away, cancels on Escape
+    fn show(&mut self, ui: &mut egui::Ui) -> EditOutcome {
+        let mut input = Input::builder()
+            .id("bookmark_label_input")
+            .value(self.label.clone())
+            .placeholder(self.path.clone())
+            .icon(egui_phosphor::regular::PENCIL_SIMPLE)
+            .build();
+        let r = input.show(ui);
+        if r.inner {
+            self.label = input.value.clone();
+        }
+        if std::mem::take(&mut self.focus) {
+            r.response.request_focus();
+            return EditOutcome::Editing;
+        }
+
+        if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+            EditOutcome::Cancelled
+        } else if r.response.lost_focus() {
+            EditOutcome::Committed(BookmarksEvent::Rename {
+                index: self.index,
+                label: self.label.clone(),
+            })
+        } else {
+            EditOutcome::Editing
+        }
+    }
+}
+
+enum EditOutcome {
+    Editing,
+    Cancelled,
+    Committed(BookmarksEvent),
+}
+
+/// List row for a bookmark: its label (or path) with the path and, for other
+/// files, the file name underneath
+fn bookmark_item(bookmark: &Bookmark, show_filename: bool) -> ListItem {
+    let file_name = show_filename.then(|| file_name(&bookmark.file_path).to_string());
+    let (title, description) = match bookmark.label.as_deref().filter(|l| !l.is_empty()) {
+        Some(label) => (
+            label.to_string(),
+            Some(match file_name {
+                Some(file_name) => format!("{} · {}", bookmark.path, file_name),
+                None => bookmark.path.clone(),
+            }),
+        ),
+        None => (bookmark.path.clone(), file_name),
+    };
+    ListItem::builder()
+        .title(title)
+        .maybe_description(description)
+        .prefix(ListItemPrefix::Icon {
+            glyph: egui_phosphor::regular::BOOKMARK_SIMPLE.to_string(),
+            color: None,
+        })
+        .actions(vec![
+            ListItemAction::builder()
+                .icon(egui_phosphor::regular::PENCIL_SIMPLE)
+                .tooltip("Rename")
+                .build(),
+        ])
+        .build()
+}
+
+impl Bookmarks {
+    /// Text currently typed in the jump-to-path input
+    pub fn jump_query(&self) -> &str {
+        &self.jump_input
+    }
+
+    /// Jump-to-path input with a suggestion list underneath
+    ///
+    /// Up/Down highlight a suggestion, Tab accepts it, Enter jumps to the
+    /// highlighted suggestion or the typed path.
+    fn render_jump_input(
+        &mut self,
+        ui: &mut egui::Ui,
+        completion: Option<&PathCompletion>,
+        events: &mut Vec<BookmarksEvent>,
+    ) {
+        // Ignore suggestions computed for older text
+        let completion = completion.filter(|c| c.query == self.jump_input);
+        let suggestions = completion.map_or(&[][..], |c| c.suggestions.as_slice());
+        if self
+            .jump_highlight
+            .is_some_and(|idx| idx >= suggestions.len())
+        {
+            self.jump_highlight = None;
+        }
+
+        let mut accepted = None;
+        if self.jump_focused && !suggestions.is_empty() {
+            let (down, up, tab) = ui.input_mut(|i| {
+                (
+                    i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                    i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                    i.consume_key(egui::Modifiers::NONE, egui::Key::Tab),
+                )
+            });
+            if down {
+                self.jump_highlight = Some(
+                    self.jump_highlight
+                        .map_or(0, |idx| (idx + 1).min(suggestions.len() - 1)),
+                );
+            }
+            if up {
+                self.jump_highlight = self.jump_highlight.and_then(|idx| idx.checked_sub(1));
+            }
+            if tab {
+                accepted = Some(suggestions[self.jump_highlight.unwrap_or(0)].clone());
+            }
+        }
+        if let Some(path) = accepted.take() {
+            self.jump_input = path;
+            self.jump_highlight = None;
+            self.jump_refocus = true;
+        }
+
+        let mut input = Input::builder()
+            .id("jump_input")
+            .value(self.jump_input.clone())
+            .placeholder("Jump to path (e.g., 0.user.name)")
+            .icon(egui_phosphor::regular::CROSSHAIR)
+            .build();
+        let r = input.show(ui);
+        if r.inner {
+            self.jump_input = input.value.clone();
+            self.jump_highlight = None;
+            // Suggestions for the new text are computed next frame
+            ui.ctx().request_repaint();
+        }
+        let response = r.response;
+
+        if std::mem::take(&mut self.jump_refocus) {
+            response.request_focus();
+            move_cursor_to_end(ui.ctx(), response.id, &self.jump_input);
+            ui.ctx().request_repaint();
+        }
+        if response.has_focus() && !suggestions.is_empty() {
+            // Keep focus on Tab and the vertical arrows so they drive the suggestions
+            ui.memory_mut(|m| {
+                m.set_focus_lock_filter(
+                    response.id,
+                    egui::EventFilter {
+                        tab: true,
+                        vertical_arrows: true,
+                        ..Default::default()
+                    },
+                )
+            });
+        }
+
+        if response.lost_focus()
+            && ui.input(|i| i.key_pressed(egui::Key::Enter))
+            && !self.jump_input.is_empty()
+        {
+            let highlighted = self.jump_highlight.and_then(|idx| suggestions.get(idx));
+            if let Some(path) = highlighted {
+                events.push(BookmarksEvent::JumpToPath(path.clone()));
+                self.jump_input.clear();
+            } else if completion.is_some_and(|c| !c.exists) {
+                // Keep the text so it can be fixed; the hint below explains why
+                response.request_focus();
+            } else {
+                events.push(BookmarksEvent::JumpToPath(self.jump_input.clone()));
+                self.jump_input.clear();
+            }
+            self.jump_highlight = None;
+        }
+        if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+            self.jump_input.clear();
+            self.jump_highlight = None;
+        }
+        let focused = response.has_focus() || self.jump_refocus;
+
+        if self.jump_focused && !suggestions.is_empty() && !self.jump_input.is_empty() {
+            egui::Frame::new()
+                .fill(ui.visuals().extreme_bg_color)
+                .corner_radius(4)
+                .inner_margin(4)
+                .show(ui, |ui| {
+                    for (idx, path) in suggestions.iter().enumerate() {
+                        let selected = self.jump_highlight == Some(idx);
+                        if ui.selectable_label(selected, path).clicked() {
+                            self.jump_input = path.clone();
+                            self.jump_highlight = None;
+                            self.jump_refocus = true;
+                        }
+                    }
+                });
+        } else if !self.jump_input.is_empty()
+            && completion.is_some_and(|c| !c.exists && c.suggestions.is_empty())
+        {
+            Typography::caption(ui, "No such path");
+        }
+
+        self.jump_focused = focused || self.jump_refocus;
+    }
+
+    /// Filter input with the sort order underneath
+    fn render_filter_row(&mut self, ui: &mut egui::Ui) {
+        let mut input = Input::builder()
+            .id("bookmarks_filter_input")
+            .value(self.filter.clone())
+            .placeholder("Filter bookmarks…")
+            .icon(egui_phosphor::regular::FUNNEL)
+            .build();
+        let r = input.show(ui);
+        if r.inner {
+            self.filter = input.value.clone();
+        }
+        ui.add_space(4.0);
+
+        let mut select = Select::builder()
+            .id("bookmarks_sort_select")
+            .value(self.sort.label())
+            .options(
+                BookmarkSort::ALL
+                    .iter()
+                    .map(|s| {
+                        SelectOption::builder()
+                            .value(s.label())
+                            .label(s.label())
+                            .build()
+                    })
+                    .collect(),
+            )
+            .prefix_label("Sort: ")
+            .size(Size::Small)
+            .build();
+        if let Some(label) = select.show(ui).inner.selected {
+            self.sort = BookmarkSort::ALL
+                .into_iter()
+                .find(|s| s.label() == label)
+                .unwrap_or_default();
+        }
+    }
+}
+
+/// Put the text cursor of the `TextEdit` with `id` after the last character
+fn move_cursor_to_end(ctx: &egui::Context, id: egui::Id, text: &str) {
+    if let Some(mut state) = egui::TextEdit::load_state(ctx, id) {
+        let end = egui::text::CCursor::new(text.chars().count());
+        state
+            .cursor
+            .set_char_range(Some(egui::text::CCursorRange::one(end)));
+        state.store(ctx, id);
+    }
 }
 
 impl StatefulComponent for Bookmarks {
@@ -39,85 +415,126 @@ impl StatefulComponent for Bookmarks {
             return BookmarksOutput { events };
         }
 
+        let visible = visible_bookmarks(props.bookmarks, &self.filter, self.sort);
+
         // Header
-        ui.add(SidebarHeader::builder().title("BOOKMARKS").build());
+        let action_clicked = SidebarHeader::builder()
+            .title("BOOKMARKS")
+            .maybe_trailing_text(
+                (visible.len() < props.bookmarks.len())
+                    .then(|| format!("{} of {}", visible.len(), props.bookmarks.len())),
+            )
+            .actions(vec![
+                SidebarHeaderAction::builder()
+                    .icon(egui_phosphor::regular::DOWNLOAD_SIMPLE)
+                    .tooltip("Import bookmarks…")
+                    .build(),
+                SidebarHeaderAction::builder()
+                    .icon(egui_phosphor::regular::UPLOAD_SIMPLE)
+                    .tooltip("Export bookmarks…")
+                    .build(),
+            ])
+            .build()
+            .show(ui)
+            .inner;
+        match action_clicked {
+            Some(0) => events.push(BookmarksEvent::Import),
+            Some(1) if !props.bookmarks.is_empty() => events.push(BookmarksEvent::Export),
+            _ => {}
+        }
         ui.add_space(8.0);
 
-        // Jump-to-path input
-        {
-            let mut input = Input::builder()
-                .id("jump_input")
-                .value(self.jump_input.clone())
-                .placeholder("Jump to path (e.g., 0.user.name)")
-                .icon(egui_phosphor::regular::CROSSHAIR)
-                .build();
-            let r = input.show(ui);
-            if r.inner {
-                self.jump_input = input.value.clone();
-            }
-            let response = r.response;
-            if response.lost_focus()
-                && ui.input(|i| i.key_pressed(egui::Key::Enter))
-                && !self.jump_input.is_empty()
+        self.render_jump_input(ui, props.path_completion, &mut events);
+
+        ui.add(Separator::with_margins(8.0, 4.0));
+
+        self.render_filter_row(ui);
+        ui.add_space(4.0);
+
+        // Follow the edited bookmark if the list changed under the edit, and
+        // drop the edit once the filter hides it
+        if let Some(edit) = &mut self.editing {
+            match props
+                .bookmarks
+                .iter()
+                .position(|b| b.path == edit.path && b.file_path == edit.file_path)
             {
-                events.push(BookmarksEvent::JumpToPath(self.jump_input.clone()));
-                self.jump_input.clear();
-            }
-            if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
-                self.jump_input.clear();
+                Some(index) if visible.contains(&index) => edit.index = index,
+                _ => self.editing = None,
             }
         }
 
-        ui.add(Separator::with_margins(8.0, 4.0));
-
         egui::ScrollArea::vertical()
             .auto_shrink([false, false])
             .show(ui, |ui| {
-                let show_filename: Vec<bool> = props
-                    .bookmarks
+                let items: Vec<ListItem> = visible
                     .iter()
-                    .map(|b| props.current_file_path != Some(&b.file_path))
+                    .map(|&i| {
+                        let b = &props.bookmarks[i];
+                        bookmark_item(b, props.current_file_path != Some(&b.file_path))
+                    })
                     .collect();
 
-                let items: Vec<ListItem> = props
-                    .bookmarks
-                    .iter()
-                    .enumerate()
-                    .map(|(i, b)| {
-                        let title = b.label.as_deref().unwrap_or(b.path.as_str());
-                        let description = if show_filename[i] {
-                            Some(
-                                std::path::Path::new(&b.file_path)
-                                    .file_name()
-                                    .and_then(|n| n.to_str())
-                                    .unwrap_or(b.file_path.as_str())
-                                    .to_string(),
-                            )
+                // The edited row is replaced by a text field, splitting the list around it
+                let split = self
+                    .editing
+                    .as_ref()
+                    .and_then(|edit| visible.iter().position(|&i| i == edit.index))
+                    .unwrap_or(items.len());
+                let mut items = items.into_iter();
+                let before: Vec<ListItem> = items.by_ref().take(split).collect();
+                let after: Vec<ListItem> = items.skip(1).collect();
+
+                let mut list_events = Vec::new();
+                if !before.is_empty() || self.editing.is_none() {
+                    let event = List::builder()
+                        .items(before)
+                        .empty_label(if props.bookmarks.is_empty() {
+                            "No bookmarks — press Cmd+D to add one"
                         } else {
-                            None
-                        };
-                        ListItem::builder()
-                            .title(title.to_string())
-                            .maybe_description(description)
-                            .prefix(ListItemPrefix::Icon {
-                                glyph: egui_phosphor::regular::BOOKMARK_SIMPLE.to_string(),
-                                color: None,
-                            })
-                            .build()
-                    })
-                    .collect();
+                            "No bookmarks match the filter"
+                        })
+                        .build()
+                        .show(ui);
+                    list_events.extend(event.map(|event| (0, event)));
+                }
+                if let Some(edit) = &mut self.editing {
+                    match edit.show(ui) {
+                        EditOutcome::Editing => {}
+                        EditOutcome::Cancelled => self.editing = None,
+                        EditOutcome::Committed(event) => {
+                            events.push(event);
+                            self.editing = None;
+                        }
+                    }
+                    if !after.is_empty() {
+                        let event = List::builder().items(after).build().show(ui);
+                        list_events.extend(event.map(|event| (split + 1, event)));
+                    }
+                }
 
-                if let Some(ListEvent::ItemClicked(item_idx)) = List::builder()
-                    .items(items)
-                    .empty_label("No bookmarks — press Cmd+D to add one")
-                    .build()
-                    .show(ui)
-                    && let Some(b) = props.bookmarks.get(item_idx)
-                {
-                    events.push(BookmarksEvent::NavigateToBookmark {
-                        file_path: b.file_path.clone(),
-                        path: b.path.clone(),
-                    });
+                // List rows map back to bookmarks through `visible`
+                let bookmark_at = |row: usize| {
+                    let index = *visible.get(row)?;
+                    Some((index, props.bookmarks.get(index)?))
+                };
+                for (offset, event) in list_events {
+                    match event {
+                        ListEvent::ItemClicked(i) => {
+                            if let Some((_, b)) = bookmark_at(offset + i) {
+                                events.push(BookmarksEvent::NavigateToBookmark {
+                                    file_path: b.file_path.clone(),
+                                    path: b.path.clone(),
+                                });
+                            }
+                        }
+                        ListEvent::ActionClicked { item, action: 0 } => {
+                            if let Some((index, b)) = bookmark_at(offset + item) {
+                                self.editing = Some(LabelEdit::new(index, b));
+                            }
+                        }
+                        _ => {}
+                    }
                 }
             });
 
@@ -150,6 +567,73 @@ mod tests {
         assert!(format!("{:?}", event).contains("JumpToPath"));
     }
 
+    #[test]
+    fn test_bookmark_item_shows_label_with_path() {
+        let mut bookmark = Bookmark {
+            path: "0.user.email".to_string(),
+            file_path: "/data/users.json".to_string(),
+            label: None,
+            created_at: 0,
+        };
+        let item = bookmark_item(&bookmark, false);
+        assert_eq!(item.title, "0.user.email");
+        assert_eq!(item.description, None);
+
+        bookmark.label = Some("Email".to_string());
+        let item = bookmark_item(&bookmark, false);
+        assert_eq!(item.title, "Email");
+        assert_eq!(item.description.as_deref(), Some("0.user.email"));
+
+        let item = bookmark_item(&bookmark, true);
+        assert_eq!(
+            item.description.as_deref(),
+            Some("0.user.email · users.json")
+        );
+    }
+
+    fn bookmark(path: &str, file_path: &str, label: Option<&str>, created_at: u64) -> Bookmark {
+        Bookmark {
+            path: path.to_string(),
+            file_path: file_path.to_string(),
+            label: label.map(str::to_string),
+            created_at,
+        }
+    }
+
+    #[test]
+    fn test_visible_bookmarks_filter() {
+        let bookmarks = [
+            bookmark("0.user.email", "/data/users.json", None, 1),
+            bookmark("3.total", "/data/Orders.ndjson", Some("Big order"), 2),
+            bookmark("1.name", "/logs/app.ndjson", Some("User name"), 3),
+        ];
+        let filter = |text| visible_bookmarks(&bookmarks, text, BookmarkSort::Created);
+        assert_eq!(filter(""), [0, 1, 2]);
+        // Path, label and file name all match, ignoring case
+        assert_eq!(filter("USER"), [0, 2]);
+        assert_eq!(filter("orders"), [1]);
+        assert_eq!(filter(" big "), [1]);
+        // The directory isn't part of the file name
+        assert!(filter("logs").is_empty());
+    }
+
+    #[test]
+    fn test_visible_bookmarks_sort() {
+        let bookmarks = [
+            bookmark("2.b", "/x/b.json", None, 30),
+            bookmark("1.a", "/x/a.json", None, 20),
+            bookmark("0.c", "/x/b.json", None, 10),
+        ];
+        let sorted = |sort| visible_bookmarks(&bookmarks, "", sort);
+        assert_eq!(sorted(BookmarkSort::Created), [2, 1, 0]);
+        assert_eq!(sorted(BookmarkSort::File), [1, 2, 0]);
+        assert_eq!(sorted(BookmarkSort::Path), [2, 1, 0]);
+        assert_eq!(
+            visible_bookmarks(&bookmarks, "b.json", BookmarkSort::Path),
+            [2, 0]
+        );
+    }
+
     #[test]
     fn test_bookmarks_event_clone() {
         let event = BookmarksEvent::JumpToPath("test".to_string());
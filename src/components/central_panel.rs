@@ -1,12 +1,22 @@
 use crate::components::file_viewer::FileViewer;
+use crate::components::file_viewer::sort::RootSort;
 use crate::components::traits::ContextComponent;
 use crate::error::{ErrorHandler, ThothError};
+use crate::file::detect_file_type::{DetectedFileType, Detection};
 use crate::file::loaders::FileKind;
+use crate::file::open_progress::OpenProgress;
+use crate::file::schema::{SchemaValidation, SchemaValidator};
+use crate::file::watcher::FileWatcher;
+use crate::helpers::{CacheStats, NumberFormat, PathCompletion, PathFormat, format_bytes};
 use crate::plugin::render_node::{UiEvent, UiNode, UiOutput, render_ui_node};
 use crate::search;
+use crate::settings::IndentGuides;
+use crate::state::FileSession;
+use crate::theme::ThemeColors;
 use eframe::egui;
-use std::path::PathBuf;
-use thoth_plugin_sdk::components::Separator;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thoth_plugin_sdk::components::{Separator, Typography};
 
 /// Props passed down to the CentralPanel (immutable, one-way binding)
 pub struct CentralPanelProps<'a> {
@@ -16,6 +26,31 @@ pub struct CentralPanelProps<'a> {
     pub search_message: Option<search::SearchMessage>,
     pub cache_size: usize,
     pub syntax_highlighting: bool,
+    /// Width of one tree indentation level, in pixels
+    pub indent_size: f32,
+    /// How tree indent guide lines are drawn
+    pub indent_guides: IndentGuides,
+    /// Rows kept between the selection and the view's edge while navigating
+    pub scroll_margin: usize,
+    /// Tree depth opened automatically when a file is opened (0 keeps it collapsed)
+    pub auto_expand_depth: usize,
+    /// Reload the open file when it changes on disk
+    pub auto_reload: bool,
+    /// Files larger than this wait for the user to confirm before loading
+    pub max_file_size_mb: u64,
+    /// Show value type badges at the end of tree rows
+    pub show_type_badges: bool,
+    /// Allow editing primitive values in place
+    pub inline_editing: bool,
+    /// Presentation mode, which shows files read-only
+    pub presentation: bool,
+    /// How numeric leaf values are displayed
+    pub number_format: NumberFormat,
+    /// Field-name patterns for numeric timestamps; `None` when timestamps
+    /// aren't humanized
+    pub timestamp_fields: Option<&'a [String]>,
+    /// JSON Schema the root records are validated against
+    pub schema: Option<&'a Arc<SchemaValidator>>,
     /// When `Some`, render this interactive `UiNode` tree from the plugin instead of the file viewer.
     pub plugin_ui: Option<&'a UiOutput>,
     /// Recent files passed down for the Welcome screen shown on empty tabs.
@@ -32,8 +67,32 @@ pub enum CentralPanelEvent {
         total_items: usize,
     },
     FileOpenError(ThothError),
-    FileClosed,
+    /// The file to open exceeds `performance.max_file_size_mb`; it is only
+    /// loaded once [`CentralPanel::confirm_large_file`] is called
+    FileTooLarge {
+        path: PathBuf,
+        size_bytes: u64,
+    },
+    /// The tab asked for another file (or none) while the open one has
+    /// unsaved edits; it stays open until they are saved or discarded with
+    /// [`CentralPanel::save_edits`] / [`CentralPanel::discard_edits`]
+    UnsavedEdits,
+    /// The file at this path was closed, leaving the tab empty
+    FileClosed(PathBuf),
+    /// The user canceled opening a file; the tab should go back to the file
+    /// still shown, if any
+    OpenCanceled {
+        previous: Option<(PathBuf, FileKind)>,
+    },
     FileTypeChanged(FileKind),
+    /// The open file changed on disk and was reloaded in place
+    FileReloaded {
+        file_type: FileKind,
+        total_items: usize,
+    },
+    /// The open file changed on disk but could not be reloaded; the previous
+    /// contents are still shown
+    FileReloadError(ThothError),
     ErrorCleared,
     /// A widget interaction from the active plugin pane — forward to the loader.
     PluginUiEvent(UiEvent),
@@ -41,6 +100,8 @@ pub enum CentralPanelEvent {
     OpenFilePicker,
     /// User clicked a recent file on the Welcome screen.
     OpenRecentFile(PathBuf),
+    /// Text copied from the viewer's context menu, to put on the clipboard.
+    CopyText(String),
 }
 
 pub struct CentralPanelOutput {
@@ -53,7 +114,25 @@ pub struct CentralPanel {
     loaded_path: Option<PathBuf>,
     loaded_type: Option<FileKind>,
     last_open_err: Option<ThothError>,
-    searching: bool,
+    /// Watches `loaded_path` for external changes while auto-reload is on
+    watcher: Option<FileWatcher>,
+    /// Path the watcher was last set up for, so a failed watch isn't retried every frame
+    watched_path: Option<PathBuf>,
+    /// Over-limit file the user agreed to open
+    large_file_confirmed: Option<PathBuf>,
+    /// Over-limit file a `FileTooLarge` event was sent for, so it's only sent once
+    awaiting_confirmation: Option<PathBuf>,
+    /// File that failed to open, so it isn't retried every frame while the
+    /// tab still shows its error
+    failed_path: Option<PathBuf>,
+    /// Type the tab asked for when the file being opened was started
+    opening_type: FileKind,
+    /// An `UnsavedEdits` event was sent for the file the tab asks for, so
+    /// it's only sent once
+    awaiting_unsaved: bool,
+    /// The watched file changed while it had unsaved edits; it is reloaded
+    /// once they are saved or discarded
+    reload_pending: bool,
 }
 
 impl ContextComponent for CentralPanel {
@@ -77,69 +156,71 @@ impl CentralPanel {
         props: CentralPanelProps<'_>,
         events: &mut Vec<CentralPanelEvent>,
     ) {
+        if self.awaiting_confirmation.as_ref() != props.file_path.as_ref() {
+            self.awaiting_confirmation = None;
+        }
+        if self.failed_path.as_ref() != props.file_path.as_ref() {
+            self.failed_path = None;
+        }
+        self.poll_open(events);
+
         // Open / close viewer once on change
         match (props.file_path, self.loaded_path.as_ref(), self.loaded_type) {
             (Some(new_path), Some(curr_path), Some(curr_ty))
                 if curr_path == new_path && curr_ty == props.file_type =>
             {
-                // no change
+                // no change; the tab went back to the open file
+                self.file_viewer.cancel_open();
+                self.awaiting_unsaved = false;
+            }
+            (_, Some(_), _) if self.file_viewer.unsaved_edits() > 0 => {
+                // Keep the edited file until its edits are saved or discarded
+                if !self.awaiting_unsaved {
+                    self.awaiting_unsaved = true;
+                    events.push(CentralPanelEvent::UnsavedEdits);
+                }
+            }
+            (Some(new_path), _, _)
+                if (self.failed_path.as_ref() == Some(new_path) && props.error.is_some())
+                    || self
+                        .file_viewer
+                        .opening()
+                        .is_some_and(|(path, _)| path == new_path) =>
+            {
+                // Failed to open, or still opening in the background
+            }
+            (Some(new_path), _, _)
+                if !self.within_size_limit(new_path, props.max_file_size_mb, events) =>
+            {
+                // Waiting for the user to confirm opening an over-limit file
             }
             (Some(new_path), _, _) => {
                 self.last_open_err = None;
-                let mut file_type = props.file_type;
-                match self.file_viewer.open(new_path, &mut file_type) {
-                    Ok(()) => {
-                        self.loaded_path = Some(new_path.clone());
-                        self.loaded_type = Some(file_type);
-                        let total_items = self.file_viewer.total_item_count();
-                        events.push(CentralPanelEvent::FileOpened {
-                            path: new_path.clone(),
-                            file_type,
-                            total_items,
-                        });
-                        events.push(CentralPanelEvent::ErrorCleared);
-                        // clear any prior search filter on new file
-                        self.file_viewer.set_root_filter(None);
-
-                        // Emit event if file type changed during opening
-                        if file_type != props.file_type {
-                            events.push(CentralPanelEvent::FileTypeChanged(file_type));
-                        }
-                    }
-                    Err(e) => {
-                        // Use the error as-is if it's already a ThothError variant,
-                        // otherwise wrap it appropriately
-                        let error = match &e {
-                            ThothError::FileNotFound { .. }
-                            | ThothError::FileReadError { .. }
-                            | ThothError::InvalidFileType { .. }
-                            | ThothError::JsonParseError { .. } => e,
-                            _ => ThothError::FileReadError {
-                                path: new_path.to_path_buf(),
-                                reason: e.to_string(),
-                            },
-                        };
-                        self.last_open_err = Some(error.clone());
-                        events.push(CentralPanelEvent::FileOpenError(error));
-                        self.loaded_path = None;
-                        self.loaded_type = None;
-                    }
-                }
+                self.opening_type = props.file_type;
+                self.file_viewer
+                    .set_auto_expand_depth(props.auto_expand_depth);
+                let limit_mb = self.decompress_limit_mb(new_path, props.max_file_size_mb);
+                self.file_viewer.set_max_file_size_mb(limit_mb);
+                self.file_viewer.begin_open(new_path, ui.ctx());
             }
             (None, Some(_), _) => {
                 self.file_viewer = FileViewer::with_cache_size(props.cache_size);
-                self.loaded_path = None;
                 self.loaded_type = None;
                 self.last_open_err = None;
-                events.push(CentralPanelEvent::FileClosed);
+                if let Some(path) = self.loaded_path.take() {
+                    events.push(CentralPanelEvent::FileClosed(path));
+                }
+            }
+            (None, None, _) => {
+                // Nothing selected; stop opening a file the tab no longer shows
+                self.file_viewer.cancel_open();
             }
-            (None, None, _) => { /* nothing selected */ }
         }
 
+        self.poll_file_watcher(ui.ctx(), props.auto_reload, events);
+
         // React to search messages
         if let Some(msg) = props.search_message {
-            self.searching = msg.is_searching();
-
             match msg {
                 search::SearchMessage::StartSearch(search) => {
                     self.file_viewer.set_highlights(Some(&search.results));
@@ -148,8 +229,7 @@ impl CentralPanel {
                     // Users can click on search results to navigate to them
                 }
                 search::SearchMessage::StopSearch => {
-                    // No filtering to clear
-                    self.file_viewer.set_highlights(None);
+                    // A cancelled scan keeps highlighting the hits it found
                 }
             }
         }
@@ -168,19 +248,10 @@ impl CentralPanel {
                 // Show any error (either from props or open attempt)
                 if let Some(err) = props.error.as_ref().or(self.last_open_err.as_ref()) {
                     let message = ErrorHandler::get_user_message(err);
-                    ui.colored_label(egui::Color32::RED, message);
+                    ui.colored_label(ThemeColors::from_ctx(ui.ctx()).error, message);
                     ui.add(Separator::plain());
                 }
 
-                if self.searching {
-                    ui.horizontal(|ui| {
-                        ui.add(egui::Spinner::new().size(16.0));
-                        ui.label("Searching…");
-                    });
-                    ui.add_space(6.0);
-                    return;
-                }
-
                 // Plugin pane takes priority over the file viewer.
                 if let Some(output) = props.plugin_ui {
                     match serde_json::from_str::<UiNode>(&output.node_json) {
@@ -193,7 +264,7 @@ impl CentralPanel {
                         }
                         Err(e) => {
                             ui.colored_label(
-                                egui::Color32::RED,
+                                ThemeColors::from_ctx(ui.ctx()).error,
                                 format!("Plugin UI parse error: {e}"),
                             );
                         }
@@ -201,6 +272,13 @@ impl CentralPanel {
                     return;
                 }
 
+                if let Some((path, progress)) = self.file_viewer.opening() {
+                    if Self::render_opening(ui, path, progress) {
+                        self.cancel_open(events);
+                    }
+                    return;
+                }
+
                 if self.loaded_path.is_none() {
                     use crate::components::welcome::{WelcomeEvent, WelcomePanel};
                     let welcome_events = WelcomePanel::render(ui, props.recent_files, props.colors);
@@ -220,12 +298,176 @@ impl CentralPanel {
                 // Update viewer settings right before rendering (so changes apply immediately)
                 self.file_viewer
                     .set_syntax_highlighting(props.syntax_highlighting);
+                self.file_viewer.set_indent_size(props.indent_size);
+                self.file_viewer.set_indent_guides(props.indent_guides);
+                self.file_viewer.set_scroll_margin(props.scroll_margin);
+                self.file_viewer
+                    .set_show_type_badges(props.show_type_badges);
+                self.file_viewer.set_inline_editing(props.inline_editing);
+                self.file_viewer.set_read_only(props.presentation);
+                if let Some(path) = self.loaded_path.clone() {
+                    let limit_mb = self.decompress_limit_mb(&path, props.max_file_size_mb);
+                    self.file_viewer.set_max_file_size_mb(limit_mb);
+                }
+                self.file_viewer.set_number_format(props.number_format);
+                self.file_viewer
+                    .set_timestamp_fields(props.timestamp_fields);
+                self.file_viewer.set_schema(props.schema.cloned());
 
                 // Render the viewer (no filtering UI needed - search results shown in sidebar)
                 self.file_viewer.ui(ui);
+                if let Some(text) = self.file_viewer.take_copy_request() {
+                    events.push(CentralPanelEvent::CopyText(text));
+                    // The app copies it at the start of the next frame
+                    ui.ctx().request_repaint();
+                }
             });
     }
 
+    /// Take in the file opened in the background once it has loaded
+    fn poll_open(&mut self, events: &mut Vec<CentralPanelEvent>) {
+        let Some(path) = self
+            .file_viewer
+            .opening()
+            .map(|(path, _)| path.to_path_buf())
+        else {
+            return;
+        };
+        let mut file_type = self.opening_type;
+        match self.file_viewer.poll_open(&mut file_type) {
+            None => {}
+            Some(Ok(())) => {
+                self.loaded_path = Some(path.clone());
+                self.loaded_type = Some(file_type);
+                let total_items = self.file_viewer.total_item_count();
+                events.push(CentralPanelEvent::FileOpened {
+                    path,
+                    file_type,
+                    total_items,
+                });
+                events.push(CentralPanelEvent::ErrorCleared);
+                // clear any prior search filter on new file
+                self.file_viewer.set_root_filter(None);
+
+                // Emit event if file type changed during opening
+                if file_type != self.opening_type {
+                    events.push(CentralPanelEvent::FileTypeChanged(file_type));
+                }
+            }
+            // Canceled by the user, who already got the previous file back
+            Some(Err(ThothError::OpenCanceled { .. })) => {}
+            Some(Err(e)) => {
+                // Use the error as-is if it's already a ThothError variant,
+                // otherwise wrap it appropriately
+                let error = match &e {
+                    ThothError::FileNotFound { .. }
+                    | ThothError::FileReadError { .. }
+                    | ThothError::InvalidFileType { .. }
+                    | ThothError::FileTooLarge { .. }
+                    | ThothError::UnsupportedFormat { .. }
+                    | ThothError::JsonParseError { .. } => e,
+                    _ => ThothError::FileReadError {
+                        path: path.clone(),
+                        reason: e.to_string(),
+                    },
+                };
+                self.last_open_err = Some(error.clone());
+                events.push(CentralPanelEvent::FileOpenError(error));
+                self.failed_path = Some(path);
+                self.loaded_path = None;
+                self.loaded_type = None;
+            }
+        }
+    }
+
+    /// Stop opening the file in the background and go back to the file
+    /// still shown, if any
+    fn cancel_open(&mut self, events: &mut Vec<CentralPanelEvent>) {
+        if self.file_viewer.cancel_open() {
+            let previous = self.loaded_path.clone().zip(self.loaded_type);
+            events.push(CentralPanelEvent::OpenCanceled { previous });
+        }
+    }
+
+    /// Show how far opening `path` got; returns true if Cancel was clicked
+    fn render_opening(ui: &mut egui::Ui, path: &Path, progress: &OpenProgress) -> bool {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let mut cancel = false;
+        ui.vertical_centered(|ui| {
+            ui.add_space(ui.available_height() * 0.35);
+            Typography::heading(ui, &format!("Opening {}", file_name));
+            ui.add_space(8.0);
+            let bar = egui::ProgressBar::new(progress.fraction().unwrap_or(0.0))
+                .desired_width(320.0)
+                .animate(progress.fraction().is_none());
+            let bar = match progress.bytes() {
+                (scanned, Some(total)) => bar.text(format!(
+                    "Indexing… {} of {}",
+                    format_bytes(scanned),
+                    format_bytes(total)
+                )),
+                (_, None) => bar.text("Reading…"),
+            };
+            ui.add(bar);
+            ui.add_space(8.0);
+            if ui.button("Cancel").clicked() {
+                cancel = true;
+            }
+        });
+        // Progress arrives from another thread without waking the UI
+        ui.ctx()
+            .request_repaint_after(std::time::Duration::from_millis(100));
+        cancel
+    }
+
+    /// Reload the open file once a burst of external changes has settled
+    fn poll_file_watcher(
+        &mut self,
+        ctx: &egui::Context,
+        auto_reload: bool,
+        events: &mut Vec<CentralPanelEvent>,
+    ) {
+        let path = self.loaded_path.as_ref().filter(|_| auto_reload);
+        if path != self.watched_path.as_ref() {
+            self.watched_path = path.cloned();
+            self.watcher = path.and_then(|path| Self::watch(ctx, path));
+        }
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+
+        if watcher.poll() {
+            self.reload_pending = true;
+        } else if let Some(delay) = watcher.pending_delay() {
+            // Come back once the pending change has settled, even if idle
+            ctx.request_repaint_after(delay);
+        }
+        // Reloading would throw away values edited in place
+        if !self.reload_pending || self.file_viewer.unsaved_edits() > 0 {
+            return;
+        }
+        self.reload_pending = false;
+
+        let mut file_type = self.loaded_type.unwrap_or_default();
+        match self.file_viewer.reload(&mut file_type) {
+            Ok(()) => {
+                self.loaded_type = Some(file_type);
+                events.push(CentralPanelEvent::FileReloaded {
+                    file_type,
+                    total_items: self.file_viewer.total_item_count(),
+                });
+            }
+            Err(e) => events.push(CentralPanelEvent::FileReloadError(e)),
+        }
+    }
+
+    fn watch(ctx: &egui::Context, path: &Path) -> Option<FileWatcher> {
+        let ctx = ctx.clone();
+        FileWatcher::new(path, move || ctx.request_repaint())
+            .map_err(|e| eprintln!("Failed to watch {}: {}", path.display(), e))
+            .ok()
+    }
+
     // ========================================================================
     // Keyboard Shortcut Support - Wrapper methods
     // ========================================================================
@@ -245,11 +487,50 @@ impl CentralPanel {
         self.file_viewer.expand_all_nodes();
     }
 
+    /// Whether a file is being opened in the background
+    pub fn is_opening(&self) -> bool {
+        self.file_viewer.opening().is_some()
+    }
+
+    /// Whether an expand-all is still in progress
+    pub fn is_expanding_all(&mut self) -> bool {
+        self.file_viewer.is_expanding_all()
+    }
+
+    /// Clear the tree's row filter (for keyboard shortcuts)
+    /// Returns true if there was one to clear
+    pub fn clear_tree_filter(&mut self) -> bool {
+        self.file_viewer.clear_tree_filter()
+    }
+
+    /// Abort an in-progress expand-all (for keyboard shortcuts)
+    /// Returns true if a job was cancelled
+    pub fn cancel_expand_all(&mut self) -> bool {
+        self.file_viewer.cancel_expand_all()
+    }
+
     /// Collapse all nodes in the tree (for keyboard shortcuts)
     pub fn collapse_all_nodes(&mut self) {
         self.file_viewer.collapse_all_nodes();
     }
 
+    /// Switch between the tree and the flat `path: value` view (for keyboard shortcuts)
+    pub fn toggle_flat_view(&mut self) {
+        self.file_viewer.toggle_flat_view();
+    }
+
+    /// Open the whole tree one level deeper than its deepest open node (for keyboard shortcuts)
+    pub fn expand_one_level(&mut self) {
+        let depth = self.file_viewer.expanded_depth() + 1;
+        self.file_viewer.expand_to_depth(depth);
+    }
+
+    /// Close the deepest open level of the tree (for keyboard shortcuts)
+    pub fn collapse_one_level(&mut self) {
+        let depth = self.file_viewer.expanded_depth().saturating_sub(1);
+        self.file_viewer.collapse_to_depth(depth);
+    }
+
     /// Move selection up to previous item (for keyboard shortcuts)
     pub fn move_selection_up(&mut self) {
         self.file_viewer.move_selection_up();
@@ -260,6 +541,16 @@ impl CentralPanel {
         self.file_viewer.move_selection_down();
     }
 
+    /// Grow or shrink the selected range upwards (for keyboard shortcuts)
+    pub fn extend_selection_up(&mut self) {
+        self.file_viewer.extend_selection_up();
+    }
+
+    /// Grow or shrink the selected range downwards (for keyboard shortcuts)
+    pub fn extend_selection_down(&mut self) {
+        self.file_viewer.extend_selection_down();
+    }
+
     /// Copy the key of the currently selected item (for keyboard shortcuts)
     pub fn copy_selected_key(&mut self) -> Option<String> {
         self.file_viewer.copy_selected_key()
@@ -275,9 +566,94 @@ impl CentralPanel {
         self.file_viewer.copy_selected_object()
     }
 
-    /// Copy the path of the currently selected item (for keyboard shortcuts)
-    pub fn copy_selected_path(&mut self) -> Option<String> {
-        self.file_viewer.copy_selected_path()
+    /// Copy the entire object of the currently selected item as single-line JSON (for keyboard shortcuts)
+    pub fn copy_selected_object_minified(&mut self) -> Option<String> {
+        self.file_viewer.copy_selected_object_minified()
+    }
+
+    /// Copy the path of the currently selected item in `format` (for keyboard shortcuts)
+    pub fn copy_selected_path(&mut self, format: PathFormat) -> Option<String> {
+        self.file_viewer.copy_selected_path(format)
+    }
+
+    /// Copy the source line number of the selected record (for keyboard shortcuts)
+    pub fn copy_selected_line_number(&mut self) -> Option<String> {
+        self.file_viewer.copy_selected_line_number()
+    }
+
+    /// The visible (filtered) records as a pretty-printed JSON array and their count
+    pub fn visible_records_json(&mut self) -> crate::error::Result<(String, usize)> {
+        self.file_viewer.visible_records_json()
+    }
+
+    /// Number of visible (filtered) records; `None` without a loaded file
+    pub fn visible_record_count(&self) -> Option<usize> {
+        self.file_viewer
+            .visible_record_indices()
+            .map(|roots| roots.len())
+    }
+
+    /// Write the visible (filtered) records to `output` as `format`
+    pub fn export_visible_records(
+        &mut self,
+        output: &Path,
+        format: crate::file::export::ExportFormat,
+    ) -> crate::error::Result<usize> {
+        self.file_viewer.export_visible_records(output, format)
+    }
+
+    /// Whether a search filter hides some records
+    pub fn is_filtered(&self) -> bool {
+        self.file_viewer.is_filtered()
+    }
+
+    /// The export format the loaded file stores its records in, if any
+    pub fn export_source_format(&self) -> Option<crate::file::export::ExportFormat> {
+        self.file_viewer.export_source_format()
+    }
+
+    /// Let an over-limit file load; it opens on the next frame
+    pub fn confirm_large_file(&mut self, path: PathBuf) {
+        self.awaiting_confirmation = None;
+        self.large_file_confirmed = Some(path);
+    }
+
+    /// Whether `path` may load under `max_file_size_mb`; a larger file that
+    /// hasn't been confirmed raises `FileTooLarge` once and waits
+    fn within_size_limit(
+        &mut self,
+        path: &Path,
+        max_file_size_mb: u64,
+        events: &mut Vec<CentralPanelEvent>,
+    ) -> bool {
+        if self.large_file_confirmed.as_deref() == Some(path) {
+            return true;
+        }
+        // Missing or unreadable files are left for `open()` to report
+        let Ok(size_bytes) = std::fs::metadata(path).map(|meta| meta.len()) else {
+            return true;
+        };
+        if size_bytes <= max_file_size_mb.saturating_mul(1024 * 1024) {
+            return true;
+        }
+        if self.awaiting_confirmation.as_deref() != Some(path) {
+            self.awaiting_confirmation = Some(path.to_path_buf());
+            events.push(CentralPanelEvent::FileTooLarge {
+                path: path.to_path_buf(),
+                size_bytes,
+            });
+        }
+        false
+    }
+
+    /// Size limit for decompressing `path`; a `.gz` file the user chose to
+    /// open over the limit is decompressed whatever its size
+    fn decompress_limit_mb(&self, path: &Path, max_file_size_mb: u64) -> u64 {
+        if self.large_file_confirmed.as_deref() == Some(path) {
+            u64::MAX
+        } else {
+            max_file_size_mb
+        }
     }
 
     /// Navigate to a specific root record (for search result navigation)
@@ -290,11 +666,102 @@ impl CentralPanel {
         self.file_viewer.navigate_to_path(path);
     }
 
+    /// Parsed-record cache statistics of the open file
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.file_viewer.cache_stats()
+    }
+
+    /// How the open file's type was detected
+    pub fn detection(&self) -> Option<Detection> {
+        self.file_viewer.detection()
+    }
+
+    /// Whether the open file was read as JSONC, with its comments left out of the tree
+    pub fn comments_stripped(&self) -> bool {
+        self.file_viewer.comments_stripped()
+    }
+
+    /// Number of records edited in place and not saved yet
+    pub fn unsaved_edits(&self) -> usize {
+        self.file_viewer.unsaved_edits()
+    }
+
+    /// Write the open file's unsaved edits back to it
+    pub fn save_edits(&mut self) -> crate::error::Result<usize> {
+        let saved = self.file_viewer.save_edits()?;
+        self.awaiting_unsaved = false;
+        Ok(saved)
+    }
+
+    /// Drop the open file's unsaved edits
+    pub fn discard_edits(&mut self) {
+        self.file_viewer.discard_edits();
+        self.awaiting_unsaved = false;
+    }
+
+    /// The file the viewer shows and the kind it was read as, which may
+    /// differ from the one the tab asks for while it's opening or held back
+    /// by unsaved edits
+    pub fn loaded_file(&self) -> Option<(PathBuf, FileKind)> {
+        self.loaded_path.clone().zip(self.loaded_type)
+    }
+
+    /// Re-read the open file as `detected`, returning its new kind and item count
+    pub fn reopen_as(
+        &mut self,
+        detected: DetectedFileType,
+    ) -> crate::error::Result<(FileKind, usize)> {
+        let mut file_type = self.loaded_type.unwrap_or_default();
+        self.file_viewer.read_as(detected, &mut file_type)?;
+        self.loaded_type = Some(file_type);
+        Ok((file_type, self.file_viewer.total_item_count()))
+    }
+
+    /// Suggest row paths continuing a partially typed path
+    pub fn complete_path(&mut self, query: &str) -> Option<PathCompletion> {
+        self.file_viewer.complete_path(query)
+    }
+
+    /// Schema validation progress and violations of the open file
+    pub fn schema_validation(&self) -> Option<&SchemaValidation> {
+        self.file_viewer.schema_validation()
+    }
+
+    /// Common keys and values of the open file's first records, once sampled
+    pub fn facets(&self) -> Option<&crate::search::Facets> {
+        self.file_viewer.facets()
+    }
+
     /// Get the currently selected path (for navigation history tracking)
     pub fn get_selected_path(&self) -> Option<&String> {
         self.file_viewer.get_selected_path()
     }
 
+    /// The open file's tree as currently expanded, as a standalone HTML page
+    pub fn export_html(&mut self, colors: &ThemeColors) -> Option<String> {
+        self.file_viewer.export_html(colors)
+    }
+
+    /// The active root-record sort, if any
+    pub fn sort(&self) -> Option<&RootSort> {
+        self.file_viewer.sort()
+    }
+
+    /// How far the open file's rows are scrolled
+    pub fn scroll_offset(&self) -> egui::Vec2 {
+        self.file_viewer.scroll_offset()
+    }
+
+    /// Reapply the record order, selection and scroll position the file had
+    /// when it was last closed this session
+    pub fn restore_session(&mut self, session: &FileSession) {
+        if let Some(sort) = &session.sort {
+            self.file_viewer.set_sort(&sort.field, sort.ascending);
+        }
+        self.file_viewer
+            .restore_view(session.selected.clone(), session.scroll_offset);
+    }
+
     /// Read this tab's live loader as a tabular dataset for the data bus
     /// (#113). See [`FileViewer::to_dataset`].
     pub fn to_dataset(&mut self) -> Option<crate::file::to_dataset::DatasetTable> {
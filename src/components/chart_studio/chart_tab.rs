@@ -16,6 +16,7 @@ use super::{
     transform,
 };
 use crate::app::tab_manager::TabId;
+use crate::notification::{NotificationManager, Toast};
 use crate::theme::ThemeColors;
 
 /// Rows kept per chart (bounds memory / render cost for huge sources).
@@ -301,6 +302,7 @@ impl ChartTab {
                     .clicked()
                 {
                     ui.ctx().copy_text(self.config_json());
+                    NotificationManager::toast(Toast::success("Chart config copied"));
                 }
                 if ui
                     .add(
@@ -0,0 +1,183 @@
+use eframe::egui;
+
+use crate::components::traits::StatefulComponent;
+use crate::state::ClipboardHistory;
+use crate::theme::ThemeColors;
+use thoth_plugin_sdk::components::{Button, ButtonColor, ButtonType, Typography};
+
+/// Height of one entry row
+const ROW_HEIGHT: f32 = 28.0;
+
+/// Most rows visible before the list scrolls
+const MAX_VISIBLE_ROWS: usize = 12;
+
+/// Characters of an entry shown in its row
+const PREVIEW_CHARS: usize = 80;
+
+/// Popup listing this session's copies; clicking one copies it again
+#[derive(Default)]
+pub struct ClipboardHistoryDialog {
+    pub open: bool,
+}
+
+pub struct ClipboardHistoryProps<'a> {
+    pub history: &'a ClipboardHistory,
+}
+
+/// What the user picked in the dialog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardHistoryAction {
+    /// Put this entry on the clipboard again
+    Copy(String),
+    /// Forget every entry
+    Clear,
+}
+
+impl ClipboardHistoryDialog {
+    pub fn show_dialog(&mut self) {
+        self.open = true;
+    }
+}
+
+impl StatefulComponent for ClipboardHistoryDialog {
+    type Props<'a> = ClipboardHistoryProps<'a>;
+    type Output = Option<ClipboardHistoryAction>;
+
+    fn render(&mut self, ui: &mut egui::Ui, props: Self::Props<'_>) -> Self::Output {
+        if !self.open {
+            return None;
+        }
+        let colors = ThemeColors::from_ctx(ui.ctx());
+
+        let mut action = None;
+        let mut close = false;
+        let modal =
+            egui::Modal::new(egui::Id::new("clipboard_history_modal")).show(ui.ctx(), |ui| {
+                ui.set_width(480.0);
+                Typography::heading(ui, "Clipboard history");
+                ui.add_space(4.0);
+                Typography::body_muted(
+                    ui,
+                    &match props.history.len() {
+                        0 => "Nothing copied yet this session".to_string(),
+                        1 => "1 copy this session. Click it to copy it again".to_string(),
+                        n => format!(
+                            "{} copies this session, newest first. Click one to copy it again",
+                            n
+                        ),
+                    },
+                );
+                ui.add_space(8.0);
+
+                if !props.history.is_empty() {
+                    egui::ScrollArea::vertical()
+                        .id_salt("clipboard_history_scroll")
+                        .max_height(ROW_HEIGHT * MAX_VISIBLE_ROWS as f32)
+                        .auto_shrink([false, true])
+                        .show(ui, |ui| {
+                            for text in props.history.entries() {
+                                let (rect, response) = ui.allocate_exact_size(
+                                    egui::vec2(ui.available_width(), ROW_HEIGHT),
+                                    egui::Sense::click(),
+                                );
+                                if response.hovered() {
+                                    ui.painter().rect_filled(rect, 4.0, colors.surface_active);
+                                }
+                                let inner = rect.shrink2(egui::vec2(8.0, 0.0));
+                                let lines = text.lines().count();
+                                if lines > 1 {
+                                    ui.painter().text(
+                                        inner.right_center(),
+                                        egui::Align2::RIGHT_CENTER,
+                                        format!("{} lines", lines),
+                                        egui::TextStyle::Small.resolve(ui.style()),
+                                        colors.fg_muted,
+                                    );
+                                }
+                                let preview_rect = if lines > 1 {
+                                    inner.with_max_x(inner.right() - 64.0)
+                                } else {
+                                    inner
+                                };
+                                ui.painter().with_clip_rect(preview_rect).text(
+                                    inner.left_center(),
+                                    egui::Align2::LEFT_CENTER,
+                                    entry_preview(text),
+                                    egui::TextStyle::Monospace.resolve(ui.style()),
+                                    colors.fg,
+                                );
+                                if response
+                                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                    .clicked()
+                                {
+                                    action = Some(ClipboardHistoryAction::Copy(text.to_string()));
+                                    close = true;
+                                }
+                            }
+                        });
+                    ui.add_space(12.0);
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .add(
+                            Button::builder()
+                                .label("Close")
+                                .button_type(ButtonType::Elevated)
+                                .color(ButtonColor::Default)
+                                .build(),
+                        )
+                        .clicked()
+                    {
+                        close = true;
+                    }
+                    if !props.history.is_empty()
+                        && ui
+                            .add(
+                                Button::builder()
+                                    .label("Clear")
+                                    .button_type(ButtonType::Text)
+                                    .color(ButtonColor::Default)
+                                    .build(),
+                            )
+                            .clicked()
+                    {
+                        action = Some(ClipboardHistoryAction::Clear);
+                    }
+                });
+            });
+
+        if close || modal.should_close() {
+            self.open = false;
+        }
+        action
+    }
+}
+
+/// One-line preview of a copied text: runs of whitespace (including newlines)
+/// collapse to a space and long text is cut short with an ellipsis
+fn entry_preview(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match collapsed.char_indices().nth(PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}…", &collapsed[..end]),
+        None => collapsed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_preview() {
+        assert_eq!(entry_preview("\"alice\""), "\"alice\"");
+        assert_eq!(
+            entry_preview("{\n  \"a\": 1,\n  \"b\": 2\n}"),
+            "{ \"a\": 1, \"b\": 2 }"
+        );
+        let long = "é".repeat(PREVIEW_CHARS + 10);
+        let preview = entry_preview(&long);
+        assert_eq!(preview.chars().count(), PREVIEW_CHARS + 1);
+        assert!(preview.ends_with('…'));
+    }
+}
@@ -0,0 +1,319 @@
+use eframe::egui;
+
+use crate::app::ShortcutAction;
+use crate::components::sidebar::SidebarSection;
+use crate::components::traits::StatefulComponent;
+use crate::helpers::fuzzy_match;
+use crate::shortcuts::KeyboardShortcuts;
+use crate::theme::ThemeColors;
+use thoth_plugin_sdk::components::{Input, Typography};
+
+/// Height of one command row
+const ROW_HEIGHT: f32 = 28.0;
+
+/// Most rows visible before the list scrolls
+const MAX_VISIBLE_ROWS: usize = 12;
+
+/// VS Code-style command palette: fuzzy-search every action and run it
+#[derive(Default)]
+pub struct CommandPalette {
+    pub open: bool,
+    query: String,
+    /// Position of the highlighted command among the filtered ones
+    selected: usize,
+    focus_input: bool,
+}
+
+pub struct CommandPaletteProps<'a> {
+    /// Shortcuts shown next to the commands bound to one
+    pub shortcuts: &'a KeyboardShortcuts,
+}
+
+/// What running a palette command does
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteAction {
+    /// Dispatched like the matching keyboard shortcut
+    Shortcut(ShortcutAction),
+    /// Open a sidebar section, leaving it open if it already is
+    ShowSection(SidebarSection),
+    LoadSchema,
+    ExportBookmarks,
+    ImportBookmarks,
+    ExportHtml,
+    ExportRecords,
+    ShowClipboardHistory,
+    CheckForUpdates,
+}
+
+/// A palette entry
+struct Command {
+    label: &'static str,
+    action: PaletteAction,
+    /// `KeyboardShortcuts` field of the command's binding, if it has one
+    binding: Option<&'static str>,
+}
+
+/// Every command, in the order listed before anything is typed
+///
+/// Movement, Escape and tab-by-number shortcuts are left out: they only make
+/// sense as keys.
+fn commands() -> Vec<Command> {
+    use PaletteAction::*;
+    use ShortcutAction as S;
+
+    let bound = |label, action, binding| Command {
+        label,
+        action: Shortcut(action),
+        binding: Some(binding),
+    };
+    let unbound = |label, action| Command {
+        label,
+        action,
+        binding: None,
+    };
+
+    vec![
+        bound("Open File…", S::OpenFile, "open_file"),
+        bound("New Window", S::NewWindow, "new_window"),
+        bound("New Tab", S::NewTab, "new_tab"),
+        bound("Close Tab", S::CloseTab, "close_tab"),
+        bound("Next Tab", S::NextTab, "next_tab"),
+        bound("Previous Tab", S::PrevTab, "prev_tab"),
+        bound("Focus Search", S::FocusSearch, "focus_search"),
+        bound("Next Match", S::NextMatch, "next_match"),
+        bound("Previous Match", S::PrevMatch, "prev_match"),
+        bound("Go to Record…", S::GoToRecord, "go_to_record"),
+        bound("Navigate Back", S::NavBack, "nav_back"),
+        bound("Navigate Forward", S::NavForward, "nav_forward"),
+        bound("Toggle Bookmark", S::ToggleBookmark, "toggle_bookmark"),
+        bound("Show Bookmarks", S::OpenBookmarks, "open_bookmarks"),
+        unbound("Export Bookmarks…", ExportBookmarks),
+        unbound("Import Bookmarks…", ImportBookmarks),
+        bound("Expand Node", S::ExpandNode, "expand_node"),
+        bound("Collapse Node", S::CollapseNode, "collapse_node"),
+        bound("Expand All", S::ExpandAll, "expand_all"),
+        bound("Collapse All", S::CollapseAll, "collapse_all"),
+        bound("Expand One Level", S::ExpandToDepth, "expand_to_depth"),
+        bound(
+            "Collapse One Level",
+            S::CollapseToDepth,
+            "collapse_to_depth",
+        ),
+        bound("Toggle Flat View", S::ToggleFlatView, "toggle_flat_view"),
+        unbound("Export View as HTML…", ExportHtml),
+        unbound("Export Visible Records…", ExportRecords),
+        bound("Copy Key", S::CopyKey, "copy_key"),
+        bound("Copy Value", S::CopyValue, "copy_value"),
+        bound("Copy Object", S::CopyObject, "copy_object"),
+        bound("Copy Minified", S::CopyMinified, "copy_minified"),
+        bound("Copy Path", S::CopyPath, "copy_path"),
+        bound("Copy Line Number", S::CopyLineNumber, "copy_line_number"),
+        bound(
+            "Copy Visible Records",
+            S::CopyVisibleRecords,
+            "copy_visible_records",
+        ),
+        unbound("Show Clipboard History", ShowClipboardHistory),
+        unbound(
+            "Show Recent Files",
+            ShowSection(SidebarSection::RecentFiles),
+        ),
+        unbound(
+            "Show Schema Validation",
+            ShowSection(SidebarSection::Schema),
+        ),
+        unbound("Load JSON Schema…", LoadSchema),
+        bound("Open Settings", S::Settings, "settings"),
+        bound("Toggle Theme", S::ToggleTheme, "toggle_theme"),
+        bound("Toggle Sidebar", S::ToggleSidebar, "toggle_sidebar"),
+        bound(
+            "Toggle Presentation Mode",
+            S::TogglePresentation,
+            "toggle_presentation",
+        ),
+        unbound("Check for Updates", CheckForUpdates),
+        #[cfg(feature = "profiling")]
+        bound("Toggle Profiler", S::ToggleProfiler, "toggle_profiler"),
+    ]
+}
+
+impl CommandPalette {
+    /// Open the palette with an empty query and the input focused
+    pub fn show_dialog(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.selected = 0;
+        self.focus_input = true;
+    }
+}
+
+impl StatefulComponent for CommandPalette {
+    type Props<'a> = CommandPaletteProps<'a>;
+    type Output = Option<PaletteAction>;
+
+    fn render(&mut self, ui: &mut egui::Ui, props: Self::Props<'_>) -> Self::Output {
+        if !self.open {
+            return None;
+        }
+        let colors = ThemeColors::from_ctx(ui.ctx());
+        let commands = commands();
+        let matches = filter(&commands, &self.query);
+
+        // Arrow keys move the highlight instead of the text cursor
+        let (up, down) = ui.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+            )
+        });
+        if !matches.is_empty() {
+            if up {
+                self.selected = self.selected.checked_sub(1).unwrap_or(matches.len() - 1);
+            }
+            if down {
+                self.selected = (self.selected + 1) % matches.len();
+            }
+        }
+        self.selected = self.selected.min(matches.len().saturating_sub(1));
+
+        let mut chosen = None;
+        let modal = egui::Modal::new(egui::Id::new("command_palette_modal")).show(ui.ctx(), |ui| {
+            ui.set_width(480.0);
+
+            let mut input = Input::builder()
+                .id("command_palette_input")
+                .value(self.query.clone())
+                .placeholder("Type a command")
+                .icon(egui_phosphor::regular::TERMINAL_WINDOW)
+                .build();
+            let r = input.show(ui);
+            if r.inner {
+                self.query = input.value.clone();
+                self.selected = 0;
+            }
+            if std::mem::take(&mut self.focus_input) {
+                r.response.request_focus();
+            }
+            if r.response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                chosen = matches.get(self.selected).copied();
+            }
+            ui.add_space(8.0);
+
+            if matches.is_empty() {
+                Typography::body_muted(ui, "No matching commands");
+                return;
+            }
+
+            egui::ScrollArea::vertical()
+                .id_salt("command_palette_scroll")
+                .max_height(ROW_HEIGHT * MAX_VISIBLE_ROWS as f32)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for (pos, &idx) in matches.iter().enumerate() {
+                        let command = &commands[idx];
+                        let (rect, response) = ui.allocate_exact_size(
+                            egui::vec2(ui.available_width(), ROW_HEIGHT),
+                            egui::Sense::click(),
+                        );
+                        if response.hovered() && ui.input(|i| i.pointer.delta() != egui::Vec2::ZERO)
+                        {
+                            self.selected = pos;
+                        }
+                        if pos == self.selected {
+                            ui.painter().rect_filled(rect, 4.0, colors.surface_active);
+                            if up || down {
+                                response.scroll_to_me(None);
+                            }
+                        }
+                        if response.clicked() {
+                            chosen = Some(idx);
+                        }
+
+                        let inner = rect.shrink2(egui::vec2(8.0, 0.0));
+                        ui.painter().text(
+                            inner.left_center(),
+                            egui::Align2::LEFT_CENTER,
+                            command.label,
+                            egui::TextStyle::Body.resolve(ui.style()),
+                            colors.fg,
+                        );
+                        if let Some(shortcut) = command.binding.and_then(|binding| {
+                            props
+                                .shortcuts
+                                .bindings()
+                                .into_iter()
+                                .find(|(action, _)| *action == binding)
+                                .map(|(_, shortcut)| shortcut.format())
+                        }) {
+                            ui.painter().text(
+                                inner.right_center(),
+                                egui::Align2::RIGHT_CENTER,
+                                shortcut,
+                                egui::TextStyle::Small.resolve(ui.style()),
+                                colors.fg_muted,
+                            );
+                        }
+                    }
+                });
+        });
+
+        if chosen.is_some() || modal.should_close() {
+            self.open = false;
+        }
+        chosen.map(|idx| commands[idx].action.clone())
+    }
+}
+
+/// Indices of the commands matching `query`, best match first
+///
+/// Ties keep the list order, so an empty query lists every command as-is.
+fn filter(commands: &[Command], query: &str) -> Vec<usize> {
+    let mut scored: Vec<(usize, i32)> = commands
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, command)| Some((idx, fuzzy_match(query, command.label, false)?.score)))
+        .collect();
+    scored.sort_by(|a, b| b.1.cmp(&a.1));
+    scored.into_iter().map(|(idx, _)| idx).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(query: &str) -> Vec<&'static str> {
+        let commands = commands();
+        filter(&commands, query)
+            .into_iter()
+            .map(|idx| commands[idx].label)
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_ranks_best_match_first() {
+        assert_eq!(labels("").len(), commands().len());
+        assert_eq!(labels("exa").first(), Some(&"Expand All"));
+        assert_eq!(labels("cp").first(), Some(&"Copy Path"));
+        assert_eq!(labels("cvr").first(), Some(&"Copy Visible Records"));
+        assert_eq!(labels("toggle theme"), ["Toggle Theme"]);
+        assert_eq!(labels("updates"), ["Check for Updates"]);
+        assert!(labels("zzz").is_empty());
+    }
+
+    #[test]
+    fn test_bindings_exist() {
+        let shortcuts = KeyboardShortcuts::default();
+        for command in commands() {
+            if let Some(binding) = command.binding {
+                assert!(
+                    shortcuts
+                        .bindings()
+                        .iter()
+                        .any(|(action, _)| *action == binding),
+                    "{}",
+                    binding
+                );
+            }
+        }
+    }
+}
@@ -1,11 +1,22 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::components::file_viewer::is_supported_file;
+use crate::components::toolbar::infer_file_type_pub;
+use crate::file::lazy_loader::FileKind;
+use crate::notification::{Notification, NotificationKind, NotificationManager};
 use crate::{app, file::detect_file_type::sniff_file_type};
 use eframe::egui;
 
+/// How long drop warnings stay on screen
+const DROP_TOAST_DURATION: Duration = Duration::from_secs(5);
+
 impl app::ThothApp {
     pub fn handle_file_drop(&mut self, ctx: &egui::Context) {
         let hovering_files = ctx.input(|i| i.raw.hovered_files.clone());
         if !hovering_files.is_empty() {
-            let mut text = String::from("Drop file to open:\n");
+            let mut text = String::from("Drop files to open:\n");
             for file in &hovering_files {
                 if let Some(path) = &file.path {
                     use std::fmt::Write as _;
@@ -45,33 +56,157 @@ impl app::ThothApp {
             );
         }
 
-        let dropped_files = ctx.input(|i| i.raw.dropped_files.clone());
-        if !dropped_files.is_empty() {
-            let nav_capacity = self.settings.performance.navigation_history_size;
-            for file in dropped_files {
-                if let Some(path) = file.path {
-                    match sniff_file_type(&path) {
-                        Ok(detected) => {
-                            use crate::file::lazy_loader::FileKind;
-                            let ft: FileKind = detected.into();
-                            let id = self.window_state.tab_manager.open_file(path, nav_capacity);
-                            if let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&id) {
-                                tab.file_type = ft;
-                                tab.error = None;
-                                self.window_state.toolbar.previous_file_type = ft;
-                            }
-                        }
-                        Err(_) => {
-                            if let Some(tab) = self.window_state.tab_manager.active_tab_mut() {
-                                tab.error = Some(crate::error::ThothError::InvalidFileType {
-                                    path: path.clone(),
-                                    expected: "JSON or NDJSON".to_string(),
-                                });
-                            }
-                        }
+        let dropped: Vec<PathBuf> = ctx.input(|i| {
+            i.raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .collect()
+        });
+        if !dropped.is_empty() {
+            self.open_external_paths(dropped);
+        }
+    }
+
+    /// Open every supported file dropped on the window or sent by the OS
+    /// ("Open With", double-click) in its own tab, focusing the first
+    ///
+    /// Unsupported files are skipped with a toast. A folder isn't opened
+    /// itself; instead a toast offers its first supported file.
+    pub(crate) fn open_external_paths(&mut self, paths: Vec<PathBuf>) {
+        if self.opening_blocked() {
+            return;
+        }
+        let nav_capacity = self.settings.performance.navigation_history_size;
+        let mut first_opened = None;
+        let mut rejected = Vec::new();
+
+        for path in paths {
+            if path.is_dir() {
+                offer_folder_file(&path);
+                continue;
+            }
+            if !is_supported_file(&path) {
+                rejected.push(display_name(&path));
+                continue;
+            }
+
+            if let Some(path_str) = path.to_str() {
+                self.persistent_state.add_recent_file(
+                    path_str.to_string(),
+                    self.settings.performance.max_recent_files,
+                );
+            }
+
+            // A file that is already open just gets its tab focused
+            let id = match self.window_state.tab_manager.find_file_tab(&path) {
+                Some(id) => id,
+                None => {
+                    let file_type = sniff_file_type(&path)
+                        .map(FileKind::from)
+                        .ok()
+                        .or_else(|| infer_file_type_pub(&path))
+                        .unwrap_or_default();
+                    let id = self.window_state.tab_manager.open_file(path, nav_capacity);
+                    if let Some(tab) = self.window_state.tab_manager.tabs.get_mut(&id) {
+                        tab.file_type = file_type;
+                        tab.error = None;
+                        self.window_state.toolbar.previous_file_type = file_type;
                     }
+                    id
                 }
+            };
+            first_opened.get_or_insert(id);
+        }
+
+        if let Some(id) = first_opened {
+            self.window_state.tab_manager.focus_tab(id);
+            if let Err(e) = self.persistent_state.save() {
+                eprintln!("Failed to save recent files: {}", e);
             }
         }
+
+        if !rejected.is_empty() {
+            NotificationManager::notify(
+                Notification::new(
+                    if rejected.len() == 1 {
+                        "Unsupported file"
+                    } else {
+                        "Unsupported files"
+                    },
+                    &format!("Skipped {}", rejected.join(", ")),
+                )
+                .with_kind(NotificationKind::Warn)
+                .with_expiration(DROP_TOAST_DURATION),
+            );
+        }
+    }
+}
+
+/// Offer to open the first supported file in a dropped folder
+fn offer_folder_file(dir: &Path) {
+    let folder = display_name(dir);
+    let Some(file) = first_supported_file(dir) else {
+        NotificationManager::notify(
+            Notification::new(
+                "Nothing to open",
+                &format!("{} has no supported files", folder),
+            )
+            .with_kind(NotificationKind::Warn)
+            .with_expiration(DROP_TOAST_DURATION),
+        );
+        return;
+    };
+
+    let name = display_name(&file);
+    NotificationManager::notify(
+        Notification::new("Folder dropped", &format!("Open {} from {}?", name, folder))
+            .with_action(
+                "Open",
+                Arc::new(move || crate::platform::enqueue_open_request(file.clone())),
+            )
+            .with_action("Dismiss", Arc::new(|| {})),
+    );
+}
+
+/// First supported file directly inside `dir`, by name
+fn first_supported_file(dir: &Path) -> Option<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && is_supported_file(path))
+        .collect();
+    files.sort();
+    files.into_iter().next()
+}
+
+fn display_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_supported_file_picks_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["notes.txt", "b.ndjson", "a.json", "image.png"] {
+            std::fs::write(dir.path().join(name), "{}").unwrap();
+        }
+        std::fs::create_dir(dir.path().join("0-nested.json")).unwrap();
+        assert_eq!(
+            first_supported_file(dir.path()),
+            Some(dir.path().join("a.json"))
+        );
+    }
+
+    #[test]
+    fn test_first_supported_file_none_without_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("readme.md"), "# hi").unwrap();
+        assert_eq!(first_supported_file(dir.path()), None);
     }
 }
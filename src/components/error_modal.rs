@@ -1,5 +1,9 @@
+use std::path::Path;
+
 use crate::components::traits::StatefulComponent;
 use crate::error::{ErrorHandler, ErrorRecovery, RecoveryAction, ThothError};
+use crate::notification::{NotificationManager, Toast};
+use crate::theme::ThemeColors;
 use eframe::egui;
 use thoth_plugin_sdk::components::{Button, ButtonColor, ButtonType};
 
@@ -7,6 +11,8 @@ use thoth_plugin_sdk::components::{Button, ButtonColor, ButtonType};
 pub struct ErrorModalProps<'a> {
     pub error: &'a ThothError,
     pub open: bool,
+    /// File open in the tab the error belongs to, included in copied details
+    pub file_path: Option<&'a Path>,
 }
 
 /// Events emitted by the error modal
@@ -62,13 +68,19 @@ impl StatefulComponent for ErrorModal {
                     ui.label(
                         egui::RichText::new("⚠")
                             .size(32.0)
-                            .color(egui::Color32::from_rgb(255, 100, 100)),
+                            .color(ThemeColors::from_ctx(ui.ctx()).error),
                     );
                     ui.add_space(12.0);
                     ui.vertical(|ui| {
                         ui.label(egui::RichText::new("An error occurred").strong().size(16.0));
                         ui.add_space(4.0);
-                        ui.label(user_message);
+                        // Long parse errors scroll; the text can be selected for bug reports
+                        egui::ScrollArea::vertical()
+                            .id_salt("error_modal_message")
+                            .max_height(240.0)
+                            .show(ui, |ui| {
+                                ui.add(egui::Label::new(&user_message).selectable(true));
+                            });
                     });
                 });
 
@@ -137,6 +149,24 @@ impl StatefulComponent for ErrorModal {
                                 recovery_action = Some(RecoveryAction::Reset);
                             }
                         }
+
+                        let copy_btn = ui.add(
+                            Button::builder()
+                                .label(format!(
+                                    "{} Copy error details",
+                                    egui_phosphor::regular::COPY
+                                ))
+                                .button_type(ButtonType::Text)
+                                .color(ButtonColor::Default)
+                                .build(),
+                        );
+                        if copy_btn.clicked() {
+                            ui.ctx().copy_text(ErrorHandler::get_error_details(
+                                props.error,
+                                props.file_path,
+                            ));
+                            NotificationManager::toast(Toast::success("Error details copied"));
+                        }
                     });
                 });
             });
@@ -0,0 +1,135 @@
+use eframe::egui;
+use thoth_plugin_sdk::components::{
+    Button, ButtonColor, ButtonType, Radio, SelectOption, Typography,
+};
+
+use crate::components::traits::StatefulComponent;
+use crate::file::export::ExportFormat;
+
+/// Popup asking which format to write the visible records in before
+/// picking where to save them
+#[derive(Default)]
+pub struct ExportRecordsDialog {
+    pub open: bool,
+    /// Format picked by the user; until then the source's own is offered
+    format: Option<ExportFormat>,
+}
+
+pub struct ExportRecordsProps {
+    /// Records that will be written
+    pub count: usize,
+    /// Whether a search narrows the records down
+    pub filtered: bool,
+    /// The format the source stores its records in, if it is one of the choices
+    pub source_format: Option<ExportFormat>,
+}
+
+impl ExportRecordsDialog {
+    pub fn show_dialog(&mut self) {
+        self.open = true;
+    }
+}
+
+impl StatefulComponent for ExportRecordsDialog {
+    type Props<'a> = ExportRecordsProps;
+    /// The chosen format, once the user clicks Export
+    type Output = Option<ExportFormat>;
+
+    fn render(&mut self, ui: &mut egui::Ui, props: Self::Props<'_>) -> Self::Output {
+        if !self.open {
+            return None;
+        }
+
+        let chosen =
+            |picked: Option<ExportFormat>| picked.or(props.source_format).unwrap_or_default();
+        let mut export = None;
+        let mut close = false;
+        let modal = egui::Modal::new(egui::Id::new("export_records_modal")).show(ui.ctx(), |ui| {
+            ui.set_width(420.0);
+            Typography::heading(ui, "Export records");
+            ui.add_space(4.0);
+            Typography::body_muted(
+                ui,
+                &format!(
+                    "{} {}{}",
+                    props.count,
+                    if props.count == 1 {
+                        "record"
+                    } else {
+                        "records"
+                    },
+                    if props.filtered {
+                        " matching the search"
+                    } else {
+                        ""
+                    }
+                ),
+            );
+            ui.add_space(8.0);
+
+            let mut radio = Radio::builder()
+                .id("export_records_format")
+                .label("Format")
+                .value(chosen(self.format).label())
+                .options(
+                    ExportFormat::ALL
+                        .iter()
+                        .map(|f| {
+                            SelectOption::builder()
+                                .value(f.label())
+                                .label(f.label())
+                                .build()
+                        })
+                        .collect(),
+                )
+                .build();
+            if let Some(label) = radio.show(ui) {
+                self.format = ExportFormat::ALL.into_iter().find(|f| f.label() == label);
+            }
+            let format = chosen(self.format);
+            ui.add_space(4.0);
+            Typography::body_muted(
+                ui,
+                if props.source_format == Some(format) {
+                    "Same as the source: records are copied exactly as stored"
+                } else {
+                    "Records are parsed and written out again"
+                },
+            );
+
+            ui.add_space(12.0);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .add(
+                        Button::builder()
+                            .label("Export…")
+                            .button_type(ButtonType::Elevated)
+                            .color(ButtonColor::Primary)
+                            .build(),
+                    )
+                    .clicked()
+                {
+                    export = Some(format);
+                    close = true;
+                }
+                if ui
+                    .add(
+                        Button::builder()
+                            .label("Cancel")
+                            .button_type(ButtonType::Text)
+                            .color(ButtonColor::Default)
+                            .build(),
+                    )
+                    .clicked()
+                {
+                    close = true;
+                }
+            });
+        });
+
+        if close || modal.should_close() {
+            self.open = false;
+        }
+        export
+    }
+}
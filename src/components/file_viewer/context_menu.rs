@@ -1,7 +1,7 @@
 use eframe::egui::Ui;
 
 use crate::file::loaders::FileType;
-use crate::helpers::{LruCache, get_context_menu_shortcuts};
+use crate::helpers::{LruCache, PathFormat, get_context_menu_shortcuts};
 use thoth_plugin_sdk::components::{Button, ButtonColor, ButtonType};
 
 use serde_json::Value;
@@ -10,10 +10,26 @@ use serde_json::Value;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(clippy::enum_variant_names)]
 pub enum ContextMenuAction {
+    /// Every row of a multi-row selection as `path: value` lines
+    CopySelectedRows,
     CopyKey,
     CopyValue,
+    CopyValueJson,
     CopyObject,
-    CopyPath,
+    CopyMinified,
+    /// The row's path in the given syntax
+    CopyPath(PathFormat),
+    CopyPathValue,
+    CopyLineNumber,
+    CopyTypeScript,
+    ExpandSiblings,
+    CollapseSiblings,
+    ViewFormatted,
+    ViewRawBytes,
+    ToggleFlatView,
+    SortAscending,
+    SortDescending,
+    FieldStats,
 }
 
 /// Configuration for which context menu items should be shown
@@ -21,12 +37,37 @@ pub enum ContextMenuAction {
 pub struct ContextMenuConfig {
     /// Always show Copy Key
     pub show_copy_key: bool,
-    /// Show Copy Value for simple values (not arrays/objects)
+    /// Show Copy Value (raw and JSON) for simple values (not arrays/objects)
     pub show_copy_value: bool,
     /// Show Copy Object for arrays and objects
     pub show_copy_object: bool,
+    /// Show Copy Minified (single-line JSON) for arrays and objects
+    pub show_copy_minified: bool,
     /// Always show Copy Path
     pub show_copy_path: bool,
+    /// Show Copy `path = value` for leaf rows of the flat view
+    pub show_copy_path_value: bool,
+    /// Show Copy Line Number when the loader can map records to source lines
+    pub show_copy_line_number: bool,
+    /// Show Copy as TypeScript for arrays and objects
+    pub show_copy_typescript: bool,
+    /// Show expanding/collapsing the nodes next to this one (non-root rows)
+    pub show_siblings: bool,
+    /// Show viewing the row's root record pretty-printed in a modal
+    pub show_view_formatted: bool,
+    /// Show a hex dump of the row's root record as stored in the file
+    pub show_view_raw_bytes: bool,
+    /// Show switching between the tree and the flat `path: value` view
+    pub show_toggle_flat_view: bool,
+    /// Whether the flat view is active (picks the toggle's label)
+    pub flat_view: bool,
+    /// Show sorting root records by this field (scalar fields inside a record)
+    pub show_sort: bool,
+    /// Show aggregates of this field across root records (scalar fields inside a record)
+    pub show_field_stats: bool,
+    /// Rows selected together with the clicked one; copying them all is
+    /// offered when there are several
+    pub selected_rows: usize,
 }
 
 impl Default for ContextMenuConfig {
@@ -35,7 +76,19 @@ impl Default for ContextMenuConfig {
             show_copy_key: true,
             show_copy_value: false,
             show_copy_object: false,
+            show_copy_minified: false,
             show_copy_path: true,
+            show_copy_path_value: false,
+            show_copy_line_number: false,
+            show_copy_typescript: false,
+            show_siblings: false,
+            show_view_formatted: false,
+            show_view_raw_bytes: false,
+            show_toggle_flat_view: false,
+            flat_view: false,
+            show_sort: false,
+            show_field_stats: false,
+            selected_rows: 0,
         }
     }
 }
@@ -59,7 +112,19 @@ impl ContextMenuConfig {
             show_copy_key: true,
             show_copy_value: show_value_menu,
             show_copy_object: show_object_menu,
+            show_copy_minified: show_object_menu,
             show_copy_path: true,
+            show_copy_path_value: false,
+            show_copy_line_number: false,
+            show_copy_typescript: show_object_menu,
+            show_siblings: false,
+            show_view_formatted: false,
+            show_view_raw_bytes: false,
+            show_toggle_flat_view: false,
+            flat_view: false,
+            show_sort: false,
+            show_field_stats: false,
+            selected_rows: 0,
         }
     }
 }
@@ -77,10 +142,37 @@ pub fn render_context_menu<F>(ui: &mut Ui, config: &ContextMenuConfig, mut on_ac
 where
     F: FnMut(ContextMenuAction),
 {
-    let (copy_key_sc, copy_value_sc, copy_object_sc, copy_path_sc) = get_context_menu_shortcuts();
+    let (
+        copy_key_sc,
+        copy_value_sc,
+        copy_object_sc,
+        copy_minified_sc,
+        copy_path_sc,
+        copy_line_number_sc,
+    ) = get_context_menu_shortcuts();
 
     let mut action_selected = false;
 
+    // Copy Selected Rows (the row is part of a multi-row selection)
+    if config.selected_rows > 1 {
+        let copy_rows_btn = ui.add(
+            Button::builder()
+                .label(format!(
+                    "Copy {} Selected Rows ({})",
+                    config.selected_rows, copy_value_sc
+                ))
+                .button_type(ButtonType::Text)
+                .color(ButtonColor::Default)
+                .build(),
+        );
+        if copy_rows_btn.clicked() {
+            on_action(ContextMenuAction::CopySelectedRows);
+            ui.close();
+            action_selected = true;
+        }
+        ui.separator();
+    }
+
     // Copy Key
     if config.show_copy_key {
         let copy_key_btn = ui.add(
@@ -97,11 +189,11 @@ where
         }
     }
 
-    // Copy Value (only show for simple values)
+    // Copy Value (only show for simple values): raw text, or as a JSON literal
     if config.show_copy_value {
         let copy_value_btn = ui.add(
             Button::builder()
-                .label(format!("Copy Value ({})", copy_value_sc))
+                .label(format!("Copy Value (raw) ({})", copy_value_sc))
                 .button_type(ButtonType::Text)
                 .color(ButtonColor::Default)
                 .build(),
@@ -111,6 +203,19 @@ where
             ui.close();
             action_selected = true;
         }
+
+        let copy_value_json_btn = ui.add(
+            Button::builder()
+                .label("Copy Value (JSON)")
+                .button_type(ButtonType::Text)
+                .color(ButtonColor::Default)
+                .build(),
+        );
+        if copy_value_json_btn.clicked() {
+            on_action(ContextMenuAction::CopyValueJson);
+            ui.close();
+            action_selected = true;
+        }
     }
 
     // Copy Object (only show for bracket values - objects and arrays)
@@ -129,17 +234,203 @@ where
         }
     }
 
-    // Copy Path
+    // Copy Minified (single-line JSON for the same subtree as Copy Object)
+    if config.show_copy_minified {
+        let copy_minified_btn = ui.add(
+            Button::builder()
+                .label(format!("Copy Minified ({})", copy_minified_sc))
+                .button_type(ButtonType::Text)
+                .color(ButtonColor::Default)
+                .build(),
+        );
+        if copy_minified_btn.clicked() {
+            on_action(ContextMenuAction::CopyMinified);
+            ui.close();
+            action_selected = true;
+        }
+    }
+
+    // Copy Path, in each supported syntax
     if config.show_copy_path {
-        let copy_path_btn = ui.add(
+        ui.menu_button(format!("Copy Path ({})", copy_path_sc), |ui| {
+            for format in PathFormat::ALL {
+                let copy_path_btn = ui.add(
+                    Button::builder()
+                        .label(format!("{}  {}", format.label(), format.example()))
+                        .button_type(ButtonType::Text)
+                        .color(ButtonColor::Default)
+                        .build(),
+                );
+                if copy_path_btn.clicked() {
+                    on_action(ContextMenuAction::CopyPath(format));
+                    ui.close();
+                    action_selected = true;
+                }
+            }
+        });
+    }
+
+    // Copy `path = value` (flat view leaves)
+    if config.show_copy_path_value {
+        let copy_path_value_btn = ui.add(
+            Button::builder()
+                .label("Copy Path = Value")
+                .button_type(ButtonType::Text)
+                .color(ButtonColor::Default)
+                .build(),
+        );
+        if copy_path_value_btn.clicked() {
+            on_action(ContextMenuAction::CopyPathValue);
+            ui.close();
+            action_selected = true;
+        }
+    }
+
+    // Copy Line Number (the record's line in the source file)
+    if config.show_copy_line_number {
+        let copy_line_btn = ui.add(
+            Button::builder()
+                .label(format!("Copy Line Number ({})", copy_line_number_sc))
+                .button_type(ButtonType::Text)
+                .color(ButtonColor::Default)
+                .build(),
+        );
+        if copy_line_btn.clicked() {
+            on_action(ContextMenuAction::CopyLineNumber);
+            ui.close();
+            action_selected = true;
+        }
+    }
+
+    // Copy as TypeScript (only show for objects and arrays)
+    if config.show_copy_typescript {
+        let copy_ts_btn = ui.add(
+            Button::builder()
+                .label("Copy as TypeScript")
+                .button_type(ButtonType::Text)
+                .color(ButtonColor::Default)
+                .build(),
+        );
+        if copy_ts_btn.clicked() {
+            on_action(ContextMenuAction::CopyTypeScript);
+            ui.close();
+            action_selected = true;
+        }
+    }
+
+    // Expand or collapse every sibling of this node, leaving the node itself
+    if config.show_siblings {
+        ui.separator();
+        for (label, action) in [
+            ("Expand Siblings", ContextMenuAction::ExpandSiblings),
+            ("Collapse Siblings", ContextMenuAction::CollapseSiblings),
+        ] {
+            let siblings_btn = ui.add(
+                Button::builder()
+                    .label(label)
+                    .button_type(ButtonType::Text)
+                    .color(ButtonColor::Default)
+                    .build(),
+            );
+            if siblings_btn.clicked() {
+                on_action(action);
+                ui.close();
+                action_selected = true;
+            }
+        }
+    }
+
+    // View the whole root record pretty-printed or as raw bytes
+    if config.show_view_formatted || config.show_view_raw_bytes {
+        ui.separator();
+    }
+    if config.show_view_formatted {
+        let view_btn = ui.add(
+            Button::builder()
+                .label("View Formatted")
+                .button_type(ButtonType::Text)
+                .color(ButtonColor::Default)
+                .build(),
+        );
+        if view_btn.clicked() {
+            on_action(ContextMenuAction::ViewFormatted);
+            ui.close();
+            action_selected = true;
+        }
+    }
+
+    if config.show_view_raw_bytes {
+        let raw_btn = ui.add(
+            Button::builder()
+                .label("View Raw Bytes")
+                .button_type(ButtonType::Text)
+                .color(ButtonColor::Default)
+                .build(),
+        );
+        if raw_btn.clicked() {
+            on_action(ContextMenuAction::ViewRawBytes);
+            ui.close();
+            action_selected = true;
+        }
+    }
+
+    // Switch between the tree and the flat view
+    if config.show_toggle_flat_view {
+        if !config.show_view_formatted && !config.show_view_raw_bytes {
+            ui.separator();
+        }
+        let toggle_btn = ui.add(
             Button::builder()
-                .label(format!("Copy Path ({})", copy_path_sc))
+                .label(if config.flat_view {
+                    "Show as Tree"
+                } else {
+                    "Show Flattened"
+                })
                 .button_type(ButtonType::Text)
                 .color(ButtonColor::Default)
                 .build(),
         );
-        if copy_path_btn.clicked() {
-            on_action(ContextMenuAction::CopyPath);
+        if toggle_btn.clicked() {
+            on_action(ContextMenuAction::ToggleFlatView);
+            ui.close();
+            action_selected = true;
+        }
+    }
+
+    // Sort root records by this field, or aggregate it across them
+    if config.show_sort || config.show_field_stats {
+        ui.separator();
+    }
+    if config.show_sort {
+        for (label, action) in [
+            ("Sort Records Ascending", ContextMenuAction::SortAscending),
+            ("Sort Records Descending", ContextMenuAction::SortDescending),
+        ] {
+            let sort_btn = ui.add(
+                Button::builder()
+                    .label(label)
+                    .button_type(ButtonType::Text)
+                    .color(ButtonColor::Default)
+                    .build(),
+            );
+            if sort_btn.clicked() {
+                on_action(action);
+                ui.close();
+                action_selected = true;
+            }
+        }
+    }
+
+    if config.show_field_stats {
+        let stats_btn = ui.add(
+            Button::builder()
+                .label("Stats for This Field")
+                .button_type(ButtonType::Text)
+                .color(ButtonColor::Default)
+                .build(),
+        );
+        if stats_btn.clicked() {
+            on_action(ContextMenuAction::FieldStats);
             ui.close();
             action_selected = true;
         }
@@ -155,7 +446,8 @@ pub trait ContextMenuHandler {
     /// Copy the key of the selected item
     fn copy_selected_key(&self, selected: &Option<String>) -> Option<String>;
 
-    /// Copy the value of the selected item
+    /// Copy the value of the selected item as plain text: strings unquoted
+    /// and unescaped, other values as JSON
     fn copy_selected_value(
         &self,
         selected: &Option<String>,
@@ -163,6 +455,14 @@ pub trait ContextMenuHandler {
         loader: &mut FileType,
     ) -> Option<String>;
 
+    /// Copy the value of the selected item as a JSON literal (strings quoted)
+    fn copy_selected_value_json(
+        &self,
+        selected: &Option<String>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String>;
+
     /// Copy the entire object/array of the selected item
     fn copy_selected_object(
         &self,
@@ -171,8 +471,45 @@ pub trait ContextMenuHandler {
         loader: &mut FileType,
     ) -> Option<String>;
 
-    /// Copy the path of the selected item
-    fn copy_selected_path(&self, selected: &Option<String>) -> Option<String>;
+    /// Copy the entire object/array of the selected item as single-line JSON
+    fn copy_selected_object_minified(
+        &self,
+        selected: &Option<String>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String>;
+
+    /// Copy the path of the selected item written in `format`
+    fn copy_selected_path(
+        &self,
+        selected: &Option<String>,
+        format: PathFormat,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String>;
+
+    /// Copy the selected item as `path = value`, the value as single-line JSON
+    fn copy_selected_path_value(
+        &self,
+        selected: &Option<String>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String>;
+
+    /// Copy the 1-based source line of the selected item's root record
+    fn copy_selected_line_number(
+        &self,
+        selected: &Option<String>,
+        loader: &FileType,
+    ) -> Option<String>;
+
+    /// Copy a TypeScript interface inferred from the selected object/array
+    fn copy_selected_typescript(
+        &self,
+        selected: &Option<String>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String>;
 }
 
 /// Execute a context menu action using a handler
@@ -185,7 +522,7 @@ pub trait ContextMenuHandler {
 /// * `loader` - The lazy file loader
 ///
 /// # Returns
-/// The text to copy to clipboard, or None if the action failed
+/// The text to copy to clipboard, or None if the action failed or doesn't copy
 pub fn execute_context_menu_action(
     action: ContextMenuAction,
     handler: &impl ContextMenuHandler,
@@ -196,7 +533,33 @@ pub fn execute_context_menu_action(
     match action {
         ContextMenuAction::CopyKey => handler.copy_selected_key(selected),
         ContextMenuAction::CopyValue => handler.copy_selected_value(selected, cache, loader),
+        ContextMenuAction::CopyValueJson => {
+            handler.copy_selected_value_json(selected, cache, loader)
+        }
         ContextMenuAction::CopyObject => handler.copy_selected_object(selected, cache, loader),
-        ContextMenuAction::CopyPath => handler.copy_selected_path(selected),
+        ContextMenuAction::CopyMinified => {
+            handler.copy_selected_object_minified(selected, cache, loader)
+        }
+        ContextMenuAction::CopyPath(format) => {
+            handler.copy_selected_path(selected, format, cache, loader)
+        }
+        ContextMenuAction::CopyPathValue => {
+            handler.copy_selected_path_value(selected, cache, loader)
+        }
+        ContextMenuAction::CopyLineNumber => handler.copy_selected_line_number(selected, loader),
+        ContextMenuAction::CopyTypeScript => {
+            handler.copy_selected_typescript(selected, cache, loader)
+        }
+        // Needs the whole selection, so the viewer copies it itself
+        ContextMenuAction::CopySelectedRows => None,
+        // These open a modal or change the view rather than copying anything
+        ContextMenuAction::ExpandSiblings
+        | ContextMenuAction::CollapseSiblings
+        | ContextMenuAction::ViewFormatted
+        | ContextMenuAction::ViewRawBytes
+        | ContextMenuAction::ToggleFlatView
+        | ContextMenuAction::SortAscending
+        | ContextMenuAction::SortDescending
+        | ContextMenuAction::FieldStats => None,
     }
 }
@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+
+use eframe::egui;
+use serde_json::{Number, Value};
+use thoth_plugin_sdk::components::{Button, ButtonColor, ButtonType, Typography};
+
+use crate::constants::{FIELD_STATS_MAX_DISTINCT, FIELD_STATS_TOP_VALUES, SORT_KEY_CHUNK_SIZE};
+use crate::file::loaders::FileType;
+use crate::helpers::{NumberFormat, format_number, lookup_rel};
+
+/// Characters of a value shown in the frequency table
+const VALUE_PREVIEW_CHARS: usize = 60;
+
+/// Aggregates of one field across root records for the "Stats for This
+/// Field" modal
+///
+/// Computed once when the modal opens by reading the records through the
+/// loader in chunks, so only one chunk of parsed values is alive at a time.
+pub struct FieldStats {
+    field: String,
+    /// Whether only the records matching the current search were read
+    filtered: bool,
+    tally: Tally,
+}
+
+/// Running totals while the records are read
+#[derive(Debug, Default)]
+struct Tally {
+    records: usize,
+    /// Records where the field is absent or null, or that failed to parse
+    missing: usize,
+    numbers: Option<NumberSummary>,
+    /// Non-numeric values as JSON text, with how often each occurs
+    counts: HashMap<String, usize>,
+    /// Non-numeric values left out of `counts` once it was full
+    uncounted: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct NumberSummary {
+    count: usize,
+    min: Number,
+    max: Number,
+    sum: f64,
+}
+
+impl FieldStats {
+    /// Aggregate `field` (relative to each root, e.g. `price` or
+    /// `user.items[0].qty`) over `roots`, or over every record when `None`
+    pub fn compute(loader: &mut FileType, field: &str, roots: Option<&[usize]>) -> Self {
+        let mut tally = Tally::default();
+        let mut add = |value: Option<Value>| {
+            tally.add(value.as_ref().and_then(|v| lookup_rel(v, field).ok()));
+        };
+
+        match roots {
+            Some(roots) => {
+                for &i in roots {
+                    add(loader.get(i).ok());
+                }
+            }
+            None => {
+                let total_len = loader.len();
+                let mut start = 0;
+                while start < total_len {
+                    let count = SORT_KEY_CHUNK_SIZE.min(total_len - start);
+                    match loader.get_range(start, count) {
+                        Ok(values) if values.len() == count => {
+                            values.into_iter().for_each(|value| add(Some(value)));
+                        }
+                        // One unparsable record fails the whole chunk; fall back
+                        // to per-record reads so only the broken records are lost
+                        _ => (start..start + count).for_each(|i| add(loader.get(i).ok())),
+                    }
+                    start += count;
+                }
+            }
+        }
+
+        Self {
+            field: field.to_string(),
+            filtered: roots.is_some(),
+            tally,
+        }
+    }
+
+    /// Show the modal; returns false once it has been closed
+    pub fn show(&self, ctx: &egui::Context, number_format: NumberFormat) -> bool {
+        let tally = &self.tally;
+        let mut open = true;
+        let modal = egui::Modal::new(egui::Id::new("field_stats_modal")).show(ctx, |ui| {
+            ui.set_width(480.0);
+
+            ui.horizontal(|ui| {
+                Typography::heading(ui, &format!("Stats for {}", self.field));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let close_btn = ui.add(
+                        Button::builder()
+                            .label("Close")
+                            .button_type(ButtonType::Elevated)
+                            .color(ButtonColor::Default)
+                            .build(),
+                    );
+                    if close_btn.clicked() {
+                        open = false;
+                    }
+                });
+            });
+            Typography::body_muted(
+                ui,
+                &format!(
+                    "{} {}{}, {} without this field",
+                    tally.records,
+                    if tally.records == 1 {
+                        "record"
+                    } else {
+                        "records"
+                    },
+                    if self.filtered {
+                        " matching the search"
+                    } else {
+                        ""
+                    },
+                    tally.missing
+                ),
+            );
+            ui.add_space(8.0);
+
+            if let Some(numbers) = &tally.numbers {
+                let float = |x: f64| {
+                    Number::from_f64(x)
+                        .map_or("-".to_string(), |n| format_number(&n, number_format))
+                };
+                egui::Grid::new("field_stats_numbers")
+                    .num_columns(2)
+                    .spacing([24.0, 4.0])
+                    .show(ui, |ui| {
+                        for (label, value) in [
+                            ("Count", numbers.count.to_string()),
+                            ("Min", format_number(&numbers.min, number_format)),
+                            ("Max", format_number(&numbers.max, number_format)),
+                            ("Sum", float(numbers.sum)),
+                            ("Average", float(numbers.sum / numbers.count as f64)),
+                        ] {
+                            Typography::body_muted(ui, label);
+                            ui.monospace(value);
+                            ui.end_row();
+                        }
+                    });
+                ui.add_space(8.0);
+            }
+
+            if !tally.counts.is_empty() {
+                let distinct = tally.counts.len();
+                let counted: usize = tally.counts.values().sum();
+                Typography::body_muted(
+                    ui,
+                    &format!(
+                        "{}{} distinct {}{}",
+                        if tally.numbers.is_some() {
+                            "Non-numeric values: "
+                        } else {
+                            ""
+                        },
+                        distinct,
+                        if distinct == 1 { "value" } else { "values" },
+                        if distinct > FIELD_STATS_TOP_VALUES {
+                            format!(", top {} shown", FIELD_STATS_TOP_VALUES)
+                        } else {
+                            String::new()
+                        }
+                    ),
+                );
+                if tally.uncounted > 0 {
+                    Typography::body_muted(
+                        ui,
+                        &format!(
+                            "{} more values weren't counted after {} distinct ones",
+                            tally.uncounted, FIELD_STATS_MAX_DISTINCT
+                        ),
+                    );
+                }
+                ui.add_space(4.0);
+                egui::ScrollArea::vertical()
+                    .id_salt("field_stats_scroll")
+                    .max_height(ctx.content_rect().height() * 0.5)
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        egui::Grid::new("field_stats_values")
+                            .num_columns(3)
+                            .spacing([24.0, 4.0])
+                            .show(ui, |ui| {
+                                for (value, count) in tally.top_values(FIELD_STATS_TOP_VALUES) {
+                                    ui.monospace(value_preview(value));
+                                    ui.monospace(count.to_string());
+                                    Typography::body_muted(
+                                        ui,
+                                        &format!("{:.1}%", count as f64 * 100.0 / counted as f64),
+                                    );
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            } else if tally.numbers.is_none() {
+                Typography::body_muted(ui, "No values found");
+            }
+        });
+
+        open && !modal.should_close()
+    }
+}
+
+impl Tally {
+    /// Count one record's value of the field
+    fn add(&mut self, value: Option<&Value>) {
+        self.records += 1;
+        match value {
+            None | Some(Value::Null) => self.missing += 1,
+            Some(Value::Number(n)) => {
+                let x = n.as_f64().unwrap_or(f64::NAN);
+                match &mut self.numbers {
+                    None => {
+                        self.numbers = Some(NumberSummary {
+                            count: 1,
+                            min: n.clone(),
+                            max: n.clone(),
+                            sum: x,
+                        })
+                    }
+                    Some(summary) => {
+                        summary.count += 1;
+                        summary.sum += x;
+                        if x < summary.min.as_f64().unwrap_or(f64::NAN) {
+                            summary.min = n.clone();
+                        }
+                        if x > summary.max.as_f64().unwrap_or(f64::NAN) {
+                            summary.max = n.clone();
+                        }
+                    }
+                }
+            }
+            Some(other) => {
+                let key = other.to_string();
+                if let Some(count) = self.counts.get_mut(&key) {
+                    *count += 1;
+                } else if self.counts.len() < FIELD_STATS_MAX_DISTINCT {
+                    self.counts.insert(key, 1);
+                } else {
+                    self.uncounted += 1;
+                }
+            }
+        }
+    }
+
+    /// The `n` most frequent non-numeric values, ties in value order
+    fn top_values(&self, n: usize) -> Vec<(&str, usize)> {
+        let mut values: Vec<(&str, usize)> = self
+            .counts
+            .iter()
+            .map(|(value, &count)| (value.as_str(), count))
+            .collect();
+        values.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        values.truncate(n);
+        values
+    }
+}
+
+/// A value's JSON text, cut short with an ellipsis when long
+fn value_preview(text: &str) -> String {
+    match text.char_indices().nth(VALUE_PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::loaders::NdjsonFile;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn ndjson_loader(lines: &[&str]) -> FileType {
+        let mut tmp = NamedTempFile::new().unwrap();
+        for line in lines {
+            writeln!(tmp, "{}", line).unwrap();
+        }
+        tmp.flush().unwrap();
+        FileType::Ndjson(NdjsonFile::open(tmp.path()).unwrap())
+    }
+
+    #[test]
+    fn test_numeric_field() {
+        let mut loader = ndjson_loader(&[
+            r#"{"item":{"price":10}}"#,
+            r#"{"item":{"price":2.5}}"#,
+            r#"{"item":{}}"#,
+            r#"{"item":{"price":-4}}"#,
+        ]);
+        let stats = FieldStats::compute(&mut loader, "item.price", None);
+        assert_eq!(stats.tally.records, 4);
+        assert_eq!(stats.tally.missing, 1);
+        assert!(stats.tally.counts.is_empty());
+        let numbers = stats.tally.numbers.unwrap();
+        assert_eq!(numbers.count, 3);
+        assert_eq!(numbers.min, Number::from(-4));
+        assert_eq!(numbers.max, Number::from(10));
+        assert_eq!(numbers.sum, 8.5);
+    }
+
+    #[test]
+    fn test_value_frequencies() {
+        let mut loader = ndjson_loader(&[
+            r#"{"s":"open"}"#,
+            r#"{"s":"closed"}"#,
+            r#"{"s":"open"}"#,
+            r#"{"s":null}"#,
+            r#"{"s":true}"#,
+        ]);
+        let stats = FieldStats::compute(&mut loader, "s", None);
+        assert!(stats.tally.numbers.is_none());
+        assert_eq!(stats.tally.missing, 1);
+        assert_eq!(
+            stats.tally.top_values(2),
+            [("\"open\"", 2), ("\"closed\"", 1)]
+        );
+    }
+
+    #[test]
+    fn test_only_given_roots() {
+        let mut loader = ndjson_loader(&[r#"{"n":1}"#, r#"{"n":2}"#, r#"{"n":3}"#]);
+        let stats = FieldStats::compute(&mut loader, "n", Some(&[0, 2]));
+        assert!(stats.filtered);
+        assert_eq!(stats.tally.records, 2);
+        assert_eq!(stats.tally.numbers.unwrap().sum, 4.0);
+    }
+
+    #[test]
+    fn test_distinct_values_are_capped() {
+        let mut tally = Tally::default();
+        for i in 0..FIELD_STATS_MAX_DISTINCT + 3 {
+            tally.add(Some(&Value::String(i.to_string())));
+        }
+        tally.add(Some(&Value::String("0".to_string())));
+        assert_eq!(tally.counts.len(), FIELD_STATS_MAX_DISTINCT);
+        assert_eq!(tally.uncounted, 3);
+        assert_eq!(tally.top_values(1), [("\"0\"", 2)]);
+    }
+}
@@ -0,0 +1,286 @@
+use std::ops::Range;
+
+use eframe::egui::{self, Ui};
+use serde_json::Value;
+use thoth_plugin_sdk::TextToken;
+use thoth_plugin_sdk::components::{Button, ButtonColor, ButtonType, Typography};
+use thoth_plugin_sdk::theme::TextPalette;
+
+use crate::file::loaders::FileType;
+use crate::notification::{NotificationManager, Toast};
+
+/// A root record pretty-printed for the "View Formatted" modal
+///
+/// The record is formatted once when the modal opens and kept as lines with
+/// their syntax tokens, so the modal only lays out the rows in view and a
+/// large record doesn't slow down the frame.
+pub struct FormattedRecord {
+    record_index: usize,
+    content: Result<FormattedText, String>,
+}
+
+struct FormattedText {
+    text: String,
+    /// Byte range of each line in `text`
+    lines: Vec<Range<usize>>,
+    /// Syntax tokens per line, relative to the line start
+    tokens: Vec<Vec<(Range<usize>, TextToken)>>,
+}
+
+impl FormattedRecord {
+    /// Read a root record and pretty-print it
+    ///
+    /// The raw bytes are parsed so the output reflects the file exactly;
+    /// loaders whose raw form isn't JSON (CSV rows, YAML documents) fall back
+    /// to the parsed value.
+    pub fn load(loader: &mut FileType, record_index: usize) -> Self {
+        let value = loader
+            .raw_slice(record_index)
+            .ok()
+            .and_then(|raw| serde_json::from_slice::<Value>(&raw).ok())
+            .map_or_else(|| loader.get(record_index), Ok);
+
+        let content = value
+            .map_err(|e| e.to_string())
+            .and_then(|value| serde_json::to_string_pretty(&value).map_err(|e| e.to_string()))
+            .map(FormattedText::new);
+
+        Self {
+            record_index,
+            content,
+        }
+    }
+
+    /// Show the modal; returns false once it has been closed
+    pub fn show(&self, ctx: &egui::Context, syntax_highlighting: bool) -> bool {
+        let mut open = true;
+        let modal = egui::Modal::new(egui::Id::new("view_formatted_modal")).show(ctx, |ui| {
+            let screen = ctx.content_rect();
+            ui.set_width((screen.width() * 0.7).clamp(320.0, 900.0));
+
+            ui.horizontal(|ui| {
+                Typography::heading(ui, &format!("Record #{}", self.record_index));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let close_btn = ui.add(
+                        Button::builder()
+                            .label("Close")
+                            .button_type(ButtonType::Elevated)
+                            .color(ButtonColor::Default)
+                            .build(),
+                    );
+                    if close_btn.clicked() {
+                        open = false;
+                    }
+                    if let Ok(formatted) = &self.content {
+                        let copy_btn = ui.add(
+                            Button::builder()
+                                .label(format!("{} Copy", egui_phosphor::regular::COPY))
+                                .button_type(ButtonType::Elevated)
+                                .color(ButtonColor::Primary)
+                                .build(),
+                        );
+                        if copy_btn.clicked() {
+                            ui.output_mut(|o| {
+                                o.commands
+                                    .push(egui::OutputCommand::CopyText(formatted.text.clone()))
+                            });
+                            NotificationManager::toast(Toast::success("Copied to clipboard"));
+                        }
+                    }
+                });
+            });
+            ui.add_space(8.0);
+
+            match &self.content {
+                Ok(formatted) => {
+                    formatted.show(ui, screen.height() * 0.7, syntax_highlighting);
+                }
+                Err(reason) => {
+                    Typography::body_muted(ui, &format!("Could not format record: {}", reason));
+                }
+            }
+        });
+
+        open && !modal.should_close()
+    }
+}
+
+impl FormattedText {
+    fn new(text: String) -> Self {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for line in text.split('\n') {
+            lines.push(start..start + line.len());
+            start += line.len() + 1;
+        }
+        let tokens = lines
+            .iter()
+            .map(|range| tokenize_json_line(&text[range.clone()]))
+            .collect();
+        Self {
+            text,
+            lines,
+            tokens,
+        }
+    }
+
+    fn show(&self, ui: &mut Ui, max_height: f32, syntax_highlighting: bool) {
+        let font = egui::TextStyle::Monospace.resolve(ui.style());
+        let row_height = ui.fonts_mut(|f| f.row_height(&font));
+        let base = ui.visuals().text_color();
+        let palette = TextPalette::from_ctx(ui.ctx());
+
+        egui::ScrollArea::both()
+            .id_salt("view_formatted_scroll")
+            .max_height(max_height)
+            .auto_shrink([false, true])
+            .show_rows(ui, row_height, self.lines.len(), |ui, row_range| {
+                for row in row_range {
+                    let line = &self.text[self.lines[row].clone()];
+                    let mut job = egui::text::LayoutJob::default();
+                    let mut pos = 0;
+                    for (range, token) in &self.tokens[row] {
+                        if range.start > pos {
+                            job.append(
+                                &line[pos..range.start],
+                                0.0,
+                                egui::TextFormat::simple(font.clone(), base),
+                            );
+                        }
+                        let color =
+                            palette.color_with_highlighting(*token, syntax_highlighting, base);
+                        job.append(
+                            &line[range.clone()],
+                            0.0,
+                            egui::TextFormat::simple(font.clone(), color),
+                        );
+                        pos = range.end;
+                    }
+                    if pos < line.len() {
+                        job.append(
+                            &line[pos..],
+                            0.0,
+                            egui::TextFormat::simple(font.clone(), base),
+                        );
+                    }
+                    ui.add(egui::Label::new(job).extend().selectable(true));
+                }
+            });
+    }
+}
+
+/// Split one line of pretty-printed JSON into syntax tokens
+///
+/// Pretty-printed JSON never breaks a string across lines, so each line can be
+/// tokenized on its own. Whitespace is left out of the result.
+fn tokenize_json_line(line: &str) -> Vec<(Range<usize>, TextToken)> {
+    let bytes = line.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b' ' | b'\t' => i += 1,
+            b'"' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(bytes.len());
+                let is_key = line[i..].trim_start().starts_with(':');
+                let token = if is_key {
+                    TextToken::Key
+                } else {
+                    TextToken::Str
+                };
+                tokens.push((start..i, token));
+            }
+            b'{' | b'}' | b'[' | b']' | b':' | b',' => {
+                tokens.push((i..i + 1, TextToken::Bracket));
+                i += 1;
+            }
+            _ => {
+                // Number or true/false/null: runs until the next delimiter
+                let start = i;
+                while i < bytes.len() && !matches!(bytes[i], b',' | b' ' | b']' | b'}') {
+                    i += 1;
+                }
+                let token = if bytes[start] == b'-' || bytes[start].is_ascii_digit() {
+                    TextToken::Number
+                } else {
+                    TextToken::Boolean
+                };
+                tokens.push((start..i, token));
+            }
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::loaders::NdjsonFile;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn tokens(line: &str) -> Vec<(&str, TextToken)> {
+        tokenize_json_line(line)
+            .into_iter()
+            .map(|(range, token)| (&line[range], token))
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_key_value_lines() {
+        assert_eq!(
+            tokens(r#"  "name": "Ann \"A\"","#),
+            [
+                (r#""name""#, TextToken::Key),
+                (":", TextToken::Bracket),
+                (r#""Ann \"A\"""#, TextToken::Str),
+                (",", TextToken::Bracket),
+            ]
+        );
+        assert_eq!(
+            tokens(r#"    "n": -1.5e3"#),
+            [
+                (r#""n""#, TextToken::Key),
+                (":", TextToken::Bracket),
+                ("-1.5e3", TextToken::Number),
+            ]
+        );
+        assert_eq!(
+            tokens("  null,"),
+            [("null", TextToken::Boolean), (",", TextToken::Bracket)]
+        );
+        assert_eq!(
+            tokens(r#"  "tags": ["#),
+            [
+                (r#""tags""#, TextToken::Key),
+                (":", TextToken::Bracket),
+                ("[", TextToken::Bracket),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_pretty_prints_record() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        writeln!(tmp, r#"{{"a":1,"b":[true,null]}}"#).unwrap();
+        writeln!(tmp, r#"{{"c":"x"}}"#).unwrap();
+        tmp.flush().unwrap();
+        let mut loader = FileType::Ndjson(NdjsonFile::open(tmp.path()).unwrap());
+
+        let record = FormattedRecord::load(&mut loader, 0);
+        let formatted = record.content.as_ref().unwrap();
+        assert_eq!(
+            formatted.text,
+            "{\n  \"a\": 1,\n  \"b\": [\n    true,\n    null\n  ]\n}"
+        );
+        assert_eq!(formatted.lines.len(), 7);
+        assert_eq!(&formatted.text[formatted.lines[1].clone()], "  \"a\": 1,");
+
+        assert!(FormattedRecord::load(&mut loader, 5).content.is_err());
+    }
+}
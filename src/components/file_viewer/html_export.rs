@@ -0,0 +1,304 @@
+use serde_json::Value;
+use thoth_plugin_sdk::components::split_display_text;
+use thoth_plugin_sdk::theme::color_to_hex;
+use thoth_plugin_sdk::tokens::TextToken;
+
+use crate::constants::HTML_EXPORT_MAX_ROWS;
+use crate::helpers::{NumberFormat, preview_value};
+use crate::theme::ThemeColors;
+
+/// Writes the tree rows as a standalone HTML page styled with the theme
+///
+/// Rows are fed in display order. Expanded containers become open
+/// `<details>` elements holding their children, so nesting follows the rows'
+/// indent levels (flat-view roots have no closing row and are closed by the
+/// next row at their level). Collapsed containers are written with their
+/// contents as closed `<details>` the reader can open in a browser, until
+/// `HTML_EXPORT_MAX_ROWS` rows have been written.
+pub struct HtmlExport {
+    out: String,
+    /// Indent levels of the containers whose `<details>` are still open
+    open: Vec<usize>,
+    rows: usize,
+    number_format: NumberFormat,
+}
+
+impl HtmlExport {
+    pub fn new(
+        title: &str,
+        colors: &ThemeColors,
+        indent_size: f32,
+        number_format: NumberFormat,
+    ) -> Self {
+        let css = color_to_hex;
+        let mut out = String::new();
+        out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str(&format!("<title>{}</title>\n", escape(title)));
+        out.push_str(&format!(
+            "<style>\n\
+             body {{ background: {bg}; color: {fg}; margin: 24px; \
+             font: 13px/1.6 ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; }}\n\
+             h1 {{ font-size: 15px; font-weight: 600; margin: 0 0 16px; }}\n\
+             .row {{ white-space: pre; }}\n\
+             .children {{ margin-left: {indent}px; border-left: 1px solid {guide}; padding-left: 4px; }}\n\
+             summary {{ cursor: pointer; white-space: pre; list-style: none; }}\n\
+             summary::-webkit-details-marker {{ display: none; }}\n\
+             summary::before {{ content: \"\\25B8  \"; color: {muted}; }}\n\
+             details[open] > summary::before {{ content: \"\\25BE  \"; }}\n\
+             .key {{ color: {key}; }}\n\
+             .str {{ color: {string}; }}\n\
+             .num {{ color: {number}; }}\n\
+             .bool {{ color: {boolean}; }}\n\
+             .punct {{ color: {punct}; }}\n\
+             .muted {{ color: {muted}; }}\n\
+             </style>\n",
+            bg = css(colors.bg),
+            fg = css(colors.fg),
+            muted = css(colors.fg_muted),
+            guide = css(colors.indent_guide),
+            key = css(colors.syntax_key),
+            string = css(colors.syntax_string),
+            number = css(colors.syntax_number),
+            boolean = css(colors.syntax_bool),
+            punct = css(colors.syntax_punctuation),
+            indent = indent_size.max(4.0),
+        ));
+        out.push_str("</head>\n<body>\n");
+        out.push_str(&format!(
+            "<h1>{}</h1>\n<div class=\"tree\">\n",
+            escape(title)
+        ));
+        Self {
+            out,
+            open: Vec::new(),
+            rows: 0,
+            number_format,
+        }
+    }
+
+    /// A row without children: a primitive, an empty container or a pager
+    pub fn leaf(&mut self, indent: usize, text: &str, tokens: (TextToken, Option<TextToken>)) {
+        self.close_above(indent);
+        self.out.push_str("<div class=\"row\">");
+        self.push_text(text, tokens);
+        self.out.push_str("</div>\n");
+        self.rows += 1;
+    }
+
+    /// An expanded container; the rows after it up to its closing row are its children
+    pub fn open(&mut self, indent: usize, text: &str, tokens: (TextToken, Option<TextToken>)) {
+        self.close_above(indent);
+        self.out.push_str("<details open><summary>");
+        self.push_text(text, tokens);
+        self.out.push_str("</summary><div class=\"children\">\n");
+        self.open.push(indent);
+        self.rows += 1;
+    }
+
+    /// The closing bracket of the container opened at `indent`
+    pub fn close(&mut self, indent: usize, text: &str) {
+        while self.open.last().is_some_and(|&level| level > indent) {
+            self.end_details();
+        }
+        if self.open.last() == Some(&indent) {
+            self.open.pop();
+            self.out.push_str("</div><div class=\"row\">");
+            self.push_span(TextToken::Bracket, text);
+            self.out.push_str("</div></details>\n");
+            self.rows += 1;
+        } else {
+            self.leaf(indent, text, (TextToken::Bracket, None));
+        }
+    }
+
+    /// A collapsed container labelled `key` (`"name"` or `[3]`), written
+    /// closed with its contents
+    pub fn collapsed(&mut self, indent: usize, key: &str, value: &Value) {
+        self.close_above(indent);
+        self.write_collapsed(key, value);
+    }
+
+    /// Close every open element and return the page
+    pub fn finish(mut self) -> String {
+        while !self.open.is_empty() {
+            self.end_details();
+        }
+        self.out.push_str("</div>\n</body>\n</html>\n");
+        self.out
+    }
+
+    fn write_collapsed(&mut self, key: &str, value: &Value) {
+        let (open, close) = match value {
+            Value::Array(_) => ("[", "]"),
+            _ => ("{", "}"),
+        };
+        self.out.push_str("<details><summary>");
+        self.push_span(TextToken::Key, key);
+        self.push_span(TextToken::Bracket, &format!(": {}", open));
+        self.out.push_str("</summary><div class=\"children\">\n");
+        self.rows += 1;
+
+        let children: Vec<(String, &Value)> = match value {
+            Value::Object(map) => map.iter().map(|(k, v)| (format!("\"{}\"", k), v)).collect(),
+            Value::Array(arr) => arr
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (format!("[{}]", i), v))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let total = children.len();
+        for (written, (child_key, child)) in children.into_iter().enumerate() {
+            if self.rows >= HTML_EXPORT_MAX_ROWS {
+                self.out.push_str("<div class=\"row muted\">");
+                self.out
+                    .push_str(&escape(&format!("… {} more not exported", total - written)));
+                self.out.push_str("</div>\n");
+                break;
+            }
+            match child {
+                Value::Object(map) if !map.is_empty() => self.write_collapsed(&child_key, child),
+                Value::Array(arr) if !arr.is_empty() => self.write_collapsed(&child_key, child),
+                _ => {
+                    let text = match child {
+                        Value::Object(_) => format!("{}: {{}}", child_key),
+                        Value::Array(_) => format!("{}: []", child_key),
+                        // Strings are exported whole rather than cut short
+                        Value::String(_) => format!("{}: {}", child_key, child),
+                        _ => format!(
+                            "{}: {}",
+                            child_key,
+                            preview_value(child, self.number_format)
+                        ),
+                    };
+                    self.out.push_str("<div class=\"row\">");
+                    self.push_text(&text, (TextToken::Key, Some(TextToken::from(child))));
+                    self.out.push_str("</div>\n");
+                    self.rows += 1;
+                }
+            }
+        }
+
+        self.out.push_str("</div><div class=\"row\">");
+        self.push_span(TextToken::Bracket, close);
+        self.out.push_str("</div></details>\n");
+    }
+
+    /// Close the containers a row at `indent` is not inside of
+    fn close_above(&mut self, indent: usize) {
+        while self.open.last().is_some_and(|&level| level >= indent) {
+            self.end_details();
+        }
+    }
+
+    fn end_details(&mut self) {
+        self.open.pop();
+        self.out.push_str("</div></details>\n");
+    }
+
+    /// A row's text coloured like the tree: the key part with the first
+    /// token and the value part with the second, if it has one
+    fn push_text(&mut self, text: &str, tokens: (TextToken, Option<TextToken>)) {
+        match tokens {
+            (key_token, Some(value_token)) if text.contains(':') => {
+                let (key, value) = split_display_text(text);
+                self.push_span(key_token, key);
+                self.push_span(TextToken::Bracket, ":");
+                self.push_span(value_token, value);
+            }
+            (token, _) => self.push_span(token, text),
+        }
+    }
+
+    fn push_span(&mut self, token: TextToken, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let class = match token {
+            TextToken::Key => "key",
+            TextToken::Str => "str",
+            TextToken::Number => "num",
+            TextToken::Boolean => "bool",
+            TextToken::Bracket => "punct",
+        };
+        self.out.push_str(&format!(
+            "<span class=\"{}\">{}</span>",
+            class,
+            escape(text)
+        ));
+    }
+}
+
+/// Escape text for use in HTML element content and quoted attributes
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn export() -> HtmlExport {
+        HtmlExport::new("t", &ThemeColors::default(), 16.0, NumberFormat::Plain)
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape(r#"<script>alert("x & 'y'")</script>"#),
+            "&lt;script&gt;alert(&quot;x &amp; &#39;y&#39;&quot;)&lt;/script&gt;"
+        );
+    }
+
+    #[test]
+    fn test_nesting_follows_indent() {
+        let mut html = export();
+        html.open(0, "[0]: {", (TextToken::Key, Some(TextToken::Bracket)));
+        html.leaf(1, "\"a\": 1", (TextToken::Key, Some(TextToken::Number)));
+        html.close(0, "}");
+        // A flat-view root has no closing row
+        html.open(
+            0,
+            "[1]: (1 value)",
+            (TextToken::Key, Some(TextToken::Bracket)),
+        );
+        html.leaf(1, "b: 2", (TextToken::Key, Some(TextToken::Number)));
+        html.leaf(0, "[2]: 3", (TextToken::Key, Some(TextToken::Number)));
+        let page = html.finish();
+
+        assert_eq!(page.matches("<details open>").count(), 2);
+        assert_eq!(page.matches("</details>").count(), 2);
+        let second_close = page.rfind("</details>").unwrap();
+        assert!(second_close < page.find("[2]").unwrap());
+        assert!(page.contains("<span class=\"num\"> 1</span>"));
+    }
+
+    #[test]
+    fn test_collapsed_keeps_contents_closed() {
+        let mut html = export();
+        html.collapsed(
+            0,
+            "[0]",
+            &json!({"name": "<b>bob</b>", "tags": ["x"], "empty": {}}),
+        );
+        let page = html.finish();
+
+        assert!(!page.contains("<details open>"));
+        assert_eq!(page.matches("<details>").count(), 2);
+        assert!(page.contains("&lt;b&gt;bob&lt;/b&gt;"));
+        assert!(!page.contains("<b>bob"));
+        assert!(page.contains("&quot;empty&quot;"));
+    }
+}
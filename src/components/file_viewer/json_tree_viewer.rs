@@ -1,21 +1,39 @@
+use crate::constants::{ARRAY_WINDOW_SIZE, EXPAND_TO_DEPTH_MAX_ROWS, SCROLL_MARGIN};
+use crate::error::{Result, ThothError};
+use crate::file::edits::parse_edited_value;
 use crate::file::loaders::FileType;
 use crate::helpers::{
-    LruCache, format_simple_kv, get_object_string, preview_value, scroll_to_search_target,
-    scroll_to_selection, split_root_rel,
+    LruCache, NumberFormat, PathFormat, flatten_value, format_path, format_simple_kv,
+    get_object_string, get_object_string_minified, humanize_timestamp, infer_typescript_interface,
+    interface_name_from_path, lookup_rel, lookup_rel_mut, preview_value, scroll_to_search_target,
+    scroll_to_selection, split_root_rel, walk_rel,
 };
 use crate::search::results::{FieldComponent, MatchFragment, MatchTarget};
-use crate::theme::{ROW_HEIGHT, row_fill, selected_row_bg};
+use crate::settings::IndentGuides;
+use crate::theme::{ThemeColors, row_fill, selected_row_bg, tree_row_height};
 use eframe::egui::{self, Ui};
 use serde_json::Value;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
-use thoth_plugin_sdk::components::{DataRow, RowHighlights};
+use thoth_plugin_sdk::components::{
+    Button, ButtonColor, ButtonType, DataRow, DataRowIcon, RowHighlights, split_display_text,
+};
 use thoth_plugin_sdk::tokens::TextToken;
 
 use super::context_menu::{
-    ContextMenuConfig, ContextMenuHandler, execute_context_menu_action, render_context_menu,
+    ContextMenuAction, ContextMenuConfig, ContextMenuHandler, execute_context_menu_action,
+    render_context_menu,
 };
-use super::viewer_trait::FileFormatViewer;
+use super::html_export::HtmlExport;
+use super::sort::RootSort;
+use super::types::MultiSelection;
+use super::viewer_trait::{DepthExpansion, FileFormatViewer};
+
+/// Characters of a malformed record's raw text shown on its placeholder row
+const MALFORMED_PREVIEW_CHARS: usize = 200;
+
+/// Width of the strip beside the rows that marks where search hits are
+const HIT_STRIP_WIDTH: f32 = 8.0;
 
 /// JSON-specific tree viewer that handles expansion and rendering
 ///
@@ -30,8 +48,386 @@ pub struct JsonTreeViewer {
     /// Target row index for search navigation (persists across frames)
     search_target_row: Option<usize>,
 
+    /// Scroll offset of the rows as of the last render
+    scroll_offset: egui::Vec2,
+
+    /// Offset to scroll the rows to on the next render
+    pending_scroll_offset: Option<egui::Vec2>,
+
     /// Highlighted terms per record/path
     record_highlights: HashMap<usize, HashMap<String, PathHighlightTerms>>,
+
+    /// Search match count per record, shown as a badge on its root row
+    match_counts: HashMap<usize, usize>,
+
+    /// Distinct matched row paths per record, in match order, for badge jumps
+    match_paths: HashMap<usize, Vec<String>>,
+
+    /// Indices into `rows` of rows showing a search hit, in row order
+    hit_rows: Vec<usize>,
+
+    /// In-progress expand-all, advanced a bounded number of nodes per frame
+    expand_job: Option<ExpandAllJob>,
+
+    /// Sort picked from a row's context menu, applied by FileViewer
+    sort_request: Option<RootSort>,
+
+    /// Root record picked for "View Formatted", opened by FileViewer
+    view_formatted_request: Option<usize>,
+
+    /// Root record picked for "View Raw Bytes", opened by FileViewer
+    view_raw_bytes_request: Option<usize>,
+
+    /// Field picked for "Stats for This Field", aggregated by FileViewer
+    field_stats_request: Option<String>,
+
+    /// Text copied from the context menu, put on the clipboard by the app
+    copy_request: Option<String>,
+
+    /// Row paths of values failing schema validation, per invalid root record
+    schema_violations: HashMap<usize, HashSet<String>>,
+
+    /// Whether double-clicking a primitive value opens an inline editor
+    inline_editing: bool,
+
+    /// How numeric leaf values are displayed
+    number_format: NumberFormat,
+
+    /// How indent guide lines are drawn
+    indent_guides: IndentGuides,
+
+    /// Rows kept between the selection and the view's edge while navigating
+    scroll_margin: usize,
+
+    /// Field-name patterns for numeric timestamps; `None` when timestamps
+    /// aren't humanized
+    timestamp_fields: Option<Vec<String>>,
+
+    /// Value currently being edited in place, if any
+    editing: Option<InlineEdit>,
+
+    /// Show each expanded root as a flat `path: value` list of its leaves
+    flat: bool,
+
+    /// Visible elements of expanded arrays longer than `ARRAY_WINDOW_SIZE`,
+    /// keyed by array path; arrays without an entry show their first window
+    array_windows: HashMap<String, ArrayWindow>,
+
+    /// Lowercased text the built rows are filtered by; empty shows every row
+    tree_filter: String,
+
+    /// Rows whose key or value contains `tree_filter`, as of the last rebuild
+    tree_filter_matches: usize,
+}
+
+/// Range of elements built as rows for one long array
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ArrayWindow {
+    start: usize,
+    end: usize,
+    /// Element count seen on the last rebuild
+    len: usize,
+}
+
+/// Change to an array window requested from its pager row
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ArrayPage {
+    Previous,
+    Next,
+    JumpTo(usize),
+}
+
+/// Path suffix of the pager row above a window that doesn't start at 0
+const ARRAY_PREV_SUFFIX: &str = "/_prev";
+/// Path suffix of the pager row below a window that doesn't reach the end
+const ARRAY_MORE_SUFFIX: &str = "/_more";
+
+/// An open inline editor for one primitive value
+struct InlineEdit {
+    /// Row path of the edited value
+    path: String,
+    /// Text in the editor, parsed with `parse_edited_value` on commit
+    text: String,
+    /// Why the last commit failed, shown on hover
+    error: Option<String>,
+    /// Whether the editor still needs keyboard focus (set when it opens)
+    request_focus: bool,
+}
+
+/// Incremental expand-all state
+///
+/// Root records are walked one at a time to discover their expandable paths,
+/// which are then inserted into the expansion set in bounded chunks so that a
+/// record with hundreds of thousands of nodes doesn't block a single frame.
+#[derive(Default)]
+struct ExpandAllJob {
+    /// Root indices whose subtrees haven't been walked yet
+    pending_roots: VecDeque<usize>,
+    /// Discovered expandable paths not yet inserted into `expanded`
+    pending_paths: VecDeque<String>,
+    /// Number of root records the job started with
+    roots_total: usize,
+    /// Expandable paths discovered so far
+    discovered: usize,
+    /// Paths processed so far (inserted or already expanded)
+    processed: usize,
+}
+
+impl ExpandAllJob {
+    fn is_done(&self) -> bool {
+        self.pending_roots.is_empty() && self.pending_paths.is_empty()
+    }
+
+    /// Estimated completion in `0.0..=1.0`, extrapolating the node count of
+    /// the roots not walked yet from the ones already walked
+    fn progress(&self) -> f32 {
+        let roots_walked = self.roots_total - self.pending_roots.len();
+        if roots_walked == 0 || self.discovered == 0 {
+            return roots_walked as f32 / self.roots_total.max(1) as f32;
+        }
+        let estimated_total =
+            self.discovered as f64 * self.roots_total as f64 / roots_walked as f64;
+        (self.processed as f64 / estimated_total).clamp(0.0, 1.0) as f32
+    }
+}
+
+/// Row path of the node containing `path`, trimming its last `.key` or `[idx]`
+///
+/// A closing-bracket row's parent is the row that opened it; root rows have none.
+fn parent_path(path: &str) -> Option<&str> {
+    if let Some(opened) = path
+        .strip_suffix("/_close")
+        .or_else(|| path.strip_suffix(ARRAY_PREV_SUFFIX))
+        .or_else(|| path.strip_suffix(ARRAY_MORE_SUFFIX))
+    {
+        return Some(opened);
+    }
+    let cut = path.rfind(['.', '['])?;
+    Some(&path[..cut])
+}
+
+/// Whether the row at `path` shows a value, rather than closing a bracket
+/// or paging a long array
+fn is_value_row(path: &str) -> bool {
+    !path.ends_with("/_close")
+        && !path.ends_with(ARRAY_PREV_SUFFIX)
+        && !path.ends_with(ARRAY_MORE_SUFFIX)
+}
+
+/// Row path of the bracket row paired with `path`: a container's closing
+/// row for its opening row and the other way round
+///
+/// Only expanded containers have a closing row, so the partner of any other
+/// row matches nothing.
+fn bracket_partner(path: &str) -> String {
+    match path.strip_suffix("/_close") {
+        Some(opened) => opened.to_string(),
+        None => format!("{}/_close", path),
+    }
+}
+
+/// Element index of `path` within the array at `parent`, e.g. 12 for `0.items[12]`
+fn array_index(parent: &str, path: &str) -> Option<usize> {
+    path.strip_prefix(parent)?
+        .strip_prefix('[')?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+/// Collect the paths of every expandable node under `value` (depth-first, parents first)
+fn collect_expandable_paths(value: &Value, path: &str, out: &mut VecDeque<String>) {
+    match value {
+        Value::Object(map) => {
+            out.push_back(path.to_string());
+            for (key, val) in map.iter() {
+                collect_expandable_paths(val, &format!("{}.{}", path, key), out);
+            }
+        }
+        Value::Array(arr) => {
+            out.push_back(path.to_string());
+            for (idx, val) in arr.iter().enumerate() {
+                collect_expandable_paths(val, &format!("{}[{}]", path, idx), out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Insert every expandable path in `value` that sits at an indent below `depth`
+///
+/// `rows` tracks how many rows the resulting tree renders (children plus the
+/// closing bracket of each opened node). Returns false as soon as it exceeds
+/// `EXPAND_TO_DEPTH_MAX_ROWS`.
+fn collect_paths_above_depth(
+    value: &Value,
+    path: &str,
+    indent: usize,
+    depth: usize,
+    out: &mut HashSet<String>,
+    rows: &mut usize,
+) -> bool {
+    if indent >= depth {
+        return true;
+    }
+    match value {
+        Value::Object(map) => {
+            out.insert(path.to_string());
+            *rows += map.len() + 1;
+            if *rows > EXPAND_TO_DEPTH_MAX_ROWS {
+                return false;
+            }
+            map.iter().all(|(key, val)| {
+                collect_paths_above_depth(
+                    val,
+                    &format!("{}.{}", path, key),
+                    indent + 1,
+                    depth,
+                    out,
+                    rows,
+                )
+            })
+        }
+        Value::Array(arr) => {
+            out.insert(path.to_string());
+            *rows += arr.len() + 1;
+            if *rows > EXPAND_TO_DEPTH_MAX_ROWS {
+                return false;
+            }
+            arr.iter().enumerate().all(|(idx, val)| {
+                collect_paths_above_depth(
+                    val,
+                    &format!("{}[{}]", path, idx),
+                    indent + 1,
+                    depth,
+                    out,
+                    rows,
+                )
+            })
+        }
+        _ => true,
+    }
+}
+
+/// Horizontal offset of the indent guide for `level`, centred in that level's
+/// indentation so it lines up with the `DataRow` content indented by `indent_size`
+fn indent_guide_offset(level: usize, indent_size: f32) -> f32 {
+    level as f32 * indent_size + indent_size / 2.0
+}
+
+/// Whether the indent guide at `level` beside the row at `path` runs down the
+/// side of one of the selection's `ancestors` (see `selection_ancestors`)
+fn is_active_guide(path: &str, level: usize, ancestors: &[(usize, String)]) -> bool {
+    ancestors.iter().any(|(indent, ancestor)| {
+        *indent == level
+            && path
+                .strip_prefix(ancestor.as_str())
+                .is_some_and(|rest| rest.starts_with(['.', '[', '/']))
+    })
+}
+
+/// Row standing in for the elements of a long array outside its window
+fn array_pager_row(array_path: &str, suffix: &str, indent: usize, text: String) -> JsonRow {
+    JsonRow {
+        path: format!("{}{}", array_path, suffix),
+        indent,
+        is_expandable: false,
+        is_expanded: false,
+        display_text: text,
+        text_token: (TextToken::Bracket, None),
+        highlights: RowHighlights::default(),
+        type_badge: None,
+        parse_error: None,
+        annotation: None,
+    }
+}
+
+/// Which of `rows` stay visible under the tree filter `needle` (lowercase)
+///
+/// A row stays if its key or value contains `needle`, or if it encloses such
+/// a row, so matches keep their ancestors. Closing rows follow the row that
+/// opened them.
+fn tree_filter_keep(rows: &[JsonRow], needle: &str) -> Vec<bool> {
+    let mut keep = vec![false; rows.len()];
+    // Rows enclosing the current one, innermost last
+    let mut open: Vec<usize> = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        if row.path.ends_with("/_close") {
+            while open.last().is_some_and(|&o| rows[o].indent > row.indent) {
+                open.pop();
+            }
+            if let Some(opener) = open.pop() {
+                keep[i] = keep[opener];
+            }
+            continue;
+        }
+        while open.last().is_some_and(|&o| rows[o].indent >= row.indent) {
+            open.pop();
+        }
+        if row.display_text.to_lowercase().contains(needle) {
+            keep[i] = true;
+            for &ancestor in open.iter().rev() {
+                if keep[ancestor] {
+                    break;
+                }
+                keep[ancestor] = true;
+            }
+        }
+        open.push(i);
+    }
+    keep
+}
+
+/// Byte ranges of `needle` (lowercase) in `text`, ignoring case
+fn find_ignore_case(text: &str, needle: &str) -> Vec<std::ops::Range<usize>> {
+    let lower = text.to_lowercase();
+    // Offsets only carry over when lowercasing kept every byte length
+    if lower.len() != text.len() {
+        return Vec::new();
+    }
+    lower
+        .match_indices(needle)
+        .map(|(at, _)| at..at + needle.len())
+        .filter(|range| text.is_char_boundary(range.start) && text.is_char_boundary(range.end))
+        .collect()
+}
+
+/// Placeholder row for a record that failed to parse, showing its raw text
+fn malformed_row(idx: usize, raw: &[u8], reason: String) -> JsonRow {
+    let raw = String::from_utf8_lossy(raw);
+    let raw = raw.trim();
+    let preview = match raw.char_indices().nth(MALFORMED_PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}…", &raw[..end]),
+        None => raw.to_string(),
+    };
+    JsonRow {
+        path: idx.to_string(),
+        indent: 0,
+        is_expandable: false,
+        is_expanded: false,
+        display_text: format!("[{}]: {}", idx, preview),
+        text_token: (TextToken::Key, Some(TextToken::Str)),
+        highlights: RowHighlights::default(),
+        type_badge: None,
+        parse_error: Some(reason),
+        annotation: None,
+    }
+}
+
+/// Where the middle of row `row_index` sits, as a fraction of the tree's height
+fn tick_fraction(row_index: usize, row_count: usize) -> f32 {
+    (row_index as f32 + 0.5) / row_count.max(1) as f32
+}
+
+/// The hit row whose mark is closest to `fraction` of the way down the strip
+fn nearest_hit_row(hit_rows: &[usize], row_count: usize, fraction: f32) -> Option<usize> {
+    let after = hit_rows.partition_point(|&row| tick_fraction(row, row_count) < fraction);
+    let distance = |row: usize| (tick_fraction(row, row_count) - fraction).abs();
+    [after.checked_sub(1), Some(after)]
+        .into_iter()
+        .flatten()
+        .filter_map(|i| hit_rows.get(i).copied())
+        .min_by(|&a, &b| distance(a).total_cmp(&distance(b)))
 }
 
 #[derive(Default, Clone)]
@@ -49,6 +445,37 @@ struct JsonRow {
     display_text: String,
     text_token: (TextToken, Option<TextToken>),
     highlights: RowHighlights,
+    /// Type/size descriptor of the row's value; `None` for closing brackets
+    type_badge: Option<TypeBadge>,
+    /// Why the record couldn't be parsed; set on placeholder rows for
+    /// malformed records, which show the raw text instead of a value
+    parse_error: Option<String>,
+    /// Readable date shown after a value that looks like a timestamp
+    annotation: Option<String>,
+}
+
+/// Short value type descriptor shown at the end of a row (`str`, `obj[3]`, ...)
+#[derive(Clone, Debug, PartialEq)]
+struct TypeBadge {
+    label: String,
+    token: TextToken,
+}
+
+impl TypeBadge {
+    fn of(value: &Value) -> Self {
+        let label = match value {
+            Value::Null => "null".to_string(),
+            Value::Bool(_) => "bool".to_string(),
+            Value::Number(_) => "num".to_string(),
+            Value::String(_) => "str".to_string(),
+            Value::Array(arr) => format!("arr[{}]", arr.len()),
+            Value::Object(map) => format!("obj[{}]", map.len()),
+        };
+        Self {
+            label,
+            token: TextToken::from(value),
+        }
+    }
 }
 
 fn compute_row_highlights(display_text: &str, terms: Option<&PathHighlightTerms>) -> RowHighlights {
@@ -56,9 +483,7 @@ fn compute_row_highlights(display_text: &str, terms: Option<&PathHighlightTerms>
         return RowHighlights::default();
     };
 
-    let mut parts = display_text.splitn(2, ':');
-    let key_part = parts.next().unwrap_or("");
-    let value_part = parts.next().unwrap_or("");
+    let (key_part, value_part) = split_display_text(display_text);
     let has_colon = !value_part.is_empty();
     let key_ranges = adjust_key_ranges(key_part, &terms.key_ranges);
     let value_ranges = if has_colon {
@@ -73,6 +498,50 @@ fn compute_row_highlights(display_text: &str, terms: Option<&PathHighlightTerms>
     }
 }
 
+/// Highlights for a flat `path: value` row
+///
+/// Key matches are recorded against the last key only, so they are shifted
+/// onto the path's final segment; index segments have no key to highlight.
+fn compute_flat_row_highlights(
+    display_text: &str,
+    rel_path: &str,
+    terms: Option<&PathHighlightTerms>,
+) -> RowHighlights {
+    let Some(terms) = terms else {
+        return RowHighlights::default();
+    };
+
+    let key_ranges = if rel_path.ends_with(']') {
+        Vec::new()
+    } else {
+        let key_start = rel_path.rfind('.').map_or(0, |dot| dot + 1);
+        terms
+            .key_ranges
+            .iter()
+            .filter_map(|range| {
+                let start = key_start + range.start;
+                let end = (key_start + range.end).min(rel_path.len());
+                if start < end { Some(start..end) } else { None }
+            })
+            .collect()
+    };
+    let (_, value_part) = split_display_text(display_text);
+
+    RowHighlights {
+        key_ranges,
+        value_ranges: adjust_value_ranges(value_part, &terms.value_ranges),
+    }
+}
+
+/// Value text of a flat row; empty objects and arrays show as `{}` / `[]`
+fn flat_value_text(value: &Value, number_format: NumberFormat) -> String {
+    match value {
+        Value::Object(_) => "{}".to_string(),
+        Value::Array(_) => "[]".to_string(),
+        _ => preview_value(value, number_format),
+    }
+}
+
 fn adjust_key_ranges(
     key_part: &str,
     ranges: &[std::ops::Range<usize>],
@@ -122,23 +591,59 @@ fn adjust_value_ranges(
     }
 
     let trimmed = &value_part[leading_ws..limit];
-    if trimmed.starts_with('"') {
+    let quoted = trimmed.len() >= 2 && trimmed.starts_with('"') && trimmed.ends_with('"');
+    if quoted {
         offset += 1;
-    }
-    if trimmed.ends_with('"') && limit > offset {
         limit -= 1;
     }
+    let content = &value_part[offset..limit];
 
     ranges
         .iter()
         .filter_map(|range| {
-            let start = offset + range.start;
-            let end = (offset + range.end).min(limit);
-            if start < end { Some(start..end) } else { None }
+            // Match ranges index the raw string; the row shows it escaped
+            let (start, end) = if quoted {
+                (
+                    escaped_offset(content, range.start, false),
+                    escaped_offset(content, range.end, true),
+                )
+            } else {
+                (range.start.min(content.len()), range.end.min(content.len()))
+            };
+            if start < end {
+                Some(offset + start..offset + end)
+            } else {
+                None
+            }
         })
         .collect()
 }
 
+/// Byte offset within escaped string `content` of byte `raw` of the raw string
+///
+/// An offset inside a multibyte character is moved to the character's start,
+/// or past its end when `round_up` is set, so ranges never split one.
+/// Offsets beyond a truncated preview map to its end.
+fn escaped_offset(content: &str, raw: usize, round_up: bool) -> usize {
+    let mut raw_pos = 0;
+    let mut chars = content.char_indices();
+    while let Some((idx, ch)) = chars.next() {
+        if raw_pos >= raw {
+            return idx;
+        }
+        let raw_len = if ch == '\\' {
+            chars.next().map_or(1, |(_, escaped)| escaped.len_utf8())
+        } else {
+            ch.len_utf8()
+        };
+        if !round_up && raw < raw_pos + raw_len {
+            return idx;
+        }
+        raw_pos += raw_len;
+    }
+    content.len()
+}
+
 fn leading_whitespace_len(text: &str) -> usize {
     text.chars()
         .take_while(|c| c.is_whitespace())
@@ -170,14 +675,44 @@ impl JsonTreeViewer {
             expanded: HashSet::new(),
             rows: Vec::new(),
             search_target_row: None,
+            scroll_offset: egui::Vec2::ZERO,
+            pending_scroll_offset: None,
             record_highlights: HashMap::new(),
+            hit_rows: Vec::new(),
+            match_counts: HashMap::new(),
+            match_paths: HashMap::new(),
+            expand_job: None,
+            sort_request: None,
+            view_formatted_request: None,
+            view_raw_bytes_request: None,
+            field_stats_request: None,
+            copy_request: None,
+            schema_violations: HashMap::new(),
+            inline_editing: false,
+            number_format: NumberFormat::Plain,
+            indent_guides: IndentGuides::Lines,
+            scroll_margin: SCROLL_MARGIN,
+            timestamp_fields: None,
+            editing: None,
+            flat: false,
+            array_windows: HashMap::new(),
+            tree_filter: String::new(),
+            tree_filter_matches: 0,
         }
     }
 
-    pub fn set_highlights(&mut self, highlights: &HashMap<usize, Arc<Vec<MatchFragment>>>) {
+    pub fn set_highlights(
+        &mut self,
+        highlights: &HashMap<usize, Arc<Vec<MatchFragment>>>,
+        match_counts: &HashMap<usize, usize>,
+    ) {
         self.record_highlights.clear();
+        self.match_counts = match_counts.clone();
+        self.match_paths.clear();
         for (record_index, fragments) in highlights {
             let mut path_map: HashMap<String, PathHighlightTerms> = HashMap::new();
+            let mut match_paths: Vec<String> = Vec::new();
+            let mut seen: HashSet<&str> = HashSet::new();
             for fragment in fragments.iter() {
                 let MatchTarget::JsonField { component } = &fragment.target else {
                     continue;
@@ -185,6 +720,9 @@ impl JsonTreeViewer {
                 let Some(path) = fragment.path.as_deref() else {
                     continue;
                 };
+                if !fragment.is_record_marker() && seen.insert(path) {
+                    match_paths.push(path.to_string());
+                }
                 let entry = path_map.entry(path.to_string()).or_default();
                 if let Some(range) = fragment.text_range.as_ref().and_then(|r| {
                     let start = usize::try_from(r.start).ok()?;
@@ -201,7 +739,247 @@ impl JsonTreeViewer {
             if !path_map.is_empty() {
                 self.record_highlights.insert(*record_index, path_map);
             }
+            if !match_paths.is_empty() {
+                self.match_paths.insert(*record_index, match_paths);
+            }
+        }
+    }
+
+    /// The selected row's ancestors as `(indent, path)`, innermost first
+    ///
+    /// A row's children draw their indent guide at the row's own indent, so
+    /// these are the guide columns that lead to the selection. Ancestors are
+    /// found by walking up to each less-indented row, which also works for
+    /// keys containing `.` or `[`.
+    fn selection_ancestors(&self, selected: &str) -> Vec<(usize, String)> {
+        let Some(idx) = self.rows.iter().position(|r| r.path == selected) else {
+            return Vec::new();
+        };
+        let mut indent = self.rows[idx].indent;
+        let mut ancestors = Vec::new();
+        for row in self.rows[..idx].iter().rev() {
+            if indent == 0 {
+                break;
+            }
+            if row.indent < indent {
+                indent = row.indent;
+                ancestors.push((row.indent, row.path.clone()));
+            }
+        }
+        ancestors
+    }
+
+    /// The rows as currently expanded, as a standalone HTML page
+    ///
+    /// Collapsed objects and arrays are read from their record so their
+    /// contents can be opened in the browser.
+    pub fn export_html(
+        &self,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+        title: &str,
+        colors: &ThemeColors,
+        indent_size: f32,
+    ) -> String {
+        let mut html = HtmlExport::new(title, colors, indent_size, self.number_format);
+        // Consecutive collapsed rows are usually in the same record
+        let mut record: Option<(usize, Value)> = None;
+        for row in &self.rows {
+            if row.path.ends_with("/_close") {
+                html.close(row.indent, &row.display_text);
+                continue;
+            }
+            if row.is_expanded {
+                html.open(row.indent, &row.display_text, row.text_token);
+                continue;
+            }
+            let value = if row.is_expandable
+                && let Ok((root, rel)) = split_root_rel(&row.path)
+            {
+                if record.as_ref().is_none_or(|(idx, _)| *idx != root) {
+                    record = load_root_value(root, cache, loader).map(|value| (root, value));
+                }
+                record
+                    .as_ref()
+                    .and_then(|(_, value)| lookup_rel(value, rel).ok())
+            } else {
+                None
+            };
+            match value {
+                Some(value) if matches!(value, Value::Object(_) | Value::Array(_)) => {
+                    let (key, _) = split_display_text(&row.display_text);
+                    html.collapsed(row.indent, key, value);
+                }
+                _ => html.leaf(row.indent, &row.display_text, row.text_token),
+            }
+        }
+        html.finish()
+    }
+
+    /// The match in `root` after the selected one, wrapping to the first
+    ///
+    /// Starts from the first match when the selection isn't one of them.
+    fn next_match_path(&self, root: usize, selected: Option<&str>) -> Option<String> {
+        let paths = self.match_paths.get(&root)?;
+        let next = selected
+            .and_then(|selected| paths.iter().position(|path| path == selected))
+            .map_or(0, |idx| (idx + 1) % paths.len());
+        paths.get(next).cloned()
+    }
+
+    /// Expand or collapse every expandable sibling of the node at `path`,
+    /// leaving the node itself as it is
+    ///
+    /// Siblings are the rows one level below the node's parent, so this works
+    /// for keys containing `.` or `[` too. Returns true if rows need rebuilding.
+    fn set_siblings_expanded(&mut self, path: &str, expand: bool) -> bool {
+        let path = path.strip_suffix("/_close").unwrap_or(path);
+        let Some(parent) = self.select_parent(&Some(path.to_string())) else {
+            return false;
+        };
+        let (Some(parent_idx), Some(indent)) = (
+            self.rows.iter().position(|row| row.path == parent),
+            self.rows
+                .iter()
+                .find(|row| row.path == path)
+                .map(|row| row.indent),
+        ) else {
+            return false;
+        };
+
+        let siblings: Vec<String> = self.rows[parent_idx + 1..]
+            .iter()
+            .take_while(|row| row.indent >= indent)
+            .filter(|row| row.indent == indent && row.is_expandable && row.path != path)
+            .map(|row| row.path.clone())
+            .collect();
+
+        let mut changed = false;
+        for sibling in siblings {
+            changed |= if expand {
+                self.expanded.insert(sibling)
+            } else {
+                self.expanded.remove(&sibling)
+            };
+        }
+        changed
+    }
+
+    /// Expand every ancestor of `path` so its row is built
+    fn reveal_path(&mut self, path: &str) {
+        let mut current = path;
+        while let Some(parent) = parent_path(current) {
+            self.expanded.insert(parent.to_string());
+            current = parent;
+        }
+        self.show_in_array_windows(path);
+    }
+
+    /// Elements of the array at `path` to build rows for
+    ///
+    /// Short arrays are built whole; long ones get a window that is created on
+    /// first expansion and clamped to the array's current length.
+    fn array_window(&mut self, path: &str, len: usize) -> std::ops::Range<usize> {
+        if len <= ARRAY_WINDOW_SIZE {
+            return 0..len;
+        }
+        let window = self
+            .array_windows
+            .entry(path.to_string())
+            .or_insert(ArrayWindow {
+                start: 0,
+                end: ARRAY_WINDOW_SIZE,
+                len,
+            });
+        window.len = len;
+        window.end = window.end.min(len);
+        window.start = window.start.min(window.end.saturating_sub(1));
+        window.start..window.end
+    }
+
+    /// Move the window of the array at `path`, returning the row path to
+    /// select after a jump
+    fn page_array(&mut self, path: &str, page: ArrayPage) -> Option<String> {
+        let window = self.array_windows.get_mut(path)?;
+        match page {
+            ArrayPage::Previous => {
+                window.start = window.start.saturating_sub(ARRAY_WINDOW_SIZE);
+            }
+            ArrayPage::Next => {
+                window.end = (window.end + ARRAY_WINDOW_SIZE).min(window.len);
+            }
+            ArrayPage::JumpTo(idx) => {
+                let idx = idx.min(window.len.saturating_sub(1));
+                window.start = idx;
+                window.end = (idx + ARRAY_WINDOW_SIZE).min(window.len);
+                return Some(format!("{}[{}]", path, idx));
+            }
+        }
+        None
+    }
+
+    /// Replace the expanded set so every node at indent < `depth` among the
+    /// visible roots is open and everything deeper is closed
+    ///
+    /// Each root is fetched through the cache/loader, so this works before the
+    /// first rebuild. The expanded set is swapped in one go, so a single rebuild
+    /// applies it no matter how many nodes change.
+    fn set_expansion_depth(
+        &mut self,
+        depth: usize,
+        visible_roots: &Option<Vec<usize>>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+        total_len: usize,
+    ) -> DepthExpansion {
+        let mut expanded = HashSet::new();
+        if depth > 0 {
+            let roots: Vec<usize> = match visible_roots {
+                Some(roots) => roots.clone(),
+                None => (0..total_len).collect(),
+            };
+            // Every root renders a row of its own
+            let mut rows = roots.len();
+            if rows > EXPAND_TO_DEPTH_MAX_ROWS {
+                return DepthExpansion::TooManyRows;
+            }
+            for root in roots {
+                let value = if let Some(v) = cache.get(&root) {
+                    v.clone()
+                } else {
+                    match loader.get(root) {
+                        Ok(v) => {
+                            cache.put(root, v.clone());
+                            v
+                        }
+                        Err(_) => continue,
+                    }
+                };
+                if !collect_paths_above_depth(
+                    &value,
+                    &root.to_string(),
+                    0,
+                    depth,
+                    &mut expanded,
+                    &mut rows,
+                ) {
+                    return DepthExpansion::TooManyRows;
+                }
+            }
+        }
+
+        self.expand_job = None;
+        if expanded == self.expanded {
+            return DepthExpansion::Unchanged;
         }
+        self.expanded = expanded;
+        DepthExpansion::Changed
+    }
+
+    /// Readable date for a leaf value when timestamps are humanized
+    fn timestamp_note(&self, key: Option<&str>, value: &Value) -> Option<String> {
+        let date = humanize_timestamp(key, value, self.timestamp_fields.as_deref()?)?;
+        Some(format!("({})", date))
     }
 
     /// Rebuild rows based on visible roots and cache
@@ -237,6 +1015,11 @@ impl JsonTreeViewer {
                         cache.put(i, v.clone());
                         v
                     }
+                    Err(ThothError::JsonParseError { reason, .. }) => {
+                        let raw = loader.raw_slice(i).unwrap_or_default();
+                        self.rows.push(malformed_row(i, &raw, reason));
+                        continue;
+                    }
                     Err(_) => continue,
                 }
             };
@@ -244,7 +1027,17 @@ impl JsonTreeViewer {
             let is_expandable = matches!(value, Value::Object(_) | Value::Array(_));
             let is_expanded = is_expandable && self.expanded.contains(&path);
 
-            let display_text = if is_expandable {
+            // Flat mode only flattens the roots that are open
+            let leaves = (self.flat && is_expanded).then(|| flatten_value(&value, &path));
+
+            let display_text = if let Some(leaves) = &leaves {
+                format!(
+                    "[{}]: ({} {})",
+                    i,
+                    leaves.len(),
+                    if leaves.len() == 1 { "value" } else { "values" }
+                )
+            } else if is_expandable {
                 if is_expanded {
                     match &value {
                         Value::Array(_) => format!("[{}]: [", i),
@@ -254,7 +1047,7 @@ impl JsonTreeViewer {
                     format!("[{}]: (…) ", i)
                 }
             } else {
-                format!("[{}]: {}", i, preview_value(&value))
+                format!("[{}]: {}", i, preview_value(&value, self.number_format))
             };
 
             let row_highlights = compute_row_highlights(
@@ -274,9 +1067,14 @@ impl JsonTreeViewer {
                     (TextToken::Key, Some(TextToken::from(&value)))
                 },
                 highlights: row_highlights,
+                type_badge: Some(TypeBadge::of(&value)),
+                parse_error: None,
+                annotation: self.timestamp_note(None, &value),
             });
 
-            if is_expanded {
+            if let Some(leaves) = leaves {
+                self.build_flat_rows(leaves, &path, highlight_paths.as_ref());
+            } else if is_expanded {
                 self.build_rows_from_value(&value, &path, 1, highlight_paths.as_ref());
 
                 // Closing bracket/brace
@@ -292,22 +1090,179 @@ impl JsonTreeViewer {
                     display_text: close_char.to_string(),
                     text_token: (TextToken::Bracket, None),
                     highlights: RowHighlights::default(),
+                    type_badge: None,
+                    parse_error: None,
+                    annotation: None,
                 });
             }
         }
+
+        if !self.tree_filter.is_empty() {
+            let mut keep = tree_filter_keep(&self.rows, &self.tree_filter).into_iter();
+            self.rows.retain(|_| keep.next().unwrap_or(false));
+        }
+
+        self.hit_rows = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| self.is_hit_row(row))
+            .map(|(row_index, _)| row_index)
+            .collect();
+
+        // Marked after the hits are collected so filter matches don't count as search hits
+        self.tree_filter_matches = 0;
+        if !self.tree_filter.is_empty() {
+            for row in &mut self.rows {
+                if row.path.ends_with("/_close")
+                    || !row.display_text.to_lowercase().contains(&self.tree_filter)
+                {
+                    continue;
+                }
+                self.tree_filter_matches += 1;
+                let (key_part, value_part) = split_display_text(&row.display_text);
+                row.highlights
+                    .key_ranges
+                    .extend(find_ignore_case(key_part, &self.tree_filter));
+                row.highlights
+                    .value_ranges
+                    .extend(find_ignore_case(value_part, &self.tree_filter));
+            }
+        }
     }
 
-    /// Build rows from a JSON value recursively
-    fn build_rows_from_value(
+    /// Whether a row shows a search hit: matched text, or the root row of a
+    /// record with matches
+    fn is_hit_row(&self, row: &JsonRow) -> bool {
+        !row.highlights.key_ranges.is_empty()
+            || !row.highlights.value_ranges.is_empty()
+            || (row.indent == 0
+                && row
+                    .path
+                    .parse::<usize>()
+                    .is_ok_and(|root| self.match_counts.contains_key(&root)))
+    }
+
+    /// Mark where the search hits are along `strip`, with the visible part
+    /// of the tree outlined; returns the hit row whose mark was clicked
+    fn show_hit_strip(
+        &self,
+        ui: &mut Ui,
+        strip: egui::Rect,
+        scroll: &egui::scroll_area::ScrollAreaOutput<()>,
+        row_height: f32,
+    ) -> Option<usize> {
+        let row_count = self.rows.len();
+        let colors = ui.ctx().memory(|mem| {
+            mem.data
+                .get_temp::<crate::theme::ThemeColors>(egui::Id::new("theme_colors"))
+                .unwrap_or_else(|| {
+                    crate::theme::Theme::for_dark_mode(ui.visuals().dark_mode).colors()
+                })
+        });
+        let painter = ui.painter_at(strip);
+        painter.rect_filled(strip, 0.0, colors.bg_sunken);
+
+        let y_at = |fraction: f32| strip.top() + fraction * strip.height();
+        let content_height = (row_count as f32 * row_height).max(1.0);
+        let view_top = scroll.state.offset.y / content_height;
+        let view_bottom = view_top + scroll.inner_rect.height() / content_height;
+        if view_top > 0.0 || view_bottom < 1.0 {
+            painter.rect_filled(
+                egui::Rect::from_x_y_ranges(strip.x_range(), y_at(view_top)..=y_at(view_bottom)),
+                0.0,
+                colors.surface_active.gamma_multiply(0.6),
+            );
+        }
+
+        // Hits closer together than a pixel share one mark
+        let mut last_y = f32::NEG_INFINITY;
+        for &row_index in &self.hit_rows {
+            let y = y_at(tick_fraction(row_index, row_count)).round();
+            if y > last_y {
+                painter.rect_filled(
+                    egui::Rect::from_center_size(
+                        egui::pos2(strip.center().x, y),
+                        egui::vec2(strip.width() - 2.0, 2.0),
+                    ),
+                    1.0,
+                    colors.accent,
+                );
+                last_y = y;
+            }
+        }
+
+        let response = ui
+            .interact(
+                strip,
+                ui.id().with("json_tree_hit_strip"),
+                egui::Sense::click(),
+            )
+            .on_hover_cursor(egui::CursorIcon::PointingHand)
+            .on_hover_text(format!(
+                "{} {} with search hits. Click a mark to jump to it",
+                self.hit_rows.len(),
+                if self.hit_rows.len() == 1 {
+                    "row"
+                } else {
+                    "rows"
+                }
+            ));
+        let pointer = response
+            .interact_pointer_pos()
+            .filter(|_| response.clicked())?;
+        nearest_hit_row(
+            &self.hit_rows,
+            row_count,
+            (pointer.y - strip.top()) / strip.height(),
+        )
+    }
+
+    /// Build one `path: value` row per leaf of a flattened root record
+    fn build_flat_rows(
         &mut self,
-        value: &Value,
-        path: &str,
-        indent: usize,
+        leaves: Vec<(String, Value)>,
+        root_path: &str,
         highlights_map: Option<&HashMap<String, PathHighlightTerms>>,
     ) {
-        #[cfg(feature = "profiling")]
-        puffin::profile_function!();
-
+        for (path, val) in leaves {
+            let rel = &path[root_path.len()..];
+            let rel = rel.strip_prefix('.').unwrap_or(rel);
+            let display_text = format!("{}: {}", rel, flat_value_text(&val, self.number_format));
+            let row_highlights = compute_flat_row_highlights(
+                &display_text,
+                rel,
+                highlights_map.and_then(|map| map.get(&path)),
+            );
+
+            // Array elements have no field name to match
+            let key = rel.rsplit('.').next().filter(|key| !key.ends_with(']'));
+            self.rows.push(JsonRow {
+                indent: 1,
+                is_expandable: false,
+                is_expanded: false,
+                display_text,
+                text_token: (TextToken::Key, Some(TextToken::from(&val))),
+                highlights: row_highlights,
+                type_badge: Some(TypeBadge::of(&val)),
+                parse_error: None,
+                annotation: self.timestamp_note(key, &val),
+                path,
+            });
+        }
+    }
+
+    /// Build rows from a JSON value recursively
+    fn build_rows_from_value(
+        &mut self,
+        value: &Value,
+        path: &str,
+        indent: usize,
+        highlights_map: Option<&HashMap<String, PathHighlightTerms>>,
+    ) {
+        #[cfg(feature = "profiling")]
+        puffin::profile_function!();
+
         match value {
             Value::Object(map) => {
                 for (key, val) in map.iter() {
@@ -324,7 +1279,7 @@ impl JsonTreeViewer {
                     let display_text = if is_expandable {
                         format!("\"{}\": {}", key, if is_expanded { open } else { empty })
                     } else {
-                        format_simple_kv(key, val)
+                        format_simple_kv(key, val, self.number_format)
                     };
                     let row_highlights = compute_row_highlights(
                         &display_text,
@@ -346,6 +1301,9 @@ impl JsonTreeViewer {
                             }),
                         ),
                         highlights: row_highlights,
+                        type_badge: Some(TypeBadge::of(val)),
+                        parse_error: None,
+                        annotation: self.timestamp_note(Some(key), val),
                     });
 
                     if is_expanded {
@@ -363,12 +1321,24 @@ impl JsonTreeViewer {
                             .to_string(),
                             text_token: (TextToken::Bracket, None),
                             highlights: RowHighlights::default(),
+                            type_badge: None,
+                            parse_error: None,
+                            annotation: None,
                         });
                     }
                 }
             }
             Value::Array(arr) => {
-                for (idx, val) in arr.iter().enumerate() {
+                let window = self.array_window(path, arr.len());
+                if window.start > 0 {
+                    self.rows.push(array_pager_row(
+                        path,
+                        ARRAY_PREV_SUFFIX,
+                        indent,
+                        format!("… {} earlier elements", window.start),
+                    ));
+                }
+                for (idx, val) in arr.iter().enumerate().skip(window.start).take(window.len()) {
                     let new_path = format!("{}[{}]", path, idx);
                     let is_expandable = matches!(val, Value::Object(_) | Value::Array(_));
                     let is_expanded = is_expandable && self.expanded.contains(&new_path);
@@ -382,7 +1352,7 @@ impl JsonTreeViewer {
                     let display_text = if is_expandable {
                         format!("[{}]: {}", idx, if is_expanded { open } else { empty })
                     } else {
-                        format!("[{}]: {}", idx, preview_value(val))
+                        format!("[{}]: {}", idx, preview_value(val, self.number_format))
                     };
                     let row_highlights = compute_row_highlights(
                         &display_text,
@@ -401,6 +1371,9 @@ impl JsonTreeViewer {
                             (TextToken::Key, Some(TextToken::from(val)))
                         },
                         highlights: row_highlights,
+                        type_badge: Some(TypeBadge::of(val)),
+                        parse_error: None,
+                        annotation: self.timestamp_note(None, val),
                     });
 
                     if is_expanded {
@@ -418,13 +1391,24 @@ impl JsonTreeViewer {
                             .to_string(),
                             text_token: (TextToken::Bracket, None),
                             highlights: RowHighlights::default(),
+                            type_badge: None,
+                            parse_error: None,
+                            annotation: None,
                         });
                     }
                 }
+                if window.end < arr.len() {
+                    self.rows.push(array_pager_row(
+                        path,
+                        ARRAY_MORE_SUFFIX,
+                        indent,
+                        format!("… {} more elements", arr.len() - window.end),
+                    ));
+                }
             }
             _ => {
                 // Primitives
-                let display_text = preview_value(value).to_string();
+                let display_text = preview_value(value, self.number_format);
                 let row_highlights = compute_row_highlights(
                     &display_text,
                     highlights_map.and_then(|map| map.get(path)),
@@ -437,6 +1421,9 @@ impl JsonTreeViewer {
                     display_text,
                     text_token: (TextToken::from(value), None),
                     highlights: row_highlights,
+                    type_badge: Some(TypeBadge::of(value)),
+                    parse_error: None,
+                    annotation: self.timestamp_note(None, value),
                 });
             }
         }
@@ -448,21 +1435,34 @@ impl JsonTreeViewer {
         &mut self,
         ui: &mut Ui,
         selected: &mut Option<String>,
+        multi_selection: &mut MultiSelection,
         cache: &mut LruCache<usize, Value>,
         loader: &mut FileType,
         should_scroll_to_selection: &mut bool,
         is_search_navigation: bool,
         syntax_highlighting: bool,
+        indent_size: f32,
+        show_type_badges: bool,
     ) -> bool {
         #[cfg(feature = "profiling")]
         puffin::profile_function!();
 
         let row_count = self.rows.len();
-        let row_height = ROW_HEIGHT;
+        let row_height = tree_row_height(ui);
 
         let mut toggles: Vec<String> = Vec::new();
         let mut new_selected: Option<String> = None;
+        let mut row_click: Option<(String, egui::Modifiers, bool)> = None;
         let mut copy_clipboard: Option<String> = None;
+        let mut sort_request: Option<RootSort> = None;
+        let mut view_formatted_request: Option<usize> = None;
+        let mut view_raw_bytes_request: Option<usize> = None;
+        let mut field_stats_request: Option<String> = None;
+        let mut match_jump: Option<usize> = None;
+        let mut toggle_flat: Option<String> = None;
+        let mut siblings_request: Option<(String, bool)> = None;
+        let mut page_request: Option<(String, ArrayPage)> = None;
+        let flat = self.flat;
 
         // Make the scroll area interactive so clicking it removes focus from search input
         let scroll_area_response = ui.interact(
@@ -471,6 +1471,13 @@ impl JsonTreeViewer {
             egui::Sense::click(),
         );
 
+        // Search hits are marked in a strip to the right of the rows
+        let hit_strip = (!self.hit_rows.is_empty()).then(|| {
+            let full = ui.available_rect_before_wrap();
+            ui.set_max_width(full.width() - HIT_STRIP_WIDTH);
+            full.with_min_x(full.right() - HIT_STRIP_WIDTH)
+        });
+
         if scroll_area_response.clicked() {
             ui.memory_mut(|mem| mem.request_focus(scroll_area_response.id));
         }
@@ -485,17 +1492,25 @@ impl JsonTreeViewer {
             *should_scroll_to_selection = false;
         }
 
-        let scroll_area = egui::ScrollArea::both()
+        let mut scroll_area = egui::ScrollArea::both()
             .auto_shrink([false, false])
             .id_salt("json_tree_scroll");
+        if let Some(offset) = self.pending_scroll_offset.take() {
+            scroll_area = scroll_area.scroll_offset(offset);
+        }
 
         let search_target = self.search_target_row;
+        let scroll_margin = self.scroll_margin;
         let mut target_reached = false;
+        let editable = self.inline_editing && loader.supports_editing();
+        let mut edit_request: Option<String> = None;
+        let mut edit_action: Option<bool> = None;
 
-        scroll_area.show_rows(ui, row_height, row_count, |ui, row_range| {
+        let scroll_output = scroll_area.show_rows(ui, row_height, row_count, |ui, row_range| {
             // Handle search navigation with incremental scrolling (persists across frames)
             if let Some(target_row) = search_target {
-                target_reached = scroll_to_search_target(ui, &row_range, target_row, row_height);
+                target_reached =
+                    scroll_to_search_target(ui, &row_range, target_row, row_height, scroll_margin);
             }
 
             // Handle keyboard navigation
@@ -509,40 +1524,85 @@ impl JsonTreeViewer {
                         &row_range,
                         row_idx,
                         row_height,
+                        scroll_margin,
                         should_scroll_to_selection,
                     );
                 }
             }
 
-            // Get indent guide color from theme
-            let guide_color = ui.ctx().memory(|mem| {
-                mem.data
-                    .get_temp::<crate::theme::ThemeColors>(egui::Id::new("theme_colors"))
-                    .map(|colors| colors.indent_guide)
-                    .unwrap_or_else(|| egui::Color32::from_rgb(100, 100, 100))
-            });
+            // Get indent guide, error and accent colors from theme
+            let (guide_color, active_guide_color, error_color, accent_color) =
+                ui.ctx().memory(|mem| {
+                    let colors = mem
+                        .data
+                        .get_temp::<crate::theme::ThemeColors>(egui::Id::new("theme_colors"))
+                        .unwrap_or_else(|| {
+                            crate::theme::Theme::for_dark_mode(ui.visuals().dark_mode).colors()
+                        });
+                    (
+                        colors.indent_guide,
+                        colors.fg_muted,
+                        colors.error,
+                        colors.accent,
+                    )
+                });
+            let guide_ancestors = match (self.indent_guides, selected.as_deref()) {
+                (IndentGuides::HighlightActive, Some(selected)) => {
+                    self.selection_ancestors(selected)
+                }
+                _ => Vec::new(),
+            };
+
+            let multi = multi_selection.active(selected);
+            // The other end of the selected container, tinted so its extent
+            // is visible without counting indentation
+            let partner = selected
+                .as_deref()
+                .filter(|path| is_value_row(path) || path.ends_with("/_close"))
+                .map(bracket_partner);
 
             for row_index in row_range {
                 if let Some(row) = self.rows.get(row_index) {
                     let path = &row.path;
                     let display = &row.display_text;
-                    let display2_parts: Vec<&str> = display.splitn(2, ':').collect();
-                    let is_key_display = display2_parts.len() == 2 && row.text_token.1.is_some();
-                    let display2 = if is_key_display {
-                        display2_parts.get(1).unwrap_or(&"")
-                    } else {
-                        ""
-                    };
-
-                    // Selected background with alternating colors
-                    let bg = if selected.as_deref() == Some(path.as_str()) {
+                    let (key_part, value_part) = split_display_text(display);
+                    let is_key_display = display.contains(':') && row.text_token.1.is_some();
+                    let display2 = if is_key_display { value_part } else { "" };
+
+                    // Schema violations of this row's record, if it failed validation
+                    let violations = split_root_rel(path)
+                        .ok()
+                        .and_then(|(root, _)| self.schema_violations.get(&root));
+                    let is_open_row = !path.ends_with("/_close");
+                    let is_violation =
+                        is_open_row && violations.is_some_and(|paths| paths.contains(path));
+
+                    // Selected background with alternating colors; failing values
+                    // and malformed records are tinted
+                    let in_multi = multi.is_some_and(|paths| paths.contains(path));
+                    let bg = if in_multi || selected.as_deref() == Some(path.as_str()) {
                         selected_row_bg(ui)
+                    } else if partner.as_deref() == Some(path.as_str()) {
+                        accent_color.gamma_multiply(0.15)
+                    } else if is_violation || row.parse_error.is_some() {
+                        error_color.gamma_multiply(0.2)
                     } else {
                         row_fill(row_index, ui)
                     };
 
+                    // Invalid and malformed records get a marker on their root row
+                    let marker = (row.indent == 0
+                        && is_open_row
+                        && (violations.is_some() || row.parse_error.is_some()))
+                    .then(|| {
+                        DataRowIcon::builder()
+                            .glyph(egui_phosphor::regular::WARNING_CIRCLE)
+                            .color(thoth_plugin_sdk::theme::color_to_hex(error_color))
+                            .build()
+                    });
+
                     // Draw indent guide lines before rendering row content
-                    if row.indent > 0 {
+                    if row.indent > 0 && self.indent_guides != IndentGuides::None {
                         let painter = ui.painter();
                         let rect = ui.available_rect_before_wrap();
                         let row_y_min = rect.min.y;
@@ -550,14 +1610,69 @@ impl JsonTreeViewer {
 
                         // Draw a vertical line for each indent level
                         for level in 0..row.indent {
-                            let x = rect.min.x + (level as f32 * 16.0) + 8.0;
+                            let x = rect.min.x + indent_guide_offset(level, indent_size);
+                            let color = if is_active_guide(path, level, &guide_ancestors) {
+                                active_guide_color
+                            } else {
+                                guide_color
+                            };
                             painter.line_segment(
                                 [egui::pos2(x, row_y_min), egui::pos2(x, row_y_max)],
-                                egui::Stroke::new(1.0, guide_color),
+                                egui::Stroke::new(1.0, color),
                             );
                         }
                     }
 
+                    // Long arrays page through their elements from these rows
+                    let pager = path
+                        .strip_suffix(ARRAY_PREV_SUFFIX)
+                        .map(|array| (array, false))
+                        .or_else(|| {
+                            path.strip_suffix(ARRAY_MORE_SUFFIX)
+                                .map(|array| (array, true))
+                        });
+                    if let Some((array, is_more)) = pager {
+                        if let Some(window) = self.array_windows.get(array)
+                            && let Some(page) = show_array_pager(
+                                ui,
+                                array,
+                                display,
+                                *window,
+                                is_more,
+                                row.indent,
+                                indent_size,
+                                row_height,
+                                bg,
+                            )
+                        {
+                            page_request = Some((array.to_string(), page));
+                        }
+                        continue;
+                    }
+
+                    if let Some(edit) = self.editing.as_mut().filter(|edit| edit.path == *path) {
+                        let key = is_key_display.then_some(key_part);
+                        edit_action = show_inline_editor(
+                            ui,
+                            edit,
+                            key,
+                            row.indent,
+                            indent_size,
+                            row_height,
+                            bg,
+                        );
+                        continue;
+                    }
+
+                    let badge = row.type_badge.as_ref().filter(|_| show_type_badges);
+
+                    // Matched records show their match count on the root row
+                    let match_count = path
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|_| row.indent == 0)
+                        .and_then(|root| Some((root, *self.match_counts.get(&root)?)));
+
                     // Render the full tree row — DataRow owns indent + caret.
                     let output = DataRow::builder()
                         .display_text(display.clone())
@@ -571,28 +1686,110 @@ impl JsonTreeViewer {
                         .highlights(row.highlights.clone())
                         .syntax_highlighting(syntax_highlighting)
                         .indent(row.indent)
+                        .indent_step(indent_size)
+                        .height(row_height)
                         .maybe_caret(row.is_expandable.then_some(row.is_expanded))
+                        .maybe_leading_icon(marker)
+                        .maybe_trailing(badge.map(|badge| badge.label.clone()))
+                        .maybe_trailing_token(badge.map(|badge| badge.token))
+                        .maybe_annotation(row.annotation.clone())
+                        .maybe_count_badge(match_count.map(|(_, count)| count.to_string()))
+                        .maybe_count_badge_tooltip(match_count.map(|(_, count)| {
+                            format!(
+                                "{} {} in this record. Click to jump to the next",
+                                count,
+                                if count == 1 { "match" } else { "matches" }
+                            )
+                        }))
                         .build()
                         .show(ui);
 
+                    if let Some(reason) = &row.parse_error {
+                        output
+                            .response
+                            .clone()
+                            .on_hover_text(format!("Malformed record: {}", reason));
+                    }
+
                     if output.caret_clicked {
                         toggles.push(path.clone());
+                    } else if output.badge_clicked {
+                        match_jump = match_count.map(|(root, _)| root);
                     } else if output.clicked || output.right_clicked {
-                        new_selected = Some(path.clone());
+                        let modifiers = ui.input(|i| i.modifiers);
+                        row_click = Some((path.clone(), modifiers, output.right_clicked));
+                    }
+
+                    if editable
+                        && !row.is_expandable
+                        && is_open_row
+                        && output.response.double_clicked()
+                    {
+                        edit_request = Some(path.clone());
                     }
 
                     // Context menu using the response from DataRow
                     output.response.context_menu(|ui| {
-                        let config = ContextMenuConfig::from_display(is_key_display, display2);
-                        render_context_menu(ui, &config, |action| {
-                            if let Some(text) = execute_context_menu_action(
-                                action,
-                                self,
-                                &Some(path.clone()),
-                                cache,
-                                loader,
-                            ) {
-                                copy_clipboard = Some(text);
+                        let mut config = ContextMenuConfig::from_display(is_key_display, display2);
+                        let (root, field) = match split_root_rel(&path) {
+                            Ok((root, rel)) => {
+                                (Some(root), Some(rel).filter(|rel| !rel.is_empty()))
+                            }
+                            Err(_) => (None, None),
+                        };
+                        config.show_sort = config.show_copy_value && field.is_some();
+                        config.show_field_stats = config.show_sort;
+                        config.show_view_formatted = root.is_some();
+                        config.show_view_raw_bytes = root.is_some();
+                        config.show_copy_line_number = root.is_some() && loader.has_line_numbers();
+                        config.show_copy_path_value = flat && field.is_some();
+                        config.show_toggle_flat_view = root.is_some();
+                        config.show_siblings = !flat && field.is_some();
+                        config.flat_view = flat;
+                        config.selected_rows =
+                            multi.filter(|_| in_multi).map_or(0, |paths| paths.len());
+                        render_context_menu(ui, &config, |action| match action {
+                            ContextMenuAction::CopySelectedRows => {
+                                copy_clipboard = multi.and_then(|paths| {
+                                    self.copy_selected_rows(paths, cache, loader)
+                                });
+                            }
+                            ContextMenuAction::SortAscending
+                            | ContextMenuAction::SortDescending => {
+                                sort_request = field.map(|field| RootSort {
+                                    field: field.to_string(),
+                                    ascending: action == ContextMenuAction::SortAscending,
+                                });
+                            }
+                            ContextMenuAction::ViewFormatted => {
+                                view_formatted_request = root;
+                            }
+                            ContextMenuAction::ViewRawBytes => {
+                                view_raw_bytes_request = root;
+                            }
+                            ContextMenuAction::FieldStats => {
+                                field_stats_request = field.map(str::to_string);
+                            }
+                            ContextMenuAction::ToggleFlatView => {
+                                toggle_flat = Some(path.clone());
+                            }
+                            ContextMenuAction::ExpandSiblings
+                            | ContextMenuAction::CollapseSiblings => {
+                                siblings_request = Some((
+                                    path.clone(),
+                                    action == ContextMenuAction::ExpandSiblings,
+                                ));
+                            }
+                            _ => {
+                                if let Some(text) = execute_context_menu_action(
+                                    action,
+                                    self,
+                                    &Some(path.clone()),
+                                    cache,
+                                    loader,
+                                ) {
+                                    copy_clipboard = Some(text);
+                                }
                             }
                         });
                     });
@@ -604,21 +1801,88 @@ impl JsonTreeViewer {
         if target_reached {
             self.search_target_row = None;
         }
+        self.scroll_offset = scroll_output.state.offset;
+
+        // Jump to a search hit picked from the strip
+        if let Some(strip) = hit_strip {
+            let strip = strip
+                .with_min_y(scroll_output.inner_rect.top())
+                .with_max_y(scroll_output.inner_rect.bottom());
+            if let Some(row_index) = self.show_hit_strip(ui, strip, &scroll_output, row_height) {
+                self.search_target_row = Some(row_index);
+                new_selected = Some(self.rows[row_index].path.clone());
+            }
+        }
+
+        // Plain clicks select one row, Shift-click a range from the anchor
+        // and Ctrl/Cmd-click adds or removes a row. Right-clicking inside a
+        // multi-row selection keeps it for the context menu.
+        if let Some((path, modifiers, right_click)) = row_click {
+            let in_multi = multi_selection
+                .active(selected)
+                .is_some_and(|paths| paths.contains(&path));
+            if right_click {
+                if !in_multi {
+                    multi_selection.clear();
+                }
+                *selected = Some(path);
+            } else if modifiers.shift {
+                let anchor = multi_selection
+                    .anchor_for(selected)
+                    .unwrap_or_else(|| path.clone());
+                let range = self.rows_between(&anchor, &path);
+                multi_selection.set_range(anchor, range);
+                *selected = Some(path);
+            } else if modifiers.command {
+                multi_selection.toggle(selected, &path);
+            } else {
+                multi_selection.clear();
+                *selected = Some(path);
+            }
+        }
 
         if let Some(sel) = new_selected {
             *selected = Some(sel);
         }
 
-        if let Some(text) = copy_clipboard {
-            ui.output_mut(|o| o.commands.push(egui::OutputCommand::CopyText(text)));
+        if copy_clipboard.is_some() {
+            self.copy_request = copy_clipboard;
+        }
+
+        if sort_request.is_some() {
+            self.sort_request = sort_request;
+        }
+
+        if view_formatted_request.is_some() {
+            self.view_formatted_request = view_formatted_request;
+        }
+
+        if view_raw_bytes_request.is_some() {
+            self.view_raw_bytes_request = view_raw_bytes_request;
+        }
+
+        if field_stats_request.is_some() {
+            self.field_stats_request = field_stats_request;
         }
 
         // Reset scroll flag after rendering
         *should_scroll_to_selection = false;
 
+        // Open, commit or cancel an inline edit
+        let mut edited = false;
+        if let Some(path) = edit_request {
+            self.start_edit(path, cache, loader);
+        } else {
+            match edit_action {
+                Some(true) => edited = self.commit_edit(cache, loader),
+                Some(false) => self.editing = None,
+                None => {}
+            }
+        }
+
         // Handle toggles
-        let needs_rebuild = !toggles.is_empty();
-        if needs_rebuild {
+        let mut needs_rebuild = !toggles.is_empty() || edited;
+        if !toggles.is_empty() {
             for path in toggles {
                 if !self.expanded.insert(path.clone()) {
                     self.expanded.remove(&path);
@@ -626,10 +1890,213 @@ impl JsonTreeViewer {
             }
         }
 
+        if let Some((path, expand)) = siblings_request {
+            needs_rebuild |= self.set_siblings_expanded(&path, expand);
+        }
+
+        // Move a long array's window, selecting the element jumped to
+        if let Some((array, page)) = page_request {
+            if let Some(target) = self.page_array(&array, page) {
+                *selected = Some(target);
+                *should_scroll_to_selection = true;
+            }
+            needs_rebuild = true;
+        }
+
+        // Switch views, keeping the row the menu was opened on selected
+        if let Some(path) = toggle_flat {
+            let path = Some(path);
+            self.toggle_flat_view(&path);
+            *selected = path;
+            *should_scroll_to_selection = true;
+            needs_rebuild = true;
+        }
+
+        // Step to the next match in the record whose count badge was clicked
+        if let Some(root) = match_jump
+            && let Some(path) = self.next_match_path(root, selected.as_deref())
+        {
+            self.reveal_path(&path);
+            *selected = Some(path);
+            *should_scroll_to_selection = true;
+            needs_rebuild = true;
+        }
+
         needs_rebuild
     }
 }
 
+impl JsonTreeViewer {
+    /// Open an inline editor on the primitive value at `path`
+    fn start_edit(
+        &mut self,
+        path: String,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) {
+        let Ok((root, rel)) = split_root_rel(&path) else {
+            return;
+        };
+        let Some(record) = load_root_value(root, cache, loader) else {
+            return;
+        };
+        // Strings that would otherwise parse as another type are shown quoted
+        let text = match lookup_rel(&record, rel) {
+            Ok(Value::Object(_) | Value::Array(_)) | Err(_) => return,
+            Ok(value @ Value::String(s)) if parse_edited_value(s) == *value => s.clone(),
+            Ok(value) => value.to_string(),
+        };
+        self.editing = Some(InlineEdit {
+            path,
+            text,
+            error: None,
+            request_focus: true,
+        });
+    }
+
+    /// Write the open inline edit into its record, marking the record unsaved
+    /// Returns true if rows need rebuilding; a failed edit stays open with its error
+    fn commit_edit(&mut self, cache: &mut LruCache<usize, Value>, loader: &mut FileType) -> bool {
+        let Some(mut edit) = self.editing.take() else {
+            return false;
+        };
+        let result = (|| -> Result<()> {
+            let (root, rel) = split_root_rel(&edit.path)?;
+            let mut record =
+                load_root_value(root, cache, loader).ok_or_else(|| ThothError::StateError {
+                    reason: format!("Record {} could not be loaded", root),
+                })?;
+            *lookup_rel_mut(&mut record, rel)? = parse_edited_value(&edit.text);
+            loader.set_record(root, record.clone())?;
+            cache.put(root, record);
+            Ok(())
+        })();
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                edit.error = Some(e.to_string());
+                self.editing = Some(edit);
+                false
+            }
+        }
+    }
+}
+
+/// Draw the text field of an inline edit in place of its row
+///
+/// Returns `Some(true)` to commit (Enter or clicking away), `Some(false)` to
+/// cancel (Escape) and `None` while editing continues.
+fn show_inline_editor(
+    ui: &mut Ui,
+    edit: &mut InlineEdit,
+    key: Option<&str>,
+    indent: usize,
+    indent_size: f32,
+    row_height: f32,
+    bg: egui::Color32,
+) -> Option<bool> {
+    let mut action = None;
+    egui::Frame::new().fill(bg).show(ui, |ui| {
+        ui.allocate_ui_with_layout(
+            egui::vec2(ui.available_width(), row_height),
+            egui::Layout::left_to_right(egui::Align::Center),
+            |ui| {
+                // Line the field up with the value column of leaf rows (indent + caret)
+                ui.add_space(indent as f32 * indent_size + row_height);
+                if let Some(key) = key {
+                    ui.label(format!("{}:", key.trim()));
+                }
+                let mut field = egui::TextEdit::singleline(&mut edit.text)
+                    .desired_width(f32::INFINITY)
+                    .font(egui::TextStyle::Monospace);
+                if edit.error.is_some() {
+                    field = field.text_color(crate::theme::ThemeColors::from_ctx(ui.ctx()).error);
+                }
+                let response = ui.add(field);
+                if edit.request_focus {
+                    response.request_focus();
+                    edit.request_focus = false;
+                }
+                if response.lost_focus() {
+                    action = Some(!ui.input(|i| i.key_pressed(egui::Key::Escape)));
+                }
+                if let Some(error) = &edit.error {
+                    response.on_hover_text(error);
+                }
+            },
+        );
+    });
+    action
+}
+
+/// Pager row of a long array: the hidden element count, a button that moves
+/// the window by a page and, below the window, a jump-to-index field
+#[allow(clippy::too_many_arguments)]
+fn show_array_pager(
+    ui: &mut Ui,
+    array: &str,
+    text: &str,
+    window: ArrayWindow,
+    is_more: bool,
+    indent: usize,
+    indent_size: f32,
+    row_height: f32,
+    bg: egui::Color32,
+) -> Option<ArrayPage> {
+    let mut page = None;
+    egui::Frame::new().fill(bg).show(ui, |ui| {
+        ui.allocate_ui_with_layout(
+            egui::vec2(ui.available_width(), row_height),
+            egui::Layout::left_to_right(egui::Align::Center),
+            |ui| {
+                ui.add_space(indent as f32 * indent_size + row_height);
+                ui.weak(text);
+
+                let (label, hidden) = if is_more {
+                    ("Load next", window.len - window.end)
+                } else {
+                    ("Show previous", window.start)
+                };
+                let button = Button::builder()
+                    .label(format!("{} {}", label, hidden.min(ARRAY_WINDOW_SIZE)))
+                    .button_type(ButtonType::Text)
+                    .color(ButtonColor::Primary)
+                    .build();
+                if ui.add(button).clicked() {
+                    page = Some(if is_more {
+                        ArrayPage::Next
+                    } else {
+                        ArrayPage::Previous
+                    });
+                }
+                if !is_more {
+                    return;
+                }
+
+                // Typed index survives across frames in egui's temp storage
+                let id = egui::Id::new(("array_jump", array));
+                let mut input = ui.data(|d| d.get_temp::<String>(id)).unwrap_or_default();
+                ui.label("Jump to index");
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut input)
+                        .hint_text(format!("0–{}", window.len - 1))
+                        .desired_width(80.0)
+                        .font(egui::TextStyle::Monospace),
+                );
+                if response.lost_focus()
+                    && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                    && let Ok(idx) = input.trim().parse()
+                {
+                    page = Some(ArrayPage::JumpTo(idx));
+                    input.clear();
+                }
+                ui.data_mut(|d| d.insert_temp(id, input));
+            },
+        );
+    });
+    page
+}
+
 // Implement ContextMenuHandler trait for JsonTreeViewer
 impl ContextMenuHandler for JsonTreeViewer {
     fn copy_selected_key(&self, selected: &Option<String>) -> Option<String> {
@@ -647,18 +2114,23 @@ impl ContextMenuHandler for JsonTreeViewer {
         cache: &mut LruCache<usize, Value>,
         loader: &mut FileType,
     ) -> Option<String> {
-        if let Some(path) = selected {
-            // Find the row to get display text
-            if let Some(row) = self.rows.iter().find(|r| r.path == *path) {
-                // Parse display text to extract value part
-                let parts: Vec<&str> = row.display_text.splitn(2, ':').collect();
-                if parts.len() == 2 {
-                    return Some(parts[1].trim().to_string());
-                }
-            }
+        let (root_idx, rel) = split_root_rel(selected.as_ref()?).ok()?;
+        let root = load_root_value(root_idx, cache, loader)?;
+        match lookup_rel(&root, rel).ok()? {
+            Value::String(s) => Some(s.clone()),
+            value => serde_json::to_string(value).ok(),
         }
-        let _ = (cache, loader); // Suppress unused warnings for now
-        None
+    }
+
+    fn copy_selected_value_json(
+        &self,
+        selected: &Option<String>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String> {
+        let (root_idx, rel) = split_root_rel(selected.as_ref()?).ok()?;
+        let root = load_root_value(root_idx, cache, loader)?;
+        serde_json::to_string(lookup_rel(&root, rel).ok()?).ok()
     }
 
     fn copy_selected_object(
@@ -670,28 +2142,94 @@ impl ContextMenuHandler for JsonTreeViewer {
         if let Some(path) = selected
             && let Ok((root_idx, rel)) = split_root_rel(path)
         {
-            // Try to get from cache first
-            let value = if let Some(v) = cache.get(&root_idx) {
-                v.clone()
-            } else {
-                // Load from file
-                match loader.get(root_idx) {
-                    Ok(v) => {
-                        cache.put(root_idx, v.clone());
-                        v
-                    }
-                    Err(_) => return None,
-                }
-            };
-
+            let value = load_root_value(root_idx, cache, loader)?;
             return get_object_string(value, rel).ok();
         }
         None
     }
 
-    fn copy_selected_path(&self, selected: &Option<String>) -> Option<String> {
-        selected.clone()
+    fn copy_selected_object_minified(
+        &self,
+        selected: &Option<String>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String> {
+        let (root_idx, rel) = split_root_rel(selected.as_ref()?).ok()?;
+        let value = load_root_value(root_idx, cache, loader)?;
+        get_object_string_minified(value, rel).ok()
+    }
+
+    fn copy_selected_path(
+        &self,
+        selected: &Option<String>,
+        format: PathFormat,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String> {
+        let path = selected.as_ref()?;
+        // The record tells keys holding `.` or `[` apart from nesting
+        let root = split_root_rel(path)
+            .ok()
+            .and_then(|(root_idx, _)| load_root_value(root_idx, cache, loader));
+        format_path(path, root.as_ref(), format, loader.is_single_document())
+    }
+
+    fn copy_selected_path_value(
+        &self,
+        selected: &Option<String>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String> {
+        let (root_idx, rel) = split_root_rel(selected.as_ref()?).ok()?;
+        let root = load_root_value(root_idx, cache, loader)?;
+        let value = serde_json::to_string(lookup_rel(&root, rel).ok()?).ok()?;
+        let rel = rel.strip_prefix('.').unwrap_or(rel);
+        Some(format!("{} = {}", rel, value))
+    }
+
+    fn copy_selected_line_number(
+        &self,
+        selected: &Option<String>,
+        loader: &FileType,
+    ) -> Option<String> {
+        let (root_idx, _) = split_root_rel(selected.as_ref()?).ok()?;
+        let line = loader.line_number(root_idx).ok()??;
+        Some(line.to_string())
+    }
+
+    fn copy_selected_typescript(
+        &self,
+        selected: &Option<String>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String> {
+        let path = selected.as_ref()?;
+        let (root_idx, rel) = split_root_rel(path).ok()?;
+        let root = load_root_value(root_idx, cache, loader)?;
+        let value = if rel.is_empty() {
+            root
+        } else {
+            walk_rel(root, rel).ok()?
+        };
+        Some(infer_typescript_interface(
+            &value,
+            &interface_name_from_path(path),
+        ))
+    }
+}
+
+/// Fetch a root record, going through the LRU cache first.
+fn load_root_value(
+    root_idx: usize,
+    cache: &mut LruCache<usize, Value>,
+    loader: &mut FileType,
+) -> Option<Value> {
+    if let Some(v) = cache.get(&root_idx) {
+        return Some(v.clone());
     }
+    let v = loader.get(root_idx).ok()?;
+    cache.put(root_idx, v.clone());
+    Some(v)
 }
 
 // Implement FileFormatViewer trait for JsonTreeViewer
@@ -699,6 +2237,10 @@ impl FileFormatViewer for JsonTreeViewer {
     fn reset(&mut self) {
         self.expanded.clear();
         self.rows.clear();
+        self.expand_job = None;
+        self.sort_request = None;
+        self.editing = None;
+        self.array_windows.clear();
     }
 
     fn rebuild_view(
@@ -715,20 +2257,26 @@ impl FileFormatViewer for JsonTreeViewer {
         &mut self,
         ui: &mut Ui,
         selected: &mut Option<String>,
+        multi_selection: &mut MultiSelection,
         cache: &mut LruCache<usize, Value>,
         loader: &mut FileType,
         should_scroll_to_selection: &mut bool,
         is_search_navigation: bool,
         syntax_highlighting: bool,
+        indent_size: f32,
+        show_type_badges: bool,
     ) -> bool {
         self.render(
             ui,
             selected,
+            multi_selection,
             cache,
             loader,
             should_scroll_to_selection,
             is_search_navigation,
             syntax_highlighting,
+            indent_size,
+            show_type_badges,
         )
     }
 
@@ -762,24 +2310,111 @@ impl FileFormatViewer for JsonTreeViewer {
     }
 
     fn expand_all(&mut self) -> bool {
-        // Expand all expandable rows
-        let paths_to_expand: Vec<String> = self
+        // Queue every visible root; the actual expansion happens in
+        // `step_expand_all` so large records are spread across frames
+        let pending_roots: VecDeque<usize> = self
             .rows
             .iter()
-            .filter(|row| row.is_expandable && !row.is_expanded)
-            .map(|row| row.path.clone())
+            .filter(|row| row.indent == 0 && row.is_expandable)
+            .filter_map(|row| row.path.parse().ok())
             .collect();
 
-        if !paths_to_expand.is_empty() {
-            for path in paths_to_expand {
-                self.expanded.insert(path);
+        if pending_roots.is_empty() {
+            return false;
+        }
+
+        self.expand_job = Some(ExpandAllJob {
+            roots_total: pending_roots.len(),
+            pending_roots,
+            ..Default::default()
+        });
+        true
+    }
+
+    fn step_expand_all(
+        &mut self,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+        budget: usize,
+    ) -> bool {
+        let Some(job) = self.expand_job.as_mut() else {
+            return false;
+        };
+
+        let mut changed = false;
+        let mut remaining = budget;
+        while remaining > 0 {
+            if let Some(path) = job.pending_paths.pop_front() {
+                changed |= self.expanded.insert(path);
+                job.processed += 1;
+                remaining -= 1;
+            } else if let Some(root) = job.pending_roots.pop_front() {
+                let value = if let Some(v) = cache.get(&root) {
+                    v.clone()
+                } else {
+                    match loader.get(root) {
+                        Ok(v) => {
+                            cache.put(root, v.clone());
+                            v
+                        }
+                        Err(_) => continue,
+                    }
+                };
+                let before = job.pending_paths.len();
+                collect_expandable_paths(&value, &root.to_string(), &mut job.pending_paths);
+                job.discovered += job.pending_paths.len() - before;
+            } else {
+                break;
             }
-            return true; // Need rebuild
         }
-        false
+
+        if job.is_done() {
+            self.expand_job = None;
+        }
+        changed
+    }
+
+    fn expand_all_progress(&self) -> Option<f32> {
+        self.expand_job.as_ref().map(ExpandAllJob::progress)
+    }
+
+    fn cancel_expand_all(&mut self) -> bool {
+        self.expand_job.take().is_some()
+    }
+
+    fn expand_to_depth(
+        &mut self,
+        depth: usize,
+        visible_roots: &Option<Vec<usize>>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+        total_len: usize,
+    ) -> DepthExpansion {
+        self.set_expansion_depth(depth, visible_roots, cache, loader, total_len)
+    }
+
+    fn collapse_to_depth(
+        &mut self,
+        depth: usize,
+        visible_roots: &Option<Vec<usize>>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+        total_len: usize,
+    ) -> DepthExpansion {
+        self.set_expansion_depth(depth, visible_roots, cache, loader, total_len)
+    }
+
+    fn expanded_depth(&self) -> usize {
+        self.rows
+            .iter()
+            .filter(|row| row.is_expanded)
+            .map(|row| row.indent + 1)
+            .max()
+            .unwrap_or(0)
     }
 
     fn collapse_all(&mut self) -> bool {
+        self.expand_job = None;
         if !self.expanded.is_empty() {
             self.expanded.clear();
             return true; // Need rebuild
@@ -837,6 +2472,49 @@ impl FileFormatViewer for JsonTreeViewer {
         Some(self.rows.first()?.path.clone())
     }
 
+    fn rows_between(&self, from: &str, to: &str) -> Vec<String> {
+        let position = |path: &str| self.rows.iter().position(|row| row.path == path);
+        let (Some(start), Some(end)) = (position(from), position(to)) else {
+            return vec![to.to_string()];
+        };
+        self.rows[start.min(end)..=start.max(end)]
+            .iter()
+            .map(|row| row.path.clone())
+            .collect()
+    }
+
+    fn select_first_child(&self, current: &Option<String>) -> Option<String> {
+        let path = current.as_ref()?;
+        let idx = self.rows.iter().position(|r| r.path == *path)?;
+        if !self.rows[idx].is_expanded {
+            return None;
+        }
+        // An empty container is followed straight by its own closing row
+        self.rows
+            .get(idx + 1)
+            .filter(|next| next.path.strip_suffix("/_close") != Some(path.as_str()))
+            .map(|next| next.path.clone())
+    }
+
+    fn select_parent(&self, current: &Option<String>) -> Option<String> {
+        let path = current.as_ref()?;
+        if let Some(parent) = parent_path(path)
+            && self.rows.iter().any(|r| r.path == parent)
+        {
+            return Some(parent.to_string());
+        }
+
+        // Keys containing '.' or '[' can't be trimmed reliably; fall back to
+        // the nearest row above that is indented less
+        let idx = self.rows.iter().position(|r| r.path == *path)?;
+        let indent = self.rows[idx].indent;
+        self.rows[..idx]
+            .iter()
+            .rev()
+            .find(|r| r.indent < indent)
+            .map(|r| r.path.clone())
+    }
+
     // ========================================================================
     // Clipboard Operations
     // ========================================================================
@@ -865,18 +2543,215 @@ impl FileFormatViewer for JsonTreeViewer {
         ContextMenuHandler::copy_selected_object(self, selected, cache, loader)
     }
 
-    fn copy_selected_path(&self, selected: &Option<String>) -> Option<String> {
-        ContextMenuHandler::copy_selected_path(self, selected)
+    fn copy_selected_object_minified(
+        &self,
+        selected: &Option<String>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String> {
+        ContextMenuHandler::copy_selected_object_minified(self, selected, cache, loader)
     }
 
-    fn navigate_to_root(&mut self, root_index: usize) -> bool {
-        // Create the path for the root record (e.g., "0", "1", "2")
-        let path = root_index.to_string();
-
-        // Expand this root record to show its contents
-        self.expanded.insert(path);
-
-        // Need to rebuild the view since we expanded a node
+    fn copy_selected_path(
+        &self,
+        selected: &Option<String>,
+        format: PathFormat,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String> {
+        ContextMenuHandler::copy_selected_path(self, selected, format, cache, loader)
+    }
+
+    fn copy_selected_line_number(
+        &self,
+        selected: &Option<String>,
+        loader: &FileType,
+    ) -> Option<String> {
+        ContextMenuHandler::copy_selected_line_number(self, selected, loader)
+    }
+
+    fn selected_rows(&self, paths: &HashSet<String>) -> Vec<String> {
+        let inside_selected = |path: &str| {
+            let mut current = path;
+            while let Some(parent) = parent_path(current) {
+                if paths.contains(parent) {
+                    return true;
+                }
+                current = parent;
+            }
+            false
+        };
+        self.rows
+            .iter()
+            .map(|row| &row.path)
+            .filter(|path| paths.contains(*path) && is_value_row(path) && !inside_selected(path))
+            .cloned()
+            .collect()
+    }
+
+    fn copy_selected_rows(
+        &self,
+        paths: &HashSet<String>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String> {
+        let lines: Vec<String> = self
+            .selected_rows(paths)
+            .into_iter()
+            .filter_map(|path| {
+                let value = self.copy_selected_value_json(&Some(path.clone()), cache, loader)?;
+                Some(format!("{}: {}", path, value))
+            })
+            .collect();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    fn expanded_paths(&self) -> Option<&HashSet<String>> {
+        Some(&self.expanded)
+    }
+
+    fn set_expanded_paths(&mut self, expanded: HashSet<String>) {
+        self.expanded = expanded;
+    }
+
+    fn show_in_array_windows(&mut self, path: &str) {
+        let mut current = path;
+        while let Some(parent) = parent_path(current) {
+            if let Some(idx) = array_index(parent, current) {
+                let start = idx - idx % ARRAY_WINDOW_SIZE;
+                match self.array_windows.get_mut(parent) {
+                    Some(window) if (window.start..window.end).contains(&idx) => {}
+                    Some(window) => {
+                        window.start = start;
+                        window.end = start + ARRAY_WINDOW_SIZE;
+                    }
+                    // Arrays without a window show the first page; the length
+                    // is filled in (and the window clamped) on the next rebuild
+                    None if idx >= ARRAY_WINDOW_SIZE => {
+                        self.array_windows.insert(
+                            parent.to_string(),
+                            ArrayWindow {
+                                start,
+                                end: start + ARRAY_WINDOW_SIZE,
+                                len: usize::MAX,
+                            },
+                        );
+                    }
+                    None => {}
+                }
+            }
+            current = parent;
+        }
+    }
+
+    fn take_sort_request(&mut self) -> Option<RootSort> {
+        self.sort_request.take()
+    }
+
+    fn toggle_flat_view(&mut self, selected: &Option<String>) -> bool {
+        self.flat = !self.flat;
+        self.editing = None;
+        if self.flat
+            && let Some((root, _)) = selected
+                .as_deref()
+                .and_then(|path| split_root_rel(path).ok())
+        {
+            self.expanded.insert(root.to_string());
+        }
+        true
+    }
+
+    fn take_view_formatted_request(&mut self) -> Option<usize> {
+        self.view_formatted_request.take()
+    }
+
+    fn take_view_raw_bytes_request(&mut self) -> Option<usize> {
+        self.view_raw_bytes_request.take()
+    }
+
+    fn take_field_stats_request(&mut self) -> Option<String> {
+        self.field_stats_request.take()
+    }
+
+    fn take_copy_request(&mut self) -> Option<String> {
+        self.copy_request.take()
+    }
+
+    fn set_schema_violations(&mut self, record_index: usize, paths: HashSet<String>) {
+        self.schema_violations.insert(record_index, paths);
+    }
+
+    fn set_inline_editing(&mut self, enabled: bool) {
+        self.inline_editing = enabled;
+        if !enabled {
+            self.editing = None;
+        }
+    }
+
+    fn set_number_format(&mut self, format: NumberFormat) {
+        self.number_format = format;
+    }
+
+    fn set_indent_guides(&mut self, guides: IndentGuides) {
+        self.indent_guides = guides;
+    }
+
+    fn set_scroll_margin(&mut self, rows: usize) {
+        self.scroll_margin = rows;
+    }
+
+    fn scroll_offset(&self) -> egui::Vec2 {
+        self.scroll_offset
+    }
+
+    fn export_html(
+        &self,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+        title: &str,
+        colors: &ThemeColors,
+        indent_size: f32,
+    ) -> Option<String> {
+        Some(JsonTreeViewer::export_html(
+            self,
+            cache,
+            loader,
+            title,
+            colors,
+            indent_size,
+        ))
+    }
+
+    fn set_scroll_offset(&mut self, offset: egui::Vec2) {
+        self.pending_scroll_offset = Some(offset);
+    }
+
+    fn set_timestamp_fields(&mut self, fields: Option<&[String]>) {
+        if self.timestamp_fields.as_deref() != fields {
+            self.timestamp_fields = fields.map(<[String]>::to_vec);
+        }
+    }
+
+    fn set_tree_filter(&mut self, filter: &str) {
+        self.tree_filter = filter.trim().to_lowercase();
+    }
+
+    fn tree_filter_matches(&self) -> Option<usize> {
+        Some(self.tree_filter_matches)
+    }
+
+    fn clear_schema_violations(&mut self) {
+        self.schema_violations.clear();
+    }
+
+    fn navigate_to_root(&mut self, root_index: usize) -> bool {
+        // Create the path for the root record (e.g., "0", "1", "2")
+        let path = root_index.to_string();
+
+        // Expand this root record to show its contents
+        self.expanded.insert(path);
+
+        // Need to rebuild the view since we expanded a node
         true
     }
 }
@@ -1070,6 +2945,518 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parent_path_trims_last_segment() {
+        assert_eq!(parent_path("0.user.items[2]"), Some("0.user.items"));
+        assert_eq!(parent_path("0.user.items"), Some("0.user"));
+        assert_eq!(parent_path("0[1]"), Some("0"));
+        assert_eq!(parent_path("0.user/_close"), Some("0.user"));
+        assert_eq!(parent_path("12"), None);
+    }
+
+    #[test]
+    fn test_bracket_partner_pairs_open_and_close_rows() {
+        let json = r#"[{"a": 1, "b": {"c": "x"}}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        viewer.expanded.insert("0".to_string());
+        viewer.expanded.insert("0.b".to_string());
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        let has_row = |path: &str| viewer.rows.iter().any(|row| row.path == path);
+        assert_eq!(bracket_partner("0.b"), "0.b/_close");
+        assert!(has_row(&bracket_partner("0.b")));
+        assert_eq!(bracket_partner("0/_close"), "0");
+        assert!(has_row(&bracket_partner("0/_close")));
+        // A primitive has no closing row to pair with
+        assert!(!has_row(&bracket_partner("0.a")));
+    }
+
+    #[test]
+    fn test_right_arrow_expands_then_enters_first_child() {
+        let json = r#"[{"user": {"name": "a"}, "empty": {}}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        viewer.expanded.insert("0".to_string());
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        // Collapsed node: Right expands it and keeps the selection
+        let selected = Some("0.user".to_string());
+        assert!(viewer.expand_selected(&selected));
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        // Expanded node: Right moves to the first child
+        assert!(!viewer.expand_selected(&selected));
+        assert_eq!(
+            viewer.select_first_child(&selected),
+            Some("0.user.name".to_string())
+        );
+
+        // Leaf or empty container: nowhere to go
+        assert_eq!(
+            viewer.select_first_child(&Some("0.user.name".to_string())),
+            None
+        );
+        let empty = Some("0.empty".to_string());
+        assert!(viewer.expand_selected(&empty));
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert_eq!(viewer.select_first_child(&empty), None);
+    }
+
+    #[test]
+    fn test_left_arrow_collapses_then_moves_to_parent() {
+        let json = r#"[{"user": {"tags": ["x"]}}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        for path in ["0", "0.user", "0.user.tags"] {
+            viewer.expanded.insert(path.to_string());
+        }
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        // Leaf: Left moves to the parent
+        let leaf = Some("0.user.tags[0]".to_string());
+        assert!(!viewer.collapse_selected(&leaf));
+        assert_eq!(viewer.select_parent(&leaf), Some("0.user.tags".to_string()));
+
+        // Expanded node: Left collapses it and keeps the selection
+        let tags = Some("0.user.tags".to_string());
+        assert!(viewer.collapse_selected(&tags));
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        // Collapsed node: Left moves to the parent
+        assert!(!viewer.collapse_selected(&tags));
+        assert_eq!(viewer.select_parent(&tags), Some("0.user".to_string()));
+
+        // Closing bracket goes back to its opening row; roots have no parent
+        assert_eq!(
+            viewer.select_parent(&Some("0.user/_close".to_string())),
+            Some("0.user".to_string())
+        );
+        assert_eq!(viewer.select_parent(&Some("0".to_string())), None);
+    }
+
+    #[test]
+    fn test_collapse_and_expand_siblings() {
+        let json = r#"[{"a": {"x": 1}, "b.c": [1], "d": 2, "e": {"y": {"z": 3}}}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        for path in ["0", "0.a", "0.b.c", "0.e", "0.e.y"] {
+            viewer.expanded.insert(path.to_string());
+        }
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        // Collapsing leaves the node and nested nodes of the siblings alone
+        assert!(viewer.set_siblings_expanded("0.a", false));
+        let expanded: HashSet<&str> = viewer.expanded.iter().map(String::as_str).collect();
+        assert_eq!(expanded, HashSet::from(["0", "0.a", "0.e.y"]));
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert!(!viewer.set_siblings_expanded("0.a", false));
+
+        // Expanding opens only the containers next to the node
+        viewer.expanded.remove("0.a");
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert!(viewer.set_siblings_expanded("0.d", true));
+        let expanded: HashSet<&str> = viewer.expanded.iter().map(String::as_str).collect();
+        assert_eq!(
+            expanded,
+            HashSet::from(["0", "0.a", "0.b.c", "0.e", "0.e.y"])
+        );
+
+        // Root records have no siblings to toggle
+        assert!(!viewer.set_siblings_expanded("0", false));
+    }
+
+    #[test]
+    fn test_long_array_rows_are_windowed() {
+        let items: Vec<String> = (0..2500).map(|i| i.to_string()).collect();
+        let json = format!(r#"[{{"items": [{}]}}]"#, items.join(","));
+        let (mut loader, len) = make_json_array_loader(&json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        viewer.expanded.insert("0".to_string());
+        viewer.expanded.insert("0.items".to_string());
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        let has_row =
+            |viewer: &JsonTreeViewer, path: &str| viewer.rows.iter().any(|r| r.path == path);
+        // Root, array, one window of elements, pager and two closing rows
+        assert_eq!(viewer.rows.len(), ARRAY_WINDOW_SIZE + 5);
+        assert!(has_row(&viewer, "0.items[999]"));
+        assert!(!has_row(&viewer, "0.items[1000]"));
+        assert!(has_row(&viewer, "0.items/_more"));
+        assert!(!has_row(&viewer, "0.items/_prev"));
+
+        // Loading the next page extends the window
+        assert_eq!(viewer.page_array("0.items", ArrayPage::Next), None);
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert!(has_row(&viewer, "0.items[1999]"));
+        assert!(!has_row(&viewer, "0.items[2000]"));
+
+        // Jumping starts the window at the index, clamped to the end
+        assert_eq!(
+            viewer.page_array("0.items", ArrayPage::JumpTo(2400)),
+            Some("0.items[2400]".to_string())
+        );
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert!(has_row(&viewer, "0.items/_prev"));
+        assert!(has_row(&viewer, "0.items[2499]"));
+        assert!(!has_row(&viewer, "0.items/_more"));
+
+        // Revealing an element outside the window pages to it
+        viewer.reveal_path("0.items[1500]");
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert!(has_row(&viewer, "0.items[1000]"));
+        assert!(has_row(&viewer, "0.items[1500]"));
+        assert!(!has_row(&viewer, "0.items[2400]"));
+
+        // Copying the array still copies every element
+        let selected = Some("0.items".to_string());
+        let copied = ContextMenuHandler::copy_selected_object_minified(
+            &viewer,
+            &selected,
+            &mut cache,
+            &mut loader,
+        )
+        .unwrap();
+        let copied: Value = serde_json::from_str(&copied).unwrap();
+        assert_eq!(copied.as_array().map(Vec::len), Some(2500));
+    }
+
+    #[test]
+    fn test_select_parent_with_dotted_key() {
+        let json = r#"[{"a.b": {"c": 1}}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        viewer.expanded.insert("0".to_string());
+        viewer.expanded.insert("0.a.b".to_string());
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        assert_eq!(
+            viewer.select_parent(&Some("0.a.b.c".to_string())),
+            Some("0.a.b".to_string())
+        );
+        assert_eq!(
+            viewer.select_parent(&Some("0.a.b".to_string())),
+            Some("0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_match_badge_jumps_through_record_matches() {
+        let json = r#"[{"name": "x", "tags": ["x"]}, {"id": 1}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+
+        let fragment = |path: &str, component| MatchFragment {
+            target: MatchTarget::JsonField { component },
+            path: Some(Arc::from(path)),
+            confidence: 1.0,
+            text_range: Some(0..1),
+            ..MatchFragment::default()
+        };
+        let marker = MatchFragment {
+            target: MatchTarget::JsonField {
+                component: FieldComponent::EntireRow,
+            },
+            path: Some(Arc::from("0")),
+            confidence: crate::search::results::RECORD_MARKER_CONFIDENCE,
+            ..MatchFragment::default()
+        };
+        let fragments = vec![
+            fragment("0.name", FieldComponent::Value),
+            fragment("0.tags[0]", FieldComponent::Value),
+            fragment("0.tags[0]", FieldComponent::Value),
+            marker,
+        ];
+        let highlights = HashMap::from([(0, Arc::new(fragments))]);
+        viewer.set_highlights(&highlights, &HashMap::from([(0, 3)]));
+        assert_eq!(viewer.match_counts.get(&0), Some(&3));
+
+        // Starts at the first match, steps through distinct paths and wraps
+        let first = viewer.next_match_path(0, Some("0")).unwrap();
+        assert_eq!(first, "0.name");
+        let second = viewer.next_match_path(0, Some(first.as_str())).unwrap();
+        assert_eq!(second, "0.tags[0]");
+        assert_eq!(
+            viewer.next_match_path(0, Some(second.as_str())),
+            Some("0.name".to_string())
+        );
+        assert_eq!(viewer.next_match_path(1, None), None);
+
+        // Jumping into a collapsed record expands the way down
+        viewer.reveal_path(&second);
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert!(viewer.rows.iter().any(|row| row.path == second));
+
+        // Matched rows and the matched record's root get marks in the strip
+        let hit_paths: Vec<&str> = viewer
+            .hit_rows
+            .iter()
+            .map(|&row_index| viewer.rows[row_index].path.as_str())
+            .collect();
+        assert!(hit_paths.contains(&"0"));
+        assert!(hit_paths.contains(&"0.name"));
+        assert!(!hit_paths.contains(&"1"));
+
+        // Clearing the search drops the badges
+        viewer.set_highlights(&HashMap::new(), &HashMap::new());
+        assert!(viewer.match_counts.is_empty());
+        assert_eq!(viewer.next_match_path(0, None), None);
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert!(viewer.hit_rows.is_empty());
+    }
+
+    #[test]
+    fn test_nearest_hit_row() {
+        let hits = [2, 50, 90];
+        assert_eq!(nearest_hit_row(&hits, 100, 0.0), Some(2));
+        assert_eq!(nearest_hit_row(&hits, 100, 0.3), Some(50));
+        assert_eq!(nearest_hit_row(&hits, 100, 0.75), Some(90));
+        assert_eq!(nearest_hit_row(&hits, 100, 1.0), Some(90));
+        assert_eq!(nearest_hit_row(&[], 100, 0.5), None);
+    }
+
+    #[test]
+    fn test_flat_view_lists_leaves_of_open_roots() {
+        let json = r#"[{"server": {"hosts": ["a"], "port": 80}, "tags": []}, {"id": 2}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+
+        // Turning the flat view on opens the selected row's root only
+        assert!(viewer.toggle_flat_view(&Some("0.server".to_string())));
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert_eq!(
+            row_info(&viewer),
+            vec![
+                ("0".to_string(), true, "[0]: (3 values)".to_string()),
+                (
+                    "0.server.hosts[0]".to_string(),
+                    false,
+                    "server.hosts[0]: \"a\"".to_string()
+                ),
+                (
+                    "0.server.port".to_string(),
+                    false,
+                    "server.port: 80".to_string()
+                ),
+                ("0.tags".to_string(), false, "tags: []".to_string()),
+                ("1".to_string(), true, "[1]: (…) ".to_string()),
+            ]
+        );
+
+        // Switching back restores the tree rows
+        assert!(viewer.toggle_flat_view(&None));
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert!(viewer.rows.iter().any(|row| row.path == "0.server"));
+        assert!(viewer.rows.iter().any(|row| row.path == "0/_close"));
+    }
+
+    #[test]
+    fn test_flat_row_highlights_land_on_last_key() {
+        let terms = PathHighlightTerms {
+            key_ranges: vec![0..4],
+            value_ranges: vec![0..2],
+        };
+        let highlights =
+            compute_flat_row_highlights("server.port: \"8080\"", "server.port", Some(&terms));
+        assert_eq!(highlights.key_ranges, vec![7..11]);
+        assert_eq!(highlights.value_ranges, vec![2..4]);
+    }
+
+    #[test]
+    fn test_tree_filter_keeps_matches_and_ancestors() {
+        let (mut loader, len) = make_json_array_loader(
+            r#"[{"user": {"name": "Ada", "age": 36}, "tags": ["x"]}, {"id": 2}]"#,
+        );
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        for path in ["0", "0.user", "0.tags", "1"] {
+            viewer.expanded.insert(path.to_string());
+        }
+
+        viewer.set_tree_filter(" ADA ");
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        let paths: Vec<&str> = viewer.rows.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["0", "0.user", "0.user.name", "0.user/_close", "0/_close"]
+        );
+        assert_eq!(viewer.tree_filter_matches(), Some(1));
+        let name = &viewer.rows[2];
+        let (_, value_part) = split_display_text(&name.display_text);
+        assert_eq!(&value_part[name.highlights.value_ranges[0].clone()], "Ada");
+        // Filter matches aren't search hits
+        assert!(viewer.hit_rows.is_empty());
+
+        viewer.set_tree_filter("");
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert_eq!(viewer.rows.len(), 12);
+        assert_eq!(viewer.tree_filter_matches(), Some(0));
+    }
+
+    #[test]
+    fn test_find_ignore_case() {
+        assert_eq!(
+            find_ignore_case("\"Name\": \"nAMe\"", "name"),
+            [1..5, 9..13]
+        );
+        assert!(find_ignore_case("abc", "x").is_empty());
+    }
+
+    #[test]
+    fn test_row_highlights_cover_every_value_match() {
+        // "abcab" matched at both ends
+        let terms = PathHighlightTerms {
+            key_ranges: vec![],
+            value_ranges: vec![0..2, 3..5],
+        };
+        let text = "\"k\": \"abcab\"";
+        let highlights = compute_row_highlights(text, Some(&terms));
+        let (_, value_part) = split_display_text(text);
+        let matched: Vec<&str> = highlights
+            .value_ranges
+            .iter()
+            .map(|range| &value_part[range.clone()])
+            .collect();
+        assert_eq!(matched, vec!["ab", "ab"]);
+    }
+
+    #[test]
+    fn test_value_highlights_follow_escapes_and_multibyte_text() {
+        let value = Value::String("say \"héllo\" 日本".to_string());
+        let text = format_simple_kv("a:b", &value, NumberFormat::Plain);
+        let raw = value.as_str().unwrap();
+        let terms = PathHighlightTerms {
+            key_ranges: vec![0..3],
+            value_ranges: vec![
+                raw.find("héllo").unwrap()..raw.find("héllo").unwrap() + "héllo".len(),
+                // Starts inside "日"
+                raw.find('日').unwrap() + 1..raw.len(),
+            ],
+        };
+        let highlights = compute_row_highlights(&text, Some(&terms));
+        let (key_part, value_part) = split_display_text(&text);
+        assert_eq!(&key_part[highlights.key_ranges[0].clone()], "a:b");
+        let matched: Vec<&str> = highlights
+            .value_ranges
+            .iter()
+            .map(|range| &value_part[range.clone()])
+            .collect();
+        assert_eq!(matched, vec!["héllo", "日本"]);
+    }
+
+    #[test]
+    fn test_copy_value_raw_and_json() {
+        let json = r#"[{"text": "line\nsaid \"hi\"", "n": 1.5, "ok": null}]"#;
+        let (mut loader, _) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let viewer = JsonTreeViewer::new();
+        let mut copy = |path: &str, json: bool| {
+            let selected = Some(path.to_string());
+            if json {
+                viewer.copy_selected_value_json(&selected, &mut cache, &mut loader)
+            } else {
+                ContextMenuHandler::copy_selected_value(&viewer, &selected, &mut cache, &mut loader)
+            }
+        };
+
+        assert_eq!(copy("0.text", false).as_deref(), Some("line\nsaid \"hi\""));
+        assert_eq!(
+            copy("0.text", true).as_deref(),
+            Some(r#""line\nsaid \"hi\"""#)
+        );
+        assert_eq!(copy("0.n", false).as_deref(), Some("1.5"));
+        assert_eq!(copy("0.ok", false).as_deref(), Some("null"));
+        assert_eq!(copy("0.missing", false), None);
+    }
+
+    #[test]
+    fn test_copy_selected_rows() {
+        let json = r#"[{"a": 1, "b": {"c": "x"}}, {"a": 2}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        viewer.expanded.insert("0".to_string());
+        viewer.expanded.insert("0.b".to_string());
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        // Shift-click range covers every visible row in between, either way
+        let range = viewer.rows_between("1", "0.a");
+        assert_eq!(
+            range,
+            ["0.a", "0.b", "0.b.c", "0.b/_close", "0/_close", "1"]
+        );
+        assert_eq!(viewer.rows_between("0.a", "1"), range);
+
+        // Closing rows and rows inside a selected container aren't repeated
+        let paths: HashSet<String> = range.into_iter().collect();
+        assert_eq!(viewer.selected_rows(&paths), ["0.a", "0.b", "1"]);
+        assert_eq!(
+            viewer
+                .copy_selected_rows(&paths, &mut cache, &mut loader)
+                .as_deref(),
+            Some("0.a: 1\n0.b: {\"c\":\"x\"}\n1: {\"a\":2}")
+        );
+    }
+
+    #[test]
+    fn test_large_integers_are_shown_and_copied_exactly() {
+        let json = r#"[{"id": 12345678901234567890, "wei": 123456789012345678901234567890}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        viewer.expanded.insert("0".to_string());
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        for (path, digits) in [
+            ("0.id", "12345678901234567890"),
+            ("0.wei", "123456789012345678901234567890"),
+        ] {
+            let row = viewer.rows.iter().find(|r| r.path == path).unwrap();
+            assert!(row.display_text.ends_with(digits), "{}", row.display_text);
+            let selected = Some(path.to_string());
+            assert_eq!(
+                ContextMenuHandler::copy_selected_value(
+                    &viewer,
+                    &selected,
+                    &mut cache,
+                    &mut loader
+                )
+                .as_deref(),
+                Some(digits)
+            );
+        }
+        assert_eq!(
+            viewer
+                .copy_selected_object_minified(&Some("0".to_string()), &mut cache, &mut loader)
+                .as_deref(),
+            Some(r#"{"id":12345678901234567890,"wei":123456789012345678901234567890}"#)
+        );
+    }
+
+    #[test]
+    fn test_copy_path_value() {
+        let json = r#"[{"user": {"name": "a \"b\""}}]"#;
+        let (mut loader, _) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let viewer = JsonTreeViewer::new();
+        assert_eq!(
+            viewer.copy_selected_path_value(
+                &Some("0.user.name".to_string()),
+                &mut cache,
+                &mut loader
+            ),
+            Some(r#"user.name = "a \"b\"""#.to_string())
+        );
+    }
+
     #[test]
     fn test_no_duplicate_rows_for_primitive_in_array() {
         // When a primitive array element is "expanded" (path in expanded set),
@@ -1129,13 +3516,12 @@ mod tests {
         // Initial build
         viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
 
-        // Expand all repeatedly until stable
-        for _ in 0..5 {
-            let changed = viewer.expand_all();
-            if changed {
-                viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
-            }
+        // Expand all and drive the job to completion
+        assert!(viewer.expand_all());
+        while viewer.expand_all_progress().is_some() {
+            viewer.step_expand_all(&mut cache, &mut loader, 1);
         }
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
 
         let texts = row_display_texts(&viewer);
         // "Alice" should appear exactly once
@@ -1155,6 +3541,224 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_expand_all_is_chunked_across_steps() {
+        let json = r#"[{"a": {"b": {"c": [1, {"d": 2}]}}}, {"e": [3]}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        assert!(viewer.expand_all());
+        assert_eq!(viewer.expand_all_progress(), Some(0.0));
+
+        // A budget of one node per step never expands more than one path at a time
+        let mut steps = 0;
+        while viewer.expand_all_progress().is_some() {
+            let before = viewer.expanded.len();
+            viewer.step_expand_all(&mut cache, &mut loader, 1);
+            assert!(viewer.expanded.len() <= before + 1);
+            steps += 1;
+        }
+
+        // 0, 0.a, 0.a.b, 0.a.b.c, 0.a.b.c[1], 1, 1.e
+        assert_eq!(viewer.expanded.len(), 7);
+        assert!(steps >= 7);
+        assert!(viewer.expanded.contains("0.a.b.c[1]"));
+        assert!(viewer.expanded.contains("1.e"));
+    }
+
+    #[test]
+    fn test_cancel_expand_all_keeps_partial_expansion() {
+        let json = r#"[{"a": {"b": {"c": {"d": 1}}}}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        assert!(viewer.expand_all());
+        assert!(viewer.step_expand_all(&mut cache, &mut loader, 2));
+        assert!(viewer.cancel_expand_all());
+
+        // Whatever was expanded before cancelling stays expanded
+        assert_eq!(viewer.expand_all_progress(), None);
+        assert!(viewer.expanded.contains("0"));
+        assert!(viewer.expanded.contains("0.a"));
+        assert!(!viewer.expanded.contains("0.a.b"));
+        assert!(!viewer.step_expand_all(&mut cache, &mut loader, 10));
+        assert!(!viewer.cancel_expand_all());
+    }
+
+    #[test]
+    fn test_indent_size_scales_content_and_guides() {
+        let row = |indent_size: f32| {
+            DataRow::builder()
+                .display_text("\"a\": 1")
+                .row_id("0.a.b")
+                .indent(2)
+                .indent_step(indent_size)
+                .build()
+        };
+
+        assert_eq!(row(12.0).indent_offset(), 24.0);
+        assert_eq!(row(24.0).indent_offset(), 48.0);
+        assert_eq!(
+            DataRow::builder()
+                .display_text("x")
+                .row_id("0")
+                .indent(2)
+                .build()
+                .indent_offset(),
+            32.0
+        );
+
+        // Guides sit in the middle of each level, so they track the content offset
+        assert_eq!(indent_guide_offset(0, 12.0), 6.0);
+        assert_eq!(indent_guide_offset(1, 12.0), 18.0);
+        assert_eq!(indent_guide_offset(0, 24.0), 12.0);
+        assert_eq!(indent_guide_offset(1, 24.0), 36.0);
+        for indent_size in [12.0, 24.0] {
+            let content = row(indent_size).indent_offset();
+            assert!(indent_guide_offset(1, indent_size) < content);
+            assert!(indent_guide_offset(2, indent_size) > content);
+        }
+    }
+
+    #[test]
+    fn test_active_guides_follow_selection_ancestors() {
+        let json = r#"[{"a.b": {"c": [1, 2]}, "d": 3}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        viewer.expanded.insert("0".to_string());
+        viewer.expanded.insert("0.a.b".to_string());
+        viewer.expanded.insert("0.a.b.c".to_string());
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        let ancestors = viewer.selection_ancestors("0.a.b.c[1]");
+        assert_eq!(
+            ancestors,
+            [
+                (2, "0.a.b.c".to_string()),
+                (1, "0.a.b".to_string()),
+                (0, "0".to_string()),
+            ]
+        );
+
+        // Every guide beside the selected element and its sibling leads to it
+        for level in 0..3 {
+            assert!(is_active_guide("0.a.b.c[0]", level, &ancestors));
+        }
+        // Outside `c` only the outer levels do
+        assert!(is_active_guide("0.a.b.c/_close", 1, &ancestors));
+        assert!(is_active_guide("0.d", 0, &ancestors));
+        assert!(!is_active_guide("0.d", 1, &ancestors));
+        assert!(!is_active_guide("1.d", 0, &ancestors));
+
+        assert!(viewer.selection_ancestors("0").is_empty());
+        assert!(viewer.selection_ancestors("missing").is_empty());
+    }
+
+    #[test]
+    fn test_expand_to_depth_opens_exact_levels() {
+        let json = r#"[{"a": {"b": {"c": 1}}, "list": [[1], 2]}, 5]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        assert_eq!(
+            viewer.expand_to_depth(2, &None, &mut cache, &mut loader, len),
+            DepthExpansion::Changed
+        );
+        let expected: HashSet<String> = ["0", "0.a", "0.list"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(viewer.expanded, expected);
+
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert_eq!(viewer.expanded_depth(), 2);
+
+        // Same depth again is a no-op, so no rebuild is requested
+        assert_eq!(
+            viewer.expand_to_depth(2, &None, &mut cache, &mut loader, len),
+            DepthExpansion::Unchanged
+        );
+    }
+
+    #[test]
+    fn test_collapse_to_depth_closes_deeper_nodes() {
+        let json = r#"[{"a": {"b": {"c": 1}}}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+
+        viewer.expand_to_depth(10, &None, &mut cache, &mut loader, len);
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert_eq!(viewer.expanded_depth(), 3);
+
+        assert_eq!(
+            viewer.collapse_to_depth(1, &None, &mut cache, &mut loader, len),
+            DepthExpansion::Changed
+        );
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert_eq!(viewer.expanded, HashSet::from(["0".to_string()]));
+        assert_eq!(viewer.expanded_depth(), 1);
+
+        viewer.collapse_to_depth(0, &None, &mut cache, &mut loader, len);
+        assert!(viewer.expanded.is_empty());
+    }
+
+    #[test]
+    fn test_auto_expand_depth_one_before_first_render() {
+        // Mirrors FileViewer::open: the depth is applied before any rows exist
+        let json = r#"[{"user": {"name": "Alice"}, "tags": ["x"]}, {"user": {"name": "Bob"}}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+
+        assert_eq!(
+            viewer.expand_to_depth(1, &None, &mut cache, &mut loader, len),
+            DepthExpansion::Changed
+        );
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        let mut expandable_rows: Vec<(&str, bool)> = viewer
+            .rows
+            .iter()
+            .filter(|row| row.is_expandable)
+            .map(|row| (row.path.as_str(), row.is_expanded))
+            .collect();
+        expandable_rows.sort();
+        assert_eq!(
+            expandable_rows,
+            [
+                ("0", true),
+                ("0.tags", false),
+                ("0.user", false),
+                ("1", true),
+                ("1.user", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_to_depth_refuses_too_many_rows() {
+        // One root holding an array wide enough to exceed the cap once opened
+        let items = vec!["0"; EXPAND_TO_DEPTH_MAX_ROWS].join(",");
+        let json = format!("[[{}]]", items);
+        let (mut loader, len) = make_json_array_loader(&json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+
+        assert_eq!(
+            viewer.expand_to_depth(2, &None, &mut cache, &mut loader, len),
+            DepthExpansion::TooManyRows
+        );
+        assert!(viewer.expanded.is_empty());
+    }
+
     // ========================================================================
     // Bug #64 Screenshot 1: String field in object "expands" via right arrow,
     // causing the URL to appear twice — once inline and once as a child row
@@ -1215,4 +3819,156 @@ mod tests {
             example_count, texts
         );
     }
+
+    #[test]
+    fn test_copy_selected_object_minified() {
+        let json = r#"[{"user": {"name": "Ann \"A\"", "tags": ["x", 1, null]}, "n": 42}]"#;
+        let (mut loader, _) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let viewer = JsonTreeViewer::new();
+        let mut copy = |path: &str| {
+            ContextMenuHandler::copy_selected_object_minified(
+                &viewer,
+                &Some(path.to_string()),
+                &mut cache,
+                &mut loader,
+            )
+        };
+
+        assert_eq!(
+            copy("0.user").as_deref(),
+            Some(r#"{"name":"Ann \"A\"","tags":["x",1,null]}"#)
+        );
+        assert_eq!(copy("0.user.tags").as_deref(), Some(r#"["x",1,null]"#));
+        // Scalars are copied as JSON literals
+        assert_eq!(copy("0.n").as_deref(), Some("42"));
+        assert_eq!(copy("0.user.tags[0]").as_deref(), Some(r#""x""#));
+        assert_eq!(copy("0.missing"), None);
+    }
+
+    #[test]
+    fn test_type_badges() {
+        let json = r#"[{"s": "x", "n": 1.5, "b": true, "z": null, "o": {"a": 1, "b": 2}, "l": [1, 2, 3]}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        viewer.expanded.insert("0".to_string());
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        let badges: Vec<(String, Option<String>)> = viewer
+            .rows
+            .iter()
+            .map(|r| {
+                (
+                    r.path.clone(),
+                    r.type_badge.as_ref().map(|b| b.label.clone()),
+                )
+            })
+            .collect();
+        let badge = |path: &str| {
+            badges
+                .iter()
+                .find(|(p, _)| p == path)
+                .and_then(|(_, b)| b.clone())
+        };
+        assert_eq!(badge("0").as_deref(), Some("obj[6]"));
+        assert_eq!(badge("0.s").as_deref(), Some("str"));
+        assert_eq!(badge("0.n").as_deref(), Some("num"));
+        assert_eq!(badge("0.b").as_deref(), Some("bool"));
+        assert_eq!(badge("0.z").as_deref(), Some("null"));
+        // Collapsed containers still report their child count
+        assert_eq!(badge("0.o").as_deref(), Some("obj[2]"));
+        assert_eq!(badge("0.l").as_deref(), Some("arr[3]"));
+        assert_eq!(badge("0/_close"), None);
+
+        let number = viewer.rows.iter().find(|r| r.path == "0.n").unwrap();
+        assert_eq!(number.type_badge.as_ref().unwrap().token, TextToken::Number);
+    }
+
+    #[test]
+    fn test_timestamp_annotations() {
+        let json = r#"[{"created_at": 1704067200, "id": 1704067200, "seen": "2024-01-01T00:00:00Z", "log": [1704067200]}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        viewer.expanded.insert("0".to_string());
+        viewer.expanded.insert("0.log".to_string());
+        let annotation = |viewer: &JsonTreeViewer, path: &str| {
+            viewer
+                .rows
+                .iter()
+                .find(|r| r.path == path)
+                .and_then(|r| r.annotation.clone())
+        };
+
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert_eq!(annotation(&viewer, "0.created_at"), None);
+
+        viewer.set_timestamp_fields(Some(&["*_at".to_string()]));
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+        assert_eq!(
+            annotation(&viewer, "0.created_at").as_deref(),
+            Some("(2024-01-01 00:00 UTC)")
+        );
+        assert_eq!(
+            annotation(&viewer, "0.seen").as_deref(),
+            Some("(2024-01-01 00:00 UTC)")
+        );
+        // Ids and array elements have no matching field name
+        assert_eq!(annotation(&viewer, "0.id"), None);
+        assert_eq!(annotation(&viewer, "0.log[0]"), None);
+        // The raw value is still what the row shows
+        let row = viewer
+            .rows
+            .iter()
+            .find(|r| r.path == "0.created_at")
+            .unwrap();
+        assert_eq!(row.display_text, "\"created_at\": 1704067200");
+    }
+
+    #[test]
+    fn test_malformed_ndjson_record_gets_placeholder_row() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(b"{\"id\":1}\n{broken\n{\"id\":3}\n").unwrap();
+        tmp.flush().unwrap();
+        let mut loader =
+            FileType::Ndjson(crate::file::loaders::NdjsonFile::open(tmp.path()).unwrap());
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, 3);
+
+        assert_eq!(
+            row_display_texts(&viewer),
+            vec!["[0]: (…) ", "[1]: {broken", "[2]: (…) "]
+        );
+        let broken = &viewer.rows[1];
+        assert!(broken.parse_error.is_some());
+        assert!(!broken.is_expandable);
+        assert!(viewer.rows[0].parse_error.is_none());
+    }
+
+    #[test]
+    fn test_export_html_follows_expansion() {
+        let json = r#"[{"a": "<i>x</i>", "b": {"c": [1, 2]}}, {"d": true}]"#;
+        let (mut loader, len) = make_json_array_loader(json);
+        let mut cache = LruCache::new(16);
+        let mut viewer = JsonTreeViewer::new();
+        viewer.expanded.insert("0".to_string());
+        viewer.rebuild_rows(&None, &mut cache, &mut loader, len);
+
+        let page = viewer.export_html(
+            &mut cache,
+            &mut loader,
+            "data.json",
+            &ThemeColors::default(),
+            16.0,
+        );
+        // Record 0 is open; its "b" and all of record 1 are closed but present
+        assert_eq!(page.matches("<details open>").count(), 1);
+        assert_eq!(page.matches("<details>").count(), 3);
+        assert!(page.contains("&lt;i&gt;x&lt;/i&gt;"));
+        assert!(page.contains("&quot;d&quot;"));
+        assert!(page.contains("<span class=\"bool\"> true</span>"));
+        assert!(page.trim_end().ends_with("</html>"));
+    }
 }
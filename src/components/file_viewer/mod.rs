@@ -1,23 +1,74 @@
 pub mod context_menu;
+pub mod field_stats;
+pub mod formatted_record;
+pub mod html_export;
 pub mod json_tree_viewer;
 pub mod plugin_table_viewer;
+pub mod raw_bytes;
+pub mod sort;
 pub mod types;
 pub mod viewer_trait;
 pub mod viewer_type;
 
-use eframe::egui::Ui;
+use eframe::egui::{self, Ui};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use thoth_plugin_sdk::components::{DEFAULT_INDENT_STEP, IconButton, Input, Size};
 
+use self::field_stats::FieldStats;
+use self::formatted_record::FormattedRecord;
+use self::raw_bytes::RawBytesRecord;
+use self::sort::{RootSort, SortKeyCache};
 use self::types::ViewerState;
+use self::viewer_trait::DepthExpansion;
 use self::viewer_type::ViewerType;
 use crate::PLUGIN_MANAGER;
-use crate::file::loaders::{FileKind, FileType, load_file_auto};
-use crate::helpers::LruCache;
+use crate::app::persistent_state::PersistentState;
+use crate::constants::{
+    DEFAULT_MAX_FILE_SIZE_MB, EXPAND_ALL_NODES_PER_FRAME, EXPAND_TO_DEPTH_MAX_ROWS, SCROLL_MARGIN,
+};
+use crate::file::detect_file_type::{DetectedFileType, Detection};
+use crate::file::loaders::{FileKind, FileType, file_type_override, load_file};
+use crate::file::open_progress::OpenProgress;
+use crate::file::schema::{SchemaValidation, SchemaValidator};
+use crate::helpers::{
+    CacheStats, LruCache, NumberFormat, PathCompletion, PathFormat, complete_path, lookup_rel,
+    split_root_rel,
+};
+use crate::notification::{
+    Notification, NotificationKind, NotificationManager, NotificationStatus,
+};
 use crate::plugin::Capability;
 use crate::search::results::{MatchFragment, SearchResults};
+use crate::search::{FacetSampler, Facets};
+use crate::settings::IndentGuides;
+use crate::theme::ThemeColors;
+
+/// Built-in extensions handled without plugins.
+const BUILTIN_EXTENSIONS: &[&str] = &[
+    "json", "ndjson", "jsonl", "geojson", "csv", "tsv", "yaml", "yml", "toml", "xml", "parquet",
+    "gz",
+];
+
+/// Whether `path` has an extension the viewer can open, either built in or
+/// through an installed loader plugin, or a name with a file type override
+pub fn is_supported_file(path: &Path) -> bool {
+    if file_type_override(path).is_some() {
+        return true;
+    }
+    let Some(ext) = path.extension().map(|e| e.to_string_lossy().to_lowercase()) else {
+        return false;
+    };
+    BUILTIN_EXTENSIONS.contains(&ext.as_str())
+        || PLUGIN_MANAGER
+            .get()
+            .and_then(|opt| opt.as_ref())
+            .is_some_and(|pm| pm.find_loader_for_extension(&ext).is_some())
+}
 
 /// Generic file viewer that manages common viewing concerns (loading, caching, selection)
 /// and delegates format-specific rendering to specialized viewers via the ViewerType enum.
@@ -26,7 +77,8 @@ use crate::search::results::{MatchFragment, SearchResults};
 /// 1. Create a new viewer struct (e.g., `CsvTableViewer`)
 /// 2. Implement `FileFormatViewer` trait for it
 /// 3. Add the viewer to `ViewerType` enum
-/// 4. That's it! FileViewer will automatically work with the new viewer
+/// 4. Register it for its file kind with `viewer_type::register_viewer`
+/// 5. That's it! FileViewer will automatically work with the new viewer
 pub struct FileViewer {
     /// File loader for lazy parsing
     loader: Option<FileType>,
@@ -49,8 +101,100 @@ pub struct FileViewer {
     /// Highlights for records and paths from search results
     highlights: HashMap<usize, Arc<Vec<MatchFragment>>>,
 
+    /// Search match count per record, shown on root rows
+    match_counts: HashMap<usize, usize>,
+
     /// Enable syntax highlighting
     syntax_highlighting: bool,
+
+    /// Width of one tree indentation level, in pixels
+    indent_size: f32,
+
+    /// How tree indent guide lines are drawn
+    indent_guides: IndentGuides,
+
+    /// Rows kept between the selection and the view's edge while navigating
+    scroll_margin: usize,
+
+    /// Show value type badges at the end of rows
+    show_type_badges: bool,
+
+    /// Allow editing primitive values in place (double-click)
+    inline_editing: bool,
+
+    /// Presentation mode: nothing can be edited and the save bar is hidden
+    read_only: bool,
+
+    /// How numeric leaf values are displayed
+    number_format: NumberFormat,
+
+    /// Field-name patterns for numeric timestamps; `None` when timestamps
+    /// aren't humanized
+    timestamp_fields: Option<Vec<String>>,
+
+    /// Active root-record sort, if any
+    sort: Option<RootSort>,
+
+    /// Sort keys for the current sort field, kept so re-sorting doesn't re-parse
+    sort_keys: Option<SortKeyCache>,
+
+    /// Expanded node paths as last loaded from / saved to disk
+    persisted_expansion: HashSet<String>,
+
+    /// Depth opened automatically when a file without saved expansion is opened
+    auto_expand_depth: usize,
+
+    /// Whether the depth-expansion row limit has already been reported
+    warned_expansion_limit: bool,
+
+    /// Root record shown in the "View Formatted" modal, while it is open
+    formatted_record: Option<FormattedRecord>,
+
+    /// Root record shown in the "View Raw Bytes" modal, while it is open
+    raw_bytes_record: Option<RawBytesRecord>,
+
+    /// Aggregates shown in the "Stats for This Field" modal, while it is open
+    field_stats: Option<FieldStats>,
+
+    /// Text copied from the viewer this frame, waiting to be taken by the app
+    copy_request: Option<String>,
+
+    /// Validation of the root records against the tab's JSON Schema, if one is loaded
+    schema: Option<SchemaValidation>,
+
+    /// Common keys and values of the first records, offered as search chips
+    facets: FacetSampler,
+
+    /// How the built-in loaders sniffed the open file; `None` for plugin files
+    detection: Option<Detection>,
+
+    /// Type the user chose to read the open file as, overriding detection
+    forced_type: Option<DetectedFileType>,
+
+    /// Largest size in megabytes a `.gz` file may decompress to
+    max_file_size_mb: u64,
+
+    /// File being indexed on a background thread; the current file stays
+    /// open until it finishes
+    opening: Option<FileOpening>,
+}
+
+/// A loader and how it was picked, as returned by [`FileViewer::load`]
+type Loaded = (FileType, FileKind, Option<Detection>);
+
+/// A file being opened on a background thread
+///
+/// Dropping it cancels the open.
+struct FileOpening {
+    path: PathBuf,
+    progress: Arc<OpenProgress>,
+    rx: Receiver<crate::error::Result<Loaded>>,
+}
+
+impl Drop for FileOpening {
+    fn drop(&mut self) {
+        self.progress.cancel();
+    }
 }
 
 impl FileViewer {
@@ -69,7 +213,31 @@ impl FileViewer {
             state: ViewerState::default(),
             file_path: None,
             highlights: HashMap::new(),
+            match_counts: HashMap::new(),
             syntax_highlighting: true, // Default to enabled
+            indent_size: DEFAULT_INDENT_STEP,
+            indent_guides: IndentGuides::Lines,
+            scroll_margin: SCROLL_MARGIN,
+            show_type_badges: false,
+            inline_editing: false,
+            read_only: false,
+            number_format: NumberFormat::Plain,
+            timestamp_fields: None,
+            sort: None,
+            sort_keys: None,
+            persisted_expansion: HashSet::new(),
+            auto_expand_depth: 0,
+            warned_expansion_limit: false,
+            formatted_record: None,
+            raw_bytes_record: None,
+            field_stats: None,
+            copy_request: None,
+            schema: None,
+            facets: FacetSampler::default(),
+            detection: None,
+            forced_type: None,
+            max_file_size_mb: DEFAULT_MAX_FILE_SIZE_MB,
+            opening: None,
         }
     }
 
@@ -78,11 +246,202 @@ impl FileViewer {
         self.syntax_highlighting = enabled;
     }
 
-    /// Open a file for viewing (compatible with old JsonViewer API)
+    /// Set the width of one tree indentation level, in pixels
+    pub fn set_indent_size(&mut self, indent_size: f32) {
+        self.indent_size = indent_size;
+    }
+
+    /// Set how tree indent guide lines are drawn
+    pub fn set_indent_guides(&mut self, guides: IndentGuides) {
+        self.indent_guides = guides;
+    }
+
+    /// Set how many rows keyboard navigation keeps around the selection
+    pub fn set_scroll_margin(&mut self, rows: usize) {
+        self.scroll_margin = rows;
+    }
+
+    /// Show or hide value type badges at the end of rows
+    pub fn set_show_type_badges(&mut self, enabled: bool) {
+        self.show_type_badges = enabled;
+    }
+
+    /// Allow or disallow editing primitive values in place
+    pub fn set_inline_editing(&mut self, enabled: bool) {
+        self.inline_editing = enabled;
+    }
+
+    /// Make the viewer read-only, overriding inline editing
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// Set how numeric leaf values are displayed
+    pub fn set_number_format(&mut self, format: NumberFormat) {
+        self.number_format = format;
+    }
+
+    /// Humanize timestamps using these field-name patterns, or stop with `None`
+    pub fn set_timestamp_fields(&mut self, fields: Option<&[String]>) {
+        if self.timestamp_fields.as_deref() != fields {
+            self.timestamp_fields = fields.map(<[String]>::to_vec);
+        }
+    }
+
+    /// Take text copied from the viewer's context menu since the last call
+    pub fn take_copy_request(&mut self) -> Option<String> {
+        self.copy_request.take()
+    }
+
+    /// Set the depth expanded automatically when a file is opened (0 disables)
+    pub fn set_auto_expand_depth(&mut self, depth: usize) {
+        self.auto_expand_depth = depth;
+    }
+
+    /// Set the largest size in megabytes a `.gz` file may decompress to
+    pub fn set_max_file_size_mb(&mut self, limit_mb: u64) {
+        self.max_file_size_mb = limit_mb;
+    }
+
+    /// Open a file for viewing, indexing it on the calling thread
     pub fn open(&mut self, path: &Path, file_type: &mut FileKind) -> crate::error::Result<()> {
-        // Built-in extensions handled without plugins.
-        const JSON_EXTENSIONS: &[&str] = &["json", "ndjson", "jsonl", "geojson"];
+        self.opening = None;
+        // A type chosen by the user only sticks to the file it was chosen for
+        if self.file_path.as_deref() != Some(path) {
+            self.forced_type = None;
+        }
+        let loaded = Self::load(
+            path,
+            self.forced_type,
+            self.max_file_size_mb,
+            &OpenProgress::default(),
+        )?;
+        self.finish_open(path, loaded, file_type);
+        Ok(())
+    }
+
+    /// Start opening `path` on a background thread
+    ///
+    /// The current file stays open and usable until [`Self::poll_open`]
+    /// reports the new one ready; opening another file first cancels this one.
+    pub fn begin_open(&mut self, path: &Path, ctx: &egui::Context) {
+        if self.file_path.as_deref() != Some(path) {
+            self.forced_type = None;
+        }
+        let progress = Arc::new(OpenProgress::default());
+        let (tx, rx) = mpsc::channel();
+        let job = (
+            path.to_path_buf(),
+            self.forced_type,
+            self.max_file_size_mb,
+            Arc::clone(&progress),
+        );
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let (path, forced, max_file_size_mb, progress) = job;
+            let _ = tx.send(Self::load(&path, forced, max_file_size_mb, &progress));
+            ctx.request_repaint();
+        });
+        self.opening = Some(FileOpening {
+            path: path.to_path_buf(),
+            progress,
+            rx,
+        });
+    }
+
+    /// Show the file started with [`Self::begin_open`] once it has loaded
+    ///
+    /// Returns `None` while it is still opening (or nothing is), and the
+    /// outcome once it has finished; on error the current file stays open.
+    pub fn poll_open(&mut self, file_type: &mut FileKind) -> Option<crate::error::Result<()>> {
+        let opening = self.opening.as_ref()?;
+        let result = match opening.rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return None,
+            Err(mpsc::TryRecvError::Disconnected) => Err(crate::error::ThothError::FileReadError {
+                path: opening.path.clone(),
+                reason: "the file stopped loading unexpectedly".to_string(),
+            }),
+        };
+        let opening = self.opening.take()?;
+        Some(result.map(|loaded| self.finish_open(&opening.path, loaded, file_type)))
+    }
+
+    /// Stop opening the file started with [`Self::begin_open`], returning
+    /// whether one was being opened
+    pub fn cancel_open(&mut self) -> bool {
+        self.opening.take().is_some()
+    }
 
+    /// The file being opened in the background and how far indexing it got
+    pub fn opening(&self) -> Option<(&Path, &OpenProgress)> {
+        self.opening
+            .as_ref()
+            .map(|opening| (opening.path.as_path(), opening.progress.as_ref()))
+    }
+
+    /// Replace the open file with a freshly loaded one
+    fn finish_open(&mut self, path: &Path, loaded: Loaded, file_type: &mut FileKind) {
+        let (loader, kind, detection) = loaded;
+
+        // Remember how the previous file was expanded before replacing it
+        self.persist_expansion();
+
+        *file_type = kind;
+        self.loader = Some(loader);
+        self.file_path = Some(path.to_path_buf());
+        self.detection = detection;
+
+        // Clear cache and reset state (recreating the cache also restarts its hit counters)
+        self.cache = LruCache::new(self.cache_size);
+        self.state = ViewerState::default();
+        self.highlights.clear();
+        self.match_counts.clear();
+        self.sort = None;
+        self.sort_keys = None;
+        self.formatted_record = None;
+        self.raw_bytes_record = None;
+        self.field_stats = None;
+
+        // Create appropriate viewer for file type
+        self.viewer = Some(ViewerType::from_file_type(*file_type));
+        self.apply_highlights_to_viewer();
+
+        // Reopen nodes that were expanded the last time this file was viewed
+        self.persisted_expansion = PersistentState::load_expansion(&path.to_string_lossy())
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to load expansion state: {}", e);
+                None
+            })
+            .unwrap_or_default();
+        if let Some(viewer) = self.viewer.as_mut() {
+            viewer
+                .as_viewer_mut()
+                .set_expanded_paths(self.persisted_expansion.clone());
+        }
+        // A restored expansion takes precedence over the configured default depth
+        if self.auto_expand_depth > 0 && self.persisted_expansion.is_empty() {
+            self.expand_to_depth(self.auto_expand_depth);
+        }
+        self.restart_schema_validation();
+        self.restart_facets();
+    }
+
+    /// Pick a loader for `path`: an installed plugin for its extension, or
+    /// the built-in loaders with format sniffing (skipped for a `forced` type)
+    ///
+    /// Built-in loaders report their indexing to `progress`, and a `.gz` file
+    /// may decompress to at most `max_file_size_mb` megabytes.
+    fn load(
+        path: &Path,
+        forced: Option<DetectedFileType>,
+        max_file_size_mb: u64,
+        progress: &OpenProgress,
+    ) -> crate::error::Result<Loaded> {
+        if let Some(forced) = forced {
+            let (detection, ft) = load_file(path, Some(forced), max_file_size_mb, progress)?;
+            return Ok((ft, forced.into(), Some(detection)));
+        }
         let ext = path.extension().map(|e| e.to_string_lossy().to_lowercase());
         let ext_str = ext.as_deref().unwrap_or("");
 
@@ -94,13 +453,14 @@ impl FileViewer {
             .and_then(|opt| opt.as_ref())
             .and_then(|pm| {
                 if pm.find_loader_for_extension(ext_str).is_some() {
-                    let result: crate::error::Result<(FileType, FileKind)> =
+                    let result: crate::error::Result<Loaded> =
                         if pm.plugin_has_capability(ext_str, &Capability::FileViewer) {
-                            pm.open_file_with_viewer(ext_str, path)
-                                .map(|wfl| (FileType::PluginWithViewer(wfl), FileKind::PluginTable))
+                            pm.open_file_with_viewer(ext_str, path).map(|wfl| {
+                                (FileType::PluginWithViewer(wfl), FileKind::PluginTable, None)
+                            })
                         } else {
                             pm.open_file(ext_str, path)
-                                .map(|wfl| (FileType::Plugin(wfl), FileKind::Plugin))
+                                .map(|wfl| (FileType::Plugin(wfl), FileKind::Plugin, None))
                         };
                     Some(result)
                 } else {
@@ -108,47 +468,222 @@ impl FileViewer {
                 }
             });
 
-        let (loader, kind) = match plugin_result {
-            Some(Ok((file_type, file_kind))) => (file_type, file_kind),
-            Some(Err(e)) => return Err(e),
-            None if JSON_EXTENSIONS.contains(&ext_str) => {
-                let (detected, ft) = load_file_auto(path)?;
-                (ft, detected.into())
-            }
-            None => {
-                return Err(crate::error::ThothError::InvalidFileType {
-                    path: path.to_path_buf(),
-                    expected: format!(
-                        "a supported format ({}) or an installed plugin for .{ext_str} files",
-                        JSON_EXTENSIONS
-                            .iter()
-                            .map(|e| format!(".{e}"))
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    ),
-                });
+        match plugin_result {
+            Some(result) => result,
+            // Files with an override are read by the built-in loaders whatever
+            // their extension
+            None if BUILTIN_EXTENSIONS.contains(&ext_str) || file_type_override(path).is_some() => {
+                let (detection, ft) = load_file(path, None, max_file_size_mb, progress)?;
+                Ok((ft, detection.file_type.into(), Some(detection)))
             }
+            None => Err(crate::error::ThothError::InvalidFileType {
+                path: path.to_path_buf(),
+                expected: format!(
+                    "a supported format ({}) or an installed plugin for .{ext_str} files",
+                    BUILTIN_EXTENSIONS
+                        .iter()
+                        .map(|e| format!(".{e}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }),
+        }
+    }
+
+    /// Re-read the current file from disk after it changed
+    ///
+    /// Selection, expansion, the active sort and the root filter are kept
+    /// wherever their paths or records still exist. If the new contents fail
+    /// to load the old ones stay on screen and the error is returned.
+    pub fn reload(&mut self, file_type: &mut FileKind) -> crate::error::Result<()> {
+        let Some(path) = self.file_path.clone() else {
+            return Ok(());
         };
+        let (mut loader, kind, detection) = Self::load(
+            &path,
+            self.forced_type,
+            self.max_file_size_mb,
+            &OpenProgress::default(),
+        )?;
+        let mut cache = LruCache::new(self.cache_size);
 
-        *file_type = kind;
-        self.loader = Some(loader);
-        self.file_path = Some(path.to_path_buf());
+        let expanded = self.viewer.as_mut().and_then(|viewer| {
+            let viewer = viewer.as_viewer_mut();
+            viewer.cancel_expand_all();
+            viewer.expanded_paths().map(|paths| {
+                paths
+                    .iter()
+                    .filter(|path| path_exists(path, &mut cache, &mut loader))
+                    .cloned()
+                    .collect::<HashSet<String>>()
+            })
+        });
+        if self
+            .state
+            .selected
+            .as_ref()
+            .is_some_and(|selected| !path_exists(selected, &mut cache, &mut loader))
+        {
+            self.state.selected = None;
+        }
 
-        // Clear cache and reset state (recreate cache since LruCache doesn't have clear)
-        self.cache = LruCache::new(self.cache_size);
-        self.state = ViewerState::default();
-        self.highlights.clear();
+        let total_len = loader.len();
+        if let Some(filter) = self.state.root_filter.as_mut() {
+            filter.retain(|&i| i < total_len);
+        }
+        if let Some(sort) = self.sort.as_ref() {
+            self.sort_keys = Some(SortKeyCache::build(&mut loader, &sort.field));
+        }
 
-        // Create appropriate viewer for file type
-        self.viewer = Some(ViewerType::from_file_type(*file_type));
-        self.apply_highlights_to_viewer();
+        if kind != *file_type {
+            *file_type = kind;
+            self.viewer = Some(ViewerType::from_file_type(kind));
+            self.apply_highlights_to_viewer();
+        }
+        if let (Some(viewer), Some(expanded)) = (self.viewer.as_mut(), expanded) {
+            viewer.as_viewer_mut().set_expanded_paths(expanded);
+        }
 
+        self.loader = Some(loader);
+        self.cache = cache;
+        self.detection = detection;
+        self.apply_sort();
+        self.rebuild_view();
+        self.restart_schema_validation();
+        self.restart_facets();
         Ok(())
     }
 
+    /// Validate root records against `validator`, or stop validating with `None`
+    ///
+    /// Passing the validator that is already in use keeps its progress.
+    pub fn set_schema(&mut self, validator: Option<Arc<SchemaValidator>>) {
+        let unchanged = match (&self.schema, &validator) {
+            (Some(current), Some(new)) => Arc::ptr_eq(current.validator(), new),
+            (None, None) => true,
+            _ => false,
+        };
+        if !unchanged {
+            self.schema = validator.map(|v| SchemaValidation::new(v, 0));
+            self.restart_schema_validation();
+        }
+    }
+
+    /// Schema validation progress and violations, if a schema is loaded
+    pub fn schema_validation(&self) -> Option<&SchemaValidation> {
+        self.schema.as_ref()
+    }
+
+    /// Start validating the current file from its first record
+    fn restart_schema_validation(&mut self) {
+        if let Some(viewer) = self.viewer.as_mut() {
+            viewer.as_viewer_mut().clear_schema_violations();
+        }
+        let total_len = self.loader.as_ref().map_or(0, |loader| loader.len());
+        if let Some(schema) = self.schema.as_mut() {
+            *schema = SchemaValidation::new(schema.validator().clone(), total_len);
+        }
+    }
+
+    /// Common keys and values of the open file's first records, once sampled
+    pub fn facets(&self) -> Option<&Facets> {
+        self.facets.facets()
+    }
+
+    /// Sample the current file's facets again from its first record
+    fn restart_facets(&mut self) {
+        let total_len = self.loader.as_ref().map_or(0, |loader| loader.len());
+        self.facets = FacetSampler::new(total_len);
+    }
+
     /// Set root filter for search results
     pub fn set_root_filter(&mut self, visible_roots: Option<Vec<usize>>) {
-        self.state.visible_roots = visible_roots;
+        self.state.root_filter = visible_roots;
+        self.apply_sort();
+    }
+
+    /// Sort root records by a field path relative to each record
+    /// (e.g. `timestamp`, `user.age`, `items[0].price`)
+    ///
+    /// Numbers sort numerically, strings lexicographically and `false < true`;
+    /// records where the field is missing or null always come last. Sort keys
+    /// are read through the loader in chunks and cached per field, so flipping
+    /// the direction or changing the filter doesn't re-parse the file.
+    pub fn set_sort(&mut self, field: &str, ascending: bool) {
+        let Some(loader) = self.loader.as_mut() else {
+            return;
+        };
+        if self
+            .sort_keys
+            .as_ref()
+            .is_none_or(|keys| keys.field != field)
+        {
+            self.sort_keys = Some(SortKeyCache::build(loader, field));
+        }
+        self.sort = Some(RootSort {
+            field: field.to_string(),
+            ascending,
+        });
+        self.apply_sort();
+        self.rebuild_view();
+    }
+
+    /// Restore file order for root records
+    pub fn clear_sort(&mut self) {
+        self.sort = None;
+        self.apply_sort();
+        self.rebuild_view();
+    }
+
+    /// The active root-record sort, if any
+    pub fn sort(&self) -> Option<&RootSort> {
+        self.sort.as_ref()
+    }
+
+    /// Recompute the rendered root order from the filter and the active sort
+    fn apply_sort(&mut self) {
+        self.state.visible_roots = match (&self.sort, &self.sort_keys) {
+            (Some(sort), Some(keys)) => {
+                let mut roots = self
+                    .state
+                    .root_filter
+                    .clone()
+                    .unwrap_or_else(|| (0..self.total_item_count()).collect());
+                keys.sort_roots(&mut roots, sort.ascending);
+                Some(roots)
+            }
+            _ => self.state.root_filter.clone(),
+        };
+    }
+
+    /// Save the current file's expanded nodes if they changed since the last save
+    fn persist_expansion(&mut self) {
+        let (Some(path), Some(viewer)) = (self.file_path.as_ref(), self.viewer.as_mut()) else {
+            return;
+        };
+        let Some(expanded) = viewer.as_viewer_mut().expanded_paths() else {
+            return;
+        };
+        if *expanded == self.persisted_expansion {
+            return;
+        }
+        if let Err(e) = PersistentState::save_expansion(&path.to_string_lossy(), expanded) {
+            eprintln!("Failed to save expansion state: {}", e);
+        }
+        self.persisted_expansion = expanded.clone();
+    }
+
+    /// Rebuild the viewer's rows for the current root order
+    fn rebuild_view(&mut self) {
+        if let (Some(viewer), Some(loader)) = (self.viewer.as_mut(), self.loader.as_mut()) {
+            let total_len = loader.len();
+            viewer.as_viewer_mut().rebuild_view(
+                &self.state.visible_roots,
+                &mut self.cache,
+                loader,
+                total_len,
+            );
+        }
     }
 
     /// Navigate to and expand a specific root record by index
@@ -188,6 +723,8 @@ impl FileViewer {
     pub fn navigate_to_path(&mut self, path: String) {
         // Auto-expand parent nodes to make the path visible
         if let Some(viewer) = self.viewer.as_mut() {
+            viewer.as_viewer_mut().show_in_array_windows(&path);
+
             // Expand each parent node in the path hierarchy
             let path_parts: Vec<&str> = path.split('.').collect();
             let mut current_path = String::new();
@@ -216,11 +753,127 @@ impl FileViewer {
         self.state.is_search_navigation = true;
     }
 
+    /// How the open file's type was detected, when a built-in loader reads it
+    pub fn detection(&self) -> Option<Detection> {
+        self.detection
+    }
+
+    /// Whether the open file was read as JSONC, with its comments left out of the tree
+    pub fn comments_stripped(&self) -> bool {
+        self.loader
+            .as_ref()
+            .is_some_and(FileType::comments_stripped)
+    }
+
+    /// Re-read the open file as `detected` instead of its sniffed type
+    ///
+    /// Like [`Self::reload`], the old contents stay on screen if the file
+    /// can't be read that way.
+    pub fn read_as(
+        &mut self,
+        detected: DetectedFileType,
+        file_type: &mut FileKind,
+    ) -> crate::error::Result<()> {
+        let previous = self.forced_type.replace(detected);
+        let result = self.reload(file_type);
+        if result.is_err() {
+            self.forced_type = previous;
+        }
+        result
+    }
+
+    /// Number of records edited in place and not saved yet
+    pub fn unsaved_edits(&self) -> usize {
+        self.loader.as_ref().map_or(0, FileType::dirty_count)
+    }
+
+    /// Write the edited records back to the open file, returning how many
+    /// were saved
+    pub fn save_edits(&mut self) -> crate::error::Result<usize> {
+        match (self.loader.as_mut(), self.file_path.as_ref()) {
+            (Some(loader), Some(path)) => loader.save_edits(path),
+            _ => Ok(0),
+        }
+    }
+
+    /// Drop the edited records, going back to the file's contents
+    pub fn discard_edits(&mut self) {
+        if let Some(loader) = self.loader.as_mut() {
+            loader.discard_edits();
+            self.cache.clear();
+        }
+    }
+
+    /// Hit rate and fill level of the parsed-record cache, while a file is open
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.loader.as_ref().map(|_| self.cache.stats())
+    }
+
+    /// Suggest row paths continuing a partially typed `query`
+    ///
+    /// Only the record the query starts in is loaded (through the cache).
+    pub fn complete_path(&mut self, query: &str) -> Option<PathCompletion> {
+        let loader = self.loader.as_mut()?;
+        let total = loader.len();
+        let root = split_root_rel(query)
+            .ok()
+            .map(|(root, _)| root)
+            .filter(|root| *root < total);
+        if let Some(root) = root
+            && self.cache.get(&root).is_none()
+            && let Ok(value) = loader.get(root)
+        {
+            self.cache.put(root, value);
+        }
+        let value = match root {
+            Some(root) => self.cache.get(&root),
+            None => None,
+        };
+        Some(complete_path(query, total, value))
+    }
+
     /// Get the currently selected path
     pub fn get_selected_path(&self) -> Option<&String> {
         self.state.selected.as_ref()
     }
 
+    /// How far the rows were scrolled on the last render
+    pub fn scroll_offset(&self) -> egui::Vec2 {
+        self.viewer.as_ref().map_or(egui::Vec2::ZERO, |viewer| {
+            viewer.as_viewer().scroll_offset()
+        })
+    }
+
+    /// The tree as currently expanded, as a standalone HTML page styled with
+    /// `colors`; `None` without a file or for viewers that can't export
+    pub fn export_html(&mut self, colors: &ThemeColors) -> Option<String> {
+        let (Some(loader), Some(viewer)) = (self.loader.as_mut(), self.viewer.as_ref()) else {
+            return None;
+        };
+        let title = self
+            .file_path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        viewer
+            .as_viewer()
+            .export_html(&mut self.cache, loader, &title, colors, self.indent_size)
+    }
+
+    /// Put back a selection and scroll position saved when the file was last
+    /// closed, without scrolling to the selection
+    pub fn restore_view(&mut self, selected: Option<String>, scroll_offset: egui::Vec2) {
+        if let Some(viewer) = self.viewer.as_mut() {
+            let viewer = viewer.as_viewer_mut();
+            if let Some(path) = &selected {
+                viewer.show_in_array_windows(path);
+            }
+            viewer.set_scroll_offset(scroll_offset);
+        }
+        self.state.selected = selected;
+    }
+
     /// Render the file viewer UI
     pub fn ui(&mut self, ui: &mut Ui) {
         let (Some(loader), Some(viewer_box)) = (self.loader.as_mut(), self.viewer.as_mut()) else {
@@ -233,7 +886,124 @@ impl FileViewer {
         let total_len = loader.len();
         let viewer = viewer_box.as_viewer_mut();
 
+        // Advance an in-progress expand-all by one chunk and show its progress
+        if viewer.expand_all_progress().is_some() {
+            viewer.step_expand_all(&mut self.cache, loader, EXPAND_ALL_NODES_PER_FRAME);
+            if let Some(progress) = viewer.expand_all_progress() {
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::ProgressBar::new(progress)
+                            .desired_width(240.0)
+                            .text(format!(
+                                "Expanding… {:.0}% (Esc to cancel)",
+                                progress * 100.0
+                            )),
+                    );
+                    if ui.button("Cancel").clicked() {
+                        viewer.cancel_expand_all();
+                    }
+                });
+                ui.ctx().request_repaint();
+            }
+        }
+
+        // Offer to save or discard values edited in place
+        let dirty = loader.dirty_count();
+        if dirty > 0 && !self.read_only {
+            let (mut save, mut discard) = (false, false);
+            ui.horizontal(|ui| {
+                ui.label(format!("{} unsaved record(s)", dirty));
+                save = ui.button("Save").clicked();
+                discard = ui.button("Discard").clicked();
+            });
+            if save && let Some(path) = self.file_path.as_ref() {
+                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                match loader.save_edits(path) {
+                    Ok(saved) => {
+                        NotificationManager::notify(
+                            Notification::new(
+                                "Edits saved",
+                                &format!("Saved {} record(s) to {}", saved, file_name),
+                            )
+                            .with_toast(true)
+                            .with_status(NotificationStatus::Completed),
+                        );
+                    }
+                    Err(e) => {
+                        NotificationManager::notify_error(Notification::new(
+                            &format!("Could not save {}", file_name),
+                            &crate::error::ErrorHandler::get_user_message(&e),
+                        ));
+                    }
+                }
+            } else if discard {
+                loader.discard_edits();
+                self.cache.clear();
+            }
+        }
+
+        // Validate a few more records against the schema and mark the invalid ones
+        if let Some(schema) = self.schema.as_mut()
+            && !schema.is_done()
+        {
+            for record_index in schema.step(loader) {
+                let paths = schema.violations()[&record_index]
+                    .iter()
+                    .map(|violation| violation.path.clone())
+                    .collect();
+                viewer.set_schema_violations(record_index, paths);
+            }
+            ui.ctx().request_repaint();
+        }
+
+        // Collect facet chips from the first records
+        if !self.facets.is_done() {
+            self.facets.step(loader);
+            ui.ctx().request_repaint();
+        }
+
+        // Filter the tree's rows by key or value, leaving the record search alone
+        if let Some(matches) = viewer.tree_filter_matches() {
+            ui.horizontal(|ui| {
+                let mut input = Input::builder()
+                    .id("tree_filter_input")
+                    .value(self.state.tree_filter.clone())
+                    .placeholder("Filter rows… (Esc to clear)")
+                    .icon(egui_phosphor::regular::FUNNEL)
+                    .size(Size::Small)
+                    .desired_width(240.0)
+                    .build();
+                if input.show(ui).inner {
+                    self.state.tree_filter = input.value;
+                }
+                if !self.state.tree_filter.trim().is_empty() {
+                    ui.label(format!(
+                        "{} matching {}",
+                        matches,
+                        if matches == 1 { "row" } else { "rows" }
+                    ));
+                    if ui
+                        .add(
+                            IconButton::builder()
+                                .icon(egui_phosphor::regular::X)
+                                .tooltip("Clear filter")
+                                .frame(false)
+                                .build(),
+                        )
+                        .clicked()
+                    {
+                        self.state.tree_filter.clear();
+                    }
+                }
+            });
+        }
+        viewer.set_tree_filter(&self.state.tree_filter);
+
         // Rebuild view initially or when visible roots change
+        viewer.set_number_format(self.number_format);
+        viewer.set_indent_guides(self.indent_guides);
+        viewer.set_scroll_margin(self.scroll_margin);
+        viewer.set_timestamp_fields(self.timestamp_fields.as_deref());
         viewer.rebuild_view(
             &self.state.visible_roots,
             &mut self.cache,
@@ -242,14 +1012,20 @@ impl FileViewer {
         );
 
         // Render the viewer and check if rebuild is needed (due to user interaction)
+        viewer.set_inline_editing(
+            self.inline_editing && !self.read_only && loader.supports_editing(),
+        );
         let needs_rebuild = viewer.render(
             ui,
             &mut self.state.selected,
+            &mut self.state.multi_selection,
             &mut self.cache,
             loader,
             &mut self.state.should_scroll_to_selection,
             self.state.is_search_navigation,
             self.syntax_highlighting,
+            self.indent_size,
+            self.show_type_badges,
         );
 
         // Reset the search navigation flag after rendering
@@ -266,11 +1042,59 @@ impl FileViewer {
                 total_len,
             );
         }
+
+        // Open the "View Formatted" modal for a record picked from the context menu
+        if let Some(record_index) = viewer.take_view_formatted_request() {
+            self.formatted_record = Some(FormattedRecord::load(loader, record_index));
+        }
+
+        // Open the "View Raw Bytes" modal for a record picked from the context menu
+        if let Some(record_index) = viewer.take_view_raw_bytes_request() {
+            self.raw_bytes_record = Some(RawBytesRecord::load(loader, record_index));
+        }
+
+        // Aggregate a field picked from the context menu over the records in
+        // view, so the stats follow the current search
+        if let Some(field) = viewer.take_field_stats_request() {
+            self.field_stats = Some(FieldStats::compute(
+                loader,
+                &field,
+                self.state.visible_roots.as_deref(),
+            ));
+        }
+
+        if let Some(text) = viewer.take_copy_request() {
+            self.copy_request = Some(text);
+        }
+
+        // Apply a sort picked from the context menu
+        if let Some(request) = viewer.take_sort_request() {
+            self.set_sort(&request.field, request.ascending);
+        }
+
+        if let Some(record) = &self.formatted_record
+            && !record.show(ui.ctx(), self.syntax_highlighting)
+        {
+            self.formatted_record = None;
+        }
+
+        if let Some(record) = &self.raw_bytes_record
+            && !record.show(ui.ctx())
+        {
+            self.raw_bytes_record = None;
+        }
+
+        if let Some(stats) = &self.field_stats
+            && !stats.show(ui.ctx(), self.number_format)
+        {
+            self.field_stats = None;
+        }
     }
 
     /// Update highlight metadata from search results
     pub fn set_highlights(&mut self, results: Option<&SearchResults>) {
         self.highlights.clear();
+        self.match_counts = results.map(SearchResults::match_counts).unwrap_or_default();
         if let Some(res) = results {
             for hit in res.hits() {
                 if !hit.fragments.is_empty() {
@@ -284,7 +1108,7 @@ impl FileViewer {
 
     fn apply_highlights_to_viewer(&mut self) {
         if let Some(ViewerType::Json(json)) = self.viewer.as_mut() {
-            json.set_highlights(&self.highlights);
+            json.set_highlights(&self.highlights, &self.match_counts);
         }
     }
 
@@ -305,10 +1129,20 @@ impl FileViewer {
     // ========================================================================
 
     /// Expand the currently selected node (for keyboard shortcuts)
+    ///
+    /// An already expanded node moves the selection to its first child instead.
     /// Returns true if view needs to be rebuilt
     pub fn expand_selected_node(&mut self) -> bool {
         if let Some(viewer) = self.viewer.as_mut() {
             let result = viewer.as_viewer_mut().expand_selected(&self.state.selected);
+            if !result
+                && let Some(child) = viewer
+                    .as_viewer_mut()
+                    .select_first_child(&self.state.selected)
+            {
+                self.state.selected = Some(child);
+                self.state.should_scroll_to_selection = true;
+            }
             if result && let Some(loader) = self.loader.as_mut() {
                 // Rebuild if needed
                 let total_len = loader.len();
@@ -325,12 +1159,20 @@ impl FileViewer {
     }
 
     /// Collapse the currently selected node (for keyboard shortcuts)
+    ///
+    /// A collapsed node or leaf moves the selection to its parent instead.
     /// Returns true if view needs to be rebuilt
     pub fn collapse_selected_node(&mut self) -> bool {
         if let Some(viewer) = self.viewer.as_mut() {
             let result = viewer
                 .as_viewer_mut()
                 .collapse_selected(&self.state.selected);
+            if !result
+                && let Some(parent) = viewer.as_viewer_mut().select_parent(&self.state.selected)
+            {
+                self.state.selected = Some(parent);
+                self.state.should_scroll_to_selection = true;
+            }
             if result && let Some(loader) = self.loader.as_mut() {
                 // Rebuild if needed
                 let total_len = loader.len();
@@ -347,11 +1189,18 @@ impl FileViewer {
     }
 
     /// Expand all nodes in the tree (for keyboard shortcuts)
+    ///
+    /// Large trees are expanded incrementally: the first chunk is applied
+    /// immediately and the rest is advanced from `ui` on subsequent frames.
     pub fn expand_all_nodes(&mut self) -> bool {
         if let Some(viewer) = self.viewer.as_mut() {
             let result = viewer.as_viewer_mut().expand_all();
             if result && let Some(loader) = self.loader.as_mut() {
-                // Rebuild if needed
+                viewer.as_viewer_mut().step_expand_all(
+                    &mut self.cache,
+                    loader,
+                    EXPAND_ALL_NODES_PER_FRAME,
+                );
                 let total_len = loader.len();
                 viewer.as_viewer_mut().rebuild_view(
                     &self.state.visible_roots,
@@ -365,6 +1214,27 @@ impl FileViewer {
         false
     }
 
+    /// Whether an expand-all is still being advanced frame by frame
+    pub fn is_expanding_all(&mut self) -> bool {
+        self.viewer
+            .as_mut()
+            .is_some_and(|viewer| viewer.as_viewer_mut().expand_all_progress().is_some())
+    }
+
+    /// Clear the tree's row filter (for keyboard shortcuts)
+    /// Returns true if there was one to clear
+    pub fn clear_tree_filter(&mut self) -> bool {
+        !std::mem::take(&mut self.state.tree_filter).is_empty()
+    }
+
+    /// Abort an in-progress expand-all, leaving whatever was already expanded
+    /// Returns true if a job was cancelled
+    pub fn cancel_expand_all(&mut self) -> bool {
+        self.viewer
+            .as_mut()
+            .is_some_and(|viewer| viewer.as_viewer_mut().cancel_expand_all())
+    }
+
     /// Collapse all nodes in the tree (for keyboard shortcuts)
     pub fn collapse_all_nodes(&mut self) -> bool {
         if let Some(viewer) = self.viewer.as_mut() {
@@ -384,6 +1254,99 @@ impl FileViewer {
         false
     }
 
+    /// Switch between the tree and the flat `path: value` view (for keyboard shortcuts)
+    pub fn toggle_flat_view(&mut self) {
+        if let Some(viewer) = self.viewer.as_mut()
+            && viewer
+                .as_viewer_mut()
+                .toggle_flat_view(&self.state.selected)
+            && let Some(loader) = self.loader.as_mut()
+        {
+            let total_len = loader.len();
+            viewer.as_viewer_mut().rebuild_view(
+                &self.state.visible_roots,
+                &mut self.cache,
+                loader,
+                total_len,
+            );
+            self.state.should_scroll_to_selection = true;
+        }
+    }
+
+    /// Open every node shallower than `depth` and close everything deeper
+    pub fn expand_to_depth(&mut self, depth: usize) -> bool {
+        let Some(loader) = self.loader.as_mut() else {
+            return false;
+        };
+        let total_len = loader.len();
+        let outcome = self.viewer.as_mut().map(|viewer| {
+            viewer.as_viewer_mut().expand_to_depth(
+                depth,
+                &self.state.visible_roots,
+                &mut self.cache,
+                loader,
+                total_len,
+            )
+        });
+        self.finish_depth_change(depth, outcome)
+    }
+
+    /// Close every node at `depth` or deeper, leaving shallower nodes open
+    pub fn collapse_to_depth(&mut self, depth: usize) -> bool {
+        let Some(loader) = self.loader.as_mut() else {
+            return false;
+        };
+        let total_len = loader.len();
+        let outcome = self.viewer.as_mut().map(|viewer| {
+            viewer.as_viewer_mut().collapse_to_depth(
+                depth,
+                &self.state.visible_roots,
+                &mut self.cache,
+                loader,
+                total_len,
+            )
+        });
+        self.finish_depth_change(depth, outcome)
+    }
+
+    /// Rebuild once after a depth change, or report a refused one
+    ///
+    /// Depths that would render more than `EXPAND_TO_DEPTH_MAX_ROWS` rows are
+    /// refused by the viewer; the first refusal is surfaced as a warning.
+    fn finish_depth_change(&mut self, depth: usize, outcome: Option<DepthExpansion>) -> bool {
+        match outcome {
+            Some(DepthExpansion::Changed) => {
+                self.rebuild_view();
+                true
+            }
+            Some(DepthExpansion::Unchanged) | None => false,
+            Some(DepthExpansion::TooManyRows) => {
+                if !self.warned_expansion_limit {
+                    self.warned_expansion_limit = true;
+                    NotificationManager::notify(
+                        Notification::new(
+                            "Tree too large to expand",
+                            &format!(
+                                "Expanding to depth {} would show more than {} rows, so the tree was left as is.",
+                                depth, EXPAND_TO_DEPTH_MAX_ROWS
+                            ),
+                        )
+                        .with_kind(NotificationKind::Warn)
+                        .with_toast(true),
+                    );
+                }
+                false
+            }
+        }
+    }
+
+    /// Depth of the deepest expanded node currently shown (0 when fully collapsed)
+    pub fn expanded_depth(&mut self) -> usize {
+        self.viewer
+            .as_mut()
+            .map_or(0, |viewer| viewer.as_viewer_mut().expanded_depth())
+    }
+
     /// Move selection up to previous item (for keyboard shortcuts)
     pub fn move_selection_up(&mut self) {
         if let Some(viewer) = self.viewer.as_mut()
@@ -392,6 +1355,7 @@ impl FileViewer {
                 .move_selection_up(&self.state.selected)
         {
             self.state.selected = Some(new_selection);
+            self.state.multi_selection.clear();
             self.state.should_scroll_to_selection = true;
         }
     }
@@ -404,27 +1368,82 @@ impl FileViewer {
                 .move_selection_down(&self.state.selected)
         {
             self.state.selected = Some(new_selection);
+            self.state.multi_selection.clear();
             self.state.should_scroll_to_selection = true;
         }
     }
 
+    /// Move the focus up a row, growing or shrinking the selected range
+    /// from its anchor (for keyboard shortcuts)
+    pub fn extend_selection_up(&mut self) {
+        self.extend_selection(false);
+    }
+
+    /// Move the focus down a row, growing or shrinking the selected range
+    /// from its anchor (for keyboard shortcuts)
+    pub fn extend_selection_down(&mut self) {
+        self.extend_selection(true);
+    }
+
+    fn extend_selection(&mut self, down: bool) {
+        let Some(viewer) = self.viewer.as_mut() else {
+            return;
+        };
+        let viewer = viewer.as_viewer_mut();
+        let focus = if down {
+            viewer.move_selection_down(&self.state.selected)
+        } else {
+            viewer.move_selection_up(&self.state.selected)
+        };
+        let Some(focus) = focus else {
+            return;
+        };
+        let multi = &mut self.state.multi_selection;
+        let anchor = multi
+            .anchor_for(&self.state.selected)
+            .unwrap_or_else(|| focus.clone());
+        multi.set_range(anchor.clone(), viewer.rows_between(&anchor, &focus));
+        self.state.selected = Some(focus);
+        self.state.should_scroll_to_selection = true;
+    }
+
+    /// Rows of a multi-row selection, in display order, if several are selected
+    fn multi_selected_rows(&mut self) -> Option<Vec<String>> {
+        let paths = self.state.multi_selection.active(&self.state.selected)?;
+        Some(self.viewer.as_mut()?.as_viewer_mut().selected_rows(paths))
+    }
+
     // ========================================================================
     // Keyboard Shortcut Support - Clipboard Operations
     // ========================================================================
 
     /// Copy the key of the currently selected item (for keyboard shortcuts)
+    /// With several rows selected, copies each row's key on its own line
     /// Returns the text to copy, or None
     pub fn copy_selected_key(&mut self) -> Option<String> {
-        self.viewer
-            .as_mut()?
-            .as_viewer_mut()
-            .copy_selected_key(&self.state.selected)
+        let rows = self.multi_selected_rows();
+        let viewer = self.viewer.as_mut()?.as_viewer_mut();
+        match rows {
+            Some(rows) => Some(
+                rows.into_iter()
+                    .filter_map(|path| viewer.copy_selected_key(&Some(path)))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            None => viewer.copy_selected_key(&self.state.selected),
+        }
     }
 
     /// Copy the value of the currently selected item (for keyboard shortcuts)
+    /// With several rows selected, copies them as `path: value` lines
     /// Returns the text to copy, or None
     pub fn copy_selected_value(&mut self) -> Option<String> {
         if let (Some(viewer), Some(loader)) = (self.viewer.as_mut(), self.loader.as_mut()) {
+            if let Some(paths) = self.state.multi_selection.active(&self.state.selected) {
+                return viewer
+                    .as_viewer_mut()
+                    .copy_selected_rows(paths, &mut self.cache, loader);
+            }
             return viewer.as_viewer_mut().copy_selected_value(
                 &self.state.selected,
                 &mut self.cache,
@@ -447,14 +1466,140 @@ impl FileViewer {
         None
     }
 
-    /// Copy the path of the currently selected item (for keyboard shortcuts)
+    /// Copy the entire object of the currently selected item (for keyboard shortcuts)
+    /// Returns the text to copy (single-line JSON), or None
+    pub fn copy_selected_object_minified(&mut self) -> Option<String> {
+        if let (Some(viewer), Some(loader)) = (self.viewer.as_mut(), self.loader.as_mut()) {
+            return viewer.as_viewer_mut().copy_selected_object_minified(
+                &self.state.selected,
+                &mut self.cache,
+                loader,
+            );
+        }
+        None
+    }
+
+    /// Copy the path of the currently selected item in `format` (for keyboard shortcuts)
+    /// With several rows selected, copies each row's path on its own line
     /// Returns the text to copy, or None
-    pub fn copy_selected_path(&mut self) -> Option<String> {
-        self.viewer
-            .as_mut()?
-            .as_viewer_mut()
-            .copy_selected_path(&self.state.selected)
+    pub fn copy_selected_path(&mut self, format: PathFormat) -> Option<String> {
+        let rows = self.multi_selected_rows();
+        let viewer = self.viewer.as_mut()?.as_viewer_mut();
+        let loader = self.loader.as_mut()?;
+        match rows {
+            Some(rows) => Some(
+                rows.into_iter()
+                    .filter_map(|path| {
+                        viewer.copy_selected_path(&Some(path), format, &mut self.cache, loader)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            None => {
+                viewer.copy_selected_path(&self.state.selected, format, &mut self.cache, loader)
+            }
+        }
+    }
+
+    /// Copy the 1-based source line of the selected record (for keyboard shortcuts)
+    /// Returns the text to copy, or None
+    pub fn copy_selected_line_number(&mut self) -> Option<String> {
+        if let (Some(viewer), Some(loader)) = (self.viewer.as_mut(), self.loader.as_ref()) {
+            return viewer
+                .as_viewer_mut()
+                .copy_selected_line_number(&self.state.selected, loader);
+        }
+        None
+    }
+
+    /// Indices of the visible root records in display order, every record
+    /// when nothing is filtered; `None` without a loaded file
+    pub fn visible_record_indices(&self) -> Option<Vec<usize>> {
+        let loader = self.loader.as_ref()?;
+        Some(match &self.state.visible_roots {
+            Some(roots) => roots.clone(),
+            None => (0..loader.len()).collect(),
+        })
+    }
+
+    /// Whether a search filter hides some of the root records
+    pub fn is_filtered(&self) -> bool {
+        self.state.root_filter.is_some()
+    }
+
+    /// The export format the loaded file stores its records in, if any
+    pub fn export_source_format(&self) -> Option<crate::file::export::ExportFormat> {
+        self.loader
+            .as_ref()
+            .and_then(crate::file::export::ExportFormat::of_source)
+    }
+
+    /// Write the visible root records to `output` as `format`, returning
+    /// how many were written
+    pub fn export_visible_records(
+        &mut self,
+        output: &Path,
+        format: crate::file::export::ExportFormat,
+    ) -> crate::error::Result<usize> {
+        let roots = self.visible_record_indices().unwrap_or_default();
+        let Some(loader) = self.loader.as_mut() else {
+            return Err(crate::error::ThothError::StateError {
+                reason: "No file loaded".to_string(),
+            });
+        };
+        crate::file::export::export_selected(loader, &roots, output, format)
+    }
+
+    /// The visible root records (every record when nothing is filtered) as
+    /// a pretty-printed JSON array, along with the record count
+    ///
+    /// Cached records are reused; the rest are parsed without being added
+    /// to the cache, so a large copy doesn't evict what's on screen.
+    pub fn visible_records_json(&mut self) -> crate::error::Result<(String, usize)> {
+        let Some(loader) = self.loader.as_mut() else {
+            return Err(crate::error::ThothError::StateError {
+                reason: "No file loaded".to_string(),
+            });
+        };
+        let roots: Vec<usize> = match &self.state.visible_roots {
+            Some(roots) => roots.clone(),
+            None => (0..loader.len()).collect(),
+        };
+
+        let mut records = Vec::with_capacity(roots.len());
+        for &root in &roots {
+            let value = match self.cache.get(&root) {
+                Some(value) => value.clone(),
+                None => loader.get(root)?,
+            };
+            records.push(value);
+        }
+        let text = serde_json::to_string_pretty(&Value::Array(records)).map_err(|e| {
+            crate::error::ThothError::InvalidJsonStructure {
+                reason: format!("Failed to serialize records: {}", e),
+            }
+        })?;
+        Ok((text, roots.len()))
+    }
+}
+
+/// Whether a row path like `0.user.items[2]` still resolves in the loaded data
+fn path_exists(path: &str, cache: &mut LruCache<usize, Value>, loader: &mut FileType) -> bool {
+    let Ok((root, rel)) = split_root_rel(path) else {
+        return false;
+    };
+    if root >= loader.len() {
+        return false;
     }
+    if cache.get(&root).is_none() {
+        match loader.get(root) {
+            Ok(value) => cache.put(root, value),
+            Err(_) => return false,
+        }
+    }
+    cache
+        .get(&root)
+        .is_some_and(|value| lookup_rel(value, rel).is_ok())
 }
 
 impl Default for FileViewer {
@@ -462,3 +1607,150 @@ impl Default for FileViewer {
         Self::new()
     }
 }
+
+impl Drop for FileViewer {
+    /// Closing a tab (or the app) keeps the file's expansion for next time
+    fn drop(&mut self) {
+        self.persist_expansion();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::loaders::NdjsonFile;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_visible_records_json_follows_filter() {
+        let mut tmp = NamedTempFile::with_suffix(".ndjson").unwrap();
+        for id in 0..3 {
+            writeln!(tmp, r#"{{"id":{}}}"#, id).unwrap();
+        }
+        tmp.flush().unwrap();
+        let mut viewer = FileViewer::new();
+        let mut kind = FileKind::Ndjson;
+        viewer.open(tmp.path(), &mut kind).unwrap();
+
+        let (text, count) = viewer.visible_records_json().unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(
+            serde_json::from_str::<Value>(&text).unwrap(),
+            serde_json::json!([{"id": 0}, {"id": 1}, {"id": 2}])
+        );
+
+        viewer.set_root_filter(Some(vec![2, 0]));
+        let (text, count) = viewer.visible_records_json().unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            serde_json::from_str::<Value>(&text).unwrap(),
+            serde_json::json!([{"id": 2}, {"id": 0}])
+        );
+    }
+
+    #[test]
+    fn test_open_uses_registered_viewer() {
+        use self::viewer_type::register_viewer;
+
+        let mut tmp = NamedTempFile::with_suffix(".csv").unwrap();
+        writeln!(tmp, "id,name\n1,a\n2,b").unwrap();
+        tmp.flush().unwrap();
+
+        let previous = register_viewer(FileKind::Csv, || {
+            ViewerType::PluginTable(plugin_table_viewer::PluginTableViewer::new())
+        });
+        let mut viewer = FileViewer::new();
+        let mut kind = FileKind::Json;
+        let opened = viewer.open(tmp.path(), &mut kind);
+        if let Some(previous) = previous {
+            register_viewer(FileKind::Csv, previous);
+        }
+
+        opened.unwrap();
+        assert_eq!(kind, FileKind::Csv);
+        assert!(matches!(viewer.viewer, Some(ViewerType::PluginTable(_))));
+        assert_eq!(viewer.total_item_count(), 2);
+    }
+
+    #[test]
+    fn test_background_open_keeps_current_file_until_loaded() {
+        let ndjson = |ids: &[u32]| {
+            let mut tmp = NamedTempFile::with_suffix(".ndjson").unwrap();
+            for id in ids {
+                writeln!(tmp, r#"{{"id":{}}}"#, id).unwrap();
+            }
+            tmp.flush().unwrap();
+            tmp
+        };
+        let (first, second) = (ndjson(&[1]), ndjson(&[1, 2, 3]));
+        let ctx = egui::Context::default();
+        let mut viewer = FileViewer::new();
+        let mut kind = FileKind::Ndjson;
+        viewer.open(first.path(), &mut kind).unwrap();
+
+        // Canceling leaves the first file open
+        viewer.begin_open(second.path(), &ctx);
+        assert_eq!(viewer.opening().map(|(path, _)| path), Some(second.path()));
+        assert!(viewer.cancel_open());
+        assert!(viewer.opening().is_none());
+        assert_eq!(viewer.total_item_count(), 1);
+
+        viewer.begin_open(second.path(), &ctx);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let result = loop {
+            if let Some(result) = viewer.poll_open(&mut kind) {
+                break result;
+            }
+            assert!(std::time::Instant::now() < deadline, "open didn't finish");
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        };
+        result.unwrap();
+        assert!(viewer.opening().is_none());
+        assert_eq!(viewer.total_item_count(), 3);
+    }
+
+    #[test]
+    fn test_unsaved_edits_are_saved_or_discarded() {
+        let mut tmp = NamedTempFile::with_suffix(".ndjson").unwrap();
+        writeln!(tmp, r#"{{"id":1}}"#).unwrap();
+        tmp.flush().unwrap();
+        let mut viewer = FileViewer::new();
+        let mut kind = FileKind::Ndjson;
+        viewer.open(tmp.path(), &mut kind).unwrap();
+        let edit = |viewer: &mut FileViewer, id: u32| {
+            let loader = viewer.loader.as_mut().unwrap();
+            loader
+                .set_record(0, serde_json::json!({ "id": id }))
+                .unwrap();
+        };
+
+        edit(&mut viewer, 2);
+        assert_eq!(viewer.unsaved_edits(), 1);
+        viewer.discard_edits();
+        assert_eq!(viewer.unsaved_edits(), 0);
+
+        edit(&mut viewer, 3);
+        assert_eq!(viewer.save_edits().unwrap(), 1);
+        assert_eq!(viewer.unsaved_edits(), 0);
+        let saved = std::fs::read_to_string(tmp.path()).unwrap();
+        assert_eq!(saved.trim(), r#"{"id":3}"#);
+    }
+
+    #[test]
+    fn test_path_exists_after_reload() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        writeln!(tmp, r#"{{"user":{{"tags":["a","b"]}}}}"#).unwrap();
+        writeln!(tmp, r#"{{"id":2}}"#).unwrap();
+        tmp.flush().unwrap();
+        let mut loader = FileType::Ndjson(NdjsonFile::open(tmp.path()).unwrap());
+        let mut cache = LruCache::new(10);
+
+        assert!(path_exists("0", &mut cache, &mut loader));
+        assert!(path_exists("0.user.tags[1]", &mut cache, &mut loader));
+        assert!(path_exists("0.user/_close", &mut cache, &mut loader));
+        assert!(!path_exists("0.user.tags[2]", &mut cache, &mut loader));
+        assert!(!path_exists("1.user", &mut cache, &mut loader));
+        assert!(!path_exists("2", &mut cache, &mut loader));
+    }
+}
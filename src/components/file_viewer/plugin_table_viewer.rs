@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use eframe::egui;
 use serde_json::Value;
 
+use crate::components::file_viewer::types::MultiSelection;
 use crate::components::file_viewer::viewer_trait::FileFormatViewer;
 use crate::file::loaders::FileType;
 use crate::helpers::LruCache;
@@ -81,11 +82,14 @@ impl FileFormatViewer for PluginTableViewer {
         &mut self,
         ui: &mut egui::Ui,
         _selected: &mut Option<String>,
+        _multi_selection: &mut MultiSelection,
         cache: &mut LruCache<usize, Value>,
         loader: &mut FileType,
         _should_scroll_to_selection: &mut bool,
         _is_search_navigation: bool,
         _syntax_highlighting: bool,
+        _indent_size: f32,
+        _show_type_badges: bool,
     ) -> bool {
         let display_mode = self.display_mode;
         let headers = self.headers.clone();
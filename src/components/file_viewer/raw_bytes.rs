@@ -0,0 +1,347 @@
+use eframe::egui::{self, Ui};
+use thoth_plugin_sdk::components::{Button, ButtonColor, ButtonType, Typography};
+
+use crate::file::loaders::FileType;
+use crate::notification::{NotificationManager, Toast};
+use crate::theme::ThemeColors;
+
+/// Bytes shown per row of the hex dump
+const BYTES_PER_ROW: usize = 16;
+
+/// A root record's source bytes for the "View Raw Bytes" modal
+///
+/// The bytes are read once when the modal opens and scanned for encoding
+/// problems; the dump itself is laid out row by row as it scrolls into view.
+pub struct RawBytesRecord {
+    record_index: usize,
+    content: Result<RawBytes, String>,
+}
+
+struct RawBytes {
+    bytes: Vec<u8>,
+    /// Encoding problems found in `bytes`, one line each
+    anomalies: Vec<String>,
+}
+
+/// How a byte is coloured in the dump
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ByteClass {
+    /// Printable ASCII and ordinary whitespace (tab, newline, carriage return)
+    Text,
+    /// Other bytes below 0x20, and DEL
+    Control,
+    /// 0x80 and above: part of a multi-byte character, or invalid
+    NonAscii,
+}
+
+impl ByteClass {
+    fn of(byte: u8) -> Self {
+        match byte {
+            b'\t' | b'\n' | b'\r' | 0x20..=0x7e => ByteClass::Text,
+            0x00..=0x1f | 0x7f => ByteClass::Control,
+            _ => ByteClass::NonAscii,
+        }
+    }
+}
+
+impl RawBytesRecord {
+    /// Read a root record's bytes as stored in the file
+    pub fn load(loader: &FileType, record_index: usize) -> Self {
+        let content = loader
+            .raw_slice(record_index)
+            .map(|bytes| RawBytes {
+                anomalies: detect_anomalies(&bytes),
+                bytes,
+            })
+            .map_err(|e| e.to_string());
+        Self {
+            record_index,
+            content,
+        }
+    }
+
+    /// Show the modal; returns false once it has been closed
+    pub fn show(&self, ctx: &egui::Context) -> bool {
+        let mut open = true;
+        let modal = egui::Modal::new(egui::Id::new("view_raw_bytes_modal")).show(ctx, |ui| {
+            let screen = ctx.content_rect();
+            ui.set_width((screen.width() * 0.7).clamp(320.0, 900.0));
+
+            ui.horizontal(|ui| {
+                Typography::heading(ui, &format!("Record #{} bytes", self.record_index));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let close_btn = ui.add(
+                        Button::builder()
+                            .label("Close")
+                            .button_type(ButtonType::Elevated)
+                            .color(ButtonColor::Default)
+                            .build(),
+                    );
+                    if close_btn.clicked() {
+                        open = false;
+                    }
+                    if let Ok(raw) = &self.content {
+                        let copy = |ui: &mut Ui, label: &str| {
+                            ui.add(
+                                Button::builder()
+                                    .label(format!("{} {}", egui_phosphor::regular::COPY, label))
+                                    .button_type(ButtonType::Elevated)
+                                    .color(ButtonColor::Primary)
+                                    .build(),
+                            )
+                            .clicked()
+                        };
+                        let text = if copy(ui, "Copy Base64") {
+                            Some(base64(&raw.bytes))
+                        } else if copy(ui, "Copy Hex") {
+                            Some(hex(&raw.bytes))
+                        } else {
+                            None
+                        };
+                        if let Some(text) = text {
+                            ui.output_mut(|o| o.commands.push(egui::OutputCommand::CopyText(text)));
+                            NotificationManager::toast(Toast::success("Copied to clipboard"));
+                        }
+                    }
+                });
+            });
+            ui.add_space(8.0);
+
+            match &self.content {
+                Ok(raw) => raw.show(ui, screen.height() * 0.6),
+                Err(reason) => {
+                    Typography::body_muted(ui, &format!("Could not read record: {}", reason));
+                }
+            }
+        });
+
+        open && !modal.should_close()
+    }
+}
+
+impl RawBytes {
+    fn show(&self, ui: &mut Ui, max_height: f32) {
+        let colors = ThemeColors::from_ctx(ui.ctx());
+        Typography::body_muted(
+            ui,
+            &format!(
+                "{} {}",
+                self.bytes.len(),
+                if self.bytes.len() == 1 {
+                    "byte"
+                } else {
+                    "bytes"
+                }
+            ),
+        );
+        if self.anomalies.is_empty() {
+            Typography::body_muted(ui, "No encoding anomalies found");
+        }
+        for anomaly in &self.anomalies {
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(egui_phosphor::regular::WARNING).color(colors.warning),
+                );
+                ui.label(egui::RichText::new(anomaly).color(colors.warning));
+            });
+        }
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("■ control").color(colors.error).small());
+            ui.label(
+                egui::RichText::new("■ non-ASCII")
+                    .color(colors.warning)
+                    .small(),
+            );
+        });
+        ui.add_space(8.0);
+
+        let font = egui::TextStyle::Monospace.resolve(ui.style());
+        let row_height = ui.fonts_mut(|f| f.row_height(&font));
+        let color = |class: ByteClass| match class {
+            ByteClass::Text => colors.fg,
+            ByteClass::Control => colors.error,
+            ByteClass::NonAscii => colors.warning,
+        };
+        let rows = self.bytes.len().div_ceil(BYTES_PER_ROW);
+
+        egui::ScrollArea::both()
+            .id_salt("view_raw_bytes_scroll")
+            .max_height(max_height)
+            .auto_shrink([false, true])
+            .show_rows(ui, row_height, rows, |ui, row_range| {
+                for row in row_range {
+                    let start = row * BYTES_PER_ROW;
+                    let chunk = &self.bytes[start..(start + BYTES_PER_ROW).min(self.bytes.len())];
+                    let text_format = |color| egui::TextFormat::simple(font.clone(), color);
+
+                    let mut job = egui::text::LayoutJob::default();
+                    job.append(
+                        &format!("{:08x}  ", start),
+                        0.0,
+                        text_format(colors.fg_muted),
+                    );
+                    for col in 0..BYTES_PER_ROW {
+                        let gap = if col + 1 == BYTES_PER_ROW / 2 {
+                            "  "
+                        } else {
+                            " "
+                        };
+                        let cell = chunk
+                            .get(col)
+                            .map_or("  ".to_string(), |b| format!("{:02x}", b));
+                        let class = chunk
+                            .get(col)
+                            .map_or(ByteClass::Text, |&b| ByteClass::of(b));
+                        job.append(&cell, 0.0, text_format(color(class)));
+                        if col + 1 < BYTES_PER_ROW {
+                            job.append(gap, 0.0, text_format(colors.fg));
+                        }
+                    }
+                    job.append("  |", 0.0, text_format(colors.fg_muted));
+                    for &byte in chunk {
+                        let class = ByteClass::of(byte);
+                        let ch = if byte.is_ascii_graphic() || byte == b' ' {
+                            byte as char
+                        } else {
+                            '.'
+                        };
+                        job.append(&ch.to_string(), 0.0, text_format(color(class)));
+                    }
+                    job.append("|", 0.0, text_format(colors.fg_muted));
+                    ui.add(egui::Label::new(job).extend().selectable(true));
+                }
+            });
+    }
+}
+
+/// Describe encoding problems that can keep a record from parsing
+///
+/// Reports byte order marks, invalid UTF-8 sequences and control bytes other
+/// than tab, newline and carriage return, each with the offset of the first
+/// occurrence.
+fn detect_anomalies(bytes: &[u8]) -> Vec<String> {
+    let mut anomalies = Vec::new();
+
+    if bytes.starts_with(&[0xff, 0xfe]) {
+        anomalies.push("UTF-16 little-endian byte order mark at offset 0".to_string());
+    } else if bytes.starts_with(&[0xfe, 0xff]) {
+        anomalies.push("UTF-16 big-endian byte order mark at offset 0".to_string());
+    }
+    let boms: Vec<usize> = memchr::memmem::find_iter(bytes, b"\xef\xbb\xbf").collect();
+    if let Some(&first) = boms.first() {
+        anomalies.push(describe("UTF-8 byte order mark", boms.len(), first));
+    }
+
+    let mut invalid = 0;
+    let mut first_invalid = None;
+    let mut offset = 0;
+    for chunk in bytes.utf8_chunks() {
+        offset += chunk.valid().len();
+        if !chunk.invalid().is_empty() {
+            invalid += 1;
+            first_invalid.get_or_insert(offset);
+        }
+        offset += chunk.invalid().len();
+    }
+    if let Some(first) = first_invalid {
+        anomalies.push(describe("invalid UTF-8 sequence", invalid, first));
+    }
+
+    let controls: Vec<usize> = bytes
+        .iter()
+        .enumerate()
+        .filter(|&(_, &b)| ByteClass::of(b) == ByteClass::Control)
+        .map(|(i, _)| i)
+        .collect();
+    if let Some(&first) = controls.first() {
+        anomalies.push(describe("control byte", controls.len(), first));
+    }
+
+    anomalies
+}
+
+/// "2 control bytes, first at offset 5" / "1 control byte at offset 5"
+fn describe(what: &str, count: usize, first: usize) -> String {
+    if count == 1 {
+        format!("1 {} at offset {}", what, first)
+    } else {
+        format!("{} {}s, first at offset {}", count, what, first)
+    }
+}
+
+/// Bytes as space-separated lowercase hex pairs
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Bytes as standard, padded base64
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | ((b as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_anomalies() {
+        assert!(detect_anomalies(br#"{"a":"caf\xc3\xa9"}"#).is_empty());
+        assert_eq!(
+            detect_anomalies(b"\xef\xbb\xbf{\"a\":1}\r\n"),
+            ["1 UTF-8 byte order mark at offset 0"]
+        );
+        assert_eq!(
+            detect_anomalies(b"{\"a\":\"\xff\x00\xe9\"}"),
+            [
+                "2 invalid UTF-8 sequences, first at offset 6",
+                "1 control byte at offset 7",
+            ]
+        );
+        assert_eq!(
+            detect_anomalies(b"\xff\xfe{\x00"),
+            [
+                "UTF-16 little-endian byte order mark at offset 0",
+                "2 invalid UTF-8 sequences, first at offset 0",
+                "1 control byte at offset 3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_byte_classes() {
+        assert_eq!(ByteClass::of(b'a'), ByteClass::Text);
+        assert_eq!(ByteClass::of(b'\n'), ByteClass::Text);
+        assert_eq!(ByteClass::of(0x00), ByteClass::Control);
+        assert_eq!(ByteClass::of(0x7f), ByteClass::Control);
+        assert_eq!(ByteClass::of(0xc3), ByteClass::NonAscii);
+    }
+
+    #[test]
+    fn test_hex_and_base64() {
+        assert_eq!(hex(b"{\xff\n"), "7b ff 0a");
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar\xff"), "Zm9vYmFy/w==");
+    }
+}
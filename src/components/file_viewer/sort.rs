@@ -0,0 +1,192 @@
+use std::cmp::Ordering;
+
+use serde_json::Value;
+
+use crate::constants::SORT_KEY_CHUNK_SIZE;
+use crate::file::loaders::FileType;
+use crate::helpers::walk_rel;
+
+/// Active root-record ordering: a field path relative to each root
+/// (e.g. `timestamp`, `user.age`, `items[0].price`) and a direction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootSort {
+    pub field: String,
+    pub ascending: bool,
+}
+
+/// Comparable projection of the sort field's value for one root record
+#[derive(Debug, Clone, PartialEq)]
+pub enum SortKey {
+    Bool(bool),
+    Number(f64),
+    String(String),
+    /// Arrays and objects, compared by their JSON text
+    Other(String),
+    /// Field absent or `null` — always ordered last
+    Missing,
+}
+
+impl SortKey {
+    pub fn from_value(value: Option<&Value>) -> Self {
+        match value {
+            None | Some(Value::Null) => SortKey::Missing,
+            Some(Value::Bool(b)) => SortKey::Bool(*b),
+            Some(Value::Number(n)) => n.as_f64().map_or(SortKey::Missing, SortKey::Number),
+            Some(Value::String(s)) => SortKey::String(s.clone()),
+            Some(other) => SortKey::Other(other.to_string()),
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            SortKey::Bool(_) => 0,
+            SortKey::Number(_) => 1,
+            SortKey::String(_) => 2,
+            SortKey::Other(_) => 3,
+            SortKey::Missing => 4,
+        }
+    }
+
+    /// Order two keys in the given direction; `Missing` sorts last either way
+    pub fn compare(&self, other: &Self, ascending: bool) -> Ordering {
+        let ordering = match (self, other) {
+            (SortKey::Missing, SortKey::Missing) => return Ordering::Equal,
+            (SortKey::Missing, _) => return Ordering::Greater,
+            (_, SortKey::Missing) => return Ordering::Less,
+            (SortKey::Bool(a), SortKey::Bool(b)) => a.cmp(b),
+            (SortKey::Number(a), SortKey::Number(b)) => a.total_cmp(b),
+            (SortKey::String(a), SortKey::String(b)) | (SortKey::Other(a), SortKey::Other(b)) => {
+                a.cmp(b)
+            }
+            (a, b) => a.rank().cmp(&b.rank()),
+        };
+        if ascending {
+            ordering
+        } else {
+            ordering.reverse()
+        }
+    }
+}
+
+/// Sort keys for every root record, computed once per field
+pub struct SortKeyCache {
+    pub field: String,
+    pub keys: Vec<SortKey>,
+}
+
+impl SortKeyCache {
+    /// Extract `field` from every root record, reading the file in chunks of
+    /// `SORT_KEY_CHUNK_SIZE` so only one chunk of parsed values is alive at a time
+    pub fn build(loader: &mut FileType, field: &str) -> Self {
+        let total_len = loader.len();
+        let mut keys = Vec::with_capacity(total_len);
+
+        let mut start = 0;
+        while start < total_len {
+            let count = SORT_KEY_CHUNK_SIZE.min(total_len - start);
+            match loader.get_range(start, count) {
+                Ok(values) if values.len() == count => {
+                    keys.extend(values.into_iter().map(|value| key_for(value, field)));
+                }
+                // One unparsable record fails the whole chunk; fall back to
+                // per-record reads so only the broken records lose their key
+                _ => keys.extend((start..start + count).map(|i| match loader.get(i) {
+                    Ok(value) => key_for(value, field),
+                    Err(_) => SortKey::Missing,
+                })),
+            }
+            start += count;
+        }
+
+        Self {
+            field: field.to_string(),
+            keys,
+        }
+    }
+
+    /// Reorder root indices by their cached keys (ties keep file order)
+    pub fn sort_roots(&self, roots: &mut [usize], ascending: bool) {
+        let key = |i: usize| self.keys.get(i).unwrap_or(&SortKey::Missing);
+        roots.sort_by(|&a, &b| key(a).compare(key(b), ascending).then(a.cmp(&b)));
+    }
+}
+
+fn key_for(value: Value, field: &str) -> SortKey {
+    SortKey::from_value(walk_rel(value, field).ok().as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::loaders::NdjsonFile;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn ndjson_loader(lines: &[&str]) -> FileType {
+        let mut tmp = NamedTempFile::new().unwrap();
+        for line in lines {
+            writeln!(tmp, "{}", line).unwrap();
+        }
+        tmp.flush().unwrap();
+        FileType::Ndjson(NdjsonFile::open(tmp.path()).unwrap())
+    }
+
+    fn sorted(loader: &mut FileType, field: &str, ascending: bool) -> Vec<usize> {
+        let cache = SortKeyCache::build(loader, field);
+        let mut roots: Vec<usize> = (0..loader.len()).collect();
+        cache.sort_roots(&mut roots, ascending);
+        roots
+    }
+
+    #[test]
+    fn test_numbers_sort_numerically() {
+        let mut loader = ndjson_loader(&[r#"{"n":10}"#, r#"{"n":2}"#, r#"{"n":-1.5}"#]);
+        assert_eq!(sorted(&mut loader, "n", true), [2, 1, 0]);
+        assert_eq!(sorted(&mut loader, "n", false), [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_strings_and_bools() {
+        let mut loader = ndjson_loader(&[r#"{"s":"b","f":true}"#, r#"{"s":"a","f":false}"#]);
+        assert_eq!(sorted(&mut loader, "s", true), [1, 0]);
+        assert_eq!(sorted(&mut loader, "f", true), [1, 0]);
+        assert_eq!(sorted(&mut loader, "f", false), [0, 1]);
+    }
+
+    #[test]
+    fn test_missing_and_null_sort_last_in_both_directions() {
+        let mut loader = ndjson_loader(&[
+            r#"{"n":null}"#,
+            r#"{"n":3}"#,
+            r#"{"other":1}"#,
+            r#"{"n":1}"#,
+        ]);
+        assert_eq!(sorted(&mut loader, "n", true), [3, 1, 0, 2]);
+        assert_eq!(sorted(&mut loader, "n", false), [1, 3, 0, 2]);
+    }
+
+    #[test]
+    fn test_nested_field_path() {
+        let mut loader = ndjson_loader(&[
+            r#"{"user":{"age":40},"items":[{"price":5}]}"#,
+            r#"{"user":{"age":30},"items":[{"price":9}]}"#,
+        ]);
+        assert_eq!(sorted(&mut loader, "user.age", true), [1, 0]);
+        assert_eq!(sorted(&mut loader, "items[0].price", true), [0, 1]);
+    }
+
+    #[test]
+    fn test_build_spans_multiple_chunks() {
+        let lines: Vec<String> = (0..SORT_KEY_CHUNK_SIZE + 5)
+            .map(|i| format!(r#"{{"n":{}}}"#, SORT_KEY_CHUNK_SIZE + 5 - i))
+            .collect();
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let mut loader = ndjson_loader(&refs);
+
+        let cache = SortKeyCache::build(&mut loader, "n");
+        assert_eq!(cache.keys.len(), refs.len());
+        let roots = sorted(&mut loader, "n", true);
+        assert_eq!(roots.first(), Some(&(refs.len() - 1)));
+        assert_eq!(roots.last(), Some(&0));
+    }
+}
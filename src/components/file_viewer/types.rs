@@ -1,16 +1,151 @@
+use std::collections::HashSet;
+
 /// Common types shared across file viewers
 /// Viewer state that's common to all file types
 #[derive(Default)]
 pub struct ViewerState {
-    /// Filtered root indices (e.g., from search results)
+    /// Root indices to render, in display order (filter with any sort applied)
     pub visible_roots: Option<Vec<usize>>,
 
+    /// Filtered root indices before sorting (e.g., from search results)
+    pub root_filter: Option<Vec<usize>>,
+
     /// Currently selected item path
+    /// With several rows selected, this is the focused one keyboard
+    /// navigation and single-row actions use
     pub selected: Option<String>,
 
+    /// Rows selected together with Shift- or Ctrl/Cmd-click
+    pub multi_selection: MultiSelection,
+
     /// Flag to indicate if we should scroll to the selected item on next render
     pub should_scroll_to_selection: bool,
 
     /// Flag to indicate if this is a large jump (search navigation) vs keyboard navigation
     pub is_search_navigation: bool,
+
+    /// Text the tree's rows are filtered by, separate from the record search
+    pub tree_filter: String,
+}
+
+/// Several rows selected at once, on top of the focused row in
+/// `ViewerState::selected`
+///
+/// The set only counts while it holds the focused row, so anything that moves
+/// the focus elsewhere (search navigation, bookmarks, go to record) drops back
+/// to a single selected row without having to clear it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MultiSelection {
+    /// Every selected row path; empty unless at least two rows are selected
+    paths: HashSet<String>,
+    /// Row a Shift range extends from
+    anchor: Option<String>,
+}
+
+impl MultiSelection {
+    /// The selected paths, when several rows are selected around `focus`
+    pub fn active(&self, focus: &Option<String>) -> Option<&HashSet<String>> {
+        let focus = focus.as_ref()?;
+        (self.paths.len() > 1 && self.paths.contains(focus)).then_some(&self.paths)
+    }
+
+    /// Row a Shift-click or Shift+Up/Down from `focus` extends from
+    pub fn anchor_for(&self, focus: &Option<String>) -> Option<String> {
+        match self.active(focus) {
+            Some(_) => self.anchor.clone(),
+            None => focus.clone(),
+        }
+    }
+
+    /// Select the rows `range`, which runs from `anchor` to the new focus
+    pub fn set_range(&mut self, anchor: String, range: Vec<String>) {
+        self.paths = range.into_iter().collect();
+        self.anchor = Some(anchor);
+        if self.paths.len() < 2 {
+            self.paths.clear();
+        }
+    }
+
+    /// Add `path` to the selection, or take it out if already selected,
+    /// moving the focus to match
+    ///
+    /// Adding focuses the added row. Taking out the focused row moves the
+    /// focus to the anchor, or to some other selected row; taking out the
+    /// only selected row leaves nothing selected.
+    pub fn toggle(&mut self, focus: &mut Option<String>, path: &str) {
+        if self.active(focus).is_none() {
+            self.paths = focus.iter().cloned().collect();
+        }
+        if self.paths.insert(path.to_string()) {
+            *focus = Some(path.to_string());
+            self.anchor = focus.clone();
+        } else {
+            self.paths.remove(path);
+            if !self
+                .anchor
+                .as_ref()
+                .is_some_and(|anchor| self.paths.contains(anchor))
+            {
+                self.anchor = self.paths.iter().min().cloned();
+            }
+            if focus.as_deref() == Some(path) {
+                *focus = self.anchor.clone();
+            }
+        }
+        if self.paths.len() < 2 {
+            self.paths.clear();
+        }
+    }
+
+    /// Drop back to a single selected row
+    pub fn clear(&mut self) {
+        self.paths.clear();
+        self.anchor = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn focus(path: &str) -> Option<String> {
+        Some(path.to_string())
+    }
+
+    #[test]
+    fn test_toggle_adds_and_removes_rows() {
+        let mut multi = MultiSelection::default();
+        let mut selected = focus("0.a");
+        multi.toggle(&mut selected, "0.b");
+        assert_eq!(selected, focus("0.b"));
+        assert_eq!(multi.active(&selected).map(HashSet::len), Some(2));
+
+        multi.toggle(&mut selected, "0.c");
+        multi.toggle(&mut selected, "0.c");
+        assert_eq!(selected, focus("0.b"));
+        assert_eq!(multi.active(&selected).map(HashSet::len), Some(2));
+
+        // One row left is a plain single selection
+        multi.toggle(&mut selected, "0.b");
+        assert_eq!(selected, focus("0.a"));
+        assert!(multi.active(&selected).is_none());
+        multi.toggle(&mut selected, "0.a");
+        assert_eq!(selected, None);
+    }
+
+    #[test]
+    fn test_selection_follows_focus() {
+        let mut multi = MultiSelection::default();
+        multi.set_range(
+            "0.a".to_string(),
+            vec!["0.a".to_string(), "0.b".to_string(), "0.c".to_string()],
+        );
+        assert_eq!(multi.anchor_for(&focus("0.c")), focus("0.a"));
+        // Focus moved outside the selection: it no longer counts
+        assert!(multi.active(&focus("1")).is_none());
+        assert_eq!(multi.anchor_for(&focus("1")), focus("1"));
+
+        multi.set_range("0.a".to_string(), vec!["0.a".to_string()]);
+        assert!(multi.active(&focus("0.a")).is_none());
+    }
 }
@@ -1,8 +1,25 @@
-use eframe::egui::Ui;
+use eframe::egui::{self, Ui};
 use serde_json::Value;
+use std::collections::HashSet;
 
+use super::sort::RootSort;
+use super::types::MultiSelection;
 use crate::file::loaders::FileType;
-use crate::helpers::LruCache;
+use crate::helpers::{LruCache, NumberFormat, PathFormat};
+use crate::settings::IndentGuides;
+use crate::theme::ThemeColors;
+
+/// Outcome of setting a viewer's expansion to a fixed depth
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthExpansion {
+    /// The expanded set already matched the requested depth
+    Unchanged,
+    /// The expanded set was replaced; a rebuild applies it
+    Changed,
+    /// The requested depth would render more than `EXPAND_TO_DEPTH_MAX_ROWS`
+    /// rows, so the expansion was left untouched
+    TooManyRows,
+}
 
 /// Trait that all file format viewers must implement
 ///
@@ -44,21 +61,27 @@ pub trait FileFormatViewer {
     /// # Arguments
     /// * `ui` - egui UI context
     /// * `selected` - Currently selected item path (mutable)
+    /// * `multi_selection` - Rows selected together with Shift- or Ctrl/Cmd-click (mutable)
     /// * `cache` - LRU cache for parsed values
     /// * `loader` - File loader for lazy loading
     /// * `should_scroll_to_selection` - Whether to scroll to the selected item (mutable, will be reset after scrolling)
     /// * `is_search_navigation` - Whether this is search navigation (large jump) vs keyboard navigation
     /// * `syntax_highlighting` - Whether to enable syntax highlighting
+    /// * `indent_size` - Width of one tree indentation level, in pixels
+    /// * `show_type_badges` - Whether to show a value type badge at the end of each row
     #[allow(clippy::too_many_arguments)]
     fn render(
         &mut self,
         ui: &mut Ui,
         selected: &mut Option<String>,
+        multi_selection: &mut MultiSelection,
         cache: &mut LruCache<usize, Value>,
         loader: &mut FileType,
         should_scroll_to_selection: &mut bool,
         is_search_navigation: bool,
         syntax_highlighting: bool,
+        indent_size: f32,
+        show_type_badges: bool,
     ) -> bool;
 
     // ========================================================================
@@ -79,12 +102,69 @@ pub trait FileFormatViewer {
         false // Default: no-op
     }
 
-    /// Expand all nodes in the tree
-    /// Returns true if a rebuild is needed
+    /// Start expanding all nodes in the tree
+    /// Viewers with potentially huge trees may queue the work and apply it
+    /// incrementally via `step_expand_all`
+    /// Returns true if there is work to do
     fn expand_all(&mut self) -> bool {
         false // Default: no-op
     }
 
+    /// Advance an in-progress expand-all by at most `budget` nodes
+    /// Returns true if a rebuild is needed
+    fn step_expand_all(
+        &mut self,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+        budget: usize,
+    ) -> bool {
+        let _ = (cache, loader, budget);
+        false // Default: no-op
+    }
+
+    /// Progress of an in-progress expand-all in `0.0..=1.0`, or None when idle
+    fn expand_all_progress(&self) -> Option<f32> {
+        None // Default: no-op
+    }
+
+    /// Abort an in-progress expand-all, keeping whatever was already expanded
+    /// Returns true if a job was cancelled
+    fn cancel_expand_all(&mut self) -> bool {
+        false // Default: no-op
+    }
+
+    /// Open every node shallower than `depth` among `visible_roots` (all
+    /// roots when None) and close everything deeper
+    fn expand_to_depth(
+        &mut self,
+        depth: usize,
+        visible_roots: &Option<Vec<usize>>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+        total_len: usize,
+    ) -> DepthExpansion {
+        let _ = (depth, visible_roots, cache, loader, total_len);
+        DepthExpansion::Unchanged // Default: no-op
+    }
+
+    /// Close every node at `depth` or deeper, leaving shallower nodes open
+    fn collapse_to_depth(
+        &mut self,
+        depth: usize,
+        visible_roots: &Option<Vec<usize>>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+        total_len: usize,
+    ) -> DepthExpansion {
+        let _ = (depth, visible_roots, cache, loader, total_len);
+        DepthExpansion::Unchanged // Default: no-op
+    }
+
+    /// Depth of the deepest expanded node currently shown (0 when fully collapsed)
+    fn expanded_depth(&self) -> usize {
+        0 // Default: no-op
+    }
+
     /// Collapse all nodes in the tree
     /// Returns true if a rebuild is needed
     fn collapse_all(&mut self) -> bool {
@@ -105,6 +185,157 @@ pub trait FileFormatViewer {
         None // Default: no-op
     }
 
+    /// Paths of the rows from `from` to `to` inclusive, in display order
+    /// Used to select a range with Shift-click or Shift+Up/Down
+    fn rows_between(&self, from: &str, to: &str) -> Vec<String> {
+        let _ = from;
+        vec![to.to_string()] // Default: just the row moved to
+    }
+
+    /// Move selection to the first child of an expanded node
+    /// Returns the new selection path, or None if the node has no visible children
+    fn select_first_child(&self, current: &Option<String>) -> Option<String> {
+        let _ = current;
+        None // Default: no-op
+    }
+
+    /// Move selection to the parent of the current node
+    /// Returns the new selection path, or None at the top level
+    fn select_parent(&self, current: &Option<String>) -> Option<String> {
+        let _ = current;
+        None // Default: no-op
+    }
+
+    /// Paths of the currently expanded nodes, for viewers with a tree
+    fn expanded_paths(&self) -> Option<&HashSet<String>> {
+        None // Default: no-op
+    }
+
+    /// Replace the expanded node set (e.g. restored from a previous session)
+    /// Paths that don't exist in the file are ignored when rows are built
+    fn set_expanded_paths(&mut self, expanded: HashSet<String>) {
+        let _ = expanded; // Default: no-op
+    }
+
+    /// Page any long arrays along `path` so the row at `path` is built
+    fn show_in_array_windows(&mut self, path: &str) {
+        let _ = path; // Default: no-op
+    }
+
+    /// Take a sort the user requested while rendering (e.g. from a context menu)
+    /// FileViewer applies it after the frame's render
+    fn take_sort_request(&mut self) -> Option<RootSort> {
+        None // Default: no-op
+    }
+
+    /// Mark a root record as failing schema validation, along with the row
+    /// paths of the values that failed
+    fn set_schema_violations(&mut self, record_index: usize, paths: HashSet<String>) {
+        let _ = (record_index, paths); // Default: no-op
+    }
+
+    /// Remove every schema validation marker
+    fn clear_schema_violations(&mut self) {
+        // Default: no-op
+    }
+
+    /// Allow or disallow editing primitive values in place
+    fn set_inline_editing(&mut self, enabled: bool) {
+        let _ = enabled; // Default: no-op
+    }
+
+    /// Set how numeric leaf values are displayed
+    fn set_number_format(&mut self, format: NumberFormat) {
+        let _ = format; // Default: no-op
+    }
+
+    /// Set how tree indent guide lines are drawn
+    fn set_indent_guides(&mut self, guides: IndentGuides) {
+        let _ = guides; // Default: no-op
+    }
+
+    /// Set how many rows keyboard navigation keeps between the selection
+    /// and the edge of the view
+    fn set_scroll_margin(&mut self, rows: usize) {
+        let _ = rows; // Default: no-op
+    }
+
+    /// Show readable dates after timestamp values; `fields` are the name
+    /// patterns for numeric timestamps, `None` turns it off
+    fn set_timestamp_fields(&mut self, fields: Option<&[String]>) {
+        let _ = fields; // Default: no-op
+    }
+
+    /// Hide the built rows whose key and value don't contain `filter`,
+    /// ignoring case, keeping the ancestors of the rows that do; an empty
+    /// filter shows every row
+    fn set_tree_filter(&mut self, filter: &str) {
+        let _ = filter; // Default: no-op
+    }
+
+    /// Rows matching the tree filter as of the last rebuild, or None if the
+    /// viewer can't filter its rows
+    fn tree_filter_matches(&self) -> Option<usize> {
+        None // Default: no-op
+    }
+
+    /// How far the rows were scrolled on the last render
+    fn scroll_offset(&self) -> egui::Vec2 {
+        egui::Vec2::ZERO // Default: no-op
+    }
+
+    /// Scroll the rows to `offset` on the next render
+    fn set_scroll_offset(&mut self, offset: egui::Vec2) {
+        let _ = offset; // Default: no-op
+    }
+
+    /// The rows as currently shown, as a standalone HTML page styled with
+    /// `colors`; `None` for viewers that can't export
+    fn export_html(
+        &self,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+        title: &str,
+        colors: &ThemeColors,
+        indent_size: f32,
+    ) -> Option<String> {
+        let _ = (cache, loader, title, colors, indent_size); // Default: no-op
+        None
+    }
+
+    /// Switch between the tree and a flat `path: value` list of leaves
+    /// Opens the selected row's root so it is flattened right away
+    /// Returns true if a rebuild is needed
+    fn toggle_flat_view(&mut self, selected: &Option<String>) -> bool {
+        let _ = selected;
+        false // Default: no-op
+    }
+
+    /// Take the root record the user asked to view formatted (e.g. from a context menu)
+    /// FileViewer opens the modal after the frame's render
+    fn take_view_formatted_request(&mut self) -> Option<usize> {
+        None // Default: no-op
+    }
+
+    /// Take the root record the user asked to view as raw bytes (e.g. from a context menu)
+    /// FileViewer opens the modal after the frame's render
+    fn take_view_raw_bytes_request(&mut self) -> Option<usize> {
+        None // Default: no-op
+    }
+
+    /// Take the field (relative to each root) the user asked for stats on
+    /// (e.g. from a context menu); FileViewer computes and shows them after
+    /// the frame's render
+    fn take_field_stats_request(&mut self) -> Option<String> {
+        None // Default: no-op
+    }
+
+    /// Take text the user copied from the viewer (e.g. from a context menu)
+    /// The app puts it on the clipboard and records it in the clipboard history
+    fn take_copy_request(&mut self) -> Option<String> {
+        None // Default: no-op
+    }
+
     /// Navigate to a specific root record by index
     /// This should select the record and expand it if applicable
     /// Returns true if a rebuild is needed
@@ -148,9 +379,59 @@ pub trait FileFormatViewer {
         None // Default: no-op
     }
 
-    /// Copy the path of the currently selected item to clipboard
+    /// Copy the entire object of the currently selected item to clipboard (single-line JSON)
     /// Returns the text to copy, or None if not applicable
-    fn copy_selected_path(&self, selected: &Option<String>) -> Option<String> {
+    fn copy_selected_object_minified(
+        &self,
+        selected: &Option<String>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String> {
+        let _ = (selected, cache, loader);
+        None // Default: no-op
+    }
+
+    /// Copy the path of the currently selected item to clipboard, written in `format`
+    /// Returns the text to copy, or None if not applicable
+    fn copy_selected_path(
+        &self,
+        selected: &Option<String>,
+        format: PathFormat,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String> {
+        let _ = (format, cache, loader);
         selected.clone() // Default: return the path itself
     }
+
+    /// Copy the source line number of the selected item's record to clipboard
+    /// Returns the text to copy, or None if not applicable
+    fn copy_selected_line_number(
+        &self,
+        selected: &Option<String>,
+        loader: &FileType,
+    ) -> Option<String> {
+        let _ = (selected, loader);
+        None // Default: no-op
+    }
+
+    /// The rows of a multi-row selection to copy, in display order
+    /// Rows inside another selected row are left out, as copying the outer
+    /// row already includes them
+    fn selected_rows(&self, paths: &HashSet<String>) -> Vec<String> {
+        let _ = paths;
+        Vec::new() // Default: no-op
+    }
+
+    /// Copy a multi-row selection as `path: value` lines, values as JSON
+    /// Returns the text to copy, or None if not applicable
+    fn copy_selected_rows(
+        &self,
+        paths: &HashSet<String>,
+        cache: &mut LruCache<usize, Value>,
+        loader: &mut FileType,
+    ) -> Option<String> {
+        let _ = (paths, cache, loader);
+        None // Default: no-op
+    }
 }
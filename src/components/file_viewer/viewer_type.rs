@@ -2,6 +2,41 @@ use super::json_tree_viewer::JsonTreeViewer;
 use super::plugin_table_viewer::PluginTableViewer;
 use super::viewer_trait::FileFormatViewer;
 use crate::file::loaders::FileKind;
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+/// Builds the viewer for one kind of file
+pub type ViewerConstructor = fn() -> ViewerType;
+
+/// Viewer constructors keyed by file kind, seeded with the built-ins
+///
+/// Kinds without an entry fall back to the JSON tree viewer.
+static VIEWERS: LazyLock<RwLock<HashMap<FileKind, ViewerConstructor>>> = LazyLock::new(|| {
+    let builtins: [(FileKind, ViewerConstructor); 9] = [
+        (FileKind::Json, json_viewer),
+        (FileKind::Ndjson, json_viewer),
+        (FileKind::Csv, json_viewer),
+        (FileKind::Yaml, json_viewer),
+        (FileKind::Toml, json_viewer),
+        (FileKind::Xml, json_viewer),
+        (FileKind::Parquet, json_viewer),
+        (FileKind::Plugin, json_viewer),
+        (FileKind::PluginTable, || {
+            ViewerType::PluginTable(PluginTableViewer::new())
+        }),
+    ];
+    RwLock::new(HashMap::from(builtins))
+});
+
+fn json_viewer() -> ViewerType {
+    ViewerType::Json(JsonTreeViewer::new())
+}
+
+/// Show files of `kind` with viewers built by `build`, returning the
+/// constructor it replaces
+pub fn register_viewer(kind: FileKind, build: ViewerConstructor) -> Option<ViewerConstructor> {
+    VIEWERS.write().ok()?.insert(kind, build)
+}
 
 /// Enum representing different file format viewers
 ///
@@ -17,7 +52,8 @@ use crate::file::loaders::FileKind;
 /// 1. Create a new viewer struct (e.g., `CsvTableViewer`)
 /// 2. Implement `FileFormatViewer` trait for it (REQUIRED - enforced at compile time)
 /// 3. Add a new variant to this enum: `Csv(CsvTableViewer)`
-/// 4. Update `from_file_type()` to handle the new file type
+/// 4. Register a constructor for the file kind with [`register_viewer`]
+///    (built-ins are seeded in `VIEWERS`)
 /// 5. Update the `as_viewer_mut()` match to include the new variant
 ///
 /// # Example
@@ -32,8 +68,8 @@ use crate::file::loaders::FileKind;
 ///     Csv(CsvTableViewer),  // Compiler will verify FileFormatViewer is implemented
 /// }
 ///
-/// // Step 4: Update from_file_type
-/// FileType::Csv => ViewerType::Csv(CsvTableViewer::new()),
+/// // Step 4: Register the constructor
+/// register_viewer(FileKind::Csv, || ViewerType::Csv(CsvTableViewer::new()));
 ///
 /// // Step 5: Update as_viewer_mut - this enforces the trait bound!
 /// ViewerType::Csv(viewer) => viewer,  // Won't compile if trait not implemented
@@ -46,14 +82,14 @@ pub enum ViewerType {
 }
 
 impl ViewerType {
-    /// Create a viewer based on file type
+    /// Create the viewer registered for a file type
     pub fn from_file_type(file_type: FileKind) -> Self {
-        match file_type {
-            FileKind::Json | FileKind::Ndjson | FileKind::Plugin => {
-                ViewerType::Json(JsonTreeViewer::new())
-            }
-            FileKind::PluginTable => ViewerType::PluginTable(PluginTableViewer::new()),
-        }
+        let build = VIEWERS
+            .read()
+            .ok()
+            .and_then(|viewers| viewers.get(&file_type).copied())
+            .unwrap_or(json_viewer);
+        build()
     }
 
     /// Get mutable reference to the underlying viewer as a trait object
@@ -73,4 +109,12 @@ impl ViewerType {
             ViewerType::PluginTable(viewer) => viewer,
         }
     }
+
+    /// Get a shared reference to the underlying viewer as a trait object
+    pub fn as_viewer(&self) -> &dyn FileFormatViewer {
+        match self {
+            ViewerType::Json(viewer) => viewer,
+            ViewerType::PluginTable(viewer) => viewer,
+        }
+    }
 }
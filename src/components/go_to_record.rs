@@ -0,0 +1,246 @@
+use eframe::egui::{self, RichText};
+
+use crate::components::traits::StatefulComponent;
+use crate::search::results::SearchResults;
+use crate::theme::ThemeColors;
+use thoth_plugin_sdk::components::{Button, ButtonColor, ButtonType, Input, Typography};
+
+/// "Go to record #" dialog: jumps straight to a root record by its index
+#[derive(Default)]
+pub struct GoToRecordDialog {
+    pub open: bool,
+    input: String,
+    /// Stay within the active search's results instead of clearing the search
+    within_results: bool,
+    error: Option<String>,
+    focus_input: bool,
+}
+
+pub struct GoToRecordProps<'a> {
+    /// Number of root records in the open file
+    pub total_records: usize,
+    /// Results of the active search, if any
+    pub search_results: Option<&'a SearchResults>,
+}
+
+/// A validated jump request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GoToRecordRequest {
+    pub record_index: usize,
+    /// Clear the active search before navigating
+    pub clear_search: bool,
+}
+
+impl GoToRecordDialog {
+    /// Open the dialog with the input focused, keeping the last entered index
+    pub fn show_dialog(&mut self) {
+        self.open = true;
+        self.within_results = true;
+        self.error = None;
+        self.focus_input = true;
+    }
+}
+
+impl StatefulComponent for GoToRecordDialog {
+    type Props<'a> = GoToRecordProps<'a>;
+    type Output = Option<GoToRecordRequest>;
+
+    fn render(&mut self, ui: &mut egui::Ui, props: Self::Props<'_>) -> Self::Output {
+        if !self.open {
+            return None;
+        }
+        let colors = ui.ctx().memory(|mem| {
+            mem.data
+                .get_temp::<ThemeColors>(egui::Id::new("theme_colors"))
+                .unwrap_or_else(|| crate::theme::Theme::default().colors())
+        });
+
+        let mut request = None;
+        let mut close = false;
+        let modal = egui::Modal::new(egui::Id::new("go_to_record_modal")).show(ui.ctx(), |ui| {
+            ui.set_width(320.0);
+            Typography::heading(ui, "Go to record");
+            ui.add_space(4.0);
+            Typography::body_muted(
+                ui,
+                &match props.total_records {
+                    0 => "The file has no records".to_string(),
+                    n => format!("Record index from 0 to {}", n - 1),
+                },
+            );
+            ui.add_space(8.0);
+
+            let mut input = Input::builder()
+                .id("go_to_record_input")
+                .value(self.input.clone())
+                .placeholder("Record #")
+                .icon(egui_phosphor::regular::HASH)
+                .build();
+            let r = input.show(ui);
+            if r.inner {
+                self.input = input.value.clone();
+                self.error = None;
+            }
+            if std::mem::take(&mut self.focus_input) {
+                r.response.request_focus();
+            }
+            let submitted =
+                r.response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+            if props.search_results.is_some() {
+                ui.add_space(4.0);
+                ui.checkbox(&mut self.within_results, "Within search results")
+                    .on_hover_text("Unchecked, the search is cleared before jumping");
+            }
+
+            if let Some(error) = &self.error {
+                ui.add_space(4.0);
+                ui.label(RichText::new(error).color(colors.error).size(12.0));
+            }
+
+            ui.add_space(12.0);
+            let mut go = submitted;
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                go |= ui
+                    .add(
+                        Button::builder()
+                            .label("Go")
+                            .button_type(ButtonType::Elevated)
+                            .color(ButtonColor::Primary)
+                            .build(),
+                    )
+                    .clicked();
+                if ui
+                    .add(
+                        Button::builder()
+                            .label("Cancel")
+                            .button_type(ButtonType::Text)
+                            .color(ButtonColor::Default)
+                            .build(),
+                    )
+                    .clicked()
+                {
+                    close = true;
+                }
+            });
+
+            if go {
+                match resolve_target(
+                    &self.input,
+                    props.total_records,
+                    props.search_results,
+                    self.within_results,
+                ) {
+                    Ok(target) => {
+                        request = Some(target);
+                        close = true;
+                    }
+                    Err(error) => {
+                        self.error = Some(error);
+                        self.focus_input = true;
+                    }
+                }
+            }
+        });
+
+        if close || modal.should_close() {
+            self.open = false;
+        }
+        request
+    }
+}
+
+/// Validate the entered index against the file and, when staying within
+/// search results, against the records the search matched
+fn resolve_target(
+    input: &str,
+    total_records: usize,
+    search_results: Option<&SearchResults>,
+    within_results: bool,
+) -> Result<GoToRecordRequest, String> {
+    let record_index: usize = input
+        .trim()
+        .trim_start_matches('#')
+        .parse()
+        .map_err(|_| "Enter a record number, e.g. 45000".to_string())?;
+    if record_index >= total_records {
+        return Err(match total_records {
+            0 => "The file has no records".to_string(),
+            n => format!("Record #{} is out of range (0–{})", record_index, n - 1),
+        });
+    }
+
+    let clear_search = match search_results {
+        Some(results) if within_results => {
+            if !results.record_indices().any(|idx| idx == record_index) {
+                return Err(format!(
+                    "Record #{} is not in the search results",
+                    record_index
+                ));
+            }
+            false
+        }
+        Some(_) => true,
+        None => false,
+    };
+    Ok(GoToRecordRequest {
+        record_index,
+        clear_search,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::results::SearchHit;
+
+    fn results(records: &[usize]) -> SearchResults {
+        let hits = records
+            .iter()
+            .map(|&record_index| SearchHit {
+                record_index,
+                ..SearchHit::default()
+            })
+            .collect();
+        SearchResults::new(hits, 100)
+    }
+
+    #[test]
+    fn test_resolve_target_validates_range() {
+        assert_eq!(
+            resolve_target(" 42 ", 100, None, true),
+            Ok(GoToRecordRequest {
+                record_index: 42,
+                clear_search: false
+            })
+        );
+        assert_eq!(
+            resolve_target("#7", 100, None, true).unwrap().record_index,
+            7
+        );
+        assert!(resolve_target("100", 100, None, true).is_err());
+        assert!(resolve_target("-1", 100, None, true).is_err());
+        assert!(resolve_target("abc", 100, None, true).is_err());
+        assert!(resolve_target("0", 0, None, true).is_err());
+    }
+
+    #[test]
+    fn test_resolve_target_with_search() {
+        let found = results(&[3, 9]);
+        assert_eq!(
+            resolve_target("9", 100, Some(&found), true),
+            Ok(GoToRecordRequest {
+                record_index: 9,
+                clear_search: false
+            })
+        );
+        assert!(resolve_target("4", 100, Some(&found), true).is_err());
+        assert_eq!(
+            resolve_target("4", 100, Some(&found), false),
+            Ok(GoToRecordRequest {
+                record_index: 4,
+                clear_search: true
+            })
+        );
+    }
+}
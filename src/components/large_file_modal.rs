@@ -0,0 +1,186 @@
+use eframe::egui::{self, Frame, Layout, Margin, RichText};
+
+use crate::{
+    components::traits::StatelessComponent,
+    helpers::format_bytes,
+    theme::{ThemeColors, phosphor_font_id},
+};
+use thoth_plugin_sdk::components::{
+    Button, ButtonColor, ButtonSize, ButtonType, Typography, TypographyVariant,
+};
+
+/// Confirmation shown before opening a file larger than
+/// `performance.max_file_size_mb`
+pub struct LargeFileModal;
+
+pub struct LargeFileModalProps<'a> {
+    pub file_name: &'a str,
+    pub size_bytes: u64,
+    pub limit_mb: u64,
+}
+
+/// What the user chose; all `false` while the modal stays open
+#[derive(Default)]
+pub struct LargeFileModalOutput {
+    pub open_once: bool,
+    pub raise_limit: bool,
+    pub cancel: bool,
+}
+
+impl StatelessComponent for LargeFileModal {
+    type Props<'a> = LargeFileModalProps<'a>;
+    type Output = LargeFileModalOutput;
+
+    fn render(ui: &mut egui::Ui, props: Self::Props<'_>) -> Self::Output {
+        let colors = ui.ctx().memory(|mem| {
+            mem.data
+                .get_temp::<ThemeColors>(egui::Id::new("theme_colors"))
+                .unwrap_or_else(|| crate::theme::Theme::default().colors())
+        });
+
+        let mut output = LargeFileModalOutput::default();
+
+        let modal = egui::Modal::new(egui::Id::new("large_file_modal")).show(ui.ctx(), |ui| {
+            ui.set_width(420.0);
+
+            // ── Header ────────────────────────────────────────────────────────
+            Frame::new()
+                .inner_margin(Margin {
+                    left: 24,
+                    right: 24,
+                    top: 24,
+                    bottom: 16,
+                })
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(egui_phosphor::regular::WARNING)
+                                .font(phosphor_font_id(28.0))
+                                .color(colors.warning),
+                        );
+                        ui.add_space(10.0);
+                        ui.vertical(|ui| {
+                            ui.add(
+                                Typography::builder()
+                                    .text("Large File")
+                                    .variant(TypographyVariant::BodyLarge)
+                                    .bold(true)
+                                    .build(),
+                            );
+                            Typography::body_muted(ui, props.file_name);
+                        });
+                    });
+                });
+
+            ui.add(egui::Separator::default().spacing(0.0));
+
+            // ── Body ──────────────────────────────────────────────────────────
+            Frame::new()
+                .inner_margin(Margin {
+                    left: 24,
+                    right: 24,
+                    top: 16,
+                    bottom: 16,
+                })
+                .show(ui, |ui| {
+                    Typography::body(
+                        ui,
+                        &format!(
+                            "This file is {}, larger than your {} MB limit. Open anyway?",
+                            format_bytes(props.size_bytes),
+                            props.limit_mb
+                        ),
+                    );
+                });
+
+            ui.add(egui::Separator::default().spacing(0.0));
+
+            // ── Footer ────────────────────────────────────────────────────────
+            Frame::new()
+                .fill(colors.bg_sunken)
+                .inner_margin(Margin {
+                    left: 24,
+                    right: 24,
+                    top: 12,
+                    bottom: 12,
+                })
+                .show(ui, |ui| {
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .add(
+                                Button::builder()
+                                    .label("Open Once")
+                                    .button_type(ButtonType::Elevated)
+                                    .color(ButtonColor::Primary)
+                                    .button_size(ButtonSize::Medium)
+                                    .build(),
+                            )
+                            .clicked()
+                        {
+                            output.open_once = true;
+                        }
+
+                        ui.add_space(8.0);
+
+                        if ui
+                            .add(
+                                Button::builder()
+                                    .label("Raise Limit")
+                                    .button_type(ButtonType::Elevated)
+                                    .color(ButtonColor::Default)
+                                    .button_size(ButtonSize::Medium)
+                                    .build(),
+                            )
+                            .on_hover_text(format!(
+                                "Set performance.max_file_size_mb to {} MB and open",
+                                raised_limit_mb(props.size_bytes)
+                            ))
+                            .clicked()
+                        {
+                            output.raise_limit = true;
+                        }
+
+                        ui.add_space(8.0);
+
+                        if ui
+                            .add(
+                                Button::builder()
+                                    .label("Cancel")
+                                    .button_type(ButtonType::Text)
+                                    .color(ButtonColor::Default)
+                                    .button_size(ButtonSize::Medium)
+                                    .build(),
+                            )
+                            .clicked()
+                        {
+                            output.cancel = true;
+                        }
+                    });
+                });
+        });
+
+        if modal.should_close() {
+            output.cancel = true;
+        }
+        output
+    }
+}
+
+/// Size limit that admits a file of `size_bytes`, rounded up to the next 100 MB
+pub fn raised_limit_mb(size_bytes: u64) -> u64 {
+    let size_mb = size_bytes.div_ceil(1024 * 1024);
+    size_mb.div_ceil(100).max(1) * 100
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raised_limit_rounds_up_to_hundreds() {
+        assert_eq!(raised_limit_mb(1), 100);
+        assert_eq!(raised_limit_mb(500 * 1024 * 1024), 500);
+        assert_eq!(raised_limit_mb(500 * 1024 * 1024 + 1), 600);
+        assert_eq!(raised_limit_mb(1288490189), 1300);
+    }
+}
@@ -8,16 +8,23 @@ pub use common::traits;
 pub mod bookmarks;
 pub mod central_panel;
 pub mod chart_studio;
+pub mod clipboard_history;
+pub mod command_palette;
 pub mod data_source_panel;
 pub mod drag_and_drop;
 pub mod error_modal;
+pub mod export_records;
 pub mod file_viewer;
+pub mod go_to_record;
+pub mod large_file_modal;
 pub mod marketplace;
 pub mod recent_files;
+pub mod schema_panel;
 pub mod search;
 pub mod settings_dialog;
 pub mod sidebar;
 pub mod status_bar;
 pub mod toolbar;
+pub mod unsaved_edits_modal;
 pub mod update_consent_modal;
 pub mod welcome;
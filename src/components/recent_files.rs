@@ -1,18 +1,26 @@
+use std::collections::HashSet;
+
 use crate::components::traits::StatefulComponent;
 use eframe::egui;
 use thoth_plugin_sdk::components::{
-    Button, ButtonColor, ButtonType, IconButton, List, ListEvent, ListItem, ListItemPostfix,
-    ListItemPrefix, SidebarHeader,
+    Button, ButtonColor, ButtonType, IconButton, List, ListEvent, ListItem, ListItemAction,
+    ListItemBadge, ListItemPostfix, ListItemPrefix, SidebarHeader, Typography,
 };
 
 pub struct RecentFilesProps<'a> {
     pub recent_files: &'a [String],
+    /// Files pinned above the recent list
+    pub pinned_files: &'a [String],
+    /// Files that couldn't be found on disk, shown greyed out
+    pub missing_files: &'a HashSet<String>,
 }
 
 #[derive(Debug, Clone)]
 pub enum RecentFilesEvent {
     OpenFile(String),
     RemoveFile(String),
+    /// Pin the file, or unpin it if already pinned
+    TogglePin(String),
     OpenFilePicker,
 }
 
@@ -44,49 +52,27 @@ impl StatefulComponent for RecentFiles {
             .scroll([false, true])
             .auto_shrink([false, false])
             .show(ui, |ui| {
-                let items: Vec<ListItem> = props
-                    .recent_files
-                    .iter()
-                    .map(|path| {
-                        let filename = std::path::Path::new(path)
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or(path.as_str());
-                        ListItem::builder()
-                            .title(filename.to_string())
-                            .prefix(ListItemPrefix::Icon {
-                                glyph: egui_phosphor::regular::FILE.to_string(),
-                                color: None,
-                            })
-                            .postfix(ListItemPostfix::IconButton(
-                                IconButton::builder()
-                                    .icon(egui_phosphor::regular::X)
-                                    .frame(true)
-                                    .tooltip("Remove")
-                                    .build(),
-                            ))
-                            .build()
-                    })
-                    .collect();
-
-                match List::builder()
-                    .items(items)
-                    .empty_label("No recent files")
-                    .build()
-                    .show(ui)
-                {
-                    Some(ListEvent::PostfixClicked(i)) => {
-                        if let Some(path) = props.recent_files.get(i) {
-                            events.push(RecentFilesEvent::RemoveFile(path.clone()));
-                        }
-                    }
-                    Some(ListEvent::ItemClicked(i)) => {
-                        if let Some(path) = props.recent_files.get(i) {
-                            events.push(RecentFilesEvent::OpenFile(path.clone()));
-                        }
-                    }
-                    _ => {}
+                if !props.pinned_files.is_empty() {
+                    Typography::caption(ui, "Pinned");
+                    show_file_list(
+                        ui,
+                        "pinned-files",
+                        props.pinned_files,
+                        props.missing_files,
+                        true,
+                        &mut events,
+                    );
+                    ui.add_space(8.0);
+                    Typography::caption(ui, "Recent");
                 }
+                show_file_list(
+                    ui,
+                    "recent-files",
+                    props.recent_files,
+                    props.missing_files,
+                    false,
+                    &mut events,
+                );
                 ui.add_space(8.0);
 
                 let avail = ui.available_width();
@@ -111,3 +97,93 @@ impl StatefulComponent for RecentFiles {
         RecentFilesOutput { events }
     }
 }
+
+/// Render one list of files, turning row clicks into events
+fn show_file_list(
+    ui: &mut egui::Ui,
+    id: &str,
+    paths: &[String],
+    missing: &HashSet<String>,
+    pinned: bool,
+    events: &mut Vec<RecentFilesEvent>,
+) {
+    let (glyph, pin_icon, pin_tooltip) = if pinned {
+        (
+            egui_phosphor::regular::PUSH_PIN,
+            egui_phosphor::regular::PUSH_PIN_SLASH,
+            "Unpin",
+        )
+    } else {
+        (
+            egui_phosphor::regular::FILE,
+            egui_phosphor::regular::PUSH_PIN,
+            "Pin",
+        )
+    };
+
+    let items: Vec<ListItem> = paths
+        .iter()
+        .map(|path| {
+            let filename = std::path::Path::new(path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(path.as_str());
+            let missing = missing.contains(path);
+            ListItem::builder()
+                .title(filename.to_string())
+                .prefix(ListItemPrefix::Icon {
+                    glyph: if missing {
+                        egui_phosphor::regular::FILE_X.to_string()
+                    } else {
+                        glyph.to_string()
+                    },
+                    color: None,
+                })
+                .maybe_badge(missing.then(|| {
+                    ListItemBadge::builder()
+                        .text("missing")
+                        .color("muted")
+                        .build()
+                }))
+                .actions(vec![
+                    ListItemAction::builder()
+                        .icon(pin_icon)
+                        .tooltip(pin_tooltip)
+                        .build(),
+                ])
+                .postfix(ListItemPostfix::IconButton(
+                    IconButton::builder()
+                        .icon(egui_phosphor::regular::X)
+                        .frame(true)
+                        .tooltip("Remove")
+                        .build(),
+                ))
+                .build()
+        })
+        .collect();
+
+    let event = List::builder()
+        .id(id)
+        .items(items)
+        .empty_label("No recent files")
+        .build()
+        .show(ui);
+    match event {
+        Some(ListEvent::PostfixClicked(i)) => {
+            if let Some(path) = paths.get(i) {
+                events.push(RecentFilesEvent::RemoveFile(path.clone()));
+            }
+        }
+        Some(ListEvent::ActionClicked { item, .. }) => {
+            if let Some(path) = paths.get(item) {
+                events.push(RecentFilesEvent::TogglePin(path.clone()));
+            }
+        }
+        Some(ListEvent::ItemClicked(i)) => {
+            if let Some(path) = paths.get(i) {
+                events.push(RecentFilesEvent::OpenFile(path.clone()));
+            }
+        }
+        None => {}
+    }
+}
@@ -0,0 +1,198 @@
+use crate::components::traits::StatefulComponent;
+use crate::constants::MAX_SCHEMA_PANEL_ERRORS;
+use crate::file::schema::{SchemaValidation, SchemaValidator, SchemaViolation};
+use eframe::egui;
+use std::sync::Arc;
+use thoth_plugin_sdk::components::{
+    List, ListEvent, ListItem, ListItemPrefix, Separator, SidebarHeader, SidebarHeaderAction,
+    Typography,
+};
+
+/// Snapshot of the active tab's schema validation, taken each frame for the sidebar
+#[derive(Debug, Clone, Default)]
+pub struct SchemaStatus {
+    /// File name of the loaded schema; `None` when no schema is loaded
+    pub schema_name: Option<String>,
+    pub checked: usize,
+    pub total: usize,
+    pub invalid_records: usize,
+    /// Violations in file order, capped at `MAX_SCHEMA_PANEL_ERRORS`
+    pub violations: Vec<(usize, SchemaViolation)>,
+}
+
+impl SchemaStatus {
+    pub fn new(
+        schema: Option<&Arc<SchemaValidator>>,
+        validation: Option<&SchemaValidation>,
+    ) -> Self {
+        let Some(schema) = schema else {
+            return Self::default();
+        };
+        let schema_name = schema
+            .path()
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| schema.path().to_string_lossy().into_owned());
+
+        let Some(validation) = validation else {
+            return Self {
+                schema_name: Some(schema_name),
+                ..Self::default()
+            };
+        };
+        let (checked, total) = validation.progress();
+        let violations = validation
+            .violations()
+            .iter()
+            .flat_map(|(record_index, violations)| {
+                violations.iter().map(|v| (*record_index, v.clone()))
+            })
+            .take(MAX_SCHEMA_PANEL_ERRORS)
+            .collect();
+
+        Self {
+            schema_name: Some(schema_name),
+            checked,
+            total,
+            invalid_records: validation.violations().len(),
+            violations,
+        }
+    }
+}
+
+pub struct SchemaPanelProps<'a> {
+    pub status: &'a SchemaStatus,
+}
+
+#[derive(Debug, Clone)]
+pub enum SchemaPanelEvent {
+    LoadSchema,
+    ClearSchema,
+    /// Select the row of a failing value
+    NavigateToViolation(String),
+}
+
+pub struct SchemaPanelOutput {
+    pub events: Vec<SchemaPanelEvent>,
+}
+
+#[derive(Default)]
+pub struct SchemaPanel;
+
+impl StatefulComponent for SchemaPanel {
+    type Props<'a> = SchemaPanelProps<'a>;
+    type Output = SchemaPanelOutput;
+
+    fn render(&mut self, ui: &mut egui::Ui, props: Self::Props<'_>) -> Self::Output {
+        #[cfg(feature = "profiling")]
+        puffin::profile_function!();
+
+        let mut events = Vec::new();
+
+        if ui.available_width() < 50.0 {
+            return SchemaPanelOutput { events };
+        }
+
+        let status = props.status;
+        let action_clicked = SidebarHeader::builder()
+            .title("SCHEMA")
+            .actions(vec![
+                SidebarHeaderAction::builder()
+                    .icon(egui_phosphor::regular::FOLDER_OPEN)
+                    .tooltip("Load JSON Schema…")
+                    .build(),
+                SidebarHeaderAction::builder()
+                    .icon(egui_phosphor::regular::X)
+                    .tooltip("Clear schema")
+                    .build(),
+            ])
+            .build()
+            .show(ui)
+            .inner;
+        match action_clicked {
+            Some(0) => events.push(SchemaPanelEvent::LoadSchema),
+            Some(1) if status.schema_name.is_some() => events.push(SchemaPanelEvent::ClearSchema),
+            _ => {}
+        }
+        ui.add_space(8.0);
+
+        let Some(schema_name) = &status.schema_name else {
+            Typography::body_muted(
+                ui,
+                "No schema loaded — load a JSON Schema to validate records",
+            );
+            return SchemaPanelOutput { events };
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(egui_phosphor::regular::SEAL_CHECK);
+            Typography::body(ui, schema_name);
+        });
+        if status.checked < status.total {
+            ui.horizontal(|ui| {
+                ui.add(egui::Spinner::new().size(14.0));
+                Typography::caption(
+                    ui,
+                    &format!("Validated {} of {} records", status.checked, status.total),
+                );
+            });
+        } else {
+            Typography::caption(ui, &format!("Validated {} records", status.total));
+        }
+
+        ui.add(Separator::with_margins(8.0, 4.0));
+
+        if status.invalid_records == 0 {
+            if status.checked >= status.total {
+                Typography::body_muted(ui, "All records match the schema");
+            }
+            return SchemaPanelOutput { events };
+        }
+
+        Typography::caption(ui, &format!("{} invalid record(s)", status.invalid_records));
+        if status.violations.len() >= MAX_SCHEMA_PANEL_ERRORS {
+            Typography::caption(
+                ui,
+                &format!("Showing the first {} errors", MAX_SCHEMA_PANEL_ERRORS),
+            );
+        }
+        ui.add_space(4.0);
+
+        let items: Vec<ListItem> = status
+            .violations
+            .iter()
+            .map(|(record_index, violation)| {
+                let field = violation
+                    .path
+                    .strip_prefix(&record_index.to_string())
+                    .map(|rest| rest.trim_start_matches('.'))
+                    .filter(|rest| !rest.is_empty())
+                    .unwrap_or("(root)");
+                ListItem::builder()
+                    .title(format!("Record #{} — {}", record_index, field))
+                    .description(violation.message.clone())
+                    .prefix(ListItemPrefix::Icon {
+                        glyph: egui_phosphor::regular::WARNING_CIRCLE.to_string(),
+                        color: None,
+                    })
+                    .build()
+            })
+            .collect();
+
+        egui::ScrollArea::vertical()
+            .id_salt("schema_violations_scroll")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                if let Some(ListEvent::ItemClicked(idx)) =
+                    List::builder().items(items).build().show(ui)
+                    && let Some((_, violation)) = status.violations.get(idx)
+                {
+                    events.push(SchemaPanelEvent::NavigateToViolation(
+                        violation.path.clone(),
+                    ));
+                }
+            });
+
+        SchemaPanelOutput { events }
+    }
+}
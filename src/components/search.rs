@@ -1,17 +1,38 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
 use crate::components::traits::StatefulComponent;
-use crate::search::{QueryMode, Search as SearchState, SearchMessage, decode_history_entry};
+use crate::search::replace::ReplaceSpec;
+use crate::search::results::{MatchPreview, MatchTarget, SearchHit};
+use crate::search::{
+    Facets, QueryMode, Search as SearchState, SearchMessage, decode_history_entry,
+};
+use crate::state::{BusyState, RecentFilesSearch, ReplaceStatus};
 use eframe::egui;
 use thoth_plugin_sdk::components::{
-    IconButton, Input, List, ListEvent, ListItem, ListItemPrefix, Separator, SidebarHeader,
+    Badge, Button, ButtonColor, ButtonType, IconButton, Input, List, ListEvent, ListItem,
+    ListItemAction, ListItemBadge, ListItemPostfix, ListItemPrefix, Separator, SidebarHeader,
     SidebarHeaderAction, Typography,
 };
 
-/// Detect query mode based on whether the query starts with '$'
-fn detect_query_mode(query: &str) -> QueryMode {
-    if query.trim_start().starts_with('$') {
+/// Glyph marking which mode a history entry was run in
+fn query_mode_icon(mode: QueryMode) -> &'static str {
+    match mode {
+        QueryMode::Text => egui_phosphor::regular::TEXT_AA,
+        QueryMode::JsonPath => egui_phosphor::regular::BRACKETS_CURLY,
+        QueryMode::Filter => egui_phosphor::regular::FUNNEL,
+        QueryMode::Fuzzy => egui_phosphor::regular::SPARKLE,
+    }
+}
+
+/// Resolve the query mode for a query: in Text mode a leading '$' means
+/// JSONPath, otherwise the mode selected in the panel applies (filter paths
+/// may also start with `$.`)
+fn resolve_query_mode(query: &str, selected: QueryMode) -> QueryMode {
+    if selected == QueryMode::Text && query.trim_start().starts_with('$') {
         QueryMode::JsonPath
     } else {
-        QueryMode::Text
+        selected
     }
 }
 
@@ -23,6 +44,17 @@ pub struct SearchProps<'a> {
     pub search_state: &'a SearchState,
     /// Search history for the current file
     pub search_history: Option<&'a Vec<String>>,
+    /// Path of the row selected in the viewer, offered as a search scope
+    pub selected_path: Option<&'a str>,
+    /// Find-and-replace preview and job status
+    pub replace: &'a ReplaceStatus,
+    /// Results of the search across recent files
+    pub recent_files: &'a RecentFilesSearch,
+    /// Common keys and values of the open file, once sampled
+    pub facets: Option<&'a Facets>,
+    /// Running operations; the query can't be edited or run during the ones
+    /// a search conflicts with
+    pub busy: BusyState,
 }
 
 /// Events emitted by the Search panel
@@ -32,8 +64,38 @@ pub enum SearchEvent {
     NavigateToResult {
         record_index: usize,
     },
+    /// User clicked one field match of a grouped result
+    NavigateToMatch {
+        record_index: usize,
+        path: String,
+    },
     /// User clicked to clear search history
     ClearHistory,
+    /// User removed one stored history entry (the raw, encoded entry)
+    RemoveHistoryEntry(String),
+    /// Copy every matching record to the clipboard as one JSON array
+    CopyResults,
+    /// Save every matching record to a file, asking for the format first
+    ExportResults,
+    /// User switched the query mode (persisted as the default for next launch)
+    QueryModeChanged(QueryMode),
+    /// User switched between grouped and flat results (persisted)
+    FlatResultsChanged(bool),
+    /// Preview which records a find-and-replace would change
+    PreviewReplace(ReplaceSpec),
+    /// Write the previewed replacements to a new file
+    ApplyReplace,
+    /// Replace mode was closed; drop the preview
+    ClearReplace,
+    /// Run the query over every recent file
+    SearchRecentFiles(SearchState),
+    /// Cancel the running search across recent files
+    StopRecentFilesSearch,
+    /// User clicked a match from the search across recent files
+    OpenRecentFilesMatch {
+        path: PathBuf,
+        record_index: usize,
+    },
 }
 
 pub struct SearchOutput {
@@ -45,6 +107,596 @@ pub struct SearchOutput {
 pub struct Search {
     search_query: String,
     match_case: bool,
+    query_mode: QueryMode,
+    /// Row path that searches are limited to ("Search within selected")
+    scope_path: Option<String>,
+    /// Find-and-replace inputs are shown
+    replace_mode: bool,
+    replace_with: String,
+    /// Optional field path replacements are limited to
+    replace_field: String,
+    replace_regex: bool,
+    /// Results are listed one row per record instead of grouped by record
+    flat_results: bool,
+    /// Queries run over every recent file instead of the active tab's file
+    recent_files_mode: bool,
+    /// The facet chips are hidden
+    facets_collapsed: bool,
+    /// Records whose field matches are shown, for the query in `expanded_query`
+    expanded_groups: HashSet<usize>,
+    expanded_query: String,
+}
+
+/// A record's search hits, shown as one collapsible group
+#[derive(Debug)]
+struct ResultGroup<'a> {
+    record_index: usize,
+    match_count: usize,
+    preview: Option<&'a MatchPreview>,
+    /// Row paths of the matched fields, each once, with the text that matched
+    fields: Vec<(&'a str, Option<&'a str>)>,
+}
+
+/// Gather `hits` under their record, in the order records first appear
+fn group_hits(hits: &[SearchHit]) -> Vec<ResultGroup<'_>> {
+    let mut groups: Vec<ResultGroup> = Vec::new();
+    let mut positions: HashMap<usize, usize> = HashMap::new();
+    for hit in hits {
+        let pos = *positions.entry(hit.record_index).or_insert_with(|| {
+            groups.push(ResultGroup {
+                record_index: hit.record_index,
+                match_count: 0,
+                preview: None,
+                fields: Vec::new(),
+            });
+            groups.len() - 1
+        });
+        let group = &mut groups[pos];
+        group.match_count += hit.match_count();
+        group.preview = group.preview.or(hit.preview.as_ref());
+        for fragment in &hit.fragments {
+            if fragment.is_record_marker()
+                || !matches!(fragment.target, MatchTarget::JsonField { .. })
+            {
+                continue;
+            }
+            let Some(path) = fragment.path.as_deref() else {
+                continue;
+            };
+            if group.fields.iter().all(|(seen, _)| *seen != path) {
+                group.fields.push((path, fragment.matched_text.as_deref()));
+            }
+        }
+    }
+    groups
+}
+
+/// One-line snippet of a hit's match for the results list
+fn preview_text(preview: &MatchPreview) -> String {
+    format!(
+        "{}{}{}",
+        preview.before.trim(),
+        preview.highlight.trim(),
+        preview.after.trim()
+    )
+}
+
+/// A field match's path without its record prefix, e.g. `user.name` for `3.user.name`
+fn field_label(record_index: usize, path: &str) -> &str {
+    match path.strip_prefix(record_index.to_string().as_str()) {
+        Some(rest) if rest.starts_with('.') => &rest[1..],
+        Some(rest) if rest.starts_with('[') => rest,
+        _ => path,
+    }
+}
+
+/// Last component of `path`, or the whole path without one
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// What a row of the grouped results list stands for
+enum GroupedRow<'a> {
+    Record(&'a ResultGroup<'a>),
+    Field { record_index: usize, path: &'a str },
+}
+
+impl Search {
+    /// Set the selected query mode (e.g. the last-used mode restored on launch)
+    pub fn set_query_mode(&mut self, mode: QueryMode) {
+        self.query_mode = mode;
+    }
+
+    /// Show results one row per record instead of grouped (restored on launch)
+    pub fn set_flat_results(&mut self, flat: bool) {
+        self.flat_results = flat;
+    }
+
+    /// Show `search`'s query and scope in the inputs, e.g. the active tab's
+    /// search after switching tabs; an empty search keeps the options last picked
+    pub fn load(&mut self, search: &SearchState) {
+        self.search_query = search.query.clone();
+        self.scope_path = search.scope_path.clone();
+        if !search.query.is_empty() {
+            self.match_case = search.match_case;
+            self.query_mode = search.query_mode;
+        }
+    }
+
+    /// Clear the query and scope; returns the message that resets the results
+    pub fn clear(&mut self) -> Option<SearchMessage> {
+        self.search_query.clear();
+        self.scope_path = None;
+        self.search_message(String::new(), self.query_mode)
+    }
+
+    fn search_message(&self, query: String, query_mode: QueryMode) -> Option<SearchMessage> {
+        SearchMessage::create_search(query, self.match_case, query_mode, self.scope_path.clone())
+    }
+
+    /// Event that runs `query`, over the recent files when that mode is on
+    fn search_event(&self, query: String, query_mode: QueryMode) -> Option<SearchEvent> {
+        if self.recent_files_mode {
+            return Some(SearchEvent::SearchRecentFiles(SearchState {
+                query,
+                match_case: self.match_case,
+                query_mode,
+                scanning: true,
+                ..SearchState::default()
+            }));
+        }
+        self.search_message(query, query_mode)
+            .map(SearchEvent::Search)
+    }
+
+    /// Find-and-replace built from the query and the replace inputs
+    fn replace_spec(&self) -> ReplaceSpec {
+        ReplaceSpec {
+            find: self.search_query.clone(),
+            replacement: self.replace_with.clone(),
+            regex: self.replace_regex,
+            match_case: self.match_case,
+            field: Some(self.replace_field.trim().to_string()).filter(|f| !f.is_empty()),
+        }
+    }
+
+    /// Chips for the open file's common keys and values; clicking one runs
+    /// its JSONPath query
+    fn render_facets(&mut self, ui: &mut egui::Ui, facets: &Facets, events: &mut Vec<SearchEvent>) {
+        ui.add(Separator::with_margins(0.0, 8.0));
+        ui.horizontal(|ui| {
+            Typography::panel_header(ui, "FACETS");
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let (icon, tooltip) = if self.facets_collapsed {
+                    (egui_phosphor::regular::CARET_RIGHT, "Show facets")
+                } else {
+                    (egui_phosphor::regular::CARET_DOWN, "Hide facets")
+                };
+                let clicked = ui
+                    .add(
+                        IconButton::builder()
+                            .icon(icon)
+                            .frame(false)
+                            .tooltip(tooltip)
+                            .size_px(16.0)
+                            .build(),
+                    )
+                    .clicked();
+                if clicked {
+                    self.facets_collapsed = !self.facets_collapsed;
+                }
+            });
+        });
+        if self.facets_collapsed {
+            return;
+        }
+        Typography::caption(ui, &format!("From the first {} record(s)", facets.sampled));
+        ui.add_space(4.0);
+
+        let mut clicked = None;
+        for (chips, color) in [(&facets.keys, "key"), (&facets.values, "string")] {
+            if chips.is_empty() {
+                continue;
+            }
+            ui.horizontal_wrapped(|ui| {
+                for facet in chips {
+                    let response = ui
+                        .add(
+                            Badge::builder()
+                                .label(facet.label.clone())
+                                .color(color)
+                                .soft(true)
+                                .build(),
+                        )
+                        .interact(egui::Sense::click())
+                        .on_hover_cursor(egui::CursorIcon::PointingHand)
+                        .on_hover_text(format!(
+                            "{}\nIn {} of {} sampled records",
+                            facet.query, facet.count, facets.sampled
+                        ));
+                    if response.clicked() {
+                        clicked = Some(facet.query.clone());
+                    }
+                }
+            });
+            ui.add_space(4.0);
+        }
+
+        if let Some(query) = clicked {
+            // Text mode already reads a leading `$` as JSONPath
+            if !matches!(self.query_mode, QueryMode::Text | QueryMode::JsonPath) {
+                self.query_mode = QueryMode::JsonPath;
+                events.push(SearchEvent::QueryModeChanged(QueryMode::JsonPath));
+            }
+            self.search_query = query.clone();
+            events.extend(self.search_event(query, QueryMode::JsonPath));
+        }
+    }
+
+    /// Replacement and field inputs, preview/apply buttons and the job status
+    fn render_replace(
+        &mut self,
+        ui: &mut egui::Ui,
+        replace: &ReplaceStatus,
+        events: &mut Vec<SearchEvent>,
+    ) {
+        ui.add(Separator::with_margins(0.0, 8.0));
+        Typography::panel_header(ui, "REPLACE");
+        ui.add_space(4.0);
+
+        let mut replace_input = Input::builder()
+            .id("replace_with")
+            .value(self.replace_with.clone())
+            .placeholder("Replace with")
+            .icon(egui_phosphor::regular::SWAP)
+            .build();
+        if replace_input.show(ui).inner {
+            self.replace_with = replace_input.value.clone();
+        }
+        ui.add_space(4.0);
+
+        let mut field_input = Input::builder()
+            .id("replace_field")
+            .value(self.replace_field.clone())
+            .placeholder("Only in field (optional), e.g. user.status")
+            .icon(egui_phosphor::regular::TREE_STRUCTURE)
+            .build();
+        if field_input.show(ui).inner {
+            self.replace_field = field_input.value.clone();
+        }
+        ui.add_space(4.0);
+
+        ui.checkbox(&mut self.replace_regex, "Regex")
+            .on_hover_text("Treat the query as a regex; $1 in the replacement inserts a group");
+        ui.add_space(4.0);
+
+        let spec = self.replace_spec();
+        // Applying writes what was previewed, so the preview must match the inputs
+        let preview = replace
+            .preview
+            .as_ref()
+            .filter(|preview| preview.spec == spec);
+        let busy = replace.previewing || replace.writing;
+        ui.horizontal(|ui| {
+            let preview_btn = ui.add_enabled(
+                !busy && !self.search_query.is_empty(),
+                Button::builder()
+                    .label("Preview")
+                    .button_type(ButtonType::Elevated)
+                    .color(ButtonColor::Default)
+                    .build(),
+            );
+            if preview_btn.clicked() {
+                events.push(SearchEvent::PreviewReplace(spec.clone()));
+            }
+
+            let apply_btn = ui
+                .add_enabled(
+                    !busy && preview.is_some_and(|preview| !preview.records.is_empty()),
+                    Button::builder()
+                        .label("Apply to New File…")
+                        .button_type(ButtonType::Elevated)
+                        .color(ButtonColor::Primary)
+                        .build(),
+                )
+                .on_hover_text("The original file is never modified");
+            if apply_btn.clicked() {
+                events.push(SearchEvent::ApplyReplace);
+            }
+        });
+        ui.add_space(4.0);
+
+        if replace.previewing || replace.writing {
+            ui.horizontal(|ui| {
+                ui.add(egui::Spinner::new().size(14.0));
+                ui.label(if replace.writing {
+                    "Writing…"
+                } else {
+                    "Finding replacements…"
+                });
+            });
+        } else if let Some(error) = &replace.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        } else if let Some(preview) = preview {
+            Typography::caption(
+                ui,
+                &format!("{} record(s) will change", preview.records.len()),
+            );
+        }
+    }
+}
+
+impl Search {
+    /// One row per matched record, as before grouping was added
+    fn render_flat_results(
+        &self,
+        ui: &mut egui::Ui,
+        search_state: &SearchState,
+        events: &mut Vec<SearchEvent>,
+    ) {
+        let hits = search_state.results.hits();
+        let items: Vec<ListItem> = hits
+            .iter()
+            .map(|hit| {
+                ListItem::builder()
+                    .title(format!("Record #{}", hit.record_index))
+                    .maybe_description(hit.preview.as_ref().map(preview_text))
+                    .prefix(ListItemPrefix::Icon {
+                        glyph: egui_phosphor::regular::MAGNIFYING_GLASS.to_string(),
+                        color: None,
+                    })
+                    .build()
+            })
+            .collect();
+
+        if let Some(ListEvent::ItemClicked(idx)) = result_list(ui, "search_results_scroll", items)
+            && let Some(hit) = hits.get(idx)
+        {
+            events.push(SearchEvent::NavigateToResult {
+                record_index: hit.record_index,
+            });
+        }
+    }
+
+    /// One collapsible row per record with its match count; expanding it
+    /// lists the record's matched fields
+    fn render_grouped_results(
+        &mut self,
+        ui: &mut egui::Ui,
+        search_state: &SearchState,
+        events: &mut Vec<SearchEvent>,
+    ) {
+        if self.expanded_query != search_state.query {
+            self.expanded_query = search_state.query.clone();
+            self.expanded_groups.clear();
+        }
+
+        let groups = group_hits(search_state.results.hits());
+        let mut rows: Vec<GroupedRow> = Vec::new();
+        let mut items: Vec<ListItem> = Vec::new();
+        for group in &groups {
+            let expanded = self.expanded_groups.contains(&group.record_index);
+            let toggle = (!group.fields.is_empty()).then(|| {
+                let (icon, tooltip) = if expanded {
+                    (egui_phosphor::regular::CARET_DOWN, "Hide matched fields")
+                } else {
+                    (egui_phosphor::regular::CARET_RIGHT, "Show matched fields")
+                };
+                ListItemPostfix::IconButton(
+                    IconButton::builder()
+                        .icon(icon)
+                        .frame(false)
+                        .tooltip(tooltip)
+                        .size_px(18.0)
+                        .build(),
+                )
+            });
+            rows.push(GroupedRow::Record(group));
+            items.push(
+                ListItem::builder()
+                    .title(format!("Record #{}", group.record_index))
+                    .maybe_description(group.preview.map(preview_text))
+                    .prefix(ListItemPrefix::Icon {
+                        glyph: egui_phosphor::regular::MAGNIFYING_GLASS.to_string(),
+                        color: None,
+                    })
+                    .badge(
+                        ListItemBadge::builder()
+                            .text(match group.match_count {
+                                1 => "1 match".to_string(),
+                                n => format!("{} matches", n),
+                            })
+                            .color("muted")
+                            .build(),
+                    )
+                    .maybe_postfix(toggle)
+                    .build(),
+            );
+
+            if expanded {
+                for &(path, text) in &group.fields {
+                    rows.push(GroupedRow::Field {
+                        record_index: group.record_index,
+                        path,
+                    });
+                    items.push(
+                        ListItem::builder()
+                            .title(field_label(group.record_index, path))
+                            .maybe_description(text.map(str::to_string))
+                            .prefix(ListItemPrefix::Icon {
+                                glyph: egui_phosphor::regular::ARROW_ELBOW_DOWN_RIGHT.to_string(),
+                                color: None,
+                            })
+                            .build(),
+                    );
+                }
+            }
+        }
+
+        match result_list(ui, "search_results_scroll", items) {
+            Some(ListEvent::ItemClicked(idx)) => match rows.get(idx) {
+                Some(GroupedRow::Record(group)) => {
+                    events.push(SearchEvent::NavigateToResult {
+                        record_index: group.record_index,
+                    });
+                }
+                Some(GroupedRow::Field { record_index, path }) => {
+                    events.push(SearchEvent::NavigateToMatch {
+                        record_index: *record_index,
+                        path: path.to_string(),
+                    });
+                }
+                None => {}
+            },
+            Some(ListEvent::PostfixClicked(idx)) => {
+                if let Some(GroupedRow::Record(group)) = rows.get(idx)
+                    && !self.expanded_groups.remove(&group.record_index)
+                {
+                    self.expanded_groups.insert(group.record_index);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Matches of the search across recent files, each file's records listed
+/// under it, then the files that were skipped
+fn render_recent_files_results(
+    ui: &mut egui::Ui,
+    recent: &RecentFilesSearch,
+    events: &mut Vec<SearchEvent>,
+) {
+    let match_count = recent.match_count();
+    if recent.is_running() {
+        ui.horizontal(|ui| {
+            ui.add(egui::Spinner::new().size(14.0));
+            Typography::caption(
+                ui,
+                &format!(
+                    "Searching file {} of {}… {} found",
+                    (recent.current_file + 1).min(recent.total_files),
+                    recent.total_files,
+                    match_count
+                ),
+            );
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let clicked = ui
+                    .add(
+                        IconButton::builder()
+                            .icon(egui_phosphor::regular::STOP)
+                            .frame(false)
+                            .tooltip("Stop searching (Esc)")
+                            .size_px(16.0)
+                            .build(),
+                    )
+                    .clicked();
+                if clicked {
+                    events.push(SearchEvent::StopRecentFilesSearch);
+                }
+            });
+        });
+        ui.add_space(4.0);
+    } else if let Some(error) = &recent.error {
+        ui.colored_label(ui.visuals().error_fg_color, error);
+        return;
+    } else if recent.query.is_empty() {
+        Typography::body_muted(ui, "Press Enter to search every recent file");
+        return;
+    } else if match_count == 0 {
+        Typography::body_muted(
+            ui,
+            &format!("No results found in {} file(s)", recent.total_files),
+        );
+    } else {
+        Typography::caption(
+            ui,
+            &format!(
+                "{} result(s) in {} of {} file(s)",
+                match_count,
+                recent.files.len(),
+                recent.total_files
+            ),
+        );
+        ui.add_space(4.0);
+    }
+
+    // (file, record) each row opens; a file's row opens its first match
+    let mut rows: Vec<(&Path, usize)> = Vec::new();
+    let mut items: Vec<ListItem> = Vec::new();
+    for file in &recent.files {
+        let Some(first) = file.hits.first() else {
+            continue;
+        };
+        let count = if file.truncated {
+            format!("{}+ records", file.hits.len())
+        } else {
+            match file.hits.len() {
+                1 => "1 record".to_string(),
+                n => format!("{} records", n),
+            }
+        };
+        rows.push((file.path.as_path(), first.record_index));
+        items.push(
+            ListItem::builder()
+                .title(file_name(&file.path))
+                .maybe_description(file.path.parent().map(|dir| dir.display().to_string()))
+                .prefix(ListItemPrefix::Icon {
+                    glyph: egui_phosphor::regular::FILE.to_string(),
+                    color: None,
+                })
+                .badge(ListItemBadge::builder().text(count).color("muted").build())
+                .build(),
+        );
+        for hit in &file.hits {
+            rows.push((file.path.as_path(), hit.record_index));
+            items.push(
+                ListItem::builder()
+                    .title(format!("Record #{}", hit.record_index))
+                    .maybe_description(hit.preview.as_ref().map(preview_text))
+                    .prefix(ListItemPrefix::Icon {
+                        glyph: egui_phosphor::regular::ARROW_ELBOW_DOWN_RIGHT.to_string(),
+                        color: None,
+                    })
+                    .build(),
+            );
+        }
+    }
+    if !items.is_empty()
+        && let Some(ListEvent::ItemClicked(idx)) =
+            result_list(ui, "recent_files_results_scroll", items)
+        && let Some(&(path, record_index)) = rows.get(idx)
+    {
+        events.push(SearchEvent::OpenRecentFilesMatch {
+            path: path.to_path_buf(),
+            record_index,
+        });
+    }
+
+    if !recent.skipped.is_empty() {
+        ui.add_space(4.0);
+        for (path, reason) in &recent.skipped {
+            Typography::caption(ui, &format!("Skipped {}: {}", file_name(path), reason));
+        }
+    }
+}
+
+/// Clickable list of matched records, shared by search results and the replace preview
+fn result_list(ui: &mut egui::Ui, id_salt: &str, items: Vec<ListItem>) -> Option<ListEvent> {
+    egui::ScrollArea::vertical()
+        .id_salt(id_salt)
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            List::builder()
+                .items(items)
+                .max_height(300.0)
+                .build()
+                .show(ui)
+        })
+        .inner
 }
 
 impl StatefulComponent for Search {
@@ -66,6 +718,10 @@ impl StatefulComponent for Search {
                     .icon(egui_phosphor::regular::X)
                     .tooltip("Clear search")
                     .build(),
+                SidebarHeaderAction::builder()
+                    .icon(egui_phosphor::regular::SWAP)
+                    .tooltip("Find and replace")
+                    .build(),
             ])
             .build()
             .show(ui)
@@ -73,40 +729,52 @@ impl StatefulComponent for Search {
         match action_clicked {
             // Search
             Some(0) if !self.search_query.is_empty() => {
-                let query_mode = detect_query_mode(&self.search_query);
-                if let Some(msg) = SearchMessage::create_search(
-                    self.search_query.clone(),
-                    self.match_case,
-                    query_mode,
-                ) {
-                    events.push(SearchEvent::Search(msg));
-                }
+                let query_mode = resolve_query_mode(&self.search_query, self.query_mode);
+                events.extend(self.search_event(self.search_query.clone(), query_mode));
             }
             // Clear
             Some(1) => {
-                self.search_query.clear();
-                let query_mode = detect_query_mode("");
-                if let Some(msg) =
-                    SearchMessage::create_search(String::new(), self.match_case, query_mode)
-                {
+                if let Some(msg) = self.clear() {
                     events.push(SearchEvent::Search(msg));
                 }
             }
+            // Find and replace
+            Some(2) => {
+                self.replace_mode = !self.replace_mode;
+                if !self.replace_mode {
+                    events.push(SearchEvent::ClearReplace);
+                }
+            }
             _ => {}
         }
         ui.add_space(8.0);
 
+        let placeholder = match self.query_mode {
+            QueryMode::Text => "Search… ($ prefix for JSONPath, e.g. $.user.name = \"alice\")",
+            QueryMode::JsonPath => "JSONPath, e.g. $.user.name = \"alice\"",
+            QueryMode::Filter => "Filter, e.g. price > 100 AND status = \"open\"",
+            QueryMode::Fuzzy => "Fuzzy, e.g. usrnm for user_name",
+        };
         let mut search_input = Input::builder()
             .id("search_query")
             .value(self.search_query.clone())
-            .placeholder("Search… ($ prefix for JSONPath, e.g. $.user.name = \"alice\")")
+            .placeholder(placeholder)
             .icon(egui_phosphor::regular::MAGNIFYING_GLASS)
             .build();
-        let search_out = search_input.show(ui);
+        let search_blocker = props.busy.search_blocker();
+        let search_out = ui
+            .add_enabled_ui(search_blocker.is_none(), |ui| search_input.show(ui))
+            .inner;
         if search_out.inner {
             self.search_query = search_input.value.clone();
+            // Clearing the query also drops the scope
+            if self.search_query.is_empty() {
+                self.scope_path = None;
+            }
         }
-        let response = search_out.response;
+        let response = search_out
+            .response
+            .on_disabled_hover_text(search_blocker.unwrap_or_default());
 
         if props.just_opened {
             response.request_focus();
@@ -125,10 +793,58 @@ impl StatefulComponent for Search {
             || (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)));
 
         if should_search && !self.search_query.is_empty() {
-            let query_mode = detect_query_mode(&self.search_query);
-            if let Some(msg) =
-                SearchMessage::create_search(self.search_query.clone(), self.match_case, query_mode)
+            let query_mode = resolve_query_mode(&self.search_query, self.query_mode);
+            events.extend(self.search_event(self.search_query.clone(), query_mode));
+        }
+
+        ui.add_space(8.0);
+
+        // Scope: restrict the search to the row selected in the viewer
+        let mut rescan = false;
+        ui.horizontal(|ui| {
+            let within = ui
+                .add_enabled(
+                    props.selected_path.is_some() && !self.recent_files_mode,
+                    egui::Button::new(format!(
+                        "{} Search within selected",
+                        egui_phosphor::regular::TREE_STRUCTURE
+                    )),
+                )
+                .on_hover_text("Only match inside the selected row")
+                .on_disabled_hover_text(if self.recent_files_mode {
+                    "Searches across recent files always cover whole files"
+                } else {
+                    "Select a row in the viewer to scope the search"
+                });
+            if within.clicked()
+                && let Some(path) = props.selected_path
             {
+                self.scope_path = Some(path.strip_suffix("/_close").unwrap_or(path).to_string());
+                rescan = true;
+            }
+        });
+        if let Some(scope) = self.scope_path.as_ref().filter(|_| !self.recent_files_mode) {
+            ui.horizontal(|ui| {
+                Typography::caption(ui, &format!("Scope: {}", scope));
+                let clicked = ui
+                    .add(
+                        IconButton::builder()
+                            .icon(egui_phosphor::regular::X)
+                            .frame(false)
+                            .tooltip("Search the whole file")
+                            .size_px(16.0)
+                            .build(),
+                    )
+                    .clicked();
+                if clicked {
+                    self.scope_path = None;
+                    rescan = true;
+                }
+            });
+        }
+        if rescan && !self.search_query.is_empty() {
+            let query_mode = resolve_query_mode(&self.search_query, self.query_mode);
+            if let Some(msg) = self.search_message(self.search_query.clone(), query_mode) {
                 events.push(SearchEvent::Search(msg));
             }
         }
@@ -146,16 +862,53 @@ impl StatefulComponent for Search {
             )
         });
 
+        let recent_toggle = ui
+            .checkbox(&mut self.recent_files_mode, "Search all recent files")
+            .on_hover_text("Run the query over every recent and pinned file");
+        if recent_toggle.changed() && !self.recent_files_mode && props.recent_files.is_running() {
+            events.push(SearchEvent::StopRecentFilesSearch);
+        }
+
+        // Query mode toggle
+        ui.horizontal(|ui| {
+            ui.label("Mode:");
+            for (mode, label) in [
+                (QueryMode::Text, "Text"),
+                (QueryMode::JsonPath, "JSONPath"),
+                (QueryMode::Filter, "Filter"),
+                (QueryMode::Fuzzy, "Fuzzy"),
+            ] {
+                let selected = self.query_mode == mode;
+                if ui.selectable_label(selected, label).clicked() && !selected {
+                    self.query_mode = mode;
+                    events.push(SearchEvent::QueryModeChanged(mode));
+                }
+            }
+        });
+
+        if self.replace_mode {
+            self.render_replace(ui, props.replace, &mut events);
+        }
+
+        if let Some(facets) = props.facets.filter(|facets| !facets.keys.is_empty()) {
+            self.render_facets(ui, facets, &mut events);
+        }
+
         ui.add_space(8.0);
 
         // Display search history if no active search and history exists
         if props.search_state.query.is_empty()
             && let Some(history) = props.search_history
         {
-            let queries: Vec<String> = history
+            // Keep the raw entry alongside the decoded one so a delete can
+            // name exactly what is stored
+            let queries: Vec<(&String, QueryMode, String)> = history
                 .iter()
-                .map(|e| decode_history_entry(e).1)
-                .filter(|q| !q.trim().is_empty())
+                .map(|entry| {
+                    let (mode, query) = decode_history_entry(entry);
+                    (entry, mode, query)
+                })
+                .filter(|(_, _, q)| !q.trim().is_empty())
                 .collect();
 
             if !queries.is_empty() {
@@ -183,100 +936,194 @@ impl StatefulComponent for Search {
 
                 let items: Vec<ListItem> = queries
                     .iter()
-                    .map(|q| {
+                    .map(|(_, mode, q)| {
                         ListItem::builder()
                             .title(q.clone())
                             .prefix(ListItemPrefix::Icon {
-                                glyph: egui_phosphor::regular::CLOCK_COUNTER_CLOCKWISE.to_string(),
+                                glyph: query_mode_icon(*mode).to_string(),
                                 color: None,
                             })
+                            .actions(vec![
+                                ListItemAction::builder()
+                                    .icon(egui_phosphor::regular::X)
+                                    .tooltip("Remove from history")
+                                    .build(),
+                            ])
                             .build()
                     })
                     .collect();
 
-                if let Some(ListEvent::ItemClicked(idx)) = List::builder()
+                match List::builder()
                     .items(items)
                     .max_height(300.0)
                     .build()
                     .show(ui)
-                    && let Some(q) = queries.get(idx)
                 {
-                    self.search_query = q.clone();
-                    let query_mode = detect_query_mode(q);
-                    if let Some(msg) =
-                        SearchMessage::create_search(q.clone(), self.match_case, query_mode)
-                    {
-                        events.push(SearchEvent::Search(msg));
+                    Some(ListEvent::ItemClicked(idx)) => {
+                        if let Some((_, mode, q)) = queries.get(idx) {
+                            // Reapply the mode the query was originally run with
+                            self.search_query = q.clone();
+                            let query_mode = resolve_query_mode(q, *mode);
+                            if self.query_mode != query_mode {
+                                self.query_mode = query_mode;
+                                events.push(SearchEvent::QueryModeChanged(query_mode));
+                            }
+                            events.extend(self.search_event(q.clone(), query_mode));
+                        }
                     }
+                    Some(ListEvent::ActionClicked { item, .. }) => {
+                        if let Some((entry, _, _)) = queries.get(item) {
+                            events.push(SearchEvent::RemoveHistoryEntry((*entry).clone()));
+                        }
+                    }
+                    _ => {}
                 }
             }
         }
 
+        // Escape cancels a running scan before it closes anything else
+        if self.recent_files_mode {
+            if props.recent_files.is_running()
+                && ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape))
+            {
+                events.push(SearchEvent::StopRecentFilesSearch);
+            }
+        } else if props.search_state.scanning
+            && ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape))
+        {
+            events.push(SearchEvent::Search(SearchMessage::StopSearch));
+        }
+
         ui.add(Separator::with_margins(0.0, 8.0));
 
-        // Display search results list
-        if !props.search_state.query.is_empty() {
+        // In replace mode the preview takes the place of the search results
+        let replace_preview = props
+            .replace
+            .preview
+            .as_ref()
+            .filter(|_| self.replace_mode && !props.replace.previewing);
+        if self.recent_files_mode {
+            render_recent_files_results(ui, props.recent_files, &mut events);
+        } else if let Some(preview) = replace_preview {
+            let items: Vec<ListItem> = preview
+                .hits
+                .iter()
+                .map(|hit| {
+                    let first = &hit.changes[0];
+                    let more = match hit.changes.len() {
+                        1 => String::new(),
+                        n => format!(" (+{} more)", n - 1),
+                    };
+                    ListItem::builder()
+                        .title(format!("Record #{}", hit.record_index))
+                        .description(format!("{} → {}{}", first.before, first.after, more))
+                        .prefix(ListItemPrefix::Icon {
+                            glyph: egui_phosphor::regular::SWAP.to_string(),
+                            color: None,
+                        })
+                        .build()
+                })
+                .collect();
+            if preview.records.len() > preview.hits.len() {
+                Typography::caption(
+                    ui,
+                    &format!(
+                        "Showing the first {} of {} records",
+                        preview.hits.len(),
+                        preview.records.len()
+                    ),
+                );
+                ui.add_space(4.0);
+            }
+            if let Some(ListEvent::ItemClicked(idx)) =
+                result_list(ui, "replace_preview_scroll", items)
+                && let Some(hit) = preview.hits.get(idx)
+            {
+                events.push(SearchEvent::NavigateToResult {
+                    record_index: hit.record_index,
+                });
+            }
+        } else if !props.search_state.query.is_empty() {
             let result_count = props.search_state.results.len();
 
             if props.search_state.scanning {
                 ui.horizontal(|ui| {
                     ui.add(egui::Spinner::new().size(14.0));
-                    ui.label("Searching...");
+                    Typography::caption(ui, &format!("Scanning… {} found", result_count));
                 });
-            } else if result_count > 0 {
-                Typography::caption(ui, &format!("{} result(s)", result_count));
                 ui.add_space(4.0);
+            }
 
-                let hits = props.search_state.results.hits();
-                let titles: Vec<String> = hits
-                    .iter()
-                    .map(|hit| format!("Record #{}", hit.record_index))
-                    .collect();
-                let descriptions: Vec<Option<String>> = hits
-                    .iter()
-                    .map(|hit| {
-                        hit.preview.as_ref().map(|p| {
-                            format!(
-                                "{}{}{}",
-                                p.before.trim(),
-                                p.highlight.trim(),
-                                p.after.trim()
-                            )
-                        })
-                    })
-                    .collect();
-                let items: Vec<ListItem> = titles
-                    .iter()
-                    .zip(descriptions.iter())
-                    .map(|(title, desc): (&String, &Option<String>)| {
-                        ListItem::builder()
-                            .title(title.clone())
-                            .maybe_description(desc.clone())
-                            .prefix(ListItemPrefix::Icon {
-                                glyph: egui_phosphor::regular::MAGNIFYING_GLASS.to_string(),
-                                color: None,
-                            })
-                            .build()
-                    })
-                    .collect();
-
-                egui::ScrollArea::vertical()
-                    .id_salt("search_results_scroll")
-                    .auto_shrink([false, false])
-                    .show(ui, |ui| {
-                        if let Some(ListEvent::ItemClicked(idx)) = List::builder()
-                            .items(items)
-                            .max_height(300.0)
-                            .build()
-                            .show(ui)
-                            && let Some(hit) = props.search_state.results.hits().get(idx)
-                        {
-                            events.push(SearchEvent::NavigateToResult {
-                                record_index: hit.record_index,
-                            });
+            if result_count > 0 {
+                if !props.search_state.scanning {
+                    ui.horizontal(|ui| {
+                        if props.search_state.results.is_truncated() {
+                            Typography::caption(
+                                ui,
+                                &format!("{}+ result(s) (truncated)", result_count),
+                            );
+                        } else {
+                            Typography::caption(ui, &format!("{} result(s)", result_count));
                         }
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            let clicked = ui
+                                .add(
+                                    IconButton::builder()
+                                        .icon(egui_phosphor::regular::COPY)
+                                        .frame(false)
+                                        .tooltip("Copy results as a JSON array")
+                                        .size_px(16.0)
+                                        .build(),
+                                )
+                                .clicked();
+                            if clicked {
+                                events.push(SearchEvent::CopyResults);
+                            }
+
+                            let clicked = ui
+                                .add(
+                                    IconButton::builder()
+                                        .icon(egui_phosphor::regular::DOWNLOAD_SIMPLE)
+                                        .frame(false)
+                                        .tooltip("Export results to a file")
+                                        .size_px(16.0)
+                                        .build(),
+                                )
+                                .clicked();
+                            if clicked {
+                                events.push(SearchEvent::ExportResults);
+                            }
+
+                            let (icon, tooltip) = if self.flat_results {
+                                (egui_phosphor::regular::TREE_VIEW, "Group by record")
+                            } else {
+                                (egui_phosphor::regular::LIST, "Show as flat list")
+                            };
+                            let clicked = ui
+                                .add(
+                                    IconButton::builder()
+                                        .icon(icon)
+                                        .frame(false)
+                                        .tooltip(tooltip)
+                                        .size_px(16.0)
+                                        .build(),
+                                )
+                                .clicked();
+                            if clicked {
+                                self.flat_results = !self.flat_results;
+                                events.push(SearchEvent::FlatResultsChanged(self.flat_results));
+                            }
+                        });
                     });
-            } else {
+                    ui.add_space(4.0);
+                }
+
+                if self.flat_results {
+                    self.render_flat_results(ui, props.search_state, &mut events);
+                } else {
+                    self.render_grouped_results(ui, props.search_state, &mut events);
+                }
+            } else if !props.search_state.scanning {
                 Typography::body_muted(ui, "No results found");
             }
         }
@@ -284,3 +1131,97 @@ impl StatefulComponent for Search {
         SearchOutput { events }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::results::{FieldComponent, MatchFragment};
+    use std::sync::Arc;
+
+    fn field(path: &str, text: &str) -> MatchFragment {
+        MatchFragment {
+            target: MatchTarget::JsonField {
+                component: FieldComponent::Value,
+            },
+            path: Some(Arc::from(path)),
+            matched_text: Some(text.to_string()),
+            text_range: Some(0..1),
+            ..Default::default()
+        }
+    }
+
+    fn hit(record_index: usize, fragments: Vec<MatchFragment>) -> SearchHit {
+        SearchHit {
+            record_index,
+            fragments,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_group_hits_by_record() {
+        let hits = vec![
+            hit(
+                7,
+                vec![
+                    field("7.user.name", "alice"),
+                    field("7.user.name", "alice"),
+                    field("7.tags[0]", "admin"),
+                ],
+            ),
+            hit(2, vec![MatchFragment::default()]),
+            hit(7, vec![field("7.note", "alice!")]),
+        ];
+        let groups = group_hits(&hits);
+
+        assert_eq!(
+            groups.iter().map(|g| g.record_index).collect::<Vec<_>>(),
+            [7, 2]
+        );
+        assert_eq!(groups[0].match_count, 4);
+        assert_eq!(
+            groups[0].fields,
+            [
+                ("7.user.name", Some("alice")),
+                ("7.tags[0]", Some("admin")),
+                ("7.note", Some("alice!")),
+            ]
+        );
+        // A raw-text match still counts, but has no field to list
+        assert_eq!(groups[1].match_count, 1);
+        assert!(groups[1].fields.is_empty());
+    }
+
+    #[test]
+    fn test_recent_files_mode_searches_whole_files() {
+        let mut panel = Search {
+            scope_path: Some("3.user".to_string()),
+            match_case: true,
+            ..Search::default()
+        };
+        assert!(matches!(
+            panel.search_event("bob".to_string(), QueryMode::Text),
+            Some(SearchEvent::Search(SearchMessage::StartSearch(ref s)))
+                if s.scope_path.as_deref() == Some("3.user")
+        ));
+
+        panel.recent_files_mode = true;
+        let Some(SearchEvent::SearchRecentFiles(search)) =
+            panel.search_event("$.user".to_string(), QueryMode::JsonPath)
+        else {
+            panic!("expected a search across recent files");
+        };
+        assert_eq!(search.query, "$.user");
+        assert_eq!(search.query_mode, QueryMode::JsonPath);
+        assert!(search.match_case);
+        assert_eq!(search.scope_path, None);
+    }
+
+    #[test]
+    fn test_field_label_drops_record_prefix() {
+        assert_eq!(field_label(7, "7.user.name"), "user.name");
+        assert_eq!(field_label(7, "7[2]"), "[2]");
+        assert_eq!(field_label(7, "7"), "7");
+        assert_eq!(field_label(1, "12.a"), "12.a");
+    }
+}
@@ -10,6 +10,9 @@ use thoth_plugin_sdk::components::{Select, SelectOption};
 #[derive(Debug, Clone)]
 pub enum GeneralTabEvent {
     ThemeName(String),
+    FollowSystemTheme(bool),
+    LoadThemeFile,
+    ExportThemeFile,
     FontSize(f32),
     FontFamily(Option<String>),
     WindowWidth(f32),
@@ -60,8 +63,16 @@ impl StatelessComponent for GeneralTab {
                     );
                     for evt in picker_out.events {
                         use crate::components::settings_dialog::theme_picker::ThemePickerEvent;
-                        let ThemePickerEvent::ThemeSelected(name) = evt;
-                        events.push(GeneralTabEvent::ThemeName(name));
+                        events.push(match evt {
+                            ThemePickerEvent::ThemeSelected(name) => {
+                                GeneralTabEvent::ThemeName(name)
+                            }
+                            ThemePickerEvent::FollowSystemChanged(on) => {
+                                GeneralTabEvent::FollowSystemTheme(on)
+                            }
+                            ThemePickerEvent::LoadFromFile => GeneralTabEvent::LoadThemeFile,
+                            ThemePickerEvent::ExportToFile => GeneralTabEvent::ExportThemeFile,
+                        });
                     }
                 });
 
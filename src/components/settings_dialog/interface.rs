@@ -11,6 +11,9 @@ use thoth_plugin_sdk::components::ToggleSwitch;
 pub enum InterfaceTabEvent {
     SidebarWidthChanged(f32),
     RememberSidebarStateChanged(bool),
+    ReopenLastFileChanged(bool),
+    RestoreWindowGeometryChanged(bool),
+    PruneMissingRecentFilesChanged(bool),
     ShowToolbarChanged(bool),
     ShowStatusBarChanged(bool),
     EnableAnimationsChanged(bool),
@@ -90,6 +93,65 @@ impl StatelessComponent for InterfaceTab {
                             }
                         },
                     );
+
+                    setting_row(
+                        ui,
+                        "Reopen last file",
+                        Some("Open the last file on launch when no tabs were restored."),
+                        s.reopen_last_file != b.reopen_last_file,
+                        None,
+                        colors,
+                        |ui| {
+                            let on = s.reopen_last_file;
+                            if ui
+                                .add(ToggleSwitch::builder().enabled(on).build())
+                                .clicked()
+                            {
+                                events.push(InterfaceTabEvent::ReopenLastFileChanged(!on));
+                            }
+                        },
+                    );
+
+                    setting_row(
+                        ui,
+                        "Remember window position",
+                        Some(
+                            "Reopen the main and settings windows where and as large as they were.",
+                        ),
+                        s.restore_window_geometry != b.restore_window_geometry,
+                        None,
+                        colors,
+                        |ui| {
+                            let on = s.restore_window_geometry;
+                            if ui
+                                .add(ToggleSwitch::builder().enabled(on).build())
+                                .clicked()
+                            {
+                                events.push(InterfaceTabEvent::RestoreWindowGeometryChanged(!on));
+                            }
+                        },
+                    );
+
+                    setting_row(
+                        ui,
+                        "Remove missing recent files",
+                        Some(
+                            "Drop recent files that were deleted or moved. Files on network shares or removable drives are only greyed out.",
+                        ),
+                        s.prune_missing_recent_files != b.prune_missing_recent_files,
+                        None,
+                        colors,
+                        |ui| {
+                            let on = s.prune_missing_recent_files;
+                            if ui
+                                .add(ToggleSwitch::builder().enabled(on).build())
+                                .clicked()
+                            {
+                                events
+                                    .push(InterfaceTabEvent::PruneMissingRecentFilesChanged(!on));
+                            }
+                        },
+                    );
                 });
 
                 // ── Chrome ───────────────────────────────────────────────────
@@ -31,10 +31,14 @@ pub use shortcuts::ShortcutsTab;
 pub use updates::UpdatesTab;
 pub use viewer::ViewerTab;
 
+use crate::app::persistent_state::WindowGeometry;
 use crate::components::settings_dialog::plugins::{PluginsTab, PluginsTabEvent, PluginsTabProps};
 use crate::components::traits::ContextComponent;
-use crate::notification::{Notification, NotificationManager, NotificationStatus};
+use crate::notification::{
+    Notification, NotificationKind, NotificationManager, NotificationStatus,
+};
 use crate::settings::Settings;
+use crate::shortcuts::KeyboardShortcuts;
 use crate::theme::{self, Theme, ThemeColors, icon_rich_text, phosphor_font_id};
 use eframe::egui;
 use std::sync::{Arc, Mutex};
@@ -72,6 +76,9 @@ pub struct SettingsDialog {
 
     /// Plugin Id for selected plugin settings (shared into viewport closure)
     open_plugin_settings_id: Arc<Mutex<Option<String>>>,
+
+    /// Latest position and size of the settings window, updated by the viewport
+    viewport_geometry: Arc<Mutex<Option<WindowGeometry>>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -153,6 +160,7 @@ impl Default for SettingsDialog {
             viewport_selected_tab: Arc::new(Mutex::new(SettingsTab::General)),
             viewport_events: Arc::new(Mutex::new(Vec::new())),
             open_plugin_settings_id: Arc::new(Mutex::new(None)),
+            viewport_geometry: Arc::new(Mutex::new(None)),
         }
     }
 }
@@ -202,6 +210,52 @@ impl SettingsDialog {
         }
     }
 
+    /// Apply a theme file to the draft settings; the dialog previews it live
+    fn load_theme_file(settings: &mut Settings, path: &std::path::Path) {
+        match Theme::from_file(path) {
+            Ok(loaded) => {
+                let message = if loaded.defaulted.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        "Missing colours taken from the default theme: {}",
+                        loaded.defaulted.join(", ")
+                    )
+                };
+                NotificationManager::notify(
+                    Notification::new(&format!("Loaded theme \"{}\"", loaded.theme.name), &message)
+                        .with_toast(true),
+                );
+                settings.theme = loaded.theme;
+            }
+            Err(e) => {
+                NotificationManager::notify_error(Notification::new(
+                    "Failed to load theme",
+                    &crate::error::ErrorHandler::get_user_message(&e),
+                ));
+            }
+        }
+    }
+
+    fn export_theme_file(theme: &Theme, path: &std::path::Path) {
+        match theme.to_file(path) {
+            Ok(()) => {
+                NotificationManager::notify(
+                    Notification::new("Theme exported", &path.display().to_string())
+                        .with_kind(NotificationKind::Success)
+                        .with_toast(true)
+                        .with_status(NotificationStatus::Completed),
+                );
+            }
+            Err(e) => {
+                NotificationManager::notify_error(Notification::new(
+                    "Failed to export theme",
+                    &crate::error::ErrorHandler::get_user_message(&e),
+                ));
+            }
+        }
+    }
+
     /// Helper method to render tab content with proper event handling
     /// This consolidates the duplicate tab rendering logic
     #[allow(clippy::too_many_arguments)]
@@ -213,6 +267,7 @@ impl SettingsDialog {
         theme_colors: &ThemeColors,
         update_state: Option<&crate::update::UpdateState>,
         last_check: Option<chrono::DateTime<chrono::Utc>>,
+        check_failures: u32,
         current_version: &str,
         dialog_events: &mut Vec<SettingsDialogEvent>,
         open_plugin_settings_id: &Arc<Mutex<Option<String>>>,
@@ -235,6 +290,20 @@ impl SettingsDialog {
                         GeneralTabEvent::ThemeName(name) => {
                             settings.theme = Theme::from_name(&name);
                         }
+                        GeneralTabEvent::FollowSystemTheme(on) => {
+                            settings.follow_system_theme = on;
+                        }
+                        GeneralTabEvent::LoadThemeFile => {
+                            if let Some(path) = crate::app::pick_theme_file() {
+                                Self::load_theme_file(settings, &path);
+                            }
+                        }
+                        GeneralTabEvent::ExportThemeFile => {
+                            if let Some(path) = crate::app::pick_theme_export(&settings.theme.name)
+                            {
+                                Self::export_theme_file(&settings.theme, &path);
+                            }
+                        }
                         GeneralTabEvent::FontSize(s) => {
                             settings.font_size = s;
                         }
@@ -268,6 +337,15 @@ impl SettingsDialog {
                         InterfaceTabEvent::RememberSidebarStateChanged(v) => {
                             settings.ui.remember_sidebar_state = v;
                         }
+                        InterfaceTabEvent::ReopenLastFileChanged(v) => {
+                            settings.ui.reopen_last_file = v;
+                        }
+                        InterfaceTabEvent::RestoreWindowGeometryChanged(v) => {
+                            settings.ui.restore_window_geometry = v;
+                        }
+                        InterfaceTabEvent::PruneMissingRecentFilesChanged(v) => {
+                            settings.ui.prune_missing_recent_files = v;
+                        }
                         InterfaceTabEvent::ShowToolbarChanged(v) => {
                             settings.ui.show_toolbar = v;
                         }
@@ -327,6 +405,12 @@ impl SettingsDialog {
                         PerformanceTabEvent::NavigationHistorySizeChanged(size) => {
                             settings.performance.navigation_history_size = size;
                         }
+                        PerformanceTabEvent::CopyWarnSizeChanged(size) => {
+                            settings.performance.copy_warn_size_mb = size;
+                        }
+                        PerformanceTabEvent::MaxSearchResultsChanged(max) => {
+                            settings.performance.max_search_results = max;
+                        }
                     }
                 }
             }
@@ -346,18 +430,67 @@ impl SettingsDialog {
                         ViewerTabEvent::SyntaxHighlightingChanged(enabled) => {
                             settings.viewer.syntax_highlighting = enabled;
                         }
+                        ViewerTabEvent::AutoReloadChanged(enabled) => {
+                            settings.viewer.auto_reload = enabled;
+                        }
+                        ViewerTabEvent::ShowTypeBadgesChanged(enabled) => {
+                            settings.viewer.show_type_badges = enabled;
+                        }
+                        ViewerTabEvent::InlineEditingChanged(enabled) => {
+                            settings.viewer.inline_editing = enabled;
+                        }
+                        ViewerTabEvent::NumberFormatChanged(format) => {
+                            settings.viewer.number_format = format;
+                        }
+                        ViewerTabEvent::IndentGuidesChanged(guides) => {
+                            settings.viewer.indent_guides = guides;
+                        }
+                        ViewerTabEvent::HumanizeTimestampsChanged(enabled) => {
+                            settings.viewer.humanize_timestamps = enabled;
+                        }
+                        ViewerTabEvent::TimestampFieldsChanged(fields) => {
+                            settings.viewer.timestamp_fields = fields;
+                        }
+                        ViewerTabEvent::FileTypeOverridesChanged(overrides) => {
+                            settings.viewer.file_type_overrides = overrides;
+                        }
+                        ViewerTabEvent::CopyPathFormatChanged(format) => {
+                            settings.viewer.copy_path_format = format;
+                        }
                     }
                 }
             }
             SettingsTab::Shortcuts => {
-                let _output = ShortcutsTab::render(
+                let output = ShortcutsTab::render(
                     ui,
                     shortcuts::ShortcutsTabProps {
                         shortcuts: &settings.shortcuts,
                         theme_colors,
                     },
                 );
-                // No events to handle yet - shortcuts are read-only
+
+                // Handle events
+                for event in output.events {
+                    use shortcuts::ShortcutsTabEvent;
+                    match event {
+                        ShortcutsTabEvent::ShortcutChanged(action, shortcut) => {
+                            if let Some(bound) = settings.shortcuts.get_mut(action) {
+                                *bound = shortcut;
+                            }
+                        }
+                        ShortcutsTabEvent::ResetShortcut(action) => {
+                            let mut defaults = KeyboardShortcuts::default();
+                            if let (Some(bound), Some(default)) =
+                                (settings.shortcuts.get_mut(action), defaults.get_mut(action))
+                            {
+                                *bound = default.clone();
+                            }
+                        }
+                        ShortcutsTabEvent::ResetAll => {
+                            settings.shortcuts = KeyboardShortcuts::default();
+                        }
+                    }
+                }
             }
             SettingsTab::Plugins => {
                 // let current_ui = plugin_settings_ui.lock().ok().and_then(|g| g.clone());
@@ -429,6 +562,7 @@ impl SettingsDialog {
                         update_settings: &settings.updates,
                         update_state,
                         last_check,
+                        check_failures,
                         current_version,
                         theme_colors,
                     },
@@ -444,6 +578,12 @@ impl SettingsDialog {
                         UpdatesTabEvent::CheckIntervalChanged(hours) => {
                             settings.updates.check_interval_hours = hours;
                         }
+                        UpdatesTabEvent::CheckTimeoutChanged(secs) => {
+                            settings.updates.check_timeout_secs = secs;
+                        }
+                        UpdatesTabEvent::IncludePrereleasesChanged(value) => {
+                            settings.updates.include_prereleases = value;
+                        }
                         UpdatesTabEvent::CheckForUpdates => {
                             dialog_events.push(SettingsDialogEvent::CheckForUpdates);
                         }
@@ -466,8 +606,12 @@ pub struct SettingsDialogProps<'a> {
     pub update_state: Option<&'a crate::update::UpdateState>,
     /// Timestamp of the last update check
     pub last_check: Option<chrono::DateTime<chrono::Utc>>,
+    /// Update checks that failed in a row, which bring the next one forward
+    pub check_failures: u32,
     /// Current version string
     pub current_version: &'a str,
+    /// Where to open the settings window; sized from the main window when None
+    pub window_geometry: Option<WindowGeometry>,
 }
 
 /// Events from SettingsDialog that need to be handled by the application
@@ -486,6 +630,8 @@ pub struct SettingsDialogOutput {
     pub new_settings: Option<Settings>,
     /// Events that need to be handled by the application
     pub events: Vec<SettingsDialogEvent>,
+    /// Position and size the settings window had when it was closed
+    pub closed_geometry: Option<WindowGeometry>,
 }
 
 /// Returns true when a section's fields differ from the baseline.
@@ -493,6 +639,7 @@ fn section_is_dirty(tab: SettingsTab, draft: &Settings, baseline: &Settings) ->
     match tab {
         SettingsTab::General => {
             draft.theme != baseline.theme
+                || draft.follow_system_theme != baseline.follow_system_theme
                 || draft.font_size != baseline.font_size
                 || draft.font_family != baseline.font_family
                 || draft.window.default_width != baseline.window.default_width
@@ -504,17 +651,31 @@ fn section_is_dirty(tab: SettingsTab, draft: &Settings, baseline: &Settings) ->
                 || draft.ui.show_status_bar != baseline.ui.show_status_bar
                 || draft.ui.enable_animations != baseline.ui.enable_animations
                 || draft.ui.remember_sidebar_state != baseline.ui.remember_sidebar_state
+                || draft.ui.reopen_last_file != baseline.ui.reopen_last_file
+                || draft.ui.restore_window_geometry != baseline.ui.restore_window_geometry
+                || draft.ui.prune_missing_recent_files != baseline.ui.prune_missing_recent_files
         }
         SettingsTab::Viewer => {
             draft.viewer.syntax_highlighting != baseline.viewer.syntax_highlighting
+                || draft.viewer.auto_reload != baseline.viewer.auto_reload
+                || draft.viewer.show_type_badges != baseline.viewer.show_type_badges
+                || draft.viewer.inline_editing != baseline.viewer.inline_editing
+                || draft.viewer.number_format != baseline.viewer.number_format
+                || draft.viewer.indent_guides != baseline.viewer.indent_guides
+                || draft.viewer.humanize_timestamps != baseline.viewer.humanize_timestamps
+                || draft.viewer.timestamp_fields != baseline.viewer.timestamp_fields
+                || draft.viewer.file_type_overrides != baseline.viewer.file_type_overrides
+                || draft.viewer.copy_path_format != baseline.viewer.copy_path_format
         }
         SettingsTab::Performance => {
             draft.performance.cache_size != baseline.performance.cache_size
                 || draft.performance.max_recent_files != baseline.performance.max_recent_files
                 || draft.performance.navigation_history_size
                     != baseline.performance.navigation_history_size
+                || draft.performance.copy_warn_size_mb != baseline.performance.copy_warn_size_mb
+                || draft.performance.max_search_results != baseline.performance.max_search_results
         }
-        SettingsTab::Shortcuts => false,
+        SettingsTab::Shortcuts => draft.shortcuts != baseline.shortcuts,
         SettingsTab::Plugins => {
             draft.plugins.enabled != baseline.plugins.enabled
                 || draft.plugins.disabled_plugin_ids != baseline.plugins.disabled_plugin_ids
@@ -524,6 +685,8 @@ fn section_is_dirty(tab: SettingsTab, draft: &Settings, baseline: &Settings) ->
         SettingsTab::Updates => {
             draft.updates.auto_check != baseline.updates.auto_check
                 || draft.updates.check_interval_hours != baseline.updates.check_interval_hours
+                || draft.updates.check_timeout_secs != baseline.updates.check_timeout_secs
+                || draft.updates.include_prereleases != baseline.updates.include_prereleases
         }
         SettingsTab::Developer => draft.dev.show_profiler != baseline.dev.show_profiler,
     }
@@ -535,6 +698,7 @@ fn reset_section(tab: SettingsTab, draft: &mut Settings) {
     match tab {
         SettingsTab::General => {
             draft.theme = def.theme;
+            draft.follow_system_theme = def.follow_system_theme;
             draft.font_size = def.font_size;
             draft.font_family = def.font_family;
             draft.window = def.window;
@@ -548,6 +712,9 @@ fn reset_section(tab: SettingsTab, draft: &mut Settings) {
         SettingsTab::Performance => {
             draft.performance = def.performance;
         }
+        SettingsTab::Shortcuts => {
+            draft.shortcuts = def.shortcuts;
+        }
         SettingsTab::Updates => {
             draft.updates = def.updates;
         }
@@ -558,7 +725,6 @@ fn reset_section(tab: SettingsTab, draft: &mut Settings) {
             draft.plugins.disabled_plugin_ids = def.plugins.disabled_plugin_ids;
             draft.plugins.plugin_settings = def.plugins.plugin_settings;
         }
-        _ => {}
     }
 }
 
@@ -572,6 +738,7 @@ impl ContextComponent for SettingsDialog {
             return SettingsDialogOutput {
                 new_settings: None,
                 events: Vec::new(),
+                closed_geometry: None,
             };
         }
 
@@ -586,10 +753,12 @@ impl ContextComponent for SettingsDialog {
         let viewport_events = Arc::clone(&self.viewport_events);
         let open_plugin_settings_id = Arc::clone(&self.open_plugin_settings_id);
         let viewport_baseline = Arc::clone(&self.viewport_baseline);
+        let viewport_geometry = Arc::clone(&self.viewport_geometry);
 
         // Clone update state and version for the viewport
         let update_state_clone = props.update_state.cloned();
         let last_check_clone = props.last_check;
+        let check_failures = props.check_failures;
         let current_version = props.current_version.to_string();
 
         // Size the settings window to 75% of the parent window, clamped to a
@@ -598,16 +767,28 @@ impl ContextComponent for SettingsDialog {
         let settings_w = (parent_size.x * 0.85).max(800.0);
         let settings_h = (parent_size.y * 0.85).max(520.0);
 
-        ui.ctx().show_viewport_deferred(
-            viewport_id,
-            egui::ViewportBuilder::default()
-                .with_title("Thoth - Settings")
-                .with_decorations(false)
-                .with_inner_size([settings_w, settings_h])
-                .with_min_inner_size([800.0, 520.0]),
-            move |ui, class| {
+        let builder = egui::ViewportBuilder::default()
+            .with_title("Thoth - Settings")
+            .with_decorations(false)
+            .with_min_inner_size([800.0, 520.0]);
+        let builder = match props.window_geometry {
+            Some(geometry) => builder
+                .with_position(geometry.position)
+                .with_inner_size(geometry.size),
+            None => builder.with_inner_size([settings_w, settings_h]),
+        };
+
+        ui.ctx()
+            .show_viewport_deferred(viewport_id, builder, move |ui, class| {
                 let ctx = ui.ctx().clone();
 
+                // Track the window's geometry so it can be restored next time
+                if let Some(geometry) = ctx.input(|i| WindowGeometry::from_viewport(i.viewport()))
+                    && let Ok(mut current) = viewport_geometry.lock()
+                {
+                    *current = Some(geometry);
+                }
+
                 // Check if viewport is being closed (X button clicked)
                 if class == egui::ViewportClass::Deferred
                     && ctx.input(|i| i.viewport().close_requested())
@@ -1058,6 +1239,7 @@ impl ContextComponent for SettingsDialog {
                                 &theme_colors,
                                 update_state_clone.as_ref(),
                                 last_check_clone,
+                                check_failures,
                                 &current_version,
                                 &mut events,
                                 &open_plugin_settings_id,
@@ -1075,12 +1257,12 @@ impl ContextComponent for SettingsDialog {
                     }
                     ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                 }
-            },
-        );
+            });
 
         // Check if viewport was closed or Apply was clicked
         let mut result = None;
         let mut collected_events = Vec::new();
+        let mut closed_geometry = None;
 
         if let Ok(mut closed) = self.viewport_closed.lock()
             && *closed
@@ -1092,6 +1274,9 @@ impl ContextComponent for SettingsDialog {
             if let Ok(mut viewport_result) = self.viewport_result.lock() {
                 result = viewport_result.take();
             }
+            if let Ok(mut geometry) = self.viewport_geometry.lock() {
+                closed_geometry = geometry.take();
+            }
         }
 
         // Collect any events that were generated
@@ -1102,6 +1287,7 @@ impl ContextComponent for SettingsDialog {
         SettingsDialogOutput {
             new_settings: result,
             events: collected_events,
+            closed_geometry,
         }
     }
 }
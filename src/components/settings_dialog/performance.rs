@@ -17,6 +17,8 @@ pub enum PerformanceTabEvent {
     CacheSizeChanged(usize),
     MaxRecentFilesChanged(usize),
     NavigationHistorySizeChanged(usize),
+    CopyWarnSizeChanged(u64),
+    MaxSearchResultsChanged(usize),
 }
 
 pub struct PerformanceTabOutput {
@@ -40,7 +42,7 @@ impl StatelessComponent for PerformanceTab {
                     ui,
                     egui_phosphor::regular::GAUGE,
                     "Performance",
-                    "Cache, history and recent files.",
+                    "Cache, history, recent files and clipboard limits.",
                     colors,
                 );
 
@@ -113,6 +115,51 @@ impl StatelessComponent for PerformanceTab {
                     );
                 });
 
+                group_rows(ui, "CLIPBOARD", "perf-clipboard", colors, |ui| {
+                    setting_row(
+                        ui,
+                        "Large copy warning",
+                        Some("Ask before copying more records than this to the clipboard."),
+                        s.copy_warn_size_mb != def.copy_warn_size_mb,
+                        None,
+                        colors,
+                        |ui| {
+                            let mut val = s.copy_warn_size_mb;
+                            if ui
+                                .add(egui::DragValue::new(&mut val).range(1..=1000).suffix(" MB"))
+                                .changed()
+                            {
+                                events.push(PerformanceTabEvent::CopyWarnSizeChanged(val));
+                            }
+                        },
+                    );
+                });
+
+                group_rows(ui, "SEARCH", "perf-search", colors, |ui| {
+                    setting_row(
+                        ui,
+                        "Search result limit",
+                        Some("Stop a search after this many matching records."),
+                        s.max_search_results != def.max_search_results,
+                        None,
+                        colors,
+                        |ui| {
+                            let mut val = s.max_search_results;
+                            if ui
+                                .add(
+                                    egui::DragValue::new(&mut val)
+                                        .range(100..=10_000_000)
+                                        .speed(100)
+                                        .suffix(" records"),
+                                )
+                                .changed()
+                            {
+                                events.push(PerformanceTabEvent::MaxSearchResultsChanged(val));
+                            }
+                        },
+                    );
+                });
+
                 ui.add_space(24.0);
             });
 
@@ -1,9 +1,12 @@
+use std::collections::HashMap;
+
 use eframe::egui;
 
 use crate::components::settings_dialog::helpers::{group_rows, section_header, setting_row};
 use crate::components::traits::StatelessComponent;
 use crate::shortcuts::{KeyboardShortcuts, Shortcut};
 use crate::theme::ThemeColors;
+use thoth_plugin_sdk::components::{Button, ButtonColor, ButtonType, IconButton};
 
 pub struct ShortcutsTab;
 
@@ -13,56 +16,197 @@ pub struct ShortcutsTabProps<'a> {
 }
 
 #[derive(Debug, Clone)]
-pub enum ShortcutsTabEvent {}
+pub enum ShortcutsTabEvent {
+    /// A new chord was recorded for an action (named by its `KeyboardShortcuts` field)
+    ShortcutChanged(&'static str, Shortcut),
+    /// Restore an action's default shortcut
+    ResetShortcut(&'static str),
+    /// Restore every default shortcut
+    ResetAll,
+}
 
 pub struct ShortcutsTabOutput {
     pub events: Vec<ShortcutsTabEvent>,
 }
 
+/// Groups of rebindable actions: title, group id and (action, label) rows
+const GROUPS: &[(&str, &str, &[(&str, &str)])] = &[
+    (
+        "FILE",
+        "sc-file",
+        &[("open_file", "Open file"), ("new_window", "New window")],
+    ),
+    (
+        "TABS",
+        "sc-tabs",
+        &[
+            ("new_tab", "New tab"),
+            ("close_tab", "Close tab"),
+            ("next_tab", "Next tab"),
+            ("prev_tab", "Previous tab"),
+        ],
+    ),
+    (
+        "NAVIGATION",
+        "sc-nav",
+        &[
+            ("focus_search", "Focus search"),
+            ("next_match", "Next match"),
+            ("prev_match", "Previous match"),
+            ("go_to_record", "Go to record #"),
+            ("nav_back", "Navigate back"),
+            ("nav_forward", "Navigate forward"),
+            ("escape", "Escape / dismiss"),
+        ],
+    ),
+    (
+        "TREE",
+        "sc-tree",
+        &[
+            ("expand_node", "Expand node"),
+            ("collapse_node", "Collapse node"),
+            ("expand_all", "Expand all"),
+            ("collapse_all", "Collapse all"),
+            ("expand_to_depth", "Expand one level"),
+            ("collapse_to_depth", "Collapse one level"),
+            ("toggle_flat_view", "Toggle flat view"),
+        ],
+    ),
+    (
+        "CLIPBOARD",
+        "sc-clip",
+        &[
+            ("copy_key", "Copy key"),
+            ("copy_value", "Copy value"),
+            ("copy_object", "Copy object"),
+            ("copy_minified", "Copy minified"),
+            ("copy_path", "Copy path"),
+            ("copy_line_number", "Copy line number"),
+            ("copy_visible_records", "Copy visible records"),
+        ],
+    ),
+    (
+        "BOOKMARKS",
+        "sc-marks",
+        &[
+            ("toggle_bookmark", "Toggle bookmark"),
+            ("open_bookmarks", "Open bookmarks"),
+        ],
+    ),
+    (
+        "MOVEMENT",
+        "sc-move",
+        &[
+            ("move_up", "Move up"),
+            ("move_down", "Move down"),
+            ("extend_selection_up", "Extend selection up"),
+            ("extend_selection_down", "Extend selection down"),
+        ],
+    ),
+    (
+        "UI",
+        "sc-ui",
+        &[
+            ("settings", "Open settings"),
+            ("toggle_theme", "Toggle theme"),
+            ("toggle_sidebar", "Toggle sidebar"),
+            ("toggle_presentation", "Presentation mode"),
+            ("command_palette", "Command palette"),
+        ],
+    ),
+    (
+        "DEVELOPER",
+        "sc-dev",
+        &[("toggle_profiler", "Toggle profiler")],
+    ),
+];
+
+/// Badge text while waiting for a key chord
+const RECORDING_TEXT: &str = "Press keys…";
+
+/// Recording mode, kept in egui temp storage between frames
+#[derive(Clone, Default)]
+struct RecordingState {
+    /// Action waiting for its new chord
+    recording: Option<&'static str>,
+    /// Action whose last recorded chord was rejected, and why
+    rejected: Option<(&'static str, &'static str)>,
+}
+
+/// Label shown for an action, falling back to its field name
+fn action_label(action: &str) -> &str {
+    GROUPS
+        .iter()
+        .flat_map(|(_, _, rows)| rows.iter())
+        .find(|(name, _)| *name == action)
+        .map_or(action, |(_, label)| *label)
+}
+
+/// Turn the next key press into a recorded chord for `action`
+///
+/// Plain Esc cancels; keys that can't be bound, and typing keys without a
+/// modifier, are rejected with a message instead of being applied.
+fn capture_chord(
+    ui: &mut egui::Ui,
+    action: &'static str,
+    state: &mut RecordingState,
+    events: &mut Vec<ShortcutsTabEvent>,
+) {
+    let pressed = ui.input(|i| {
+        i.events.iter().find_map(|event| match event {
+            egui::Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+                ..
+            } => Some((*key, *modifiers)),
+            _ => None,
+        })
+    });
+    let Some((key, modifiers)) = pressed else {
+        return;
+    };
+    ui.input_mut(|i| i.consume_key(modifiers, key));
+    state.recording = None;
+    if key == egui::Key::Escape && modifiers.is_none() {
+        return;
+    }
+
+    match Shortcut::from_key_press(key, modifiers) {
+        Some(shortcut) => match shortcut.rejection_reason() {
+            Some(reason) => state.rejected = Some((action, reason)),
+            None => events.push(ShortcutsTabEvent::ShortcutChanged(action, shortcut)),
+        },
+        None => state.rejected = Some((action, "This key can't be used in shortcuts")),
+    }
+}
+
 impl StatelessComponent for ShortcutsTab {
     type Props<'a> = ShortcutsTabProps<'a>;
     type Output = ShortcutsTabOutput;
 
     fn render(ui: &mut egui::Ui, props: Self::Props<'_>) -> Self::Output {
+        let mut events = Vec::new();
         let sc = props.shortcuts;
         let colors = props.theme_colors;
+        let defaults = KeyboardShortcuts::default();
+        let bindings: HashMap<&str, &Shortcut> = sc.bindings().into_iter().collect();
+        let default_bindings: HashMap<&str, &Shortcut> = defaults.bindings().into_iter().collect();
+
+        let state_id = egui::Id::new("shortcuts_tab_recording");
+        let mut state: RecordingState = ui.ctx().data(|d| d.get_temp(state_id).unwrap_or_default());
+        if let Some(action) = state.recording {
+            capture_chord(ui, action, &mut state, &mut events);
+        }
 
         // Pre-compute the widest badge so every badge gets the same width.
         let badge_width = {
             let font_id = egui::FontId::proportional(12.0);
-            let all: &[&Shortcut] = &[
-                &sc.open_file,
-                &sc.new_window,
-                &sc.close_tab,
-                &sc.new_tab,
-                &sc.next_tab,
-                &sc.prev_tab,
-                &sc.focus_search,
-                &sc.next_match,
-                &sc.prev_match,
-                &sc.nav_back,
-                &sc.nav_forward,
-                &sc.escape,
-                &sc.expand_node,
-                &sc.collapse_node,
-                &sc.expand_all,
-                &sc.collapse_all,
-                &sc.copy_key,
-                &sc.copy_value,
-                &sc.copy_object,
-                &sc.copy_path,
-                &sc.toggle_bookmark,
-                &sc.open_bookmarks,
-                &sc.move_up,
-                &sc.move_down,
-                &sc.settings,
-                &sc.toggle_theme,
-                &sc.toggle_profiler,
-            ];
-            let max_text_w = all
-                .iter()
-                .map(|s| {
-                    let txt = s.format();
+            let max_text_w = bindings
+                .values()
+                .map(|s| s.format())
+                .chain([RECORDING_TEXT.to_string()])
+                .map(|txt| {
                     if txt.is_empty() {
                         return 0.0_f32;
                     }
@@ -83,120 +227,154 @@ impl StatelessComponent for ShortcutsTab {
                     ui,
                     egui_phosphor::regular::KEYBOARD,
                     "Shortcuts",
-                    "Keyboard shortcuts per action.",
+                    "Click a shortcut to record a new key combination.",
                     colors,
                 );
 
-                // ── File ────────────────────────────────────────────────────
-                group_rows(ui, "FILE", "sc-file", colors, |ui| {
-                    shortcut_row(ui, "Open file", &sc.open_file, badge_width, colors);
-                    shortcut_row(ui, "New window", &sc.new_window, badge_width, colors);
-                });
-
-                // ── Tabs ─────────────────────────────────────────────────────
-                group_rows(ui, "TABS", "sc-tabs", colors, |ui| {
-                    shortcut_row(ui, "New tab", &sc.new_tab, badge_width, colors);
-                    shortcut_row(ui, "Close tab", &sc.close_tab, badge_width, colors);
-                    shortcut_row(ui, "Next tab", &sc.next_tab, badge_width, colors);
-                    shortcut_row(ui, "Previous tab", &sc.prev_tab, badge_width, colors);
-                    static_shortcut_row(
-                        ui,
-                        "Switch to tab 1–9",
-                        if cfg!(target_os = "macos") {
-                            "⌘1 – ⌘9"
-                        } else {
-                            "Ctrl+1 – Ctrl+9"
-                        },
-                        badge_width,
-                        colors,
-                    );
-                });
-
-                // ── Navigation ───────────────────────────────────────────────
-                group_rows(ui, "NAVIGATION", "sc-nav", colors, |ui| {
-                    shortcut_row(ui, "Focus search", &sc.focus_search, badge_width, colors);
-                    shortcut_row(ui, "Next match", &sc.next_match, badge_width, colors);
-                    shortcut_row(ui, "Previous match", &sc.prev_match, badge_width, colors);
-                    shortcut_row(ui, "Navigate back", &sc.nav_back, badge_width, colors);
-                    shortcut_row(ui, "Navigate forward", &sc.nav_forward, badge_width, colors);
-                    shortcut_row(ui, "Escape / dismiss", &sc.escape, badge_width, colors);
-                });
-
-                // ── Tree ─────────────────────────────────────────────────────
-                group_rows(ui, "TREE", "sc-tree", colors, |ui| {
-                    shortcut_row(ui, "Expand node", &sc.expand_node, badge_width, colors);
-                    shortcut_row(ui, "Collapse node", &sc.collapse_node, badge_width, colors);
-                    shortcut_row(ui, "Expand all", &sc.expand_all, badge_width, colors);
-                    shortcut_row(ui, "Collapse all", &sc.collapse_all, badge_width, colors);
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let reset_all = ui.add_enabled(
+                            *sc != defaults,
+                            Button::builder()
+                                .label("Reset all")
+                                .button_type(ButtonType::Text)
+                                .color(ButtonColor::Default)
+                                .build(),
+                        );
+                        if reset_all.clicked() {
+                            events.push(ShortcutsTabEvent::ResetAll);
+                            state = RecordingState::default();
+                        }
+                    });
                 });
 
-                // ── Clipboard ────────────────────────────────────────────────
-                group_rows(ui, "CLIPBOARD", "sc-clip", colors, |ui| {
-                    shortcut_row(ui, "Copy key", &sc.copy_key, badge_width, colors);
-                    shortcut_row(ui, "Copy value", &sc.copy_value, badge_width, colors);
-                    shortcut_row(ui, "Copy object", &sc.copy_object, badge_width, colors);
-                    shortcut_row(ui, "Copy path", &sc.copy_path, badge_width, colors);
-                });
-
-                // ── Bookmarks ────────────────────────────────────────────────
-                group_rows(ui, "BOOKMARKS", "sc-marks", colors, |ui| {
-                    shortcut_row(
-                        ui,
-                        "Toggle bookmark",
-                        &sc.toggle_bookmark,
-                        badge_width,
-                        colors,
-                    );
-                    shortcut_row(
-                        ui,
-                        "Open bookmarks",
-                        &sc.open_bookmarks,
-                        badge_width,
-                        colors,
-                    );
-                });
-
-                // ── Movement ────────────────────────────────────────────────
-                group_rows(ui, "MOVEMENT", "sc-move", colors, |ui| {
-                    shortcut_row(ui, "Move up", &sc.move_up, badge_width, colors);
-                    shortcut_row(ui, "Move down", &sc.move_down, badge_width, colors);
-                });
-
-                // ── UI ───────────────────────────────────────────────────────
-                group_rows(ui, "UI", "sc-ui", colors, |ui| {
-                    shortcut_row(ui, "Open settings", &sc.settings, badge_width, colors);
-                    shortcut_row(ui, "Toggle theme", &sc.toggle_theme, badge_width, colors);
-                });
-
-                // ── Developer ────────────────────────────────────────────────
-                group_rows(ui, "DEVELOPER", "sc-dev", colors, |ui| {
-                    shortcut_row(
-                        ui,
-                        "Toggle profiler",
-                        &sc.toggle_profiler,
-                        badge_width,
-                        colors,
-                    );
-                });
+                for (title, id, rows) in GROUPS {
+                    group_rows(ui, title, id, colors, |ui| {
+                        for &(action, label) in rows.iter() {
+                            let (Some(shortcut), Some(default)) = (
+                                bindings.get(action).copied(),
+                                default_bindings.get(action).copied(),
+                            ) else {
+                                continue;
+                            };
+                            shortcut_row(
+                                ui,
+                                ShortcutRow {
+                                    action,
+                                    label,
+                                    shortcut,
+                                    dirty: !shortcut.same_chord(default),
+                                    conflict: sc.conflicting_action(action, shortcut),
+                                },
+                                badge_width,
+                                &mut state,
+                                &mut events,
+                                colors,
+                            );
+                        }
+                        if *id == "sc-tabs" {
+                            static_shortcut_row(
+                                ui,
+                                "Switch to tab 1–9",
+                                if cfg!(target_os = "macos") {
+                                    "⌘1 – ⌘9"
+                                } else {
+                                    "Ctrl+1 – Ctrl+9"
+                                },
+                                badge_width,
+                                colors,
+                            );
+                        }
+                    });
+                }
 
                 ui.add_space(24.0);
             });
 
-        ShortcutsTabOutput { events: Vec::new() }
+        ui.ctx().data_mut(|d| d.insert_temp(state_id, state));
+
+        ShortcutsTabOutput { events }
     }
 }
 
-/// Render a single shortcut as a `setting_row` with a fixed-width keyboard badge.
+/// One rebindable action's row
+struct ShortcutRow<'a> {
+    action: &'static str,
+    label: &'a str,
+    shortcut: &'a Shortcut,
+    /// Whether the shortcut differs from its default
+    dirty: bool,
+    /// Another action bound to the same chord
+    conflict: Option<&'static str>,
+}
+
+/// Render an action's shortcut as a `setting_row` with a fixed-width keyboard
+/// badge that starts recording when clicked, plus a reset button once changed
 fn shortcut_row(
     ui: &mut egui::Ui,
-    label: &str,
-    shortcut: &Shortcut,
+    row: ShortcutRow,
     badge_width: f32,
+    state: &mut RecordingState,
+    events: &mut Vec<ShortcutsTabEvent>,
     colors: &ThemeColors,
 ) {
-    setting_row(ui, label, None, false, None, colors, |ui| {
-        kbd_badge(ui, &shortcut.format(), badge_width, colors);
-    });
+    let is_recording = state.recording == Some(row.action);
+    let error = match state.rejected {
+        Some((action, reason)) if action == row.action => Some(reason.to_string()),
+        _ => row
+            .conflict
+            .map(|other| format!("Also bound to \"{}\"", action_label(other))),
+    };
+
+    setting_row(
+        ui,
+        row.label,
+        None,
+        row.dirty,
+        error.as_deref(),
+        colors,
+        |ui| {
+            let text = if is_recording {
+                RECORDING_TEXT.to_string()
+            } else {
+                row.shortcut.format()
+            };
+            let badge = kbd_badge(
+                ui,
+                &text,
+                badge_width,
+                egui::Sense::click(),
+                is_recording,
+                colors,
+            )
+            .on_hover_cursor(egui::CursorIcon::PointingHand)
+            .on_hover_text(if is_recording {
+                "Press a key combination, or Esc to cancel"
+            } else {
+                "Click to record a new shortcut"
+            });
+            if badge.clicked() {
+                state.recording = (!is_recording).then_some(row.action);
+                state.rejected = None;
+            }
+
+            if row.dirty {
+                let reset = ui.add(
+                    IconButton::builder()
+                        .icon(egui_phosphor::regular::ARROW_COUNTER_CLOCKWISE)
+                        .tooltip("Reset to default")
+                        .frame(false)
+                        .size_px(20.0)
+                        .build(),
+                );
+                if reset.clicked() {
+                    events.push(ShortcutsTabEvent::ResetShortcut(row.action));
+                    state.rejected = None;
+                }
+            }
+        },
+    );
 }
 
 /// Render a shortcut row with a literal string badge (for hardcoded shortcuts like ⌘1–9).
@@ -208,18 +386,26 @@ fn static_shortcut_row(
     colors: &ThemeColors,
 ) {
     setting_row(ui, label, None, false, None, colors, |ui| {
-        kbd_badge(ui, text, badge_width, colors);
+        kbd_badge(ui, text, badge_width, egui::Sense::hover(), false, colors);
     });
 }
 
 /// A pill-shaped keyboard shortcut badge with a uniform fixed width.
-fn kbd_badge(ui: &mut egui::Ui, text: &str, width: f32, colors: &ThemeColors) {
+/// `active` outlines it in the accent colour (while recording).
+fn kbd_badge(
+    ui: &mut egui::Ui,
+    text: &str,
+    width: f32,
+    sense: egui::Sense,
+    active: bool,
+    colors: &ThemeColors,
+) -> egui::Response {
     let pad_v = 4.0;
     let height = ui.text_style_height(&egui::TextStyle::Body) + pad_v * 2.0;
 
     if text.is_empty() {
         // Still allocate the same width so columns stay aligned.
-        let (rect, _) = ui.allocate_exact_size(egui::vec2(width, height), egui::Sense::hover());
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(width, height), sense);
         ui.painter().text(
             rect.center(),
             egui::Align2::CENTER_CENTER,
@@ -227,7 +413,7 @@ fn kbd_badge(ui: &mut egui::Ui, text: &str, width: f32, colors: &ThemeColors) {
             egui::FontId::proportional(12.0),
             colors.fg_muted,
         );
-        return;
+        return response;
     }
 
     let font_id = egui::FontId::proportional(12.0);
@@ -235,13 +421,20 @@ fn kbd_badge(ui: &mut egui::Ui, text: &str, width: f32, colors: &ThemeColors) {
         .painter()
         .layout_no_wrap(text.to_string(), font_id, colors.fg);
 
-    let (rect, _) = ui.allocate_exact_size(egui::vec2(width, height), egui::Sense::hover());
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(width, height), sense);
 
     ui.painter().rect(
         rect,
         egui::CornerRadius::same(4),
         colors.bg_sunken,
-        egui::Stroke::new(1.0, colors.surface_active),
+        egui::Stroke::new(
+            1.0,
+            if active {
+                colors.accent
+            } else {
+                colors.surface_active
+            },
+        ),
         egui::StrokeKind::Outside,
     );
 
@@ -254,4 +447,5 @@ fn kbd_badge(ui: &mut egui::Ui, text: &str, width: f32, colors: &ThemeColors) {
         galley,
         colors.fg,
     );
+    response
 }
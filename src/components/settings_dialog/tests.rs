@@ -1,5 +1,7 @@
 use super::*;
 use crate::components::traits::StatelessComponent;
+use crate::file::detect_file_type::DetectedFileType;
+use crate::helpers::{NumberFormat, PathFormat};
 use crate::settings::*;
 use crate::theme::{Theme, ThemeColors};
 
@@ -64,6 +66,18 @@ fn test_general_tab_window_height_event() {
     }
 }
 
+#[test]
+fn test_general_section_follow_system_theme_dirty_and_reset() {
+    let baseline = Settings::default();
+    let mut draft = Settings::default();
+    draft.follow_system_theme = true;
+    assert!(section_is_dirty(SettingsTab::General, &draft, &baseline));
+
+    reset_section(SettingsTab::General, &mut draft);
+    assert!(!draft.follow_system_theme);
+    assert!(!section_is_dirty(SettingsTab::General, &draft, &baseline));
+}
+
 // ── Interface Tab ────────────────────────────────────────────────────────────
 
 #[test]
@@ -97,6 +111,18 @@ fn test_interface_tab_ui_events() {
     ));
 }
 
+#[test]
+fn test_interface_section_prune_missing_recent_files_dirty_and_reset() {
+    let baseline = Settings::default();
+    let mut draft = Settings::default();
+    draft.ui.prune_missing_recent_files = true;
+    assert!(section_is_dirty(SettingsTab::Interface, &draft, &baseline));
+
+    reset_section(SettingsTab::Interface, &mut draft);
+    assert!(!draft.ui.prune_missing_recent_files);
+    assert!(!section_is_dirty(SettingsTab::Interface, &draft, &baseline));
+}
+
 // ── Performance Tab ──────────────────────────────────────────────────────────
 
 #[test]
@@ -166,12 +192,103 @@ fn test_viewer_tab_syntax_highlighting_event() {
     let enable_event = viewer::ViewerTabEvent::SyntaxHighlightingChanged(true);
     let disable_event = viewer::ViewerTabEvent::SyntaxHighlightingChanged(false);
 
-    match enable_event {
-        viewer::ViewerTabEvent::SyntaxHighlightingChanged(v) => assert!(v),
-    }
-    match disable_event {
-        viewer::ViewerTabEvent::SyntaxHighlightingChanged(v) => assert!(!v),
-    }
+    assert!(matches!(
+        enable_event,
+        viewer::ViewerTabEvent::SyntaxHighlightingChanged(true)
+    ));
+    assert!(matches!(
+        disable_event,
+        viewer::ViewerTabEvent::SyntaxHighlightingChanged(false)
+    ));
+}
+
+#[test]
+fn test_viewer_tab_auto_reload_event() {
+    let event = viewer::ViewerTabEvent::AutoReloadChanged(true);
+    assert!(matches!(
+        event,
+        viewer::ViewerTabEvent::AutoReloadChanged(true)
+    ));
+    assert!(!ViewerSettings::default().auto_reload);
+}
+
+#[test]
+fn test_viewer_tab_show_type_badges_event() {
+    let event = viewer::ViewerTabEvent::ShowTypeBadgesChanged(true);
+    assert!(matches!(
+        event,
+        viewer::ViewerTabEvent::ShowTypeBadgesChanged(true)
+    ));
+    assert!(!ViewerSettings::default().show_type_badges);
+}
+
+#[test]
+fn test_viewer_tab_inline_editing_event() {
+    let event = viewer::ViewerTabEvent::InlineEditingChanged(true);
+    assert!(matches!(
+        event,
+        viewer::ViewerTabEvent::InlineEditingChanged(true)
+    ));
+    assert!(!ViewerSettings::default().inline_editing);
+}
+
+#[test]
+fn test_viewer_tab_number_format_event() {
+    let event = viewer::ViewerTabEvent::NumberFormatChanged(NumberFormat::Grouped);
+    assert!(matches!(
+        event,
+        viewer::ViewerTabEvent::NumberFormatChanged(NumberFormat::Grouped)
+    ));
+    assert_eq!(ViewerSettings::default().number_format, NumberFormat::Plain);
+}
+
+#[test]
+fn test_viewer_tab_indent_guides_event() {
+    let event = viewer::ViewerTabEvent::IndentGuidesChanged(IndentGuides::HighlightActive);
+    assert!(matches!(
+        event,
+        viewer::ViewerTabEvent::IndentGuidesChanged(IndentGuides::HighlightActive)
+    ));
+    assert_eq!(ViewerSettings::default().indent_guides, IndentGuides::Lines);
+}
+
+#[test]
+fn test_viewer_tab_timestamp_events() {
+    let event = viewer::ViewerTabEvent::HumanizeTimestampsChanged(true);
+    assert!(matches!(
+        event,
+        viewer::ViewerTabEvent::HumanizeTimestampsChanged(true)
+    ));
+    let event = viewer::ViewerTabEvent::TimestampFieldsChanged(vec!["*_at".to_string()]);
+    assert!(matches!(
+        event,
+        viewer::ViewerTabEvent::TimestampFieldsChanged(fields) if fields == ["*_at"]
+    ));
+    assert!(!ViewerSettings::default().humanize_timestamps);
+}
+
+#[test]
+fn test_viewer_tab_file_type_overrides_event() {
+    let overrides = vec![FileTypeOverride {
+        pattern: "*.log".to_string(),
+        file_type: DetectedFileType::Ndjson,
+    }];
+    let event = viewer::ViewerTabEvent::FileTypeOverridesChanged(overrides.clone());
+    assert!(matches!(
+        event,
+        viewer::ViewerTabEvent::FileTypeOverridesChanged(list) if list == overrides
+    ));
+    assert!(ViewerSettings::default().file_type_overrides.is_empty());
+}
+
+#[test]
+fn test_viewer_tab_copy_path_format_event() {
+    let event = viewer::ViewerTabEvent::CopyPathFormatChanged(PathFormat::Bracket);
+    assert!(matches!(
+        event,
+        viewer::ViewerTabEvent::CopyPathFormatChanged(PathFormat::Bracket)
+    ));
+    assert_eq!(ViewerSettings::default().copy_path_format, PathFormat::Dot);
 }
 
 #[test]
@@ -199,6 +316,21 @@ fn test_shortcuts_tab_renders() {
     });
 }
 
+#[test]
+fn test_shortcuts_section_dirty_and_reset() {
+    let baseline = Settings::default();
+    let mut draft = Settings::default();
+    assert!(!section_is_dirty(SettingsTab::Shortcuts, &draft, &baseline));
+
+    if let Some(copy_key) = draft.shortcuts.get_mut("copy_key") {
+        *copy_key = crate::shortcuts::Shortcut::new("K").command();
+    }
+    assert!(section_is_dirty(SettingsTab::Shortcuts, &draft, &baseline));
+
+    reset_section(SettingsTab::Shortcuts, &mut draft);
+    assert!(!section_is_dirty(SettingsTab::Shortcuts, &draft, &baseline));
+}
+
 // ── Advanced / Developer Tab ─────────────────────────────────────────────────
 
 #[test]
@@ -252,6 +384,7 @@ fn test_updates_tab_renders_no_update() {
                 update_settings: &update_settings,
                 update_state: None,
                 last_check: None,
+                check_failures: 0,
                 current_version: "0.2.16",
                 theme_colors: &theme_colors,
             },
@@ -274,6 +407,7 @@ fn test_updates_tab_renders_with_checking_state() {
                 update_settings: &update_settings,
                 update_state: Some(&state),
                 last_check: None,
+                check_failures: 0,
                 current_version: "0.2.16",
                 theme_colors: &theme_colors,
             },
@@ -296,6 +430,7 @@ fn test_updates_tab_renders_with_idle_state() {
                 update_settings: &update_settings,
                 update_state: Some(&state),
                 last_check: None,
+                check_failures: 0,
                 current_version: "0.2.16",
                 theme_colors: &theme_colors,
             },
@@ -331,6 +466,7 @@ fn test_updates_tab_renders_with_update_available_state() {
                 update_settings: &update_settings,
                 update_state: Some(&state),
                 last_check: None,
+                check_failures: 0,
                 current_version: "0.2.16",
                 theme_colors: &theme_colors,
             },
@@ -356,6 +492,7 @@ fn test_updates_tab_renders_with_downloading_state() {
                 update_settings: &update_settings,
                 update_state: Some(&state),
                 last_check: None,
+                check_failures: 0,
                 current_version: "0.2.16",
                 theme_colors: &theme_colors,
             },
@@ -381,6 +518,7 @@ fn test_updates_tab_renders_with_ready_to_install_state() {
                 update_settings: &update_settings,
                 update_state: Some(&state),
                 last_check: None,
+                check_failures: 0,
                 current_version: "0.2.16",
                 theme_colors: &theme_colors,
             },
@@ -403,6 +541,7 @@ fn test_updates_tab_renders_with_installing_state() {
                 update_settings: &update_settings,
                 update_state: Some(&state),
                 last_check: None,
+                check_failures: 0,
                 current_version: "0.2.16",
                 theme_colors: &theme_colors,
             },
@@ -427,6 +566,7 @@ fn test_updates_tab_renders_with_error_state() {
                 update_settings: &update_settings,
                 update_state: Some(&state),
                 last_check: None,
+                check_failures: 0,
                 current_version: "0.2.16",
                 theme_colors: &theme_colors,
             },
@@ -449,6 +589,7 @@ fn test_updates_tab_with_last_check_timestamp() {
                 update_settings: &update_settings,
                 update_state: None,
                 last_check,
+                check_failures: 0,
                 current_version: "0.2.16",
                 theme_colors: &theme_colors,
             },
@@ -497,6 +638,8 @@ fn test_update_settings_defaults() {
     let s = UpdateSettings::default();
     assert!(s.auto_check);
     assert_eq!(s.check_interval_hours, 24);
+    assert_eq!(s.check_timeout_secs, 5);
+    assert!(!s.include_prereleases);
 }
 
 // ── SettingsDialog ───────────────────────────────────────────────────────────
@@ -749,6 +892,9 @@ fn test_ui_settings_defaults() {
     assert!(s.show_status_bar);
     assert!(s.show_toolbar);
     assert!(s.enable_animations);
+    assert!(s.reopen_last_file);
+    assert!(s.restore_window_geometry);
+    assert!(!s.prune_missing_recent_files);
 }
 
 #[test]
@@ -826,7 +972,10 @@ fn test_event_handling_clones_work() {
         general::GeneralTabEvent::WindowWidth(1920.0),
         general::GeneralTabEvent::WindowHeight(1080.0),
         general::GeneralTabEvent::ThemeName("mocha".to_string()),
+        general::GeneralTabEvent::FollowSystemTheme(true),
         general::GeneralTabEvent::FontSize(14.0),
+        general::GeneralTabEvent::LoadThemeFile,
+        general::GeneralTabEvent::ExportThemeFile,
     ];
     for event in &general_events {
         let _ = event.clone();
@@ -834,6 +983,9 @@ fn test_event_handling_clones_work() {
 
     let interface_events = vec![
         interface::InterfaceTabEvent::RememberSidebarStateChanged(true),
+        interface::InterfaceTabEvent::ReopenLastFileChanged(false),
+        interface::InterfaceTabEvent::RestoreWindowGeometryChanged(false),
+        interface::InterfaceTabEvent::PruneMissingRecentFilesChanged(true),
         interface::InterfaceTabEvent::ShowToolbarChanged(false),
         interface::InterfaceTabEvent::ShowStatusBarChanged(false),
         interface::InterfaceTabEvent::EnableAnimationsChanged(true),
@@ -902,6 +1054,7 @@ fn test_updates_tab_with_release_without_body() {
                 update_settings: &update_settings,
                 update_state: Some(&state),
                 last_check: None,
+                check_failures: 0,
                 current_version: "0.2.16",
                 theme_colors: &theme_colors,
             },
@@ -945,6 +1098,7 @@ fn test_all_update_states_are_covered() {
                     update_settings: &update_settings,
                     update_state: Some(&state),
                     last_check: None,
+                    check_failures: 0,
                     current_version: "0.2.16",
                     theme_colors: &theme_colors,
                 },
@@ -1,6 +1,6 @@
 use eframe::egui::{self, Color32, RichText};
 
-use thoth_plugin_sdk::components::Input;
+use thoth_plugin_sdk::components::{Input, ToggleSwitch};
 
 use crate::{
     components::{settings_dialog::helpers::setting_row, traits::StatelessComponent},
@@ -19,6 +19,12 @@ pub struct ThemePickerProps<'a> {
 #[derive(Debug, Clone)]
 pub enum ThemePickerEvent {
     ThemeSelected(String),
+    /// Switch between the theme's light and dark variants with the OS
+    FollowSystemChanged(bool),
+    /// Load a theme from a standalone `.toml` / `.json` file
+    LoadFromFile,
+    /// Write the active theme to a file
+    ExportToFile,
 }
 
 pub struct ThemePickerOutput {
@@ -88,12 +94,16 @@ impl StatelessComponent for ThemePicker {
                         .color(colors.fg),
                 );
                 ui.add_space(8.0);
-                // Family · dark/light meta
+                // Family · dark/light meta; themes loaded from a file aren't in the catalog
                 let (_, is_dark, family) = Theme::catalog()
                     .iter()
                     .find(|(n, _, _)| *n == current_name.as_str())
                     .cloned()
-                    .unwrap_or(("".to_string(), true, "".to_string()));
+                    .unwrap_or((
+                        "".to_string(),
+                        props.setting.theme.dark_mode,
+                        "Custom".to_string(),
+                    ));
                 let mode = if is_dark { "dark" } else { "light" };
                 ui.label(
                     RichText::new(format!("{family} · {mode}"))
@@ -102,6 +112,41 @@ impl StatelessComponent for ThemePicker {
                 );
             });
         });
+        setting_row(
+            ui,
+            "Follow system appearance",
+            Some("Switch to the light or dark variant of this theme's family when the OS does."),
+            props.setting.follow_system_theme != props.baseline.follow_system_theme,
+            None,
+            colors,
+            |ui| {
+                let on = props.setting.follow_system_theme;
+                if ui
+                    .add(ToggleSwitch::builder().enabled(on).build())
+                    .clicked()
+                {
+                    events.push(ThemePickerEvent::FollowSystemChanged(!on));
+                }
+            },
+        );
+        setting_row(
+            ui,
+            "Theme file",
+            Some("Load a .toml or .json theme, or export the active one."),
+            false,
+            None,
+            colors,
+            |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Load from file…").clicked() {
+                        events.push(ThemePickerEvent::LoadFromFile);
+                    }
+                    if ui.button("Export…").clicked() {
+                        events.push(ThemePickerEvent::ExportToFile);
+                    }
+                });
+            },
+        );
 
         // ── Filter bar ───────────────────────────────────────────────────────
         let filter_id = egui::Id::new("theme_picker_filter");
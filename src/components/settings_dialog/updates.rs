@@ -6,6 +6,7 @@ use crate::components::traits::StatelessComponent;
 use crate::settings::UpdateSettings;
 use crate::theme::ThemeColors;
 use crate::update::UpdateState;
+use crate::update::types::next_check_delay;
 use thoth_plugin_sdk::components::{Button, ButtonColor, ButtonType, ToggleSwitch};
 
 pub struct UpdatesTab;
@@ -14,6 +15,8 @@ pub struct UpdatesTabProps<'a> {
     pub update_settings: &'a UpdateSettings,
     pub update_state: Option<&'a UpdateState>,
     pub last_check: Option<DateTime<Utc>>,
+    /// Checks that failed in a row, which bring the next one forward
+    pub check_failures: u32,
     pub current_version: &'a str,
     pub theme_colors: &'a ThemeColors,
 }
@@ -22,6 +25,8 @@ pub struct UpdatesTabProps<'a> {
 pub enum UpdatesTabEvent {
     AutoCheckChanged(bool),
     CheckIntervalChanged(u64),
+    CheckTimeoutChanged(u64),
+    IncludePrereleasesChanged(bool),
     CheckForUpdates,
     DownloadUpdate,
     InstallUpdate,
@@ -93,6 +98,48 @@ impl StatelessComponent for UpdatesTab {
                             }
                         },
                     );
+
+                    setting_row(
+                        ui,
+                        "Check timeout",
+                        Some(
+                            "Give up on a check when GitHub doesn't answer in time (1–60 seconds).",
+                        ),
+                        s.check_timeout_secs != def.check_timeout_secs,
+                        None,
+                        colors,
+                        |ui| {
+                            let mut val = s.check_timeout_secs;
+                            if ui
+                                .add(
+                                    egui::Slider::new(&mut val, 1..=60)
+                                        .suffix(" s")
+                                        .clamping(egui::SliderClamping::Always),
+                                )
+                                .changed()
+                            {
+                                events.push(UpdatesTabEvent::CheckTimeoutChanged(val));
+                            }
+                        },
+                    );
+
+                    setting_row(
+                        ui,
+                        "Include pre-releases",
+                        Some("Also offer betas and release candidates, which may be less stable."),
+                        s.include_prereleases != def.include_prereleases,
+                        None,
+                        colors,
+                        |ui| {
+                            let on = s.include_prereleases;
+                            if ui
+                                .add(ToggleSwitch::builder().enabled(on).build())
+                                .clicked()
+                            {
+                                events.push(UpdatesTabEvent::IncludePrereleasesChanged(!on));
+                            }
+                        },
+                    );
                 });
 
                 // ── Status ────────────────────────────────────────────────────────
@@ -127,8 +174,10 @@ impl StatelessComponent for UpdatesTab {
                         let next_check_str = props
                             .last_check
                             .map(|t| {
-                                let next =
-                                    t + chrono::Duration::hours(s.check_interval_hours as i64);
+                                let next = t + next_check_delay(
+                                    s.check_interval_hours,
+                                    props.check_failures,
+                                );
                                 let local: chrono::DateTime<chrono::Local> = next.into();
                                 local.format("%b %d, %Y %H:%M").to_string()
                             })
@@ -1,9 +1,11 @@
 use crate::components::settings_dialog::helpers::{group_rows, section_header, setting_row};
 use crate::components::traits::StatelessComponent;
-use crate::settings::ViewerSettings;
+use crate::file::detect_file_type::DetectedFileType;
+use crate::helpers::{NumberFormat, PathFormat};
+use crate::settings::{FileTypeOverride, IndentGuides, ViewerSettings};
 use crate::theme::ThemeColors;
 use eframe::egui;
-use thoth_plugin_sdk::components::ToggleSwitch;
+use thoth_plugin_sdk::components::{IconButton, Input, Select, SelectOption, ToggleSwitch};
 
 pub struct ViewerTab;
 
@@ -15,6 +17,15 @@ pub struct ViewerTabProps<'a> {
 #[derive(Debug, Clone)]
 pub enum ViewerTabEvent {
     SyntaxHighlightingChanged(bool),
+    AutoReloadChanged(bool),
+    ShowTypeBadgesChanged(bool),
+    InlineEditingChanged(bool),
+    NumberFormatChanged(NumberFormat),
+    IndentGuidesChanged(IndentGuides),
+    HumanizeTimestampsChanged(bool),
+    TimestampFieldsChanged(Vec<String>),
+    FileTypeOverridesChanged(Vec<FileTypeOverride>),
+    CopyPathFormatChanged(PathFormat),
 }
 
 pub struct ViewerTabOutput {
@@ -60,6 +71,228 @@ impl StatelessComponent for ViewerTab {
                             }
                         },
                     );
+                    setting_row(
+                        ui,
+                        "Reload on change",
+                        Some("Reload the open file when another program saves it."),
+                        s.auto_reload != def.auto_reload,
+                        None,
+                        colors,
+                        |ui| {
+                            let on = s.auto_reload;
+                            if ui
+                                .add(ToggleSwitch::builder().enabled(on).build())
+                                .clicked()
+                            {
+                                events.push(ViewerTabEvent::AutoReloadChanged(!on));
+                            }
+                        },
+                    );
+                    setting_row(
+                        ui,
+                        "Type badges",
+                        Some("Show each value's type and child count at the end of its row."),
+                        s.show_type_badges != def.show_type_badges,
+                        None,
+                        colors,
+                        |ui| {
+                            let on = s.show_type_badges;
+                            if ui
+                                .add(ToggleSwitch::builder().enabled(on).build())
+                                .clicked()
+                            {
+                                events.push(ViewerTabEvent::ShowTypeBadgesChanged(!on));
+                            }
+                        },
+                    );
+                    setting_row(
+                        ui,
+                        "Inline editing",
+                        Some("Double-click a value to edit it, then save the file from the bar above the tree."),
+                        s.inline_editing != def.inline_editing,
+                        None,
+                        colors,
+                        |ui| {
+                            let on = s.inline_editing;
+                            if ui
+                                .add(ToggleSwitch::builder().enabled(on).build())
+                                .clicked()
+                            {
+                                events.push(ViewerTabEvent::InlineEditingChanged(!on));
+                            }
+                        },
+                    );
+                    setting_row(
+                        ui,
+                        "Number format",
+                        Some("How numbers are shown in the tree. Copying and editing use the stored value."),
+                        s.number_format != def.number_format,
+                        None,
+                        colors,
+                        |ui| {
+                            let options = NumberFormat::ALL
+                                .iter()
+                                .map(|format| {
+                                    SelectOption::builder()
+                                        .value(format.label())
+                                        .label(format.label())
+                                        .build()
+                                })
+                                .collect();
+                            let mut select = Select::builder()
+                                .id("number_format_combo")
+                                .value(s.number_format.label())
+                                .options(options)
+                                .build();
+                            if let Some(selected) = select.show(ui).inner.selected
+                                && let Some(format) = NumberFormat::ALL
+                                    .into_iter()
+                                    .find(|format| format.label() == selected)
+                            {
+                                events.push(ViewerTabEvent::NumberFormatChanged(format));
+                            }
+                        },
+                    );
+                    setting_row(
+                        ui,
+                        "Copy path format",
+                        Some("Syntax the Copy Path shortcut uses. The context menu offers every format."),
+                        s.copy_path_format != def.copy_path_format,
+                        None,
+                        colors,
+                        |ui| {
+                            let options = PathFormat::ALL
+                                .iter()
+                                .map(|format| {
+                                    SelectOption::builder()
+                                        .value(format.label())
+                                        .label(format.label())
+                                        .build()
+                                })
+                                .collect();
+                            let mut select = Select::builder()
+                                .id("copy_path_format_combo")
+                                .value(s.copy_path_format.label())
+                                .options(options)
+                                .build();
+                            if let Some(selected) = select.show(ui).inner.selected
+                                && let Some(format) = PathFormat::ALL
+                                    .into_iter()
+                                    .find(|format| format.label() == selected)
+                            {
+                                events.push(ViewerTabEvent::CopyPathFormatChanged(format));
+                            }
+                        },
+                    );
+                    setting_row(
+                        ui,
+                        "Indent guides",
+                        Some("Lines marking each tree level. Highlight active brightens the ones leading to the selected row."),
+                        s.indent_guides != def.indent_guides,
+                        None,
+                        colors,
+                        |ui| {
+                            let options = IndentGuides::ALL
+                                .iter()
+                                .map(|guides| {
+                                    SelectOption::builder()
+                                        .value(guides.label())
+                                        .label(guides.label())
+                                        .build()
+                                })
+                                .collect();
+                            let mut select = Select::builder()
+                                .id("indent_guides_combo")
+                                .value(s.indent_guides.label())
+                                .options(options)
+                                .build();
+                            if let Some(selected) = select.show(ui).inner.selected
+                                && let Some(guides) = IndentGuides::ALL
+                                    .into_iter()
+                                    .find(|guides| guides.label() == selected)
+                            {
+                                events.push(ViewerTabEvent::IndentGuidesChanged(guides));
+                            }
+                        },
+                    );
+                    setting_row(
+                        ui,
+                        "Readable timestamps",
+                        Some("Show a UTC date after ISO-8601 strings and after numbers in timestamp fields."),
+                        s.humanize_timestamps != def.humanize_timestamps,
+                        None,
+                        colors,
+                        |ui| {
+                            let on = s.humanize_timestamps;
+                            if ui
+                                .add(ToggleSwitch::builder().enabled(on).build())
+                                .clicked()
+                            {
+                                events.push(ViewerTabEvent::HumanizeTimestampsChanged(!on));
+                            }
+                        },
+                    );
+                    if s.humanize_timestamps {
+                        setting_row(
+                            ui,
+                            "Timestamp fields",
+                            Some("Comma-separated field names whose numbers are unix seconds or milliseconds; * matches anything."),
+                            s.timestamp_fields != def.timestamp_fields,
+                            None,
+                            colors,
+                            |ui| {
+                                // Split without trimming so the text round-trips
+                                // while typing; patterns are trimmed when matched
+                                let mut input = Input::builder()
+                                    .id("timestamp_fields_input")
+                                    .value(s.timestamp_fields.join(","))
+                                    .placeholder("*_at, *_time")
+                                    .rows(1)
+                                    .build();
+                                if input.show(ui).inner {
+                                    let fields = if input.value.is_empty() {
+                                        Vec::new()
+                                    } else {
+                                        input.value.split(',').map(String::from).collect()
+                                    };
+                                    events.push(ViewerTabEvent::TimestampFieldsChanged(fields));
+                                }
+                            },
+                        );
+                    }
+                });
+
+                group_rows(ui, "FILE TYPES", "viewer-file-types", colors, |ui| {
+                    let overrides = &s.file_type_overrides;
+                    setting_row(
+                        ui,
+                        "File type overrides",
+                        Some("Open files whose names match a pattern as the chosen type instead of detecting it; * matches anything."),
+                        *overrides != def.file_type_overrides,
+                        None,
+                        colors,
+                        |ui| {
+                            if ui
+                                .add(
+                                    IconButton::builder()
+                                        .icon(egui_phosphor::regular::PLUS)
+                                        .frame(false)
+                                        .build(),
+                                )
+                                .clicked()
+                            {
+                                let mut changed = overrides.clone();
+                                changed.push(FileTypeOverride {
+                                    pattern: String::new(),
+                                    file_type: DetectedFileType::Ndjson,
+                                });
+                                events.push(ViewerTabEvent::FileTypeOverridesChanged(changed));
+                            }
+                        },
+                    );
+                    if let Some(changed) = render_file_type_overrides(ui, overrides) {
+                        events.push(ViewerTabEvent::FileTypeOverridesChanged(changed));
+                    }
                 });
 
                 ui.add_space(24.0);
@@ -68,3 +301,76 @@ impl StatelessComponent for ViewerTab {
         ViewerTabOutput { events }
     }
 }
+
+/// One editable row per override: its pattern, the type it opens as and a
+/// remove button. Returns the new list when anything changed.
+fn render_file_type_overrides(
+    ui: &mut egui::Ui,
+    overrides: &[FileTypeOverride],
+) -> Option<Vec<FileTypeOverride>> {
+    let mut changed = None;
+    let mut remove_idx = None;
+
+    for (i, entry) in overrides.iter().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let mut input = Input::builder()
+                .id(format!("file_type_override_pattern_{}", i))
+                .value(entry.pattern.clone())
+                .placeholder("*.log")
+                .rows(1)
+                .desired_width(200.0)
+                .build();
+            if input.show(ui).inner {
+                let mut list = overrides.to_vec();
+                list[i].pattern = input.value.clone();
+                changed = Some(list);
+            }
+
+            let options = DetectedFileType::ALL
+                .iter()
+                .map(|file_type| {
+                    SelectOption::builder()
+                        .value(file_type.label())
+                        .label(file_type.label())
+                        .build()
+                })
+                .collect();
+            let mut select = Select::builder()
+                .id(format!("file_type_override_type_{}", i))
+                .value(entry.file_type.label())
+                .options(options)
+                .width(140.0)
+                .build();
+            if let Some(selected) = select.show(ui).inner.selected
+                && let Some(file_type) = DetectedFileType::ALL
+                    .into_iter()
+                    .find(|file_type| file_type.label() == selected)
+            {
+                let mut list = overrides.to_vec();
+                list[i].file_type = file_type;
+                changed = Some(list);
+            }
+
+            if ui
+                .add(
+                    IconButton::builder()
+                        .icon(egui_phosphor::regular::MINUS)
+                        .frame(false)
+                        .build(),
+                )
+                .clicked()
+            {
+                remove_idx = Some(i);
+            }
+        });
+    }
+
+    if let Some(i) = remove_idx {
+        let mut list = overrides.to_vec();
+        list.remove(i);
+        changed = Some(list);
+    }
+    ui.add_space(4.0);
+    changed
+}
@@ -11,12 +11,16 @@ use crate::components::data_source_panel::{
 };
 use crate::components::marketplace::{Marketplace, MarketplaceProps};
 use crate::components::recent_files::{RecentFiles, RecentFilesEvent, RecentFilesProps};
+use crate::components::schema_panel::{
+    SchemaPanel, SchemaPanelEvent, SchemaPanelProps, SchemaStatus,
+};
 use crate::components::search::{Search, SearchEvent, SearchProps};
 use crate::components::traits::StatelessComponent;
 use crate::components::traits::{ContextComponent, StatefulComponent};
 use crate::constants::{MAX_SIDEBAR_WIDTH_RATIO, MIN_SIDEBAR_WIDTH};
 use crate::plugin::{Plugin, render_node::render_ui_node, wasm_data_source::ConsentRequest};
-use crate::search::SearchMessage;
+use crate::search::replace::ReplaceSpec;
+use crate::search::{QueryMode, SearchMessage};
 use eframe::egui::{self, Margin};
 use thoth_plugin_sdk::components::IconButton;
 
@@ -26,6 +30,8 @@ pub enum SidebarSection {
     RecentFiles,
     Search,
     Bookmarks,
+    /// JSON Schema validation of the active file
+    Schema,
     DataSource {
         plugin_id: String,
     },
@@ -41,6 +47,10 @@ pub enum SidebarSection {
 /// Props passed to the Sidebar (immutable, one-way binding)
 pub struct SidebarProps<'a> {
     pub recent_files: &'a [String],
+    /// Recent files pinned above the recent list
+    pub pinned_files: &'a [String],
+    /// Recent and pinned files that couldn't be found on disk
+    pub missing_files: &'a std::collections::HashSet<String>,
     pub bookmarks: &'a [Bookmark],
     pub current_file_path: Option<&'a str>,
     pub expanded: bool,
@@ -52,6 +62,20 @@ pub struct SidebarProps<'a> {
     pub search_state: &'a crate::search::Search,
     /// Search history for the current file
     pub search_history: Option<&'a Vec<String>>,
+    /// Path of the row selected in the active tab's viewer
+    pub selected_path: Option<&'a str>,
+    /// Suggestions for the jump-to-path text typed in the bookmarks panel
+    pub path_completion: Option<&'a crate::helpers::PathCompletion>,
+    /// Schema validation status of the active tab
+    pub schema_status: &'a SchemaStatus,
+    /// Find-and-replace status of the active tab
+    pub replace_status: &'a crate::state::ReplaceStatus,
+    /// Search across the recent files
+    pub recent_files_search: &'a crate::state::RecentFilesSearch,
+    /// Common keys and values of the active tab's file, for the search panel
+    pub facets: Option<&'a crate::search::Facets>,
+    /// Running operations, which the search panel waits for
+    pub busy: crate::state::BusyState,
     /// All registered data-source plugins — one icon button is shown per plugin.
     pub data_source_plugins: &'a [&'a Plugin],
     /// Pure ui-component plugins (new-ui-component, not data sources) — one icon
@@ -75,6 +99,8 @@ pub struct PluginSidebarInfo<'a> {
 pub enum SidebarEvent {
     OpenFile(String),
     RemoveRecentFile(String),
+    /// Pin a recent file, or unpin it if already pinned
+    TogglePinRecentFile(String),
     OpenFilePicker,
     SectionToggled(SidebarSection),
     /// Open a pure ui-component plugin (by id) in a new tab.
@@ -85,14 +111,48 @@ pub enum SidebarEvent {
     NavigateToSearchResult {
         record_index: usize,
     },
+    /// Select one matched field of a search result
+    NavigateToSearchMatch {
+        record_index: usize,
+        path: String,
+    },
     ClearSearchHistory,
+    /// Remove one raw entry from the current file's search history
+    RemoveSearchHistoryEntry(String),
+    /// Copy the visible (search-filtered) records as a JSON array
+    CopyVisibleRecords,
+    /// Export the visible (search-filtered) records to a file
+    ExportVisibleRecords,
+    SearchQueryModeChanged(QueryMode),
+    SearchFlatResultsChanged(bool),
+    PreviewReplace(ReplaceSpec),
+    ApplyReplace,
+    ClearReplace,
+    /// Run a query over every recent file
+    SearchRecentFiles(crate::search::Search),
+    StopRecentFilesSearch,
+    /// Open a file found by the search across recent files at a record
+    OpenRecentFilesMatch {
+        path: std::path::PathBuf,
+        record_index: usize,
+    },
     // Bookmark events
     NavigateToBookmark {
         file_path: String,
         path: String,
     },
     RemoveBookmark(usize),
+    /// Set a bookmark's label; an empty label clears it
+    RenameBookmark {
+        index: usize,
+        label: String,
+    },
+    ExportBookmarks,
+    ImportBookmarks,
     JumpToPath(String),
+    // Schema events
+    LoadSchema,
+    ClearSchema,
 
     // Datasource Plugin Events
     DataSourceQueryResult {
@@ -129,6 +189,7 @@ pub struct Sidebar {
     recent_files: RecentFiles,
     search: Search,
     bookmarks: Bookmarks,
+    schema_panel: SchemaPanel,
 
     data_source_panel: HashMap<String, DataSourcePanel>,
     chart_studio: ChartStudio,
@@ -140,6 +201,7 @@ impl Default for Sidebar {
             recent_files: RecentFiles,
             search: Search::default(),
             bookmarks: Bookmarks::default(),
+            schema_panel: SchemaPanel,
             data_source_panel: HashMap::new(),
             chart_studio: ChartStudio::default(),
         }
@@ -175,6 +237,31 @@ impl Sidebar {
         self.chart_studio.set_columns(columns);
     }
 
+    /// Text typed in the bookmarks panel's jump-to-path input
+    pub fn jump_query(&self) -> &str {
+        self.bookmarks.jump_query()
+    }
+
+    /// Set the search panel's selected query mode (restored on launch).
+    pub fn set_search_query_mode(&mut self, mode: QueryMode) {
+        self.search.set_query_mode(mode);
+    }
+
+    /// List search results one row per record instead of grouped (restored on launch).
+    pub fn set_search_flat_results(&mut self, flat: bool) {
+        self.search.set_flat_results(flat);
+    }
+
+    /// Clear the search panel's query; returns the message that resets the results
+    pub fn clear_search(&mut self) -> Option<SearchMessage> {
+        self.search.clear()
+    }
+
+    /// Show another tab's search in the search panel's inputs.
+    pub fn load_search(&mut self, search: &crate::search::Search) {
+        self.search.load(search);
+    }
+
     /// Update the Chart Studio's "Open Charts" list.
     pub fn set_chart_open(&mut self, open: Vec<(TabId, String)>) {
         self.chart_studio.set_open_charts(open);
@@ -212,6 +299,8 @@ impl Sidebar {
                     ui,
                     RecentFilesProps {
                         recent_files: props.recent_files,
+                        pinned_files: props.pinned_files,
+                        missing_files: props.missing_files,
                     },
                 );
 
@@ -224,6 +313,9 @@ impl Sidebar {
                         RecentFilesEvent::RemoveFile(path) => {
                             events.push(SidebarEvent::RemoveRecentFile(path));
                         }
+                        RecentFilesEvent::TogglePin(path) => {
+                            events.push(SidebarEvent::TogglePinRecentFile(path));
+                        }
                         RecentFilesEvent::OpenFilePicker => {
                             events.push(SidebarEvent::OpenFilePicker);
                         }
@@ -239,6 +331,7 @@ impl Sidebar {
                     BookmarksProps {
                         bookmarks: props.bookmarks,
                         current_file_path: props.current_file_path,
+                        path_completion: props.path_completion,
                     },
                 );
 
@@ -251,6 +344,29 @@ impl Sidebar {
                         BookmarksEvent::JumpToPath(path) => {
                             events.push(SidebarEvent::JumpToPath(path));
                         }
+                        BookmarksEvent::Rename { index, label } => {
+                            events.push(SidebarEvent::RenameBookmark { index, label });
+                        }
+                        BookmarksEvent::Export => events.push(SidebarEvent::ExportBookmarks),
+                        BookmarksEvent::Import => events.push(SidebarEvent::ImportBookmarks),
+                    }
+                }
+            }
+            Some(SidebarSection::Schema) => {
+                let output = self.schema_panel.render(
+                    ui,
+                    SchemaPanelProps {
+                        status: props.schema_status,
+                    },
+                );
+
+                for event in output.events {
+                    match event {
+                        SchemaPanelEvent::LoadSchema => events.push(SidebarEvent::LoadSchema),
+                        SchemaPanelEvent::ClearSchema => events.push(SidebarEvent::ClearSchema),
+                        SchemaPanelEvent::NavigateToViolation(path) => {
+                            events.push(SidebarEvent::JumpToPath(path));
+                        }
                     }
                 }
             }
@@ -382,6 +498,18 @@ impl Sidebar {
             events.push(SidebarEvent::SectionToggled(SidebarSection::Bookmarks));
         }
 
+        if rail_button(
+            ui,
+            sidebar_btn(
+                egui_phosphor::regular::SEAL_CHECK,
+                "Schema Validation",
+                props.selected_section == Some(SidebarSection::Schema),
+            ),
+            accent,
+        ) {
+            events.push(SidebarEvent::SectionToggled(SidebarSection::Schema));
+        }
+
         if rail_button(
             ui,
             sidebar_btn(
@@ -496,6 +624,11 @@ impl Sidebar {
                 just_opened: props.focus_search,
                 search_state: props.search_state,
                 search_history: props.search_history,
+                selected_path: props.selected_path,
+                replace: props.replace_status,
+                recent_files: props.recent_files_search,
+                facets: props.facets,
+                busy: props.busy,
             },
         );
 
@@ -506,7 +639,35 @@ impl Sidebar {
                 SearchEvent::NavigateToResult { record_index } => {
                     events.push(SidebarEvent::NavigateToSearchResult { record_index })
                 }
+                SearchEvent::NavigateToMatch { record_index, path } => {
+                    events.push(SidebarEvent::NavigateToSearchMatch { record_index, path })
+                }
                 SearchEvent::ClearHistory => events.push(SidebarEvent::ClearSearchHistory),
+                SearchEvent::RemoveHistoryEntry(entry) => {
+                    events.push(SidebarEvent::RemoveSearchHistoryEntry(entry))
+                }
+                SearchEvent::CopyResults => events.push(SidebarEvent::CopyVisibleRecords),
+                SearchEvent::ExportResults => events.push(SidebarEvent::ExportVisibleRecords),
+                SearchEvent::QueryModeChanged(mode) => {
+                    events.push(SidebarEvent::SearchQueryModeChanged(mode))
+                }
+                SearchEvent::FlatResultsChanged(flat) => {
+                    events.push(SidebarEvent::SearchFlatResultsChanged(flat))
+                }
+                SearchEvent::PreviewReplace(spec) => {
+                    events.push(SidebarEvent::PreviewReplace(spec))
+                }
+                SearchEvent::ApplyReplace => events.push(SidebarEvent::ApplyReplace),
+                SearchEvent::ClearReplace => events.push(SidebarEvent::ClearReplace),
+                SearchEvent::SearchRecentFiles(search) => {
+                    events.push(SidebarEvent::SearchRecentFiles(search))
+                }
+                SearchEvent::StopRecentFilesSearch => {
+                    events.push(SidebarEvent::StopRecentFilesSearch)
+                }
+                SearchEvent::OpenRecentFilesMatch { path, record_index } => {
+                    events.push(SidebarEvent::OpenRecentFilesMatch { path, record_index })
+                }
             }
         }
     }
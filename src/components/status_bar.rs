@@ -1,13 +1,17 @@
 use crate::theme::icon_rich_text;
 use eframe::egui;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::components::traits::ContextComponent;
 use crate::consent::{
     manager::ConsentManager,
     modal::{ConsentModal, ConsentModalProps},
 };
+use crate::file::detect_file_type::{
+    DetectedFileType, Detection, LineEnding, TextEncoding, TextFormat,
+};
 use crate::file::loaders::FileKind;
+use crate::helpers::CacheStats;
 use crate::notification::notification_dropdown::{NotificationDropdown, NotificationDropdownProps};
 use crate::settings::Settings;
 use thoth_plugin_sdk::components::Breadcrumbs;
@@ -27,12 +31,37 @@ pub struct StatusBarProps<'a> {
     /// File type
     pub file_type: &'a FileKind,
 
+    /// How the file type was detected; an ambiguous one is highlighted.
+    /// Also carries the file's text encoding and line endings
+    pub detection: Option<Detection>,
+
+    /// The file was read as JSONC, so its comments aren't in the tree
+    pub comments_stripped: bool,
+
     /// Total item count
     pub item_count: usize,
 
-    /// Filtered item count (if search is active)
+    /// Filtered item count (if search is active); the hits so far while scanning
     pub filtered_count: Option<usize>,
 
+    /// The search stopped at the result limit, so `filtered_count` is a floor
+    pub search_truncated: bool,
+
+    /// Current search match as `(position, total)`, once one has been navigated to
+    pub match_position: Option<(usize, usize)>,
+
+    /// Path the active search is scoped to, if any
+    pub search_scope: Option<&'a str>,
+
+    /// When the file was last reloaded after changing on disk
+    pub reloaded_at: Option<chrono::DateTime<chrono::Local>>,
+
+    /// Parsed-record cache statistics of the open file
+    pub cache_stats: Option<CacheStats>,
+
+    /// Approximate resident memory of the process in bytes, where available
+    pub resident_memory: Option<u64>,
+
     /// Current status
     pub status: StatusBarStatus,
 
@@ -99,6 +128,12 @@ impl StatusBarStatus {
 pub enum StatusBarEvent {
     /// User clicked on a breadcrumb to navigate
     NavigateToPath(String),
+    /// User asked to show the open file in the system file manager
+    RevealFile(PathBuf),
+    /// User asked to copy the open file's full path
+    CopyFilePath(PathBuf),
+    /// User asked to read the open file as another type than the detected one
+    ReopenAs(DetectedFileType),
 }
 
 /// Output from status bar component
@@ -106,6 +141,98 @@ pub struct StatusBarOutput {
     pub events: Vec<StatusBarEvent>,
 }
 
+/// Render the open file's name. Clicking it reveals the file in the system
+/// file manager and right-clicking offers copy/reveal; once the file no
+/// longer exists the name is greyed out and inert.
+fn render_file_path(
+    ui: &mut egui::Ui,
+    path: &Path,
+    muted: egui::Color32,
+    events: &mut Vec<StatusBarEvent>,
+) {
+    let filename = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Untitled");
+
+    if !path.exists() {
+        ui.label(egui::RichText::new(filename).color(muted))
+            .on_hover_text(format!("{}\n(no longer exists)", path.display()));
+        return;
+    }
+
+    let response = ui
+        .add(egui::Label::new(filename).sense(egui::Sense::click()))
+        .on_hover_cursor(egui::CursorIcon::PointingHand)
+        .on_hover_text(format!(
+            "{}\nClick to open containing folder",
+            path.display()
+        ));
+    if response.clicked() {
+        events.push(StatusBarEvent::RevealFile(path.to_path_buf()));
+    }
+    response.context_menu(|ui| {
+        if ui.button("Copy full path").clicked() {
+            events.push(StatusBarEvent::CopyFilePath(path.to_path_buf()));
+            ui.close();
+        }
+        if ui.button("Open containing folder").clicked() {
+            events.push(StatusBarEvent::RevealFile(path.to_path_buf()));
+            ui.close();
+        }
+    });
+}
+
+/// File type label for an ambiguous detection: highlighted, and clickable
+/// to read the file as the other candidate type
+fn render_ambiguous_file_type(
+    ui: &mut egui::Ui,
+    file_type: &FileKind,
+    detected: DetectedFileType,
+    alternative: DetectedFileType,
+    warning: egui::Color32,
+    events: &mut Vec<StatusBarEvent>,
+) {
+    let label = format!("{:?} {}", file_type, egui_phosphor::regular::WARNING);
+    let response = ui
+        .add(
+            egui::Label::new(egui::RichText::new(label).color(warning)).sense(egui::Sense::click()),
+        )
+        .on_hover_cursor(egui::CursorIcon::PointingHand)
+        .on_hover_text(format!(
+            "Opened as {}, but the content could also be {}\nClick to reopen as {}",
+            detected.label(),
+            alternative.label(),
+            alternative.label()
+        ));
+    if response.clicked() {
+        events.push(StatusBarEvent::ReopenAs(alternative));
+    }
+}
+
+/// Encoding and line endings of the open file, as sniffed from its first
+/// chunk; mixed line endings are highlighted
+fn render_text_format(ui: &mut egui::Ui, text: TextFormat, warning: egui::Color32) {
+    ui.label(text.encoding.label())
+        .on_hover_text(match text.encoding {
+            TextEncoding::Utf8Bom => "UTF-8 starting with a byte order mark",
+            _ => "Text encoding, detected from the start of the file",
+        });
+    let Some(ending) = text.line_ending else {
+        return;
+    };
+    let label = egui::RichText::new(ending.label());
+    let (label, hover) = match ending {
+        LineEnding::Lf => (label, "Lines end in LF (\\n)"),
+        LineEnding::Crlf => (label, "Lines end in CRLF (\\r\\n), as on Windows"),
+        LineEnding::Mixed => (
+            label.color(warning),
+            "Some lines end in LF and others in CRLF",
+        ),
+    };
+    ui.label(label).on_hover_text(hover);
+}
+
 /// Render the live plugin signals from the host [`SignalRegistry`] as compact,
 /// source-attributed chips: a status-colored dot, the plugin's short name, and
 /// each `key value`. Draws nothing when no plugin has emitted a signal.
@@ -315,7 +442,7 @@ impl ContextComponent for StatusBar {
             }))
             .show_inside(ui, |ui| {
                 // Use theme text color from context
-                let text_color = ui.ctx().memory(|mem| {
+                let colors = ui.ctx().memory(|mem| {
                     mem.data
                         .get_temp::<crate::theme::ThemeColors>(egui::Id::new("theme_colors"))
                         .unwrap_or_else(|| {
@@ -323,9 +450,8 @@ impl ContextComponent for StatusBar {
                             let dark_mode = ui.ctx().global_style().visuals.dark_mode;
                             crate::theme::Theme::for_dark_mode(dark_mode).colors()
                         })
-                        .fg
                 });
-                ui.style_mut().visuals.override_text_color = Some(text_color);
+                ui.style_mut().visuals.override_text_color = Some(colors.fg);
 
                 ui.horizontal(|ui| {
                     ui.spacing_mut().item_spacing = egui::vec2(8.0, 0.0);
@@ -344,19 +470,37 @@ impl ContextComponent for StatusBar {
 
                         // Filename with icon
                         if let Some(path) = props.file_path {
-                            let filename = path
-                                .file_name()
-                                .and_then(|n| n.to_str())
-                                .unwrap_or("Untitled");
                             ui.label(icon_rich_text(egui_phosphor::regular::FILE_TEXT, 12.0));
-                            ui.label(filename);
+                            render_file_path(ui, path, colors.fg_muted, &mut events);
                             ui.separator();
                         }
 
                         // Item count with icon
-                        if let Some(filtered) = props.filtered_count {
+                        if props.status == StatusBarStatus::Searching {
+                            ui.label(icon_rich_text(egui_phosphor::regular::FUNNEL, 12.0));
+                            ui.label(format!(
+                                "scanning… {} found",
+                                props.filtered_count.unwrap_or(0)
+                            ));
+                        } else if let Some(filtered) = props.filtered_count {
                             ui.label(icon_rich_text(egui_phosphor::regular::FUNNEL, 12.0));
-                            ui.label(format!("{} of {} items", filtered, props.item_count));
+                            if props.search_truncated {
+                                ui.label(format!(
+                                    "{}+ (truncated) of {} items",
+                                    filtered, props.item_count
+                                ))
+                                .on_hover_text(
+                                    "The search stopped at performance.max_search_results; \
+                                     narrow the query or raise the limit to see every match",
+                                );
+                            } else {
+                                ui.label(format!("{} of {} items", filtered, props.item_count));
+                            }
+                            if let Some((position, total)) = props.match_position {
+                                ui.separator();
+                                ui.label(icon_rich_text(egui_phosphor::regular::CROSSHAIR, 12.0));
+                                ui.label(format!("{} / {}", position, total));
+                            }
                         } else if props.item_count > 0 {
                             ui.label(icon_rich_text(egui_phosphor::regular::LIST_BULLETS, 12.0));
                             ui.label(format!("{} items", props.item_count));
@@ -365,17 +509,91 @@ impl ContextComponent for StatusBar {
                             ui.label("No items");
                         }
 
+                        if let Some(scope) = props.search_scope {
+                            ui.separator();
+                            ui.label(icon_rich_text(egui_phosphor::regular::TREE_STRUCTURE, 12.0));
+                            ui.label("scoped")
+                                .on_hover_text(format!("Search limited to {}", scope));
+                        }
+
                         ui.separator();
 
                         // File type with icon
                         let file_type_icon = match props.file_type {
                             FileKind::Json => egui_phosphor::regular::BRACKETS_CURLY,
                             FileKind::Ndjson => egui_phosphor::regular::LIST_DASHES,
+                            FileKind::Csv => egui_phosphor::regular::FILE_CSV,
+                            FileKind::Yaml => egui_phosphor::regular::FILE_CODE,
+                            FileKind::Toml => egui_phosphor::regular::GEAR_FINE,
+                            FileKind::Xml => egui_phosphor::regular::CODE,
+                            FileKind::Parquet => egui_phosphor::regular::DATABASE,
                             FileKind::Plugin => egui_phosphor::regular::PLUG,
                             FileKind::PluginTable => egui_phosphor::regular::TABLE,
                         };
                         ui.label(icon_rich_text(file_type_icon, 12.0));
-                        ui.label(format!("{:?}", props.file_type));
+                        match props.detection {
+                            Some(Detection {
+                                file_type: detected,
+                                alternative: Some(alternative),
+                                ..
+                            }) => render_ambiguous_file_type(
+                                ui,
+                                props.file_type,
+                                detected,
+                                alternative,
+                                colors.warning,
+                                &mut events,
+                            ),
+                            _ => {
+                                ui.label(format!("{:?}", props.file_type));
+                            }
+                        }
+                        if props.comments_stripped {
+                            ui.label(
+                                egui::RichText::new("comments hidden").color(colors.fg_muted),
+                            )
+                            .on_hover_text(
+                                "Read as JSON with comments: comments and trailing commas \
+                                 aren't shown in the tree\nView Raw Bytes shows the file as written",
+                            );
+                        }
+                        if let Some(text) = props.detection.and_then(|d| d.text) {
+                            ui.separator();
+                            render_text_format(ui, text, colors.warning);
+                        }
+
+                        if let Some(reloaded_at) = props.reloaded_at {
+                            ui.separator();
+                            ui.label(icon_rich_text(
+                                egui_phosphor::regular::ARROWS_CLOCKWISE,
+                                12.0,
+                            ));
+                            ui.label(format!("reloaded at {}", reloaded_at.format("%H:%M")));
+                        }
+
+                        if let Some(stats) = props.cache_stats
+                            && let Some(hit_rate) = stats.hit_rate()
+                        {
+                            ui.separator();
+                            ui.label(icon_rich_text(egui_phosphor::regular::GAUGE, 12.0));
+                            ui.label(format!(
+                                "cache {:.0}% · {}/{}",
+                                hit_rate * 100.0,
+                                stats.len,
+                                stats.capacity
+                            ))
+                            .on_hover_text(format!(
+                                "{} hits, {} misses · {} of {} records cached\n\
+                                 Tune with performance.cache_size",
+                                stats.hits, stats.misses, stats.len, stats.capacity
+                            ));
+                        }
+                        if let Some(bytes) = props.resident_memory {
+                            ui.separator();
+                            ui.label(icon_rich_text(egui_phosphor::regular::MEMORY, 12.0));
+                            ui.label(format!("{} MB", bytes / (1024 * 1024)))
+                                .on_hover_text("Approximate resident memory");
+                        }
 
                         // Live plugin signals (push channel), grouped by source.
                         // Renders nothing when no plugin has emitted.
@@ -5,7 +5,8 @@ use eframe::egui;
 use thoth_plugin_sdk::components::IconButton;
 
 use crate::{
-    components::traits::ContextComponent, file::lazy_loader::FileKind, shortcuts::KeyboardShortcuts,
+    components::traits::ContextComponent, file::lazy_loader::FileKind,
+    shortcuts::KeyboardShortcuts, state::BusyState,
 };
 
 // pick_file is only used by the Linux in-window menu bar.
@@ -27,13 +28,21 @@ pub struct ToolbarProps<'a> {
     pub can_go_back: bool,
     pub can_go_forward: bool,
     pub plugins_enabled: bool,
+    /// Running operations; opening and closing files wait for the ones they
+    /// conflict with
+    pub busy: BusyState,
 }
 
 /// Events emitted by the toolbar (bottom-to-top communication)
 pub enum ToolbarEvent {
-    FileOpen { path: PathBuf, file_type: FileKind },
+    FileOpen {
+        path: PathBuf,
+        file_type: FileKind,
+    },
     CloseTab,
     NewWindow,
+    /// Save the open file's tree, as currently expanded, as an HTML page
+    ExportHtml,
     ToggleTheme,
     OpenSettings,
     NavigateBack,
@@ -156,6 +165,12 @@ impl Toolbar {
 
                         ui.add_space(8.0);
                         ui.label(egui::RichText::new(title).size(13.0));
+
+                        if let Some(busy_label) = props.busy.label() {
+                            ui.add_space(8.0);
+                            ui.spinner();
+                            ui.label(egui::RichText::new(busy_label).size(12.0).weak());
+                        }
                     });
                 });
         }
@@ -197,8 +212,18 @@ impl Toolbar {
                     let close_shortcut = props.shortcuts.close_tab.format();
                     let new_win_shortcut = props.shortcuts.new_window.format();
 
+                    let open_blocker = props.busy.open_blocker();
+                    let close_blocker = props.busy.close_blocker();
+
                     ui.menu_button("File", |ui| {
-                        if ui.button(format!("Open File…  {open_shortcut}")).clicked() {
+                        if ui
+                            .add_enabled(
+                                open_blocker.is_none(),
+                                egui::Button::new(format!("Open File…  {open_shortcut}")),
+                            )
+                            .on_disabled_hover_text(open_blocker.unwrap_or_default())
+                            .clicked()
+                        {
                             ui.close();
                             if let Some(path) = pick_file(plugins_enabled)
                                 && let Some(file_type) = infer_file_type(&path)
@@ -214,7 +239,19 @@ impl Toolbar {
                             ui.close();
                         }
                         ui.separator();
-                        if ui.button(format!("Close Tab  {close_shortcut}")).clicked() {
+                        if ui.button("Export View as HTML…").clicked() {
+                            pending = Some(ToolbarEvent::ExportHtml);
+                            ui.close();
+                        }
+                        ui.separator();
+                        if ui
+                            .add_enabled(
+                                close_blocker.is_none(),
+                                egui::Button::new(format!("Close Tab  {close_shortcut}")),
+                            )
+                            .on_disabled_hover_text(close_blocker.unwrap_or_default())
+                            .clicked()
+                        {
                             pending = Some(ToolbarEvent::CloseTab);
                             ui.close();
                         }
@@ -236,6 +273,8 @@ fn infer_file_type(path: &Path) -> Option<FileKind> {
     match ext.as_str() {
         "ndjson" => Some(FileKind::Ndjson),
         "json" => Some(FileKind::Json),
+        // Compressed files take the type of the inner extension
+        "gz" => infer_file_type(&crate::file::gzip::inner_path(path)),
         _ => {
             // Ask the plugin registry whether any plugin handles this extension
             // so we don't fall back to a stale file-type from the previous file.
@@ -250,7 +289,15 @@ fn infer_file_type(path: &Path) -> Option<FileKind> {
                     },
                 );
             }
-            None
+            // Installed plugins take precedence over the built-in CSV/YAML/TOML/XML/Parquet loaders
+            match ext.as_str() {
+                "csv" | "tsv" => Some(FileKind::Csv),
+                "yaml" | "yml" => Some(FileKind::Yaml),
+                "toml" => Some(FileKind::Toml),
+                "xml" => Some(FileKind::Xml),
+                "parquet" => Some(FileKind::Parquet),
+                _ => None,
+            }
         }
     }
 }
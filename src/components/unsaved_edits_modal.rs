@@ -0,0 +1,166 @@
+use eframe::egui::{self, Frame, Layout, Margin, RichText};
+
+use crate::{
+    components::traits::StatelessComponent,
+    theme::{ThemeColors, phosphor_font_id},
+};
+use thoth_plugin_sdk::components::{
+    Button, ButtonColor, ButtonSize, ButtonType, Typography, TypographyVariant,
+};
+
+/// Question asked before a file with values edited in place is closed,
+/// replaced or re-read
+pub struct UnsavedEditsModal;
+
+pub struct UnsavedEditsModalProps<'a> {
+    pub file_name: &'a str,
+    /// Records with unsaved edits
+    pub record_count: usize,
+}
+
+/// What the user chose; all `false` while the modal stays open
+#[derive(Default)]
+pub struct UnsavedEditsModalOutput {
+    pub save: bool,
+    pub discard: bool,
+    pub cancel: bool,
+}
+
+impl StatelessComponent for UnsavedEditsModal {
+    type Props<'a> = UnsavedEditsModalProps<'a>;
+    type Output = UnsavedEditsModalOutput;
+
+    fn render(ui: &mut egui::Ui, props: Self::Props<'_>) -> Self::Output {
+        let colors = ui.ctx().memory(|mem| {
+            mem.data
+                .get_temp::<ThemeColors>(egui::Id::new("theme_colors"))
+                .unwrap_or_else(|| crate::theme::Theme::default().colors())
+        });
+
+        let mut output = UnsavedEditsModalOutput::default();
+
+        let modal = egui::Modal::new(egui::Id::new("unsaved_edits_modal")).show(ui.ctx(), |ui| {
+            ui.set_width(420.0);
+
+            // ── Header ────────────────────────────────────────────────────────
+            Frame::new()
+                .inner_margin(Margin {
+                    left: 24,
+                    right: 24,
+                    top: 24,
+                    bottom: 16,
+                })
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(egui_phosphor::regular::WARNING)
+                                .font(phosphor_font_id(28.0))
+                                .color(colors.warning),
+                        );
+                        ui.add_space(10.0);
+                        ui.vertical(|ui| {
+                            ui.add(
+                                Typography::builder()
+                                    .text("Unsaved Edits")
+                                    .variant(TypographyVariant::BodyLarge)
+                                    .bold(true)
+                                    .build(),
+                            );
+                            Typography::body_muted(ui, props.file_name);
+                        });
+                    });
+                });
+
+            ui.add(egui::Separator::default().spacing(0.0));
+
+            // ── Body ──────────────────────────────────────────────────────────
+            Frame::new()
+                .inner_margin(Margin {
+                    left: 24,
+                    right: 24,
+                    top: 16,
+                    bottom: 16,
+                })
+                .show(ui, |ui| {
+                    Typography::body(
+                        ui,
+                        &format!(
+                            "{} edited {} not been saved. Save them to the file first?",
+                            props.record_count,
+                            if props.record_count == 1 {
+                                "record has"
+                            } else {
+                                "records have"
+                            }
+                        ),
+                    );
+                });
+
+            ui.add(egui::Separator::default().spacing(0.0));
+
+            // ── Footer ────────────────────────────────────────────────────────
+            Frame::new()
+                .fill(colors.bg_sunken)
+                .inner_margin(Margin {
+                    left: 24,
+                    right: 24,
+                    top: 12,
+                    bottom: 12,
+                })
+                .show(ui, |ui| {
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .add(
+                                Button::builder()
+                                    .label("Save")
+                                    .button_type(ButtonType::Elevated)
+                                    .color(ButtonColor::Primary)
+                                    .button_size(ButtonSize::Medium)
+                                    .build(),
+                            )
+                            .clicked()
+                        {
+                            output.save = true;
+                        }
+
+                        ui.add_space(8.0);
+
+                        if ui
+                            .add(
+                                Button::builder()
+                                    .label("Discard")
+                                    .button_type(ButtonType::Elevated)
+                                    .color(ButtonColor::Default)
+                                    .button_size(ButtonSize::Medium)
+                                    .build(),
+                            )
+                            .clicked()
+                        {
+                            output.discard = true;
+                        }
+
+                        ui.add_space(8.0);
+
+                        if ui
+                            .add(
+                                Button::builder()
+                                    .label("Cancel")
+                                    .button_type(ButtonType::Text)
+                                    .color(ButtonColor::Default)
+                                    .button_size(ButtonSize::Medium)
+                                    .build(),
+                            )
+                            .clicked()
+                        {
+                            output.cancel = true;
+                        }
+                    });
+                });
+        });
+
+        if modal.should_close() {
+            output.cancel = true;
+        }
+        output
+    }
+}
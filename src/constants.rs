@@ -10,6 +10,95 @@ pub const MAX_SIDEBAR_WIDTH_RATIO: f32 = 0.7; // 70% of window width
 // Recent files configuration
 pub const MAX_RECENT_FILES: usize = 10;
 
-// Scroll margin for automatic scrolling behavior
-// Number of rows margin before scrolling
+// Default scroll margin for automatic scrolling behavior
+// Number of rows kept between the selection and the view's edge
 pub const SCROLL_MARGIN: usize = 3;
+
+// Presentation mode scales the configured font size by this factor
+pub const PRESENTATION_FONT_SCALE: f32 = 1.3;
+
+// Expand-all is applied incrementally so huge records don't freeze the UI
+// Number of nodes expanded per frame
+pub const EXPAND_ALL_NODES_PER_FRAME: usize = 5_000;
+
+// Expanding to a fixed depth is applied in one go, so it is capped instead
+// Maximum number of rows a depth expansion may produce
+pub const EXPAND_TO_DEPTH_MAX_ROWS: usize = 100_000;
+
+// Expanded arrays longer than this show a window of elements at a time
+// Elements shown at first, and added or skipped per "Load next"/"Show previous"
+pub const ARRAY_WINDOW_SIZE: usize = 1_000;
+
+// Sorting root records reads sort keys in chunks instead of loading every record
+// Number of records parsed per chunk
+pub const SORT_KEY_CHUNK_SIZE: usize = 1_000;
+
+// Field stats list the most frequent non-numeric values of a field
+// Number of values listed
+pub const FIELD_STATS_TOP_VALUES: usize = 20;
+// Distinct values counted before new ones are left out, bounding memory on
+// fields like ids where every record differs
+pub const FIELD_STATS_MAX_DISTINCT: usize = 100_000;
+
+// Editors often save in several writes, so a change is only acted on once the
+// file has been quiet for this long
+pub const FILE_RELOAD_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(300);
+
+// Schema validation runs over all root records a little each frame
+// Time spent validating per frame
+pub const SCHEMA_VALIDATION_FRAME_BUDGET: std::time::Duration = std::time::Duration::from_millis(8);
+// Violations kept per record (later ones are dropped)
+pub const MAX_SCHEMA_ERRORS_PER_RECORD: usize = 50;
+// Violations listed in the schema sidebar panel
+pub const MAX_SCHEMA_PANEL_ERRORS: usize = 500;
+
+// Facet chips in the search panel come from the first records of a file,
+// sampled a little each frame
+pub const FACET_SAMPLE_RECORDS: usize = 1_000;
+pub const FACET_FRAME_BUDGET: std::time::Duration = std::time::Duration::from_millis(4);
+// A field with more distinct values than this in the sample gets no value chips
+pub const FACET_MAX_DISTINCT_VALUES: usize = 8;
+// Chips shown for keys, and for values across all fields
+pub const FACET_MAX_KEY_CHIPS: usize = 16;
+pub const FACET_MAX_VALUE_CHIPS: usize = 24;
+
+// NDJSON line boundaries are indexed on the rayon pool for large files
+// Files smaller than this are indexed in a single sequential pass
+pub const NDJSON_PARALLEL_INDEX_MIN_BYTES: u64 = 64 * 1024 * 1024;
+// Bytes scanned for newlines per parallel task
+pub const NDJSON_INDEX_CHUNK_BYTES: u64 = 8 * 1024 * 1024;
+
+// Files whose first bytes look binary are refused before any loader indexes
+// them. Bytes read from the start of a file to decide
+pub const BINARY_SNIFF_BYTES: u64 = 8 * 1024;
+// Share of those bytes that may be null or invalid UTF-8 before the file
+// counts as binary; legacy 8-bit encodings stay well below
+pub const BINARY_MAX_INVALID_UTF8_RATIO: f64 = 0.3;
+
+// A failed automatic update check is retried after this many seconds, the
+// wait doubling with each failure in a row up to the regular check interval
+pub const UPDATE_RETRY_BASE_SECS: i64 = 60;
+
+// Bytes scanned between progress reports (and cancel checks) while a file
+// is indexed on open
+pub const OPEN_PROGRESS_STEP_BYTES: u64 = 1024 * 1024;
+
+// Default for `performance.max_file_size_mb`, also the decompressed size
+// limit for `.gz` files opened outside the viewer (search, MCP)
+pub const DEFAULT_MAX_FILE_SIZE_MB: u64 = 500;
+
+// Records whose changes are listed in a find-and-replace preview
+pub const MAX_REPLACE_PREVIEW_RECORDS: usize = 500;
+
+// Suggestions shown under the jump-to-path input
+pub const MAX_PATH_SUGGESTIONS: usize = 20;
+
+// Clipboard history keeps this session's recent copies (never saved to disk)
+// Number of copies remembered
+pub const CLIPBOARD_HISTORY_SIZE: usize = 25;
+// Larger copies are put on the clipboard but not remembered
+pub const CLIPBOARD_HISTORY_MAX_ENTRY_BYTES: usize = 1024 * 1024;
+
+// Rows written to an HTML export of the tree; collapsed nodes stop exporting
+// their contents past this
+pub const HTML_EXPORT_MAX_ROWS: usize = 200_000;
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use super::types::ThothError;
 
 /// Centralized error handler for consistent error display across the application
@@ -10,16 +12,18 @@ impl ErrorHandler {
             ThothError::FileNotFound { path } => {
                 format!("Could not find the file:\n{}", path.display())
             }
-            ThothError::FileReadError { path, .. } => {
+            ThothError::FileReadError { path, reason } => {
                 format!(
-                    "Unable to read the file:\n{}\n\nPlease check if the file exists and you have permission to read it.",
-                    path.display()
+                    "Unable to read the file:\n{}\n{}\n\nPlease check if the file exists and you have permission to read it.",
+                    path.display(),
+                    reason
                 )
             }
-            ThothError::FileWriteError { path, .. } => {
+            ThothError::FileWriteError { path, reason } => {
                 format!(
-                    "Unable to write to the file:\n{}\n\nPlease check if you have permission to write to this location.",
-                    path.display()
+                    "Unable to write to the file:\n{}\n{}\n\nPlease check if you have permission to write to this location.",
+                    path.display(),
+                    reason
                 )
             }
             ThothError::InvalidFileType { path, expected } => {
@@ -29,6 +33,23 @@ impl ErrorHandler {
                     expected
                 )
             }
+            ThothError::FileTooLarge { path, limit_mb } => {
+                format!(
+                    "The file is too large to open:\n{}\n\nIts contents exceed the {} MB limit.",
+                    path.display(),
+                    limit_mb
+                )
+            }
+            ThothError::OpenCanceled { path } => {
+                format!("Opening the file was canceled:\n{}", path.display())
+            }
+            ThothError::UnsupportedFormat { path, reason } => {
+                format!(
+                    "This doesn't look like a text/JSON file:\n{}\n\n{}",
+                    path.display(),
+                    reason
+                )
+            }
             ThothError::JsonParseError { line, reason } => {
                 if let Some(line) = line {
                     format!("Invalid JSON at line {}:\n{}", line, reason)
@@ -112,6 +133,23 @@ impl ErrorHandler {
         }
     }
 
+    /// Error details for a bug report: the full error with its path and
+    /// reason, plus the Thoth version, platform and the open file
+    pub fn get_error_details(error: &ThothError, file_path: Option<&Path>) -> String {
+        let mut details = format!(
+            "{}\n\nThoth version: {}\nPlatform: {} {}",
+            error,
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        );
+        if let Some(path) = file_path {
+            details.push_str(&format!("\nFile: {}", path.display()));
+        }
+        details.push_str(&format!("\nDebug: {:?}", error));
+        details
+    }
+
     /// Get a technical error message (for logs/debugging)
     pub fn get_technical_message(error: &ThothError) -> String {
         format!("{:?}", error)
@@ -124,6 +162,9 @@ impl ErrorHandler {
             ThothError::FileNotFound { .. } => true,
             ThothError::FileReadError { .. } => true,
             ThothError::InvalidFileType { .. } => true,
+            ThothError::FileTooLarge { .. } => true,
+            ThothError::OpenCanceled { .. } => true,
+            ThothError::UnsupportedFormat { .. } => true,
             ThothError::FileWriteError { .. } => false, // More serious
 
             // JSON errors - recoverable (user can try different file)
@@ -170,3 +211,24 @@ impl ErrorHandler {
         eprintln!("[ERROR] {}", Self::get_technical_message(error));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_details_include_reason_path_and_version() {
+        let error = ThothError::FileReadError {
+            path: "data.ndjson".into(),
+            reason: "Permission denied".to_string(),
+        };
+        let details = ErrorHandler::get_error_details(&error, Some(Path::new("/tmp/data.ndjson")));
+        assert!(details.starts_with("Failed to read file 'data.ndjson': Permission denied\n"));
+        assert!(details.contains(&format!("Thoth version: {}", env!("CARGO_PKG_VERSION"))));
+        assert!(details.contains("File: /tmp/data.ndjson"));
+
+        let details = ErrorHandler::get_error_details(&error, None);
+        assert!(!details.contains("File:"));
+        assert!(ErrorHandler::get_user_message(&error).contains("Permission denied"));
+    }
+}
@@ -25,6 +25,9 @@ impl ErrorRecovery {
             ThothError::FileReadError { .. } => RecoveryAction::ShowError,
             ThothError::FileWriteError { .. } => RecoveryAction::ShowError,
             ThothError::InvalidFileType { .. } => RecoveryAction::ShowError,
+            ThothError::FileTooLarge { .. } => RecoveryAction::ShowError,
+            ThothError::OpenCanceled { .. } => RecoveryAction::ClearError,
+            ThothError::UnsupportedFormat { .. } => RecoveryAction::ShowError,
 
             // JSON errors - show and allow user to try different file
             ThothError::JsonParseError { .. } => RecoveryAction::ShowError,
@@ -75,6 +78,12 @@ impl ErrorRecovery {
             ThothError::InvalidFileType { .. } => {
                 Some("Please select a JSON or NDJSON file.".to_string())
             }
+            ThothError::FileTooLarge { .. } => Some(
+                "Raise performance.max_file_size_mb in settings to open larger files.".to_string(),
+            ),
+            ThothError::UnsupportedFormat { .. } => {
+                Some("Open a JSON, NDJSON, CSV, YAML, TOML, XML or Parquet file.".to_string())
+            }
             ThothError::JsonParseError { .. } => {
                 Some("Check if the file contains valid JSON.".to_string())
             }
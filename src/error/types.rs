@@ -19,6 +19,19 @@ pub enum ThothError {
         path: PathBuf,
         expected: String,
     },
+    FileTooLarge {
+        path: PathBuf,
+        limit_mb: u64,
+    },
+    /// The user stopped the file from opening before it finished indexing
+    OpenCanceled {
+        path: PathBuf,
+    },
+    /// The file's content looks binary rather than text
+    UnsupportedFormat {
+        path: PathBuf,
+        reason: String,
+    },
 
     // JSON/NDJSON parsing errors
     JsonParseError {
@@ -127,6 +140,25 @@ impl std::fmt::Display for ThothError {
                     expected
                 )
             }
+            ThothError::FileTooLarge { path, limit_mb } => {
+                write!(
+                    f,
+                    "File '{}' exceeds the {} MB size limit",
+                    path.display(),
+                    limit_mb
+                )
+            }
+            ThothError::OpenCanceled { path } => {
+                write!(f, "Opening '{}' was canceled", path.display())
+            }
+            ThothError::UnsupportedFormat { path, reason } => {
+                write!(
+                    f,
+                    "'{}' doesn't look like a text file: {}",
+                    path.display(),
+                    reason
+                )
+            }
 
             // JSON errors
             ThothError::JsonParseError { line, reason } => {
@@ -1,16 +1,237 @@
+use crate::constants::{BINARY_MAX_INVALID_UTF8_RATIO, BINARY_SNIFF_BYTES};
 use crate::error::{Result, ThothError};
+use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DetectedFileType {
     Ndjson,
     JsonArray,
     JsonObject,
+    Csv,
+    Yaml,
+    Toml,
+    Xml,
+    Parquet,
+    /// JSON with comments and trailing commas, read as a single document
+    Jsonc,
+}
+
+/// Candidate CSV delimiters, in order of preference when counts tie.
+const CSV_DELIMITERS: [u8; 4] = [b',', b'\t', b';', b'|'];
+
+/// Magic bytes at the start (and end) of every Parquet file.
+const PARQUET_MAGIC: &[u8] = b"PAR1";
+
+/// Byte order mark some editors write at the start of UTF-8 files.
+pub const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Byte order marks of UTF-16 text, little- and big-endian.
+const UTF16_LE_BOM: &[u8] = b"\xff\xfe";
+const UTF16_BE_BOM: &[u8] = b"\xfe\xff";
+
+/// Most non-empty lines parsed when deciding between NDJSON and JSON.
+const SNIFF_LINES: usize = 8;
+
+/// Longest line parsed while sniffing; a longer first line (such as a whole
+/// minified document) is taken to be a single document.
+const SNIFF_LINE_BYTES: u64 = 16 * 1024 * 1024;
+
+impl DetectedFileType {
+    pub const ALL: [DetectedFileType; 9] = [
+        DetectedFileType::Ndjson,
+        DetectedFileType::JsonArray,
+        DetectedFileType::JsonObject,
+        DetectedFileType::Csv,
+        DetectedFileType::Yaml,
+        DetectedFileType::Toml,
+        DetectedFileType::Xml,
+        DetectedFileType::Parquet,
+        DetectedFileType::Jsonc,
+    ];
+
+    /// Name shown to the user
+    pub fn label(self) -> &'static str {
+        match self {
+            DetectedFileType::Ndjson => "NDJSON",
+            DetectedFileType::JsonArray => "JSON array",
+            DetectedFileType::JsonObject => "JSON object",
+            DetectedFileType::Csv => "CSV",
+            DetectedFileType::Yaml => "YAML",
+            DetectedFileType::Toml => "TOML",
+            DetectedFileType::Xml => "XML",
+            DetectedFileType::Parquet => "Parquet",
+            DetectedFileType::Jsonc => "JSONC",
+        }
+    }
+}
+
+/// The type a file was sniffed as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Detection {
+    pub file_type: DetectedFileType,
+    /// Set when detection was ambiguous: another type the file could be read
+    /// as, e.g. NDJSON for a `.ndjson` file holding one multi-line object.
+    pub alternative: Option<DetectedFileType>,
+    /// Encoding and line endings of a text file; `None` for Parquet
+    pub text: Option<TextFormat>,
+}
+
+impl Detection {
+    fn certain(file_type: DetectedFileType) -> Self {
+        Self {
+            file_type,
+            alternative: None,
+            text: None,
+        }
+    }
+}
+
+/// Encoding of a text file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    /// UTF-8 starting with a byte order mark
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+}
+
+impl TextEncoding {
+    /// Name shown to the user
+    pub fn label(self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf8Bom => "UTF-8 BOM",
+            TextEncoding::Utf16Le => "UTF-16 LE",
+            TextEncoding::Utf16Be => "UTF-16 BE",
+        }
+    }
+
+    pub fn is_utf16(self) -> bool {
+        matches!(self, TextEncoding::Utf16Le | TextEncoding::Utf16Be)
+    }
+}
+
+/// How the lines of a text file end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+    /// Both LF and CRLF occur
+    Mixed,
+}
+
+impl LineEnding {
+    /// Name shown to the user
+    pub fn label(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "LF",
+            LineEnding::Crlf => "CRLF",
+            LineEnding::Mixed => "Mixed",
+        }
+    }
+}
+
+/// Encoding and line endings of a text file, read from its first chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextFormat {
+    pub encoding: TextEncoding,
+    /// `None` when the chunk has no line break
+    pub line_ending: Option<LineEnding>,
+}
+
+impl TextFormat {
+    /// Read the format off `bytes`, the start of a file
+    ///
+    /// UTF-16 is recognized by its byte order mark or, without one, by the
+    /// null byte next to each ASCII character.
+    pub fn of(bytes: &[u8]) -> Self {
+        let encoding = if bytes.starts_with(UTF8_BOM) {
+            TextEncoding::Utf8Bom
+        } else if bytes.starts_with(UTF16_LE_BOM) {
+            TextEncoding::Utf16Le
+        } else if bytes.starts_with(UTF16_BE_BOM) {
+            TextEncoding::Utf16Be
+        } else {
+            match bytes {
+                [a, 0, b, 0, ..] if is_ascii_text(*a) && is_ascii_text(*b) => TextEncoding::Utf16Le,
+                [0, a, 0, b, ..] if is_ascii_text(*a) && is_ascii_text(*b) => TextEncoding::Utf16Be,
+                _ => TextEncoding::Utf8,
+            }
+        };
+        let line_ending = match encoding {
+            TextEncoding::Utf16Le => line_ending(
+                bytes
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]])),
+            ),
+            TextEncoding::Utf16Be => line_ending(
+                bytes
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]])),
+            ),
+            _ => line_ending(bytes.iter().map(|&b| u16::from(b))),
+        };
+        Self {
+            encoding,
+            line_ending,
+        }
+    }
+}
+
+/// Printable ASCII or whitespace, as text starts with
+fn is_ascii_text(byte: u8) -> bool {
+    byte.is_ascii_graphic() || byte.is_ascii_whitespace()
+}
+
+/// Line ending style of a run of UTF-8 bytes or UTF-16 code units
+fn line_ending(units: impl Iterator<Item = u16>) -> Option<LineEnding> {
+    let (mut lf, mut crlf) = (0usize, 0usize);
+    let mut prev = 0u16;
+    for unit in units {
+        if unit == u16::from(b'\n') {
+            if prev == u16::from(b'\r') {
+                crlf += 1;
+            } else {
+                lf += 1;
+            }
+        }
+        prev = unit;
+    }
+    match (lf, crlf) {
+        (0, 0) => None,
+        (_, 0) => Some(LineEnding::Lf),
+        (0, _) => Some(LineEnding::Crlf),
+        _ => Some(LineEnding::Mixed),
+    }
+}
+
+/// Encoding and line endings of `path`, read from its first
+/// [`BINARY_SNIFF_BYTES`]; `None` if it can't be read
+pub fn sniff_text_format(path: &Path) -> Option<TextFormat> {
+    let mut prefix = Vec::new();
+    File::open(path)
+        .ok()?
+        .take(BINARY_SNIFF_BYTES)
+        .read_to_end(&mut prefix)
+        .ok()?;
+    Some(TextFormat::of(&prefix))
 }
 
 pub fn sniff_file_type(path: &Path) -> Result<DetectedFileType> {
+    detect_file_type(path).map(|detection| detection.file_type)
+}
+
+/// Sniff the type of `path`, noting when its content also fits another type
+///
+/// Only the JSON family is ever ambiguous: a file whose first lines are each
+/// a complete value is NDJSON, one whose first line is not is a single
+/// document, and the extension is only a tie-breaker or a reason for doubt.
+pub fn detect_file_type(path: &Path) -> Result<Detection> {
     let file = File::open(path).map_err(|e| {
         if e.kind() == std::io::ErrorKind::NotFound {
             ThothError::FileNotFound {
@@ -23,28 +244,94 @@ pub fn sniff_file_type(path: &Path) -> Result<DetectedFileType> {
             }
         }
     })?;
-    let mut reader = BufReader::new(file);
 
-    // Read a small prefix to find the first non-ws char
-    let mut prefix = [0u8; 8192];
-    let n = reader
-        .read(&mut prefix)
+    // Read a small prefix to rule out binary data and find the first non-ws char
+    let mut prefix = Vec::new();
+    file.take(BINARY_SNIFF_BYTES)
+        .read_to_end(&mut prefix)
         .map_err(|e| ThothError::FileReadError {
             path: path.to_path_buf(),
             reason: e.to_string(),
         })?;
-    if n == 0 {
+    let bytes = prefix.as_slice();
+
+    // Parquet is binary; its files open with the `PAR1` magic
+    if bytes.starts_with(PARQUET_MAGIC)
+        || path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("parquet"))
+    {
+        return Ok(Detection::certain(DetectedFileType::Parquet));
+    }
+    let text = TextFormat::of(bytes);
+    // The loaders index UTF-8 bytes, so UTF-16 can't be read as it is
+    if text.encoding.is_utf16() {
+        return Err(ThothError::UnsupportedFormat {
+            path: path.to_path_buf(),
+            reason: format!(
+                "It is {} text, which can't be opened yet. Save it as UTF-8 to view it.",
+                text.encoding.label()
+            ),
+        });
+    }
+    detect_text_type(path, bytes).map(|detection| Detection {
+        text: Some(text),
+        ..detection
+    })
+}
+
+/// Sniff the type of `path`, a file that isn't Parquet, from its first bytes
+fn detect_text_type(path: &Path, bytes: &[u8]) -> Result<Detection> {
+    // Anything else binary is refused before a loader tries to index it
+    if let Some(reason) = binary_reason(bytes) {
+        return Err(ThothError::UnsupportedFormat {
+            path: path.to_path_buf(),
+            reason: reason.to_string(),
+        });
+    }
+
+    // The extension is a reliable hint for CSV, which has no leading marker
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv") || ext.eq_ignore_ascii_case("tsv"))
+    {
+        return Ok(Detection::certain(DetectedFileType::Csv));
+    }
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"))
+    {
+        return Ok(Detection::certain(DetectedFileType::Yaml));
+    }
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"))
+    {
+        return Ok(Detection::certain(DetectedFileType::Toml));
+    }
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("xml"))
+    {
+        return Ok(Detection::certain(DetectedFileType::Xml));
+    }
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("jsonc") || ext.eq_ignore_ascii_case("json5"))
+    {
+        return Ok(Detection::certain(DetectedFileType::Jsonc));
+    }
+    if bytes.is_empty() {
         return Err(ThothError::InvalidFileType {
             path: path.to_path_buf(),
             expected: "non-empty JSON or NDJSON file".to_string(),
         });
     }
-    let bytes = &prefix[..n];
 
     // Skip UTF-8 BOM if present
     let mut i = 0usize;
-    if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
-        i = 3;
+    if bytes.starts_with(UTF8_BOM) {
+        i = UTF8_BOM.len();
     }
     while i < bytes.len() && matches!(bytes[i], b' ' | b'\n' | b'\r' | b'\t') {
         i += 1;
@@ -54,58 +341,587 @@ pub fn sniff_file_type(path: &Path) -> Result<DetectedFileType> {
         expected: "file with JSON content".to_string(),
     })?;
 
-    if first == b'[' {
-        return Ok(DetectedFileType::JsonArray);
+    if first == b'<' && looks_like_xml(&bytes[i..]) {
+        return Ok(Detection::certain(DetectedFileType::Xml));
     }
-    if first != b'{' {
-        // Strictly speaking NDJSON lines can start with [ as well, but common case is '{'
-        // If it's not '[' or '{', treat it as NDJSON only if first two lines parse as JSON.
-        return ndjson_if_two_lines_parse(path);
+    // No other format opens with a JavaScript-style comment
+    if bytes[i..].starts_with(b"//") || bytes[i..].starts_with(b"/*") {
+        return Ok(Detection::certain(DetectedFileType::Jsonc));
     }
+    if first != b'{' && first != b'[' {
+        // If it's not '[' or '{', treat it as NDJSON only if first two lines parse as JSON,
+        // otherwise as YAML if it looks like a document, or CSV if it has a
+        // delimiter-separated header.
+        return ndjson_if_two_lines_parse(path)
+            .map(Detection::certain)
+            .or_else(|err| {
+                yaml_if_structured(bytes)
+                    .or_else(|| csv_if_delimited(bytes))
+                    .map(Detection::certain)
+                    .ok_or(err)
+            });
+    }
+
+    // Starts with '{' or '[' – a single document or NDJSON
+    let document = if first == b'[' {
+        DetectedFileType::JsonArray
+    } else {
+        DetectedFileType::JsonObject
+    };
+    let has_ext = |exts: &[&str]| {
+        path.extension()
+            .is_some_and(|ext| exts.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+    };
+    let probe = probe_lines(path)?;
+    let detection = match probe.valid {
+        // The first line isn't a complete value, so the document spans lines
+        0 => Detection {
+            file_type: document,
+            alternative: has_ext(&["ndjson", "jsonl"]).then_some(DetectedFileType::Ndjson),
+            text: None,
+        },
+        // One complete value on its own line: either reading works
+        1 if !probe.more => {
+            if has_ext(&["ndjson", "jsonl"]) {
+                Detection::certain(DetectedFileType::Ndjson)
+            } else {
+                Detection::certain(document)
+            }
+        }
+        // A complete value followed by a line that doesn't parse: likely
+        // NDJSON with a broken record, but it could be a damaged document
+        1 => Detection {
+            file_type: DetectedFileType::Ndjson,
+            alternative: Some(document),
+            text: None,
+        },
+        _ => Detection {
+            file_type: DetectedFileType::Ndjson,
+            alternative: has_ext(&["json"]).then_some(document),
+            text: None,
+        },
+    };
+    Ok(detection)
+}
 
-    // Starts with '{' – could be Object or NDJSON. Check first two non-empty lines.
-    ndjson_if_two_lines_parse(path).or(Ok(DetectedFileType::JsonObject))
+/// Why `bytes`, the start of a file, look like binary data rather than text,
+/// or `None` when they pass as text
+///
+/// Null bytes and invalid UTF-8 together may make up at most
+/// [`BINARY_MAX_INVALID_UTF8_RATIO`] of the bytes, so a stray control byte
+/// or text in a legacy 8-bit encoding still opens. A character cut off by
+/// the end of the prefix doesn't count.
+fn binary_reason(bytes: &[u8]) -> Option<&'static str> {
+    let nulls = bytes.iter().filter(|&&b| b == 0).count();
+    let mut invalid = 0usize;
+    let mut rest = bytes;
+    while let Err(e) = std::str::from_utf8(rest) {
+        let Some(len) = e.error_len() else {
+            break;
+        };
+        invalid += len;
+        rest = &rest[e.valid_up_to() + len..];
+    }
+    if ((nulls + invalid) as f64) <= bytes.len() as f64 * BINARY_MAX_INVALID_UTF8_RATIO {
+        return None;
+    }
+    Some(if nulls > 0 {
+        "It contains null bytes, which text files don't."
+    } else {
+        "Much of it isn't valid UTF-8 text."
+    })
 }
 
 fn ndjson_if_two_lines_parse(path: &Path) -> Result<DetectedFileType> {
-    let file = File::open(path).map_err(|e| ThothError::FileReadError {
+    if probe_lines(path)?.valid >= 2 {
+        return Ok(DetectedFileType::Ndjson);
+    }
+    Err(ThothError::InvalidFileType {
+        path: path.to_path_buf(),
+        expected: "NDJSON format (newline-delimited JSON)".to_string(),
+    })
+}
+
+/// How the first non-empty lines of a file parse on their own
+struct LineProbe {
+    /// Leading non-empty lines that are each a complete JSON value
+    valid: usize,
+    /// A non-empty line follows the valid ones
+    more: bool,
+}
+
+/// Parse up to [`SNIFF_LINES`] non-empty lines, stopping at the first one
+/// that isn't a complete JSON value or is too long to check; blank lines and
+/// a BOM are skipped
+fn probe_lines(path: &Path) -> Result<LineProbe> {
+    let read_error = |e: std::io::Error| ThothError::FileReadError {
         path: path.to_path_buf(),
         reason: e.to_string(),
-    })?;
-    let mut reader = BufReader::new(file);
+    };
+    let mut reader = BufReader::new(File::open(path).map_err(read_error)?);
 
-    let mut valid = 0usize;
-    let mut buf = String::new();
-    // Look only at the first few non-empty lines
-    for _ in 0..8 {
+    let mut probe = LineProbe {
+        valid: 0,
+        more: false,
+    };
+    let mut buf = Vec::new();
+    loop {
         buf.clear();
-        if reader
-            .read_line(&mut buf)
-            .map_err(|e| ThothError::FileReadError {
-                path: path.to_path_buf(),
-                reason: e.to_string(),
-            })?
-            == 0
-        {
+        let n = (&mut reader)
+            .take(SNIFF_LINE_BYTES + 1)
+            .read_until(b'\n', &mut buf)
+            .map_err(read_error)?;
+        if n == 0 {
             break;
         }
-        let line = buf.trim();
+        if n as u64 > SNIFF_LINE_BYTES {
+            probe.more = true;
+            break;
+        }
+        let line = buf.strip_prefix(UTF8_BOM).unwrap_or(&buf).trim_ascii();
         if line.is_empty() {
             continue;
         }
+        if probe.valid == SNIFF_LINES || serde_json::from_slice::<serde_json::Value>(line).is_err()
+        {
+            probe.more = true;
+            break;
+        }
+        probe.valid += 1;
+    }
+    Ok(probe)
+}
+
+/// Pick the delimiter that occurs most often (outside quotes) in a header line.
+pub fn sniff_csv_delimiter(line: &str) -> Option<u8> {
+    CSV_DELIMITERS
+        .iter()
+        .map(|&d| (d, count_unquoted(line, d)))
+        .filter(|&(_, count)| count > 0)
+        .fold(None, |best: Option<(u8, usize)>, candidate| match best {
+            Some(b) if b.1 >= candidate.1 => Some(b),
+            _ => Some(candidate),
+        })
+        .map(|(d, _)| d)
+}
 
-        if serde_json::from_str::<serde_json::Value>(line).is_ok() {
-            valid += 1;
-            if valid >= 2 {
-                return Ok(DetectedFileType::Ndjson);
+fn count_unquoted(line: &str, delimiter: u8) -> usize {
+    let mut in_quotes = false;
+    line.bytes()
+        .filter(|&b| {
+            if b == b'"' {
+                in_quotes = !in_quotes;
             }
-        } else {
-            // First non-empty line didn't parse → not NDJSON
-            break;
+            !in_quotes && b == delimiter
+        })
+        .count()
+}
+
+/// Treat the file as CSV if its first line has a delimiter and the second line
+/// (when present) uses the same delimiter the same number of times.
+fn csv_if_delimited(prefix: &[u8]) -> Option<DetectedFileType> {
+    let text = String::from_utf8_lossy(prefix);
+    let text = text.trim_start_matches('\u{feff}');
+    let mut lines = text.lines().filter(|l| !l.trim().is_empty());
+    let header = lines.next()?;
+    let delimiter = sniff_csv_delimiter(header)?;
+    match lines.next() {
+        Some(row) if count_unquoted(row, delimiter) != count_unquoted(header, delimiter) => None,
+        _ => Some(DetectedFileType::Csv),
+    }
+}
+
+/// Whether `text` (starting at its first `<`) opens with an XML declaration,
+/// comment, doctype or element tag
+fn looks_like_xml(text: &[u8]) -> bool {
+    match text.get(1) {
+        Some(b'?' | b'!') => true,
+        Some(&c) => c.is_ascii_alphabetic() || c == b'_',
+        None => false,
+    }
+}
+
+/// Treat the file as YAML if its first meaningful line is a document marker
+/// (`---` or a `%YAML` directive) or a `key: value` / `key:` mapping entry.
+fn yaml_if_structured(prefix: &[u8]) -> Option<DetectedFileType> {
+    let text = String::from_utf8_lossy(prefix);
+    let text = text.trim_start_matches('\u{feff}');
+    let line = text
+        .lines()
+        .map(str::trim_end)
+        .find(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'))?;
+
+    if line.starts_with("---") || line.starts_with("%YAML") {
+        return Some(DetectedFileType::Yaml);
+    }
+
+    let (key, rest) = line.split_once(':')?;
+    let key = key.trim();
+    let plain_key = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ' '));
+    let quoted_key = key.len() >= 2
+        && (key.starts_with('"') && key.ends_with('"')
+            || key.starts_with('\'') && key.ends_with('\''));
+    let separated = rest.is_empty() || rest.starts_with(' ');
+
+    ((plain_key || quoted_key) && separated).then_some(DetectedFileType::Yaml)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn sniff(contents: &str, suffix: &str) -> Result<DetectedFileType> {
+        let mut file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file.flush().unwrap();
+        sniff_file_type(file.path())
+    }
+
+    /// How `contents` are detected, leaving out the text format (tested on its own)
+    fn detect(contents: &str, suffix: &str) -> Detection {
+        let mut file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file.flush().unwrap();
+        Detection {
+            text: None,
+            ..detect_file_type(file.path()).unwrap()
         }
     }
-    Err(ThothError::InvalidFileType {
-        path: path.to_path_buf(),
-        expected: "NDJSON format (newline-delimited JSON)".to_string(),
-    })
+
+    fn ambiguous(file_type: DetectedFileType, alternative: DetectedFileType) -> Detection {
+        Detection {
+            file_type,
+            alternative: Some(alternative),
+            text: None,
+        }
+    }
+
+    #[test]
+    fn test_sniff_json_kinds() {
+        assert_eq!(
+            sniff("[1, 2]", ".json").unwrap(),
+            DetectedFileType::JsonArray
+        );
+        assert_eq!(
+            sniff("{\"a\": 1}", ".json").unwrap(),
+            DetectedFileType::JsonObject
+        );
+        assert_eq!(
+            sniff("{\"a\": 1}\n{\"a\": 2}\n", ".ndjson").unwrap(),
+            DetectedFileType::Ndjson
+        );
+    }
+
+    #[test]
+    fn test_detect_multi_line_object() {
+        let pretty = "{\n  \"a\": 1,\n  \"b\": {\"c\": 2}\n}\n";
+        assert_eq!(
+            detect(pretty, ".json"),
+            Detection::certain(DetectedFileType::JsonObject)
+        );
+        // The extension says NDJSON but the content is one document
+        assert_eq!(
+            detect(pretty, ".ndjson"),
+            ambiguous(DetectedFileType::JsonObject, DetectedFileType::Ndjson)
+        );
+        // A one-line object only follows the extension
+        assert_eq!(
+            detect("{\"a\": 1}\n", ".jsonl"),
+            Detection::certain(DetectedFileType::Ndjson)
+        );
+    }
+
+    #[test]
+    fn test_detect_ndjson_with_blank_lines() {
+        let ndjson = "\n{\"a\":1}\n\n\n\n\n\n\n\n\n{\"a\":2}\r\n\n";
+        assert_eq!(
+            detect(ndjson, ".ndjson"),
+            Detection::certain(DetectedFileType::Ndjson)
+        );
+        // NDJSON saved as .json is still NDJSON, but worth pointing out
+        assert_eq!(
+            detect(ndjson, ".json"),
+            ambiguous(DetectedFileType::Ndjson, DetectedFileType::JsonObject)
+        );
+        // A complete record followed by a broken one
+        assert_eq!(
+            detect("{\"a\":1}\n{\"a\":\n", ".json"),
+            ambiguous(DetectedFileType::Ndjson, DetectedFileType::JsonObject)
+        );
+    }
+
+    #[test]
+    fn test_detect_array_with_newlines_in_elements() {
+        let array = "[\n  {\"a\": 1,\n   \"b\": [1,\n 2]},\n  {\"a\": 2}\n]\n";
+        assert_eq!(
+            detect(array, ".json"),
+            Detection::certain(DetectedFileType::JsonArray)
+        );
+        assert_eq!(
+            detect("[{\"a\": 1},\n{\"a\": 2}]", ".json"),
+            Detection::certain(DetectedFileType::JsonArray)
+        );
+        // NDJSON whose records are arrays
+        assert_eq!(
+            detect("[1, 2]\n[3, 4]\n", ""),
+            Detection::certain(DetectedFileType::Ndjson)
+        );
+    }
+
+    #[test]
+    fn test_detect_leading_bom() {
+        assert_eq!(
+            sniff("\u{feff}{\"a\":1}\n{\"a\":2}\n", ".json").unwrap(),
+            DetectedFileType::Ndjson
+        );
+        assert_eq!(
+            sniff("\u{feff}{\n  \"a\": 1\n}", ".json").unwrap(),
+            DetectedFileType::JsonObject
+        );
+        assert_eq!(
+            sniff("\u{feff}\n[1, 2]", ".json").unwrap(),
+            DetectedFileType::JsonArray
+        );
+    }
+
+    #[test]
+    fn test_sniff_csv_by_extension() {
+        assert_eq!(
+            sniff("name\nAlice\n", ".csv").unwrap(),
+            DetectedFileType::Csv
+        );
+        assert_eq!(
+            sniff("a\tb\n1\t2\n", ".TSV").unwrap(),
+            DetectedFileType::Csv
+        );
+    }
+
+    #[test]
+    fn test_sniff_csv_by_content() {
+        assert_eq!(
+            sniff("id,name\n1,\"Doe, Jane\"\n", ".txt").unwrap(),
+            DetectedFileType::Csv
+        );
+        assert_eq!(
+            sniff("id;name\n1;Jane\n", "").unwrap(),
+            DetectedFileType::Csv
+        );
+    }
+
+    #[test]
+    fn test_sniff_yaml_by_extension() {
+        assert_eq!(
+            sniff("- a\n- b\n", ".yaml").unwrap(),
+            DetectedFileType::Yaml
+        );
+        assert_eq!(sniff("a: 1\n", ".YML").unwrap(), DetectedFileType::Yaml);
+    }
+
+    #[test]
+    fn test_sniff_jsonc() {
+        assert_eq!(sniff("[1, 2,]", ".jsonc").unwrap(), DetectedFileType::Jsonc);
+        assert_eq!(sniff("{a: 1}", ".JSON5").unwrap(), DetectedFileType::Jsonc);
+        // A leading comment gives it away whatever the extension
+        assert_eq!(
+            sniff("// settings\n{\"a\": 1}", ".json").unwrap(),
+            DetectedFileType::Jsonc
+        );
+        assert_eq!(
+            sniff("/* settings */ {\"a\": 1}", "").unwrap(),
+            DetectedFileType::Jsonc
+        );
+    }
+
+    #[test]
+    fn test_sniff_toml_by_extension() {
+        assert_eq!(
+            sniff("[server]\nport = 8080\n", ".toml").unwrap(),
+            DetectedFileType::Toml
+        );
+        assert_eq!(sniff("a = 1\n", ".TOML").unwrap(), DetectedFileType::Toml);
+    }
+
+    #[test]
+    fn test_sniff_parquet() {
+        assert_eq!(
+            sniff("anything", ".parquet").unwrap(),
+            DetectedFileType::Parquet
+        );
+        assert_eq!(
+            sniff("PAR1\u{15}\u{4}", ".data").unwrap(),
+            DetectedFileType::Parquet
+        );
+    }
+
+    #[test]
+    fn test_sniff_xml() {
+        assert_eq!(sniff("<a/>", ".xml").unwrap(), DetectedFileType::Xml);
+        assert_eq!(
+            sniff("<?xml version=\"1.0\"?>\n<root/>", ".txt").unwrap(),
+            DetectedFileType::Xml
+        );
+        assert_eq!(
+            sniff("\n  <config><name>x</name></config>", ".data").unwrap(),
+            DetectedFileType::Xml
+        );
+        assert!(sniff("< not markup", ".data").is_err());
+    }
+
+    #[test]
+    fn test_sniff_yaml_by_content() {
+        assert_eq!(
+            sniff("---\nname: thoth\n", ".txt").unwrap(),
+            DetectedFileType::Yaml
+        );
+        assert_eq!(
+            sniff("# config\nserver:\n  port: 8080\n", "").unwrap(),
+            DetectedFileType::Yaml
+        );
+        assert_eq!(
+            sniff("name: Jane, Doe\n", ".txt").unwrap(),
+            DetectedFileType::Yaml
+        );
+        // A timestamp-like first cell is not a mapping key
+        assert_eq!(
+            sniff("12:30,ok\n13:45,ok\n", ".txt").unwrap(),
+            DetectedFileType::Csv
+        );
+    }
+
+    #[test]
+    fn test_sniff_rejects_plain_text() {
+        assert!(sniff("just some words\nmore words\n", ".txt").is_err());
+        assert!(sniff("a,b,c\nno delimiters here\n", ".txt").is_err());
+    }
+
+    #[test]
+    fn test_sniff_csv_delimiter() {
+        assert_eq!(sniff_csv_delimiter("a,b,c"), Some(b','));
+        assert_eq!(sniff_csv_delimiter("a\tb,c\td"), Some(b'\t'));
+        assert_eq!(sniff_csv_delimiter("\"a;b\",c"), Some(b','));
+        assert_eq!(sniff_csv_delimiter("a|b|c"), Some(b'|'));
+        assert_eq!(sniff_csv_delimiter("abc"), None);
+    }
+
+    fn sniff_bytes(contents: &[u8], suffix: &str) -> Result<DetectedFileType> {
+        let mut file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+        file.write_all(contents).unwrap();
+        file.flush().unwrap();
+        sniff_file_type(file.path())
+    }
+
+    #[test]
+    fn test_sniff_rejects_binary() {
+        // A PNG header: magic, then chunk lengths full of null bytes
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\x01\0".to_vec();
+        png.extend((0..4096u32).map(|i| (i * 7 % 256) as u8));
+        assert!(matches!(
+            sniff_bytes(&png, ".json"),
+            Err(ThothError::UnsupportedFormat { .. })
+        ));
+        // Binary data without null bytes, even with a text extension
+        let noise: Vec<u8> = (0..4096u32).map(|i| 0x80 + (i * 13 % 120) as u8).collect();
+        assert!(matches!(
+            sniff_bytes(&noise, ".csv"),
+            Err(ThothError::UnsupportedFormat { .. })
+        ));
+        // Parquet is binary but supported
+        assert_eq!(
+            sniff_bytes(b"PAR1\0\0\x15\x04", "").unwrap(),
+            DetectedFileType::Parquet
+        );
+    }
+
+    #[test]
+    fn test_sniff_accepts_text_that_is_not_plain_ascii() {
+        // Multi-byte characters run across the end of the sniffed prefix
+        let line = "{\"name\":\"Zoë 日本語 🙂\"}\n";
+        let text = line.repeat(BINARY_SNIFF_BYTES as usize / line.len() + 10);
+        assert_eq!(sniff(&text, ".ndjson").unwrap(), DetectedFileType::Ndjson);
+        // A stray control byte or a few Latin-1 characters are no reason to refuse
+        assert_eq!(
+            sniff_bytes(b"{\"a\":\"caf\xe9\"}\n{\"b\":\"\0\"}\n", ".csv").unwrap(),
+            DetectedFileType::Csv
+        );
+    }
+
+    #[test]
+    fn test_text_format_encoding_and_line_endings() {
+        let format = |encoding, line_ending| TextFormat {
+            encoding,
+            line_ending,
+        };
+        assert_eq!(
+            TextFormat::of(b"{\"a\":1}\n{\"a\":2}\n"),
+            format(TextEncoding::Utf8, Some(LineEnding::Lf))
+        );
+        assert_eq!(
+            TextFormat::of(b"\xef\xbb\xbf{\r\n  \"a\": 1\r\n}"),
+            format(TextEncoding::Utf8Bom, Some(LineEnding::Crlf))
+        );
+        assert_eq!(
+            TextFormat::of(b"a\r\nb\nc"),
+            format(TextEncoding::Utf8, Some(LineEnding::Mixed))
+        );
+        assert_eq!(
+            TextFormat::of(b"{\"a\": 1}"),
+            format(TextEncoding::Utf8, None)
+        );
+
+        let utf16le: Vec<u8> = "\u{feff}{}\r\n"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        assert_eq!(
+            TextFormat::of(&utf16le),
+            format(TextEncoding::Utf16Le, Some(LineEnding::Crlf))
+        );
+        // Without a byte order mark
+        let utf16be: Vec<u8> = "[1]\n".encode_utf16().flat_map(u16::to_be_bytes).collect();
+        assert_eq!(
+            TextFormat::of(&utf16be),
+            format(TextEncoding::Utf16Be, Some(LineEnding::Lf))
+        );
+    }
+
+    #[test]
+    fn test_detect_reports_text_format() {
+        let mut file = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+        file.write_all(b"{\"a\":1}\r\n{\"a\":2}\r\n").unwrap();
+        file.flush().unwrap();
+        let detection = detect_file_type(file.path()).unwrap();
+        assert_eq!(
+            detection.text,
+            Some(TextFormat {
+                encoding: TextEncoding::Utf8,
+                line_ending: Some(LineEnding::Crlf),
+            })
+        );
+        assert_eq!(sniff_text_format(file.path()), detection.text);
+    }
+
+    #[test]
+    fn test_sniff_utf16_is_unsupported() {
+        let utf16: Vec<u8> = "\u{feff}{\"a\": 1}\n"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        match sniff_bytes(&utf16, ".json") {
+            Err(ThothError::UnsupportedFormat { reason, .. }) => {
+                assert!(reason.contains("UTF-16 LE"), "{}", reason)
+            }
+            other => panic!("expected UTF-16 to be refused, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_sniff_empty_file_is_error() {
+        let file = NamedTempFile::new().unwrap();
+        assert!(sniff_file_type(file.path()).is_err());
+    }
 }
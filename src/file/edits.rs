@@ -0,0 +1,158 @@
+use crate::error::{Result, ThothError};
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{BufWriter, Read, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+/// Parse text typed into an inline editor into the value it stands for
+///
+/// Numbers, booleans and `null` keep their JSON type; input wrapped in
+/// double quotes is taken as a JSON string literal (so `"42"` stays a
+/// string), and anything else becomes a plain string.
+pub fn parse_edited_value(text: &str) -> Value {
+    let trimmed = text.trim();
+    if trimmed.starts_with('"') && trimmed.ends_with('"') && trimmed.len() >= 2 {
+        if let Ok(value @ Value::String(_)) = serde_json::from_str(trimmed) {
+            return value;
+        }
+        return Value::String(trimmed[1..trimmed.len() - 1].to_string());
+    }
+    match serde_json::from_str(trimmed) {
+        Ok(value @ (Value::Number(_) | Value::Bool(_) | Value::Null)) => value,
+        _ => Value::String(text.to_string()),
+    }
+}
+
+/// Write `source` to `output` with the records at `spans` replaced by `edits`
+///
+/// Bytes outside the edited spans (separators, whitespace, untouched
+/// records) are copied verbatim, so only the edited records change. The
+/// result goes to a temp file next to `output` and is renamed into place
+/// once complete, keeping the original file's permissions.
+pub(crate) fn rewrite_spans(
+    source: &File,
+    spans: &[(u64, u64)],
+    edits: &BTreeMap<usize, Value>,
+    output: &Path,
+) -> Result<()> {
+    let write_error = |e: std::io::Error| ThothError::FileWriteError {
+        path: output.to_path_buf(),
+        reason: format!("Failed to save edits: {}", e),
+    };
+
+    let dir = output
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let tmp = tempfile::NamedTempFile::new_in(dir).map_err(write_error)?;
+    let mut writer = BufWriter::new(tmp.as_file());
+
+    let mut reader = source.try_clone().map_err(write_error)?;
+    let source_len = reader.metadata().map_err(write_error)?.len();
+    let mut pos = 0;
+    for (&record_index, value) in edits {
+        let &(start, end) = spans
+            .get(record_index)
+            .ok_or_else(|| ThothError::StateError {
+                reason: format!("Edited record {} is out of bounds", record_index),
+            })?;
+        copy_range(&mut reader, &mut writer, pos, start).map_err(write_error)?;
+        serde_json::to_writer(&mut writer, value).map_err(|e| write_error(e.into()))?;
+        pos = end;
+    }
+    copy_range(&mut reader, &mut writer, pos, source_len).map_err(write_error)?;
+    writer.flush().map_err(write_error)?;
+    drop(writer);
+
+    let permissions = fs::metadata(output).map(|meta| meta.permissions());
+    tmp.persist(output).map_err(|e| write_error(e.error))?;
+    if let Ok(permissions) = permissions {
+        let _ = fs::set_permissions(output, permissions);
+    }
+    Ok(())
+}
+
+/// Rewrite the record at `idx` in `output` with `value` and update the index
+///
+/// The file is rewritten through [`rewrite_spans`], then `file` is reopened on
+/// the new contents and the spans after `idx` are shifted by the change in the
+/// record's length, so nothing has to be re-indexed.
+pub(crate) fn replace_span(
+    file: &mut File,
+    spans: &mut [(u64, u64)],
+    idx: usize,
+    value: Value,
+    output: &Path,
+) -> Result<()> {
+    let Some(&(start, end)) = spans.get(idx) else {
+        return Err(ThothError::InvalidJsonStructure {
+            reason: format!("Record index {} out of bounds", idx),
+        });
+    };
+    let new_end = start + serde_json::to_vec(&value)?.len() as u64;
+    rewrite_spans(file, spans, &BTreeMap::from([(idx, value)]), output)?;
+
+    *file = File::open(output).map_err(|e| ThothError::FileReadError {
+        path: output.to_path_buf(),
+        reason: format!("Failed to reopen after saving: {}", e),
+    })?;
+    spans[idx].1 = new_end;
+    for span in &mut spans[idx + 1..] {
+        span.0 = span.0 - end + new_end;
+        span.1 = span.1 - end + new_end;
+    }
+    Ok(())
+}
+
+/// Copy bytes `start..end` of `reader` to `writer`
+fn copy_range(
+    reader: &mut File,
+    writer: &mut impl Write,
+    start: u64,
+    end: u64,
+) -> std::io::Result<()> {
+    reader.seek(SeekFrom::Start(start))?;
+    let copied = std::io::copy(&mut reader.take(end - start), writer)?;
+    if copied < end - start {
+        return Err(std::io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_edited_value_keeps_json_types() {
+        assert_eq!(parse_edited_value("42"), json!(42));
+        assert_eq!(parse_edited_value("-1.5"), json!(-1.5));
+        assert_eq!(parse_edited_value("true"), json!(true));
+        assert_eq!(parse_edited_value("null"), Value::Null);
+        assert_eq!(parse_edited_value("hello"), json!("hello"));
+        assert_eq!(parse_edited_value("\"42\""), json!("42"));
+        assert_eq!(parse_edited_value("\"a \\\"b\\\"\""), json!("a \"b\""));
+        // Structured input is not expanded into objects
+        assert_eq!(parse_edited_value("{\"a\":1}"), json!("{\"a\":1}"));
+    }
+
+    #[test]
+    fn test_rewrite_spans_copies_untouched_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.json");
+        let text = "[\n  {\"a\": 1},\n  {\"a\": 2},\n  {\"a\": 3}\n]\n";
+        fs::write(&path, text).unwrap();
+        let spans = [(4, 12), (16, 24), (28, 36)];
+
+        let edits = BTreeMap::from([(1, json!({"a": "two"}))]);
+        rewrite_spans(&File::open(&path).unwrap(), &spans, &edits, &path).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "[\n  {\"a\": 1},\n  {\"a\":\"two\"},\n  {\"a\": 3}\n]\n"
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use crate::error::{Result, ThothError};
+use crate::file::loaders::FileType;
+use serde_json::Value;
+use std::{
+    fs,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+/// Stream every root record of `loader` to `output`, passing each through `transform`
+///
+/// Records are read and written one at a time, so exporting a multi-GB file
+/// never holds more than one record in memory. NDJSON sources are written as
+/// NDJSON, single-value sources as that value, and everything else as a JSON
+/// array. The output is written to a temp file next to `output` and only
+/// renamed into place once complete, so a failed export leaves no partial file.
+///
+/// Returns the number of records written.
+pub fn export_records(
+    loader: &mut FileType,
+    output: &Path,
+    mut transform: impl FnMut(usize, Value) -> Result<Value>,
+) -> Result<usize> {
+    let layout = match loader {
+        FileType::Ndjson(_) => Layout::Lines,
+        _ if loader.is_single_document() => Layout::Single,
+        _ => Layout::Array,
+    };
+
+    let total = loader.len();
+    write_via_temp(output, |writer| {
+        let write_error = write_error(output);
+        if layout == Layout::Array {
+            writer.write_all(b"[").map_err(write_error)?;
+        }
+        for record_index in 0..total {
+            let value = transform(record_index, loader.get(record_index)?)?;
+            if layout == Layout::Array && record_index > 0 {
+                writer.write_all(b",").map_err(write_error)?;
+            }
+            serde_json::to_writer(&mut *writer, &value).map_err(|e| write_error(e.into()))?;
+            if layout == Layout::Lines {
+                writer.write_all(b"\n").map_err(write_error)?;
+            }
+        }
+        if layout == Layout::Array {
+            writer.write_all(b"]").map_err(write_error)?;
+        }
+        Ok(())
+    })?;
+    Ok(total)
+}
+
+/// How exported records are laid out, whatever the source's format
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One compact record per line, each line ending in a newline
+    #[default]
+    Ndjson,
+    /// A JSON array on a single line
+    JsonArray,
+    /// A JSON array with every record indented on its own lines
+    JsonArrayPretty,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [
+        ExportFormat::Ndjson,
+        ExportFormat::JsonArray,
+        ExportFormat::JsonArrayPretty,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Ndjson => "NDJSON",
+            ExportFormat::JsonArray => "JSON array",
+            ExportFormat::JsonArrayPretty => "JSON array (pretty)",
+        }
+    }
+
+    /// File extension for the format, without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Ndjson => "ndjson",
+            ExportFormat::JsonArray | ExportFormat::JsonArrayPretty => "json",
+        }
+    }
+
+    /// The format `loader` stores its records in, if it is one of these
+    pub fn of_source(loader: &FileType) -> Option<Self> {
+        match loader {
+            FileType::Ndjson(_) => Some(ExportFormat::Ndjson),
+            FileType::JsonArray(_) => Some(ExportFormat::JsonArray),
+            _ => None,
+        }
+    }
+}
+
+/// Write the root records at `records` of `loader` to `output` as `format`
+///
+/// When `format` is the source's own, each record's bytes are copied as they
+/// are (unsaved edits included); otherwise every record is parsed and
+/// serialized again. Written through a temp file like [`export_records`].
+///
+/// Returns the number of records written.
+pub fn export_selected(
+    loader: &mut FileType,
+    records: &[usize],
+    output: &Path,
+    format: ExportFormat,
+) -> Result<usize> {
+    let raw = ExportFormat::of_source(loader) == Some(format);
+    write_via_temp(output, |writer| {
+        let write_error = write_error(output);
+        let (open, separator, close) = match format {
+            ExportFormat::Ndjson => ("", "", ""),
+            ExportFormat::JsonArray => ("[", ",", "]\n"),
+            ExportFormat::JsonArrayPretty if records.is_empty() => ("[", "", "]\n"),
+            ExportFormat::JsonArrayPretty => ("[\n  ", ",\n  ", "\n]\n"),
+        };
+        writer.write_all(open.as_bytes()).map_err(write_error)?;
+        for (i, &record_index) in records.iter().enumerate() {
+            if i > 0 {
+                writer
+                    .write_all(separator.as_bytes())
+                    .map_err(write_error)?;
+            }
+            if raw {
+                writer
+                    .write_all(&loader.raw_slice(record_index)?)
+                    .map_err(write_error)?;
+            } else {
+                let value = loader.get(record_index)?;
+                let text = match format {
+                    ExportFormat::JsonArrayPretty => {
+                        serde_json::to_string_pretty(&value).map(|text| text.replace('\n', "\n  "))
+                    }
+                    _ => serde_json::to_string(&value),
+                }
+                .map_err(|e| write_error(e.into()))?;
+                writer.write_all(text.as_bytes()).map_err(write_error)?;
+            }
+            if format == ExportFormat::Ndjson {
+                writer.write_all(b"\n").map_err(write_error)?;
+            }
+        }
+        writer.write_all(close.as_bytes()).map_err(write_error)?;
+        Ok(())
+    })?;
+    Ok(records.len())
+}
+
+fn write_error(output: &Path) -> impl Fn(std::io::Error) -> ThothError + Copy + '_ {
+    move |e| ThothError::FileReadError {
+        path: output.to_path_buf(),
+        reason: format!("Failed to write export: {}", e),
+    }
+}
+
+/// Run `write` against a temp file next to `output`, then move it into place
+///
+/// Nothing is left behind if `write` fails.
+fn write_via_temp(output: &Path, write: impl FnOnce(&mut dyn Write) -> Result<()>) -> Result<()> {
+    let write_error = write_error(output);
+    let dir = output
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let tmp = tempfile::NamedTempFile::new_in(dir).map_err(write_error)?;
+    let mut writer = BufWriter::new(tmp.as_file());
+    write(&mut writer)?;
+    writer.flush().map_err(write_error)?;
+    drop(writer);
+
+    tmp.persist(output).map_err(|e| write_error(e.error))?;
+    // Temp files are created owner-only; give the export normal permissions
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(output, fs::Permissions::from_mode(0o644));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// One record per line (NDJSON)
+    Lines,
+    /// The file's only record, written as-is
+    Single,
+    /// Records as the elements of a JSON array
+    Array,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::DEFAULT_MAX_FILE_SIZE_MB;
+    use crate::file::loaders::load_file_auto;
+
+    #[test]
+    fn test_export_keeps_ndjson_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("in.ndjson");
+        fs::write(&source, "{\"a\":1}\n{\"a\":2}\n").unwrap();
+        let (_, mut loader) = load_file_auto(&source, DEFAULT_MAX_FILE_SIZE_MB).unwrap();
+
+        let output = dir.path().join("out.ndjson");
+        let written = export_records(&mut loader, &output, |i, mut value| {
+            value["i"] = i.into();
+            Ok(value)
+        })
+        .unwrap();
+        assert_eq!(written, 2);
+        assert_eq!(
+            fs::read_to_string(&output).unwrap(),
+            "{\"a\":1,\"i\":0}\n{\"a\":2,\"i\":1}\n"
+        );
+    }
+
+    #[test]
+    fn test_export_writes_json_array() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("in.json");
+        fs::write(&source, "[{\"a\":1}, {\"a\":2}]").unwrap();
+        let (_, mut loader) = load_file_auto(&source, DEFAULT_MAX_FILE_SIZE_MB).unwrap();
+
+        let output = dir.path().join("out.json");
+        export_records(&mut loader, &output, |_, value| Ok(value)).unwrap();
+        let exported: Value = serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(exported, serde_json::json!([{"a": 1}, {"a": 2}]));
+    }
+
+    #[test]
+    fn test_failed_export_leaves_no_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("in.ndjson");
+        fs::write(&source, "{\"a\":1}\n{\"a\":2}\n").unwrap();
+        let (_, mut loader) = load_file_auto(&source, DEFAULT_MAX_FILE_SIZE_MB).unwrap();
+
+        let output = dir.path().join("out.ndjson");
+        let result = export_records(&mut loader, &output, |i, value| {
+            if i == 1 {
+                Err(ThothError::StateError {
+                    reason: "stop".to_string(),
+                })
+            } else {
+                Ok(value)
+            }
+        });
+        assert!(result.is_err());
+        assert!(!output.exists());
+    }
+
+    /// Export records 2 and 0 of `source_text` saved as `name` in each format
+    fn export_each_format(name: &str, source_text: &str) -> Vec<String> {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join(name);
+        fs::write(&source, source_text).unwrap();
+        let (_, mut loader) = load_file_auto(&source, DEFAULT_MAX_FILE_SIZE_MB).unwrap();
+
+        ExportFormat::ALL
+            .iter()
+            .map(|&format| {
+                let output = dir.path().join(format!("out.{}", format.extension()));
+                let written = export_selected(&mut loader, &[2, 0], &output, format).unwrap();
+                assert_eq!(written, 2);
+                fs::read_to_string(&output).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_export_selected_from_ndjson() {
+        let outputs = export_each_format("in.ndjson", "{\"a\": 1}\r\n{\"a\":2}\n{\"b\":[1, 2]}");
+        // Same format: the lines are copied as written, CRLF and missing
+        // trailing newline normalized
+        assert_eq!(outputs[0], "{\"b\":[1, 2]}\n{\"a\": 1}\n");
+        assert_eq!(outputs[1], "[{\"b\":[1,2]},{\"a\":1}]\n");
+        assert_eq!(
+            outputs[2],
+            "[\n  {\n    \"b\": [\n      1,\n      2\n    ]\n  },\n  {\n    \"a\": 1\n  }\n]\n"
+        );
+    }
+
+    #[test]
+    fn test_export_selected_from_json_array() {
+        let outputs = export_each_format("in.json", "[{\"a\": 1}, {\"a\":2},\n {\"b\":\"x\"}]");
+        assert_eq!(outputs[0], "{\"b\":\"x\"}\n{\"a\":1}\n");
+        // Same format: the elements are copied as written
+        assert_eq!(outputs[1], "[{\"b\":\"x\"},{\"a\": 1}]\n");
+        assert_eq!(
+            outputs[2],
+            "[\n  {\n    \"b\": \"x\"\n  },\n  {\n    \"a\": 1\n  }\n]\n"
+        );
+        for output in &outputs[1..] {
+            let parsed: Value = serde_json::from_str(output).unwrap();
+            assert_eq!(parsed, serde_json::json!([{"b": "x"}, {"a": 1}]));
+        }
+    }
+
+    #[test]
+    fn test_export_selected_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("in.ndjson");
+        fs::write(&source, "{\"a\":1}\n").unwrap();
+        let (_, mut loader) = load_file_auto(&source, DEFAULT_MAX_FILE_SIZE_MB).unwrap();
+
+        let expected = ["", "[]\n", "[]\n"];
+        for (format, expected) in ExportFormat::ALL.into_iter().zip(expected) {
+            let output = dir.path().join("out");
+            assert_eq!(
+                export_selected(&mut loader, &[], &output, format).unwrap(),
+                0
+            );
+            assert_eq!(fs::read_to_string(&output).unwrap(), expected);
+        }
+    }
+}
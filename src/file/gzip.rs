@@ -0,0 +1,187 @@
+use crate::error::{Result, ThothError};
+use flate2::read::MultiGzDecoder;
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs::{self, File},
+    hash::{Hash, Hasher},
+    io::{self, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Returns true if the path has a `.gz` extension
+pub fn is_gzip_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gz"))
+}
+
+/// Path with the trailing `.gz` removed (`data.ndjson.gz` -> `data.ndjson`)
+pub fn inner_path(path: &Path) -> PathBuf {
+    path.with_extension("")
+}
+
+/// Decompress a `.gz` file into the temp directory and return the path of
+/// the uncompressed copy.
+///
+/// The copy keeps the inner extension so type sniffing behaves as it would
+/// for the plain file, and it is keyed by the source path, size and mtime so
+/// re-opening the same file (e.g. for a search scan) reuses it. Fails with
+/// [`ThothError::FileTooLarge`] once the output, or the reused copy, exceeds
+/// `limit_mb` megabytes. Copies stay until [`clear_temp_copies`] runs.
+pub fn decompress_to_temp(path: &Path, limit_mb: u64) -> Result<PathBuf> {
+    let dir = temp_copies_dir();
+    fs::create_dir_all(&dir)?;
+
+    let target = dir.join(cache_file_name(path)?);
+    if target.is_file() {
+        let cached_len = fs::metadata(&target)?.len();
+        if cached_len > limit_mb.saturating_mul(BYTES_PER_MB) {
+            return Err(ThothError::FileTooLarge {
+                path: path.to_path_buf(),
+                limit_mb,
+            });
+        }
+        return Ok(target);
+    }
+
+    // Write to a scratch file first so a concurrent reader never sees a partial copy
+    let mut scratch = tempfile::NamedTempFile::new_in(&dir)?;
+    decompress_with_limit(File::open(path)?, scratch.as_file_mut(), limit_mb).map_err(
+        |e| match e {
+            ThothError::FileTooLarge { limit_mb, .. } => ThothError::FileTooLarge {
+                path: path.to_path_buf(),
+                limit_mb,
+            },
+            other => ThothError::FileReadError {
+                path: path.to_path_buf(),
+                reason: format!("Failed to decompress gzip data: {}", other),
+            },
+        },
+    )?;
+    scratch
+        .persist(&target)
+        .map_err(|e| ThothError::FileReadError {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?;
+
+    Ok(target)
+}
+
+/// Remove the decompressed copies left by [`decompress_to_temp`]
+///
+/// Called when the app starts and when it exits. Copies still open
+/// elsewhere are skipped where the platform refuses to remove them.
+pub fn clear_temp_copies() {
+    let Ok(entries) = fs::read_dir(temp_copies_dir()) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let _ = fs::remove_file(entry.path());
+    }
+}
+
+fn temp_copies_dir() -> PathBuf {
+    std::env::temp_dir().join("thoth-gz")
+}
+
+/// Stream `input` through a gzip decoder into `output`, stopping as soon as
+/// more than `limit_mb` megabytes have been produced.
+fn decompress_with_limit(
+    input: impl Read,
+    output: &mut impl io::Write,
+    limit_mb: u64,
+) -> Result<u64> {
+    let limit = limit_mb.saturating_mul(BYTES_PER_MB);
+    let decoder = MultiGzDecoder::new(BufReader::new(input));
+    let written = io::copy(&mut decoder.take(limit.saturating_add(1)), output)?;
+    if written > limit {
+        return Err(ThothError::FileTooLarge {
+            path: PathBuf::new(),
+            limit_mb,
+        });
+    }
+    output.flush()?;
+    Ok(written)
+}
+
+fn cache_file_name(path: &Path) -> Result<String> {
+    let meta = fs::metadata(path)?;
+    let mut hasher = DefaultHasher::new();
+    crate::platform::canonical_path(path).hash(&mut hasher);
+    meta.len().hash(&mut hasher);
+    meta.modified().ok().hash(&mut hasher);
+
+    let name = format!("{:016x}", hasher.finish());
+    Ok(match inner_path(path).extension() {
+        Some(ext) => format!("{}.{}", name, ext.to_string_lossy()),
+        None => name,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{Compression, write::GzEncoder};
+    use std::io::Write;
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_is_gzip_path() {
+        assert!(is_gzip_path(Path::new("logs.ndjson.gz")));
+        assert!(is_gzip_path(Path::new("DATA.JSON.GZ")));
+        assert!(!is_gzip_path(Path::new("data.json")));
+        assert_eq!(inner_path(Path::new("a/b.json.gz")), Path::new("a/b.json"));
+    }
+
+    #[test]
+    fn test_decompress_with_limit_round_trip() {
+        let data = b"{\"a\":1}\n{\"a\":2}\n";
+        let mut out = Vec::new();
+        let written = decompress_with_limit(&gzip(data)[..], &mut out, 1).unwrap();
+        assert_eq!(written, data.len() as u64);
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    fn test_decompress_with_limit_rejects_oversized_output() {
+        // Highly compressible, so the archive itself is tiny
+        let data = vec![b' '; (BYTES_PER_MB + 1) as usize];
+        let mut out = Vec::new();
+        let err = decompress_with_limit(&gzip(&data)[..], &mut out, 1).unwrap_err();
+        assert!(matches!(err, ThothError::FileTooLarge { limit_mb: 1, .. }));
+    }
+
+    #[test]
+    fn test_decompress_to_temp_keeps_inner_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.ndjson.gz");
+        fs::write(&path, gzip(b"{\"id\":1}\n{\"id\":2}\n")).unwrap();
+
+        let out = decompress_to_temp(&path, 1).unwrap();
+        assert_eq!(out.extension().unwrap(), "ndjson");
+        assert_eq!(fs::read(&out).unwrap(), b"{\"id\":1}\n{\"id\":2}\n");
+
+        // Re-opening the same archive reuses the cached copy
+        assert_eq!(decompress_to_temp(&path, 1).unwrap(), out);
+        // ...unless it is over the limit of the new open
+        let err = decompress_to_temp(&path, 0).unwrap_err();
+        assert!(matches!(err, ThothError::FileTooLarge { limit_mb: 0, .. }));
+    }
+
+    #[test]
+    fn test_decompress_to_temp_rejects_corrupt_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("broken.json.gz");
+        fs::write(&path, b"not gzip at all").unwrap();
+
+        let err = decompress_to_temp(&path, 1).unwrap_err();
+        assert!(matches!(err, ThothError::FileReadError { .. }));
+    }
+}
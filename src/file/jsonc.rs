@@ -0,0 +1,118 @@
+/// Blank out the `//` and `/* */` comments and trailing commas of JSON with
+/// comments (JSONC), as written by VS Code and many config files, so
+/// serde_json can parse it
+///
+/// They are replaced with spaces (line breaks inside block comments are
+/// kept), so byte offsets, line numbers and parse error positions still
+/// match the source.
+///
+/// Returns `None` when `src` has nothing to strip, e.g. it is strict JSON
+/// already or is broken in some other way.
+pub fn strip_jsonc(src: &[u8]) -> Option<Vec<u8>> {
+    let mut out = src.to_vec();
+    let mut stripped = false;
+    // Last comma with only whitespace or comments after it so far
+    let mut pending_comma: Option<usize> = None;
+    let mut i = 0;
+    while i < out.len() {
+        match out[i] {
+            b'"' => {
+                pending_comma = None;
+                i += 1;
+                while i < out.len() && out[i] != b'"' {
+                    i += if out[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            b'/' if out.get(i + 1) == Some(&b'/') => {
+                while i < out.len() && out[i] != b'\n' {
+                    out[i] = b' ';
+                    i += 1;
+                }
+                stripped = true;
+            }
+            b'/' if out.get(i + 1) == Some(&b'*') => {
+                let end = out[i + 2..]
+                    .windows(2)
+                    .position(|w| w == b"*/")
+                    .map_or(out.len(), |at| i + 2 + at + 2);
+                for byte in &mut out[i..end] {
+                    if !matches!(*byte, b'\n' | b'\r') {
+                        *byte = b' ';
+                    }
+                }
+                stripped = true;
+                i = end;
+            }
+            b',' => {
+                pending_comma = Some(i);
+                i += 1;
+            }
+            b'}' | b']' => {
+                if let Some(comma) = pending_comma.take() {
+                    out[comma] = b' ';
+                    stripped = true;
+                }
+                i += 1;
+            }
+            b' ' | b'\t' | b'\n' | b'\r' => i += 1,
+            _ => {
+                pending_comma = None;
+                i += 1;
+            }
+        }
+    }
+    stripped.then_some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    fn parse(src: &str) -> Value {
+        let stripped = strip_jsonc(src.as_bytes()).expect("something to strip");
+        assert_eq!(stripped.len(), src.len());
+        serde_json::from_slice(&stripped).unwrap()
+    }
+
+    #[test]
+    fn test_strips_comments_and_trailing_commas() {
+        let src = r#"{
+            // Editor settings
+            "editor.fontSize": 14, /* points */
+            "files.exclude": {
+                "**/.git": true,
+            },
+            "list": [1, 2, /* last */],
+        }"#;
+        assert_eq!(
+            parse(src),
+            json!({"editor.fontSize": 14, "files.exclude": {"**/.git": true}, "list": [1, 2]})
+        );
+    }
+
+    #[test]
+    fn test_keeps_comment_markers_inside_strings() {
+        let value = parse(r#"{"url": "http://a/*b*/", "q": "\"//\"", /* c */ "n": 1}"#);
+        assert_eq!(value["url"], "http://a/*b*/");
+        assert_eq!(value["q"], "\"//\"");
+        assert_eq!(value["n"], 1);
+    }
+
+    #[test]
+    fn test_keeps_line_numbers() {
+        let stripped = strip_jsonc(b"{\n/* a\nb */ \"x\": 1,\n}").unwrap();
+        assert_eq!(
+            String::from_utf8(stripped).unwrap(),
+            "{\n    \n     \"x\": 1 \n}"
+        );
+    }
+
+    #[test]
+    fn test_strict_json_has_nothing_to_strip() {
+        assert_eq!(strip_jsonc(br#"{"a": [1, 2], "b": "//"}"#), None);
+        // A comma between values isn't trailing
+        assert_eq!(strip_jsonc(b"[1,\n 2]"), None);
+    }
+}
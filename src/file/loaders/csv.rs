@@ -0,0 +1,353 @@
+use crate::error::{Result, ThothError};
+use crate::file::detect_file_type::sniff_csv_delimiter;
+use crate::file::loaders::FileLoader;
+use crate::platform::FileIO;
+use anyhow::Context;
+use serde_json::{Map, Value};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+/// Lazy loader for CSV/TSV files
+///
+/// Indexes record boundaries during initialization (respecting quoted fields
+/// that span lines) and parses individual rows on demand. Each row is exposed
+/// as a JSON object keyed by the header names, or by positional keys
+/// (`col0`, `col1`, …) when the file has no header row.
+pub struct CsvFile {
+    file: File,
+    delimiter: u8,
+    headers: Vec<String>,
+    // (start, end) byte offsets for each data row (end is exclusive, line terminator stripped)
+    row_spans: Vec<(u64, u64)>,
+}
+
+impl CsvFile {
+    /// Open a CSV file, detect its delimiter and header, and index all rows
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path).with_context(|| "open CSV")?;
+
+        let mut spans = index_records(&file)?;
+        let first = match spans.first() {
+            Some(&(start, end)) => read_span(&file, start, end)?,
+            None => Vec::new(),
+        };
+
+        let is_tsv = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("tsv"));
+        let delimiter = sniff_csv_delimiter(&String::from_utf8_lossy(&first))
+            .unwrap_or(if is_tsv { b'\t' } else { b',' });
+
+        let first_fields = parse_record(&first, delimiter);
+        let headers = if !spans.is_empty() && looks_like_header(&first_fields) {
+            spans.remove(0);
+            first_fields
+        } else {
+            (0..first_fields.len()).map(positional_key).collect()
+        };
+
+        Ok(Self {
+            file,
+            delimiter,
+            headers,
+            row_spans: spans,
+        })
+    }
+
+    /// Returns the number of data rows (excluding the header)
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.row_spans.len()
+    }
+
+    /// Column names used as object keys
+    #[cfg(test)]
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    /// Get the row at the specified index as a JSON object
+    ///
+    /// This performs a position-independent read and is safe for parallel access.
+    pub fn get(&mut self, idx: usize) -> Result<Value> {
+        self.record(idx)
+    }
+
+    /// Get the row at the specified index serialized as JSON bytes
+    ///
+    /// Search treats raw records as JSON, so rows are handed out in their
+    /// JSON form rather than as the original CSV text.
+    pub fn raw_record(&self, idx: usize) -> Result<Vec<u8>> {
+        let value = self.record(idx)?;
+        serde_json::to_vec(&value).map_err(|e| ThothError::InvalidJsonStructure {
+            reason: format!("Failed to serialize CSV row {}: {}", idx, e),
+        })
+    }
+
+    fn record(&self, idx: usize) -> Result<Value> {
+        let (start, end) =
+            *self
+                .row_spans
+                .get(idx)
+                .ok_or_else(|| ThothError::InvalidJsonStructure {
+                    reason: format!("CSV row index {} out of bounds", idx),
+                })?;
+        let bytes = read_span(&self.file, start, end)?;
+        let fields = parse_record(&bytes, self.delimiter);
+
+        let mut row = Map::with_capacity(self.headers.len().max(fields.len()));
+        for (i, field) in fields.into_iter().enumerate() {
+            let key = self
+                .headers
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| positional_key(i));
+            row.insert(key, Value::String(field));
+        }
+        // Short rows still expose every column
+        for header in self.headers.iter().skip(row.len()) {
+            row.insert(header.clone(), Value::Null);
+        }
+        Ok(Value::Object(row))
+    }
+}
+
+impl FileLoader for CsvFile {
+    type Item = Value;
+
+    fn open(path: &Path) -> Result<Self> {
+        CsvFile::open(path)
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn get(&mut self, idx: usize) -> Result<Self::Item> {
+        self.get(idx)
+    }
+
+    fn raw_bytes(&self, idx: usize) -> Result<Vec<u8>> {
+        self.raw_record(idx)
+    }
+}
+
+/// Build (start, end) spans for every non-empty record in a single streaming pass.
+///
+/// A newline only ends a record when it is outside double quotes, so quoted
+/// fields may contain line breaks. Quote state is tracked by toggling on every
+/// `"`, which also handles escaped `""` pairs.
+fn index_records(file: &File) -> Result<Vec<(u64, u64)>> {
+    let mut spans = Vec::new();
+    let mut reader = BufReader::new(file.try_clone()?);
+    let mut pos: u64 = 0;
+    let mut record_start: u64 = 0;
+    let mut in_quotes = false;
+    let mut buf = Vec::with_capacity(8 * 1024);
+
+    loop {
+        buf.clear();
+        let n = reader.read_until(b'\n', &mut buf)?;
+        if n == 0 {
+            break;
+        }
+        if pos == 0 && buf.starts_with(&[0xEF, 0xBB, 0xBF]) {
+            record_start = 3;
+        }
+        pos += n as u64;
+
+        let quotes = buf.iter().filter(|&&b| b == b'"').count();
+        if quotes % 2 == 1 {
+            in_quotes = !in_quotes;
+        }
+        if in_quotes {
+            continue;
+        }
+
+        let mut end = pos;
+        if buf.last() == Some(&b'\n') {
+            end -= 1;
+            if buf.len() >= 2 && buf[buf.len() - 2] == b'\r' {
+                end -= 1;
+            }
+        }
+        if end > record_start {
+            spans.push((record_start, end));
+        }
+        record_start = pos;
+    }
+
+    // Unterminated quote at EOF: keep what we have as the last record
+    if in_quotes && pos > record_start {
+        spans.push((record_start, pos));
+    }
+
+    Ok(spans)
+}
+
+fn read_span(file: &File, start: u64, end: u64) -> Result<Vec<u8>> {
+    let mut buf = vec![0u8; (end - start) as usize];
+    file.read_at(&mut buf, start)?;
+    Ok(buf)
+}
+
+/// Split one record into fields (RFC 4180 quoting: `"a,b"`, `"say ""hi"""`).
+fn parse_record(bytes: &[u8], delimiter: u8) -> Vec<String> {
+    let text = String::from_utf8_lossy(bytes);
+    let delimiter = delimiter as char;
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut at_field_start = true;
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if in_quotes {
+            if ch == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(ch);
+            }
+        } else if ch == delimiter {
+            fields.push(std::mem::take(&mut field));
+            at_field_start = true;
+            continue;
+        } else if ch == '"' && at_field_start {
+            in_quotes = true;
+        } else {
+            field.push(ch);
+        }
+        at_field_start = false;
+    }
+    fields.push(field);
+    fields
+}
+
+/// Header heuristic: every cell is a non-empty, non-numeric, unique label.
+fn looks_like_header(fields: &[String]) -> bool {
+    let mut seen = std::collections::HashSet::new();
+    fields.iter().all(|f| {
+        let f = f.trim();
+        !f.is_empty() && f.parse::<f64>().is_err() && seen.insert(f)
+    })
+}
+
+fn positional_key(i: usize) -> String {
+    format!("col{}", i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_csv_basic_loading() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"id,name\n1,Alice\n2,Bob\n").unwrap();
+        let mut loader = CsvFile::open(file.path()).unwrap();
+        assert_eq!(loader.len(), 2);
+        assert_eq!(loader.headers(), ["id", "name"]);
+
+        let val = loader.get(0).unwrap();
+        assert_eq!(val["id"], "1");
+        assert_eq!(val["name"], "Alice");
+        assert_eq!(loader.get(1).unwrap()["name"], "Bob");
+    }
+
+    #[test]
+    fn test_csv_quoted_fields_with_commas_and_newlines() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"id,comment,tag\r\n1,\"hello, world\",a\r\n2,\"line one\nline two\",b\r\n3,\"say \"\"hi\"\"\",c\r\n").unwrap();
+        let mut loader = CsvFile::open(file.path()).unwrap();
+        assert_eq!(loader.len(), 3);
+        assert_eq!(loader.get(0).unwrap()["comment"], "hello, world");
+        assert_eq!(loader.get(1).unwrap()["comment"], "line one\nline two");
+        assert_eq!(loader.get(1).unwrap()["tag"], "b");
+        assert_eq!(loader.get(2).unwrap()["comment"], "say \"hi\"");
+        assert_eq!(loader.get(2).unwrap()["tag"], "c");
+    }
+
+    #[test]
+    fn test_csv_without_header_uses_positional_keys() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"1,Alice,30\n2,Bob,25\n").unwrap();
+        let mut loader = CsvFile::open(file.path()).unwrap();
+        assert_eq!(loader.len(), 2);
+        let val = loader.get(0).unwrap();
+        assert_eq!(val["col0"], "1");
+        assert_eq!(val["col1"], "Alice");
+        assert_eq!(val["col2"], "30");
+    }
+
+    #[test]
+    fn test_csv_ragged_rows() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"a,b,c\n1,2\n1,2,3,4\n").unwrap();
+        let mut loader = CsvFile::open(file.path()).unwrap();
+        let short = loader.get(0).unwrap();
+        assert_eq!(short["b"], "2");
+        assert_eq!(short["c"], Value::Null);
+        let long = loader.get(1).unwrap();
+        assert_eq!(long["c"], "3");
+        assert_eq!(long["col3"], "4");
+    }
+
+    #[test]
+    fn test_csv_detects_other_delimiters() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"id;name\n1;Alice\n").unwrap();
+        let mut loader = CsvFile::open(file.path()).unwrap();
+        assert_eq!(loader.get(0).unwrap()["name"], "Alice");
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"id\tname\n1\tAlice, Jr.\n").unwrap();
+        let mut loader = CsvFile::open(file.path()).unwrap();
+        assert_eq!(loader.get(0).unwrap()["name"], "Alice, Jr.");
+    }
+
+    #[test]
+    fn test_csv_skips_bom_and_blank_lines() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all("\u{feff}id,name\n\n1,Alice\n\n".as_bytes())
+            .unwrap();
+        let mut loader = CsvFile::open(file.path()).unwrap();
+        assert_eq!(loader.len(), 1);
+        assert_eq!(loader.headers(), ["id", "name"]);
+        assert_eq!(loader.get(0).unwrap()["id"], "1");
+    }
+
+    #[test]
+    fn test_csv_raw_bytes_are_json() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"id,name\n1,Alice\n").unwrap();
+        let loader = CsvFile::open(file.path()).unwrap();
+        let raw = loader.raw_record(0).unwrap();
+        let parsed: Value = serde_json::from_slice(&raw).unwrap();
+        assert_eq!(parsed["name"], "Alice");
+    }
+
+    #[test]
+    fn test_csv_out_of_bounds() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"id\n1\n").unwrap();
+        let mut loader = CsvFile::open(file.path()).unwrap();
+        assert!(loader.get(1).is_err());
+    }
+
+    #[test]
+    fn test_csv_empty_file() {
+        let file = NamedTempFile::new().unwrap();
+        let loader = CsvFile::open(file.path()).unwrap();
+        assert_eq!(loader.len(), 0);
+    }
+}
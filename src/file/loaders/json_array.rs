@@ -1,9 +1,16 @@
+use crate::constants::OPEN_PROGRESS_STEP_BYTES;
 use crate::error::{Result, ThothError};
+use crate::file::detect_file_type::UTF8_BOM;
+use crate::file::edits;
 use crate::file::loaders::FileLoader;
+use crate::file::open_progress::OpenProgress;
 use crate::platform::FileIO;
 use anyhow::Context;
 use serde_json::Value;
-use std::{fs::File, io::Read, path::Path};
+use std::{collections::BTreeMap, fs::File, io::Read, path::Path};
+
+// Read size used when counting lines before an element
+const LINE_COUNT_CHUNK_BYTES: usize = 1 << 20;
 
 /// Lazy loader for JSON files containing a top-level array
 ///
@@ -13,6 +20,8 @@ use std::{fs::File, io::Read, path::Path};
 pub struct JsonArrayFile {
     file: File,
     element_spans: Vec<(u64, u64)>, // (start, end) exclusive
+    // Elements edited in memory but not yet saved, by array index
+    edits: BTreeMap<usize, Value>,
 }
 
 impl JsonArrayFile {
@@ -21,21 +30,42 @@ impl JsonArrayFile {
     /// This reads the entire file to build an index of element spans,
     /// which allows for efficient random access later without re-parsing.
     pub fn open(path: &Path) -> Result<Self> {
+        Self::open_with_progress(path, &OpenProgress::default())
+    }
+
+    /// [`open`](Self::open), reporting progress and stopping once it is canceled
+    ///
+    /// Reading the file and indexing it each count for half of the progress.
+    pub fn open_with_progress(path: &Path, progress: &OpenProgress) -> Result<Self> {
         let mut file = File::open(path).with_context(|| "open JSON")?;
-        let mut buf = Vec::new();
-        file.read_to_end(&mut buf)?;
+        let file_len = file.metadata()?.len();
+        progress.set_total(file_len.saturating_mul(2));
+
+        let mut buf = Vec::with_capacity(file_len as usize);
+        let mut chunk = vec![0u8; OPEN_PROGRESS_STEP_BYTES as usize];
+        loop {
+            progress.check(path)?;
+            let n = file.read(&mut chunk)?;
+            if n == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+            progress.advance(n as u64);
+        }
 
         // Find the top-level array and index each element span without parsing it.
-        let spans =
-            index_json_array_elements(&buf).map_err(|e| ThothError::InvalidJsonStructure {
-                reason: format!("failed to index top-level array: {}", e),
-            })?;
+        let spans = index_json_array_elements(&buf, progress);
+        progress.check(path)?;
+        let spans = spans.map_err(|e| ThothError::InvalidJsonStructure {
+            reason: format!("failed to index top-level array: {}", e),
+        })?;
 
         // Keep the file for later slice reads
         let file = File::open(path)?;
         Ok(Self {
             file,
             element_spans: spans,
+            edits: BTreeMap::new(),
         })
     }
 
@@ -49,6 +79,9 @@ impl JsonArrayFile {
     ///
     /// This performs a position-independent read and is safe for parallel access.
     pub fn get(&mut self, idx: usize) -> Result<Value> {
+        if let Some(value) = self.edits.get(&idx) {
+            return Ok(value.clone());
+        }
         let (start, end) =
             *self
                 .element_spans
@@ -69,6 +102,9 @@ impl JsonArrayFile {
     ///
     /// This performs a position-independent read and is safe for parallel access.
     pub fn raw_element(&self, idx: usize) -> Result<Vec<u8>> {
+        if let Some(value) = self.edits.get(&idx) {
+            return Ok(serde_json::to_vec(value)?);
+        }
         let (start, end) =
             *self
                 .element_spans
@@ -82,16 +118,93 @@ impl JsonArrayFile {
 
         Ok(buf)
     }
+
+    /// 1-based line number the element at `idx` starts on in the source file
+    ///
+    /// Counts the newlines before the element's first byte, reading the file
+    /// in chunks, so it costs one pass over everything before the element.
+    pub fn line_number(&self, idx: usize) -> Result<usize> {
+        let &(start, _) =
+            self.element_spans
+                .get(idx)
+                .ok_or_else(|| ThothError::InvalidJsonStructure {
+                    reason: format!("Element index {} out of bounds", idx),
+                })?;
+        let mut buf = vec![0u8; LINE_COUNT_CHUNK_BYTES.min(start as usize)];
+        let (mut offset, mut newlines) = (0u64, 0usize);
+        while offset < start {
+            let len = buf.len().min((start - offset) as usize);
+            let n = self.file.read_at(&mut buf[..len], offset)?;
+            if n == 0 {
+                break;
+            }
+            newlines += memchr::memchr_iter(b'\n', &buf[..n]).count();
+            offset += n as u64;
+        }
+        Ok(newlines + 1)
+    }
+
+    /// Replace the element at `idx` in memory; the file is untouched until [`Self::save`]
+    pub fn set(&mut self, idx: usize, value: Value) -> Result<()> {
+        if idx >= self.element_spans.len() {
+            return Err(ThothError::InvalidJsonStructure {
+                reason: format!("Element index {} out of bounds", idx),
+            });
+        }
+        self.edits.insert(idx, value);
+        Ok(())
+    }
+
+    /// Number of elements edited since the file was opened or last saved
+    pub fn dirty_count(&self) -> usize {
+        self.edits.len()
+    }
+
+    /// Drop all unsaved edits
+    pub fn discard_edits(&mut self) {
+        self.edits.clear();
+    }
+
+    /// Rewrite the element at `idx` in `path` right away
+    ///
+    /// Separators, indentation and other elements are copied unchanged and
+    /// other unsaved edits stay pending; the element index is shifted rather
+    /// than rebuilt.
+    pub fn replace(&mut self, idx: usize, value: Value, path: &Path) -> Result<()> {
+        edits::replace_span(&mut self.file, &mut self.element_spans, idx, value, path)?;
+        self.edits.remove(&idx);
+        Ok(())
+    }
+
+    /// Write the edited elements back to `path` and reopen it
+    ///
+    /// Only the edited elements are re-serialized; every other byte is copied
+    /// from the current file.
+    pub fn save(&mut self, path: &Path) -> Result<usize> {
+        let saved = self.edits.len();
+        if saved == 0 {
+            return Ok(0);
+        }
+        edits::rewrite_spans(&self.file, &self.element_spans, &self.edits, path)?;
+        *self = Self::open(path)?;
+        Ok(saved)
+    }
 }
 
 /// Index the boundaries of elements in a top-level JSON array
 ///
 /// This function scans through a JSON array without fully parsing it,
 /// recording the byte positions where each element starts and ends.
-/// This enables efficient random access to array elements.
-fn index_json_array_elements(bytes: &[u8]) -> Result<Vec<(u64, u64)>> {
-    // Skip leading whitespace
-    let mut i = skip_ws(bytes, 0).ok_or_else(|| ThothError::InvalidJsonStructure {
+/// This enables efficient random access to array elements. Scanning stops
+/// early once `progress` is canceled, leaving the spans incomplete.
+fn index_json_array_elements(bytes: &[u8], progress: &OpenProgress) -> Result<Vec<(u64, u64)>> {
+    // Skip a byte order mark and leading whitespace
+    let start = if bytes.starts_with(UTF8_BOM) {
+        UTF8_BOM.len()
+    } else {
+        0
+    };
+    let mut i = skip_ws(bytes, start).ok_or_else(|| ThothError::InvalidJsonStructure {
         reason: "empty file".to_string(),
     })?;
     if bytes.get(i) != Some(&b'[') {
@@ -107,8 +220,17 @@ fn index_json_array_elements(bytes: &[u8]) -> Result<Vec<(u64, u64)>> {
     let mut depth: i32 = 0; // depth relative to inside the array
     let mut elem_start: Option<u64> = None;
     let mut want_new_elem = true;
+    let step = OPEN_PROGRESS_STEP_BYTES as usize;
+    let mut reported = 0;
 
     while i < bytes.len() {
+        if i - reported >= step {
+            progress.advance((i - reported) as u64);
+            reported = i;
+            if progress.is_canceled() {
+                break;
+            }
+        }
         let b = bytes[i];
 
         if in_string {
@@ -160,6 +282,12 @@ fn index_json_array_elements(bytes: &[u8]) -> Result<Vec<(u64, u64)>> {
                     want_new_elem = true;
                 }
             }
+            // Only JSONC has anything outside strings starting with '/'
+            b'/' => {
+                return Err(ThothError::InvalidJsonStructure {
+                    reason: "comment between array elements".to_string(),
+                });
+            }
             c if is_ws(c) => { /* skip */ }
             _ => {
                 if want_new_elem {
@@ -172,6 +300,8 @@ fn index_json_array_elements(bytes: &[u8]) -> Result<Vec<(u64, u64)>> {
         i += 1;
     }
 
+    progress.advance((bytes.len() - reported) as u64);
+
     // Minimal sanity check: we should have encountered a ']'
     if !bytes.contains(&b']') {
         return Err(ThothError::InvalidJsonStructure {
@@ -308,6 +438,15 @@ mod tests {
         assert_eq!(val["id"], 2);
     }
 
+    #[test]
+    fn test_json_array_rejects_comments_between_elements() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "[\n {{\"a\":1}}, // c\n {{\"b\":2}}\n]").unwrap();
+        file.flush().unwrap();
+
+        assert!(JsonArrayFile::open(file.path()).is_err());
+    }
+
     #[test]
     fn test_json_array_out_of_bounds() {
         let mut file = NamedTempFile::new().unwrap();
@@ -332,6 +471,27 @@ mod tests {
         assert!(s.contains("1"));
     }
 
+    #[test]
+    fn test_json_array_open_progress() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, r#"[{{"id":1}},{{"id":2}}]"#).unwrap();
+        file.flush().unwrap();
+        let len = file.as_file().metadata().unwrap().len();
+
+        // Reading and indexing each count once
+        let progress = OpenProgress::default();
+        let loader = JsonArrayFile::open_with_progress(file.path(), &progress).unwrap();
+        assert_eq!(loader.len(), 2);
+        assert_eq!(progress.bytes(), (2 * len, Some(2 * len)));
+
+        let progress = OpenProgress::default();
+        progress.cancel();
+        assert!(matches!(
+            JsonArrayFile::open_with_progress(file.path(), &progress),
+            Err(ThothError::OpenCanceled { .. })
+        ));
+    }
+
     #[test]
     fn test_json_array_fileloader_trait() {
         let mut file = NamedTempFile::new().unwrap();
@@ -347,4 +507,50 @@ mod tests {
         let val = loader.get(0).unwrap();
         assert_eq!(val["id"], 1);
     }
+
+    #[test]
+    fn test_json_array_save_replaces_edited_elements() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "[\n  {{\"id\": 1}},\n  {{\"id\": 2}}\n]\n").unwrap();
+
+        let mut loader = JsonArrayFile::open(file.path()).unwrap();
+        loader.set(0, serde_json::json!({"id": "one"})).unwrap();
+        assert_eq!(loader.raw_element(0).unwrap(), br#"{"id":"one"}"#);
+
+        loader.save(file.path()).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(file.path()).unwrap(),
+            "[\n  {\"id\":\"one\"},\n  {\"id\": 2}\n]\n"
+        );
+        assert_eq!(loader.len(), 2);
+        assert_eq!(loader.get(0).unwrap()["id"], "one");
+    }
+
+    #[test]
+    fn test_json_array_replace_keeps_formatting() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "[\n  {{\"id\": 1}},\n  {{\"id\": 2}},\n  {{\"id\": 3}}\n]\n"
+        )
+        .unwrap();
+
+        let mut loader = JsonArrayFile::open(file.path()).unwrap();
+        loader
+            .replace(
+                0,
+                serde_json::json!({"id": "one", "tags": ["a"]}),
+                file.path(),
+            )
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(file.path()).unwrap(),
+            "[\n  {\"id\":\"one\",\"tags\":[\"a\"]},\n  {\"id\": 2},\n  {\"id\": 3}\n]\n"
+        );
+
+        let reopened = JsonArrayFile::open(file.path()).unwrap();
+        assert_eq!(loader.element_spans, reopened.element_spans);
+        assert_eq!(loader.raw_element(2).unwrap(), br#"{"id": 3}"#);
+        assert_eq!(loader.line_number(2).unwrap(), 4);
+    }
 }
@@ -1,17 +1,36 @@
+mod csv;
 mod json_array;
 mod ndjson;
+mod parquet;
 mod single;
+mod toml;
+mod xml;
+mod yaml;
 
+pub use csv::CsvFile;
 pub use json_array::JsonArrayFile;
 pub use ndjson::NdjsonFile;
+pub use parquet::ParquetFile;
 pub use single::SingleValueFile;
-
-use crate::error::Result;
-use crate::file::detect_file_type::DetectedFileType;
+pub use toml::TomlFile;
+pub use xml::XmlFile;
+pub use yaml::YamlFile;
+
+use crate::constants::DEFAULT_MAX_FILE_SIZE_MB;
+use crate::error::{Result, ThothError};
+use crate::file::detect_file_type::{
+    DetectedFileType, Detection, detect_file_type, sniff_text_format,
+};
+use crate::file::jsonc::strip_jsonc;
+use crate::file::open_progress::OpenProgress;
+use crate::helpers::matches_pattern;
 use crate::plugin::wasm_file_viewer_loader::{DisplayMode, WasmFileViewerLoader};
 use crate::plugin::wasm_loader::WasmFileLoader;
+use crate::settings::FileTypeOverride;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::{LazyLock, RwLock};
 
 /// Common trait for all lazy file loaders.
 ///
@@ -43,11 +62,16 @@ pub trait FileLoader {
 /// A lightweight, `Copy` tag describing what kind of file is loaded.
 /// Used in window state, toolbar events, and status bar display.
 /// Does not hold any file handles.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub enum FileKind {
     #[default]
     Ndjson,
     Json,
+    Csv,
+    Yaml,
+    Toml,
+    Xml,
+    Parquet,
     Plugin,
     PluginTable,
 }
@@ -56,7 +80,14 @@ impl From<DetectedFileType> for FileKind {
     fn from(val: DetectedFileType) -> Self {
         match val {
             DetectedFileType::Ndjson => FileKind::Ndjson,
-            DetectedFileType::JsonArray | DetectedFileType::JsonObject => FileKind::Json,
+            DetectedFileType::JsonArray
+            | DetectedFileType::JsonObject
+            | DetectedFileType::Jsonc => FileKind::Json,
+            DetectedFileType::Csv => FileKind::Csv,
+            DetectedFileType::Yaml => FileKind::Yaml,
+            DetectedFileType::Toml => FileKind::Toml,
+            DetectedFileType::Xml => FileKind::Xml,
+            DetectedFileType::Parquet => FileKind::Parquet,
         }
     }
 }
@@ -69,6 +100,16 @@ pub enum FileType {
     Ndjson(NdjsonFile),
     JsonArray(JsonArrayFile),
     Single(SingleValueFile),
+    /// Delimiter-separated rows, each exposed as an object keyed by header.
+    Csv(CsvFile),
+    /// YAML stream, one record per document.
+    Yaml(YamlFile),
+    /// TOML document, exposed as a single root record.
+    Toml(TomlFile),
+    /// XML document, exposed as a single root record.
+    Xml(XmlFile),
+    /// Parquet table, one record per row, decoded a row group at a time.
+    Parquet(ParquetFile),
     /// Loaded via a WASM plugin (file-loader only).
     Plugin(WasmFileLoader),
     /// Loaded via a WASM plugin that also controls rendering (file-loader + file-viewer).
@@ -82,6 +123,11 @@ impl FileType {
         match self {
             FileType::Ndjson(_) => FileKind::Ndjson,
             FileType::JsonArray(_) | FileType::Single(_) => FileKind::Json,
+            FileType::Csv(_) => FileKind::Csv,
+            FileType::Yaml(_) => FileKind::Yaml,
+            FileType::Toml(_) => FileKind::Toml,
+            FileType::Xml(_) => FileKind::Xml,
+            FileType::Parquet(_) => FileKind::Parquet,
             FileType::Plugin(_) => FileKind::Plugin,
             FileType::PluginWithViewer(_) => FileKind::PluginTable,
         }
@@ -94,6 +140,11 @@ impl FileType {
             FileType::Ndjson(f) => f.len(),
             FileType::JsonArray(f) => f.len(),
             FileType::Single(_) => 1,
+            FileType::Csv(f) => f.len(),
+            FileType::Yaml(f) => f.len(),
+            FileType::Toml(_) => 1,
+            FileType::Xml(_) => 1,
+            FileType::Parquet(f) => f.len(),
             FileType::Plugin(f) => f.len(),
             FileType::PluginWithViewer(f) => f.len(),
         }
@@ -105,6 +156,11 @@ impl FileType {
             FileType::Ndjson(f) => f.get(idx),
             FileType::JsonArray(f) => f.get(idx),
             FileType::Single(f) => f.get(idx),
+            FileType::Csv(f) => f.get(idx),
+            FileType::Yaml(f) => f.get(idx),
+            FileType::Toml(f) => f.get(idx),
+            FileType::Xml(f) => f.get(idx),
+            FileType::Parquet(f) => f.get(idx),
             FileType::Plugin(f) => f.get(idx),
             FileType::PluginWithViewer(f) => f.get(idx),
         }
@@ -137,11 +193,118 @@ impl FileType {
             FileType::Ndjson(f) => f.raw_line(idx),
             FileType::JsonArray(f) => f.raw_element(idx),
             FileType::Single(f) => f.raw_all(),
+            FileType::Csv(f) => f.raw_record(idx),
+            FileType::Yaml(f) => f.raw_document(idx),
+            FileType::Toml(f) => f.raw_document(idx),
+            FileType::Xml(f) => f.raw_document(idx),
+            FileType::Parquet(f) => f.raw_row(idx),
             FileType::Plugin(f) => f.raw_bytes(idx),
             FileType::PluginWithViewer(f) => f.raw_bytes(idx),
         }
     }
 
+    /// Raw bytes of the element at `idx` as strict JSON
+    ///
+    /// The same as [`Self::raw_slice`], except that a JSONC document has its
+    /// comments and trailing commas blanked out, so offsets still line up.
+    pub fn json_slice(&self, idx: usize) -> Result<Vec<u8>> {
+        let raw = self.raw_slice(idx)?;
+        Ok(match self {
+            FileType::Single(_) => strip_jsonc(&raw).unwrap_or(raw),
+            _ => raw,
+        })
+    }
+
+    /// Whether the document was read as JSONC, leaving comments or trailing
+    /// commas out of the tree
+    pub fn comments_stripped(&self) -> bool {
+        matches!(self, FileType::Single(f) if f.comments_stripped())
+    }
+
+    /// Whether [`Self::line_number`] can locate records in the source file.
+    pub fn has_line_numbers(&self) -> bool {
+        matches!(self, FileType::Ndjson(_) | FileType::JsonArray(_))
+    }
+
+    /// 1-based line in the source file where the record at `idx` starts.
+    ///
+    /// `None` for formats whose records don't map onto source lines.
+    pub fn line_number(&self, idx: usize) -> Result<Option<usize>> {
+        match self {
+            FileType::Ndjson(f) => Ok(f.line_number(idx)),
+            FileType::JsonArray(f) => f.line_number(idx).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Whether records can be edited in place and saved back to the file.
+    pub fn supports_editing(&self) -> bool {
+        matches!(self, FileType::Ndjson(_) | FileType::JsonArray(_))
+    }
+
+    /// Replace the record at `idx` in memory, marking it unsaved.
+    pub fn set_record(&mut self, idx: usize, value: Value) -> Result<()> {
+        match self {
+            FileType::Ndjson(f) => f.set(idx, value),
+            FileType::JsonArray(f) => f.set(idx, value),
+            _ => Err(ThothError::StateError {
+                reason: "Editing is only supported for NDJSON and JSON array files".to_string(),
+            }),
+        }
+    }
+
+    /// Number of records with unsaved edits.
+    pub fn dirty_count(&self) -> usize {
+        match self {
+            FileType::Ndjson(f) => f.dirty_count(),
+            FileType::JsonArray(f) => f.dirty_count(),
+            _ => 0,
+        }
+    }
+
+    /// Drop all unsaved edits.
+    pub fn discard_edits(&mut self) {
+        match self {
+            FileType::Ndjson(f) => f.discard_edits(),
+            FileType::JsonArray(f) => f.discard_edits(),
+            _ => {}
+        }
+    }
+
+    /// Write unsaved edits back to `path`, keeping its format. Returns the
+    /// number of records saved.
+    pub fn save_edits(&mut self, path: &Path) -> Result<usize> {
+        if crate::file::gzip::is_gzip_path(path) {
+            return Err(ThothError::FileWriteError {
+                path: path.to_path_buf(),
+                reason: "Saving edits to compressed files is not supported".to_string(),
+            });
+        }
+        match self {
+            FileType::Ndjson(f) => f.save(path),
+            FileType::JsonArray(f) => f.save(path),
+            _ => Ok(0),
+        }
+    }
+
+    /// Rewrite the record at `idx` in the file at `path` immediately, without
+    /// touching other records or other unsaved edits.
+    pub fn replace(&mut self, idx: usize, value: Value, path: &Path) -> Result<()> {
+        if crate::file::gzip::is_gzip_path(path) {
+            return Err(ThothError::FileWriteError {
+                path: path.to_path_buf(),
+                reason: "Saving edits to compressed files is not supported".to_string(),
+            });
+        }
+        match self {
+            FileType::Ndjson(f) => f.replace(idx, value, path),
+            FileType::JsonArray(f) => f.replace(idx, value, path),
+            _ => Err(ThothError::StateError {
+                reason: "Editing is only supported for NDJSON and JSON array files".to_string(),
+            }),
+        }
+    }
+
     /// Ask the plugin how it wants its data displayed.
     /// Only available for PluginWithViewer loaders; defaults to Table.
     pub fn preferred_display(&mut self) -> DisplayMode {
@@ -160,6 +323,15 @@ impl FileType {
         }
     }
 
+    /// Whether the file is one document shown as a single root record,
+    /// rather than a sequence of records
+    pub fn is_single_document(&self) -> bool {
+        matches!(
+            self,
+            FileType::Single(_) | FileType::Toml(_) | FileType::Xml(_)
+        )
+    }
+
     /// Return plugin-supplied column headers. Only available for PluginWithViewer loaders.
     pub fn column_headers(&mut self) -> Option<Vec<String>> {
         match self {
@@ -173,7 +345,7 @@ impl FileLoader for FileType {
     type Item = Value;
 
     fn open(path: &Path) -> Result<Self> {
-        let (_detected, file_type) = load_file_auto(path)?;
+        let (_detected, file_type) = load_file_auto(path, DEFAULT_MAX_FILE_SIZE_MB)?;
         Ok(file_type)
     }
 
@@ -190,17 +362,200 @@ impl FileLoader for FileType {
     }
 }
 
+/// Opens a file whose contents were sniffed as one format, reporting how far
+/// it got through `OpenProgress` if it indexes the whole file
+pub type LoaderConstructor = fn(&Path, &OpenProgress) -> Result<FileType>;
+
+/// Loader constructors keyed by detected format, seeded with the built-ins
+static LOADERS: LazyLock<RwLock<HashMap<DetectedFileType, LoaderConstructor>>> =
+    LazyLock::new(|| {
+        let builtins: [(DetectedFileType, LoaderConstructor); 9] = [
+            (DetectedFileType::Ndjson, |path, progress| {
+                Ok(FileType::Ndjson(NdjsonFile::open_with_progress(
+                    path, progress,
+                )?))
+            }),
+            (DetectedFileType::JsonArray, |path, progress| {
+                match JsonArrayFile::open_with_progress(path, progress) {
+                    Ok(f) => Ok(FileType::JsonArray(f)),
+                    // An array that can't be indexed may still be JSONC with
+                    // comments between elements; read that as one document
+                    Err(e @ ThothError::InvalidJsonStructure { .. }) => {
+                        let mut single = SingleValueFile::open(path)?;
+                        match single.get(0) {
+                            Ok(Value::Array(_)) => Ok(FileType::Single(single)),
+                            _ => Err(e),
+                        }
+                    }
+                    Err(e) => Err(e),
+                }
+            }),
+            (DetectedFileType::JsonObject, |path, _| {
+                Ok(FileType::Single(SingleValueFile::open(path)?))
+            }),
+            (DetectedFileType::Jsonc, |path, _| {
+                Ok(FileType::Single(SingleValueFile::open(path)?))
+            }),
+            (DetectedFileType::Csv, |path, _| {
+                Ok(FileType::Csv(CsvFile::open(path)?))
+            }),
+            (DetectedFileType::Yaml, |path, _| {
+                Ok(FileType::Yaml(YamlFile::open(path)?))
+            }),
+            (DetectedFileType::Toml, |path, _| {
+                Ok(FileType::Toml(TomlFile::open(path)?))
+            }),
+            (DetectedFileType::Xml, |path, _| {
+                Ok(FileType::Xml(XmlFile::open(path)?))
+            }),
+            (DetectedFileType::Parquet, |path, _| {
+                Ok(FileType::Parquet(ParquetFile::open(path)?))
+            }),
+        ];
+        RwLock::new(HashMap::from(builtins))
+    });
+
+/// Open files sniffed as `detected` with `open`, returning the constructor it replaces
+pub fn register_loader(
+    detected: DetectedFileType,
+    open: LoaderConstructor,
+) -> Option<LoaderConstructor> {
+    LOADERS.write().ok()?.insert(detected, open)
+}
+
+/// File-name patterns opened as a fixed type, first match wins
+///
+/// Mirrors `viewer.file_type_overrides`; the app pushes the current list
+/// whenever settings are loaded or saved.
+static FILE_TYPE_OVERRIDES: LazyLock<RwLock<Vec<FileTypeOverride>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
+
+/// Replace the patterns consulted before sniffing a file's contents
+pub fn set_file_type_overrides(overrides: Vec<FileTypeOverride>) {
+    if let Ok(mut current) = FILE_TYPE_OVERRIDES.write() {
+        *current = overrides;
+    }
+}
+
+/// The type `path` is configured to open as, if its name matches an override
+///
+/// A `.gz` file also matches by its name without the suffix, since its
+/// decompressed contents are what get loaded.
+pub fn file_type_override(path: &Path) -> Option<DetectedFileType> {
+    let name = path.file_name()?.to_string_lossy();
+    let inner = crate::file::gzip::is_gzip_path(path)
+        .then(|| path.file_stem().map(|stem| stem.to_string_lossy()))
+        .flatten();
+    let overrides = FILE_TYPE_OVERRIDES.read().ok()?;
+    overrides
+        .iter()
+        .find(|o| {
+            matches_pattern(&o.pattern, &name)
+                || inner
+                    .as_deref()
+                    .is_some_and(|inner| matches_pattern(&o.pattern, inner))
+        })
+        .map(|o| o.file_type)
+}
+
 /// Load a file with automatic format detection.
-pub fn load_file_auto(path: &Path) -> Result<(DetectedFileType, FileType)> {
-    use crate::file::detect_file_type::sniff_file_type;
-
-    let detected = sniff_file_type(path)?;
-    let file_type = match detected {
-        DetectedFileType::Ndjson => FileType::Ndjson(NdjsonFile::open(path)?),
-        DetectedFileType::JsonArray => FileType::JsonArray(JsonArrayFile::open(path)?),
-        DetectedFileType::JsonObject => FileType::Single(SingleValueFile::open(path)?),
+///
+/// A matching [`file_type_override`] is tried before sniffing.
+///
+/// The loader is picked from the ones registered with [`register_loader`].
+/// A `.gz` file may decompress to at most `max_file_size_mb` megabytes.
+pub fn load_file_auto(path: &Path, max_file_size_mb: u64) -> Result<(Detection, FileType)> {
+    load_file(path, None, max_file_size_mb, &OpenProgress::default())
+}
+
+/// Load a file as `file_type`, whatever it sniffs as
+///
+/// When the sniffed type differs it is returned as the detection's
+/// alternative, so the user can switch back. A `.gz` file may decompress to
+/// at most `max_file_size_mb` megabytes.
+pub fn load_file_as(
+    path: &Path,
+    file_type: DetectedFileType,
+    max_file_size_mb: u64,
+) -> Result<(Detection, FileType)> {
+    load_file(
+        path,
+        Some(file_type),
+        max_file_size_mb,
+        &OpenProgress::default(),
+    )
+}
+
+/// Load a file as `forced`, or as whatever it sniffs as with `None`
+///
+/// Without a forced type, a matching [`file_type_override`] is tried first
+/// and sniffing only happens if the file doesn't load as that type.
+/// A `.gz` file may decompress to at most `max_file_size_mb` megabytes.
+/// Indexing reports to `progress` and stops with
+/// [`ThothError::OpenCanceled`] once it is canceled.
+pub fn load_file(
+    path: &Path,
+    forced: Option<DetectedFileType>,
+    max_file_size_mb: u64,
+    progress: &OpenProgress,
+) -> Result<(Detection, FileType)> {
+    let source = path;
+    // `.gz` files are decompressed to a temp copy so every loader (and
+    // `raw_slice`) works on the uncompressed bytes
+    let decompressed;
+    let path = if crate::file::gzip::is_gzip_path(path) {
+        decompressed = crate::file::gzip::decompress_to_temp(path, max_file_size_mb)?;
+        decompressed.as_path()
+    } else {
+        path
     };
-    Ok((detected, file_type))
+    progress.check(source)?;
+
+    if forced.is_none()
+        && let Some(overridden) = file_type_override(source)
+    {
+        let loaded = open_as(path, overridden, progress);
+        progress.check(source)?;
+        if let Ok(file_type) = loaded {
+            let detection = Detection {
+                file_type: overridden,
+                alternative: None,
+                text: (overridden != DetectedFileType::Parquet)
+                    .then(|| sniff_text_format(path))
+                    .flatten(),
+            };
+            return Ok((detection, file_type));
+        }
+        // The contents aren't of the configured type; sniff them instead
+    }
+
+    let mut detection = detect_file_type(path)?;
+    if let Some(forced) = forced
+        && forced != detection.file_type
+    {
+        detection = Detection {
+            file_type: forced,
+            alternative: Some(detection.file_type),
+            ..detection
+        };
+    }
+    let loaded = open_as(path, detection.file_type, progress);
+    // Canceled loaders may stop with any error; report the cancel instead
+    progress.check(source)?;
+    Ok((detection, loaded?))
+}
+
+/// Open `path` with the loader registered for `detected`
+fn open_as(path: &Path, detected: DetectedFileType, progress: &OpenProgress) -> Result<FileType> {
+    let open = LOADERS
+        .read()
+        .ok()
+        .and_then(|loaders| loaders.get(&detected).copied())
+        .ok_or_else(|| ThothError::InvalidFileType {
+            path: path.to_path_buf(),
+            expected: format!("a registered loader for {:?} files", detected),
+        })?;
+    open(path, progress)
 }
 
 #[cfg(test)]
@@ -246,3 +601,74 @@ mod get_range_tests {
         assert!(loader.get_range(2, 10).unwrap().is_empty());
     }
 }
+
+#[cfg(test)]
+mod override_tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn file_type_override_skips_sniffing_and_falls_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("single.thoth-override");
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(b"{\"a\": 1}\n")
+            .unwrap();
+        let sniffed = load_file_auto(&path, DEFAULT_MAX_FILE_SIZE_MB)
+            .unwrap()
+            .0
+            .file_type;
+        assert_eq!(sniffed, DetectedFileType::JsonObject);
+
+        let set = |file_type| {
+            set_file_type_overrides(vec![FileTypeOverride {
+                pattern: "*.THOTH-override".to_string(),
+                file_type,
+            }])
+        };
+        set(DetectedFileType::Ndjson);
+        assert_eq!(
+            file_type_override(&dir.path().join("x.thoth-override.gz")),
+            Some(DetectedFileType::Ndjson)
+        );
+        assert_eq!(file_type_override(&dir.path().join("x.json")), None);
+        let (detection, loader) = load_file_auto(&path, DEFAULT_MAX_FILE_SIZE_MB).unwrap();
+        assert_eq!(detection.file_type, DetectedFileType::Ndjson);
+        assert!(matches!(loader, FileType::Ndjson(_)));
+        // Forcing a type still wins over the override
+        let (detection, _) = load_file_as(
+            &path,
+            DetectedFileType::JsonObject,
+            DEFAULT_MAX_FILE_SIZE_MB,
+        )
+        .unwrap();
+        assert_eq!(detection.file_type, DetectedFileType::JsonObject);
+
+        // Contents that aren't of the configured type are sniffed instead
+        set(DetectedFileType::Parquet);
+        let (detection, _) = load_file_auto(&path, DEFAULT_MAX_FILE_SIZE_MB).unwrap();
+        assert_eq!(detection.file_type, sniffed);
+
+        set_file_type_overrides(Vec::new());
+    }
+
+    #[test]
+    fn json_array_with_comments_loads_as_jsonc() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("commented.json");
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(b"[\n {\"a\":1}, // c\n {\"b\":2}\n]")
+            .unwrap();
+
+        let (detection, mut loader) = load_file_auto(&path, DEFAULT_MAX_FILE_SIZE_MB).unwrap();
+        assert_eq!(detection.file_type, DetectedFileType::JsonArray);
+        assert!(matches!(loader, FileType::Single(_)));
+        assert_eq!(
+            loader.get(0).unwrap(),
+            serde_json::json!([{"a": 1}, {"b": 2}])
+        );
+        assert!(loader.comments_stripped());
+    }
+}
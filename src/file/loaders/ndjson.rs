@@ -1,9 +1,17 @@
+use crate::constants::{
+    NDJSON_INDEX_CHUNK_BYTES, NDJSON_PARALLEL_INDEX_MIN_BYTES, OPEN_PROGRESS_STEP_BYTES,
+};
 use crate::error::{Result, ThothError};
+use crate::file::detect_file_type::UTF8_BOM;
+use crate::file::edits;
 use crate::file::loaders::FileLoader;
+use crate::file::open_progress::OpenProgress;
 use crate::platform::FileIO;
 use anyhow::Context;
+use rayon::prelude::*;
 use serde_json::Value;
 use std::{
+    collections::BTreeMap,
     fs::File,
     io::{BufRead, BufReader},
     path::Path,
@@ -18,52 +26,47 @@ pub struct NdjsonFile {
     file: File,
     // (start, end) byte offsets for each line (end is exclusive)
     line_spans: Vec<(u64, u64)>,
+    // Records edited in memory but not yet saved, by line index
+    edits: BTreeMap<usize, Value>,
 }
 
 impl NdjsonFile {
     /// Open an NDJSON file and index all line boundaries
     ///
-    /// This performs a single streaming pass to build an index of line spans,
-    /// which allows for efficient random access later.
+    /// Large files are scanned in parallel chunks; small ones in a single
+    /// streaming pass. Both produce the same spans.
     pub fn open(path: &Path) -> Result<Self> {
-        let file = File::open(path).with_context(|| "open NDJSON")?;
-
-        // Build (start,end) for each line using a single streaming pass
-        let mut spans = Vec::new();
-        let mut reader = BufReader::new(file.try_clone()?);
-        let mut pos: u64 = 0;
-        let mut buf = Vec::with_capacity(8 * 1024);
-        loop {
-            buf.clear();
-            let n = reader.read_until(b'\n', &mut buf)?;
-            if n == 0 {
-                break;
-            }
+        Self::open_with_progress(path, &OpenProgress::default())
+    }
 
-            // Exclude the '\n' from the span (common for substring search)
-            let end_exclusive = if buf.last() == Some(&b'\n') {
-                pos + (n as u64) - 1
-            } else {
-                pos + (n as u64)
-            };
-
-            // Also strip trailing '\r' if present (CRLF files)
-            let (start, mut end) = (pos, end_exclusive);
-            if end > start {
-                // Read last byte of this slice to check for '\r'
-                // (We don't have the bytes here, but we can detect from buf)
-                if buf.len() >= 2 && buf[buf.len() - 2] == b'\r' && buf[buf.len() - 1] == b'\n' {
-                    end -= 1;
-                }
+    /// [`open`](Self::open), reporting the bytes scanned to `progress` and
+    /// stopping once it is canceled
+    pub fn open_with_progress(path: &Path, progress: &OpenProgress) -> Result<Self> {
+        let file = File::open(path).with_context(|| "open NDJSON")?;
+        let file_len = file.metadata()?.len();
+        progress.set_total(file_len);
+
+        let mut line_spans = if file_len >= NDJSON_PARALLEL_INDEX_MIN_BYTES {
+            index_lines_parallel(&file, file_len, NDJSON_INDEX_CHUNK_BYTES, progress)?
+        } else {
+            index_lines_sequential(&file, progress)?
+        };
+        progress.check(path)?;
+        // A byte order mark is not part of the first record
+        if let Some(first) = line_spans.first_mut()
+            && first.1 - first.0 >= UTF8_BOM.len() as u64
+        {
+            let mut head = [0u8; 3];
+            file.read_at(&mut head, 0)?;
+            if head == UTF8_BOM {
+                first.0 += UTF8_BOM.len() as u64;
             }
-
-            spans.push((start, end));
-            pos += n as u64;
         }
 
         Ok(Self {
             file,
-            line_spans: spans,
+            line_spans,
+            edits: BTreeMap::new(),
         })
     }
 
@@ -75,8 +78,13 @@ impl NdjsonFile {
 
     /// Get a parsed JSON value at the specified line index
     ///
+    /// A malformed line fails with [`ThothError::JsonParseError`] for that
+    /// record alone, so callers can show it in place and keep going.
     /// This performs a position-independent read and is safe for parallel access.
     pub fn get(&mut self, idx: usize) -> Result<Value> {
+        if let Some(value) = self.edits.get(&idx) {
+            return Ok(value.clone());
+        }
         // Read the exact span and parse (no shared cursor)
         let (start, end) =
             *self
@@ -89,15 +97,19 @@ impl NdjsonFile {
         let mut buf = vec![0u8; len];
         self.file.read_at(&mut buf, start)?;
 
-        let v: Value = serde_json::from_slice(&buf)
-            .with_context(|| format!("invalid JSON at line index {}", idx))?;
-        Ok(v)
+        serde_json::from_slice(&buf).map_err(|e| ThothError::JsonParseError {
+            line: self.line_number(idx),
+            reason: e.to_string(),
+        })
     }
 
     /// Get raw bytes for a line at the specified index
     ///
     /// This performs a position-independent read and is safe for parallel access.
     pub fn raw_line(&self, idx: usize) -> Result<Vec<u8>> {
+        if let Some(value) = self.edits.get(&idx) {
+            return Ok(serde_json::to_vec(value)?);
+        }
         let (start, end) =
             *self
                 .line_spans
@@ -111,6 +123,187 @@ impl NdjsonFile {
 
         Ok(buf)
     }
+
+    /// 1-based line number of the record at `idx` in the source file
+    ///
+    /// Every line gets a span when indexing, blank ones included, so the
+    /// record index maps straight onto the line.
+    pub fn line_number(&self, idx: usize) -> Option<usize> {
+        (idx < self.line_spans.len()).then_some(idx + 1)
+    }
+
+    /// Replace the line at `idx` in memory; the file is untouched until [`Self::save`]
+    pub fn set(&mut self, idx: usize, value: Value) -> Result<()> {
+        if idx >= self.line_spans.len() {
+            return Err(ThothError::InvalidJsonStructure {
+                reason: format!("Line index {} out of bounds", idx),
+            });
+        }
+        self.edits.insert(idx, value);
+        Ok(())
+    }
+
+    /// Number of lines edited since the file was opened or last saved
+    pub fn dirty_count(&self) -> usize {
+        self.edits.len()
+    }
+
+    /// Drop all unsaved edits
+    pub fn discard_edits(&mut self) {
+        self.edits.clear();
+    }
+
+    /// Rewrite the line at `idx` in `path` right away, keeping its line ending
+    ///
+    /// Other lines' bytes are copied unchanged and other unsaved edits stay
+    /// pending; the line index is shifted rather than rebuilt.
+    pub fn replace(&mut self, idx: usize, value: Value, path: &Path) -> Result<()> {
+        edits::replace_span(&mut self.file, &mut self.line_spans, idx, value, path)?;
+        self.edits.remove(&idx);
+        Ok(())
+    }
+
+    /// Write the edited lines back to `path` and reopen it
+    ///
+    /// Only the edited lines are re-serialized; every other byte is copied
+    /// from the current file.
+    pub fn save(&mut self, path: &Path) -> Result<usize> {
+        let saved = self.edits.len();
+        if saved == 0 {
+            return Ok(0);
+        }
+        edits::rewrite_spans(&self.file, &self.line_spans, &self.edits, path)?;
+        *self = Self::open(path)?;
+        Ok(saved)
+    }
+}
+
+/// Build (start, end) for each line using a single streaming pass
+fn index_lines_sequential(file: &File, progress: &OpenProgress) -> Result<Vec<(u64, u64)>> {
+    let mut spans = Vec::new();
+    let mut reader = BufReader::new(file.try_clone()?);
+    let mut pos: u64 = 0;
+    let mut reported: u64 = 0;
+    let mut buf = Vec::with_capacity(8 * 1024);
+    loop {
+        if pos - reported >= OPEN_PROGRESS_STEP_BYTES {
+            progress.advance(pos - reported);
+            reported = pos;
+            if progress.is_canceled() {
+                break;
+            }
+        }
+        buf.clear();
+        let n = reader.read_until(b'\n', &mut buf)?;
+        if n == 0 {
+            break;
+        }
+
+        // Exclude the '\n' from the span (common for substring search)
+        let end_exclusive = if buf.last() == Some(&b'\n') {
+            pos + (n as u64) - 1
+        } else {
+            pos + (n as u64)
+        };
+
+        // Also strip trailing '\r' if present (CRLF files)
+        let (start, mut end) = (pos, end_exclusive);
+        if end > start
+            && buf.len() >= 2
+            && buf[buf.len() - 2] == b'\r'
+            && buf[buf.len() - 1] == b'\n'
+        {
+            end -= 1;
+        }
+
+        spans.push((start, end));
+        pos += n as u64;
+    }
+    progress.advance(pos - reported);
+    Ok(spans)
+}
+
+/// Set on a newline offset when the byte before it is '\r'
+const CR_BEFORE: u64 = 1 << 63;
+
+/// Build the same spans as [`index_lines_sequential`] by scanning
+/// `chunk_bytes`-sized chunks for newlines on the rayon pool
+///
+/// Each chunk reports its newline offsets (flagged when preceded by '\r');
+/// the lists are then stitched in file order, so lines straddling a chunk
+/// boundary come out whole.
+///
+/// Chunks not started yet are skipped once `progress` is canceled, leaving
+/// the spans incomplete; the caller checks for that.
+fn index_lines_parallel(
+    file: &File,
+    file_len: u64,
+    chunk_bytes: u64,
+    progress: &OpenProgress,
+) -> Result<Vec<(u64, u64)>> {
+    let chunk_bytes = chunk_bytes.max(1);
+    let chunks: Vec<u64> = (0..file_len.div_ceil(chunk_bytes))
+        .map(|i| i * chunk_bytes)
+        .collect();
+
+    let newlines: Vec<Vec<u64>> = chunks
+        .par_iter()
+        .map(|&start| -> std::io::Result<Vec<u64>> {
+            if progress.is_canceled() {
+                return Ok(Vec::new());
+            }
+            let end = (start + chunk_bytes).min(file_len);
+            // One byte of overlap so a '\r' just before the chunk is seen
+            let read_from = start.saturating_sub(1);
+            let mut buf = vec![0u8; (end - read_from) as usize];
+            read_exact_at(file, &mut buf, read_from)?;
+
+            let skip = (start - read_from) as usize;
+            progress.advance(end - start);
+            Ok(memchr::memchr_iter(b'\n', &buf[skip..])
+                .map(|i| {
+                    let at = skip + i;
+                    let offset = read_from + at as u64;
+                    if at > 0 && buf[at - 1] == b'\r' {
+                        offset | CR_BEFORE
+                    } else {
+                        offset
+                    }
+                })
+                .collect())
+        })
+        .collect::<std::io::Result<_>>()?;
+
+    let mut spans = Vec::with_capacity(newlines.iter().map(Vec::len).sum::<usize>() + 1);
+    let mut line_start = 0;
+    for newline in newlines.into_iter().flatten() {
+        let pos = newline & !CR_BEFORE;
+        let end = if newline & CR_BEFORE != 0 && pos > line_start {
+            pos - 1
+        } else {
+            pos
+        };
+        spans.push((line_start, end));
+        line_start = pos + 1;
+    }
+    // Last line without a trailing newline
+    if line_start < file_len {
+        spans.push((line_start, file_len));
+    }
+    Ok(spans)
+}
+
+/// Fill `buf` from `offset`, retrying short reads
+fn read_exact_at(file: &File, mut buf: &mut [u8], mut offset: u64) -> std::io::Result<()> {
+    while !buf.is_empty() {
+        let n = file.read_at(buf, offset)?;
+        if n == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        buf = &mut buf[n..];
+        offset += n as u64;
+    }
+    Ok(())
 }
 
 impl FileLoader for NdjsonFile {
@@ -177,6 +370,16 @@ mod tests {
         assert_eq!(val["id"], 1);
     }
 
+    #[test]
+    fn test_ndjson_skips_leading_bom() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "\u{feff}{{\"id\":1}}\n{{\"id\":2}}\n").unwrap();
+
+        let mut loader = NdjsonFile::open(file.path()).unwrap();
+        assert_eq!(loader.get(0).unwrap()["id"], 1);
+        assert_eq!(loader.raw_line(0).unwrap(), br#"{"id":1}"#);
+    }
+
     #[test]
     fn test_ndjson_out_of_bounds() {
         let mut file = NamedTempFile::new().unwrap();
@@ -211,6 +414,64 @@ mod tests {
         assert_eq!(val["id"], 1);
     }
 
+    #[test]
+    fn test_parallel_index_matches_sequential() {
+        let mut file = NamedTempFile::new().unwrap();
+        // CRLF and LF endings, blank lines, a lone '\r' and no trailing newline
+        write!(
+            file,
+            "{{\"id\":1}}\n{{\"id\":2}}\r\n\n\r\n{{\"name\":\"a long line that spans chunks\"}}\n\
+             {{\"id\":3}}\r{{\"id\":4}}\r\n\r\n{{\"id\":5}}"
+        )
+        .unwrap();
+        file.flush().unwrap();
+
+        let handle = file.reopen().unwrap();
+        let len = handle.metadata().unwrap().len();
+        let sequential = index_lines_sequential(&handle, &OpenProgress::default()).unwrap();
+        // Every chunk size, so boundaries fall on '\r', '\n' and mid-line bytes
+        for chunk_bytes in 1..=len + 1 {
+            assert_eq!(
+                index_lines_parallel(&handle, len, chunk_bytes, &OpenProgress::default()).unwrap(),
+                sequential,
+                "chunk size {}",
+                chunk_bytes
+            );
+        }
+
+        // Trailing newline and empty file
+        for contents in ["{}\n{}\n", "\n", ""] {
+            let mut file = NamedTempFile::new().unwrap();
+            write!(file, "{}", contents).unwrap();
+            let handle = file.reopen().unwrap();
+            let len = handle.metadata().unwrap().len();
+            assert_eq!(
+                index_lines_parallel(&handle, len, 3, &OpenProgress::default()).unwrap(),
+                index_lines_sequential(&handle, &OpenProgress::default()).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_open_reports_progress_and_cancels() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, r#"{{"id":1}}"#).unwrap();
+        writeln!(file, r#"{{"id":2}}"#).unwrap();
+        file.flush().unwrap();
+        let len = file.as_file().metadata().unwrap().len();
+
+        let progress = OpenProgress::default();
+        NdjsonFile::open_with_progress(file.path(), &progress).unwrap();
+        assert_eq!(progress.bytes(), (len, Some(len)));
+
+        let progress = OpenProgress::default();
+        progress.cancel();
+        assert!(matches!(
+            NdjsonFile::open_with_progress(file.path(), &progress),
+            Err(ThothError::OpenCanceled { .. })
+        ));
+    }
+
     #[test]
     fn test_ndjson_fileloader_trait() {
         let mut file = NamedTempFile::new().unwrap();
@@ -230,4 +491,60 @@ mod tests {
         let raw = loader.raw_bytes(1).unwrap();
         assert!(!raw.is_empty());
     }
+
+    #[test]
+    fn test_ndjson_save_replaces_edited_lines() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{{\"id\":1}}\r\n{{\"id\": 2}}\r\n{{\"id\":3}}\r\n").unwrap();
+
+        let mut loader = NdjsonFile::open(file.path()).unwrap();
+        loader.set(1, serde_json::json!({"id": 20})).unwrap();
+        assert_eq!(loader.dirty_count(), 1);
+        assert_eq!(loader.get(1).unwrap()["id"], 20);
+        assert!(loader.set(3, Value::Null).is_err());
+
+        assert_eq!(loader.save(file.path()).unwrap(), 1);
+        assert_eq!(loader.dirty_count(), 0);
+        assert_eq!(
+            std::fs::read_to_string(file.path()).unwrap(),
+            "{\"id\":1}\r\n{\"id\":20}\r\n{\"id\":3}\r\n"
+        );
+        assert_eq!(loader.get(1).unwrap()["id"], 20);
+    }
+
+    #[test]
+    fn test_ndjson_replace_rewrites_one_line() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "{{\"id\": 1}}\r\n{{\"id\": 2}}\r\n\r\n{{\"id\": 3}}\n"
+        )
+        .unwrap();
+
+        let mut loader = NdjsonFile::open(file.path()).unwrap();
+        loader.set(3, serde_json::json!({"id": 30})).unwrap();
+        loader
+            .replace(
+                1,
+                serde_json::json!({"id": "two", "n": [1, 2]}),
+                file.path(),
+            )
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(file.path()).unwrap(),
+            "{\"id\": 1}\r\n{\"id\":\"two\",\"n\":[1,2]}\r\n\r\n{\"id\": 3}\n"
+        );
+
+        // The shifted index matches a fresh one, and other edits stay unsaved
+        let reopened = NdjsonFile::open(file.path()).unwrap();
+        assert_eq!(loader.line_spans, reopened.line_spans);
+        assert_eq!(loader.get(1).unwrap()["n"][1], 2);
+        assert_eq!(loader.dirty_count(), 1);
+        assert_eq!(loader.get(3).unwrap()["id"], 30);
+
+        // Shrinking a later line keeps the spans in step too
+        loader.replace(0, Value::Null, file.path()).unwrap();
+        assert_eq!(loader.raw_line(1).unwrap(), br#"{"id":"two","n":[1,2]}"#);
+        assert!(loader.replace(4, Value::Null, file.path()).is_err());
+    }
 }
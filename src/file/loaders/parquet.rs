@@ -0,0 +1,229 @@
+use crate::error::{Result, ThothError};
+use crate::file::loaders::FileLoader;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use serde_json::Value;
+use std::{
+    collections::VecDeque,
+    fs::File,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// Number of decoded row groups kept in memory
+///
+/// Rows can only be decoded a whole row group at a time, so recently used
+/// groups are kept around; enough for each search worker to hold its own.
+const DECODED_GROUP_CACHE_SIZE: usize = 8;
+
+/// Read-only loader for Apache Parquet files
+///
+/// Only the footer metadata is read during `open()`. Each row is exposed as
+/// a JSON object keyed by column name; `get()` decodes just the row group
+/// containing the requested row. Logical types are rendered as readable
+/// scalars: timestamps and dates as strings, decimals as exact decimal
+/// strings.
+pub struct ParquetFile {
+    path: PathBuf,
+    reader: SerializedFileReader<File>,
+    // Index of the first row of each row group, in file order
+    group_starts: Vec<usize>,
+    len: usize,
+    decoded: Mutex<VecDeque<(usize, Arc<Vec<Value>>)>>,
+}
+
+impl ParquetFile {
+    /// Open a Parquet file and read its footer metadata
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path).map_err(|e| ThothError::FileReadError {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?;
+        let reader = SerializedFileReader::new(file).map_err(|e| ThothError::FileReadError {
+            path: path.to_path_buf(),
+            reason: format!("Invalid Parquet file: {}", e),
+        })?;
+
+        let mut group_starts = Vec::with_capacity(reader.num_row_groups());
+        let mut next = 0usize;
+        for group in reader.metadata().row_groups() {
+            group_starts.push(next);
+            next += usize::try_from(group.num_rows()).unwrap_or(0);
+        }
+        let len = usize::try_from(reader.metadata().file_metadata().num_rows()).unwrap_or(next);
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            reader,
+            group_starts,
+            len,
+            decoded: Mutex::new(VecDeque::with_capacity(DECODED_GROUP_CACHE_SIZE)),
+        })
+    }
+
+    /// Returns the number of rows across all row groups
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Get the row at the specified index as a JSON object
+    pub fn get(&mut self, idx: usize) -> Result<Value> {
+        self.row(idx)
+    }
+
+    /// Get the row at the specified index serialized as JSON bytes
+    ///
+    /// Search treats raw records as JSON, so rows are handed out in their
+    /// JSON form rather than as the encoded column data.
+    pub fn raw_row(&self, idx: usize) -> Result<Vec<u8>> {
+        let value = self.row(idx)?;
+        serde_json::to_vec(&value).map_err(|e| ThothError::InvalidJsonStructure {
+            reason: format!("Failed to serialize Parquet row: {}", e),
+        })
+    }
+
+    fn row(&self, idx: usize) -> Result<Value> {
+        if idx >= self.len {
+            return Err(ThothError::InvalidJsonStructure {
+                reason: format!("Row index {} out of range (0..{})", idx, self.len),
+            });
+        }
+        // The last group starting at or before `idx`
+        let group = self.group_starts.partition_point(|&start| start <= idx) - 1;
+        let rows = self.decoded_group(group)?;
+        rows.get(idx - self.group_starts[group])
+            .cloned()
+            .ok_or_else(|| ThothError::FileReadError {
+                path: self.path.clone(),
+                reason: format!("Row group {} is shorter than its metadata", group),
+            })
+    }
+
+    /// Rows of a row group, decoding it unless it is already cached
+    fn decoded_group(&self, group: usize) -> Result<Arc<Vec<Value>>> {
+        if let Ok(cache) = self.decoded.lock()
+            && let Some((_, rows)) = cache.iter().find(|(g, _)| *g == group)
+        {
+            return Ok(Arc::clone(rows));
+        }
+
+        let rows = Arc::new(self.decode_group(group)?);
+        if let Ok(mut cache) = self.decoded.lock() {
+            if cache.len() >= DECODED_GROUP_CACHE_SIZE {
+                cache.pop_front();
+            }
+            cache.push_back((group, Arc::clone(&rows)));
+        }
+        Ok(rows)
+    }
+
+    fn decode_group(&self, group: usize) -> Result<Vec<Value>> {
+        let read_error = |e: parquet::errors::ParquetError| ThothError::FileReadError {
+            path: self.path.clone(),
+            reason: format!("Failed to read row group {}: {}", group, e),
+        };
+        let reader = self.reader.get_row_group(group).map_err(read_error)?;
+        reader
+            .get_row_iter(None)
+            .map_err(read_error)?
+            .map(|row| row.map(|row| row.to_json_value()).map_err(read_error))
+            .collect()
+    }
+}
+
+impl FileLoader for ParquetFile {
+    type Item = Value;
+
+    fn open(path: &Path) -> Result<Self> {
+        ParquetFile::open(path)
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn get(&mut self, idx: usize) -> Result<Self::Item> {
+        self.get(idx)
+    }
+
+    fn raw_bytes(&self, idx: usize) -> Result<Vec<u8>> {
+        self.raw_row(idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::data_type::{ByteArray, ByteArrayType, Int64Type};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+    use serde_json::json;
+    use tempfile::NamedTempFile;
+
+    /// Write `groups` as row groups of an `id`/`name` table
+    fn parquet_file(groups: &[&[(i64, &str)]]) -> NamedTempFile {
+        let schema = Arc::new(
+            parse_message_type(
+                "message schema { REQUIRED INT64 id; REQUIRED BYTE_ARRAY name (UTF8); }",
+            )
+            .unwrap(),
+        );
+        let props = Arc::new(WriterProperties::builder().build());
+        let file = NamedTempFile::new().unwrap();
+        let mut writer = SerializedFileWriter::new(file.reopen().unwrap(), schema, props).unwrap();
+
+        for rows in groups {
+            let ids: Vec<i64> = rows.iter().map(|(id, _)| *id).collect();
+            let names: Vec<ByteArray> = rows.iter().map(|(_, name)| (*name).into()).collect();
+
+            let mut group = writer.next_row_group().unwrap();
+            let mut column = group.next_column().unwrap().unwrap();
+            column
+                .typed::<Int64Type>()
+                .write_batch(&ids, None, None)
+                .unwrap();
+            column.close().unwrap();
+            let mut column = group.next_column().unwrap().unwrap();
+            column
+                .typed::<ByteArrayType>()
+                .write_batch(&names, None, None)
+                .unwrap();
+            column.close().unwrap();
+            group.close().unwrap();
+        }
+        writer.close().unwrap();
+        file
+    }
+
+    #[test]
+    fn test_parquet_rows_across_row_groups() {
+        let file = parquet_file(&[&[(1, "a"), (2, "b")], &[(3, "c")]]);
+        let mut loader = ParquetFile::open(file.path()).unwrap();
+        assert_eq!(loader.len(), 3);
+        assert_eq!(loader.get(0).unwrap(), json!({"id": 1, "name": "a"}));
+        assert_eq!(loader.get(2).unwrap(), json!({"id": 3, "name": "c"}));
+        assert_eq!(loader.get(1).unwrap(), json!({"id": 2, "name": "b"}));
+    }
+
+    #[test]
+    fn test_parquet_raw_row_is_json() {
+        let file = parquet_file(&[&[(7, "x")]]);
+        let loader = ParquetFile::open(file.path()).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&loader.raw_row(0).unwrap()).unwrap(),
+            json!({"id": 7, "name": "x"})
+        );
+    }
+
+    #[test]
+    fn test_parquet_out_of_range_and_invalid() {
+        let file = parquet_file(&[&[(1, "a")]]);
+        let mut loader = ParquetFile::open(file.path()).unwrap();
+        assert!(loader.get(1).is_err());
+
+        let mut bogus = NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut bogus, b"PAR1 not really").unwrap();
+        assert!(ParquetFile::open(bogus.path()).is_err());
+    }
+}
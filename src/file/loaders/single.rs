@@ -1,4 +1,6 @@
 use crate::error::{Result, ThothError};
+use crate::file::detect_file_type::UTF8_BOM;
+use crate::file::jsonc::strip_jsonc;
 use crate::file::loaders::FileLoader;
 use crate::platform::FileIO;
 use serde_json::Value;
@@ -8,9 +10,12 @@ use std::{fs::File, path::Path};
 ///
 /// This loader handles files containing a single JSON object or value.
 /// The value is parsed on first access and cached for subsequent accesses.
+/// A file that isn't strict JSON is parsed again as JSONC, without its
+/// comments and trailing commas; the raw bytes stay as written.
 pub struct SingleValueFile {
     file: File,
     parsed: Option<Value>,
+    comments_stripped: bool,
 }
 
 impl SingleValueFile {
@@ -22,6 +27,7 @@ impl SingleValueFile {
         Ok(Self {
             file: File::open(path)?,
             parsed: None,
+            comments_stripped: false,
         })
     }
 
@@ -44,11 +50,28 @@ impl SingleValueFile {
         let mut buf = vec![0u8; len];
         self.file.read_at(&mut buf, 0)?;
 
-        let v: Value = serde_json::from_slice(&buf)?;
+        let text = buf.strip_prefix(UTF8_BOM).unwrap_or(&buf);
+        let v: Value = match serde_json::from_slice(text) {
+            Ok(v) => v,
+            Err(e) => {
+                // Report the strict error if the file isn't valid JSONC either
+                let v = strip_jsonc(text)
+                    .and_then(|stripped| serde_json::from_slice(&stripped).ok())
+                    .ok_or(e)?;
+                self.comments_stripped = true;
+                v
+            }
+        };
         self.parsed = Some(v.clone());
         Ok(v)
     }
 
+    /// Whether the value was parsed as JSONC, with comments or trailing
+    /// commas left out
+    pub fn comments_stripped(&self) -> bool {
+        self.comments_stripped
+    }
+
     /// Get raw bytes for the entire file
     ///
     /// This performs a position-independent read and is safe for parallel access.
@@ -170,6 +193,37 @@ mod tests {
         assert_eq!(s, r#"{"id":1}"#);
     }
 
+    #[test]
+    fn test_single_value_jsonc() {
+        let source = "{\n  // font\n  \"size\": 14, /* pt */\n  \"tabs\": [2, 4,],\n}\n";
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{}", source).unwrap();
+        file.flush().unwrap();
+
+        let mut loader = SingleValueFile::open(file.path()).unwrap();
+        assert!(!loader.comments_stripped());
+        let val = loader.get(0).unwrap();
+        assert_eq!(val["size"], 14);
+        assert_eq!(val["tabs"], serde_json::json!([2, 4]));
+        assert!(loader.comments_stripped());
+        // The raw bytes keep the comments
+        assert_eq!(
+            String::from_utf8(loader.raw_all().unwrap()).unwrap(),
+            source
+        );
+    }
+
+    #[test]
+    fn test_single_value_invalid_jsonc_keeps_strict_error() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{{\"a\": 1 // note\n \"b\": 2}}").unwrap();
+        file.flush().unwrap();
+
+        let mut loader = SingleValueFile::open(file.path()).unwrap();
+        assert!(loader.get(0).is_err());
+        assert!(!loader.comments_stripped());
+    }
+
     #[test]
     fn test_single_value_raw_bytes_out_of_bounds() {
         let mut file = NamedTempFile::new().unwrap();
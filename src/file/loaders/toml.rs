@@ -0,0 +1,187 @@
+use crate::error::{Result, ThothError};
+use crate::file::loaders::FileLoader;
+use serde_json::{Map, Number, Value};
+use std::{fs, path::Path};
+
+/// Loader for TOML files
+///
+/// A TOML file is always a single top-level table, so it is parsed during
+/// `open()` and exposed as one root record, the same way `SingleValueFile`
+/// exposes a JSON object.
+pub struct TomlFile {
+    value: Value,
+}
+
+impl TomlFile {
+    /// Open a TOML file and convert the document to JSON
+    pub fn open(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path).map_err(|e| ThothError::FileReadError {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?;
+
+        let table: toml::Table = toml::from_str(&text).map_err(|e| ThothError::FileReadError {
+            path: path.to_path_buf(),
+            reason: format!("Invalid TOML: {}", e),
+        })?;
+
+        Ok(Self {
+            value: toml_to_json(toml::Value::Table(table)),
+        })
+    }
+
+    /// Get the document (always at index 0)
+    pub fn get(&mut self, idx: usize) -> Result<Value> {
+        self.document(idx).cloned()
+    }
+
+    /// Get the document serialized as JSON bytes
+    ///
+    /// Search treats raw records as JSON, so the document is handed out in
+    /// its JSON form rather than as the original TOML text.
+    pub fn raw_document(&self, idx: usize) -> Result<Vec<u8>> {
+        let value = self.document(idx)?;
+        serde_json::to_vec(value).map_err(|e| ThothError::InvalidJsonStructure {
+            reason: format!("Failed to serialize TOML document: {}", e),
+        })
+    }
+
+    fn document(&self, idx: usize) -> Result<&Value> {
+        if idx != 0 {
+            return Err(ThothError::InvalidJsonStructure {
+                reason: format!("TOML document only has index 0, got {}", idx),
+            });
+        }
+        Ok(&self.value)
+    }
+}
+
+impl FileLoader for TomlFile {
+    type Item = Value;
+
+    fn open(path: &Path) -> Result<Self> {
+        TomlFile::open(path)
+    }
+
+    fn len(&self) -> usize {
+        1 // A TOML file is a single table
+    }
+
+    fn get(&mut self, idx: usize) -> Result<Self::Item> {
+        self.get(idx)
+    }
+
+    fn raw_bytes(&self, idx: usize) -> Result<Vec<u8>> {
+        self.raw_document(idx)
+    }
+}
+
+/// Convert a TOML value into the equivalent JSON value.
+///
+/// Tables and arrays (including arrays of tables) map onto objects and
+/// arrays. Datetimes, which JSON has no type for, become their TOML text,
+/// and so do non-finite floats.
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::Number(i.into()),
+        toml::Value::Float(f) => Number::from_f64(f)
+            .map(Value::Number)
+            .unwrap_or_else(|| Value::String(f.to_string())),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(dt) => Value::String(dt.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => {
+            let mut object = Map::with_capacity(table.len());
+            for (key, value) in table {
+                object.insert(key, toml_to_json(value));
+            }
+            Value::Object(object)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_toml_single_root() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"name = \"thoth\"\ntags = [\"json\", \"toml\"]\n")
+            .unwrap();
+        let mut loader = TomlFile::open(file.path()).unwrap();
+        assert_eq!(loader.len(), 1);
+        assert_eq!(
+            loader.get(0).unwrap(),
+            json!({"name": "thoth", "tags": ["json", "toml"]})
+        );
+    }
+
+    #[test]
+    fn test_toml_scalar_types() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"flag = true\ncount = 42\nneg = -7\nratio = 1.5\ninf = inf\n")
+            .unwrap();
+        let mut loader = TomlFile::open(file.path()).unwrap();
+        let val = loader.get(0).unwrap();
+        assert_eq!(val["flag"], Value::Bool(true));
+        assert_eq!(val["count"], json!(42));
+        assert_eq!(val["neg"], json!(-7));
+        assert_eq!(val["ratio"], json!(1.5));
+        assert!(val["inf"].is_string());
+    }
+
+    #[test]
+    fn test_toml_datetimes_become_strings() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"created = 1979-05-27T07:32:00Z\nday = 1979-05-27\nat = 07:32:00\nlocal = 1979-05-27T07:32:00\n").unwrap();
+        let mut loader = TomlFile::open(file.path()).unwrap();
+        let val = loader.get(0).unwrap();
+        assert_eq!(val["created"], json!("1979-05-27T07:32:00Z"));
+        assert_eq!(val["day"], json!("1979-05-27"));
+        assert_eq!(val["at"], json!("07:32:00"));
+        assert_eq!(val["local"], json!("1979-05-27T07:32:00"));
+    }
+
+    #[test]
+    fn test_toml_tables_and_arrays_of_tables() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"[server]\nport = 8080\n\n[server.tls]\nenabled = false\n\n[[users]]\nname = \"a\"\n\n[[users]]\nname = \"b\"\n").unwrap();
+        let mut loader = TomlFile::open(file.path()).unwrap();
+        let val = loader.get(0).unwrap();
+        assert_eq!(
+            val["server"],
+            json!({"port": 8080, "tls": {"enabled": false}})
+        );
+        assert_eq!(val["users"], json!([{"name": "a"}, {"name": "b"}]));
+    }
+
+    #[test]
+    fn test_toml_raw_bytes_are_json() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"id = 1\n").unwrap();
+        let loader = TomlFile::open(file.path()).unwrap();
+        let parsed: Value = serde_json::from_slice(&loader.raw_document(0).unwrap()).unwrap();
+        assert_eq!(parsed, json!({"id": 1}));
+    }
+
+    #[test]
+    fn test_toml_invalid_document_is_error() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"key = [unclosed\n").unwrap();
+        assert!(TomlFile::open(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_toml_out_of_bounds() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"id = 1\n").unwrap();
+        let mut loader = TomlFile::open(file.path()).unwrap();
+        assert!(loader.get(1).is_err());
+        assert!(loader.raw_bytes(1).is_err());
+    }
+}
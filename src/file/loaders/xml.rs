@@ -0,0 +1,285 @@
+use crate::error::{Result, ThothError};
+use crate::file::loaders::FileLoader;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::reader::Reader;
+use serde_json::{Map, Value};
+use std::{fs, path::Path};
+
+/// Key holding an element's attributes
+const ATTRIBUTES_KEY: &str = "@attributes";
+/// Key holding an element's text when it also has attributes or children
+const TEXT_KEY: &str = "#text";
+
+/// Loader for XML files
+///
+/// The whole document is one element tree, so it is converted during
+/// `open()` and exposed as a single root record keyed by the root element's
+/// name, the same way `SingleValueFile` exposes a JSON object.
+pub struct XmlFile {
+    value: Value,
+}
+
+impl XmlFile {
+    /// Open an XML file and convert the document to JSON
+    pub fn open(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path).map_err(|e| ThothError::FileReadError {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?;
+
+        let value = xml_to_json(&text).map_err(|reason| ThothError::FileReadError {
+            path: path.to_path_buf(),
+            reason: format!("Invalid XML: {}", reason),
+        })?;
+        Ok(Self { value })
+    }
+
+    /// Get the document (always at index 0)
+    pub fn get(&mut self, idx: usize) -> Result<Value> {
+        self.document(idx).cloned()
+    }
+
+    /// Get the document serialized as JSON bytes
+    ///
+    /// Search treats raw records as JSON, so the document is handed out in
+    /// its JSON form rather than as the original XML text.
+    pub fn raw_document(&self, idx: usize) -> Result<Vec<u8>> {
+        let value = self.document(idx)?;
+        serde_json::to_vec(value).map_err(|e| ThothError::InvalidJsonStructure {
+            reason: format!("Failed to serialize XML document: {}", e),
+        })
+    }
+
+    fn document(&self, idx: usize) -> Result<&Value> {
+        if idx != 0 {
+            return Err(ThothError::InvalidJsonStructure {
+                reason: format!("XML document only has index 0, got {}", idx),
+            });
+        }
+        Ok(&self.value)
+    }
+}
+
+impl FileLoader for XmlFile {
+    type Item = Value;
+
+    fn open(path: &Path) -> Result<Self> {
+        XmlFile::open(path)
+    }
+
+    fn len(&self) -> usize {
+        1 // An XML file has a single root element
+    }
+
+    fn get(&mut self, idx: usize) -> Result<Self::Item> {
+        self.get(idx)
+    }
+
+    fn raw_bytes(&self, idx: usize) -> Result<Vec<u8>> {
+        self.raw_document(idx)
+    }
+}
+
+/// An element whose end tag hasn't been reached yet
+struct OpenElement {
+    name: String,
+    children: Map<String, Value>,
+    text: String,
+}
+
+impl OpenElement {
+    fn start(start: &BytesStart) -> std::result::Result<Self, String> {
+        let name = String::from_utf8_lossy(start.name().as_ref()).into_owned();
+        let mut attributes = Map::new();
+        for attr in start.attributes() {
+            let attr = attr.map_err(|e| e.to_string())?;
+            let key = String::from_utf8_lossy(attr.key.as_ref()).into_owned();
+            let value = attr.unescape_value().map_err(|e| e.to_string())?;
+            attributes.insert(key, Value::String(value.into_owned()));
+        }
+
+        let mut children = Map::new();
+        if !attributes.is_empty() {
+            children.insert(ATTRIBUTES_KEY.to_string(), Value::Object(attributes));
+        }
+        Ok(Self {
+            name,
+            children,
+            text: String::new(),
+        })
+    }
+
+    /// The element's JSON form: plain text for text-only elements, `null`
+    /// for empty ones, otherwise an object of attributes, children and text
+    fn finish(mut self) -> Value {
+        let text = self.text.trim();
+        if self.children.is_empty() {
+            return if text.is_empty() {
+                Value::Null
+            } else {
+                Value::String(text.to_string())
+            };
+        }
+        if !text.is_empty() {
+            self.children
+                .insert(TEXT_KEY.to_string(), Value::String(text.to_string()));
+        }
+        Value::Object(self.children)
+    }
+}
+
+/// Add a child element, turning repeated names into an array
+fn insert_child(children: &mut Map<String, Value>, name: String, value: Value) {
+    match children.get_mut(&name) {
+        Some(Value::Array(items)) => items.push(value),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = Value::Array(vec![first, value]);
+        }
+        None => {
+            children.insert(name, value);
+        }
+    }
+}
+
+/// Convert an XML document into the equivalent JSON value.
+///
+/// The result is an object with the root element's name as its only key.
+/// Element names keep their namespace prefix (`soap:Envelope`), attributes
+/// go under `@attributes`, text under `#text`, and repeated child elements
+/// become arrays. Text stays a string since XML has no value types.
+fn xml_to_json(text: &str) -> std::result::Result<Value, String> {
+    let mut reader = Reader::from_str(text);
+    let mut stack: Vec<OpenElement> = Vec::new();
+    let mut root = Map::new();
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("{} at byte {}", e, reader.error_position()))?;
+        match event {
+            Event::Start(start) => stack.push(OpenElement::start(&start)?),
+            Event::Empty(start) => {
+                let element = OpenElement::start(&start)?;
+                let name = element.name.clone();
+                let value = element.finish();
+                match stack.last_mut() {
+                    Some(parent) => insert_child(&mut parent.children, name, value),
+                    None => insert_child(&mut root, name, value),
+                }
+            }
+            Event::End(_) => {
+                let element = stack.pop().ok_or("unexpected closing tag")?;
+                let name = element.name.clone();
+                let value = element.finish();
+                match stack.last_mut() {
+                    Some(parent) => insert_child(&mut parent.children, name, value),
+                    None => insert_child(&mut root, name, value),
+                }
+            }
+            Event::Text(content) => {
+                if let Some(element) = stack.last_mut() {
+                    element
+                        .text
+                        .push_str(&content.unescape().map_err(|e| e.to_string())?);
+                }
+            }
+            Event::CData(content) => {
+                if let Some(element) = stack.last_mut() {
+                    element
+                        .text
+                        .push_str(&String::from_utf8_lossy(&content.into_inner()));
+                }
+            }
+            Event::Eof => break,
+            // Declarations, comments, processing instructions and doctypes
+            // carry no data
+            _ => {}
+        }
+    }
+
+    if let Some(element) = stack.last() {
+        return Err(format!("unclosed element <{}>", element.name));
+    }
+    if root.is_empty() {
+        return Err("no root element".to_string());
+    }
+    Ok(Value::Object(root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_xml_single_root() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(
+            b"<?xml version=\"1.0\"?>\n<config>\n  <name>thoth</name>\n  <debug/>\n</config>\n",
+        )
+        .unwrap();
+        let mut loader = XmlFile::open(file.path()).unwrap();
+        assert_eq!(loader.len(), 1);
+        assert_eq!(
+            loader.get(0).unwrap(),
+            json!({"config": {"name": "thoth", "debug": null}})
+        );
+    }
+
+    #[test]
+    fn test_xml_attributes_text_and_repeats() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(br#"<items count="2"><item id="1">first &amp; best</item><item id="2"/><note><![CDATA[<raw>]]></note></items>"#).unwrap();
+        let mut loader = XmlFile::open(file.path()).unwrap();
+        assert_eq!(
+            loader.get(0).unwrap(),
+            json!({"items": {
+                "@attributes": {"count": "2"},
+                "item": [
+                    {"@attributes": {"id": "1"}, "#text": "first & best"},
+                    {"@attributes": {"id": "2"}}
+                ],
+                "note": "<raw>"
+            }})
+        );
+    }
+
+    #[test]
+    fn test_xml_keeps_namespace_prefixes() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(br#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/"><soap:Body><m:Price xmlns:m="urn:x">34.5</m:Price></soap:Body></soap:Envelope>"#).unwrap();
+        let mut loader = XmlFile::open(file.path()).unwrap();
+        let val = loader.get(0).unwrap();
+        let envelope = &val["soap:Envelope"];
+        assert_eq!(
+            envelope["@attributes"]["xmlns:soap"],
+            "http://schemas.xmlsoap.org/soap/envelope/"
+        );
+        assert_eq!(envelope["soap:Body"]["m:Price"]["#text"], "34.5");
+    }
+
+    #[test]
+    fn test_xml_invalid_is_error() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"<a><b></a>").unwrap();
+        assert!(XmlFile::open(file.path()).is_err());
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"<a><b></b>").unwrap();
+        assert!(XmlFile::open(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_xml_only_index_zero() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"<a>1</a>").unwrap();
+        let mut loader = XmlFile::open(file.path()).unwrap();
+        assert!(loader.get(1).is_err());
+        assert_eq!(
+            serde_json::from_slice::<Value>(&loader.raw_document(0).unwrap()).unwrap(),
+            json!({"a": "1"})
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use crate::error::{Result, ThothError};
+use crate::file::loaders::FileLoader;
+use serde::Deserialize;
+use serde_json::{Map, Number, Value};
+use std::{fs, path::Path};
+
+/// Loader for YAML files, including multi-document streams
+///
+/// YAML has no cheap record boundaries (anchors, block scalars and flow
+/// collections can all span `---`-looking lines), so the whole stream is
+/// parsed during `open()`. Each document becomes one root record, the same
+/// way each line of an NDJSON file does.
+pub struct YamlFile {
+    documents: Vec<Value>,
+}
+
+impl YamlFile {
+    /// Open a YAML file and convert every document to JSON
+    ///
+    /// Aliases are expanded and `<<` merge keys applied before conversion.
+    pub fn open(path: &Path) -> Result<Self> {
+        let bytes = fs::read(path).map_err(|e| ThothError::FileReadError {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?;
+
+        let invalid = |e: serde_yaml::Error| ThothError::FileReadError {
+            path: path.to_path_buf(),
+            reason: format!("Invalid YAML: {}", e),
+        };
+
+        let mut documents = Vec::new();
+        for document in serde_yaml::Deserializer::from_slice(&bytes) {
+            let mut value = serde_yaml::Value::deserialize(document).map_err(invalid)?;
+            value.apply_merge().map_err(invalid)?;
+            // Empty documents (e.g. a trailing `---`) carry no data
+            if !value.is_null() {
+                documents.push(yaml_to_json(value));
+            }
+        }
+
+        Ok(Self { documents })
+    }
+
+    /// Returns the number of documents in the stream
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Get the document at the specified index
+    pub fn get(&mut self, idx: usize) -> Result<Value> {
+        self.document(idx).cloned()
+    }
+
+    /// Get the document at the specified index serialized as JSON bytes
+    ///
+    /// Search treats raw records as JSON, so documents are handed out in
+    /// their JSON form rather than as the original YAML text.
+    pub fn raw_document(&self, idx: usize) -> Result<Vec<u8>> {
+        let value = self.document(idx)?;
+        serde_json::to_vec(value).map_err(|e| ThothError::InvalidJsonStructure {
+            reason: format!("Failed to serialize YAML document {}: {}", idx, e),
+        })
+    }
+
+    fn document(&self, idx: usize) -> Result<&Value> {
+        self.documents
+            .get(idx)
+            .ok_or_else(|| ThothError::InvalidJsonStructure {
+                reason: format!("YAML document index {} out of bounds", idx),
+            })
+    }
+}
+
+impl FileLoader for YamlFile {
+    type Item = Value;
+
+    fn open(path: &Path) -> Result<Self> {
+        YamlFile::open(path)
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn get(&mut self, idx: usize) -> Result<Self::Item> {
+        self.get(idx)
+    }
+
+    fn raw_bytes(&self, idx: usize) -> Result<Vec<u8>> {
+        self.raw_document(idx)
+    }
+}
+
+/// Convert a YAML value into the equivalent JSON value.
+///
+/// Scalars keep their resolved types (so `true`, `42`, `1.5` and `~` stay
+/// bool/number/null), tags are dropped in favour of the tagged value, and
+/// non-finite floats, which JSON cannot represent, become strings.
+fn yaml_to_json(value: serde_yaml::Value) -> Value {
+    match value {
+        serde_yaml::Value::Null => Value::Null,
+        serde_yaml::Value::Bool(b) => Value::Bool(b),
+        serde_yaml::Value::Number(n) => number_to_json(&n),
+        serde_yaml::Value::String(s) => Value::String(s),
+        serde_yaml::Value::Sequence(items) => {
+            Value::Array(items.into_iter().map(yaml_to_json).collect())
+        }
+        serde_yaml::Value::Mapping(mapping) => {
+            let mut object = Map::with_capacity(mapping.len());
+            for (key, value) in mapping {
+                object.insert(key_to_string(key), yaml_to_json(value));
+            }
+            Value::Object(object)
+        }
+        serde_yaml::Value::Tagged(tagged) => yaml_to_json(tagged.value),
+    }
+}
+
+fn number_to_json(n: &serde_yaml::Number) -> Value {
+    if let Some(i) = n.as_i64() {
+        Value::Number(i.into())
+    } else if let Some(u) = n.as_u64() {
+        Value::Number(u.into())
+    } else {
+        n.as_f64()
+            .and_then(Number::from_f64)
+            .map(Value::Number)
+            .unwrap_or_else(|| Value::String(n.to_string()))
+    }
+}
+
+/// JSON object keys must be strings; render other YAML keys as text.
+fn key_to_string(key: serde_yaml::Value) -> String {
+    match key {
+        serde_yaml::Value::String(s) => s,
+        serde_yaml::Value::Null => "null".to_string(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::Tagged(tagged) => key_to_string(tagged.value),
+        complex => yaml_to_json(complex).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_yaml_single_document() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"name: thoth\ntags:\n  - json\n  - yaml\n")
+            .unwrap();
+        let mut loader = YamlFile::open(file.path()).unwrap();
+        assert_eq!(loader.len(), 1);
+        assert_eq!(
+            loader.get(0).unwrap(),
+            json!({"name": "thoth", "tags": ["json", "yaml"]})
+        );
+    }
+
+    #[test]
+    fn test_yaml_multi_document_stream() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"---\nid: 1\n---\nid: 2\n---\n- a\n- b\n---\n")
+            .unwrap();
+        let mut loader = YamlFile::open(file.path()).unwrap();
+        assert_eq!(loader.len(), 3);
+        assert_eq!(loader.get(0).unwrap(), json!({"id": 1}));
+        assert_eq!(loader.get(1).unwrap(), json!({"id": 2}));
+        assert_eq!(loader.get(2).unwrap(), json!(["a", "b"]));
+    }
+
+    #[test]
+    fn test_yaml_scalar_types() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"flag: true\ncount: 42\nneg: -7\nratio: 1.5\nnothing: ~\nempty:\nquoted: \"42\"\ninf: .inf\n").unwrap();
+        let mut loader = YamlFile::open(file.path()).unwrap();
+        let val = loader.get(0).unwrap();
+        assert_eq!(val["flag"], Value::Bool(true));
+        assert_eq!(val["count"], json!(42));
+        assert_eq!(val["neg"], json!(-7));
+        assert_eq!(val["ratio"], json!(1.5));
+        assert_eq!(val["nothing"], Value::Null);
+        assert_eq!(val["empty"], Value::Null);
+        assert_eq!(val["quoted"], json!("42"));
+        assert!(val["inf"].is_string());
+    }
+
+    #[test]
+    fn test_yaml_anchors_aliases_and_merge_keys() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"defaults: &defaults\n  retries: 3\n  timeout: 10\nprimary:\n  <<: *defaults\n  timeout: 30\ncopy: *defaults\n").unwrap();
+        let mut loader = YamlFile::open(file.path()).unwrap();
+        let val = loader.get(0).unwrap();
+        assert_eq!(val["primary"], json!({"retries": 3, "timeout": 30}));
+        assert_eq!(val["copy"], json!({"retries": 3, "timeout": 10}));
+    }
+
+    #[test]
+    fn test_yaml_non_string_keys_and_tags() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"1: one\ntrue: yes\nnull: nil\nvalue: !custom 5\n")
+            .unwrap();
+        let mut loader = YamlFile::open(file.path()).unwrap();
+        let val = loader.get(0).unwrap();
+        assert_eq!(val["1"], "one");
+        assert_eq!(val["true"], "yes");
+        assert_eq!(val["null"], "nil");
+        assert_eq!(val["value"], json!(5));
+    }
+
+    #[test]
+    fn test_yaml_raw_bytes_are_json() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"id: 1\n").unwrap();
+        let loader = YamlFile::open(file.path()).unwrap();
+        let parsed: Value = serde_json::from_slice(&loader.raw_document(0).unwrap()).unwrap();
+        assert_eq!(parsed, json!({"id": 1}));
+    }
+
+    #[test]
+    fn test_yaml_invalid_document_is_error() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"key: [unclosed\n").unwrap();
+        assert!(YamlFile::open(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_yaml_out_of_bounds() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"id: 1\n").unwrap();
+        let mut loader = YamlFile::open(file.path()).unwrap();
+        assert!(loader.get(1).is_err());
+    }
+}
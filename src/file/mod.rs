@@ -1,4 +1,11 @@
 pub mod detect_file_type;
+pub mod edits;
+pub mod export;
+pub mod gzip;
+pub mod jsonc;
 pub mod lazy_loader;
 pub mod loaders;
+pub mod open_progress;
+pub mod schema;
 pub mod to_dataset;
+pub mod watcher;
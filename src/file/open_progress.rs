@@ -0,0 +1,88 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use crate::error::{Result, ThothError};
+
+/// Progress of a file being indexed while it opens, shared between the
+/// thread opening it and the UI showing it
+///
+/// Loaders that index the whole file up front report the bytes they have
+/// scanned and check [`is_canceled`](Self::is_canceled) between chunks;
+/// others never set a total, which the UI shows as an indeterminate spinner.
+#[derive(Debug, Default)]
+pub struct OpenProgress {
+    scanned: AtomicU64,
+    total: AtomicU64,
+    canceled: AtomicBool,
+}
+
+impl OpenProgress {
+    /// Start counting towards `bytes` scanned in total
+    pub fn set_total(&self, bytes: u64) {
+        self.scanned.store(0, Ordering::Relaxed);
+        self.total.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Count `bytes` more as scanned
+    pub fn advance(&self, bytes: u64) {
+        self.scanned.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Bytes scanned so far and the total, once known
+    pub fn bytes(&self) -> (u64, Option<u64>) {
+        let total = self.total.load(Ordering::Relaxed);
+        let scanned = self.scanned.load(Ordering::Relaxed).min(total);
+        (scanned, (total > 0).then_some(total))
+    }
+
+    /// Share of the file scanned, in `0.0..=1.0`, once the total is known
+    pub fn fraction(&self) -> Option<f32> {
+        let (scanned, total) = self.bytes();
+        total.map(|total| scanned as f32 / total as f32)
+    }
+
+    /// Ask the loader to stop at its next check
+    pub fn cancel(&self) {
+        self.canceled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_canceled(&self) -> bool {
+        self.canceled.load(Ordering::Relaxed)
+    }
+
+    /// Fail with [`ThothError::OpenCanceled`] once canceling was asked for
+    pub fn check(&self, path: &Path) -> Result<()> {
+        if self.is_canceled() {
+            return Err(ThothError::OpenCanceled {
+                path: path.to_path_buf(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fraction_and_cancel() {
+        let progress = OpenProgress::default();
+        assert_eq!(progress.fraction(), None);
+        assert!(progress.check(Path::new("a.json")).is_ok());
+
+        progress.set_total(200);
+        progress.advance(50);
+        assert_eq!(progress.bytes(), (50, Some(200)));
+        assert_eq!(progress.fraction(), Some(0.25));
+        // Overshooting the total never reads as more than done
+        progress.advance(500);
+        assert_eq!(progress.fraction(), Some(1.0));
+
+        progress.cancel();
+        assert!(matches!(
+            progress.check(Path::new("a.json")),
+            Err(ThothError::OpenCanceled { .. })
+        ));
+    }
+}
@@ -0,0 +1,277 @@
+use crate::constants::{MAX_SCHEMA_ERRORS_PER_RECORD, SCHEMA_VALIDATION_FRAME_BUDGET};
+use crate::error::{Result, ThothError};
+use crate::file::loaders::FileType;
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+/// A compiled JSON Schema that root records are checked against
+///
+/// `$ref`s inside the schema are resolved when it is compiled; relative
+/// references to other files resolve against the schema's own location.
+pub struct SchemaValidator {
+    path: PathBuf,
+    validator: jsonschema::Validator,
+}
+
+/// One schema violation inside a root record
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    /// Row path of the offending value (e.g. `3.user.items[2]`)
+    pub path: String,
+    pub message: String,
+}
+
+impl SchemaValidator {
+    /// Read and compile a JSON Schema file
+    pub fn open(path: &Path) -> Result<Self> {
+        let invalid = |reason: String| ThothError::FileReadError {
+            path: path.to_path_buf(),
+            reason: format!("Invalid JSON Schema: {}", reason),
+        };
+
+        let text = fs::read_to_string(path).map_err(|e| ThothError::FileReadError {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?;
+        let schema: Value = serde_json::from_str(&text).map_err(|e| invalid(e.to_string()))?;
+
+        let base_uri = file_uri(&crate::platform::canonical_path(path));
+        let validator = jsonschema::options()
+            .with_base_uri(base_uri)
+            .build(&schema)
+            .map_err(|e| invalid(e.to_string()))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            validator,
+        })
+    }
+
+    /// The schema file this validator was compiled from
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Check one root record, returning at most `MAX_SCHEMA_ERRORS_PER_RECORD` violations
+    pub fn validate(&self, record_index: usize, value: &Value) -> Vec<SchemaViolation> {
+        self.validator
+            .iter_errors(value)
+            .take(MAX_SCHEMA_ERRORS_PER_RECORD)
+            .map(|error| SchemaViolation {
+                path: pointer_to_row_path(record_index, value, error.instance_path().as_str()),
+                message: error.to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Incremental validation of every root record in a file
+///
+/// Records are checked in file order within a per-frame time budget, so a
+/// multi-GB file keeps rendering while its records are validated.
+pub struct SchemaValidation {
+    validator: Arc<SchemaValidator>,
+    next_record: usize,
+    total: usize,
+    violations: BTreeMap<usize, Vec<SchemaViolation>>,
+}
+
+impl SchemaValidation {
+    pub fn new(validator: Arc<SchemaValidator>, total: usize) -> Self {
+        Self {
+            validator,
+            next_record: 0,
+            total,
+            violations: BTreeMap::new(),
+        }
+    }
+
+    pub fn validator(&self) -> &Arc<SchemaValidator> {
+        &self.validator
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.next_record >= self.total
+    }
+
+    /// `(records checked, total records)`
+    pub fn progress(&self) -> (usize, usize) {
+        (self.next_record, self.total)
+    }
+
+    /// Violations of every invalid record checked so far, by root index
+    pub fn violations(&self) -> &BTreeMap<usize, Vec<SchemaViolation>> {
+        &self.violations
+    }
+
+    /// Validate records until `SCHEMA_VALIDATION_FRAME_BUDGET` runs out
+    ///
+    /// Returns the root indices of records found invalid in this step.
+    /// Records that can't be parsed are skipped; opening them reports the error.
+    pub fn step(&mut self, loader: &mut FileType) -> Vec<usize> {
+        let started = Instant::now();
+        let mut invalid = Vec::new();
+        while !self.is_done() && started.elapsed() < SCHEMA_VALIDATION_FRAME_BUDGET {
+            let record_index = self.next_record;
+            self.next_record += 1;
+            let Ok(value) = loader.get(record_index) else {
+                continue;
+            };
+            let violations = self.validator.validate(record_index, &value);
+            if !violations.is_empty() {
+                self.violations.insert(record_index, violations);
+                invalid.push(record_index);
+            }
+        }
+        invalid
+    }
+}
+
+/// Convert a JSON Pointer inside a record (`/user/items/2`) into a row path
+/// (`3.user.items[2]`)
+///
+/// The record is walked alongside the pointer so that numeric segments only
+/// become array indices where the value really is an array.
+fn pointer_to_row_path(record_index: usize, value: &Value, pointer: &str) -> String {
+    let mut path = record_index.to_string();
+    let mut current = Some(value);
+    for segment in pointer.split('/').skip(1) {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        match current {
+            Some(Value::Array(items)) if segment.parse::<usize>().is_ok() => {
+                let idx: usize = segment.parse().unwrap_or_default();
+                path.push_str(&format!("[{}]", idx));
+                current = items.get(idx);
+            }
+            _ => {
+                path.push('.');
+                path.push_str(&segment);
+                current = current.and_then(|v| v.get(&segment));
+            }
+        }
+    }
+    path
+}
+
+/// Build a `file://` URI for an absolute path, used as the schema's base URI
+fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    if path.starts_with('/') {
+        format!("file://{}", path)
+    } else {
+        // Windows drive paths (C:/...) need an extra slash
+        format!("file:///{}", path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::loaders::NdjsonFile;
+    use serde_json::json;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn schema_file(dir: &Path, name: &str, schema: &Value) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, schema.to_string()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_pointer_to_row_path() {
+        let value = json!({"user": {"items": [1, {"a/b": 2}]}, "7": {"x": 1}});
+        assert_eq!(pointer_to_row_path(3, &value, ""), "3");
+        assert_eq!(
+            pointer_to_row_path(3, &value, "/user/items/1"),
+            "3.user.items[1]"
+        );
+        assert_eq!(
+            pointer_to_row_path(3, &value, "/user/items/1/a~1b"),
+            "3.user.items[1].a/b"
+        );
+        // A numeric key on an object stays a key
+        assert_eq!(pointer_to_row_path(0, &value, "/7/x"), "0.7.x");
+    }
+
+    #[test]
+    fn test_validate_resolves_refs() {
+        let dir = tempfile::tempdir().unwrap();
+        schema_file(
+            dir.path(),
+            "item.json",
+            &json!({"type": "object", "required": ["price"]}),
+        );
+        let path = schema_file(
+            dir.path(),
+            "schema.json",
+            &json!({
+                "type": "object",
+                "properties": {
+                    "user": {"$ref": "#/$defs/user"},
+                    "items": {"type": "array", "items": {"$ref": "item.json"}}
+                },
+                "$defs": {
+                    "user": {"type": "object", "properties": {"age": {"type": "integer"}}}
+                }
+            }),
+        );
+        let validator = SchemaValidator::open(&path).unwrap();
+
+        let valid = json!({"user": {"age": 3}, "items": [{"price": 1}]});
+        assert!(validator.validate(0, &valid).is_empty());
+
+        let invalid = json!({"user": {"age": "old"}, "items": [{"price": 1}, {}]});
+        let mut paths: Vec<String> = validator
+            .validate(5, &invalid)
+            .into_iter()
+            .map(|v| v.path)
+            .collect();
+        paths.sort();
+        assert_eq!(paths, ["5.items[1]", "5.user.age"]);
+    }
+
+    #[test]
+    fn test_open_rejects_invalid_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let not_json = dir.path().join("broken.json");
+        fs::write(&not_json, "{").unwrap();
+        assert!(SchemaValidator::open(&not_json).is_err());
+
+        let bad_keyword = schema_file(dir.path(), "bad.json", &json!({"type": 12}));
+        assert!(SchemaValidator::open(&bad_keyword).is_err());
+    }
+
+    #[test]
+    fn test_incremental_validation_collects_invalid_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = schema_file(
+            dir.path(),
+            "schema.json",
+            &json!({"type": "object", "required": ["id"]}),
+        );
+        let validator = Arc::new(SchemaValidator::open(&path).unwrap());
+
+        let mut tmp = NamedTempFile::new().unwrap();
+        for line in [r#"{"id":1}"#, r#"{"name":"x"}"#, r#"{"id":3}"#, r#"{}"#] {
+            writeln!(tmp, "{}", line).unwrap();
+        }
+        tmp.flush().unwrap();
+        let mut loader = FileType::Ndjson(NdjsonFile::open(tmp.path()).unwrap());
+
+        let mut validation = SchemaValidation::new(validator, loader.len());
+        let mut invalid = Vec::new();
+        while !validation.is_done() {
+            invalid.extend(validation.step(&mut loader));
+        }
+        assert_eq!(invalid, [1, 3]);
+        assert_eq!(validation.progress(), (4, 4));
+        assert_eq!(validation.violations()[&1][0].path, "1");
+    }
+}
@@ -16,6 +16,7 @@
 use serde_json::Value;
 
 use crate::file::loaders::FileType;
+use crate::helpers::is_integer;
 
 /// Rows read from the file (bounds the crossing for large files).
 const CAP: usize = 5000;
@@ -125,12 +126,7 @@ fn cell_string(v: &Value) -> String {
 
 fn type_hint(v: &Value) -> String {
     match v {
-        Value::Number(n) => if n.is_i64() || n.is_u64() {
-            "integer"
-        } else {
-            "float"
-        }
-        .to_string(),
+        Value::Number(n) => if is_integer(n) { "integer" } else { "float" }.to_string(),
         Value::Bool(_) => "boolean".to_string(),
         Value::String(_) => "text".to_string(),
         _ => String::new(),
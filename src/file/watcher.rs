@@ -0,0 +1,166 @@
+use crate::constants::FILE_RELOAD_DEBOUNCE;
+use crate::error::{Result, ThothError};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    time::{Duration, Instant},
+};
+
+/// Watches one file for changes written by other programs
+///
+/// The parent directory is watched rather than the file itself: many editors
+/// save by writing a temp file and renaming it over the original, which would
+/// leave a watch on the file's old inode. Bursts of events are debounced, so a
+/// save made of several writes is reported once.
+pub struct FileWatcher {
+    path: PathBuf,
+    file_name: Option<OsString>,
+    events: Receiver<notify::Result<Event>>,
+    debounce: Debounce,
+    // Dropping the watcher stops it, so it is kept alive alongside the receiver
+    _watcher: RecommendedWatcher,
+}
+
+impl FileWatcher {
+    /// Start watching `path`; `wake` is called from the watcher thread on every
+    /// event so an idle UI can schedule a repaint
+    pub fn new(path: &Path, wake: impl Fn() + Send + 'static) -> Result<Self> {
+        let canonical = crate::platform::canonical_path(path);
+        let watch_error = |e: notify::Error| ThothError::FileReadError {
+            path: path.to_path_buf(),
+            reason: format!("Failed to watch file: {}", e),
+        };
+
+        let (tx, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            let _ = tx.send(event);
+            wake();
+        })
+        .map_err(watch_error)?;
+        let dir = canonical.parent().unwrap_or(&canonical);
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(watch_error)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file_name: canonical.file_name().map(OsString::from),
+            events,
+            debounce: Debounce::default(),
+            _watcher: watcher,
+        })
+    }
+
+    /// The path this watcher was created for
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Drain pending events; returns true once a burst of changes has settled
+    /// and the file should be reloaded
+    pub fn poll(&mut self) -> bool {
+        let now = Instant::now();
+        while let Ok(event) = self.events.try_recv() {
+            if let Ok(event) = event
+                && self.is_change_to_file(&event)
+            {
+                self.debounce.record(now);
+            }
+        }
+        self.debounce.ready(now)
+    }
+
+    /// Time left before a pending change settles, if one is waiting
+    pub fn pending_delay(&self) -> Option<Duration> {
+        self.debounce.remaining(Instant::now())
+    }
+
+    fn is_change_to_file(&self, event: &Event) -> bool {
+        matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+            && event
+                .paths
+                .iter()
+                .any(|p| p.file_name() == self.file_name.as_deref())
+    }
+}
+
+/// Trailing-edge debounce: fires once no change has been recorded for
+/// `FILE_RELOAD_DEBOUNCE`
+#[derive(Debug, Default)]
+struct Debounce {
+    last_change: Option<Instant>,
+}
+
+impl Debounce {
+    fn record(&mut self, at: Instant) {
+        self.last_change = Some(at);
+    }
+
+    fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.last_change
+            .map(|at| FILE_RELOAD_DEBOUNCE.saturating_sub(now.duration_since(at)))
+    }
+
+    fn ready(&mut self, now: Instant) -> bool {
+        if self.remaining(now).is_some_and(|left| left.is_zero()) {
+            self.last_change = None;
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::thread;
+
+    #[test]
+    fn test_debounce_waits_for_quiet_period() {
+        let start = Instant::now();
+        let mut debounce = Debounce::default();
+        assert!(!debounce.ready(start));
+
+        debounce.record(start);
+        // A second write shortly after the first restarts the wait
+        debounce.record(start + FILE_RELOAD_DEBOUNCE / 2);
+        assert!(!debounce.ready(start + FILE_RELOAD_DEBOUNCE));
+        assert!(debounce.ready(start + FILE_RELOAD_DEBOUNCE * 2));
+
+        // Fires once per burst
+        assert!(!debounce.ready(start + FILE_RELOAD_DEBOUNCE * 3));
+    }
+
+    #[test]
+    fn test_watcher_reports_writes_to_the_file_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.json");
+        let other = dir.path().join("other.json");
+        fs::write(&path, "{}").unwrap();
+
+        let mut watcher = FileWatcher::new(&path, || {}).unwrap();
+
+        fs::write(&other, "{}").unwrap();
+        thread::sleep(FILE_RELOAD_DEBOUNCE * 2);
+        assert!(!watcher.poll());
+
+        fs::write(&path, r#"{"a":1}"#).unwrap();
+        fs::write(&path, r#"{"a":2}"#).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut reloads = 0;
+        while Instant::now() < deadline && reloads == 0 {
+            thread::sleep(Duration::from_millis(50));
+            if watcher.poll() {
+                reloads += 1;
+            }
+        }
+        assert_eq!(reloads, 1);
+
+        // The two writes were folded into one reload
+        thread::sleep(FILE_RELOAD_DEBOUNCE * 2);
+        assert!(!watcher.poll());
+    }
+}
@@ -0,0 +1,76 @@
+use serde_json::Value;
+
+/// Flatten `value` into its leaf `(path, value)` pairs, in document order
+///
+/// Paths continue `prefix` the way tree row paths do: `.key` for object
+/// members and `[idx]` for array elements. Primitives and empty objects or
+/// arrays are leaves; a primitive `value` yields itself under `prefix`.
+pub fn flatten_value(value: &Value, prefix: &str) -> Vec<(String, Value)> {
+    let mut out = Vec::new();
+    flatten_into(value, prefix, &mut out);
+    out
+}
+
+fn flatten_into(value: &Value, path: &str, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, val) in map {
+                flatten_into(val, &format!("{}.{}", path, key), out);
+            }
+        }
+        Value::Array(arr) if !arr.is_empty() => {
+            for (idx, val) in arr.iter().enumerate() {
+                flatten_into(val, &format!("{}[{}]", path, idx), out);
+            }
+        }
+        _ => out.push((path.to_string(), value.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_flatten_nested_objects_and_arrays() {
+        let value = json!({
+            "debug": false,
+            "name": "thoth",
+            "server": {"hosts": ["a", "b"], "port": 8080}
+        });
+        let leaves = flatten_value(&value, "0");
+        let paths: Vec<&str> = leaves.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "0.debug",
+                "0.name",
+                "0.server.hosts[0]",
+                "0.server.hosts[1]",
+                "0.server.port"
+            ]
+        );
+        assert_eq!(leaves[2].1, json!("a"));
+    }
+
+    #[test]
+    fn test_flatten_keeps_empty_containers_as_leaves() {
+        let value = json!([{"tags": []}, {"meta": {}}]);
+        assert_eq!(
+            flatten_value(&value, ""),
+            vec![
+                ("[0].tags".to_string(), json!([])),
+                ("[1].meta".to_string(), json!({}))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flatten_primitive_is_its_own_leaf() {
+        assert_eq!(
+            flatten_value(&json!(3), "7"),
+            vec![("7".to_string(), json!(3))]
+        );
+    }
+}
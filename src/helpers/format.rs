@@ -1,20 +1,50 @@
-use serde_json::Value;
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
 
-pub fn format_simple_kv(key: &str, val: &Value) -> String {
+/// How numeric values are displayed; the values themselves are never changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberFormat {
+    /// As stored: `1000000`
+    #[default]
+    Plain,
+    /// Thousands separators: `1,000,000`
+    Grouped,
+    /// Scientific notation: `1e6`
+    Scientific,
+}
+
+impl NumberFormat {
+    pub const ALL: [NumberFormat; 3] = [
+        NumberFormat::Plain,
+        NumberFormat::Grouped,
+        NumberFormat::Scientific,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NumberFormat::Plain => "Plain (1000000)",
+            NumberFormat::Grouped => "Grouped (1,000,000)",
+            NumberFormat::Scientific => "Scientific (1e6)",
+        }
+    }
+}
+
+pub fn format_simple_kv(key: &str, val: &Value, number_format: NumberFormat) -> String {
     match val {
         Value::String(s) => {
             let escaped = s.replace('\\', "\\\\").replace('"', "\\\"");
             format!("\"{key}\": \"{escaped}\"")
         }
-        _ => format!("\"{key}\": {}", preview_value(val)),
+        _ => format!("\"{key}\": {}", preview_value(val, number_format)),
     }
 }
 
-pub fn preview_value(val: &Value) -> String {
+pub fn preview_value(val: &Value, number_format: NumberFormat) -> String {
     match val {
         Value::Null => "null".into(),
         Value::Bool(b) => b.to_string(),
-        Value::Number(n) => n.to_string(),
+        Value::Number(n) => format_number(n, number_format),
         Value::String(s) => {
             let escaped = s.replace('\\', "\\\\").replace('"', "\\\"");
             // truncate long strings for list view
@@ -38,6 +68,168 @@ pub fn preview_value(val: &Value) -> String {
     }
 }
 
+/// Display a JSON number in `format`
+///
+/// Numbers are formatted from their source text, so integers of any size
+/// (64-bit ids, token amounts) keep every digit, and floats keep a fractional
+/// part (`1.0e6`, `1,000.0`) so they still read as floats.
+pub fn format_number(n: &Number, format: NumberFormat) -> String {
+    match format {
+        NumberFormat::Plain => n.to_string(),
+        NumberFormat::Grouped => {
+            let text = n.to_string();
+            // Floats large or small enough to print with an exponent stay as-is
+            if text.contains(['e', 'E']) {
+                return text;
+            }
+            let (sign, unsigned) = match text.strip_prefix('-') {
+                Some(rest) => ("-", rest),
+                None => ("", text.as_str()),
+            };
+            let (int, fraction) = match unsigned.find('.') {
+                Some(dot) => unsigned.split_at(dot),
+                None => (unsigned, ""),
+            };
+            let mut grouped = String::with_capacity(text.len() + int.len() / 3);
+            grouped.push_str(sign);
+            for (i, digit) in int.chars().enumerate() {
+                if i > 0 && (int.len() - i) % 3 == 0 {
+                    grouped.push(',');
+                }
+                grouped.push(digit);
+            }
+            grouped.push_str(fraction);
+            grouped
+        }
+        NumberFormat::Scientific => {
+            if is_integer(n) {
+                integer_scientific(&n.to_string())
+            } else {
+                let text = format!("{:e}", n.as_f64().unwrap_or(f64::NAN));
+                match text.split_once('e') {
+                    Some((mantissa, exponent)) if !mantissa.contains('.') => {
+                        format!("{}.0e{}", mantissa, exponent)
+                    }
+                    _ => text,
+                }
+            }
+        }
+    }
+}
+
+/// Whether `n` was written as an integer, however many digits it has
+///
+/// Integers beyond `i64`/`u64` fail `is_i64`/`is_u64` even though they
+/// aren't floats.
+pub fn is_integer(n: &Number) -> bool {
+    !n.to_string().contains(['.', 'e', 'E'])
+}
+
+/// An integer's decimal text in `{:e}` form (`1.2e3`), exact at any length
+fn integer_scientific(text: &str) -> String {
+    let (sign, digits) = match text.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", text),
+    };
+    let (lead, rest) = digits.split_at(1);
+    let rest = rest.trim_end_matches('0');
+    let exponent = digits.len() - 1;
+    if rest.is_empty() {
+        format!("{}{}e{}", sign, lead, exponent)
+    } else {
+        format!("{}{}.{}e{}", sign, lead, rest, exponent)
+    }
+}
+
+/// Unix seconds from 2001-09-09 up to 5138; smaller integers are more often
+/// counts or ids than dates
+const UNIX_SECONDS: std::ops::Range<f64> = 1e9..1e11;
+/// The same span in milliseconds
+const UNIX_MILLIS: std::ops::Range<f64> = 1e12..1e14;
+
+/// Readable UTC form of a value that looks like a timestamp, such as
+/// `2024-01-01 00:00 UTC`
+///
+/// RFC 3339 strings are always recognised. Numbers are only read as unix
+/// seconds or milliseconds when `key` matches one of `field_patterns`, since
+/// an integer like `1704067200` is just as likely to be an id.
+pub fn humanize_timestamp(
+    key: Option<&str>,
+    val: &Value,
+    field_patterns: &[String],
+) -> Option<String> {
+    let datetime = match val {
+        Value::String(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .ok()?
+            .with_timezone(&chrono::Utc),
+        Value::Number(n) => {
+            let key = key?;
+            if !field_patterns
+                .iter()
+                .any(|pattern| matches_pattern(pattern, key))
+            {
+                return None;
+            }
+            let n = n.as_f64()?;
+            let millis = if UNIX_SECONDS.contains(&n) {
+                n * 1000.0
+            } else if UNIX_MILLIS.contains(&n) {
+                n
+            } else {
+                return None;
+            };
+            chrono::DateTime::from_timestamp_millis(millis as i64)?
+        }
+        _ => return None,
+    };
+    Some(datetime.format("%Y-%m-%d %H:%M UTC").to_string())
+}
+
+/// Case-insensitive match of a name against a pattern where `*` stands for
+/// any run of characters (`*_at`, `ts_*`, `*.log`)
+///
+/// Surrounding whitespace in the pattern is ignored; an empty pattern matches
+/// nothing.
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    let name = name.to_lowercase();
+    if pattern.is_empty() {
+        return false;
+    }
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No `*`: the whole name must match
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Human-readable byte size with one decimal above a kilobyte (`1.2 GB`)
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
 pub fn format_date(date: &str) -> String {
     if let Ok(datetime) = chrono::DateTime::parse_from_rfc3339(date) {
         format_date_static(&datetime)
@@ -79,10 +271,96 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_matches_pattern() {
+        assert!(matches_pattern("*_at", "created_at"));
+        assert!(matches_pattern("*_AT", "Updated_At"));
+        assert!(!matches_pattern("*_at", "format"));
+        assert!(matches_pattern(" *time* ", "timeout_ms"));
+        assert!(matches_pattern("ts", "TS"));
+        assert!(!matches_pattern("ts", "tsx"));
+        assert!(matches_pattern("ts_*_utc", "ts_start_utc"));
+        assert!(!matches_pattern("a*a", "a"));
+        assert!(!matches_pattern("", "anything"));
+    }
+
+    #[test]
+    fn test_humanize_timestamp() {
+        let patterns = vec!["*_at".to_string()];
+        let humanize = |key: &str, val: Value| humanize_timestamp(Some(key), &val, &patterns);
+
+        assert_eq!(
+            humanize("created_at", json!(1704067200)).as_deref(),
+            Some("2024-01-01 00:00 UTC")
+        );
+        assert_eq!(
+            humanize("created_at", json!(1704067200123u64)).as_deref(),
+            Some("2024-01-01 00:00 UTC")
+        );
+        assert_eq!(
+            humanize("created_at", json!(1704067200.5)).as_deref(),
+            Some("2024-01-01 00:00 UTC")
+        );
+        // Could be an id: left alone unless the field name matches
+        assert_eq!(humanize("id", json!(1704067200)), None);
+        assert_eq!(
+            humanize_timestamp(None, &json!(1704067200), &patterns),
+            None
+        );
+        // Too small or large to be a plausible date
+        assert_eq!(humanize("created_at", json!(42)), None);
+        assert_eq!(humanize("created_at", json!(1e17)), None);
+
+        // ISO strings are recognised whatever the field is called
+        assert_eq!(
+            humanize("when", json!("2024-01-01T02:30:00+02:00")).as_deref(),
+            Some("2024-01-01 00:30 UTC")
+        );
+        assert_eq!(humanize("created_at", json!("2024-01-01")), None);
+        assert_eq!(humanize("created_at", json!("yesterday")), None);
+        assert_eq!(humanize("created_at", json!(true)), None);
+    }
+
+    #[test]
+    fn test_big_integers_keep_every_digit() {
+        let big: Value = serde_json::from_str("123456789012345678901234567890").unwrap();
+        let Value::Number(n) = &big else {
+            panic!("not a number: {}", big);
+        };
+        assert!(is_integer(n));
+        assert_eq!(
+            preview_value(&big, NumberFormat::Plain),
+            "123456789012345678901234567890"
+        );
+        assert_eq!(
+            format_number(n, NumberFormat::Grouped),
+            "123,456,789,012,345,678,901,234,567,890"
+        );
+        assert_eq!(
+            format_number(n, NumberFormat::Scientific),
+            "1.2345678901234567890123456789e29"
+        );
+        assert_eq!(
+            format_number(&Number::from(-1200), NumberFormat::Scientific),
+            "-1.2e3"
+        );
+        assert_eq!(
+            format_number(&Number::from(0), NumberFormat::Scientific),
+            "0e0"
+        );
+
+        let float: Value = serde_json::from_str("1.5e3").unwrap();
+        let Value::Number(f) = &float else {
+            panic!("not a number: {}", float);
+        };
+        assert!(!is_integer(f));
+        assert_eq!(format_number(f, NumberFormat::Scientific), "1.5e3");
+    }
+
     #[test]
     fn test_format_simple_kv_escapes_quotes_in_string() {
         let val = json!("https://example.com/?q=\"test\"");
-        let result = format_simple_kv("url", &val);
+        let result = format_simple_kv("url", &val, NumberFormat::Plain);
         assert!(
             result.contains("\\\"test\\\""),
             "Quotes in value should be escaped, got: {}",
@@ -116,7 +394,7 @@ mod tests {
     #[test]
     fn test_format_simple_kv_escapes_backslashes() {
         let val = json!("path\\to\\file");
-        let result = format_simple_kv("path", &val);
+        let result = format_simple_kv("path", &val, NumberFormat::Plain);
         assert!(
             result.contains("path\\\\to\\\\file"),
             "Backslashes should be escaped, got: {}",
@@ -127,7 +405,7 @@ mod tests {
     #[test]
     fn test_preview_value_escapes_quotes() {
         let val = json!("say \"hello\"");
-        let result = preview_value(&val);
+        let result = preview_value(&val, NumberFormat::Plain);
         assert!(
             result.contains("\\\"hello\\\""),
             "Quotes in preview should be escaped, got: {}",
@@ -138,7 +416,7 @@ mod tests {
     #[test]
     fn test_preview_value_escapes_backslashes() {
         let val = json!("back\\slash");
-        let result = preview_value(&val);
+        let result = preview_value(&val, NumberFormat::Plain);
         assert!(
             result.contains("back\\\\slash"),
             "Backslashes in preview should be escaped, got: {}",
@@ -149,15 +427,86 @@ mod tests {
     #[test]
     fn test_format_simple_kv_normal_string_unchanged() {
         let val = json!("hello world");
-        let result = format_simple_kv("greeting", &val);
+        let result = format_simple_kv("greeting", &val, NumberFormat::Plain);
         assert_eq!(result, "\"greeting\": \"hello world\"");
     }
 
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(500 * 1024 * 1024), "500.0 MB");
+        assert_eq!(format_bytes(1288490189), "1.2 GB");
+    }
+
     #[test]
     fn test_preview_value_primitives() {
-        assert_eq!(preview_value(&json!(null)), "null");
-        assert_eq!(preview_value(&json!(true)), "true");
-        assert_eq!(preview_value(&json!(42)), "42");
-        assert_eq!(preview_value(&json!("hello")), "\"hello\"");
+        assert_eq!(preview_value(&json!(null), NumberFormat::Plain), "null");
+        assert_eq!(preview_value(&json!(true), NumberFormat::Plain), "true");
+        assert_eq!(preview_value(&json!(42), NumberFormat::Plain), "42");
+        assert_eq!(
+            preview_value(&json!("hello"), NumberFormat::Plain),
+            "\"hello\""
+        );
+    }
+
+    fn number(value: Value, format: NumberFormat) -> String {
+        preview_value(&value, format)
+    }
+
+    #[test]
+    fn test_grouped_numbers() {
+        let grouped = NumberFormat::Grouped;
+        assert_eq!(number(json!(0), grouped), "0");
+        assert_eq!(number(json!(999), grouped), "999");
+        assert_eq!(number(json!(1000), grouped), "1,000");
+        assert_eq!(number(json!(1000000), grouped), "1,000,000");
+        assert_eq!(number(json!(-1234567), grouped), "-1,234,567");
+        assert_eq!(
+            number(json!(u64::MAX), grouped),
+            "18,446,744,073,709,551,615"
+        );
+        assert_eq!(
+            number(json!(i64::MIN), grouped),
+            "-9,223,372,036,854,775,808"
+        );
+        // Floats keep their fraction, and only the integer part is grouped
+        assert_eq!(number(json!(1234.5678), grouped), "1,234.5678");
+        assert_eq!(number(json!(1000000.0), grouped), "1,000,000.0");
+        assert_eq!(number(json!(-0.25), grouped), "-0.25");
+        assert_eq!(number(json!(1e21), grouped), "1e21");
+    }
+
+    #[test]
+    fn test_scientific_numbers() {
+        let scientific = NumberFormat::Scientific;
+        assert_eq!(number(json!(0), scientific), "0e0");
+        assert_eq!(number(json!(1000000), scientific), "1e6");
+        assert_eq!(number(json!(-1500), scientific), "-1.5e3");
+        // Large integers keep every digit
+        assert_eq!(
+            number(json!(u64::MAX), scientific),
+            "1.8446744073709551615e19"
+        );
+        assert_eq!(
+            number(json!(9007199254740993_i64), scientific),
+            "9.007199254740993e15"
+        );
+        // Floats keep a fractional part so they don't read as integers
+        assert_eq!(number(json!(1000000.0), scientific), "1.0e6");
+        assert_eq!(number(json!(0.00125), scientific), "1.25e-3");
+    }
+
+    #[test]
+    fn test_number_format_only_changes_numbers() {
+        for format in NumberFormat::ALL {
+            assert_eq!(number(json!("1000"), format), "\"1000\"");
+            assert_eq!(number(json!(true), format), "true");
+            assert_eq!(
+                format_simple_kv("n", &json!(12345), format),
+                format!("\"n\": {}", preview_value(&json!(12345), format))
+            );
+        }
+        assert_eq!(number(json!(12345), NumberFormat::Plain), "12345");
     }
 }
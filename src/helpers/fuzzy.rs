@@ -0,0 +1,172 @@
+use std::ops::Range;
+
+/// Score for every matched character
+const MATCH: i32 = 16;
+/// Extra score for a character that starts a word: the first character, one
+/// after a non-alphanumeric, or an uppercase letter after a lowercase one
+const WORD_START: i32 = 8;
+/// Extra score for a character right after the previous match
+const CONSECUTIVE: i32 = 8;
+/// Penalty for skipping characters between two matches
+const GAP_START: i32 = 3;
+/// Further penalty for each skipped character after the first
+const GAP_EXTEND: i32 = 1;
+
+/// Where a fuzzy query matched a text, and how well
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher is better; tight matches on word starts score highest
+    pub score: i32,
+    /// Byte ranges of the matched characters, adjacent ones merged
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// Match `query` against `text` when every query character appears in it in
+/// order; whitespace in the query is ignored
+///
+/// Picks the best-scoring placement rather than the first one found.
+pub fn fuzzy_match(query: &str, text: &str, match_case: bool) -> Option<FuzzyMatch> {
+    let fold = |c: char| {
+        if match_case {
+            c
+        } else {
+            c.to_lowercase().next().unwrap_or(c)
+        }
+    };
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(fold)
+        .collect();
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    if query.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            ranges: Vec::new(),
+        });
+    }
+    if query.len() > chars.len() {
+        return None;
+    }
+
+    let bonus = |j: usize| {
+        let c = chars[j].1;
+        let word_start = match j.checked_sub(1).map(|p| chars[p].1) {
+            None => true,
+            Some(prev) => !prev.is_alphanumeric() || (prev.is_lowercase() && c.is_uppercase()),
+        };
+        MATCH + if word_start { WORD_START } else { 0 }
+    };
+
+    // scores[i][j]: best score with query[i] matched at chars[j];
+    // from[i][j]: where query[i - 1] was matched on that path
+    let n = chars.len();
+    let mut scores: Vec<Vec<Option<i32>>> = Vec::with_capacity(query.len());
+    let mut from: Vec<Vec<usize>> = Vec::with_capacity(query.len());
+    scores.push(
+        (0..n)
+            .map(|j| (fold(chars[j].1) == query[0]).then(|| bonus(j)))
+            .collect(),
+    );
+    from.push(vec![0; n]);
+
+    for &q in &query[1..] {
+        let prev = scores.last().unwrap();
+        let mut row = vec![None; n];
+        let mut back = vec![0; n];
+        // Best earlier match at k, kept as `score + k` so the gap penalty
+        // for any later j is just `j` subtracted
+        let mut earlier: Option<(i32, usize)> = None;
+        for j in 1..n {
+            if j >= 2
+                && let Some(score) = prev[j - 2]
+            {
+                let key = score + (j - 2) as i32;
+                if earlier.is_none_or(|(best, _)| key > best) {
+                    earlier = Some((key, j - 2));
+                }
+            }
+            if fold(chars[j].1) != q {
+                continue;
+            }
+            let adjacent = prev[j - 1].map(|score| (score + CONSECUTIVE, j - 1));
+            let gapped = earlier.map(|(key, k)| {
+                let gap = (j - k - 1) as i32;
+                (key - k as i32 - GAP_START - GAP_EXTEND * (gap - 1), k)
+            });
+            let best = match (adjacent, gapped) {
+                (Some(a), Some(g)) => Some(if a.0 >= g.0 { a } else { g }),
+                (a, g) => a.or(g),
+            };
+            if let Some((score, k)) = best {
+                row[j] = Some(score + bonus(j));
+                back[j] = k;
+            }
+        }
+        scores.push(row);
+        from.push(back);
+    }
+
+    let (mut j, score) = scores
+        .last()
+        .unwrap()
+        .iter()
+        .enumerate()
+        .filter_map(|(j, score)| Some((j, (*score)?)))
+        .max_by_key(|&(j, score)| (score, std::cmp::Reverse(j)))?;
+
+    let mut positions = vec![j; query.len()];
+    for i in (1..query.len()).rev() {
+        j = from[i][j];
+        positions[i - 1] = j;
+    }
+
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for j in positions {
+        let (start, c) = chars[j];
+        let end = start + c.len_utf8();
+        match ranges.last_mut() {
+            Some(last) if last.end == start => last.end = end,
+            _ => ranges.push(start..end),
+        }
+    }
+    Some(FuzzyMatch { score, ranges })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(query: &str, text: &str) -> Option<i32> {
+        fuzzy_match(query, text, false).map(|m| m.score)
+    }
+
+    #[test]
+    fn test_fuzzy_match_requires_characters_in_order() {
+        assert!(score("xpal", "Expand All").is_some());
+        assert!(score("EXPAND all", "Expand All").is_some());
+        assert!(score("all expand", "Expand All").is_none());
+        assert!(score("expand alls", "Expand All").is_none());
+        assert!(fuzzy_match("Name", "username", true).is_none());
+        assert_eq!(score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn test_fuzzy_match_prefers_word_starts_and_runs() {
+        assert!(score("cp", "Copy Path") > score("cp", "Copy Key"));
+        assert!(score("ea", "Expand All") > score("ea", "Collapse All"));
+        assert!(score("usr", "user_id") > score("usr", "unsorted"));
+        assert!(score("fn", "firstName") > score("fn", "often"));
+    }
+
+    #[test]
+    fn test_fuzzy_match_ranges() {
+        let m = fuzzy_match("usrnm", "user_name", false).unwrap();
+        assert_eq!(m.ranges, [0..2, 3..4, 5..6, 7..8]);
+        let m = fuzzy_match("un", "user_name", false).unwrap();
+        assert_eq!(m.ranges, [0..1, 5..6]);
+        // Byte offsets, not character offsets
+        let m = fuzzy_match("cf", "café_fr", false).unwrap();
+        assert_eq!(m.ranges, [0..1, 6..7]);
+    }
+}
@@ -21,59 +21,94 @@ pub fn split_root_rel(path: &str) -> Result<(usize, &str)> {
 }
 
 /// Walk a relative path like "user.items[2].meta" starting at `value`.
-pub fn walk_rel(mut cur: serde_json::Value, mut rel: &str) -> Result<serde_json::Value> {
-    while !rel.is_empty() {
-        if let Some(rem) = rel.strip_prefix('[') {
-            // parse index
-            let close = rem
-                .find(']')
-                .ok_or_else(|| ThothError::InvalidJsonStructure {
-                    reason: format!("Unclosed bracket in path: {}", rel),
-                })?;
-            let idx_str = &rem[..close];
-            let idx: usize = idx_str
-                .parse()
-                .map_err(|_| ThothError::InvalidJsonStructure {
-                    reason: format!("Invalid array index: {}", idx_str),
-                })?;
-            cur = cur
-                .get(idx)
-                .ok_or_else(|| ThothError::InvalidJsonStructure {
-                    reason: format!("Array index {} not found", idx),
-                })?
-                .clone();
-            rel = &rem[close + 1..];
-            if rel.starts_with('.') {
-                rel = &rel[1..];
-            }
-        } else {
-            // take key until '.' or '['
-            let next_sep = rel.find(['.', '[']).unwrap_or(rel.len());
-            let key = &rel[..next_sep];
-            cur = cur
-                .get(key)
-                .ok_or_else(|| ThothError::InvalidJsonStructure {
-                    reason: format!("Key '{}' not found", key),
-                })?
-                .clone();
-            rel = &rel[next_sep..];
-            if rel.starts_with('.') {
-                rel = &rel[1..];
-            }
+pub fn walk_rel(cur: serde_json::Value, rel: &str) -> Result<serde_json::Value> {
+    lookup_rel(&cur, rel).cloned()
+}
+
+/// Borrowing form of [`walk_rel`]: resolve `rel` inside `value` without cloning.
+pub fn lookup_rel<'a>(mut cur: &'a Value, mut rel: &str) -> Result<&'a Value> {
+    while let Some((segment, rest)) = next_segment(rel)? {
+        cur = match segment {
+            Segment::Index(idx) => cur.get(idx),
+            Segment::Key(key) => cur.get(key),
         }
+        .ok_or_else(|| segment.not_found())?;
+        rel = rest;
     }
     Ok(cur)
 }
 
-/// Copy the JSON subtree for `row_path` to the clipboard. Returns true on success.
-pub fn get_object_string(root: Value, rel: &str) -> Result<String> {
-    let sub = if rel.is_empty() {
-        root
+/// Mutable form of [`lookup_rel`], used to edit a value in place.
+pub fn lookup_rel_mut<'a>(mut cur: &'a mut Value, mut rel: &str) -> Result<&'a mut Value> {
+    while let Some((segment, rest)) = next_segment(rel)? {
+        cur = match segment {
+            Segment::Index(idx) => cur.get_mut(idx),
+            Segment::Key(key) => cur.get_mut(key),
+        }
+        .ok_or_else(|| segment.not_found())?;
+        rel = rest;
+    }
+    Ok(cur)
+}
+
+/// One step of a relative path: an object key or an array index
+#[derive(Debug, Clone, Copy)]
+enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+impl Segment<'_> {
+    fn not_found(self) -> ThothError {
+        ThothError::InvalidJsonStructure {
+            reason: match self {
+                Segment::Key(key) => format!("Key '{}' not found", key),
+                Segment::Index(idx) => format!("Array index {} not found", idx),
+            },
+        }
+    }
+}
+
+/// Split the first segment off `rel`, returning it with the remaining path.
+fn next_segment(rel: &str) -> Result<Option<(Segment<'_>, &str)>> {
+    if rel.is_empty() {
+        return Ok(None);
+    }
+    let (segment, rest) = if let Some(rem) = rel.strip_prefix('[') {
+        // parse index
+        let close = rem
+            .find(']')
+            .ok_or_else(|| ThothError::InvalidJsonStructure {
+                reason: format!("Unclosed bracket in path: {}", rel),
+            })?;
+        let idx_str = &rem[..close];
+        let idx: usize = idx_str
+            .parse()
+            .map_err(|_| ThothError::InvalidJsonStructure {
+                reason: format!("Invalid array index: {}", idx_str),
+            })?;
+        (Segment::Index(idx), &rem[close + 1..])
     } else {
-        walk_rel(root, rel)?
+        // take key until '.' or '['
+        let next_sep = rel.find(['.', '[']).unwrap_or(rel.len());
+        (Segment::Key(&rel[..next_sep]), &rel[next_sep..])
     };
+    Ok(Some((segment, rest.strip_prefix('.').unwrap_or(rest))))
+}
+
+/// Copy the JSON subtree for `row_path` to the clipboard. Returns true on success.
+pub fn get_object_string(root: Value, rel: &str) -> Result<String> {
+    serde_json::to_string_pretty(lookup_rel(&root, rel)?).map_err(|e| {
+        ThothError::InvalidJsonStructure {
+            reason: format!("Failed to serialize JSON: {}", e),
+        }
+    })
+}
 
-    serde_json::to_string_pretty(&sub).map_err(|e| ThothError::InvalidJsonStructure {
+/// Single-line form of [`get_object_string`]; scalars come out as JSON
+/// literals (strings keep their quotes).
+pub fn get_object_string_minified(root: Value, rel: &str) -> Result<String> {
+    serde_json::to_string(lookup_rel(&root, rel)?).map_err(|e| ThothError::InvalidJsonStructure {
         reason: format!("Failed to serialize JSON: {}", e),
     })
 }
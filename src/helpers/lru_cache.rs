@@ -5,6 +5,25 @@ pub struct LruCache<K, V> {
     cap: usize,
     map: HashMap<K, (V, u64)>,
     tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+/// Hit/miss counters and fill level of an [`LruCache`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// Fraction of lookups that were hits, or `None` before the first lookup
+    pub fn hit_rate(&self) -> Option<f32> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f32 / lookups as f32)
+    }
 }
 
 impl<K: std::hash::Hash + Eq + Clone, V> LruCache<K, V> {
@@ -13,6 +32,8 @@ impl<K: std::hash::Hash + Eq + Clone, V> LruCache<K, V> {
             cap,
             map: HashMap::new(),
             tick: 0,
+            hits: 0,
+            misses: 0,
         }
     }
 
@@ -20,8 +41,10 @@ impl<K: std::hash::Hash + Eq + Clone, V> LruCache<K, V> {
         if let Some((v, t)) = self.map.get_mut(k) {
             self.tick += 1;
             *t = self.tick;
+            self.hits += 1;
             Some(v)
         } else {
+            self.misses += 1;
             None
         }
     }
@@ -42,4 +65,57 @@ impl<K: std::hash::Hash + Eq + Clone, V> LruCache<K, V> {
         }
         self.map.insert(k, (v, self.tick));
     }
+
+    /// Drop every entry, keeping the hit/miss counters
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Fraction of `get` calls that found their key, or `None` before the first lookup
+    pub fn hit_rate(&self) -> Option<f32> {
+        self.stats().hit_rate()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            len: self.map.len(),
+            capacity: self.cap,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hit_rate_counts_lookups() {
+        let mut cache = LruCache::new(2);
+        assert_eq!(cache.hit_rate(), None);
+
+        assert!(cache.get(&1).is_none());
+        cache.put(1, "a");
+        assert!(cache.get(&1).is_some());
+        assert!(cache.get(&1).is_some());
+        assert!(cache.get(&2).is_none());
+        assert_eq!(cache.hit_rate(), Some(0.5));
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 2));
+        assert_eq!((stats.len, stats.capacity), (1, 2));
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "a");
+        cache.put(2, "b");
+        cache.get(&1);
+        cache.put(3, "c");
+        assert!(cache.get(&2).is_none());
+        assert!(cache.get(&1).is_some());
+        assert!(cache.get(&3).is_some());
+    }
 }
@@ -1,23 +1,48 @@
+mod flatten;
 mod format;
+mod fuzzy;
 mod json_copy_to_clipboard;
 mod lru_cache;
+mod path_completion;
+mod path_format;
 mod scroll;
+mod typescript;
 
 use crate::shortcuts::Shortcut;
 use eframe::egui::IconData;
-pub use format::{format_date, format_date_static, format_simple_kv, preview_value};
-pub use json_copy_to_clipboard::{get_object_string, split_root_rel, walk_rel};
-pub use lru_cache::LruCache;
+pub use flatten::flatten_value;
+pub use format::{
+    NumberFormat, format_bytes, format_date, format_date_static, format_number, format_simple_kv,
+    humanize_timestamp, is_integer, matches_pattern, preview_value,
+};
+pub use fuzzy::{FuzzyMatch, fuzzy_match};
+pub use json_copy_to_clipboard::{
+    get_object_string, get_object_string_minified, lookup_rel, lookup_rel_mut, split_root_rel,
+    walk_rel,
+};
+pub use lru_cache::{CacheStats, LruCache};
+pub use path_completion::{PathCompletion, complete_path};
+pub use path_format::{PathFormat, PathSegment, format_path, path_segments};
 pub use scroll::{scroll_to_search_target, scroll_to_selection};
+pub use typescript::{infer_typescript_interface, interface_name_from_path};
 
 /// Get formatted shortcut strings for context menu
-/// Returns: (copy_key, copy_value, copy_object, copy_path)
-pub fn get_context_menu_shortcuts() -> (String, String, String, String) {
+/// Returns: (copy_key, copy_value, copy_object, copy_minified, copy_path, copy_line_number)
+pub fn get_context_menu_shortcuts() -> (String, String, String, String, String, String) {
     let copy_key = Shortcut::new("C").command().format();
     let copy_value = Shortcut::new("C").command().shift().format();
     let copy_object = Shortcut::new("C").command().alt().format();
-    let copy_path = Shortcut::new("P").command().shift().format();
-    (copy_key, copy_value, copy_object, copy_path)
+    let copy_minified = Shortcut::new("C").command().alt().shift().format();
+    let copy_path = Shortcut::new("K").command().shift().format();
+    let copy_line_number = Shortcut::new("L").command().shift().format();
+    (
+        copy_key,
+        copy_value,
+        copy_object,
+        copy_minified,
+        copy_path,
+        copy_line_number,
+    )
 }
 
 pub fn load_icon(bytes: &[u8]) -> Option<IconData> {
@@ -0,0 +1,202 @@
+use super::json_copy_to_clipboard::{lookup_rel, split_root_rel};
+use crate::constants::MAX_PATH_SUGGESTIONS;
+use serde_json::Value;
+
+/// Suggested continuations for a partially typed row path
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathCompletion {
+    /// The text the suggestions were computed for
+    pub query: String,
+    /// Full row paths, children of the typed path first, then matching siblings
+    pub suggestions: Vec<String>,
+    /// Whether the typed path itself resolves to a value
+    pub exists: bool,
+}
+
+/// Suggest row paths continuing `query` (e.g. `0.users[3].add`)
+///
+/// `root` is the record at the query's leading root index, if it was loaded;
+/// only that one record is walked, so nothing else has to be parsed.
+pub fn complete_path(query: &str, total_roots: usize, root: Option<&Value>) -> PathCompletion {
+    let mut completion = PathCompletion {
+        query: query.to_string(),
+        ..PathCompletion::default()
+    };
+    let mut suggestions = Vec::new();
+
+    match query.rfind(['.', '[']) {
+        // Still typing the root index
+        None => {
+            if !query.chars().all(|c| c.is_ascii_digit()) {
+                return completion;
+            }
+            completion.exists = query.parse::<usize>().is_ok_and(|idx| idx < total_roots);
+            if completion.exists
+                && let Some(root) = root
+            {
+                push_children(&mut suggestions, query, root);
+            }
+            suggestions.extend(
+                indices_with_prefix(query, total_roots)
+                    .into_iter()
+                    .map(|idx| idx.to_string()),
+            );
+        }
+        Some(cut) => {
+            let Some(root) = root else {
+                return completion;
+            };
+            let (parent, partial) = (&query[..cut], &query[cut + 1..]);
+            let bracket = query[cut..].starts_with('[');
+
+            let value = (!partial.is_empty())
+                .then(|| split_root_rel(query).ok())
+                .flatten()
+                .and_then(|(_, rel)| lookup_rel(root, rel).ok());
+            completion.exists = value.is_some();
+            if let Some(value) = value {
+                push_children(&mut suggestions, query, value);
+            }
+
+            let parent_value = split_root_rel(parent)
+                .ok()
+                .and_then(|(_, rel)| lookup_rel(root, rel).ok());
+            match parent_value {
+                Some(Value::Object(map)) if !bracket => {
+                    let partial = partial.to_lowercase();
+                    suggestions.extend(
+                        map.keys()
+                            .filter(|key| key.to_lowercase().starts_with(&partial))
+                            .take(MAX_PATH_SUGGESTIONS)
+                            .map(|key| format!("{}.{}", parent, key)),
+                    );
+                }
+                Some(Value::Array(items)) => {
+                    let partial = partial.trim_end_matches(']');
+                    suggestions.extend(
+                        indices_with_prefix(partial, items.len())
+                            .into_iter()
+                            .map(|idx| format!("{}[{}]", parent, idx)),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    completion.suggestions = suggestions
+        .into_iter()
+        .filter(|suggestion| suggestion != query && seen.insert(suggestion.clone()))
+        .take(MAX_PATH_SUGGESTIONS)
+        .collect();
+    completion
+}
+
+/// Child row paths of `value` at `path`
+fn push_children(out: &mut Vec<String>, path: &str, value: &Value) {
+    match value {
+        Value::Object(map) => out.extend(
+            map.keys()
+                .take(MAX_PATH_SUGGESTIONS)
+                .map(|key| format!("{}.{}", path, key)),
+        ),
+        Value::Array(items) => out.extend(
+            (0..items.len().min(MAX_PATH_SUGGESTIONS)).map(|idx| format!("{}[{}]", path, idx)),
+        ),
+        _ => {}
+    }
+}
+
+/// Indices below `len` whose decimal form starts with `prefix`, smallest first
+///
+/// Generated digit by digit rather than by scanning, so a file with millions
+/// of records is as cheap as a small one.
+fn indices_with_prefix(prefix: &str, len: usize) -> Vec<usize> {
+    if prefix.is_empty() {
+        return (0..len.min(MAX_PATH_SUGGESTIONS)).collect();
+    }
+    let Ok(start) = prefix.parse::<usize>() else {
+        return Vec::new();
+    };
+    if prefix.len() > 1 && prefix.starts_with('0') {
+        return Vec::new();
+    }
+    if start == 0 {
+        return if len > 0 { vec![0] } else { Vec::new() };
+    }
+
+    let mut out = Vec::new();
+    let (mut low, mut width) = (start, 1usize);
+    while low < len && out.len() < MAX_PATH_SUGGESTIONS {
+        let high = low.saturating_add(width).min(len);
+        out.extend((low..high).take(MAX_PATH_SUGGESTIONS - out.len()));
+        match (low.checked_mul(10), width.checked_mul(10)) {
+            (Some(next_low), Some(next_width)) => (low, width) = (next_low, next_width),
+            _ => break,
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_indices_with_prefix() {
+        assert_eq!(
+            indices_with_prefix("1", 25),
+            [1, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]
+        );
+        assert_eq!(indices_with_prefix("2", 25), [2, 20, 21, 22, 23, 24]);
+        assert_eq!(indices_with_prefix("0", 25), [0]);
+        assert_eq!(indices_with_prefix("05", 25), Vec::<usize>::new());
+        assert_eq!(indices_with_prefix("7", 5), Vec::<usize>::new());
+        assert_eq!(indices_with_prefix("", 3), [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_completes_keys_and_indices() {
+        let root = json!({"users": [{"name": "a"}, {"name": "b"}], "user_count": 2, "meta": {}});
+
+        let completion = complete_path("3.us", 5, Some(&root));
+        assert!(!completion.exists);
+        // Keys come back sorted
+        assert_eq!(completion.suggestions, ["3.user_count", "3.users"]);
+
+        let completion = complete_path("3.users", 5, Some(&root));
+        assert!(completion.exists);
+        assert_eq!(completion.suggestions, ["3.users[0]", "3.users[1]"]);
+
+        let completion = complete_path("3.users[1", 5, Some(&root));
+        assert_eq!(completion.suggestions, ["3.users[1]"]);
+
+        let completion = complete_path("3.users[1].", 5, Some(&root));
+        assert!(!completion.exists);
+        assert_eq!(completion.suggestions, ["3.users[1].name"]);
+    }
+
+    #[test]
+    fn test_completes_root_index() {
+        let root = json!({"id": 1});
+        let completion = complete_path("1", 12, Some(&root));
+        assert!(completion.exists);
+        assert_eq!(completion.suggestions, ["1.id", "10", "11"]);
+
+        assert_eq!(complete_path("", 3, None).suggestions, ["0", "1", "2"]);
+    }
+
+    #[test]
+    fn test_reports_missing_path() {
+        let root = json!({"users": []});
+        let completion = complete_path("0.nope.x", 1, Some(&root));
+        assert!(!completion.exists);
+        assert!(completion.suggestions.is_empty());
+
+        let completion = complete_path("9", 3, None);
+        assert!(!completion.exists);
+        assert!(completion.suggestions.is_empty());
+    }
+}
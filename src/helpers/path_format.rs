@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::helpers::split_root_rel;
+
+/// Syntax a row path is copied in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathFormat {
+    /// Thoth's own paths: `0.user.items[2]`
+    #[default]
+    Dot,
+    /// `$[0].user.items[2]`, or `$.user` for a single-document file
+    JsonPath,
+    /// JavaScript bracket notation: `[0]["user"]["items"][2]`
+    Bracket,
+}
+
+impl PathFormat {
+    pub const ALL: [PathFormat; 3] = [PathFormat::Dot, PathFormat::JsonPath, PathFormat::Bracket];
+
+    /// Name shown to the user
+    pub fn label(self) -> &'static str {
+        match self {
+            PathFormat::Dot => "Dot",
+            PathFormat::JsonPath => "JSONPath",
+            PathFormat::Bracket => "Bracket",
+        }
+    }
+
+    /// A short sample of the syntax
+    pub fn example(self) -> &'static str {
+        match self {
+            PathFormat::Dot => "0.user.items[2]",
+            PathFormat::JsonPath => "$[0].user.items[2]",
+            PathFormat::Bracket => "[0][\"user\"][2]",
+        }
+    }
+}
+
+/// One step of a path below its root record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Split a row path such as `0.user.items[2]` into its root index and segments
+///
+/// Keys are written into row paths unescaped, so a key holding `.` or `[`
+/// can't be told apart from nesting by the text alone. With the root record
+/// at hand each step is matched against the keys that actually exist
+/// (longest first); without it, keys end at the next `.` or `[`.
+pub fn path_segments(path: &str, root: Option<&Value>) -> Option<(usize, Vec<PathSegment>)> {
+    let (root_index, mut rel) = split_root_rel(path).ok()?;
+    let mut current = root;
+    let mut segments = Vec::new();
+    while !rel.is_empty() {
+        let (segment, rest) = match current {
+            Some(Value::Object(map)) => map
+                .keys()
+                .filter(|key| {
+                    rel.strip_prefix(key.as_str())
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+                })
+                .max_by_key(|key| key.len())
+                .map(|key| (PathSegment::Key(key.clone()), &rel[key.len()..]))
+                .unwrap_or_else(|| next_segment(rel)),
+            _ => next_segment(rel),
+        };
+        current = current.and_then(|value| match &segment {
+            PathSegment::Key(key) => value.get(key),
+            PathSegment::Index(index) => value.get(index),
+        });
+        segments.push(segment);
+        rel = rest.strip_prefix('.').unwrap_or(rest);
+    }
+    Some((root_index, segments))
+}
+
+/// The segment at the start of `rel` read from the text alone
+fn next_segment(rel: &str) -> (PathSegment, &str) {
+    if let Some(inner) = rel.strip_prefix('[')
+        && let Some(close) = inner.find(']')
+        && let Ok(index) = inner[..close].parse()
+    {
+        return (PathSegment::Index(index), &inner[close + 1..]);
+    }
+    // A key, up to the next separator (a leading `[` that isn't an index is part of it)
+    let first = rel.chars().next().map_or(0, char::len_utf8);
+    let end = rel[first..]
+        .find(['.', '['])
+        .map_or(rel.len(), |at| at + first);
+    (PathSegment::Key(rel[..end].to_string()), &rel[end..])
+}
+
+/// Write the row `path` in `format`
+///
+/// `root` is the path's root record, used to tell keys containing `.` or `[`
+/// apart from nesting. In a `single_document` file the root record is the
+/// whole document, so JSONPath and bracket paths start at the document
+/// rather than at a record index.
+pub fn format_path(
+    path: &str,
+    root: Option<&Value>,
+    format: PathFormat,
+    single_document: bool,
+) -> Option<String> {
+    let (root_index, segments) = path_segments(path, root)?;
+    let mut out = match format {
+        PathFormat::Dot => root_index.to_string(),
+        PathFormat::JsonPath if single_document => "$".to_string(),
+        PathFormat::JsonPath => format!("$[{}]", root_index),
+        PathFormat::Bracket if single_document => String::new(),
+        PathFormat::Bracket => format!("[{}]", root_index),
+    };
+    for segment in &segments {
+        match (segment, format) {
+            (PathSegment::Index(index), _) => out.push_str(&format!("[{}]", index)),
+            (PathSegment::Key(key), PathFormat::Dot) if is_plain_dot_key(key) => {
+                out.push('.');
+                out.push_str(key);
+            }
+            (PathSegment::Key(key), PathFormat::JsonPath) if is_identifier(key) => {
+                out.push('.');
+                out.push_str(key);
+            }
+            (PathSegment::Key(key), PathFormat::JsonPath) => {
+                out.push_str(&format!("[{}]", json_path_quote(key)));
+            }
+            (PathSegment::Key(key), PathFormat::Dot | PathFormat::Bracket) => {
+                out.push_str(&format!("[{}]", Value::String(key.clone())));
+            }
+        }
+    }
+    Some(out)
+}
+
+/// Whether `key` reads back unambiguously after a `.` in a Thoth path
+fn is_plain_dot_key(key: &str) -> bool {
+    !key.is_empty()
+        && !key
+            .chars()
+            .any(|c| matches!(c, '.' | '[' | ']' | '"' | '\'') || c.is_whitespace())
+}
+
+/// Whether `key` can follow a `.` in JSONPath: a letter, `_` or non-ASCII
+/// character, then any of those or digits
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || !c.is_ascii())
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || !c.is_ascii())
+}
+
+/// `key` as a quoted JSONPath name: single quotes unless the key holds one
+fn json_path_quote(key: &str) -> String {
+    if !key.contains('\'') {
+        format!("'{}'", key.replace('\\', "\\\\"))
+    } else if !key.contains('"') {
+        format!("\"{}\"", key.replace('\\', "\\\\"))
+    } else {
+        format!("'{}'", key.replace('\\', "\\\\").replace('\'', "\\'"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn all(path: &str, root: Option<&Value>) -> [String; 3] {
+        PathFormat::ALL.map(|format| format_path(path, root, format, false).unwrap())
+    }
+
+    #[test]
+    fn test_plain_paths() {
+        assert_eq!(
+            all("0.user.items[2]", None),
+            [
+                "0.user.items[2]",
+                "$[0].user.items[2]",
+                r#"[0]["user"]["items"][2]"#
+            ]
+        );
+        assert_eq!(all("3", None), ["3", "$[3]", "[3]"]);
+        // Closing-bracket rows copy the path of the container they close
+        assert_eq!(all("1.a/_close", None)[1], "$[1].a");
+    }
+
+    #[test]
+    fn test_single_document_starts_at_root() {
+        let format = |f| format_path("0.user[1]", None, f, true).unwrap();
+        assert_eq!(format(PathFormat::JsonPath), "$.user[1]");
+        assert_eq!(format(PathFormat::Bracket), r#"["user"][1]"#);
+        assert_eq!(format(PathFormat::Dot), "0.user[1]");
+        assert_eq!(
+            format_path("0", None, PathFormat::JsonPath, true).unwrap(),
+            "$"
+        );
+    }
+
+    #[test]
+    fn test_keys_needing_quotes() {
+        let root = json!({"first name": {"it's": 1}, "2x": {"a\"b": 2}});
+        assert_eq!(
+            all("0.first name.it's", Some(&root)),
+            [
+                r#"0["first name"]["it's"]"#,
+                r#"$[0]['first name']["it's"]"#,
+                r#"[0]["first name"]["it's"]"#
+            ]
+        );
+        assert_eq!(
+            all("0.2x.a\"b", Some(&root)),
+            [
+                r#"0.2x["a\"b"]"#,
+                r#"$[0]['2x']['a"b']"#,
+                r#"[0]["2x"]["a\"b"]"#
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keys_with_dots_and_brackets() {
+        let root = json!({
+            "a.b": {"c": [10, {"[x]": true}]},
+            "a": {"b": 1},
+            "tags[0]": "t"
+        });
+        assert_eq!(
+            path_segments("0.a.b.c[1].[x]", Some(&root)).unwrap().1,
+            [
+                PathSegment::Key("a.b".to_string()),
+                PathSegment::Key("c".to_string()),
+                PathSegment::Index(1),
+                PathSegment::Key("[x]".to_string()),
+            ]
+        );
+        assert_eq!(
+            all("0.a.b.c[1].[x]", Some(&root)),
+            [
+                r#"0["a.b"].c[1]["[x]"]"#,
+                r#"$[0]['a.b'].c[1]['[x]']"#,
+                r#"[0]["a.b"]["c"][1]["[x]"]"#
+            ]
+        );
+        assert_eq!(all("0.tags[0]", Some(&root))[1], "$[0]['tags[0]']");
+        // Without the record the text is split at every separator
+        assert_eq!(all("0.a.b", None)[1], "$[0].a.b");
+    }
+}
@@ -1,8 +1,6 @@
 use eframe::egui::{self, Ui};
 use std::ops::Range;
 
-use crate::constants::SCROLL_MARGIN;
-
 /// Handles incremental scrolling for search navigation that persists across frames.
 ///
 /// This function uses `scroll_with_delta` combined with `request_repaint` to scroll
@@ -15,15 +13,18 @@ use crate::constants::SCROLL_MARGIN;
 /// * `row_range` - The range of currently visible rows
 /// * `target_row` - The target row index to scroll to
 /// * `row_height` - The height of each row in pixels
+/// * `scroll_margin` - Rows of context to keep above the target
 pub fn scroll_to_search_target(
     ui: &mut Ui,
     row_range: &Range<usize>,
     target_row: usize,
     row_height: f32,
+    scroll_margin: usize,
 ) -> bool {
     // Calculate the ideal position: target row should be a few rows from the top
     // This gives a small amount of context above the target
-    let ideal_offset_from_top = 2;
+    let (_, viewport_height, stride) = viewport_metrics(ui, row_range, row_height);
+    let ideal_offset_from_top = clamp_margin(scroll_margin, viewport_height, stride);
     let ideal_top_row = target_row.saturating_sub(ideal_offset_from_top);
 
     // Check if we're close enough to the ideal position (within 2 rows tolerance)
@@ -47,8 +48,9 @@ pub fn scroll_to_search_target(
 
 /// Automatically scrolls the view to keep the selected item visible when navigating with keyboard.
 ///
-/// This function implements smooth scrolling behavior with margins: when the selection moves
-/// near the top or bottom edge of the visible area, the view scrolls to maintain context.
+/// The view scrolls as little as possible to keep `scroll_margin` rows between the
+/// selection and the top or bottom edge, so the rows around it stay in sight. Jumps
+/// far outside the visible area (e.g. Home/End) land with the same margin.
 /// The scroll is only triggered once per selection change (controlled by `should_scroll_to_selection`).
 ///
 /// After scrolling, the caller should reset `should_scroll_to_selection` to prevent continuous
@@ -59,47 +61,100 @@ pub fn scroll_to_search_target(
 /// * `row_range` - The range of currently visible rows
 /// * `current_index` - The index of the currently selected item
 /// * `row_height` - The height of each row in pixels
+/// * `scroll_margin` - Rows to keep between the selection and the view's edge
 /// * `should_scroll_to_selection` - Whether to scroll to the selected item (mutable flag)
 pub fn scroll_to_selection(
     ui: &mut Ui,
     row_range: &Range<usize>,
     current_index: usize,
     row_height: f32,
+    scroll_margin: usize,
     should_scroll_to_selection: &mut bool,
 ) {
     if !*should_scroll_to_selection {
         return;
     }
 
-    let scroll_margin: usize =
-        if current_index > row_range.end && current_index - row_range.end >= SCROLL_MARGIN {
-            current_index
-        } else {
-            SCROLL_MARGIN
-        };
+    let (offset, viewport_height, stride) = viewport_metrics(ui, row_range, row_height);
+    if let Some(target) = selection_scroll_offset(
+        offset,
+        viewport_height,
+        stride,
+        current_index,
+        scroll_margin,
+    ) {
+        ui.scroll_with_delta(egui::vec2(0.0, offset - target));
+    }
+}
+
+/// Current scroll offset, viewport height and row stride (height plus
+/// spacing) of the `show_rows` area `ui` belongs to
+fn viewport_metrics(ui: &Ui, row_range: &Range<usize>, row_height: f32) -> (f32, f32, f32) {
+    let stride = row_height + ui.spacing().item_spacing.y;
+    // `show_rows` places this ui at the first row it lays out
+    let content_top = ui.max_rect().top() - row_range.start as f32 * stride;
+    let viewport = ui.clip_rect();
+    (viewport.top() - content_top, viewport.height(), stride)
+}
+
+/// The margin actually used: no more than half the rows that fit in the
+/// view, so the selection and the rows around it always fit together
+fn clamp_margin(scroll_margin: usize, viewport_height: f32, stride: f32) -> usize {
+    let visible_rows = (viewport_height / stride).floor().max(0.0) as usize;
+    scroll_margin.min(visible_rows.saturating_sub(1) / 2)
+}
+
+/// Scroll offset that brings row `index` into view with `scroll_margin`
+/// rows on either side, or `None` when it is already there
+fn selection_scroll_offset(
+    offset: f32,
+    viewport_height: f32,
+    stride: f32,
+    index: usize,
+    scroll_margin: usize,
+) -> Option<f32> {
+    let margin = clamp_margin(scroll_margin, viewport_height, stride);
+    let top = index.saturating_sub(margin) as f32 * stride;
+    let bottom = (index + margin + 1) as f32 * stride;
+
+    if top < offset {
+        Some(top)
+    } else if bottom > offset + viewport_height {
+        Some(bottom - viewport_height)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Scrolling down: when near the bottom of visible range
-    if current_index >= row_range.end.saturating_sub(scroll_margin) {
-        // Relative scrolling down: scroll to current row + 1
-        let target_y = ui.max_rect().height() + (scroll_margin as f32 * row_height);
-        ui.scroll_to_rect(
-            egui::Rect::from_min_size(
-                egui::pos2(0.0, target_y),
-                egui::vec2(ui.available_width(), row_height),
-            ),
-            None,
-        );
+    #[test]
+    fn test_margin_clamped_to_half_the_view() {
+        // 10 rows fit: the selection plus 4 on each side at most
+        assert_eq!(clamp_margin(3, 100.0, 10.0), 3);
+        assert_eq!(clamp_margin(20, 100.0, 10.0), 4);
+        assert_eq!(clamp_margin(3, 25.0, 10.0), 0);
+        assert_eq!(clamp_margin(3, 0.0, 10.0), 0);
     }
-    // Scrolling up: when near the top of visible range
-    else if current_index < row_range.start + scroll_margin {
-        // Scroll up by one row height (relative scrolling)
-        let current_pos = ui.cursor().top();
-        ui.scroll_to_rect(
-            egui::Rect::from_min_size(
-                egui::pos2(0.0, (current_pos - row_height).max(0.0)),
-                egui::vec2(ui.available_width(), row_height),
-            ),
-            None,
-        );
+
+    #[test]
+    fn test_selection_offset_keeps_margin() {
+        // Rows 10..20 visible
+        let offset = selection_scroll_offset;
+        assert_eq!(offset(100.0, 100.0, 10.0, 15, 3), None);
+        assert_eq!(offset(100.0, 100.0, 10.0, 13, 3), None);
+        assert_eq!(offset(100.0, 100.0, 10.0, 16, 3), None);
+        // Moving down to row 17 scrolls so rows 18..=20 stay below it
+        assert_eq!(offset(100.0, 100.0, 10.0, 17, 3), Some(110.0));
+        // Moving up to row 12 scrolls so rows 9..=11 stay above it
+        assert_eq!(offset(100.0, 100.0, 10.0, 12, 3), Some(90.0));
+        // Without a margin the row may sit flush against the edge
+        assert_eq!(offset(100.0, 100.0, 10.0, 19, 0), None);
+        assert_eq!(offset(100.0, 100.0, 10.0, 20, 0), Some(110.0));
+        // Jumps land with the margin too, and the top never goes negative
+        assert_eq!(offset(100.0, 100.0, 10.0, 500, 3), Some(4940.0));
+        assert_eq!(offset(100.0, 100.0, 10.0, 1, 3), Some(0.0));
     }
 }
@@ -0,0 +1,426 @@
+use serde_json::Value;
+use std::collections::{HashSet, VecDeque};
+
+/// TypeScript type inferred from one or more JSON values.
+#[derive(Debug, Clone, PartialEq)]
+enum TsType {
+    String,
+    Number,
+    Boolean,
+    Null,
+    /// Element type of an array with no elements.
+    Unknown,
+    Array(Box<TsType>),
+    Object(Vec<TsField>),
+    Union(Vec<TsType>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct TsField {
+    name: String,
+    ty: TsType,
+    optional: bool,
+}
+
+impl TsType {
+    fn infer(value: &Value) -> Self {
+        match value {
+            Value::Null => TsType::Null,
+            Value::Bool(_) => TsType::Boolean,
+            Value::Number(_) => TsType::Number,
+            Value::String(_) => TsType::String,
+            Value::Array(items) => {
+                let element = items
+                    .iter()
+                    .map(TsType::infer)
+                    .reduce(TsType::merge)
+                    .unwrap_or(TsType::Unknown);
+                TsType::Array(Box::new(element))
+            }
+            Value::Object(map) => TsType::Object(
+                map.iter()
+                    .map(|(name, value)| TsField {
+                        name: name.clone(),
+                        ty: TsType::infer(value),
+                        optional: false,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Combine two observed types. Objects merge their fields (a field missing on
+    /// either side becomes optional), arrays merge their element types and
+    /// anything else becomes a union.
+    fn merge(self, other: TsType) -> TsType {
+        let mut members: Vec<TsType> = Vec::new();
+        for ty in self.into_members().into_iter().chain(other.into_members()) {
+            add_member(&mut members, ty);
+        }
+        if members.len() > 1 {
+            members.retain(|m| *m != TsType::Unknown);
+        }
+        if members.len() == 1 {
+            members.remove(0)
+        } else {
+            TsType::Union(members)
+        }
+    }
+
+    fn into_members(self) -> Vec<TsType> {
+        match self {
+            TsType::Union(members) => members,
+            other => vec![other],
+        }
+    }
+}
+
+fn add_member(members: &mut Vec<TsType>, ty: TsType) {
+    match ty {
+        TsType::Object(fields) => {
+            match members.iter_mut().find_map(|m| match m {
+                TsType::Object(existing) => Some(existing),
+                _ => None,
+            }) {
+                Some(existing) => merge_fields(existing, fields),
+                None => members.push(TsType::Object(fields)),
+            }
+        }
+        TsType::Array(element) => {
+            match members.iter_mut().find_map(|m| match m {
+                TsType::Array(existing) => Some(existing),
+                _ => None,
+            }) {
+                Some(existing) => {
+                    let current = std::mem::replace(existing.as_mut(), TsType::Unknown);
+                    **existing = current.merge(*element);
+                }
+                None => members.push(TsType::Array(element)),
+            }
+        }
+        other => {
+            if !members.contains(&other) {
+                members.push(other);
+            }
+        }
+    }
+}
+
+fn merge_fields(existing: &mut Vec<TsField>, incoming: Vec<TsField>) {
+    for field in existing.iter_mut() {
+        if !incoming.iter().any(|f| f.name == field.name) {
+            field.optional = true;
+        }
+    }
+    for field in incoming {
+        match existing.iter_mut().find(|f| f.name == field.name) {
+            Some(current) => {
+                let ty = std::mem::replace(&mut current.ty, TsType::Unknown);
+                current.ty = ty.merge(field.ty);
+                current.optional |= field.optional;
+            }
+            None => existing.push(TsField {
+                optional: true,
+                ..field
+            }),
+        }
+    }
+}
+
+/// Assigns interface names and collects the interface declarations to emit.
+#[derive(Default)]
+struct Renderer {
+    used_names: HashSet<String>,
+    pending: VecDeque<(String, Vec<TsField>)>,
+}
+
+impl Renderer {
+    fn reserve(&mut self, base: &str) -> String {
+        let mut name = base.to_string();
+        let mut suffix = 2;
+        while !self.used_names.insert(name.clone()) {
+            name = format!("{base}{suffix}");
+            suffix += 1;
+        }
+        name
+    }
+
+    /// Render a type expression, queueing an interface for every object type.
+    /// `hint` names the interface for an object found at this position.
+    fn type_expr(&mut self, ty: &TsType, hint: &str) -> String {
+        match ty {
+            TsType::String => "string".to_string(),
+            TsType::Number => "number".to_string(),
+            TsType::Boolean => "boolean".to_string(),
+            TsType::Null => "null".to_string(),
+            TsType::Unknown => "unknown".to_string(),
+            TsType::Array(element) => {
+                let inner = self.type_expr(element, &element_hint(hint));
+                if matches!(**element, TsType::Union(_)) {
+                    format!("({inner})[]")
+                } else {
+                    format!("{inner}[]")
+                }
+            }
+            TsType::Object(fields) => {
+                let name = self.reserve(hint);
+                self.pending.push_back((name.clone(), fields.clone()));
+                name
+            }
+            TsType::Union(members) => {
+                // Keep `null` last so nullable fields read as `T | null`.
+                let (nulls, others): (Vec<&TsType>, Vec<&TsType>) =
+                    members.iter().partition(|m| **m == TsType::Null);
+                others
+                    .into_iter()
+                    .chain(nulls)
+                    .map(|m| self.type_expr(m, hint))
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            }
+        }
+    }
+
+    fn interface(&mut self, name: &str, fields: &[TsField]) -> String {
+        if fields.is_empty() {
+            return format!("export interface {name} {{}}\n");
+        }
+        let mut out = format!("export interface {name} {{\n");
+        for field in fields {
+            let ty = self.type_expr(&field.ty, &pascal_case(&field.name));
+            let optional = if field.optional { "?" } else { "" };
+            out.push_str(&format!(
+                "  {}{optional}: {ty};\n",
+                property_key(&field.name)
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Infer TypeScript declarations describing `value`.
+///
+/// Objects become interfaces (nested objects get their own interface named after
+/// their key), arrays become `T[]`, mixed types become unions and nullable fields
+/// become `T | null`. Objects inside arrays are merged into a single interface;
+/// keys not present in every element are marked optional. A non-object root is
+/// emitted as a type alias.
+pub fn infer_typescript_interface(value: &Value, root_name: &str) -> String {
+    let root_name = pascal_case(root_name);
+    let mut renderer = Renderer::default();
+    let mut blocks = Vec::new();
+
+    match TsType::infer(value) {
+        TsType::Object(fields) => {
+            let name = renderer.reserve(&root_name);
+            renderer.pending.push_back((name, fields));
+        }
+        other => {
+            let name = renderer.reserve(&root_name);
+            let expr = renderer.type_expr(&other, &root_name);
+            blocks.push(format!("export type {name} = {expr};\n"));
+        }
+    }
+
+    while let Some((name, fields)) = renderer.pending.pop_front() {
+        let block = renderer.interface(&name, &fields);
+        blocks.push(block);
+    }
+    blocks.join("\n")
+}
+
+/// Derive an interface name from a tree path like `"0.user.addresses[2]"`.
+///
+/// The last key is used (singularized when the path ends in an array index);
+/// a bare record index yields `"Root"`.
+pub fn interface_name_from_path(path: &str) -> String {
+    let mut rest = path;
+    let mut indexed = false;
+    while rest.ends_with(']')
+        && let Some(open) = rest.rfind('[')
+    {
+        rest = &rest[..open];
+        indexed = true;
+    }
+
+    let key = rest.rsplit('.').next().unwrap_or(rest);
+    if key.is_empty() || key.chars().all(|c| c.is_ascii_digit()) {
+        return "Root".to_string();
+    }
+    let name = pascal_case(key);
+    if indexed { singularize(&name) } else { name }
+}
+
+fn element_hint(hint: &str) -> String {
+    let singular = singularize(hint);
+    if singular == hint {
+        format!("{hint}Item")
+    } else {
+        singular
+    }
+}
+
+fn singularize(name: &str) -> String {
+    if let Some(stem) = name.strip_suffix("ies") {
+        format!("{stem}y")
+    } else if ["sses", "xes", "ches", "shes"]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+    {
+        name[..name.len() - 2].to_string()
+    } else if name.ends_with('s') && !name.ends_with("ss") && name.len() > 1 {
+        name[..name.len() - 1].to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// `"user_profile"` / `"user-profile"` / `"userProfile"` → `"UserProfile"`.
+fn pascal_case(raw: &str) -> String {
+    let mut name = String::new();
+    for part in raw.split(|c: char| !c.is_alphanumeric()) {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            name.extend(first.to_uppercase());
+            name.push_str(chars.as_str());
+        }
+    }
+    if name.is_empty() {
+        "Root".to_string()
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("T{name}")
+    } else {
+        name
+    }
+}
+
+fn property_key(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_string()
+    } else {
+        serde_json::to_string(name).unwrap_or_else(|_| format!("\"{name}\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_flat_object() {
+        let value = json!({"id": 1, "name": "Ada", "active": true, "nickname": null});
+        assert_eq!(
+            infer_typescript_interface(&value, "user"),
+            "export interface User {\n  active: boolean;\n  id: number;\n  name: string;\n  nickname: null;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_nested_objects_get_their_own_interfaces() {
+        let value = json!({
+            "user": {"name": "Ada", "address": {"city": "London", "zip": "N1"}}
+        });
+        assert_eq!(
+            infer_typescript_interface(&value, "Root"),
+            "export interface Root {\n  user: User;\n}\n\n\
+             export interface User {\n  address: Address;\n  name: string;\n}\n\n\
+             export interface Address {\n  city: string;\n  zip: string;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_arrays_of_objects_merge_keys_and_mark_optional() {
+        let value = json!({
+            "items": [
+                {"id": 1, "label": "a"},
+                {"id": 2, "price": 9.5},
+                {"id": 3, "label": null}
+            ]
+        });
+        assert_eq!(
+            infer_typescript_interface(&value, "Order"),
+            "export interface Order {\n  items: Item[];\n}\n\n\
+             export interface Item {\n  id: number;\n  label?: string | null;\n  price?: number;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_primitive_and_mixed_arrays() {
+        let value = json!({
+            "tags": ["a", "b"],
+            "mixed": [1, "two", null],
+            "empty": [],
+            "matrix": [[1, 2], [3]]
+        });
+        let ts = infer_typescript_interface(&value, "Root");
+        assert!(ts.contains("  tags: string[];\n"), "{ts}");
+        assert!(
+            ts.contains("  mixed: (number | string | null)[];\n"),
+            "{ts}"
+        );
+        assert!(ts.contains("  empty: unknown[];\n"), "{ts}");
+        assert!(ts.contains("  matrix: number[][];\n"), "{ts}");
+    }
+
+    #[test]
+    fn test_empty_array_merges_with_populated_array() {
+        let value = json!([{"tags": []}, {"tags": ["x"]}]);
+        let ts = infer_typescript_interface(&value, "posts");
+        assert!(ts.starts_with("export type Posts = Post[];\n"), "{ts}");
+        assert!(ts.contains("  tags: string[];\n"), "{ts}");
+    }
+
+    #[test]
+    fn test_mixed_field_types_become_unions() {
+        let value = json!([{"id": 1}, {"id": "abc"}, {"id": {"raw": 7}}]);
+        let ts = infer_typescript_interface(&value, "records");
+        assert!(ts.contains("  id: number | string | Id;\n"), "{ts}");
+        assert!(
+            ts.contains("export interface Id {\n  raw: number;\n}\n"),
+            "{ts}"
+        );
+    }
+
+    #[test]
+    fn test_non_identifier_keys_are_quoted() {
+        let value = json!({"first-name": "Ada", "2fa": false, "ok_key": 1});
+        let ts = infer_typescript_interface(&value, "Root");
+        assert!(ts.contains("  \"2fa\": boolean;\n"), "{ts}");
+        assert!(ts.contains("  \"first-name\": string;\n"), "{ts}");
+        assert!(ts.contains("  ok_key: number;\n"), "{ts}");
+    }
+
+    #[test]
+    fn test_duplicate_interface_names_are_suffixed() {
+        let value = json!({"meta": {"id": 1}, "child": {"meta": {"tag": "x"}}});
+        let ts = infer_typescript_interface(&value, "Root");
+        assert!(
+            ts.contains("export interface Meta {\n  id: number;\n}\n"),
+            "{ts}"
+        );
+        assert!(
+            ts.contains("export interface Meta2 {\n  tag: string;\n}\n"),
+            "{ts}"
+        );
+    }
+
+    #[test]
+    fn test_interface_name_from_path() {
+        assert_eq!(interface_name_from_path("0"), "Root");
+        assert_eq!(interface_name_from_path("0.user"), "User");
+        assert_eq!(
+            interface_name_from_path("3.user.shipping_address"),
+            "ShippingAddress"
+        );
+        assert_eq!(interface_name_from_path("0.categories[2]"), "Category");
+        assert_eq!(interface_name_from_path("0.addresses[0]"), "Address");
+        assert_eq!(interface_name_from_path("0.matrix[0][1]"), "Matrix");
+    }
+}
@@ -16,46 +16,71 @@ use thoth::{
     plugin::manager::PluginManager, settings,
 };
 
-/// Parse command-line arguments to extract file path
-fn parse_file_argument(args: &[String]) -> Result<Option<PathBuf>> {
+/// Collect the files passed on the command line (`thoth a.json b.ndjson`)
+///
+/// Paths that can't be opened are reported on stderr and skipped, so a typo
+/// still launches the app, just without that file.
+fn parse_file_arguments(args: &[String]) -> Vec<PathBuf> {
     // Skip first argument (executable name)
-    if args.len() < 2 {
-        return Ok(None);
-    }
-
-    let file_path_str = &args[1];
+    args.iter()
+        .skip(1)
+        .filter_map(|arg| {
+            if arg.starts_with('-') {
+                // macOS passes a process serial number (`-psn_…`) to apps launched from Finder
+                if !arg.starts_with("-psn_") {
+                    eprintln!("Warning: Ignoring unknown option '{}'", arg);
+                }
+                return None;
+            }
+            resolve_file_argument(arg)
+                .map_err(|e| eprintln!("Error: {}", e))
+                .ok()
+        })
+        .collect()
+}
 
+/// Resolve one path argument to an absolute path of an existing file
+fn resolve_file_argument(file_path_str: &str) -> Result<PathBuf> {
     // Validate and sanitize the path
     let path = PathBuf::from(file_path_str);
 
     // Resolve to absolute path
-    let canonical_path = match path.canonicalize() {
-        Ok(p) => p,
-        Err(e) => {
-            eprintln!("Error: Cannot open file '{}': {}", file_path_str, e);
-            return Err(format!("Cannot open file '{}': {}", file_path_str, e).into());
-        }
-    };
+    let canonical_path = path
+        .canonicalize()
+        .map_err(|e| format!("Cannot open file '{}': {}", file_path_str, e))?;
 
     // Verify it's a file (not a directory)
     if !canonical_path.is_file() {
-        eprintln!("Error: '{}' is not a file", file_path_str);
-        return Err(format!("Not a file: {}", file_path_str).into());
+        return Err(format!("Cannot open '{}': not a file", file_path_str).into());
     }
 
     // Verify file extension is JSON-related
     if let Some(ext) = canonical_path.extension() {
         let ext_lower = ext.to_string_lossy().to_lowercase();
-        if !matches!(ext_lower.as_str(), "json" | "ndjson" | "jsonl" | "geojson") {
+        if !matches!(
+            ext_lower.as_str(),
+            "json"
+                | "ndjson"
+                | "jsonl"
+                | "geojson"
+                | "csv"
+                | "tsv"
+                | "yaml"
+                | "yml"
+                | "toml"
+                | "xml"
+                | "parquet"
+                | "gz"
+        ) {
             eprintln!(
-                "Warning: File '{}' does not have a JSON extension",
+                "Warning: File '{}' does not have a JSON, CSV, YAML, TOML, XML or Parquet extension",
                 file_path_str
             );
             // Allow opening anyway - user might know what they're doing
         }
     }
 
-    Ok(Some(canonical_path))
+    Ok(canonical_path)
 }
 
 fn main() -> Result<()> {
@@ -88,7 +113,7 @@ fn main() -> Result<()> {
             .map_err(|e| format!("MCP error: {e}").into());
     }
 
-    let file_to_open = parse_file_argument(&args)?;
+    let files_to_open = parse_file_arguments(&args);
 
     // Load settings first
     let settings = settings::Settings::load().unwrap_or_else(|e| {
@@ -127,12 +152,25 @@ fn main() -> Result<()> {
     if let Some(icon_data) = icon {
         viewport = viewport.with_icon(icon_data);
     }
+    // Reopen where the window was last closed; the app moves it back on-screen
+    // if that monitor is gone
+    let saved_geometry = settings
+        .ui
+        .restore_window_geometry
+        .then(|| app::persistent_state::PersistentState::load().ok())
+        .flatten()
+        .and_then(|state| state.get_window_geometry());
+    viewport = match saved_geometry {
+        Some(geometry) => viewport
+            .with_position(geometry.position)
+            .with_inner_size(geometry.size),
+        None => viewport.with_inner_size([
+            settings.window.default_width,
+            settings.window.default_height,
+        ]),
+    };
     let options = NativeOptions {
         viewport: viewport
-            .with_inner_size([
-                settings.window.default_width,
-                settings.window.default_height,
-            ])
             // macOS-specific: Unified title bar (like VS Code)
             // This extends content into title bar area, allowing toolbar to share row with traffic lights
             .with_fullsize_content_view(true)
@@ -161,7 +199,7 @@ fn main() -> Result<()> {
             );
             cc.egui_ctx.set_fonts(fonts);
 
-            let mut app = app::ThothApp::new(settings, file_to_open);
+            let mut app = app::ThothApp::new(settings, files_to_open);
             app.setup_native_menu(cc);
             Ok(Box::new(app))
         }),
@@ -184,11 +222,16 @@ fn main() -> Result<()> {
 mod tests {
     use super::*;
 
+    fn args(paths: &[&str]) -> Vec<String> {
+        std::iter::once("thoth")
+            .chain(paths.iter().copied())
+            .map(String::from)
+            .collect()
+    }
+
     #[test]
     fn test_parse_no_arguments() {
-        let args = vec!["thoth".to_string()];
-        let result = parse_file_argument(&args).unwrap();
-        assert!(result.is_none());
+        assert!(parse_file_arguments(&args(&[])).is_empty());
     }
 
     #[test]
@@ -197,31 +240,51 @@ mod tests {
         let test_file = std::env::temp_dir().join("test_parse.json");
         std::fs::write(&test_file, r#"{"test": true}"#).unwrap();
 
-        let args = vec!["thoth".to_string(), test_file.to_string_lossy().to_string()];
-        let result = parse_file_argument(&args).unwrap();
-        assert!(result.is_some());
-
-        let path = result.unwrap();
-        assert!(path.exists());
-        assert!(path.is_file());
+        let result = parse_file_arguments(&args(&[&test_file.to_string_lossy()]));
+        assert_eq!(result.len(), 1);
+        assert!(result[0].exists());
+        assert!(result[0].is_file());
 
         // Cleanup
         std::fs::remove_file(&test_file).ok();
     }
 
+    #[test]
+    fn test_parse_multiple_files_skips_invalid() {
+        let first = std::env::temp_dir().join("test_parse_first.json");
+        let second = std::env::temp_dir().join("test_parse_second.ndjson");
+        std::fs::write(&first, r#"{"test": true}"#).unwrap();
+        std::fs::write(&second, "{}\n{}\n").unwrap();
+
+        let result = parse_file_arguments(&args(&[
+            &first.to_string_lossy(),
+            "/nonexistent/file.json",
+            "--unknown",
+            "-psn_0_12345",
+            &second.to_string_lossy(),
+        ]));
+        assert_eq!(
+            result,
+            [
+                first.canonicalize().unwrap(),
+                second.canonicalize().unwrap()
+            ]
+        );
+
+        std::fs::remove_file(&first).ok();
+        std::fs::remove_file(&second).ok();
+    }
+
     #[test]
     fn test_parse_nonexistent_file() {
-        let args = vec!["thoth".to_string(), "/nonexistent/file.json".to_string()];
-        let result = parse_file_argument(&args);
-        assert!(result.is_err());
+        assert!(resolve_file_argument("/nonexistent/file.json").is_err());
+        assert!(parse_file_arguments(&args(&["/nonexistent/file.json"])).is_empty());
     }
 
     #[test]
     fn test_parse_directory_not_file() {
         let temp_dir = std::env::temp_dir();
-        let args = vec!["thoth".to_string(), temp_dir.to_string_lossy().to_string()];
-        let result = parse_file_argument(&args);
-        assert!(result.is_err());
+        assert!(resolve_file_argument(&temp_dir.to_string_lossy()).is_err());
     }
 
     #[test]
@@ -234,16 +297,13 @@ mod tests {
         let original_dir = std::env::current_dir().unwrap();
         std::env::set_current_dir(std::env::temp_dir()).unwrap();
 
-        let args = vec!["thoth".to_string(), "test_relative.json".to_string()];
-        let result = parse_file_argument(&args).unwrap();
-        assert!(result.is_some());
-
-        let path = result.unwrap();
-        assert!(path.is_absolute());
+        let result = resolve_file_argument("test_relative.json");
 
         // Restore directory and cleanup
         std::env::set_current_dir(original_dir).unwrap();
         std::fs::remove_file(&test_file).ok();
+
+        assert!(result.unwrap().is_absolute());
     }
 
     #[test]
@@ -254,9 +314,8 @@ mod tests {
             let test_file = std::env::temp_dir().join(format!("test.{}", ext));
             std::fs::write(&test_file, r#"{"test": true}"#).unwrap();
 
-            let args = vec!["thoth".to_string(), test_file.to_string_lossy().to_string()];
-            let result = parse_file_argument(&args).unwrap();
-            assert!(result.is_some(), "Failed for extension: {}", ext);
+            let result = resolve_file_argument(&test_file.to_string_lossy());
+            assert!(result.is_ok(), "Failed for extension: {}", ext);
 
             std::fs::remove_file(&test_file).ok();
         }
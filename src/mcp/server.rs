@@ -23,7 +23,10 @@ pub async fn run_mcp_server() -> anyhow::Result<()> {
 
     tracing::info!("Starting Thoth MCP server");
 
-    let state = ServerState::new();
+    // Only the size limit is read from settings; a missing or broken
+    // settings file falls back to the defaults
+    let settings = crate::settings::Settings::load().unwrap_or_default();
+    let state = ServerState::with_max_file_size_mb(settings.performance.max_file_size_mb);
     let server = ThothMcpServer::new(state);
 
     let service = server.serve(stdio()).await?;
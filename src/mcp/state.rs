@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
+use crate::constants::DEFAULT_MAX_FILE_SIZE_MB;
 use crate::error::Result;
 use crate::file::detect_file_type::DetectedFileType;
 use crate::file::loaders::{FileKind, FileType, load_file_auto};
@@ -18,8 +19,11 @@ pub struct OpenFile {
 
 impl OpenFile {
     /// Open a file at the given path with automatic format detection.
-    pub fn open(path: &Path) -> Result<Self> {
-        let (detected, file_type) = load_file_auto(path)?;
+    ///
+    /// A `.gz` file may decompress to at most `max_file_size_mb` megabytes.
+    pub fn open(path: &Path, max_file_size_mb: u64) -> Result<Self> {
+        let (detection, file_type) = load_file_auto(path, max_file_size_mb)?;
+        let detected = detection.file_type;
         let file_kind = FileKind::from(detected);
         Ok(Self {
             path: path.to_path_buf(),
@@ -40,6 +44,12 @@ impl OpenFile {
             DetectedFileType::Ndjson => "ndjson",
             DetectedFileType::JsonArray => "json_array",
             DetectedFileType::JsonObject => "json_object",
+            DetectedFileType::Csv => "csv",
+            DetectedFileType::Yaml => "yaml",
+            DetectedFileType::Toml => "toml",
+            DetectedFileType::Xml => "xml",
+            DetectedFileType::Parquet => "parquet",
+            DetectedFileType::Jsonc => "jsonc",
         }
     }
 }
@@ -47,9 +57,17 @@ impl OpenFile {
 /// Thread-safe shared state for the MCP server.
 ///
 /// Keyed by a user-chosen handle (defaults to the file path string).
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct ServerState {
     inner: Arc<Mutex<ServerStateInner>>,
+    /// Largest size in megabytes a `.gz` file may decompress to
+    max_file_size_mb: u64,
+}
+
+impl Default for ServerState {
+    fn default() -> Self {
+        Self::with_max_file_size_mb(DEFAULT_MAX_FILE_SIZE_MB)
+    }
 }
 
 #[derive(Default)]
@@ -63,9 +81,23 @@ impl ServerState {
         Self::default()
     }
 
+    /// Server state whose `.gz` files may decompress to at most
+    /// `max_file_size_mb` megabytes, as `performance.max_file_size_mb` sets
+    pub fn with_max_file_size_mb(max_file_size_mb: u64) -> Self {
+        Self {
+            inner: Arc::default(),
+            max_file_size_mb,
+        }
+    }
+
+    /// Largest size in megabytes a `.gz` file may decompress to
+    pub fn max_file_size_mb(&self) -> u64 {
+        self.max_file_size_mb
+    }
+
     /// Open a file and return its handle.
     pub fn open_file(&self, path: &Path) -> Result<(String, FileInfo)> {
-        let open = OpenFile::open(path)?;
+        let open = OpenFile::open(path, self.max_file_size_mb)?;
         let info = FileInfo {
             handle: String::new(), // filled below
             path: path.display().to_string(),
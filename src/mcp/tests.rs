@@ -290,6 +290,72 @@ mod mcp_tests {
         assert_eq!(search.results.len(), 0);
     }
 
+    #[test]
+    fn test_search_scoped_to_record() {
+        use crate::file::loaders::FileKind;
+        use crate::search::{QueryMode, Search};
+
+        let file = create_ndjson_file(&[
+            r#"{"name":"alice"}"#,
+            r#"{"name":"bob"}"#,
+            r#"{"name":"alice"}"#,
+        ]);
+
+        let mut search = Search {
+            query: "alice".to_string(),
+            query_mode: QueryMode::Text,
+            scope_path: Some("2".to_string()),
+            ..Search::default()
+        };
+
+        let path_opt = Some(file.path().to_path_buf());
+        search.start_scanning_internal(&path_opt, &FileKind::Ndjson);
+
+        assert!(search.error.is_none());
+        assert_eq!(search.results.len(), 1);
+        assert_eq!(search.results.hits()[0].record_index, 2);
+    }
+
+    #[test]
+    fn test_search_scoped_to_subtree() {
+        use crate::file::loaders::FileKind;
+        use crate::search::{QueryMode, Search};
+
+        let file = create_ndjson_file(&[
+            r#"{"owner":{"name":"alice"},"members":[{"name":"bob"},{"name":"carol"}]}"#,
+        ]);
+
+        // "alice" is in the record but outside the scoped subtree
+        let mut search = Search {
+            query: "alice".to_string(),
+            query_mode: QueryMode::Text,
+            scope_path: Some("0.members".to_string()),
+            ..Search::default()
+        };
+        let path_opt = Some(file.path().to_path_buf());
+        search.start_scanning_internal(&path_opt, &FileKind::Ndjson);
+        assert!(search.error.is_none());
+        assert!(search.results.is_empty());
+
+        // Field paths are reported relative to the whole document
+        search.query = "carol".to_string();
+        search.start_scanning_internal(&path_opt, &FileKind::Ndjson);
+        let paths: Vec<_> = search.results.hits()[0]
+            .fragments
+            .iter()
+            .filter_map(|fragment| fragment.path.as_deref())
+            .collect();
+        assert!(paths.contains(&"0.members[1].name"), "paths: {:?}", paths);
+
+        // In JSONPath mode `$` is the scoped node
+        search.query = "$[*].name".to_string();
+        search.query_mode = QueryMode::JsonPath;
+        search.scope_path = Some("0.members/_close".to_string());
+        search.start_scanning_internal(&path_opt, &FileKind::Ndjson);
+        assert!(search.error.is_none());
+        assert_eq!(search.results.hits()[0].fragments.len(), 2);
+    }
+
     #[test]
     fn test_search_no_matches() {
         use crate::file::loaders::FileKind;
@@ -39,7 +39,7 @@ pub struct OpenFileResult {
     pub handle: String,
     /// The resolved file path.
     pub path: String,
-    /// Detected format: "ndjson", "json_array", or "json_object".
+    /// Detected format: "ndjson", "json_array", "json_object", "csv", "yaml", "toml", "xml", or "parquet".
     pub file_type: String,
     /// Number of top-level records in the file.
     pub record_count: usize,
@@ -103,12 +103,15 @@ pub struct SearchParams {
     /// Handle of the open file to search.
     pub handle: String,
     /// The search query string. For text search, this is a substring.
-    /// For JSONPath, prefix with `$` (e.g. `$.user.name`).
+    /// For JSONPath, prefix with `$` (e.g. `$.user.name`). For filter mode,
+    /// field comparisons (e.g. `price > 100 AND status = "open"`). For fuzzy
+    /// mode, characters of a key or value in order (e.g. `usrnm`).
     pub query: String,
-    /// Search mode: "text" or "jsonpath". Defaults to "text".
+    /// Search mode: "text", "jsonpath", "filter" or "fuzzy". Defaults to "text".
     /// If the query starts with "$", jsonpath mode is used automatically.
     pub mode: Option<String>,
     /// Whether to match case-sensitively. Defaults to false.
+    /// Also applies to JSONPath member names (`$.userId` vs `userid`).
     pub match_case: Option<bool>,
     /// Maximum number of results to return. Defaults to 50.
     pub max_results: Option<usize>,
@@ -122,7 +125,7 @@ pub struct SearchResult {
     pub matches: Vec<SearchMatch>,
     /// The query that was executed.
     pub query: String,
-    /// The mode used: "text" or "jsonpath".
+    /// The mode used: "text", "jsonpath" or "filter".
     pub mode: String,
 }
 
@@ -338,7 +341,7 @@ impl ThothMcpServer {
 
     #[tool(
         name = "search",
-        description = "Search records in an open file using text substring match or JSONPath query. Returns matching record indices with preview snippets."
+        description = "Search records in an open file using text substring match, JSONPath query, field comparison filter, or fuzzy key/value match. Returns matching record indices with preview snippets."
     )]
     fn search(&self, Parameters(params): Parameters<SearchParams>) -> Json<SearchResult> {
         use crate::search::{QueryMode, Search};
@@ -350,6 +353,8 @@ impl ThothMcpServer {
         let mode = match params.mode.as_deref() {
             Some("jsonpath") => QueryMode::JsonPath,
             Some("text") => QueryMode::Text,
+            Some("filter") => QueryMode::Filter,
+            Some("fuzzy") => QueryMode::Fuzzy,
             _ => {
                 if params.query.starts_with('$') {
                     QueryMode::JsonPath
@@ -361,6 +366,8 @@ impl ThothMcpServer {
 
         let mode_str = match mode {
             QueryMode::Text => "text",
+            QueryMode::Filter => "filter",
+            QueryMode::Fuzzy => "fuzzy",
             QueryMode::JsonPath => "jsonpath",
         };
 
@@ -386,6 +393,7 @@ impl ThothMcpServer {
             query: params.query.clone(),
             match_case,
             query_mode: mode,
+            max_file_size_mb: Some(self.state.max_file_size_mb()),
             ..Search::default()
         };
 
@@ -10,6 +10,11 @@ use std::{
 
 use crate::NOTIFICATION_MANAGER;
 
+/// How long a toast stays up when no expiration is given
+const DEFAULT_TOAST_DURATION: Duration = Duration::from_secs(5);
+/// Short-lived confirmations like "Copied" need less time on screen
+const SHORT_TOAST_DURATION: Duration = Duration::from_secs(2);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NotificationStatus {
     Running,
@@ -49,6 +54,49 @@ pub struct Notification {
     pub pinned: bool,
 }
 
+/// Transient feedback shown only as a toast
+///
+/// Unlike a [`Notification`], a toast isn't kept in the notification panel;
+/// it's for confirmations ("Bookmark added") and non-fatal errors that don't
+/// warrant a blocking modal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    pub kind: NotificationKind,
+    pub text: String,
+    pub duration: Duration,
+}
+
+impl Toast {
+    pub fn new(kind: NotificationKind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+            duration: DEFAULT_TOAST_DURATION,
+        }
+    }
+
+    pub fn info(text: impl Into<String>) -> Self {
+        Self::new(NotificationKind::Info, text)
+    }
+
+    pub fn success(text: impl Into<String>) -> Self {
+        Self::new(NotificationKind::Success, text).with_duration(SHORT_TOAST_DURATION)
+    }
+
+    pub fn warning(text: impl Into<String>) -> Self {
+        Self::new(NotificationKind::Warn, text)
+    }
+
+    pub fn error(text: impl Into<String>) -> Self {
+        Self::new(NotificationKind::Error, text)
+    }
+
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+}
+
 pub struct NotificationManager {
     pub toasts: Toasts,
     pub notifications: HashMap<String, Notification>,
@@ -76,6 +124,15 @@ impl NotificationManager {
         id
     }
 
+    /// Show a transient toast without recording a notification
+    pub fn toast(toast: Toast) {
+        if let Some(mutex) = NOTIFICATION_MANAGER.get()
+            && let Ok(mut nm) = mutex.lock()
+        {
+            nm.push_toast(&toast);
+        }
+    }
+
     pub fn notify_error(notification: Notification) -> String {
         Self::notify(
             notification
@@ -127,13 +184,29 @@ impl NotificationManager {
         }
 
         if notification.show_toast {
-            self.toasts
-                .info(notification.title.clone())
-                .closable(true)
-                .duration(Duration::from_secs(5));
+            self.push_toast(&Toast {
+                kind: notification.kind,
+                text: notification.title.clone(),
+                duration: notification.expire_after.unwrap_or(DEFAULT_TOAST_DURATION),
+            });
         }
     }
 
+    /// Queue a toast card, styled by its kind's severity
+    pub fn push_toast(&mut self, toast: &Toast) {
+        let text = toast.text.clone();
+        let card = match toast.kind {
+            NotificationKind::Success => self.toasts.success(text),
+            NotificationKind::Error => self.toasts.error(text),
+            NotificationKind::Warn => self.toasts.warning(text),
+            NotificationKind::Update
+            | NotificationKind::Plugin
+            | NotificationKind::Tip
+            | NotificationKind::Info => self.toasts.info(text),
+        };
+        card.closable(true).duration(toast.duration);
+    }
+
     pub fn show_notifications(&mut self, ctx: &egui::Context) {
         self.toasts.show(ctx);
     }
@@ -236,3 +309,29 @@ impl Notification {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toast_constructors_set_kind() {
+        assert_eq!(Toast::info("a").kind, NotificationKind::Info);
+        assert_eq!(Toast::success("a").kind, NotificationKind::Success);
+        assert_eq!(Toast::warning("a").kind, NotificationKind::Warn);
+        assert_eq!(Toast::error("a").kind, NotificationKind::Error);
+        assert_eq!(Toast::success("a").duration, SHORT_TOAST_DURATION);
+        assert_eq!(Toast::error("a").duration, DEFAULT_TOAST_DURATION);
+    }
+
+    #[test]
+    fn test_toast_is_not_recorded_as_notification() {
+        let mut nm = NotificationManager::new();
+        nm.push_toast(&Toast::success("Copied"));
+        assert!(nm.notifications.is_empty());
+        assert!(nm.tasks.is_empty());
+
+        nm.add_notification(Notification::new("Saved", "").with_kind(NotificationKind::Success));
+        assert_eq!(nm.notifications.len(), 1);
+    }
+}
@@ -2,7 +2,7 @@
 ///
 /// Provides platform-specific file operations like setting executable permissions
 use crate::error::Result;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub trait FileSystemOps {
     /// Make a file executable
@@ -32,3 +32,60 @@ impl FileSystemOps for PlatformFs {
 pub fn get_fs_ops() -> Box<dyn FileSystemOps> {
     Box::new(PlatformFs)
 }
+
+/// Resolve `path` to the canonical identity of the file it points at
+///
+/// Symlinks, `..` and relative segments are resolved so the same file always
+/// maps to the same path. Falls back to the given path when it can't be
+/// resolved (broken symlink, file no longer exists).
+pub fn canonical_path(path: &Path) -> PathBuf {
+    match std::fs::canonicalize(path) {
+        Ok(canonical) => strip_verbatim_prefix(canonical),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// String form of [`canonical_path`], used as the persistence key for recent
+/// files, bookmarks and search history
+pub fn canonical_path_key(path: &str) -> String {
+    canonical_path(Path::new(path))
+        .to_str()
+        .map(str::to_string)
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// `canonicalize` returns `\\?\C:\...` verbatim paths on Windows; keep the
+/// familiar `C:\...` form for local drives so keys stay readable
+#[cfg(windows)]
+fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+    if let Some(rest) = path.to_str().and_then(|s| s.strip_prefix(r"\\?\"))
+        && !rest.starts_with(r"UNC\")
+    {
+        return PathBuf::from(rest);
+    }
+    path
+}
+
+#[cfg(not(windows))]
+fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+    path
+}
+
+/// Whether `path` lives on a network share or a removable drive
+///
+/// Such a file may only be missing because the share or drive isn't mounted
+/// right now, so callers shouldn't treat its absence as permanent. This is a
+/// prefix check on the path, so it costs nothing and never touches the disk;
+/// mapped network drive letters on Windows aren't recognized.
+pub fn is_detachable_path(path: &str) -> bool {
+    const PREFIXES: &[&str] = &[
+        r"\\",
+        "//",
+        "/Volumes/",
+        "/media/",
+        "/run/media/",
+        "/mnt/",
+        "/net/",
+    ];
+    PREFIXES.iter().any(|prefix| path.starts_with(prefix))
+}
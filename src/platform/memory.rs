@@ -0,0 +1,56 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a resident memory reading is reused before sampling again
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+static LAST_SAMPLE: Mutex<Option<(Instant, Option<u64>)>> = Mutex::new(None);
+
+/// Approximate resident memory of this process in bytes
+///
+/// Sampled at most once a second, so it is cheap to call every frame.
+/// Returns `None` on platforms where it can't be read cheaply.
+pub fn resident_memory_bytes() -> Option<u64> {
+    let Ok(mut last) = LAST_SAMPLE.lock() else {
+        return read_resident_memory();
+    };
+    if let Some((at, bytes)) = *last
+        && at.elapsed() < SAMPLE_INTERVAL
+    {
+        return bytes;
+    }
+    let bytes = read_resident_memory();
+    *last = Some((Instant::now(), bytes));
+    bytes
+}
+
+#[cfg(target_os = "linux")]
+fn read_resident_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_vm_rss(&status)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_resident_memory() -> Option<u64> {
+    None
+}
+
+/// Read the `VmRSS:  1234 kB` line of `/proc/self/status`
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_vm_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_vm_rss() {
+        let status = "Name:\tthoth\nVmPeak:\t  900 kB\nVmRSS:\t  2048 kB\nThreads:\t4\n";
+        assert_eq!(parse_vm_rss(status), Some(2048 * 1024));
+        assert_eq!(parse_vm_rss("Name:\tthoth\n"), None);
+    }
+}
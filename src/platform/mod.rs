@@ -20,6 +20,7 @@ pub mod fonts;
 pub mod fs;
 #[cfg(target_os = "macos")]
 pub mod macos;
+pub mod memory;
 pub mod native_menu;
 pub mod path_registry;
 
@@ -27,4 +28,5 @@ pub use archive::get_extractor_for_file;
 pub use file_io::FileIO;
 pub use file_open_channel::{drain_open_requests, enqueue_open_request};
 pub use fonts::{find_font_bytes, list_system_font_families};
-pub use fs::get_fs_ops;
+pub use fs::{canonical_path, canonical_path_key, get_fs_ops, is_detachable_path};
+pub use memory::resident_memory_bytes;
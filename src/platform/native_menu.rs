@@ -11,6 +11,7 @@ pub enum MenuAction {
     OpenFile,
     NewWindow,
     CloseTab,
+    ExportHtml,
     OpenSettings,
 }
 
@@ -72,6 +73,7 @@ pub fn setup(
             true,
             Some(Accelerator::new(Some(CMD_OR_CTRL), Code::KeyN)),
         );
+        let export_html_item = MenuItem::with_id("export_html", "Export View as HTML…", true, None);
         let close_tab_item = MenuItem::with_id(
             "close_tab",
             "Close Tab",
@@ -82,6 +84,8 @@ pub fn setup(
             &open_item,
             &new_window_item,
             &PredefinedMenuItem::separator(),
+            &export_html_item,
+            &PredefinedMenuItem::separator(),
             &close_tab_item,
         ]);
 
@@ -119,6 +123,7 @@ pub fn poll_events() -> Vec<MenuAction> {
                 "open_file" => Some(MenuAction::OpenFile),
                 "new_window" => Some(MenuAction::NewWindow),
                 "close_tab" => Some(MenuAction::CloseTab),
+                "export_html" => Some(MenuAction::ExportHtml),
                 "settings" => Some(MenuAction::OpenSettings),
                 _ => None,
             };
@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 use std::ops::Range;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 use std::{path::PathBuf, sync::mpsc};
 
@@ -9,15 +10,27 @@ use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use super::filter::{FilterMatch, FilterQuery};
 use super::jsonpath::{JsonPathMatch, JsonPathQuery};
 use super::results::{
-    FieldComponent, MatchFragment, MatchPreview, MatchTarget, SearchHit, SearchResults,
+    FieldComponent, MatchFragment, MatchPreview, MatchTarget, RECORD_MARKER_CONFIDENCE, SearchHit,
+    SearchResults,
 };
+use crate::constants::DEFAULT_MAX_FILE_SIZE_MB;
 use crate::error::ThothError;
 use crate::file::loaders::{FileKind, FileType, load_file_auto};
+use crate::helpers::{fuzzy_match, lookup_rel, split_root_rel};
 
 const MAX_FRAGMENTS_PER_RECORD: usize = 64;
 const PREVIEW_CONTEXT_BYTES: usize = 36;
+/// Fuzzy mode skips fields longer than this; nearly any query matches
+/// somewhere in a long enough text
+const MAX_FUZZY_FIELD_BYTES: usize = 256;
+/// A fuzzy field match must average this score per query character, which
+/// rules out matches spread thinly across a field
+const MIN_FUZZY_SCORE_PER_CHAR: i32 = 12;
+/// Records scanned (in parallel) between streamed batches of hits
+const STREAM_CHUNK_RECORDS: usize = 16_384;
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -25,6 +38,11 @@ pub enum QueryMode {
     #[default]
     Text,
     JsonPath,
+    /// Field comparisons such as `price > 100 AND status = "open"`
+    Filter,
+    /// Keys and values containing the query's characters in order, best
+    /// match first
+    Fuzzy,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -34,27 +52,94 @@ pub struct Search {
     pub scanning: bool,
     pub match_case: bool,
     pub query_mode: QueryMode,
+    /// Row path (e.g. `3` or `3.user.items[2]`) that matching is limited to;
+    /// in JSONPath mode `$` refers to this node
+    pub scope_path: Option<String>,
     pub error: Option<ThothError>,
+    /// Stop scanning once this many records have matched; `None` scans everything
+    pub max_results: Option<usize>,
+    /// Largest size in megabytes a `.gz` file may decompress to; `None` uses
+    /// the default `performance.max_file_size_mb`
+    pub max_file_size_mb: Option<u64>,
+}
+
+/// Progress of a background scan, sent over the channel from [`Search::start_scanning`]
+#[derive(Debug)]
+pub enum SearchUpdate {
+    /// Hits from the next block of records, in record order
+    Hits(Vec<SearchHit>),
+    /// The scan ended, or failed
+    Finished(crate::error::Result<ScanSummary>),
+}
+
+/// How a completed scan ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanSummary {
+    /// Number of records in the file
+    pub total_records: usize,
+    /// The scan stopped at `max_results` before visiting every record
+    pub truncated: bool,
+}
+
+/// The query compiled for the selected mode
+enum Matcher {
+    Text,
+    Fuzzy,
+    JsonPath(JsonPathQuery),
+    Filter(FilterQuery),
+}
+
+/// A parsed `scope_path`: the root record to scan and the subtree inside it
+struct SearchScope<'a> {
+    path: &'a str,
+    root: usize,
+    rel: &'a str,
+}
+
+impl<'a> SearchScope<'a> {
+    fn parse(path: &'a str) -> crate::error::Result<Self> {
+        // Closing-bracket rows ("0.user/_close") scope to the node they close
+        let path = path.strip_suffix("/_close").unwrap_or(path);
+        let (root, rel) = split_root_rel(path)?;
+        Ok(Self { path, root, rel })
+    }
+
+    /// Root records to scan: only the scoped one (if it exists)
+    fn records(&self, total: usize) -> Range<usize> {
+        if self.root < total {
+            self.root..self.root + 1
+        } else {
+            0..0
+        }
+    }
+
+    /// Resolve the scoped node inside its root record
+    fn subtree<'v>(&self, root: &'v Value) -> Option<&'v Value> {
+        lookup_rel(root, self.rel).ok()
+    }
 }
 
 impl Search {
-    /// Spawn in background and return a channel to receive the finished Search.
+    /// Spawn in background and return a channel streaming hits as they are found
+    ///
+    /// Records are scanned in blocks, so hits arrive in record order; setting
+    /// `cancel` stops the scan after the block in progress.
     pub fn start_scanning(
         &self,
         file: &Option<PathBuf>,
         file_type: &FileKind,
-    ) -> mpsc::Receiver<Search> {
+        cancel: Arc<AtomicBool>,
+    ) -> mpsc::Receiver<SearchUpdate> {
         let (tx, rx) = mpsc::channel();
-        let mut job = self.clone();
+        let job = self.clone();
         let file = file.clone();
         let file_type = *file_type;
 
-        // mark as scanning for the first UI update
-        job.scanning = true;
-
         thread::spawn(move || {
-            job.start_scanning_internal(&file, &file_type);
-            let _ = tx.send(job); // send finished (scanning=false, results filled)
+            let finished = job.scan(&file, &file_type, &cancel, |hits| {
+                let _ = tx.send(SearchUpdate::Hits(hits));
+            });
+            let _ = tx.send(SearchUpdate::Finished(finished));
         });
 
         rx
@@ -62,94 +147,166 @@ impl Search {
 
     /// Parallel substring scan over the file's records.
     /// Populates `self.results` with matching root indices, then sets `scanning = false`.
-    pub fn start_scanning_internal(&mut self, file: &Option<PathBuf>, _file_type: &FileKind) {
+    pub fn start_scanning_internal(&mut self, file: &Option<PathBuf>, file_type: &FileKind) {
         self.scanning = true;
         self.results.clear();
         self.error = None;
 
+        let mut hits = Vec::new();
+        match self.scan(file, file_type, &AtomicBool::new(false), |batch| {
+            hits.extend(batch)
+        }) {
+            Ok(summary) => {
+                self.results = SearchResults::new(hits, summary.total_records);
+                self.results.set_truncated(summary.truncated);
+            }
+            Err(e) => self.error = Some(e),
+        }
+
+        self.scanning = false;
+    }
+
+    /// Fail with a `SearchError` if the query doesn't parse in its mode
+    pub(super) fn check_query(&self) -> crate::error::Result<()> {
+        self.matcher().map(|_| ())
+    }
+
+    /// Compile the query for the selected mode
+    fn matcher(&self) -> crate::error::Result<Matcher> {
+        Ok(match self.query_mode {
+            QueryMode::Text => Matcher::Text,
+            QueryMode::Fuzzy => Matcher::Fuzzy,
+            QueryMode::JsonPath => {
+                let expr =
+                    JsonPathQuery::parse(&self.query).map_err(|err| ThothError::SearchError {
+                        query: self.query.clone(),
+                        reason: err.to_string(),
+                    })?;
+                Matcher::JsonPath(expr)
+            }
+            QueryMode::Filter => {
+                let expr =
+                    FilterQuery::parse(&self.query).map_err(|err| ThothError::SearchError {
+                        query: self.query.clone(),
+                        reason: err.to_string(),
+                    })?;
+                Matcher::Filter(expr)
+            }
+        })
+    }
+
+    /// Run the query over the file, passing each block's hits to `on_hits`
+    ///
+    /// Stops early once `max_results` records have matched.
+    pub(super) fn scan(
+        &self,
+        file: &Option<PathBuf>,
+        _file_type: &FileKind,
+        cancel: &AtomicBool,
+        mut on_hits: impl FnMut(Vec<SearchHit>),
+    ) -> crate::error::Result<ScanSummary> {
         if self.query.is_empty() {
-            self.scanning = false;
-            return;
+            return Ok(ScanSummary {
+                total_records: 0,
+                truncated: false,
+            });
         }
 
         let Some(path) = file.as_ref() else {
-            self.scanning = false;
-            self.error = Some(ThothError::StateError {
+            return Err(ThothError::StateError {
                 reason: "No file loaded".to_string(),
             });
-            return;
         };
 
         // Open lazily (auto-detect NDJSON / array JSON / single object)
-        let (_detected, store) = match load_file_auto(path) {
-            Ok(result) => result,
-            Err(e) => {
-                self.scanning = false;
-                self.error = Some(ThothError::SearchError {
-                    query: self.query.clone(),
-                    reason: format!("Failed to load file for search: {}", e),
-                });
-                return;
+        let max_file_size_mb = self.max_file_size_mb.unwrap_or(DEFAULT_MAX_FILE_SIZE_MB);
+        let (_detected, store) =
+            load_file_auto(path, max_file_size_mb).map_err(|e| ThothError::SearchError {
+                query: self.query.clone(),
+                reason: format!("Failed to load file for search: {}", e),
+            })?;
+
+        let scope = self
+            .scope_path
+            .as_deref()
+            .map(SearchScope::parse)
+            .transpose()
+            .map_err(|e| ThothError::SearchError {
+                query: self.query.clone(),
+                reason: format!("Invalid search scope: {}", e),
+            })?;
+
+        let matcher = self.matcher()?;
+
+        let total = store.len();
+        let records = scope
+            .as_ref()
+            .map_or(0..total, |scope| scope.records(total));
+        let mut start = records.start;
+        let mut found = 0usize;
+        let mut truncated = false;
+        while start < records.end && !cancel.load(Ordering::Relaxed) {
+            let end = start.saturating_add(STREAM_CHUNK_RECORDS).min(records.end);
+            // Run the appropriate matcher
+            let mut hits = match &matcher {
+                Matcher::Text => parallel_scan(
+                    &store,
+                    start..end,
+                    &self.query,
+                    self.match_case,
+                    scope.as_ref(),
+                ),
+                Matcher::Fuzzy => fuzzy_scan(
+                    &store,
+                    start..end,
+                    &self.query,
+                    self.match_case,
+                    scope.as_ref(),
+                ),
+                Matcher::JsonPath(expr) => {
+                    jsonpath_scan(&store, start..end, expr, self.match_case, scope.as_ref())
+                }
+                Matcher::Filter(expr) => {
+                    filter_scan(&store, start..end, expr, self.match_case, scope.as_ref())
+                }
+            };
+            if let Some(max) = self.max_results
+                && found + hits.len() > max
+            {
+                hits.truncate(max.saturating_sub(found));
+                truncated = true;
             }
-        };
-
-        // Move the store into an Arc so threads can share it immutably.
-        let store = Arc::new(store);
-
-        // Run the appropriate matcher
-        let results = match self.query_mode {
-            QueryMode::Text => parallel_scan(store.clone(), &self.query, self.match_case),
-            QueryMode::JsonPath => {
-                let expr = match JsonPathQuery::parse(&self.query) {
-                    Ok(expr) => expr,
-                    Err(err) => {
-                        self.scanning = false;
-                        self.error = Some(ThothError::SearchError {
-                            query: self.query.clone(),
-                            reason: err.to_string(),
-                        });
-                        return;
-                    }
-                };
-                jsonpath_scan(store.clone(), &expr, self.match_case)
+            found += hits.len();
+            if !hits.is_empty() {
+                on_hits(hits);
             }
-        };
-
-        let results = match results {
-            Ok(v) => v,
-            Err(e) => {
-                self.scanning = false;
-                self.error = Some(ThothError::SearchError {
-                    query: self.query.clone(),
-                    reason: format!("Search operation failed: {}", e),
-                });
-                return;
+            if truncated {
+                break;
             }
-        };
-
-        self.results = results;
+            start = end;
+        }
 
-        self.scanning = false;
+        Ok(ScanSummary {
+            total_records: total,
+            truncated,
+        })
     }
 }
 
+/// Substring-match the records in `records`, returning hits in record order
 fn parallel_scan(
-    store: Arc<FileType>,
+    store: &FileType,
+    records: Range<usize>,
     query: &str,
     match_case: bool,
-) -> crate::error::Result<SearchResults> {
-    let total = store.len();
-    if total == 0 {
-        return Ok(SearchResults::default());
-    }
-
+    scope: Option<&SearchScope<'_>>,
+) -> Vec<SearchHit> {
     // Prepare needle
     let mut needle = query.as_bytes().to_vec();
     let fold = !match_case;
     if fold {
         ascii_lower_in_place(&mut needle);
     }
-    let needle = Arc::new(needle);
     let lowered_query = if match_case {
         None
     } else {
@@ -157,10 +314,19 @@ fn parallel_scan(
     };
 
     let needle_len = needle.len();
-    let mut hits: Vec<SearchHit> = (0..total)
+    let mut hits: Vec<SearchHit> = records
         .into_par_iter()
         .filter_map(|i| {
-            let original = store.raw_slice(i).ok()?;
+            let raw = store.json_slice(i).ok()?;
+            // A subtree scope scans just the scoped node, re-serialized
+            let (original, root_path) = match scope {
+                Some(scope) if !scope.rel.is_empty() => {
+                    let value: Value = serde_json::from_slice(&raw).ok()?;
+                    let subtree = serde_json::to_vec(scope.subtree(&value)?).ok()?;
+                    (subtree, scope.path.to_string())
+                }
+                _ => (raw, i.to_string()),
+            };
             let hay_cow: Cow<'_, [u8]> = if fold {
                 let mut buf = original.clone();
                 ascii_lower_in_place(&mut buf);
@@ -174,38 +340,115 @@ fn parallel_scan(
             let mut fragments = collect_fragments(&finder, hay_slice, needle_len)?;
             let preview = build_preview(&original, fragments.first().unwrap());
             let query_for_fields = lowered_query.as_deref().unwrap_or(query);
-            collect_field_matches(i, &original, query_for_fields, match_case, &mut fragments);
+            collect_field_matches(
+                &root_path,
+                &original,
+                query_for_fields,
+                match_case,
+                &mut fragments,
+            );
             ensure_root_highlight(&mut fragments, i);
 
             Some(SearchHit {
                 record_index: i,
                 fragments,
                 preview,
+                score: 0,
             })
         })
         .collect();
 
     hits.sort_unstable_by_key(|hit| hit.record_index);
-    Ok(SearchResults::new(hits, total))
+    hits
 }
 
-fn jsonpath_scan(
-    store: Arc<FileType>,
-    query: &JsonPathQuery,
+/// Fuzzy-match the keys and scalar values of the records in `records`,
+/// returning hits in record order
+///
+/// Each matched run of characters becomes a fragment, and a record is scored
+/// by its best-matching field.
+fn fuzzy_scan(
+    store: &FileType,
+    records: Range<usize>,
+    query: &str,
     match_case: bool,
-) -> crate::error::Result<SearchResults> {
-    let total = store.len();
-    if total == 0 {
-        return Ok(SearchResults::default());
+    scope: Option<&SearchScope<'_>>,
+) -> Vec<SearchHit> {
+    let query_chars = query.chars().filter(|c| !c.is_whitespace()).count();
+    if query_chars == 0 {
+        return Vec::new();
     }
+    let min_score = MIN_FUZZY_SCORE_PER_CHAR * query_chars as i32;
 
-    let mut hits: Vec<SearchHit> = (0..total)
+    let mut hits: Vec<SearchHit> = records
         .into_par_iter()
         .filter_map(|i| {
-            let bytes = store.raw_slice(i).ok()?;
+            let bytes = store.json_slice(i).ok()?;
             let value: Value = serde_json::from_slice(&bytes).ok()?;
-            let root_path = i.to_string();
-            let mut matches = query.evaluate(&value, &root_path, match_case);
+            let (node, root_path) = match scope {
+                Some(scope) => (scope.subtree(&value)?, scope.path.to_string()),
+                None => (&value, i.to_string()),
+            };
+
+            let mut fragments = Vec::new();
+            // Best field so far: (score, path, text)
+            let mut best: Option<(i32, String, String)> = None;
+            visit_fields(node, &root_path, &mut |path, component, text| {
+                if text.len() > MAX_FUZZY_FIELD_BYTES {
+                    return true;
+                }
+                let Some(found) =
+                    fuzzy_match(query, text, match_case).filter(|found| found.score >= min_score)
+                else {
+                    return true;
+                };
+                if best.as_ref().is_none_or(|(score, ..)| found.score > *score) {
+                    best = Some((found.score, path.to_string(), text.to_string()));
+                }
+                for range in found.ranges {
+                    let matched_text = text.get(range.clone()).map(str::to_string);
+                    fragments.push(field_fragment(path, component, matched_text, Some(range)));
+                }
+                fragments.len() < MAX_FRAGMENTS_PER_RECORD
+            });
+            let (score, path, text) = best?;
+            fragments.truncate(MAX_FRAGMENTS_PER_RECORD);
+            ensure_root_highlight(&mut fragments, i);
+
+            Some(SearchHit {
+                record_index: i,
+                fragments,
+                preview: Some(MatchPreview {
+                    before: format!("{} -> ", path),
+                    highlight: sanitize_snippet(text.as_bytes()),
+                    after: String::new(),
+                }),
+                score: score as u32,
+            })
+        })
+        .collect();
+
+    hits.sort_unstable_by_key(|hit| hit.record_index);
+    hits
+}
+
+/// Evaluate a JSONPath query against the records in `records`, returning hits in record order
+fn jsonpath_scan(
+    store: &FileType,
+    records: Range<usize>,
+    query: &JsonPathQuery,
+    match_case: bool,
+    scope: Option<&SearchScope<'_>>,
+) -> Vec<SearchHit> {
+    let mut hits: Vec<SearchHit> = records
+        .into_par_iter()
+        .filter_map(|i| {
+            let bytes = store.json_slice(i).ok()?;
+            let value: Value = serde_json::from_slice(&bytes).ok()?;
+            let mut matches = match scope {
+                Some(scope) => query.evaluate(scope.subtree(&value)?, scope.path, match_case),
+                None => query.evaluate(&value, &i.to_string(), match_case),
+            };
             if matches.is_empty() {
                 return None;
             }
@@ -221,12 +464,76 @@ fn jsonpath_scan(
                 record_index: i,
                 fragments,
                 preview: Some(preview),
+                score: 0,
             })
         })
         .collect();
 
     hits.sort_unstable_by_key(|hit| hit.record_index);
-    Ok(SearchResults::new(hits, total))
+    hits
+}
+
+/// Evaluate a comparison filter against the records in `records`, returning hits in record order
+fn filter_scan(
+    store: &FileType,
+    records: Range<usize>,
+    query: &FilterQuery,
+    match_case: bool,
+    scope: Option<&SearchScope<'_>>,
+) -> Vec<SearchHit> {
+    let mut hits: Vec<SearchHit> = records
+        .into_par_iter()
+        .filter_map(|i| {
+            let bytes = store.json_slice(i).ok()?;
+            let value: Value = serde_json::from_slice(&bytes).ok()?;
+            let mut matches = match scope {
+                Some(scope) => query.evaluate(scope.subtree(&value)?, scope.path, match_case)?,
+                None => query.evaluate(&value, &i.to_string(), match_case)?,
+            };
+            matches.truncate(MAX_FRAGMENTS_PER_RECORD);
+            let preview = build_filter_preview(query, &matches);
+            let mut fragments: Vec<MatchFragment> = matches
+                .into_iter()
+                .map(|entry| MatchFragment {
+                    fragment_id: 1,
+                    target: MatchTarget::JsonField {
+                        component: FieldComponent::Value,
+                    },
+                    byte_range: 0..0,
+                    path: Some(Arc::<str>::from(entry.path)),
+                    confidence: 1.0,
+                    matched_text: None,
+                    text_range: None,
+                })
+                .collect();
+            ensure_root_highlight(&mut fragments, i);
+            Some(SearchHit {
+                record_index: i,
+                fragments,
+                preview: Some(preview),
+                score: 0,
+            })
+        })
+        .collect();
+
+    hits.sort_unstable_by_key(|hit| hit.record_index);
+    hits
+}
+
+fn build_filter_preview(query: &FilterQuery, matches: &[FilterMatch]) -> MatchPreview {
+    match matches.first() {
+        Some(first) => MatchPreview {
+            before: format!("{} -> {}", query.original(), first.path),
+            highlight: first.display_value.clone(),
+            after: String::new(),
+        },
+        // Matched through NOT only: no single field to point at
+        None => MatchPreview {
+            before: query.original().to_string(),
+            highlight: "match".to_string(),
+            after: String::new(),
+        },
+    }
 }
 
 fn match_fragment_from_jsonpath(entry: JsonPathMatch) -> MatchFragment {
@@ -357,7 +664,7 @@ fn sanitize_snippet(bytes: &[u8]) -> String {
 }
 
 fn collect_field_matches(
-    record_index: usize,
+    root_path: &str,
     bytes: &[u8],
     needle: &str,
     match_case: bool,
@@ -371,8 +678,7 @@ fn collect_field_matches(
         Ok(v) => v,
         Err(_) => return,
     };
-    let root_path = record_index.to_string();
-    collect_value_matches(&value, &root_path, needle, match_case, fragments);
+    collect_value_matches(&value, root_path, needle, match_case, fragments);
 }
 
 fn collect_value_matches(
@@ -382,82 +688,38 @@ fn collect_value_matches(
     match_case: bool,
     fragments: &mut Vec<MatchFragment>,
 ) {
-    if fragments.len() >= MAX_FRAGMENTS_PER_RECORD {
-        return;
-    }
+    visit_fields(value, path, &mut |path, component, text| {
+        append_matches(path, component, text, needle, match_case, fragments);
+        fragments.len() < MAX_FRAGMENTS_PER_RECORD
+    });
+}
 
+/// Call `visit` with the path, component and text of every key and scalar
+/// value under `value`, depth-first
+///
+/// Stops as soon as `visit` returns false, and then returns false itself.
+fn visit_fields(
+    value: &Value,
+    path: &str,
+    visit: &mut impl FnMut(&str, FieldComponent, &str) -> bool,
+) -> bool {
     match value {
-        Value::Object(map) => {
-            for (key, val) in map {
-                let key_path = format!("{}.{}", path, key);
-                append_matches(
-                    &key_path,
-                    FieldComponent::Key,
-                    key,
-                    needle,
-                    match_case,
-                    fragments,
-                );
-                if fragments.len() >= MAX_FRAGMENTS_PER_RECORD {
-                    return;
-                }
-                collect_value_matches(val, &key_path, needle, match_case, fragments);
-                if fragments.len() >= MAX_FRAGMENTS_PER_RECORD {
-                    return;
-                }
-            }
-        }
-        Value::Array(items) => {
-            for (idx, val) in items.iter().enumerate() {
-                let item_path = format!("{}[{}]", path, idx);
-                collect_value_matches(val, &item_path, needle, match_case, fragments);
-                if fragments.len() >= MAX_FRAGMENTS_PER_RECORD {
-                    return;
-                }
-            }
-        }
-        Value::String(text) => {
-            append_matches(
-                path,
-                FieldComponent::Value,
-                text,
-                needle,
-                match_case,
-                fragments,
-            );
-        }
-        Value::Number(num) => {
-            let text = num.to_string();
-            append_matches(
-                path,
-                FieldComponent::Value,
-                &text,
-                needle,
-                match_case,
-                fragments,
-            );
-        }
-        Value::Bool(flag) => {
-            let text = if *flag { "true" } else { "false" };
-            append_matches(
-                path,
-                FieldComponent::Value,
-                text,
-                needle,
-                match_case,
-                fragments,
-            );
-        }
-        Value::Null => {
-            append_matches(
-                path,
-                FieldComponent::Value,
-                "null",
-                needle,
-                match_case,
-                fragments,
-            );
-        }
+        Value::Object(map) => map.iter().all(|(key, val)| {
+            let key_path = format!("{}.{}", path, key);
+            visit(&key_path, FieldComponent::Key, key) && visit_fields(val, &key_path, visit)
+        }),
+        Value::Array(items) => items.iter().enumerate().all(|(idx, val)| {
+            let item_path = format!("{}[{}]", path, idx);
+            visit_fields(val, &item_path, visit)
+        }),
+        Value::String(text) => visit(path, FieldComponent::Value, text),
+        Value::Number(num) => visit(path, FieldComponent::Value, &num.to_string()),
+        Value::Bool(flag) => visit(
+            path,
+            FieldComponent::Value,
+            if *flag { "true" } else { "false" },
+        ),
+        Value::Null => visit(path, FieldComponent::Value, "null"),
     }
 }
 
@@ -509,9 +771,6 @@ fn field_fragment(
 }
 
 fn ensure_root_highlight(fragments: &mut Vec<MatchFragment>, record_index: usize) {
-    if fragments.is_empty() {
-        return;
-    }
     let root_path = record_index.to_string();
     let has_root = fragments
         .iter()
@@ -526,7 +785,7 @@ fn ensure_root_highlight(fragments: &mut Vec<MatchFragment>, record_index: usize
         },
         byte_range: 0..0,
         path: Some(Arc::<str>::from(root_path)),
-        confidence: 0.6,
+        confidence: RECORD_MARKER_CONFIDENCE,
         matched_text: None,
         text_range: None,
     });
@@ -574,3 +833,170 @@ fn find_match_ranges(haystack: &str, needle: &str, match_case: bool) -> Vec<Rang
         ranges
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn search_file(lines: usize) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        for i in 0..lines {
+            let tag = if i % 1000 == 0 { "needle" } else { "hay" };
+            writeln!(file, "{{\"id\":{},\"tag\":\"{}\"}}", i, tag).unwrap();
+        }
+        file.flush().unwrap();
+        file
+    }
+
+    fn collect(rx: mpsc::Receiver<SearchUpdate>) -> (Vec<Vec<usize>>, ScanSummary) {
+        let mut batches = Vec::new();
+        for update in rx {
+            match update {
+                SearchUpdate::Hits(hits) => {
+                    batches.push(hits.iter().map(|hit| hit.record_index).collect())
+                }
+                SearchUpdate::Finished(summary) => return (batches, summary.unwrap()),
+            }
+        }
+        panic!("scan ended without finishing");
+    }
+
+    #[test]
+    fn test_streams_hits_in_record_order() {
+        let lines = STREAM_CHUNK_RECORDS * 2 + 10;
+        let file = search_file(lines);
+        let search = Search {
+            query: "needle".to_string(),
+            ..Search::default()
+        };
+
+        let rx = search.start_scanning(
+            &Some(file.path().to_path_buf()),
+            &FileKind::Ndjson,
+            Arc::new(AtomicBool::new(false)),
+        );
+        let (batches, summary) = collect(rx);
+        assert_eq!(summary.total_records, lines);
+        assert!(!summary.truncated);
+        assert!(batches.len() > 1);
+        let indices: Vec<usize> = batches.concat();
+        let expected: Vec<usize> = (0..lines).step_by(1000).collect();
+        assert_eq!(indices, expected);
+    }
+
+    #[test]
+    fn test_scan_stops_at_result_limit() {
+        let lines = STREAM_CHUNK_RECORDS * 2 + 10;
+        let file = search_file(lines);
+        let search = Search {
+            query: "needle".to_string(),
+            max_results: Some(5),
+            ..Search::default()
+        };
+
+        let rx = search.start_scanning(
+            &Some(file.path().to_path_buf()),
+            &FileKind::Ndjson,
+            Arc::new(AtomicBool::new(false)),
+        );
+        let (batches, summary) = collect(rx);
+        assert!(summary.truncated);
+        assert_eq!(batches.concat(), vec![0, 1000, 2000, 3000, 4000]);
+
+        // Exactly as many matches as the limit is not truncated
+        let mut search = Search {
+            query: "needle".to_string(),
+            max_results: Some(lines.div_ceil(1000)),
+            ..Search::default()
+        };
+        search.start_scanning_internal(&Some(file.path().to_path_buf()), &FileKind::Ndjson);
+        assert_eq!(search.results.len(), lines.div_ceil(1000));
+        assert!(!search.results.is_truncated());
+    }
+
+    #[test]
+    fn test_cancelled_scan_stops_early() {
+        let file = search_file(STREAM_CHUNK_RECORDS * 2);
+        let search = Search {
+            query: "needle".to_string(),
+            ..Search::default()
+        };
+
+        let rx = search.start_scanning(
+            &Some(file.path().to_path_buf()),
+            &FileKind::Ndjson,
+            Arc::new(AtomicBool::new(true)),
+        );
+        let (batches, _) = collect(rx);
+        assert!(batches.is_empty());
+    }
+
+    #[test]
+    fn test_filter_mode_matches_numeric_range() {
+        let file = search_file(50);
+        let mut search = Search {
+            query: "id >= 10 AND id < 13".to_string(),
+            query_mode: QueryMode::Filter,
+            ..Search::default()
+        };
+        search.start_scanning_internal(&Some(file.path().to_path_buf()), &FileKind::Ndjson);
+
+        assert!(search.error.is_none());
+        let indices: Vec<usize> = search
+            .results
+            .hits()
+            .iter()
+            .map(|hit| hit.record_index)
+            .collect();
+        assert_eq!(indices, vec![10, 11, 12]);
+        // Both predicates name `id`, which is highlighted once
+        let paths: Vec<Option<&str>> = search.results.hits()[0]
+            .fragments
+            .iter()
+            .map(|fragment| fragment.path.as_deref())
+            .collect();
+        assert_eq!(paths, vec![Some("10.id"), Some("10")]);
+    }
+
+    #[test]
+    fn test_filter_mode_invalid_syntax_is_search_error() {
+        let file = search_file(5);
+        let mut search = Search {
+            query: "id >".to_string(),
+            query_mode: QueryMode::Filter,
+            ..Search::default()
+        };
+        search.start_scanning_internal(&Some(file.path().to_path_buf()), &FileKind::Ndjson);
+
+        assert!(matches!(search.error, Some(ThothError::SearchError { .. })));
+        assert!(search.results.hits().is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_mode_ranks_closest_keys_first() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, r#"{{"unsorted":1}}"#).unwrap();
+        writeln!(file, r#"{{"id":2}}"#).unwrap();
+        writeln!(file, r#"{{"user_name":"ada"}}"#).unwrap();
+        file.flush().unwrap();
+        let mut search = Search {
+            query: "usr".to_string(),
+            query_mode: QueryMode::Fuzzy,
+            ..Search::default()
+        };
+        search.start_scanning_internal(&Some(file.path().to_path_buf()), &FileKind::Ndjson);
+
+        assert!(search.error.is_none());
+        let indices: Vec<usize> = search
+            .results
+            .hits()
+            .iter()
+            .map(|hit| hit.record_index)
+            .collect();
+        assert_eq!(indices, vec![2, 0]);
+        let best = &search.results.hits()[0];
+        assert_eq!(best.fragments[0].path.as_deref(), Some("2.user_name"));
+        assert_eq!(best.fragments[0].text_range, Some(0..2));
+    }
+}
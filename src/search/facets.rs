@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use serde_json::Value;
+
+use crate::constants::{
+    FACET_FRAME_BUDGET, FACET_MAX_DISTINCT_VALUES, FACET_MAX_KEY_CHIPS, FACET_MAX_VALUE_CHIPS,
+    FACET_SAMPLE_RECORDS,
+};
+use crate::file::loaders::FileType;
+
+/// Longest string value offered as a chip; longer ones are free text
+const MAX_FACET_VALUE_CHARS: usize = 40;
+
+/// One chip of the facets panel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Facet {
+    /// Chip text, e.g. `status` or `status: error`
+    pub label: String,
+    /// JSONPath query selecting the records the chip stands for
+    pub query: String,
+    /// Sampled records it occurs in
+    pub count: usize,
+}
+
+/// Common top-level keys and values of a file's first records
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Facets {
+    /// Keys, most common first
+    pub keys: Vec<Facet>,
+    /// Values of fields with few distinct values, grouped by field
+    pub values: Vec<Facet>,
+    /// Records the facets were collected from
+    pub sampled: usize,
+}
+
+/// Values seen in one field, by their JSON text; `None` once the field has
+/// too many distinct values (or free text) to be worth listing
+type FieldValues = Option<HashMap<String, usize>>;
+
+/// Collects [`Facets`] from the first [`FACET_SAMPLE_RECORDS`] records of a
+/// file, a few each frame
+#[derive(Debug, Default)]
+pub struct FacetSampler {
+    next_record: usize,
+    total: usize,
+    keys: HashMap<String, usize>,
+    values: HashMap<String, FieldValues>,
+    facets: Option<Facets>,
+}
+
+impl FacetSampler {
+    /// Sample a file of `total_records` records from its first one
+    pub fn new(total_records: usize) -> Self {
+        Self {
+            total: total_records.min(FACET_SAMPLE_RECORDS),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.facets.is_some()
+    }
+
+    /// The facets, once sampling has finished
+    pub fn facets(&self) -> Option<&Facets> {
+        self.facets.as_ref()
+    }
+
+    /// Sample records until [`FACET_FRAME_BUDGET`] runs out
+    ///
+    /// Records that can't be parsed are skipped.
+    pub fn step(&mut self, loader: &mut FileType) {
+        let started = Instant::now();
+        while self.next_record < self.total && started.elapsed() < FACET_FRAME_BUDGET {
+            if let Ok(record) = loader.get(self.next_record) {
+                self.add(&record);
+            }
+            self.next_record += 1;
+        }
+        if self.next_record >= self.total {
+            self.facets = Some(self.build());
+        }
+    }
+
+    /// Count the top-level keys and scalar values of one record
+    fn add(&mut self, record: &Value) {
+        let Value::Object(map) = record else {
+            return;
+        };
+        for (key, value) in map {
+            *self.keys.entry(key.clone()).or_default() += 1;
+
+            let field = self
+                .values
+                .entry(key.clone())
+                .or_insert_with(|| Some(HashMap::new()));
+            let Some(seen) = field else {
+                continue;
+            };
+            let listable = match value {
+                Value::String(text) => text.chars().count() <= MAX_FACET_VALUE_CHARS,
+                Value::Number(_) | Value::Bool(_) | Value::Null => true,
+                Value::Array(_) | Value::Object(_) => false,
+            };
+            if !listable {
+                *field = None;
+                continue;
+            }
+            *seen.entry(value.to_string()).or_default() += 1;
+            if seen.len() > FACET_MAX_DISTINCT_VALUES {
+                *field = None;
+            }
+        }
+    }
+
+    fn build(&self) -> Facets {
+        let mut keys: Vec<(&String, usize)> =
+            self.keys.iter().map(|(key, count)| (key, *count)).collect();
+        keys.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+        let mut values = Vec::new();
+        for (key, _) in &keys {
+            let (Some(Some(seen)), Some(path)) = (self.values.get(*key), key_path(key)) else {
+                continue;
+            };
+            // A value seen only once says nothing about the field
+            let mut common: Vec<(&String, usize)> = seen
+                .iter()
+                .filter(|&(_, count)| *count > 1)
+                .map(|(literal, count)| (literal, *count))
+                .collect();
+            common.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+            for (literal, count) in common {
+                values.push(Facet {
+                    label: format!("{}: {}", key, value_label(literal)),
+                    query: format!("{} = {}", path, literal),
+                    count,
+                });
+            }
+        }
+        values.truncate(FACET_MAX_VALUE_CHIPS);
+
+        Facets {
+            keys: keys
+                .into_iter()
+                .filter_map(|(key, count)| {
+                    Some(Facet {
+                        label: key.clone(),
+                        query: key_path(key)?,
+                        count,
+                    })
+                })
+                .take(FACET_MAX_KEY_CHIPS)
+                .collect(),
+            values,
+            sampled: self.next_record,
+        }
+    }
+}
+
+/// JSONPath selecting the top-level `key`, or `None` for a key no query can name
+fn key_path(key: &str) -> Option<String> {
+    let mut chars = key.chars();
+    let identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if identifier {
+        Some(format!("$.{}", key))
+    } else if key.is_empty() || key.contains(['\'', '\\', ']']) {
+        None
+    } else {
+        Some(format!("$['{}']", key))
+    }
+}
+
+/// A value's JSON text as shown on its chip: strings without their quotes
+fn value_label(literal: &str) -> String {
+    match serde_json::from_str::<Value>(literal) {
+        Ok(Value::String(text)) => text,
+        _ => literal.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::jsonpath::JsonPathQuery;
+    use serde_json::json;
+
+    fn sample(records: &[Value]) -> Facets {
+        let mut sampler = FacetSampler::new(records.len());
+        for record in records {
+            sampler.add(record);
+            sampler.next_record += 1;
+        }
+        sampler.build()
+    }
+
+    fn labels(facets: &[Facet]) -> Vec<&str> {
+        facets.iter().map(|facet| facet.label.as_str()).collect()
+    }
+
+    #[test]
+    fn test_keys_most_common_first() {
+        let facets = sample(&[
+            json!({"id": 1, "status": "ok", "note": "x"}),
+            json!({"id": 2, "status": "error"}),
+            json!({"id": 3}),
+            json!([1, 2]),
+        ]);
+        assert_eq!(labels(&facets.keys), ["id", "status", "note"]);
+        assert_eq!(facets.keys[0].count, 3);
+        assert_eq!(facets.keys[0].query, "$.id");
+        assert_eq!(facets.sampled, 4);
+    }
+
+    #[test]
+    fn test_values_of_low_cardinality_fields() {
+        let records: Vec<Value> = (0..20)
+            .map(|i| {
+                json!({
+                    "id": i,
+                    "status": if i % 4 == 0 { "error" } else { "ok" },
+                    "active": i % 2 == 0,
+                    "tags": ["a"],
+                    "once": if i == 0 { json!("only") } else { json!(null) },
+                })
+            })
+            .collect();
+        let facets = sample(&records);
+
+        // `id` has a distinct value per record and `tags` isn't a scalar
+        assert_eq!(
+            labels(&facets.values),
+            [
+                "active: false",
+                "active: true",
+                "once: null",
+                "status: ok",
+                "status: error",
+            ]
+        );
+        let error = &facets.values[4];
+        assert_eq!(error.query, "$.status = \"error\"");
+        assert_eq!(error.count, 5);
+    }
+
+    #[test]
+    fn test_queries_select_the_facet_records() {
+        let records = [
+            json!({"status": "error", "http code": 500}),
+            json!({"status": "error", "http code": 500}),
+            json!({"status": "ok", "http code": 200}),
+        ];
+        let facets = sample(&records);
+        for facet in facets.keys.iter().chain(&facets.values) {
+            let query = JsonPathQuery::parse(&facet.query).unwrap();
+            let matching = records
+                .iter()
+                .filter(|record| !query.evaluate(record, "0", true).is_empty())
+                .count();
+            assert_eq!(matching, facet.count, "{}", facet.query);
+        }
+        assert!(
+            facets
+                .values
+                .iter()
+                .any(|facet| facet.query == "$['http code'] = 500")
+        );
+    }
+
+    #[test]
+    fn test_sampling_is_bounded() {
+        assert_eq!(FacetSampler::new(10).total, 10);
+        assert_eq!(
+            FacetSampler::new(FACET_SAMPLE_RECORDS * 50).total,
+            FACET_SAMPLE_RECORDS
+        );
+    }
+}
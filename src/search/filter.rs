@@ -0,0 +1,559 @@
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use crate::helpers::{NumberFormat, preview_value};
+
+/// A parsed comparison filter, e.g. `age >= 18 AND age < 65`.
+///
+/// Supported syntax:
+/// - predicates `field op value`, where `field` is a path into the record
+///   (`price`, `user.age`, `items[0].price`, optionally prefixed with `$.`)
+/// - operators `=` / `==`, `!=`, `<`, `<=`, `>`, `>=`
+/// - values: numbers, quoted strings, `true`, `false`, `null`, or a bare word
+///   taken as a string (`status = active`)
+/// - `AND` / `OR` (any case, or `&&` / `||`), `NOT` / `!` and parentheses;
+///   `AND` binds tighter than `OR`
+///
+/// Numbers compare numerically, and a numeric string is read as a number
+/// when compared with one; strings compare lexically. A predicate on a
+/// missing field never matches, not even `!=`. Without `match_case`, field
+/// names and string values compare ignoring ASCII case.
+#[derive(Debug, Clone)]
+pub struct FilterQuery {
+    original: String,
+    expr: FilterExpr,
+}
+
+#[derive(Debug, Clone)]
+enum FilterExpr {
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Not(Box<FilterExpr>),
+    Compare(Vec<FieldStep>, CompareOp, Value),
+}
+
+#[derive(Debug, Clone)]
+enum FieldStep {
+    Field(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(CompareOp),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+/// A field whose predicate held in a matching record
+#[derive(Debug, Clone)]
+pub struct FilterMatch {
+    pub path: String,
+    pub display_value: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum FilterError {
+    Empty,
+    Syntax(String),
+}
+
+impl std::fmt::Display for FilterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterError::Empty => write!(f, "Filter query cannot be empty"),
+            FilterError::Syntax(reason) => write!(f, "Invalid filter: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+impl FilterQuery {
+    pub fn parse(input: &str) -> Result<Self, FilterError> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err(FilterError::Empty);
+        }
+        let mut parser = FilterParser { tokens, pos: 0 };
+        let expr = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            return Err(FilterError::Syntax(format!(
+                "Unexpected {}",
+                describe(token)
+            )));
+        }
+        Ok(Self {
+            original: input.trim().to_string(),
+            expr,
+        })
+    }
+
+    pub fn original(&self) -> &str {
+        &self.original
+    }
+
+    /// Evaluate against one record. Returns `None` when it doesn't match,
+    /// otherwise each field whose predicates held, once (empty if the match
+    /// came only from `NOT`)
+    pub fn evaluate(
+        &self,
+        root: &Value,
+        root_path: &str,
+        match_case: bool,
+    ) -> Option<Vec<FilterMatch>> {
+        let mut matches = Vec::new();
+        if !self.expr.eval(root, root_path, match_case, &mut matches) {
+            return None;
+        }
+        let mut seen = HashSet::new();
+        matches.retain(|m| seen.insert(m.path.clone()));
+        Some(matches)
+    }
+}
+
+impl FilterExpr {
+    fn eval(
+        &self,
+        root: &Value,
+        root_path: &str,
+        match_case: bool,
+        out: &mut Vec<FilterMatch>,
+    ) -> bool {
+        match self {
+            FilterExpr::Or(lhs, rhs) => {
+                // Evaluate both sides so every satisfied branch is highlighted
+                let left = lhs.eval(root, root_path, match_case, out);
+                let right = rhs.eval(root, root_path, match_case, out);
+                left || right
+            }
+            FilterExpr::And(lhs, rhs) => {
+                let mut both = Vec::new();
+                let held = lhs.eval(root, root_path, match_case, &mut both)
+                    && rhs.eval(root, root_path, match_case, &mut both);
+                if held {
+                    out.extend(both);
+                }
+                held
+            }
+            // Fields under a negation didn't match anything worth showing
+            FilterExpr::Not(inner) => !inner.eval(root, root_path, match_case, &mut Vec::new()),
+            FilterExpr::Compare(steps, op, literal) => {
+                let Some((path, value)) = resolve(root, root_path, steps, match_case) else {
+                    return false;
+                };
+                if !compare(value, *op, literal, match_case) {
+                    return false;
+                }
+                out.push(FilterMatch {
+                    path,
+                    display_value: preview_value(value, NumberFormat::Plain),
+                });
+                true
+            }
+        }
+    }
+}
+
+/// Walk `steps` from the record root, returning the field's row path and value
+fn resolve<'v>(
+    root: &'v Value,
+    root_path: &str,
+    steps: &[FieldStep],
+    match_case: bool,
+) -> Option<(String, &'v Value)> {
+    let mut path = root_path.to_string();
+    let mut value = root;
+    for step in steps {
+        value = match (step, value) {
+            (FieldStep::Field(name), Value::Object(map)) => {
+                // An exact key wins, else the first case-insensitive one
+                let (key, child) = map.get_key_value(name).or_else(|| {
+                    if match_case {
+                        return None;
+                    }
+                    map.iter().find(|(key, _)| key.eq_ignore_ascii_case(name))
+                })?;
+                path.push('.');
+                path.push_str(key);
+                child
+            }
+            (FieldStep::Index(idx), Value::Array(items)) => {
+                path.push_str(&format!("[{}]", idx));
+                items.get(*idx)?
+            }
+            _ => return None,
+        };
+    }
+    Some((path, value))
+}
+
+fn compare(value: &Value, op: CompareOp, literal: &Value, match_case: bool) -> bool {
+    match ordering(value, literal, match_case) {
+        Some(ord) => match op {
+            CompareOp::Eq => ord == Ordering::Equal,
+            CompareOp::Ne => ord != Ordering::Equal,
+            CompareOp::Lt => ord == Ordering::Less,
+            CompareOp::Le => ord != Ordering::Greater,
+            CompareOp::Gt => ord == Ordering::Greater,
+            CompareOp::Ge => ord != Ordering::Less,
+        },
+        // Values of unrelated types are simply unequal
+        None => op == CompareOp::Ne,
+    }
+}
+
+fn ordering(value: &Value, literal: &Value, match_case: bool) -> Option<Ordering> {
+    match (value, literal) {
+        (Value::String(a), Value::String(b)) => Some(if match_case {
+            a.cmp(b)
+        } else {
+            a.to_ascii_lowercase().cmp(&b.to_ascii_lowercase())
+        }),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (Value::Null, Value::Null) => Some(Ordering::Equal),
+        _ => as_number(value)?.partial_cmp(&as_number(literal)?),
+    }
+}
+
+/// A number, or a string holding one
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(num) => num.as_f64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Word(word) => format!("'{word}'"),
+        Token::Quoted(text) => format!("\"{text}\""),
+        Token::Op(_) => "operator".to_string(),
+        Token::And => "AND".to_string(),
+        Token::Or => "OR".to_string(),
+        Token::Not => "NOT".to_string(),
+        Token::Open => "'('".to_string(),
+        Token::Close => "')'".to_string(),
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, FilterError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while let Some(&ch) = chars.get(pos) {
+        let next = chars.get(pos + 1).copied();
+        let (token, len) = match (ch, next) {
+            (c, _) if c.is_whitespace() => {
+                pos += 1;
+                continue;
+            }
+            ('(', _) => (Token::Open, 1),
+            (')', _) => (Token::Close, 1),
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            ('!', Some('=')) => (Token::Op(CompareOp::Ne), 2),
+            ('!', _) => (Token::Not, 1),
+            ('=', Some('=')) => (Token::Op(CompareOp::Eq), 2),
+            ('=', _) => (Token::Op(CompareOp::Eq), 1),
+            ('<', Some('=')) => (Token::Op(CompareOp::Le), 2),
+            ('<', _) => (Token::Op(CompareOp::Lt), 1),
+            ('>', Some('=')) => (Token::Op(CompareOp::Ge), 2),
+            ('>', _) => (Token::Op(CompareOp::Gt), 1),
+            (quote @ ('"' | '\''), _) => {
+                let mut text = String::new();
+                let mut end = pos + 1;
+                loop {
+                    match chars.get(end) {
+                        None => return Err(FilterError::Syntax("Unterminated string".into())),
+                        Some(&c) if c == quote => break,
+                        Some('\\') => {
+                            if let Some(&escaped) = chars.get(end + 1) {
+                                text.push(escaped);
+                            }
+                            end += 2;
+                            continue;
+                        }
+                        Some(&c) => text.push(c),
+                    }
+                    end += 1;
+                }
+                (Token::Quoted(text), end + 1 - pos)
+            }
+            ('&' | '|', _) => {
+                return Err(FilterError::Syntax(format!(
+                    "Unexpected '{ch}' (use AND/OR or &&/||)"
+                )));
+            }
+            _ => {
+                let len = chars[pos..]
+                    .iter()
+                    .take_while(|c| !c.is_whitespace() && !"()&|!=<>\"'".contains(**c))
+                    .count();
+                let word: String = chars[pos..pos + len].iter().collect();
+                let token = match word.to_ascii_uppercase().as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => Token::Word(word),
+                };
+                (token, len)
+            }
+        };
+        tokens.push(token);
+        pos += len;
+    }
+    Ok(tokens)
+}
+
+/// Parse a field path such as `$.user.items[0].price` into steps
+fn parse_field(word: &str) -> Result<Vec<FieldStep>, FilterError> {
+    let path = word
+        .strip_prefix("$.")
+        .or_else(|| word.strip_prefix('$'))
+        .unwrap_or(word);
+    let mut steps = Vec::new();
+    for part in path.split('.') {
+        let (name, mut rest) = part.split_at(part.find('[').unwrap_or(part.len()));
+        if !name.is_empty() {
+            steps.push(FieldStep::Field(name.to_string()));
+        } else if rest.is_empty() {
+            return Err(FilterError::Syntax(format!("Invalid field path '{word}'")));
+        }
+        while let Some(inner) = rest.strip_prefix('[') {
+            let (idx, tail) = inner
+                .split_once(']')
+                .ok_or_else(|| FilterError::Syntax(format!("Missing ']' in '{word}'")))?;
+            let idx = idx
+                .trim()
+                .parse()
+                .map_err(|_| FilterError::Syntax(format!("Invalid array index in '{word}'")))?;
+            steps.push(FieldStep::Index(idx));
+            rest = tail;
+        }
+        if !rest.is_empty() {
+            return Err(FilterError::Syntax(format!("Invalid field path '{word}'")));
+        }
+    }
+    Ok(steps)
+}
+
+/// The literal on the right of a comparison
+fn parse_literal(token: Token) -> Result<Value, FilterError> {
+    match token {
+        Token::Quoted(text) => Ok(Value::String(text)),
+        Token::Word(word) => Ok(match word.as_str() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            "null" => Value::Null,
+            _ => serde_json::from_str::<Value>(&word)
+                .ok()
+                .filter(Value::is_number)
+                .unwrap_or(Value::String(word)),
+        }),
+        other => Err(FilterError::Syntax(format!(
+            "Expected a value, found {}",
+            describe(&other)
+        ))),
+    }
+}
+
+/// Recursive-descent parser over the filter's tokens.
+struct FilterParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl FilterParser {
+    fn parse_or(&mut self) -> Result<FilterExpr, FilterError> {
+        let mut expr = self.parse_and()?;
+        while self.eat(&Token::Or) {
+            let rhs = self.parse_and()?;
+            expr = FilterExpr::Or(Box::new(expr), Box::new(rhs));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, FilterError> {
+        let mut expr = self.parse_unary()?;
+        while self.eat(&Token::And) {
+            let rhs = self.parse_unary()?;
+            expr = FilterExpr::And(Box::new(expr), Box::new(rhs));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpr, FilterError> {
+        if self.eat(&Token::Not) {
+            return Ok(FilterExpr::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat(&Token::Open) {
+            let expr = self.parse_or()?;
+            if !self.eat(&Token::Close) {
+                return Err(FilterError::Syntax("Missing ')'".into()));
+            }
+            return Ok(expr);
+        }
+
+        let field = match self.next() {
+            Some(Token::Word(word)) => parse_field(&word)?,
+            Some(other) => {
+                return Err(FilterError::Syntax(format!(
+                    "Expected a field, found {}",
+                    describe(&other)
+                )));
+            }
+            None => return Err(FilterError::Syntax("Expected a field".into())),
+        };
+        let op = match self.next() {
+            Some(Token::Op(op)) => op,
+            _ => {
+                return Err(FilterError::Syntax(
+                    "Expected a comparison (=, !=, <, <=, >, >=) after the field".into(),
+                ));
+            }
+        };
+        let literal = match self.next() {
+            Some(token) => parse_literal(token)?,
+            None => return Err(FilterError::Syntax("Expected a value".into())),
+        };
+        Ok(FilterExpr::Compare(field, op, literal))
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    /// Consume `token` if it comes next.
+    fn eat(&mut self, token: &Token) -> bool {
+        let matches = self.peek() == Some(token);
+        if matches {
+            self.pos += 1;
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn matches(query: &str, value: &Value) -> bool {
+        FilterQuery::parse(query)
+            .unwrap()
+            .evaluate(value, "0", false)
+            .is_some()
+    }
+
+    #[test]
+    fn compares_numbers_numerically() {
+        let record = json!({"price": 120, "qty": "9"});
+        assert!(matches("price > 100", &record));
+        assert!(!matches("price < 100", &record));
+        assert!(matches("price >= 120", &record));
+        assert!(matches("price = 120.0", &record));
+        // A numeric string compares as a number against a number literal
+        assert!(matches("qty < 10", &record));
+    }
+
+    #[test]
+    fn compares_strings_lexically() {
+        let record = json!({"name": "Bob", "code": "10"});
+        assert!(matches("name > alice", &record));
+        assert!(matches("name = 'bob'", &record));
+        // Two strings stay lexical even when they look numeric
+        assert!(matches("code < \"9\"", &record));
+        let exact = FilterQuery::parse("name = bob").unwrap();
+        assert!(exact.evaluate(&record, "0", true).is_none());
+    }
+
+    #[test]
+    fn combines_with_and_or_not() {
+        let adult = json!({"age": 30, "status": "active"});
+        let child = json!({"age": 12, "status": "active"});
+        let range = "age >= 18 AND age < 65";
+        assert!(matches(range, &adult));
+        assert!(!matches(range, &child));
+        assert!(matches("age < 18 or status = inactive", &child));
+        assert!(matches("NOT (age < 18) && status = active", &adult));
+        assert!(!matches("!(status = active)", &adult));
+        // AND binds tighter than OR
+        assert!(matches("age > 100 AND age < 0 OR status = active", &child));
+    }
+
+    #[test]
+    fn missing_fields_never_match() {
+        let record = json!({"age": 30});
+        assert!(!matches("price > 1", &record));
+        assert!(!matches("price != 1", &record));
+        assert!(matches("age != 'thirty'", &record));
+    }
+
+    #[test]
+    fn resolves_nested_paths_and_reports_fields() {
+        let record = json!({"User": {"items": [{"price": 5}, {"price": 50}]}});
+        let query = FilterQuery::parse("$.user.items[1].price >= 50").unwrap();
+        let found = query.evaluate(&record, "3", false).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, "3.User.items[1].price");
+        assert_eq!(found[0].display_value, "50");
+
+        // A field named by several predicates is reported once
+        let range = FilterQuery::parse("User.items[0].price > 1 AND user.items[0].price < 9")
+            .unwrap()
+            .evaluate(&record, "3", false)
+            .unwrap();
+        assert_eq!(range.len(), 1);
+        assert_eq!(range[0].display_value, "5");
+    }
+
+    #[test]
+    fn literals_and_booleans() {
+        let record = json!({"ok": true, "note": null});
+        assert!(matches("ok = true", &record));
+        assert!(matches("note = null", &record));
+        assert!(!matches("ok > 1", &record));
+    }
+
+    #[test]
+    fn rejects_invalid_syntax() {
+        for query in [
+            "",
+            "price >",
+            "price 100",
+            "> 100",
+            "(price > 1",
+            "price > 1 AND",
+            "price > 1 extra",
+            "items[x] = 1",
+            "name = 'open",
+            "a & b",
+        ] {
+            assert!(FilterQuery::parse(query).is_err(), "{query}");
+        }
+    }
+}
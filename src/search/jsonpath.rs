@@ -1,9 +1,23 @@
 use serde_json::Value;
 use std::ops::Range;
 
-use crate::helpers::preview_value;
+use crate::helpers::{NumberFormat, preview_value};
 use crate::search::results::FieldComponent;
 
+/// A parsed JSONPath query.
+///
+/// Supported syntax:
+/// - `$` root, dot (`.name`) and bracket (`['name']`) member access
+/// - wildcards (`.*`, `[*]`) over object members and array elements
+/// - recursive descent (`..name`, `..*`, `..[0]`)
+/// - array indexes, including negative ones (`[0]`, `[-1]`)
+/// - array slices with Python semantics (`[1:3]`, `[:2]`, `[-2:]`, `[::-1]`)
+/// - filter expressions (`[?(@.age > 30)]`) with `==`, `!=`, `<`, `<=`, `>`, `>=`,
+///   existence tests (`[?(@.isbn)]`), `!`, `&&`, `||`, parentheses and `$` references
+/// - an optional trailing equality filter on the selected values (`$.user.name = "alice"`)
+///
+/// Without `match_case`, member names match ignoring ASCII case (`$.userId` also
+/// selects `userid` and `UserID`); result paths keep the document's key casing.
 #[derive(Debug, Clone)]
 pub struct JsonPathQuery {
     original: String,
@@ -14,9 +28,61 @@ pub struct JsonPathQuery {
 #[derive(Debug, Clone)]
 enum PathSegment {
     Field(String),
-    FieldWildcard,
-    ArrayIndex(usize),
-    ArrayWildcard,
+    /// Every member of an object or element of an array.
+    Wildcard,
+    /// Array index; negative values count from the end.
+    Index(i64),
+    Slice(SliceRange),
+    /// The current node plus all of its descendants (`..`).
+    Descendants,
+    Filter(FilterExpr),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SliceRange {
+    start: Option<i64>,
+    end: Option<i64>,
+    step: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+enum FilterExpr {
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Not(Box<FilterExpr>),
+    Exists(FilterOperand),
+    Compare(FilterOperand, CompareOp, FilterOperand),
+}
+
+#[derive(Debug, Clone)]
+enum FilterOperand {
+    /// Path relative to the node under test (`@.a.b`).
+    Current(Vec<SingularStep>),
+    /// Path relative to the record root (`$.a.b`).
+    Root(Vec<SingularStep>),
+    Literal(Value),
+}
+
+#[derive(Debug, Clone)]
+enum SingularStep {
+    Field(String),
+    Index(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Per-record state needed while applying segments.
+struct EvalContext<'a> {
+    root: &'a Value,
+    match_case: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -101,11 +167,12 @@ impl JsonPathQuery {
     }
 
     pub fn evaluate(&self, root: &Value, root_path: &str, match_case: bool) -> Vec<JsonPathMatch> {
+        let ctx = EvalContext { root, match_case };
         let mut current: Vec<(String, &Value)> = vec![(root_path.to_string(), root)];
         for segment in &self.segments {
             let mut next: Vec<(String, &Value)> = Vec::new();
             for (path, value) in &current {
-                segment.apply(path, value, &mut next);
+                segment.apply(path, value, &ctx, &mut next);
             }
             if next.is_empty() {
                 return Vec::new();
@@ -185,7 +252,7 @@ impl JsonPathMatch {
                 component: FieldComponent::EntireRow,
                 matched_text: None,
                 highlight_range: None,
-                display_value: preview_value(other),
+                display_value: preview_value(other, NumberFormat::Plain),
             }),
         }
     }
@@ -201,12 +268,25 @@ fn parse_segments(expr: &str) -> Result<Vec<PathSegment>, JsonPathError> {
         match ch {
             '.' => {
                 chars.next();
-                if chars.peek().is_none() {
+                if let Some('.') = chars.peek() {
+                    chars.next();
+                    segments.push(PathSegment::Descendants);
+                    match chars.peek() {
+                        // `..[...]` is handled by the bracket arm on the next iteration
+                        Some('[') => continue,
+                        None => {
+                            return Err(JsonPathError::InvalidToken(
+                                "Recursive descent needs a selector".into(),
+                            ));
+                        }
+                        _ => {}
+                    }
+                } else if chars.peek().is_none() {
                     break;
                 }
                 if let Some('*') = chars.peek() {
                     chars.next();
-                    segments.push(PathSegment::FieldWildcard);
+                    segments.push(PathSegment::Wildcard);
                 } else {
                     let name = parse_field_name(&mut chars)?;
                     segments.push(PathSegment::Field(name));
@@ -224,13 +304,15 @@ fn parse_segments(expr: &str) -> Result<Vec<PathSegment>, JsonPathError> {
                         chars.next();
                         consume_whitespace(&mut chars);
                         expect_char(&mut chars, ']')?;
-                        segments.push(PathSegment::ArrayWildcard);
+                        segments.push(PathSegment::Wildcard);
                     }
-                    '0'..='9' => {
-                        let index = parse_number(&mut chars)?;
-                        consume_whitespace(&mut chars);
-                        expect_char(&mut chars, ']')?;
-                        segments.push(PathSegment::ArrayIndex(index));
+                    '?' => {
+                        chars.next();
+                        let body = collect_filter_body(&mut chars)?;
+                        segments.push(PathSegment::Filter(FilterExpr::parse(&body)?));
+                    }
+                    '0'..='9' | '-' | ':' => {
+                        segments.push(parse_index_or_slice(&mut chars)?);
                     }
                     '\'' | '"' => {
                         let field = parse_quoted_field(&mut chars)?;
@@ -252,6 +334,82 @@ fn parse_segments(expr: &str) -> Result<Vec<PathSegment>, JsonPathError> {
     Ok(segments)
 }
 
+/// Parse the remainder of `[index]` or `[start:end:step]` after the opening bracket.
+fn parse_index_or_slice<I>(chars: &mut std::iter::Peekable<I>) -> Result<PathSegment, JsonPathError>
+where
+    I: Iterator<Item = char>,
+{
+    let start = parse_optional_int(chars)?;
+    consume_whitespace(chars);
+    if chars.peek() != Some(&':') {
+        let index = start.ok_or_else(|| JsonPathError::InvalidToken("Expected index".into()))?;
+        expect_char(chars, ']')?;
+        return Ok(PathSegment::Index(index));
+    }
+
+    chars.next();
+    consume_whitespace(chars);
+    let end = parse_optional_int(chars)?;
+    consume_whitespace(chars);
+    let step = if chars.peek() == Some(&':') {
+        chars.next();
+        consume_whitespace(chars);
+        let step = parse_optional_int(chars)?;
+        consume_whitespace(chars);
+        step
+    } else {
+        None
+    };
+    expect_char(chars, ']')?;
+    Ok(PathSegment::Slice(SliceRange { start, end, step }))
+}
+
+fn parse_optional_int<I>(chars: &mut std::iter::Peekable<I>) -> Result<Option<i64>, JsonPathError>
+where
+    I: Iterator<Item = char>,
+{
+    match chars.peek() {
+        Some(ch) if ch.is_ascii_digit() || *ch == '-' => parse_int(chars).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Collect the raw text of a `[?...]` filter up to its closing bracket, skipping
+/// brackets that appear inside quotes or nested groups.
+fn collect_filter_body<I>(chars: &mut std::iter::Peekable<I>) -> Result<String, JsonPathError>
+where
+    I: Iterator<Item = char>,
+{
+    let mut body = String::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    while let Some(ch) = chars.next() {
+        match quote {
+            Some(q) => {
+                if ch == '\\' {
+                    body.push(ch);
+                    if let Some(escaped) = chars.next() {
+                        body.push(escaped);
+                    }
+                    continue;
+                }
+                if ch == q {
+                    quote = None;
+                }
+            }
+            None => match ch {
+                '\'' | '"' => quote = Some(ch),
+                '[' | '(' => depth += 1,
+                ']' if depth == 0 => return Ok(body),
+                ']' | ')' => depth = depth.saturating_sub(1),
+                _ => {}
+            },
+        }
+        body.push(ch);
+    }
+    Err(JsonPathError::UnterminatedBracket)
+}
+
 fn parse_field_name<I>(chars: &mut std::iter::Peekable<I>) -> Result<String, JsonPathError>
 where
     I: Iterator<Item = char>,
@@ -272,11 +430,15 @@ where
     }
 }
 
-fn parse_number<I>(chars: &mut std::iter::Peekable<I>) -> Result<usize, JsonPathError>
+fn parse_int<I>(chars: &mut std::iter::Peekable<I>) -> Result<i64, JsonPathError>
 where
     I: Iterator<Item = char>,
 {
     let mut digits = String::new();
+    if let Some('-') = chars.peek() {
+        digits.push('-');
+        chars.next();
+    }
     while let Some(&ch) = chars.peek() {
         if ch.is_ascii_digit() {
             digits.push(ch);
@@ -333,11 +495,14 @@ where
 fn split_expression_and_filter(input: &str) -> (String, Option<String>) {
     let mut in_single = false;
     let mut in_double = false;
+    let mut depth = 0usize;
     for (idx, ch) in input.char_indices() {
         match ch {
             '\'' if !in_double => in_single = !in_single,
             '"' if !in_single => in_double = !in_double,
-            '=' if !in_single && !in_double => {
+            '[' if !in_single && !in_double => depth += 1,
+            ']' if !in_single && !in_double => depth = depth.saturating_sub(1),
+            '=' if !in_single && !in_double && depth == 0 => {
                 let expr = input[..idx].trim().to_string();
                 let mut filter_part = input[idx + 1..].trim().to_string();
                 if filter_part.starts_with('=') {
@@ -383,37 +548,419 @@ impl FilterValue {
 }
 
 impl PathSegment {
-    fn apply<'a>(&self, current_path: &str, value: &'a Value, out: &mut Vec<(String, &'a Value)>) {
+    fn apply<'a>(
+        &self,
+        current_path: &str,
+        value: &'a Value,
+        ctx: &EvalContext<'a>,
+        out: &mut Vec<(String, &'a Value)>,
+    ) {
         match self {
             PathSegment::Field(name) => {
-                if let Value::Object(map) = value
-                    && let Some(child) = map.get(name)
-                {
-                    out.push((format!("{}.{}", current_path, name), child));
-                }
-            }
-            PathSegment::FieldWildcard => {
                 if let Value::Object(map) = value {
-                    for (key, child) in map.iter() {
-                        out.push((format!("{}.{}", current_path, key), child));
+                    if ctx.match_case {
+                        if let Some(child) = map.get(name) {
+                            out.push((format!("{}.{}", current_path, name), child));
+                        }
+                    } else {
+                        // Every differently-cased variant is selected, under its own key
+                        for (key, child) in map.iter() {
+                            if key.eq_ignore_ascii_case(name) {
+                                out.push((format!("{}.{}", current_path, key), child));
+                            }
+                        }
                     }
                 }
             }
-            PathSegment::ArrayIndex(idx) => {
+            PathSegment::Wildcard => push_children(current_path, value, out),
+            PathSegment::Index(idx) => {
                 if let Value::Array(items) = value
-                    && let Some(child) = items.get(*idx)
+                    && let Some(i) = normalize_index(*idx, items.len())
                 {
-                    out.push((format!("{}[{}]", current_path, idx), child));
+                    out.push((format!("{}[{}]", current_path, i), &items[i]));
                 }
             }
-            PathSegment::ArrayWildcard => {
+            PathSegment::Slice(range) => {
                 if let Value::Array(items) = value {
-                    for (i, child) in items.iter().enumerate() {
-                        out.push((format!("{}[{}]", current_path, i), child));
+                    for i in range.indices(items.len()) {
+                        out.push((format!("{}[{}]", current_path, i), &items[i]));
                     }
                 }
             }
+            PathSegment::Descendants => push_descendants(current_path.to_string(), value, out),
+            PathSegment::Filter(expr) => {
+                let mut children = Vec::new();
+                push_children(current_path, value, &mut children);
+                out.extend(
+                    children
+                        .into_iter()
+                        .filter(|(_, child)| expr.matches(child, ctx)),
+                );
+            }
+        }
+    }
+}
+
+fn push_children<'a>(current_path: &str, value: &'a Value, out: &mut Vec<(String, &'a Value)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter() {
+                out.push((format!("{}.{}", current_path, key), child));
+            }
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                out.push((format!("{}[{}]", current_path, i), child));
+            }
         }
+        _ => {}
+    }
+}
+
+/// Push `value` and every node below it in document order.
+fn push_descendants<'a>(path: String, value: &'a Value, out: &mut Vec<(String, &'a Value)>) {
+    let mut children = Vec::new();
+    push_children(&path, value, &mut children);
+    out.push((path, value));
+    for (child_path, child) in children {
+        push_descendants(child_path, child, out);
+    }
+}
+
+fn normalize_index(index: i64, len: usize) -> Option<usize> {
+    let resolved = if index < 0 { len as i64 + index } else { index };
+    (0..len as i64)
+        .contains(&resolved)
+        .then_some(resolved as usize)
+}
+
+impl SliceRange {
+    /// Selected indexes for an array of `len` elements, following Python slice rules.
+    fn indices(&self, len: usize) -> Vec<usize> {
+        let len = len as i64;
+        let step = self.step.unwrap_or(1);
+        let normalize = |i: i64| if i >= 0 { i } else { len + i };
+        let mut out = Vec::new();
+
+        if step > 0 {
+            let lower = normalize(self.start.unwrap_or(0)).clamp(0, len);
+            let upper = normalize(self.end.unwrap_or(len)).clamp(0, len);
+            let mut i = lower;
+            while i < upper {
+                out.push(i as usize);
+                i += step;
+            }
+        } else if step < 0 {
+            let upper = self.start.map_or(len - 1, normalize).clamp(-1, len - 1);
+            let lower = self.end.map_or(-1, normalize).clamp(-1, len - 1);
+            let mut i = upper;
+            while lower < i {
+                out.push(i as usize);
+                i += step;
+            }
+        }
+        out
+    }
+}
+
+impl FilterExpr {
+    fn parse(raw: &str) -> Result<Self, JsonPathError> {
+        let mut parser = FilterParser {
+            chars: raw.chars().collect(),
+            pos: 0,
+        };
+        let expr = parser.parse_or()?;
+        parser.skip_whitespace();
+        if parser.pos < parser.chars.len() {
+            let rest: String = parser.chars[parser.pos..].iter().collect();
+            return Err(JsonPathError::InvalidFilter(format!(
+                "Unexpected input '{rest}'"
+            )));
+        }
+        Ok(expr)
+    }
+
+    fn matches(&self, current: &Value, ctx: &EvalContext<'_>) -> bool {
+        match self {
+            FilterExpr::Or(lhs, rhs) => lhs.matches(current, ctx) || rhs.matches(current, ctx),
+            FilterExpr::And(lhs, rhs) => lhs.matches(current, ctx) && rhs.matches(current, ctx),
+            FilterExpr::Not(inner) => !inner.matches(current, ctx),
+            FilterExpr::Exists(operand) => operand.resolve(current, ctx).is_some(),
+            FilterExpr::Compare(lhs, op, rhs) => compare(
+                lhs.resolve(current, ctx),
+                *op,
+                rhs.resolve(current, ctx),
+                ctx.match_case,
+            ),
+        }
+    }
+}
+
+impl FilterOperand {
+    fn resolve<'v>(&'v self, current: &'v Value, ctx: &EvalContext<'v>) -> Option<&'v Value> {
+        let (start, steps) = match self {
+            FilterOperand::Literal(value) => return Some(value),
+            FilterOperand::Current(steps) => (current, steps),
+            FilterOperand::Root(steps) => (ctx.root, steps),
+        };
+        steps
+            .iter()
+            .try_fold(start, |value, step| match (step, value) {
+                // Singular paths pick one member: an exact match, else the first
+                // case-insensitive one
+                (SingularStep::Field(name), Value::Object(map)) => map.get(name).or_else(|| {
+                    if ctx.match_case {
+                        return None;
+                    }
+                    map.iter()
+                        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                        .map(|(_, child)| child)
+                }),
+                (SingularStep::Index(idx), Value::Array(items)) => {
+                    normalize_index(*idx, items.len()).map(|i| &items[i])
+                }
+                _ => None,
+            })
+    }
+}
+
+/// Compare two filter operands. A missing operand only equals another missing
+/// operand and never orders; mismatched types are unequal and never order.
+fn compare(lhs: Option<&Value>, op: CompareOp, rhs: Option<&Value>, match_case: bool) -> bool {
+    match op {
+        CompareOp::Eq => operands_equal(lhs, rhs, match_case),
+        CompareOp::Ne => !operands_equal(lhs, rhs, match_case),
+        CompareOp::Lt => operand_less(lhs, rhs),
+        CompareOp::Gt => operand_less(rhs, lhs),
+        CompareOp::Le => operand_less(lhs, rhs) || operands_equal(lhs, rhs, match_case),
+        CompareOp::Ge => operand_less(rhs, lhs) || operands_equal(lhs, rhs, match_case),
+    }
+}
+
+fn operands_equal(lhs: Option<&Value>, rhs: Option<&Value>, match_case: bool) -> bool {
+    match (lhs, rhs) {
+        (None, None) => true,
+        (Some(Value::String(a)), Some(Value::String(b))) => {
+            if match_case {
+                a == b
+            } else {
+                a.eq_ignore_ascii_case(b)
+            }
+        }
+        (Some(Value::Number(a)), Some(Value::Number(b))) => a.as_f64() == b.as_f64(),
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn operand_less(lhs: Option<&Value>, rhs: Option<&Value>) -> bool {
+    match (lhs, rhs) {
+        (Some(Value::Number(a)), Some(Value::Number(b))) => match (a.as_f64(), b.as_f64()) {
+            (Some(a), Some(b)) => a < b,
+            _ => false,
+        },
+        (Some(Value::String(a)), Some(Value::String(b))) => a < b,
+        _ => false,
+    }
+}
+
+/// Recursive-descent parser for the body of a `[?...]` filter.
+struct FilterParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl FilterParser {
+    fn parse_or(&mut self) -> Result<FilterExpr, JsonPathError> {
+        let mut expr = self.parse_and()?;
+        while self.eat("||") {
+            let rhs = self.parse_and()?;
+            expr = FilterExpr::Or(Box::new(expr), Box::new(rhs));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, JsonPathError> {
+        let mut expr = self.parse_unary()?;
+        while self.eat("&&") {
+            let rhs = self.parse_unary()?;
+            expr = FilterExpr::And(Box::new(expr), Box::new(rhs));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpr, JsonPathError> {
+        self.skip_whitespace();
+        if self.peek() == Some('!') && self.peek_at(1) != Some('=') {
+            self.pos += 1;
+            return Ok(FilterExpr::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat("(") {
+            let expr = self.parse_or()?;
+            if !self.eat(")") {
+                return Err(JsonPathError::InvalidFilter("Missing ')'".into()));
+            }
+            return Ok(expr);
+        }
+
+        let lhs = self.parse_operand()?;
+        match self.parse_op() {
+            Some(op) => {
+                let rhs = self.parse_operand()?;
+                Ok(FilterExpr::Compare(lhs, op, rhs))
+            }
+            None => match lhs {
+                FilterOperand::Literal(_) => Err(JsonPathError::InvalidFilter(
+                    "Expected a comparison after literal".into(),
+                )),
+                path => Ok(FilterExpr::Exists(path)),
+            },
+        }
+    }
+
+    fn parse_op(&mut self) -> Option<CompareOp> {
+        const OPS: [(&str, CompareOp); 6] = [
+            ("==", CompareOp::Eq),
+            ("!=", CompareOp::Ne),
+            ("<=", CompareOp::Le),
+            (">=", CompareOp::Ge),
+            ("<", CompareOp::Lt),
+            (">", CompareOp::Gt),
+        ];
+        OPS.iter()
+            .find(|(token, _)| self.eat(token))
+            .map(|(_, op)| *op)
+    }
+
+    fn parse_operand(&mut self) -> Result<FilterOperand, JsonPathError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('@') => {
+                self.pos += 1;
+                Ok(FilterOperand::Current(self.parse_steps()?))
+            }
+            Some('$') => {
+                self.pos += 1;
+                Ok(FilterOperand::Root(self.parse_steps()?))
+            }
+            Some(quote @ ('\'' | '"')) => {
+                self.pos += 1;
+                let mut text = String::new();
+                while let Some(ch) = self.peek() {
+                    self.pos += 1;
+                    if ch == quote {
+                        return Ok(FilterOperand::Literal(Value::String(text)));
+                    }
+                    if ch == '\\' {
+                        if let Some(escaped) = self.peek() {
+                            self.pos += 1;
+                            text.push(escaped);
+                        }
+                    } else {
+                        text.push(ch);
+                    }
+                }
+                Err(JsonPathError::InvalidFilter("Unterminated string".into()))
+            }
+            Some(ch) if ch.is_ascii_digit() || ch == '-' => {
+                let literal = self.take_while(|c| c.is_ascii_digit() || "+-.eE".contains(c));
+                serde_json::from_str::<Value>(&literal)
+                    .ok()
+                    .filter(Value::is_number)
+                    .map(FilterOperand::Literal)
+                    .ok_or_else(|| {
+                        JsonPathError::InvalidFilter(format!("Invalid number '{literal}'"))
+                    })
+            }
+            Some(ch) if ch.is_alphabetic() => {
+                let word = self.take_while(char::is_alphabetic);
+                match word.as_str() {
+                    "true" => Ok(FilterOperand::Literal(Value::Bool(true))),
+                    "false" => Ok(FilterOperand::Literal(Value::Bool(false))),
+                    "null" => Ok(FilterOperand::Literal(Value::Null)),
+                    _ => Err(JsonPathError::InvalidFilter(format!(
+                        "Unknown literal '{word}'"
+                    ))),
+                }
+            }
+            _ => Err(JsonPathError::InvalidFilter(
+                "Expected '@', '$' or a literal".into(),
+            )),
+        }
+    }
+
+    fn parse_steps(&mut self) -> Result<Vec<SingularStep>, JsonPathError> {
+        let mut steps = Vec::new();
+        loop {
+            match self.peek() {
+                Some('.') => {
+                    self.pos += 1;
+                    let name = self.take_while(|c| c.is_alphanumeric() || c == '_' || c == '-');
+                    if name.is_empty() {
+                        return Err(JsonPathError::InvalidFilter("Expected field name".into()));
+                    }
+                    steps.push(SingularStep::Field(name));
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    let step = match self.parse_operand()? {
+                        FilterOperand::Literal(Value::String(name)) => SingularStep::Field(name),
+                        FilterOperand::Literal(Value::Number(num)) => {
+                            num.as_i64().map(SingularStep::Index).ok_or_else(|| {
+                                JsonPathError::InvalidFilter("Invalid array index".into())
+                            })?
+                        }
+                        _ => {
+                            return Err(JsonPathError::InvalidFilter(
+                                "Expected a quoted name or index".into(),
+                            ));
+                        }
+                    };
+                    if !self.eat("]") {
+                        return Err(JsonPathError::InvalidFilter("Missing ']'".into()));
+                    }
+                    steps.push(step);
+                }
+                _ => return Ok(steps),
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consume `token` (after optional whitespace) if it comes next.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let len = token.chars().count();
+        let matches = self
+            .chars
+            .get(self.pos..self.pos + len)
+            .is_some_and(|slice| slice.iter().copied().eq(token.chars()));
+        if matches {
+            self.pos += len;
+        }
+        matches
+    }
+
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(&pred) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
     }
 }
 
@@ -443,6 +990,206 @@ mod tests {
         assert_eq!(matches[0].path, "0.user.name");
     }
 
+    fn bookstore() -> Value {
+        json!({
+            "store": {
+                "book": [
+                    {"category": "reference", "author": "Nigel Rees", "title": "Sayings of the Century", "price": 8.95},
+                    {"category": "fiction", "author": "Evelyn Waugh", "title": "Sword of Honour", "price": 12.99},
+                    {"category": "fiction", "author": "Herman Melville", "title": "Moby Dick", "isbn": "0-553-21311-3", "price": 8.99},
+                    {"category": "fiction", "author": "J. R. R. Tolkien", "title": "The Lord of the Rings", "isbn": "0-395-19395-8", "price": 22.99}
+                ],
+                "bicycle": {"color": "red", "price": 399}
+            },
+            "expensive": 10
+        })
+    }
+
+    fn paths(query: &str) -> Vec<String> {
+        let query = JsonPathQuery::parse(query).unwrap();
+        query
+            .evaluate(&bookstore(), "0", false)
+            .into_iter()
+            .map(|m| m.path)
+            .collect()
+    }
+
+    fn sorted_paths(query: &str) -> Vec<String> {
+        let mut paths = paths(query);
+        paths.sort();
+        paths
+    }
+
+    fn books(indexes: &[usize], suffix: &str) -> Vec<String> {
+        indexes
+            .iter()
+            .map(|i| format!("0.store.book[{i}]{suffix}"))
+            .collect()
+    }
+
+    #[test]
+    fn recursive_descent_finds_nested_fields() {
+        assert_eq!(paths("$..author"), books(&[0, 1, 2, 3], ".author"));
+        assert_eq!(
+            sorted_paths("$..price"),
+            vec![
+                "0.store.bicycle.price",
+                "0.store.book[0].price",
+                "0.store.book[1].price",
+                "0.store.book[2].price",
+                "0.store.book[3].price",
+            ]
+        );
+        assert_eq!(paths("$.store..color"), vec!["0.store.bicycle.color"]);
+    }
+
+    #[test]
+    fn recursive_descent_with_bracket_selector() {
+        assert_eq!(paths("$..book[2].title"), books(&[2], ".title"));
+        assert_eq!(paths("$..[3].author"), books(&[3], ".author"));
+    }
+
+    #[test]
+    fn recursive_wildcard_visits_every_node() {
+        // store, expensive + book, bicycle + 4 books + 18 book fields + 2 bicycle fields
+        assert_eq!(paths("$..*").len(), 2 + 2 + 4 + 18 + 2);
+    }
+
+    #[test]
+    fn wildcards_cover_objects_and_arrays() {
+        assert_eq!(
+            sorted_paths("$.store.*"),
+            vec!["0.store.bicycle", "0.store.book"]
+        );
+        assert_eq!(
+            paths("$.store.book[*].author"),
+            books(&[0, 1, 2, 3], ".author")
+        );
+        assert_eq!(
+            paths("$.store.book.*.title"),
+            books(&[0, 1, 2, 3], ".title")
+        );
+    }
+
+    #[test]
+    fn negative_index_counts_from_end() {
+        assert_eq!(paths("$.store.book[-1].title"), books(&[3], ".title"));
+        assert!(paths("$.store.book[-5]").is_empty());
+        assert!(paths("$.store.book[4]").is_empty());
+    }
+
+    #[test]
+    fn array_slices() {
+        assert_eq!(paths("$.store.book[1:3]"), books(&[1, 2], ""));
+        assert_eq!(paths("$.store.book[:2]"), books(&[0, 1], ""));
+        assert_eq!(paths("$.store.book[-2:]"), books(&[2, 3], ""));
+        assert_eq!(paths("$.store.book[::2]"), books(&[0, 2], ""));
+        assert_eq!(paths("$.store.book[::-1]"), books(&[3, 2, 1, 0], ""));
+        assert_eq!(paths("$.store.book[2:0:-1]"), books(&[2, 1], ""));
+        assert_eq!(paths("$.store.book[1:100]"), books(&[1, 2, 3], ""));
+        assert!(paths("$.store.book[0:4:0]").is_empty());
+    }
+
+    #[test]
+    fn filter_comparisons() {
+        assert_eq!(
+            paths("$.store.book[?(@.price < 10)].title"),
+            books(&[0, 2], ".title")
+        );
+        assert_eq!(
+            paths("$.store.book[?(@.price <= 8.99)]"),
+            books(&[0, 2], "")
+        );
+        assert_eq!(paths("$.store.book[?(@.price > 20)]"), books(&[3], ""));
+        assert_eq!(
+            paths("$.store.book[?(@.price >= 12.99)]"),
+            books(&[1, 3], "")
+        );
+        assert_eq!(
+            paths("$.store.book[?(@.category == 'reference')]"),
+            books(&[0], "")
+        );
+        assert_eq!(
+            paths("$.store.book[?(@.category != \"reference\")]"),
+            books(&[1, 2, 3], "")
+        );
+        assert_eq!(paths("$.store.book[?@.price==8.95]"), books(&[0], ""));
+    }
+
+    #[test]
+    fn filter_existence_and_logic() {
+        assert_eq!(paths("$.store.book[?(@.isbn)]"), books(&[2, 3], ""));
+        assert_eq!(paths("$.store.book[?(!@.isbn)]"), books(&[0, 1], ""));
+        assert_eq!(
+            paths("$.store.book[?(@.category == 'fiction' && @.price <= 12.99)]"),
+            books(&[1, 2], "")
+        );
+        assert_eq!(
+            paths("$.store.book[?(@.category != 'fiction' || @.price >= 22.99)]"),
+            books(&[0, 3], "")
+        );
+        assert_eq!(
+            paths("$.store.book[?(@.isbn && (@.price < 10 || @.price > 20))]"),
+            books(&[2, 3], "")
+        );
+    }
+
+    #[test]
+    fn filter_can_reference_root() {
+        assert_eq!(
+            paths("$.store.book[?(@.price > $.expensive)]"),
+            books(&[1, 3], "")
+        );
+    }
+
+    #[test]
+    fn filter_applies_to_object_members_under_descent() {
+        assert_eq!(paths("$..[?(@.color == 'red')]"), vec!["0.store.bicycle"]);
+    }
+
+    #[test]
+    fn filter_string_equality_respects_case_option() {
+        let query = JsonPathQuery::parse("$.store.book[?(@.category == 'FICTION')]").unwrap();
+        assert_eq!(query.evaluate(&bookstore(), "0", false).len(), 3);
+        assert!(query.evaluate(&bookstore(), "0", true).is_empty());
+    }
+
+    #[test]
+    fn filter_expression_combines_with_trailing_equality() {
+        assert_eq!(
+            paths("$.store.book[?(@.price > 10)].author = \"J. R. R. Tolkien\""),
+            books(&[3], ".author")
+        );
+    }
+
+    #[test]
+    fn matches_carry_highlight_components() {
+        let query = JsonPathQuery::parse("$..isbn").unwrap();
+        let matches = query.evaluate(&bookstore(), "0", false);
+        assert_eq!(matches.len(), 2);
+        assert!(matches!(matches[0].component, FieldComponent::Value));
+        assert_eq!(matches[0].matched_text.as_deref(), Some("0-553-21311-3"));
+        assert_eq!(matches[0].highlight_range, Some(0..13));
+
+        let query = JsonPathQuery::parse("$.store.book[?(@.isbn)]").unwrap();
+        let matches = query.evaluate(&bookstore(), "0", false);
+        assert!(
+            matches
+                .iter()
+                .all(|m| matches!(m.component, FieldComponent::EntireRow))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_queries() {
+        assert!(JsonPathQuery::parse("$..").is_err());
+        assert!(JsonPathQuery::parse("$.store.book[1:").is_err());
+        assert!(JsonPathQuery::parse("$.store.book[?(@.price <)]").is_err());
+        assert!(JsonPathQuery::parse("$.store.book[?(@.price < 10]").is_err());
+        assert!(JsonPathQuery::parse("$.store.book[?(10)]").is_err());
+        assert!(JsonPathQuery::parse("$.store.book[?(@.title == 'open)]").is_err());
+    }
+
     #[test]
     fn respects_case_option() {
         let query = JsonPathQuery::parse("$.user.name = 'alice'").unwrap();
@@ -450,4 +1197,64 @@ mod tests {
         assert!(query.evaluate(&value, "0", false).len() == 1);
         assert!(query.evaluate(&value, "0", true).is_empty());
     }
+
+    #[test]
+    fn field_names_ignore_case_unless_match_case() {
+        let value = json!({
+            "events": [
+                {"userId": 1},
+                {"userid": 2},
+                {"UserID": 3, "nested": {"USERID": 4}},
+                {"other": 5}
+            ]
+        });
+
+        let query = JsonPathQuery::parse("$.events[*].userId").unwrap();
+        let matches: Vec<String> = query
+            .evaluate(&value, "0", false)
+            .into_iter()
+            .map(|m| m.path)
+            .collect();
+        assert_eq!(
+            matches,
+            [
+                "0.events[0].userId",
+                "0.events[1].userid",
+                "0.events[2].UserID"
+            ]
+        );
+
+        let exact: Vec<String> = query
+            .evaluate(&value, "0", true)
+            .into_iter()
+            .map(|m| m.path)
+            .collect();
+        assert_eq!(exact, ["0.events[0].userId"]);
+    }
+
+    #[test]
+    fn recursive_descent_ignores_key_case() {
+        let value = json!({"a": {"UserID": 1, "b": {"userid": 2}}});
+        let query = JsonPathQuery::parse("$..userId").unwrap();
+
+        let mut paths: Vec<String> = query
+            .evaluate(&value, "0", false)
+            .into_iter()
+            .map(|m| m.path)
+            .collect();
+        paths.sort();
+        assert_eq!(paths, ["0.a.UserID", "0.a.b.userid"]);
+        assert!(query.evaluate(&value, "0", true).is_empty());
+    }
+
+    #[test]
+    fn filter_operands_ignore_key_case() {
+        let value = json!({"items": [{"Price": 5}, {"price": 20}]});
+        let query = JsonPathQuery::parse("$.items[?(@.price > 10)]").unwrap();
+        assert_eq!(query.evaluate(&value, "0", false).len(), 1);
+
+        let query = JsonPathQuery::parse("$.items[?(@.PRICE)]").unwrap();
+        assert_eq!(query.evaluate(&value, "0", false).len(), 2);
+        assert!(query.evaluate(&value, "0", true).is_empty());
+    }
 }
@@ -1,26 +1,25 @@
 mod engine;
+mod facets;
+mod filter;
 mod jsonpath;
+mod multi_file;
+pub mod replace;
 pub mod results;
 
-pub use engine::{QueryMode, Search};
+pub use engine::{QueryMode, Search, SearchUpdate};
+pub use facets::{Facet, FacetSampler, Facets};
+pub use multi_file::{FileMatches, MultiFileUpdate};
 
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone)]
 pub enum SearchMessage {
     StartSearch(Search),
-    #[allow(dead_code)]
+    /// Cancel the running scan, keeping the hits found so far
     StopSearch,
 }
 
 impl SearchMessage {
-    pub fn is_searching(&self) -> bool {
-        match self {
-            SearchMessage::StartSearch(search) => search.scanning,
-            SearchMessage::StopSearch => false,
-        }
-    }
-
     pub fn history_entry(&self) -> Option<String> {
         match self {
             SearchMessage::StartSearch(search) => {
@@ -30,11 +29,17 @@ impl SearchMessage {
         }
     }
 
-    pub fn create_search(query: String, match_case: bool, query_mode: QueryMode) -> Option<Self> {
+    pub fn create_search(
+        query: String,
+        match_case: bool,
+        query_mode: QueryMode,
+        scope_path: Option<String>,
+    ) -> Option<Self> {
         let search = Search {
             query,
             match_case,
             query_mode,
+            scope_path,
             scanning: true,
             ..Search::default()
         };
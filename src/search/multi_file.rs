@@ -0,0 +1,224 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use super::Search;
+use super::results::SearchHit;
+use crate::error::ThothError;
+use crate::file::loaders::FileKind;
+
+/// One file's matches from a search across several files
+#[derive(Debug, Clone)]
+pub struct FileMatches {
+    pub path: PathBuf,
+    /// Matching records, in record order
+    pub hits: Vec<SearchHit>,
+    /// The file's scan stopped at `max_results`
+    pub truncated: bool,
+}
+
+/// Progress of a search across several files, sent over the channel from
+/// [`Search::start_scanning_files`]
+#[derive(Debug)]
+pub enum MultiFileUpdate {
+    /// Started on the `index`th file (0-based)
+    Scanning { index: usize },
+    /// A file had at least one match
+    Matched(FileMatches),
+    /// A file could not be opened or searched; the rest are still searched
+    Skipped { path: PathBuf, reason: String },
+    /// Every file was searched, the search was cancelled, or the query is
+    /// invalid
+    Finished(crate::error::Result<()>),
+}
+
+impl Search {
+    /// Run the query over `files` one after another in the background
+    ///
+    /// Each file is opened only when its turn comes and dropped once scanned.
+    /// The scope is ignored, since a row path means nothing in other files;
+    /// `max_results` limits each file separately. Setting `cancel` stops the
+    /// search inside the file being scanned.
+    pub fn start_scanning_files(
+        &self,
+        files: Vec<PathBuf>,
+        cancel: Arc<AtomicBool>,
+    ) -> mpsc::Receiver<MultiFileUpdate> {
+        let (tx, rx) = mpsc::channel();
+        let job = Search {
+            scope_path: None,
+            ..self.clone()
+        };
+
+        thread::spawn(move || {
+            // A query that doesn't parse would fail the same way in every file
+            if let Err(e) = job.check_query() {
+                let _ = tx.send(MultiFileUpdate::Finished(Err(e)));
+                return;
+            }
+
+            for (index, path) in files.into_iter().enumerate() {
+                if cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+                let _ = tx.send(MultiFileUpdate::Scanning { index });
+
+                let mut hits = Vec::new();
+                let file = Some(path.clone());
+                let update = match job.scan(&file, &FileKind::default(), &cancel, |batch| {
+                    hits.extend(batch)
+                }) {
+                    Ok(_) if hits.is_empty() => continue,
+                    Ok(summary) => MultiFileUpdate::Matched(FileMatches {
+                        path,
+                        hits,
+                        truncated: summary.truncated,
+                    }),
+                    Err(ThothError::SearchError { reason, .. }) => {
+                        MultiFileUpdate::Skipped { path, reason }
+                    }
+                    Err(e) => MultiFileUpdate::Skipped {
+                        path,
+                        reason: e.to_string(),
+                    },
+                };
+                if tx.send(update).is_err() {
+                    // Nobody is listening any more
+                    return;
+                }
+            }
+            let _ = tx.send(MultiFileUpdate::Finished(Ok(())));
+        });
+
+        rx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::QueryMode;
+    use std::io::Write;
+
+    fn ndjson_file(lines: &[&str]) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        for line in lines {
+            writeln!(file, "{}", line).unwrap();
+        }
+        file.flush().unwrap();
+        file
+    }
+
+    fn run(search: &Search, files: Vec<PathBuf>) -> Vec<MultiFileUpdate> {
+        let rx = search.start_scanning_files(files, Arc::new(AtomicBool::new(false)));
+        rx.into_iter().collect()
+    }
+
+    #[test]
+    fn test_lists_matches_per_file_and_skips_unreadable_files() {
+        let first = ndjson_file(&[r#"{"user":"alice"}"#, r#"{"user":"bob"}"#]);
+        let second = ndjson_file(&[r#"{"user":"carol"}"#]);
+        let third = ndjson_file(&[r#"{"user":"bob"}"#, r#"{"user":"bobby"}"#]);
+        let missing = first.path().with_extension("missing");
+        let search = Search {
+            query: "bob".to_string(),
+            ..Search::default()
+        };
+
+        let updates = run(
+            &search,
+            vec![
+                first.path().to_path_buf(),
+                missing.clone(),
+                second.path().to_path_buf(),
+                third.path().to_path_buf(),
+            ],
+        );
+
+        let matched: Vec<(PathBuf, Vec<usize>)> = updates
+            .iter()
+            .filter_map(|update| match update {
+                MultiFileUpdate::Matched(file) => Some((
+                    file.path.clone(),
+                    file.hits.iter().map(|hit| hit.record_index).collect(),
+                )),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            matched,
+            [
+                (first.path().to_path_buf(), vec![1]),
+                (third.path().to_path_buf(), vec![0, 1]),
+            ]
+        );
+        assert!(updates.iter().any(
+            |update| matches!(update, MultiFileUpdate::Skipped { path, .. } if *path == missing)
+        ));
+        let scanned = updates
+            .iter()
+            .filter(|update| matches!(update, MultiFileUpdate::Scanning { .. }))
+            .count();
+        assert_eq!(scanned, 4);
+        assert!(matches!(
+            updates.last(),
+            Some(MultiFileUpdate::Finished(Ok(())))
+        ));
+    }
+
+    #[test]
+    fn test_respects_query_mode() {
+        let file = ndjson_file(&[r#"{"price":50}"#, r#"{"price":150}"#]);
+        let search = Search {
+            query: "price > 100".to_string(),
+            query_mode: QueryMode::Filter,
+            ..Search::default()
+        };
+
+        let updates = run(&search, vec![file.path().to_path_buf()]);
+        let Some(MultiFileUpdate::Matched(found)) = updates.get(1) else {
+            panic!("expected a match, got {:?}", updates);
+        };
+        assert_eq!(found.hits.len(), 1);
+        assert_eq!(found.hits[0].record_index, 1);
+    }
+
+    #[test]
+    fn test_invalid_query_fails_before_opening_files() {
+        let file = ndjson_file(&[r#"{"a":1}"#]);
+        let search = Search {
+            query: "$.items[1:".to_string(),
+            query_mode: QueryMode::JsonPath,
+            ..Search::default()
+        };
+
+        let updates = run(&search, vec![file.path().to_path_buf()]);
+        assert!(matches!(
+            updates.as_slice(),
+            [MultiFileUpdate::Finished(Err(
+                ThothError::SearchError { .. }
+            ))]
+        ));
+    }
+
+    #[test]
+    fn test_cancelled_search_stops_before_next_file() {
+        let file = ndjson_file(&[r#"{"a":"needle"}"#]);
+        let search = Search {
+            query: "needle".to_string(),
+            ..Search::default()
+        };
+
+        let rx = search.start_scanning_files(
+            vec![file.path().to_path_buf(), file.path().to_path_buf()],
+            Arc::new(AtomicBool::new(true)),
+        );
+        let updates: Vec<MultiFileUpdate> = rx.into_iter().collect();
+        assert!(matches!(
+            updates.as_slice(),
+            [MultiFileUpdate::Finished(Ok(()))]
+        ));
+    }
+}
@@ -0,0 +1,430 @@
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+use regex::{NoExpand, Regex, RegexBuilder};
+use serde_json::Value;
+
+use crate::constants::MAX_REPLACE_PREVIEW_RECORDS;
+use crate::error::{Result, ThothError};
+use crate::file::export::export_records;
+use crate::file::loaders::load_file_auto;
+
+/// A find-and-replace over the values of every root record
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplaceSpec {
+    /// Text to find, or a pattern when `regex` is set
+    pub find: String,
+    /// Replacement text; in regex mode `$1` / `${name}` expand to capture groups
+    pub replacement: String,
+    pub regex: bool,
+    pub match_case: bool,
+    /// Relative field path (e.g. `status` or `user.items[0].state`) that
+    /// replacements are limited to
+    pub field: Option<String>,
+}
+
+/// One value a replacement changes, as JSON text before and after
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplaceChange {
+    /// Row path of the value (e.g. `3.user.status`)
+    pub path: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// A record a replacement changes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplaceHit {
+    pub record_index: usize,
+    pub changes: Vec<ReplaceChange>,
+}
+
+/// The records a replacement would change, computed before anything is written
+#[derive(Debug, Clone, Default)]
+pub struct ReplacePreview {
+    pub spec: ReplaceSpec,
+    /// Changes of the first `MAX_REPLACE_PREVIEW_RECORDS` affected records
+    pub hits: Vec<ReplaceHit>,
+    /// Every affected record, in file order
+    pub records: Vec<usize>,
+}
+
+impl ReplacePreview {
+    /// Find every record in `source` that `spec` would change
+    ///
+    /// Every record is parsed and checked: a search of the raw text would
+    /// miss values whose JSON escapes differ from the typed text and letters
+    /// that only match when Unicode case is ignored. Errors if the pattern is
+    /// invalid or a replacement would turn a value into invalid JSON. A `.gz`
+    /// source may decompress to at most `max_file_size_mb` megabytes.
+    pub fn build(source: &Path, spec: &ReplaceSpec, max_file_size_mb: u64) -> Result<Self> {
+        let replacer = Replacer::new(spec)?;
+        let (_, mut loader) = load_file_auto(source, max_file_size_mb)?;
+
+        let mut preview = Self {
+            spec: spec.clone(),
+            ..Self::default()
+        };
+        for record_index in 0..loader.len() {
+            // Unparseable records are left alone; opening them reports the error
+            let Ok(mut value) = loader.get(record_index) else {
+                continue;
+            };
+            let changes = replacer.apply(record_index, &mut value)?;
+            if changes.is_empty() {
+                continue;
+            }
+            preview.records.push(record_index);
+            if preview.hits.len() < MAX_REPLACE_PREVIEW_RECORDS {
+                preview.hits.push(ReplaceHit {
+                    record_index,
+                    changes,
+                });
+            }
+        }
+        Ok(preview)
+    }
+
+    /// Write `source` with the previewed replacements applied to `output`
+    ///
+    /// The source file is never modified. Returns the number of records changed.
+    pub fn write(&self, source: &Path, output: &Path, max_file_size_mb: u64) -> Result<usize> {
+        if crate::platform::canonical_path(output) == crate::platform::canonical_path(source) {
+            return Err(ThothError::FileReadError {
+                path: output.to_path_buf(),
+                reason: "Replacements are written to a new file; choose a different output path"
+                    .to_string(),
+            });
+        }
+
+        let replacer = Replacer::new(&self.spec)?;
+        let (_, mut loader) = load_file_auto(source, max_file_size_mb)?;
+        export_records(&mut loader, output, |record_index, mut value| {
+            if self.records.binary_search(&record_index).is_ok() {
+                replacer.apply(record_index, &mut value)?;
+            }
+            Ok(value)
+        })?;
+        Ok(self.records.len())
+    }
+
+    /// Suggested output path next to `source` (`data.json` -> `data.replaced.json`)
+    pub fn default_output_path(source: &Path) -> PathBuf {
+        let stem = source
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "output".to_string());
+        let name = match source.extension() {
+            Some(ext) => format!("{}.replaced.{}", stem, ext.to_string_lossy()),
+            None => format!("{}.replaced", stem),
+        };
+        source.with_file_name(name)
+    }
+}
+
+/// A compiled `ReplaceSpec`
+struct Replacer {
+    pattern: Regex,
+    replacement: String,
+    /// Expand `$n` capture references in the replacement (regex mode only)
+    expand: bool,
+    field: Option<String>,
+    find: String,
+}
+
+impl Replacer {
+    fn new(spec: &ReplaceSpec) -> Result<Self> {
+        let invalid = |reason: String| ThothError::SearchError {
+            query: spec.find.clone(),
+            reason,
+        };
+        if spec.find.is_empty() {
+            return Err(invalid("Nothing to find".to_string()));
+        }
+
+        let source = if spec.regex {
+            Cow::Borrowed(spec.find.as_str())
+        } else {
+            Cow::Owned(regex::escape(&spec.find))
+        };
+        let pattern = RegexBuilder::new(&source)
+            .case_insensitive(!spec.match_case)
+            .build()
+            .map_err(|e| invalid(format!("Invalid regex: {}", e)))?;
+
+        let field = spec.field.as_deref().map(str::trim).and_then(|field| {
+            let field = field.strip_prefix('$').unwrap_or(field);
+            let field = field.strip_prefix('.').unwrap_or(field);
+            (!field.is_empty()).then(|| field.to_string())
+        });
+
+        Ok(Self {
+            pattern,
+            replacement: spec.replacement.clone(),
+            expand: spec.regex,
+            field,
+            find: spec.find.clone(),
+        })
+    }
+
+    /// Replace inside one record, returning what changed
+    fn apply(&self, record_index: usize, value: &mut Value) -> Result<Vec<ReplaceChange>> {
+        let mut path = record_index.to_string();
+        let root_len = path.len();
+        let mut changes = Vec::new();
+        self.replace_in(value, &mut path, root_len, &mut changes)?;
+        Ok(changes)
+    }
+
+    fn replace_in(
+        &self,
+        value: &mut Value,
+        path: &mut String,
+        root_len: usize,
+        changes: &mut Vec<ReplaceChange>,
+    ) -> Result<()> {
+        let rel = path[root_len..].trim_start_matches('.');
+        let in_field = self
+            .field
+            .as_deref()
+            .is_none_or(|field| is_within(rel, field));
+        // Outside the field and not on the way to it: nothing below can change
+        if !in_field
+            && !self
+                .field
+                .as_deref()
+                .is_some_and(|field| is_within(field, rel))
+        {
+            return Ok(());
+        }
+
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map.iter_mut() {
+                    let len = path.len();
+                    path.push('.');
+                    path.push_str(key);
+                    self.replace_in(child, path, root_len, changes)?;
+                    path.truncate(len);
+                }
+            }
+            Value::Array(items) => {
+                for (i, child) in items.iter_mut().enumerate() {
+                    let len = path.len();
+                    path.push_str(&format!("[{}]", i));
+                    self.replace_in(child, path, root_len, changes)?;
+                    path.truncate(len);
+                }
+            }
+            Value::String(text) if in_field => {
+                let replaced = match self.replace_text(text) {
+                    Cow::Borrowed(_) => None,
+                    Cow::Owned(replaced) => Some(replaced),
+                };
+                if let Some(replaced) = replaced.filter(|replaced| *replaced != *text) {
+                    changes.push(ReplaceChange {
+                        path: path.clone(),
+                        before: Value::String(text.clone()).to_string(),
+                        after: Value::String(replaced.clone()).to_string(),
+                    });
+                    *text = replaced;
+                }
+            }
+            // Numbers, booleans and null are only touched inside an explicit
+            // field, by rewriting their JSON text, which must stay valid JSON
+            _ if in_field && self.field.is_some() => {
+                let before = value.to_string();
+                let replaced = self.replace_text(&before);
+                if replaced != before.as_str() {
+                    let after: Value =
+                        serde_json::from_str(&replaced).map_err(|_| ThothError::SearchError {
+                            query: self.find.clone(),
+                            reason: format!(
+                                "Replacing in {} would produce invalid JSON: {}",
+                                path, replaced
+                            ),
+                        })?;
+                    changes.push(ReplaceChange {
+                        path: path.clone(),
+                        before,
+                        after: after.to_string(),
+                    });
+                    *value = after;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn replace_text<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if self.expand {
+            self.pattern.replace_all(text, self.replacement.as_str())
+        } else {
+            self.pattern.replace_all(text, NoExpand(&self.replacement))
+        }
+    }
+}
+
+/// Whether relative path `path` is `ancestor` or lies below it
+fn is_within(path: &str, ancestor: &str) -> bool {
+    ancestor.is_empty()
+        || path
+            .strip_prefix(ancestor)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::DEFAULT_MAX_FILE_SIZE_MB;
+    use serde_json::json;
+    use std::fs;
+
+    fn spec(find: &str, replacement: &str) -> ReplaceSpec {
+        ReplaceSpec {
+            find: find.to_string(),
+            replacement: replacement.to_string(),
+            match_case: true,
+            ..ReplaceSpec::default()
+        }
+    }
+
+    #[test]
+    fn test_replace_string_values_only() {
+        let replacer = Replacer::new(&spec("actve", "active")).unwrap();
+        let mut value = json!({"status": "actve", "actve": 1, "tags": ["actve", "x"]});
+        let changes = replacer.apply(2, &mut value).unwrap();
+
+        assert_eq!(
+            value,
+            json!({"status": "active", "actve": 1, "tags": ["active", "x"]})
+        );
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, ["2.status", "2.tags[0]"]);
+        assert_eq!(changes[0].before, "\"actve\"");
+        assert_eq!(changes[0].after, "\"active\"");
+    }
+
+    #[test]
+    fn test_replace_limited_to_field() {
+        let mut replace = spec("a", "b");
+        replace.field = Some("user.name".to_string());
+        let replacer = Replacer::new(&replace).unwrap();
+        let mut value = json!({"name": "a", "user": {"name": "a", "names": "a"}});
+        replacer.apply(0, &mut value).unwrap();
+        assert_eq!(
+            value,
+            json!({"name": "a", "user": {"name": "b", "names": "a"}})
+        );
+    }
+
+    #[test]
+    fn test_regex_replacement_expands_groups() {
+        let mut replace = spec(r"(\d+)-(\d+)", "$2-$1");
+        replace.regex = true;
+        let replacer = Replacer::new(&replace).unwrap();
+        let mut value = json!({"range": "1-2"});
+        replacer.apply(0, &mut value).unwrap();
+        assert_eq!(value, json!({"range": "2-1"}));
+
+        // Literal mode never expands `$`
+        let replacer = Replacer::new(&spec("1-2", "$0")).unwrap();
+        let mut value = json!({"range": "1-2"});
+        replacer.apply(0, &mut value).unwrap();
+        assert_eq!(value, json!({"range": "$0"}));
+    }
+
+    #[test]
+    fn test_replacement_producing_invalid_json_is_rejected() {
+        let mut replace = spec("true", "yes");
+        replace.field = Some("enabled".to_string());
+        let replacer = Replacer::new(&replace).unwrap();
+        let mut value = json!({"enabled": true});
+        assert!(replacer.apply(0, &mut value).is_err());
+
+        let mut replace = spec("1", "2");
+        replace.field = Some("count".to_string());
+        let replacer = Replacer::new(&replace).unwrap();
+        let mut value = json!({"count": 10});
+        replacer.apply(0, &mut value).unwrap();
+        assert_eq!(value, json!({"count": 20}));
+    }
+
+    #[test]
+    fn test_invalid_patterns_are_rejected() {
+        assert!(Replacer::new(&spec("", "x")).is_err());
+        let mut replace = spec("(", "x");
+        replace.regex = true;
+        assert!(Replacer::new(&replace).is_err());
+    }
+
+    #[test]
+    fn test_preview_and_write_to_new_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("data.ndjson");
+        let original = "{\"status\":\"actve\"}\n{\"status\":\"done\"}\n{\"status\":\"ACTVE\"}\n";
+        fs::write(&source, original).unwrap();
+
+        let mut replace = spec("actve", "active");
+        replace.match_case = false;
+        let preview = ReplacePreview::build(&source, &replace, DEFAULT_MAX_FILE_SIZE_MB).unwrap();
+        assert_eq!(preview.records, [0, 2]);
+        assert_eq!(preview.hits[1].changes[0].before, "\"ACTVE\"");
+
+        // Writing over the source is refused
+        assert!(
+            preview
+                .write(&source, &source, DEFAULT_MAX_FILE_SIZE_MB)
+                .is_err()
+        );
+
+        let output = ReplacePreview::default_output_path(&source);
+        assert_eq!(output.file_name().unwrap(), "data.replaced.ndjson");
+        assert_eq!(
+            preview
+                .write(&source, &output, DEFAULT_MAX_FILE_SIZE_MB)
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            fs::read_to_string(&output).unwrap(),
+            "{\"status\":\"active\"}\n{\"status\":\"done\"}\n{\"status\":\"active\"}\n"
+        );
+        assert_eq!(fs::read_to_string(&source).unwrap(), original);
+    }
+
+    #[test]
+    fn test_preview_finds_escaped_and_unicode_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("data.ndjson");
+        fs::write(
+            &source,
+            "{\"q\":\"say \\\"hi\\\"\"}\n{\"q\":\"caf\\u00e9\"}\n{\"q\":\"CAFÉ\"}\n",
+        )
+        .unwrap();
+
+        let preview =
+            ReplacePreview::build(&source, &spec("\"hi\"", "hello"), DEFAULT_MAX_FILE_SIZE_MB)
+                .unwrap();
+        assert_eq!(preview.records, [0]);
+        assert_eq!(preview.hits[0].changes[0].after, "\"say hello\"");
+
+        let mut replace = spec("café", "bar");
+        replace.match_case = false;
+        let preview = ReplacePreview::build(&source, &replace, DEFAULT_MAX_FILE_SIZE_MB).unwrap();
+        assert_eq!(preview.records, [1, 2]);
+
+        let output = ReplacePreview::default_output_path(&source);
+        assert_eq!(
+            preview
+                .write(&source, &output, DEFAULT_MAX_FILE_SIZE_MB)
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            fs::read_to_string(&output).unwrap(),
+            "{\"q\":\"say \\\"hi\\\"\"}\n{\"q\":\"bar\"}\n{\"q\":\"bar\"}\n"
+        );
+    }
+}
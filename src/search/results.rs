@@ -1,23 +1,32 @@
+use std::collections::HashMap;
 use std::ops::Range;
 use std::sync::Arc;
 
+/// Confidence of the marker fragment added so a record's root row is highlighted
+pub const RECORD_MARKER_CONFIDENCE: f32 = 0.6;
+
 /// Collection of search hits plus aggregate statistics.
 #[derive(Default, Debug, Clone)]
 pub struct SearchResults {
     hits: Vec<SearchHit>,
     stats: SearchStats,
+    /// The scan stopped at the result limit; more records may match
+    truncated: bool,
 }
 
 impl SearchResults {
     pub fn new(hits: Vec<SearchHit>, total_records: usize) -> Self {
         let matched_records = hits.len();
-        Self {
+        let mut results = Self {
             hits,
             stats: SearchStats {
                 total_records,
                 matched_records,
             },
-        }
+            truncated: false,
+        };
+        results.sort_by_score();
+        results
     }
 
     pub fn len(&self) -> usize {
@@ -32,13 +41,43 @@ impl SearchResults {
         self.hits.clear();
         self.stats.matched_records = 0;
         self.stats.total_records = 0;
+        self.truncated = false;
+    }
+
+    /// Add hits for records after the ones already held
+    ///
+    /// Scored hits are merged in best-first; others are appended.
+    pub fn extend(&mut self, hits: Vec<SearchHit>) {
+        self.stats.matched_records += hits.len();
+        self.hits.extend(hits);
+        self.sort_by_score();
+    }
+
+    /// Order scored (fuzzy) hits best-first, keeping record order among
+    /// equal scores
+    fn sort_by_score(&mut self) {
+        if self.hits.iter().any(|hit| hit.score > 0) {
+            self.hits.sort_by(|a, b| b.score.cmp(&a.score));
+        }
+    }
+
+    pub fn set_total_records(&mut self, total_records: usize) {
+        self.stats.total_records = total_records;
+    }
+
+    /// Whether the scan stopped at the result limit before visiting every record
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    pub fn set_truncated(&mut self, truncated: bool) {
+        self.truncated = truncated;
     }
 
     pub fn hits(&self) -> &[SearchHit] {
         &self.hits
     }
 
-    #[allow(dead_code)]
     pub fn record_indices(&self) -> impl Iterator<Item = usize> + '_ {
         self.hits.iter().map(|hit| hit.record_index)
     }
@@ -54,6 +93,15 @@ impl SearchResults {
     pub fn get(&self, idx: usize) -> Option<&SearchHit> {
         self.hits.get(idx)
     }
+
+    /// Number of matches within each matched record, keyed by record index
+    pub fn match_counts(&self) -> HashMap<usize, usize> {
+        let mut counts = HashMap::new();
+        for hit in &self.hits {
+            *counts.entry(hit.record_index).or_insert(0) += hit.match_count();
+        }
+        counts
+    }
 }
 
 /// Describes a single record that matched the query.
@@ -62,6 +110,22 @@ pub struct SearchHit {
     pub record_index: usize,
     pub fragments: Vec<MatchFragment>,
     pub preview: Option<MatchPreview>,
+    /// How well the record matched a fuzzy query; 0 in the other modes
+    pub score: u32,
+}
+
+impl SearchHit {
+    /// Number of matches in this record, not counting the root-row marker
+    ///
+    /// A hit always stands for at least one match, even when the scan only
+    /// recorded the marker (e.g. a raw-text match outside any field).
+    pub fn match_count(&self) -> usize {
+        self.fragments
+            .iter()
+            .filter(|fragment| !fragment.is_record_marker())
+            .count()
+            .max(1)
+    }
 }
 
 /// Lightweight snippet rendered in the sidebar list.
@@ -99,6 +163,20 @@ impl Default for MatchFragment {
     }
 }
 
+impl MatchFragment {
+    /// Whether this is the synthetic fragment that only marks the record's
+    /// root row, rather than a match of its own
+    pub fn is_record_marker(&self) -> bool {
+        matches!(
+            self.target,
+            MatchTarget::JsonField {
+                component: FieldComponent::EntireRow
+            }
+        ) && self.text_range.is_none()
+            && self.confidence == RECORD_MARKER_CONFIDENCE
+    }
+}
+
 /// Where the match occurred (raw blob vs structured field).
 #[allow(dead_code)]
 #[derive(Debug, Clone, Default)]
@@ -126,3 +204,81 @@ pub struct SearchStats {
     pub total_records: usize,
     pub matched_records: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(path: &str, component: FieldComponent) -> MatchFragment {
+        MatchFragment {
+            target: MatchTarget::JsonField { component },
+            path: Some(Arc::from(path)),
+            confidence: 1.0,
+            text_range: Some(0..1),
+            ..MatchFragment::default()
+        }
+    }
+
+    fn marker(record_index: usize) -> MatchFragment {
+        MatchFragment {
+            target: MatchTarget::JsonField {
+                component: FieldComponent::EntireRow,
+            },
+            path: Some(Arc::from(record_index.to_string())),
+            confidence: RECORD_MARKER_CONFIDENCE,
+            ..MatchFragment::default()
+        }
+    }
+
+    #[test]
+    fn test_match_counts_skip_root_marker() {
+        let hits = vec![
+            SearchHit {
+                record_index: 2,
+                fragments: vec![
+                    field("2.name", FieldComponent::Value),
+                    field("2.tags[0]", FieldComponent::Value),
+                    field("2.owner", FieldComponent::Key),
+                    marker(2),
+                ],
+                ..SearchHit::default()
+            },
+            SearchHit {
+                record_index: 7,
+                fragments: vec![field("7.id", FieldComponent::Value), marker(7)],
+                ..SearchHit::default()
+            },
+        ];
+        let counts = SearchResults::new(hits, 10).match_counts();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&2], 3);
+        assert_eq!(counts[&7], 1);
+    }
+
+    #[test]
+    fn test_match_count_is_at_least_one() {
+        let hit = SearchHit {
+            record_index: 0,
+            fragments: vec![marker(0)],
+            ..SearchHit::default()
+        };
+        assert_eq!(hit.match_count(), 1);
+        assert!(SearchResults::default().match_counts().is_empty());
+    }
+
+    #[test]
+    fn test_scored_hits_are_listed_best_first() {
+        let hit = |record_index, score| SearchHit {
+            record_index,
+            score,
+            ..SearchHit::default()
+        };
+        let mut results = SearchResults::new(vec![hit(0, 40), hit(3, 90), hit(5, 40)], 10);
+        results.extend(vec![hit(8, 60), hit(9, 90)]);
+        let order: Vec<usize> = results.record_indices().collect();
+        assert_eq!(order, [3, 9, 8, 0, 5]);
+
+        let unscored = SearchResults::new(vec![hit(4, 0), hit(1, 0)], 10);
+        assert_eq!(unscored.record_indices().collect::<Vec<_>>(), [4, 1]);
+    }
+}
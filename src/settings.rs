@@ -4,7 +4,9 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::helpers::default_rate_limit;
+use crate::constants::{DEFAULT_MAX_FILE_SIZE_MB, SCROLL_MARGIN};
+use crate::file::detect_file_type::DetectedFileType;
+use crate::helpers::{NumberFormat, PathFormat, default_rate_limit};
 use crate::shortcuts::KeyboardShortcuts;
 use crate::theme::Theme;
 
@@ -18,6 +20,11 @@ pub struct Settings {
     /// Dark mode enabled (derived from theme_id, kept for backwards-compat)
     pub dark_mode: bool,
 
+    /// Follow the OS light/dark appearance, switching `theme` to its light or
+    /// dark counterpart when the system changes; `dark_mode` keeps the result
+    #[serde(default)]
+    pub follow_system_theme: bool,
+
     /// Font size for UI
     pub font_size: f32,
 
@@ -83,6 +90,12 @@ pub struct UpdateSettings {
 
     /// Update check interval in hours
     pub check_interval_hours: u64,
+
+    /// Give up on an update check that hasn't finished after this many seconds
+    pub check_timeout_secs: u64,
+
+    /// Offer pre-releases (betas, release candidates) as updates too
+    pub include_prereleases: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,12 +105,24 @@ pub struct PerformanceSettings {
     /// Higher values use more memory but improve performance when re-visiting nodes
     pub cache_size: usize,
 
+    /// Maximum file size to load, in megabytes (default: 500)
+    /// For `.gz` files this applies to the decompressed size
+    pub max_file_size_mb: u64,
+
     /// Number of recent files to remember (default: 10)
     pub max_recent_files: usize,
 
     /// Navigation history size (default: 100)
     /// Number of navigation steps to remember for back/forward navigation
     pub navigation_history_size: usize,
+
+    /// Ask before copying records larger than this, in megabytes (default: 10)
+    /// A huge clipboard write can freeze the app for a moment
+    pub copy_warn_size_mb: u64,
+
+    /// Stop a search after this many matching records (default: 50000)
+    /// Broad queries such as `$..id` can otherwise match every record and stall the UI
+    pub max_search_results: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -105,6 +130,85 @@ pub struct PerformanceSettings {
 pub struct ViewerSettings {
     /// Enable syntax highlighting in JSON viewer (default: true)
     pub syntax_highlighting: bool,
+
+    /// Width of one tree indentation level in pixels (default: 16.0, range: 4.0-64.0)
+    pub indent_size: f32,
+
+    /// How tree indent guide lines are drawn: none, lines, or lines with the
+    /// selected row's ancestors highlighted (default: lines)
+    pub indent_guides: IndentGuides,
+
+    /// Tree depth expanded automatically when a file is opened (default: 0, max: 10)
+    /// Skipped for files that have a saved expansion state
+    pub auto_expand_depth: usize,
+
+    /// Rows kept between the selection and the edge of the view while
+    /// navigating (default: 3, max: 20); at most half the visible rows are used
+    pub scroll_margin: usize,
+
+    /// Reload the open file when it changes on disk (default: false)
+    pub auto_reload: bool,
+
+    /// Show a value type badge (`str`, `obj[3]`, ...) at the end of each row (default: false)
+    pub show_type_badges: bool,
+
+    /// Edit primitive values in place by double-clicking them (default: false)
+    pub inline_editing: bool,
+
+    /// How numeric values are displayed: plain, grouped or scientific (default: plain)
+    /// Copying and editing always use the number as stored
+    pub number_format: NumberFormat,
+
+    /// Show a readable UTC date after values that look like timestamps (default: false)
+    /// ISO-8601 strings are always recognised; numbers only in fields matching `timestamp_fields`
+    pub humanize_timestamps: bool,
+
+    /// Field-name patterns whose numbers are unix seconds or milliseconds; `*` matches anything
+    pub timestamp_fields: Vec<String>,
+
+    /// File-name patterns opened as a fixed type instead of sniffing their
+    /// contents; the first matching pattern wins
+    pub file_type_overrides: Vec<FileTypeOverride>,
+
+    /// Syntax the Copy Path shortcut writes paths in: dot, JSONPath or bracket (default: dot)
+    pub copy_path_format: PathFormat,
+}
+
+/// Files whose names match `pattern` (`*` matches anything, e.g. `*.log`)
+/// are opened as `file_type`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileTypeOverride {
+    pub pattern: String,
+    pub file_type: DetectedFileType,
+}
+
+/// How the tree's indent guide lines are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndentGuides {
+    /// No guide lines
+    None,
+    /// A line for every indentation level
+    #[default]
+    Lines,
+    /// Lines, with the ones leading to the selected row brightened
+    HighlightActive,
+}
+
+impl IndentGuides {
+    pub const ALL: [IndentGuides; 3] = [
+        IndentGuides::None,
+        IndentGuides::Lines,
+        IndentGuides::HighlightActive,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            IndentGuides::None => "None",
+            IndentGuides::Lines => "Lines",
+            IndentGuides::HighlightActive => "Highlight active",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -124,6 +228,16 @@ pub struct UiSettings {
 
     /// Enable animations (default: true)
     pub enable_animations: bool,
+
+    /// Reopen the last opened file on launch when no tabs were restored (default: true)
+    pub reopen_last_file: bool,
+
+    /// Reopen windows at the position and size they last had (default: true)
+    pub restore_window_geometry: bool,
+
+    /// Drop recent files that no longer exist on disk (default: false)
+    /// Files on network shares or removable drives are only greyed out
+    pub prune_missing_recent_files: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -174,6 +288,7 @@ impl Default for Settings {
         Self {
             version: 1,
             dark_mode: true,
+            follow_system_theme: false,
             font_size: 14.0,
             font_family: None,
             window: WindowSettings::default(),
@@ -203,6 +318,8 @@ impl Default for UpdateSettings {
         Self {
             auto_check: true,
             check_interval_hours: 24,
+            check_timeout_secs: 5,
+            include_prereleases: false,
         }
     }
 }
@@ -211,8 +328,11 @@ impl Default for PerformanceSettings {
     fn default() -> Self {
         Self {
             cache_size: 100,
+            max_file_size_mb: DEFAULT_MAX_FILE_SIZE_MB,
             max_recent_files: 10,
             navigation_history_size: 100,
+            copy_warn_size_mb: 10,
+            max_search_results: 50_000,
         }
     }
 }
@@ -221,6 +341,20 @@ impl Default for ViewerSettings {
     fn default() -> Self {
         Self {
             syntax_highlighting: true,
+            indent_size: 16.0,
+            indent_guides: IndentGuides::Lines,
+            auto_expand_depth: 0,
+            scroll_margin: SCROLL_MARGIN,
+            auto_reload: false,
+            show_type_badges: false,
+            inline_editing: false,
+            number_format: NumberFormat::Plain,
+            humanize_timestamps: false,
+            timestamp_fields: ["*_at", "*_time", "*timestamp*", "*_ts", "*date*"]
+                .map(String::from)
+                .to_vec(),
+            file_type_overrides: Vec::new(),
+            copy_path_format: PathFormat::Dot,
         }
     }
 }
@@ -233,6 +367,9 @@ impl Default for UiSettings {
             show_status_bar: true,
             show_toolbar: true,
             enable_animations: true,
+            reopen_last_file: true,
+            restore_window_geometry: true,
+            prune_missing_recent_files: false,
         }
     }
 }
@@ -369,6 +506,24 @@ impl Settings {
             });
         }
 
+        if self.performance.max_file_size_mb == 0 {
+            return Err(ThothError::SettingsLoadError {
+                reason: "Invalid max_file_size_mb: 0. Must be at least 1".to_string(),
+            });
+        }
+
+        if self.performance.copy_warn_size_mb == 0 {
+            return Err(ThothError::SettingsLoadError {
+                reason: "Invalid copy_warn_size_mb: 0. Must be at least 1".to_string(),
+            });
+        }
+
+        if self.performance.max_search_results == 0 {
+            return Err(ThothError::SettingsLoadError {
+                reason: "Invalid max_search_results: 0. Must be at least 1".to_string(),
+            });
+        }
+
         if self.performance.max_recent_files == 0 || self.performance.max_recent_files > 100 {
             return Err(ThothError::SettingsLoadError {
                 reason: format!(
@@ -378,6 +533,34 @@ impl Settings {
             });
         }
 
+        // Validate viewer settings
+        if self.viewer.indent_size < 4.0 || self.viewer.indent_size > 64.0 {
+            return Err(ThothError::SettingsLoadError {
+                reason: format!(
+                    "Invalid indent_size: {}. Must be between 4.0 and 64.0",
+                    self.viewer.indent_size
+                ),
+            });
+        }
+
+        if self.viewer.auto_expand_depth > 10 {
+            return Err(ThothError::SettingsLoadError {
+                reason: format!(
+                    "Invalid auto_expand_depth: {}. Must be between 0 and 10",
+                    self.viewer.auto_expand_depth
+                ),
+            });
+        }
+
+        if self.viewer.scroll_margin > 20 {
+            return Err(ThothError::SettingsLoadError {
+                reason: format!(
+                    "Invalid scroll_margin: {}. Must be between 0 and 20",
+                    self.viewer.scroll_margin
+                ),
+            });
+        }
+
         // Validate UI settings
         if self.ui.sidebar_width < 200.0 || self.ui.sidebar_width > 1000.0 {
             return Err(ThothError::SettingsLoadError {
@@ -394,6 +577,11 @@ impl Settings {
                 reason: "Invalid check_interval_hours: 0. Must be at least 1".to_string(),
             });
         }
+        if self.updates.check_timeout_secs == 0 {
+            return Err(ThothError::SettingsLoadError {
+                reason: "Invalid check_timeout_secs: 0. Must be at least 1".to_string(),
+            });
+        }
 
         Ok(())
     }
@@ -478,6 +666,7 @@ mod tests {
         let settings = Settings::default();
         assert_eq!(settings.version, 1);
         assert!(settings.dark_mode);
+        assert!(!settings.follow_system_theme);
         assert_eq!(settings.font_size, 14.0);
         assert_eq!(settings.window.default_width, 1800.0);
         assert_eq!(settings.window.default_height, 1200.0);
@@ -533,6 +722,43 @@ mod tests {
         assert!(settings.validate().is_err());
     }
 
+    #[test]
+    fn test_validation_invalid_max_file_size() {
+        let mut settings = Settings::default();
+        settings.performance.max_file_size_mb = 0;
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_invalid_indent_size() {
+        let mut settings = Settings::default();
+        settings.viewer.indent_size = 2.0;
+        assert!(settings.validate().is_err());
+
+        settings.viewer.indent_size = 100.0;
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_invalid_auto_expand_depth() {
+        let mut settings = Settings::default();
+        settings.viewer.auto_expand_depth = 10;
+        assert!(settings.validate().is_ok());
+
+        settings.viewer.auto_expand_depth = 11;
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_invalid_scroll_margin() {
+        let mut settings = Settings::default();
+        settings.viewer.scroll_margin = 20;
+        assert!(settings.validate().is_ok());
+
+        settings.viewer.scroll_margin = 21;
+        assert!(settings.validate().is_err());
+    }
+
     #[test]
     fn test_validation_invalid_window_size() {
         let mut settings = Settings::default();
@@ -559,13 +785,78 @@ mod tests {
     fn test_performance_settings_defaults() {
         let perf = PerformanceSettings::default();
         assert_eq!(perf.cache_size, 100);
+        assert_eq!(perf.max_file_size_mb, 500);
         assert_eq!(perf.max_recent_files, 10);
+        assert_eq!(perf.copy_warn_size_mb, 10);
+        assert_eq!(perf.max_search_results, 50_000);
     }
 
     #[test]
     fn test_viewer_settings_defaults() {
         let viewer = ViewerSettings::default();
         assert!(viewer.syntax_highlighting);
+        assert_eq!(viewer.indent_size, 16.0);
+        assert_eq!(viewer.indent_guides, IndentGuides::Lines);
+        assert_eq!(viewer.auto_expand_depth, 0);
+        assert_eq!(viewer.scroll_margin, 3);
+        assert!(!viewer.auto_reload);
+        assert!(!viewer.show_type_badges);
+        assert!(!viewer.inline_editing);
+        assert_eq!(viewer.number_format, NumberFormat::Plain);
+        assert!(!viewer.humanize_timestamps);
+        assert!(viewer.timestamp_fields.contains(&"*_at".to_string()));
+        assert!(viewer.file_type_overrides.is_empty());
+        assert_eq!(viewer.copy_path_format, PathFormat::Dot);
+    }
+
+    #[test]
+    fn test_file_type_overrides_roundtrip() {
+        let settings: Settings = toml::from_str(
+            "[[viewer.file_type_overrides]]\npattern = \"*.log\"\nfile_type = \"ndjson\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            settings.viewer.file_type_overrides,
+            [FileTypeOverride {
+                pattern: "*.log".to_string(),
+                file_type: DetectedFileType::Ndjson,
+            }]
+        );
+        let reloaded: Settings = toml::from_str(&toml::to_string(&settings).unwrap()).unwrap();
+        assert_eq!(
+            reloaded.viewer.file_type_overrides,
+            settings.viewer.file_type_overrides
+        );
+    }
+
+    #[test]
+    fn test_copy_path_format_in_config() {
+        let settings: Settings =
+            toml::from_str("[viewer]\ncopy_path_format = \"json_path\"\n").unwrap();
+        assert_eq!(settings.viewer.copy_path_format, PathFormat::JsonPath);
+    }
+
+    #[test]
+    fn test_number_format_in_config() {
+        let settings: Settings = toml::from_str("[viewer]\nnumber_format = \"grouped\"\n").unwrap();
+        assert_eq!(settings.viewer.number_format, NumberFormat::Grouped);
+        assert!(
+            toml::to_string(&settings)
+                .unwrap()
+                .contains("number_format = \"grouped\"")
+        );
+    }
+
+    #[test]
+    fn test_indent_guides_in_config() {
+        let settings: Settings =
+            toml::from_str("[viewer]\nindent_guides = \"highlight_active\"\n").unwrap();
+        assert_eq!(settings.viewer.indent_guides, IndentGuides::HighlightActive);
+        assert!(
+            toml::to_string(&settings)
+                .unwrap()
+                .contains("indent_guides = \"highlight_active\"")
+        );
     }
 
     #[test]
@@ -576,5 +867,8 @@ mod tests {
         assert!(ui.show_status_bar);
         assert!(ui.show_toolbar);
         assert!(ui.enable_animations);
+        assert!(ui.reopen_last_file);
+        assert!(ui.restore_window_geometry);
+        assert!(!ui.prune_missing_recent_files);
     }
 }
@@ -2,7 +2,7 @@ use eframe::egui;
 use serde::{Deserialize, Serialize};
 
 /// Keyboard shortcut configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Shortcut {
     /// The key to press (e.g., "O", "F", "ArrowRight")
     pub key: String,
@@ -57,6 +57,39 @@ impl Shortcut {
         self
     }
 
+    /// Shortcut for a key pressed with `modifiers`, or None for keys that
+    /// can't be bound
+    ///
+    /// Ctrl is folded into `command` except on macOS, where Cmd and Ctrl differ.
+    pub fn from_key_press(key: egui::Key, modifiers: egui::Modifiers) -> Option<Self> {
+        Some(Self {
+            key: key_name(key)?.to_string(),
+            ctrl: modifiers.ctrl && cfg!(target_os = "macos"),
+            alt: modifiers.alt,
+            shift: modifiers.shift,
+            command: modifiers.command,
+        })
+    }
+
+    /// Whether both shortcuts are triggered by the same key chord
+    pub fn same_chord(&self, other: &Shortcut) -> bool {
+        self.key.eq_ignore_ascii_case(&other.key)
+            && self.ctrl == other.ctrl
+            && self.alt == other.alt
+            && self.shift == other.shift
+            && self.command == other.command
+    }
+
+    /// Why this chord can't be bound, if it can't
+    ///
+    /// Keys that type or edit text need Ctrl, Alt or Cmd, otherwise they'd
+    /// fire while typing in the search box.
+    pub fn rejection_reason(&self) -> Option<&'static str> {
+        let has_modifier = self.command || self.ctrl || self.alt;
+        (!has_modifier && is_typing_key(&self.key))
+            .then_some("Letters, digits, punctuation and editing keys need Ctrl, Alt or Cmd")
+    }
+
     /// Convert to egui's KeyboardShortcut
     pub fn to_keyboard_shortcut(&self) -> egui::KeyboardShortcut {
         let mut modifiers = egui::Modifiers::default();
@@ -110,7 +143,7 @@ impl Shortcut {
 }
 
 /// All keyboard shortcuts for the application
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct KeyboardShortcuts {
     // File operations
@@ -131,6 +164,7 @@ pub struct KeyboardShortcuts {
     // Navigation
     pub focus_search: Shortcut,
     pub next_match: Shortcut,
+    pub go_to_record: Shortcut,
     pub prev_match: Shortcut,
     pub nav_back: Shortcut,
     pub nav_forward: Shortcut,
@@ -145,20 +179,33 @@ pub struct KeyboardShortcuts {
     pub collapse_node: Shortcut,
     pub expand_all: Shortcut,
     pub collapse_all: Shortcut,
+    pub expand_to_depth: Shortcut,
+    pub collapse_to_depth: Shortcut,
+    pub toggle_flat_view: Shortcut,
 
     // Clipboard
     pub copy_key: Shortcut,
     pub copy_value: Shortcut,
     pub copy_object: Shortcut,
+    pub copy_minified: Shortcut,
+    // v2: ⌘⇧K, ⌘⇧P now opens the command palette
+    #[serde(rename = "copy_json_path")]
     pub copy_path: Shortcut,
+    pub copy_line_number: Shortcut,
+    pub copy_visible_records: Shortcut,
 
     // Movement
     pub move_up: Shortcut,
     pub move_down: Shortcut,
+    pub extend_selection_up: Shortcut,
+    pub extend_selection_down: Shortcut,
 
     // UI
     pub settings: Shortcut,
     pub toggle_theme: Shortcut,
+    pub toggle_sidebar: Shortcut,
+    pub toggle_presentation: Shortcut,
+    pub command_palette: Shortcut,
 
     // Developer
     pub toggle_profiler: Shortcut,
@@ -181,6 +228,7 @@ impl Default for KeyboardShortcuts {
             // Navigation
             focus_search: Shortcut::new("F").command(),
             next_match: Shortcut::new("G").command(),
+            go_to_record: Shortcut::new("G").command().alt(),
             prev_match: Shortcut::new("G").command().shift(),
             nav_back: Shortcut::new("BracketLeft").command(),
             nav_forward: Shortcut::new("BracketRight").command(),
@@ -195,20 +243,31 @@ impl Default for KeyboardShortcuts {
             collapse_node: Shortcut::new("ArrowLeft"),
             expand_all: Shortcut::new("ArrowRight").command(),
             collapse_all: Shortcut::new("ArrowLeft").command(),
+            expand_to_depth: Shortcut::new("ArrowRight").command().shift(),
+            collapse_to_depth: Shortcut::new("ArrowLeft").command().shift(),
+            toggle_flat_view: Shortcut::new("L").command().alt(),
 
             // Clipboard
             copy_key: Shortcut::new("C").command(),
             copy_value: Shortcut::new("C").command().shift(),
             copy_object: Shortcut::new("C").command().alt(),
-            copy_path: Shortcut::new("P").command().shift(),
+            copy_minified: Shortcut::new("C").command().alt().shift(),
+            copy_path: Shortcut::new("K").command().shift(),
+            copy_line_number: Shortcut::new("L").command().shift(),
+            copy_visible_records: Shortcut::new("A").command().shift(),
 
             // Movement
             move_up: Shortcut::new("ArrowUp"),
             move_down: Shortcut::new("ArrowDown"),
+            extend_selection_up: Shortcut::new("ArrowUp").shift(),
+            extend_selection_down: Shortcut::new("ArrowDown").shift(),
 
             // UI
             settings: Shortcut::new("Comma").command(),
             toggle_theme: Shortcut::new("T").command().shift(),
+            toggle_sidebar: Shortcut::new("B").command(),
+            toggle_presentation: Shortcut::new("M").command().shift(),
+            command_palette: Shortcut::new("P").command().shift(),
 
             // Developer
             toggle_profiler: Shortcut::new("P").command().alt(),
@@ -216,6 +275,131 @@ impl Default for KeyboardShortcuts {
     }
 }
 
+impl KeyboardShortcuts {
+    /// Every action with its shortcut, keyed by field name
+    pub fn bindings(&self) -> [(&'static str, &Shortcut); 39] {
+        [
+            ("open_file", &self.open_file),
+            ("new_window", &self.new_window),
+            ("close_tab", &self.close_tab),
+            ("new_tab", &self.new_tab),
+            ("next_tab", &self.next_tab),
+            ("prev_tab", &self.prev_tab),
+            ("focus_search", &self.focus_search),
+            ("next_match", &self.next_match),
+            ("go_to_record", &self.go_to_record),
+            ("prev_match", &self.prev_match),
+            ("nav_back", &self.nav_back),
+            ("nav_forward", &self.nav_forward),
+            ("escape", &self.escape),
+            ("toggle_bookmark", &self.toggle_bookmark),
+            ("open_bookmarks", &self.open_bookmarks),
+            ("expand_node", &self.expand_node),
+            ("collapse_node", &self.collapse_node),
+            ("expand_all", &self.expand_all),
+            ("collapse_all", &self.collapse_all),
+            ("expand_to_depth", &self.expand_to_depth),
+            ("collapse_to_depth", &self.collapse_to_depth),
+            ("toggle_flat_view", &self.toggle_flat_view),
+            ("copy_key", &self.copy_key),
+            ("copy_value", &self.copy_value),
+            ("copy_object", &self.copy_object),
+            ("copy_minified", &self.copy_minified),
+            ("copy_path", &self.copy_path),
+            ("copy_line_number", &self.copy_line_number),
+            ("copy_visible_records", &self.copy_visible_records),
+            ("move_up", &self.move_up),
+            ("move_down", &self.move_down),
+            ("extend_selection_up", &self.extend_selection_up),
+            ("extend_selection_down", &self.extend_selection_down),
+            ("settings", &self.settings),
+            ("toggle_theme", &self.toggle_theme),
+            ("toggle_sidebar", &self.toggle_sidebar),
+            ("toggle_presentation", &self.toggle_presentation),
+            ("command_palette", &self.command_palette),
+            ("toggle_profiler", &self.toggle_profiler),
+        ]
+    }
+
+    /// Shortcut of the action with field name `action`
+    pub fn get_mut(&mut self, action: &str) -> Option<&mut Shortcut> {
+        Some(match action {
+            "open_file" => &mut self.open_file,
+            "new_window" => &mut self.new_window,
+            "close_tab" => &mut self.close_tab,
+            "new_tab" => &mut self.new_tab,
+            "next_tab" => &mut self.next_tab,
+            "prev_tab" => &mut self.prev_tab,
+            "focus_search" => &mut self.focus_search,
+            "next_match" => &mut self.next_match,
+            "go_to_record" => &mut self.go_to_record,
+            "prev_match" => &mut self.prev_match,
+            "nav_back" => &mut self.nav_back,
+            "nav_forward" => &mut self.nav_forward,
+            "escape" => &mut self.escape,
+            "toggle_bookmark" => &mut self.toggle_bookmark,
+            "open_bookmarks" => &mut self.open_bookmarks,
+            "expand_node" => &mut self.expand_node,
+            "collapse_node" => &mut self.collapse_node,
+            "expand_all" => &mut self.expand_all,
+            "collapse_all" => &mut self.collapse_all,
+            "expand_to_depth" => &mut self.expand_to_depth,
+            "collapse_to_depth" => &mut self.collapse_to_depth,
+            "toggle_flat_view" => &mut self.toggle_flat_view,
+            "copy_key" => &mut self.copy_key,
+            "copy_value" => &mut self.copy_value,
+            "copy_object" => &mut self.copy_object,
+            "copy_minified" => &mut self.copy_minified,
+            "copy_path" => &mut self.copy_path,
+            "copy_line_number" => &mut self.copy_line_number,
+            "copy_visible_records" => &mut self.copy_visible_records,
+            "move_up" => &mut self.move_up,
+            "move_down" => &mut self.move_down,
+            "extend_selection_up" => &mut self.extend_selection_up,
+            "extend_selection_down" => &mut self.extend_selection_down,
+            "settings" => &mut self.settings,
+            "toggle_theme" => &mut self.toggle_theme,
+            "toggle_sidebar" => &mut self.toggle_sidebar,
+            "toggle_presentation" => &mut self.toggle_presentation,
+            "command_palette" => &mut self.command_palette,
+            "toggle_profiler" => &mut self.toggle_profiler,
+            _ => return None,
+        })
+    }
+
+    /// Another action already bound to the chord of `shortcut`
+    pub fn conflicting_action(&self, action: &str, shortcut: &Shortcut) -> Option<&'static str> {
+        self.bindings()
+            .into_iter()
+            .find(|(other, bound)| *other != action && bound.same_chord(shortcut))
+            .map(|(other, _)| other)
+    }
+}
+
+/// Keys that type or edit text when pressed without Ctrl, Alt or Cmd
+fn is_typing_key(key: &str) -> bool {
+    key.len() == 1
+        || matches!(
+            key,
+            "Space"
+                | "Enter"
+                | "Tab"
+                | "Backspace"
+                | "Delete"
+                | "Comma"
+                | "Period"
+                | "Slash"
+                | "Backslash"
+                | "Semicolon"
+                | "Quote"
+                | "Backtick"
+                | "Minus"
+                | "Equal"
+                | "BracketLeft"
+                | "BracketRight"
+        )
+}
+
 /// Parse key string to egui Key
 fn parse_key(key_str: &str) -> egui::Key {
     match key_str {
@@ -312,6 +496,82 @@ fn parse_key(key_str: &str) -> egui::Key {
     }
 }
 
+/// Settings name of an egui Key, the inverse of `parse_key`
+fn key_name(key: egui::Key) -> Option<&'static str> {
+    Some(match key {
+        egui::Key::Escape => "Escape",
+        egui::Key::Enter => "Enter",
+        egui::Key::Tab => "Tab",
+        egui::Key::Space => "Space",
+        egui::Key::Backspace => "Backspace",
+        egui::Key::Delete => "Delete",
+        egui::Key::ArrowLeft => "ArrowLeft",
+        egui::Key::ArrowRight => "ArrowRight",
+        egui::Key::ArrowUp => "ArrowUp",
+        egui::Key::ArrowDown => "ArrowDown",
+        egui::Key::F1 => "F1",
+        egui::Key::F2 => "F2",
+        egui::Key::F3 => "F3",
+        egui::Key::F4 => "F4",
+        egui::Key::F5 => "F5",
+        egui::Key::F6 => "F6",
+        egui::Key::F7 => "F7",
+        egui::Key::F8 => "F8",
+        egui::Key::F9 => "F9",
+        egui::Key::F10 => "F10",
+        egui::Key::F11 => "F11",
+        egui::Key::F12 => "F12",
+        egui::Key::Comma => "Comma",
+        egui::Key::Period => "Period",
+        egui::Key::Slash => "Slash",
+        egui::Key::Backslash => "Backslash",
+        egui::Key::Semicolon => "Semicolon",
+        egui::Key::Quote => "Quote",
+        egui::Key::Backtick => "Backtick",
+        egui::Key::Minus => "Minus",
+        egui::Key::Equals => "Equal",
+        egui::Key::OpenBracket => "BracketLeft",
+        egui::Key::CloseBracket => "BracketRight",
+        egui::Key::Num0 => "0",
+        egui::Key::Num1 => "1",
+        egui::Key::Num2 => "2",
+        egui::Key::Num3 => "3",
+        egui::Key::Num4 => "4",
+        egui::Key::Num5 => "5",
+        egui::Key::Num6 => "6",
+        egui::Key::Num7 => "7",
+        egui::Key::Num8 => "8",
+        egui::Key::Num9 => "9",
+        egui::Key::A => "A",
+        egui::Key::B => "B",
+        egui::Key::C => "C",
+        egui::Key::D => "D",
+        egui::Key::E => "E",
+        egui::Key::F => "F",
+        egui::Key::G => "G",
+        egui::Key::H => "H",
+        egui::Key::I => "I",
+        egui::Key::J => "J",
+        egui::Key::K => "K",
+        egui::Key::L => "L",
+        egui::Key::M => "M",
+        egui::Key::N => "N",
+        egui::Key::O => "O",
+        egui::Key::P => "P",
+        egui::Key::Q => "Q",
+        egui::Key::R => "R",
+        egui::Key::S => "S",
+        egui::Key::T => "T",
+        egui::Key::U => "U",
+        egui::Key::V => "V",
+        egui::Key::W => "W",
+        egui::Key::X => "X",
+        egui::Key::Y => "Y",
+        egui::Key::Z => "Z",
+        _ => return None,
+    })
+}
+
 /// Format key name for display using egui-phosphor icons where applicable
 fn format_key_name(key: &str) -> String {
     match key {
@@ -384,5 +644,94 @@ mod tests {
         assert_eq!(shortcuts.next_tab.key, "ArrowRight");
         assert!(shortcuts.next_tab.command && shortcuts.next_tab.alt);
         assert_eq!(shortcuts.prev_tab.key, "ArrowLeft");
+        assert_eq!(shortcuts.toggle_sidebar.key, "B");
+        assert!(shortcuts.toggle_sidebar.command);
+        assert_eq!(shortcuts.toggle_presentation.key, "M");
+        assert!(shortcuts.toggle_presentation.command && shortcuts.toggle_presentation.shift);
+        assert_eq!(shortcuts.copy_minified.key, "C");
+        assert!(
+            shortcuts.copy_minified.command
+                && shortcuts.copy_minified.alt
+                && shortcuts.copy_minified.shift
+        );
+        assert_eq!(shortcuts.copy_line_number.key, "L");
+        assert!(shortcuts.copy_line_number.command && shortcuts.copy_line_number.shift);
+        assert_eq!(shortcuts.toggle_flat_view.key, "L");
+        assert!(shortcuts.toggle_flat_view.command && shortcuts.toggle_flat_view.alt);
+    }
+
+    #[test]
+    fn test_key_names_round_trip() {
+        for key in [
+            egui::Key::K,
+            egui::Key::Num7,
+            egui::Key::F5,
+            egui::Key::OpenBracket,
+            egui::Key::Equals,
+            egui::Key::ArrowDown,
+        ] {
+            assert_eq!(key_name(key).map(parse_key), Some(key));
+        }
+        assert_eq!(key_name(egui::Key::Home), None);
+    }
+
+    #[test]
+    fn test_shortcut_from_key_press() {
+        let shortcut = Shortcut::from_key_press(egui::Key::K, egui::Modifiers::COMMAND).unwrap();
+        assert!(shortcut.same_chord(&Shortcut::new("K").command()));
+        assert_eq!(shortcut.rejection_reason(), None);
+
+        let shifted =
+            Shortcut::from_key_press(egui::Key::OpenBracket, egui::Modifiers::SHIFT).unwrap();
+        assert_eq!(shifted.key, "BracketLeft");
+        assert!(shifted.shift && !shifted.command);
+    }
+
+    #[test]
+    fn test_typing_keys_need_a_modifier() {
+        assert!(Shortcut::new("J").rejection_reason().is_some());
+        assert!(Shortcut::new("J").shift().rejection_reason().is_some());
+        assert!(Shortcut::new("Space").rejection_reason().is_some());
+        assert!(Shortcut::new("J").alt().rejection_reason().is_none());
+        assert!(Shortcut::new("ArrowUp").rejection_reason().is_none());
+        assert!(Shortcut::new("F2").rejection_reason().is_none());
+    }
+
+    #[test]
+    fn test_default_bindings_are_valid_and_distinct() {
+        let mut shortcuts = KeyboardShortcuts::default();
+        let defaults = KeyboardShortcuts::default();
+        for (action, shortcut) in defaults.bindings() {
+            assert_eq!(shortcut.rejection_reason(), None, "{}", action);
+            assert_eq!(
+                shortcuts.conflicting_action(action, shortcut),
+                None,
+                "{}",
+                action
+            );
+            assert_eq!(shortcuts.get_mut(action).cloned(), Some(shortcut.clone()));
+        }
+        assert!(shortcuts.get_mut("not_an_action").is_none());
+    }
+
+    #[test]
+    fn test_conflicting_action() {
+        let shortcuts = KeyboardShortcuts::default();
+        let copy_key = Shortcut::new("c").command();
+        assert_eq!(
+            shortcuts.conflicting_action("open_file", &copy_key),
+            Some("copy_key")
+        );
+        assert_eq!(shortcuts.conflicting_action("copy_key", &copy_key), None);
+    }
+
+    #[test]
+    fn test_missing_shortcut_falls_back_to_default() {
+        // Settings files written before toggle_sidebar existed still get Cmd+B
+        let shortcuts: KeyboardShortcuts =
+            toml::from_str("[open_file]\nkey = \"P\"\ncommand = true\n").unwrap();
+        assert_eq!(shortcuts.open_file.key, "P");
+        assert_eq!(shortcuts.toggle_sidebar.key, "B");
+        assert!(shortcuts.toggle_sidebar.command);
     }
 }
@@ -1,8 +1,13 @@
-use std::path::PathBuf;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, atomic::AtomicBool, mpsc::Receiver};
+
+use eframe::egui;
 
 use crate::{
-    app::tab_manager::TabManager,
-    components,
+    app::tab_manager::{TabId, TabManager},
+    components::{self, file_viewer::sort::RootSort},
+    constants::{CLIPBOARD_HISTORY_MAX_ENTRY_BYTES, CLIPBOARD_HISTORY_SIZE},
     plugin::{plugin_ui_host::PluginUiHost, render_node::UiOutput},
     search, update,
 };
@@ -45,6 +50,25 @@ pub struct WindowState {
     /// Track previous expanded state to detect sidebar reopening
     pub previous_sidebar_expanded: bool,
 
+    /// Presentation mode: hides toolbar, status bar and sidebar and enlarges
+    /// fonts for screen sharing. Transient — never persisted.
+    pub presentation: bool,
+
+    /// Long-running operations in flight; conflicting toolbar actions are
+    /// disabled while any of them is running.
+    pub busy: BusyState,
+
+    /// Text copied this session, newest first. Transient — never persisted.
+    pub clipboard_history: ClipboardHistory,
+
+    /// Search, selection and scroll of files closed this session, restored
+    /// when they are opened again. Transient — never persisted.
+    pub file_sessions: FileSessions,
+    /// Tab whose search the sidebar's search panel was last synced to
+    pub search_panel_tab: Option<TabId>,
+    /// Search across the recent files, shown in the search panel
+    pub recent_files_search: RecentFilesSearch,
+
     // UI components (global)
     pub sidebar: components::sidebar::Sidebar,
     pub toolbar: components::toolbar::Toolbar,
@@ -60,6 +84,12 @@ impl Default for WindowState {
             sidebar_selected_section: Some(components::sidebar::SidebarSection::RecentFiles),
             previous_sidebar_section: None,
             previous_sidebar_expanded: false,
+            presentation: false,
+            busy: BusyState::default(),
+            clipboard_history: ClipboardHistory::default(),
+            file_sessions: FileSessions::default(),
+            search_panel_tab: None,
+            recent_files_search: RecentFilesSearch::default(),
             sidebar: components::sidebar::Sidebar::default(),
             toolbar: components::toolbar::Toolbar::default(),
             status_bar: components::status_bar::StatusBar::default(),
@@ -73,16 +103,211 @@ impl WindowState {
     pub fn active_tab_mut(&mut self) -> Option<&mut crate::app::tab_manager::TabState> {
         self.tab_manager.active_tab_mut()
     }
+
+    /// Close the active tab, remembering where the user was in its file.
+    /// Returns `true` if the closed tab was empty (showing the welcome screen).
+    pub fn close_active_tab(&mut self) -> bool {
+        if let Some(tab) = self.tab_manager.active_tab_mut()
+            && let Some((path, session)) = tab.file_session()
+        {
+            self.file_sessions.remember(&path, session);
+        }
+        self.tab_manager.close_active_tab()
+    }
 }
 
 // ============================================================================
 // Helper States - Used by WindowState and application logic
 // ============================================================================
 
+/// Background operations that make opening, closing or searching files unsafe.
+///
+/// Each flag is tracked separately so the toolbar can say what it is waiting
+/// for and so actions unrelated to a running operation stay available.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BusyState {
+    /// A file is being opened or a data-source plugin is fetching records
+    pub loading: bool,
+    /// The active tab has a background search running
+    pub searching: bool,
+    /// The active tab is expanding all nodes incrementally
+    pub expanding: bool,
+    /// The active tab is writing records to a new file
+    pub exporting: bool,
+}
+
+impl BusyState {
+    pub fn is_busy(&self) -> bool {
+        self.loading || self.searching || self.expanding || self.exporting
+    }
+
+    /// Short description of the running operation, for tooltips and the
+    /// toolbar spinner. Loading wins over exporting, then searching, then
+    /// expanding.
+    pub fn label(&self) -> Option<&'static str> {
+        if self.loading {
+            Some("Loading…")
+        } else if self.exporting {
+            Some("Exporting…")
+        } else if self.searching {
+            Some("Searching…")
+        } else if self.expanding {
+            Some("Expanding…")
+        } else {
+            None
+        }
+    }
+
+    /// The operation files can't be opened during, if one is running:
+    /// another file loading or records being written
+    pub fn open_blocker(&self) -> Option<&'static str> {
+        BusyState {
+            searching: false,
+            expanding: false,
+            ..*self
+        }
+        .label()
+    }
+
+    /// The operation the active tab can't be closed during, if one is
+    /// running; every tracked operation works on the active tab
+    pub fn close_blocker(&self) -> Option<&'static str> {
+        self.label()
+    }
+
+    /// The operation a new search can't start during, if one is running:
+    /// a search already scanning, the file loading or records being written
+    pub fn search_blocker(&self) -> Option<&'static str> {
+        BusyState {
+            expanding: false,
+            ..*self
+        }
+        .label()
+    }
+}
+
 #[derive(Default)]
 pub struct SearchEngineState {
     pub search: search::Search,
-    pub search_rx: Option<std::sync::mpsc::Receiver<search::Search>>,
+    pub search_rx: Option<Receiver<search::SearchUpdate>>,
+    /// Set to stop the running scan
+    pub search_cancel: Option<Arc<AtomicBool>>,
+    /// Position in `search.results` of the match last navigated to
+    pub current_match_index: Option<usize>,
+    /// Find-and-replace preview and output jobs
+    pub replace: ReplaceState,
+}
+
+/// Find-and-replace for one tab; previews and writes run in the background
+#[derive(Default)]
+pub struct ReplaceState {
+    pub preview: Option<Arc<search::replace::ReplacePreview>>,
+    /// Why the last preview or write failed
+    pub error: Option<String>,
+    pub preview_rx: Option<Receiver<crate::error::Result<search::replace::ReplacePreview>>>,
+    /// Finishes with the output path and the number of records changed
+    pub write_rx: Option<Receiver<crate::error::Result<(PathBuf, usize)>>>,
+}
+
+/// Snapshot of a tab's `ReplaceState`, passed to the sidebar each frame
+#[derive(Debug, Clone, Default)]
+pub struct ReplaceStatus {
+    pub preview: Option<Arc<search::replace::ReplacePreview>>,
+    pub error: Option<String>,
+    pub previewing: bool,
+    pub writing: bool,
+}
+
+impl ReplaceState {
+    pub fn status(&self) -> ReplaceStatus {
+        ReplaceStatus {
+            preview: self.preview.clone(),
+            error: self.error.clone(),
+            previewing: self.preview_rx.is_some(),
+            writing: self.write_rx.is_some(),
+        }
+    }
+}
+
+/// One query run over every recent file in the background; files are opened
+/// one at a time and only their matches are kept
+#[derive(Default)]
+pub struct RecentFilesSearch {
+    /// Query the results are for
+    pub query: String,
+    /// Files with matches, in the order they were searched
+    pub files: Vec<search::FileMatches>,
+    /// Files that could not be searched, with the reason
+    pub skipped: Vec<(PathBuf, String)>,
+    /// Number of files being searched
+    pub total_files: usize,
+    /// 0-based position of the file being scanned
+    pub current_file: usize,
+    /// Why the search could not run, e.g. an invalid JSONPath
+    pub error: Option<String>,
+    pub rx: Option<Receiver<search::MultiFileUpdate>>,
+    /// Set to stop the running search
+    pub cancel: Option<Arc<AtomicBool>>,
+}
+
+impl RecentFilesSearch {
+    pub fn is_running(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    /// Matching records across all files
+    pub fn match_count(&self) -> usize {
+        self.files.iter().map(|file| file.hits.len()).sum()
+    }
+}
+
+impl SearchEngineState {
+    /// Move to the next match, wrapping to the first after the last
+    /// Returns the record index of the new current match
+    pub fn next_match(&mut self) -> Option<usize> {
+        self.step_match(true)
+    }
+
+    /// Move to the previous match, wrapping to the last before the first
+    /// Returns the record index of the new current match
+    pub fn prev_match(&mut self) -> Option<usize> {
+        self.step_match(false)
+    }
+
+    fn step_match(&mut self, forward: bool) -> Option<usize> {
+        let len = self.search.results.len();
+        if len == 0 || self.search.query.is_empty() {
+            self.current_match_index = None;
+            return None;
+        }
+
+        let next = match self.current_match_index.filter(|&i| i < len) {
+            Some(i) if forward => (i + 1) % len,
+            Some(i) => (i + len - 1) % len,
+            None if forward => 0,
+            None => len - 1,
+        };
+        self.current_match_index = Some(next);
+        self.search.results.get(next).map(|hit| hit.record_index)
+    }
+
+    /// Point the match cursor at `record_index` (e.g. when picked from the sidebar)
+    pub fn set_current_match_record(&mut self, record_index: usize) {
+        self.current_match_index = self
+            .search
+            .results
+            .hits()
+            .iter()
+            .position(|hit| hit.record_index == record_index);
+    }
+
+    /// Current match as a 1-based `(position, total)` pair, for display
+    pub fn match_position(&self) -> Option<(usize, usize)> {
+        let total = self.search.results.len();
+        self.current_match_index
+            .filter(|&i| i < total)
+            .map(|i| (i + 1, total))
+    }
 }
 
 /// Navigation history for back/forward navigation through viewed JSON paths
@@ -211,6 +436,92 @@ impl Default for NavigationHistory {
     }
 }
 
+/// Recently copied text, newest first, so an earlier copy can be copied again
+#[derive(Debug, Clone, Default)]
+pub struct ClipboardHistory {
+    entries: VecDeque<String>,
+}
+
+impl ClipboardHistory {
+    /// Remember a copy; copying the same text again moves it back to the top
+    /// Empty and oversized copies aren't kept
+    pub fn push(&mut self, text: &str) {
+        if text.is_empty() || text.len() > CLIPBOARD_HISTORY_MAX_ENTRY_BYTES {
+            return;
+        }
+        if let Some(pos) = self.entries.iter().position(|entry| entry == text) {
+            let entry = self.entries.remove(pos).unwrap_or_default();
+            self.entries.push_front(entry);
+            return;
+        }
+        self.entries.push_front(text.to_string());
+        self.entries.truncate(CLIPBOARD_HISTORY_SIZE);
+    }
+
+    /// Copies, newest first
+    pub fn entries(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Where the user left off in a file: the search that was run, the record
+/// order, the selected row and how far the tree was scrolled
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileSession {
+    pub query: String,
+    pub query_mode: search::QueryMode,
+    pub match_case: bool,
+    pub scope_path: Option<String>,
+    pub sort: Option<RootSort>,
+    pub selected: Option<String>,
+    pub scroll_offset: egui::Vec2,
+}
+
+/// [`FileSession`]s of files closed this session, keyed by canonical path so
+/// a file reopened through a symlink or relative path finds its session
+#[derive(Debug, Clone, Default)]
+pub struct FileSessions {
+    sessions: HashMap<PathBuf, FileSession>,
+}
+
+impl FileSessions {
+    /// Remember `session` for `path`, replacing any earlier one
+    pub fn remember(&mut self, path: &Path, session: FileSession) {
+        self.sessions
+            .insert(crate::platform::canonical_path(path), session);
+    }
+
+    /// Take the session remembered for `path`, if any
+    pub fn take(&mut self, path: &Path) -> Option<FileSession> {
+        self.sessions.remove(&crate::platform::canonical_path(path))
+    }
+
+    /// Drop the session remembered for `path`
+    pub fn forget(&mut self, path: &Path) {
+        self.sessions.remove(&crate::platform::canonical_path(path));
+    }
+
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+}
+
 #[derive(Default)]
 pub struct ApplicationUpdateState {
     pub update_manager: update::UpdateManager,
@@ -201,3 +201,255 @@ mod navigation_history_tests {
         assert!(history.can_go_forward());
     }
 }
+
+#[cfg(test)]
+mod busy_state_tests {
+    use crate::state::BusyState;
+
+    #[test]
+    fn test_idle_by_default() {
+        let busy = BusyState::default();
+        assert!(!busy.is_busy());
+        assert_eq!(busy.label(), None);
+    }
+
+    #[test]
+    fn test_any_operation_marks_busy() {
+        for busy in [
+            BusyState {
+                loading: true,
+                ..BusyState::default()
+            },
+            BusyState {
+                searching: true,
+                ..BusyState::default()
+            },
+            BusyState {
+                expanding: true,
+                ..BusyState::default()
+            },
+            BusyState {
+                exporting: true,
+                ..BusyState::default()
+            },
+        ] {
+            assert!(busy.is_busy());
+            assert!(busy.label().is_some());
+        }
+    }
+
+    #[test]
+    fn test_label_prefers_loading_then_searching() {
+        let busy = BusyState {
+            loading: true,
+            searching: true,
+            expanding: true,
+            exporting: true,
+        };
+        assert_eq!(busy.label(), Some("Loading…"));
+
+        let busy = BusyState {
+            searching: true,
+            expanding: true,
+            ..BusyState::default()
+        };
+        assert_eq!(busy.label(), Some("Searching…"));
+    }
+
+    #[test]
+    fn test_actions_wait_only_for_conflicting_operations() {
+        let searching = BusyState {
+            searching: true,
+            ..BusyState::default()
+        };
+        assert_eq!(searching.open_blocker(), None);
+        assert_eq!(searching.close_blocker(), Some("Searching…"));
+        assert_eq!(searching.search_blocker(), Some("Searching…"));
+
+        let expanding = BusyState {
+            expanding: true,
+            ..BusyState::default()
+        };
+        assert_eq!(expanding.open_blocker(), None);
+        assert_eq!(expanding.search_blocker(), None);
+        assert_eq!(expanding.close_blocker(), Some("Expanding…"));
+
+        for busy in [
+            BusyState {
+                loading: true,
+                ..BusyState::default()
+            },
+            BusyState {
+                exporting: true,
+                ..BusyState::default()
+            },
+        ] {
+            assert!(busy.open_blocker().is_some());
+            assert!(busy.close_blocker().is_some());
+            assert!(busy.search_blocker().is_some());
+        }
+    }
+}
+
+#[cfg(test)]
+mod search_match_tests {
+    use crate::search::results::{SearchHit, SearchResults};
+    use crate::state::SearchEngineState;
+
+    fn state_with_matches(records: &[usize]) -> SearchEngineState {
+        let hits = records
+            .iter()
+            .map(|&record_index| SearchHit {
+                record_index,
+                ..SearchHit::default()
+            })
+            .collect();
+        let mut state = SearchEngineState::default();
+        state.search.query = "needle".to_string();
+        state.search.results = SearchResults::new(hits, 100);
+        state
+    }
+
+    #[test]
+    fn test_next_match_wraps_around() {
+        let mut state = state_with_matches(&[4, 9, 30]);
+        assert_eq!(state.match_position(), None);
+
+        assert_eq!(state.next_match(), Some(4));
+        assert_eq!(state.match_position(), Some((1, 3)));
+        assert_eq!(state.next_match(), Some(9));
+        assert_eq!(state.next_match(), Some(30));
+        assert_eq!(state.match_position(), Some((3, 3)));
+        assert_eq!(state.next_match(), Some(4));
+    }
+
+    #[test]
+    fn test_prev_match_wraps_around() {
+        let mut state = state_with_matches(&[4, 9, 30]);
+
+        assert_eq!(state.prev_match(), Some(30));
+        assert_eq!(state.prev_match(), Some(9));
+        assert_eq!(state.prev_match(), Some(4));
+        assert_eq!(state.prev_match(), Some(30));
+        assert_eq!(state.match_position(), Some((3, 3)));
+    }
+
+    #[test]
+    fn test_navigation_continues_from_picked_match() {
+        let mut state = state_with_matches(&[4, 9, 30]);
+        state.set_current_match_record(9);
+        assert_eq!(state.match_position(), Some((2, 3)));
+        assert_eq!(state.next_match(), Some(30));
+    }
+
+    #[test]
+    fn test_no_matches_without_active_search() {
+        let mut state = SearchEngineState::default();
+        assert_eq!(state.next_match(), None);
+
+        let mut state = state_with_matches(&[1, 2]);
+        state.search.query.clear();
+        assert_eq!(state.next_match(), None);
+        assert_eq!(state.match_position(), None);
+    }
+}
+
+#[cfg(test)]
+mod clipboard_history_tests {
+    use crate::constants::{CLIPBOARD_HISTORY_MAX_ENTRY_BYTES, CLIPBOARD_HISTORY_SIZE};
+    use crate::state::ClipboardHistory;
+
+    #[test]
+    fn test_newest_copy_comes_first() {
+        let mut history = ClipboardHistory::default();
+        history.push("id");
+        history.push("\"alice\"");
+        history.push("0.user.name");
+        assert_eq!(
+            history.entries().collect::<Vec<_>>(),
+            ["0.user.name", "\"alice\"", "id"]
+        );
+    }
+
+    #[test]
+    fn test_copying_again_moves_entry_to_top() {
+        let mut history = ClipboardHistory::default();
+        history.push("a");
+        history.push("b");
+        history.push("a");
+        assert_eq!(history.entries().collect::<Vec<_>>(), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut history = ClipboardHistory::default();
+        for i in 0..CLIPBOARD_HISTORY_SIZE + 5 {
+            history.push(&i.to_string());
+        }
+        assert_eq!(history.len(), CLIPBOARD_HISTORY_SIZE);
+        let newest = (CLIPBOARD_HISTORY_SIZE + 4).to_string();
+        assert_eq!(history.entries().next(), Some(newest.as_str()));
+        assert_eq!(history.entries().last(), Some("5"));
+    }
+
+    #[test]
+    fn test_empty_and_oversized_copies_are_skipped() {
+        let mut history = ClipboardHistory::default();
+        history.push("");
+        history.push(&"x".repeat(CLIPBOARD_HISTORY_MAX_ENTRY_BYTES + 1));
+        assert!(history.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod file_sessions_tests {
+    use crate::state::{FileSession, FileSessions};
+    use tempfile::TempDir;
+
+    fn session(query: &str) -> FileSession {
+        FileSession {
+            query: query.to_string(),
+            selected: Some("3.user".to_string()),
+            ..FileSession::default()
+        }
+    }
+
+    #[test]
+    fn test_session_is_taken_once() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("data.json");
+        std::fs::write(&path, "{}").unwrap();
+
+        let mut sessions = FileSessions::default();
+        sessions.remember(&path, session("alice"));
+        sessions.remember(&path, session("bob"));
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions.take(&path), Some(session("bob")));
+        assert_eq!(sessions.take(&path), None);
+    }
+
+    #[test]
+    fn test_paths_are_compared_canonically() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        let path = dir.path().join("data.json");
+        std::fs::write(&path, "{}").unwrap();
+        let roundabout = dir.path().join("sub").join("..").join("data.json");
+
+        let mut sessions = FileSessions::default();
+        sessions.remember(&roundabout, session("alice"));
+        assert_eq!(sessions.take(&path), Some(session("alice")));
+    }
+
+    #[test]
+    fn test_forget_drops_session() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("data.json");
+        std::fs::write(&path, "{}").unwrap();
+
+        let mut sessions = FileSessions::default();
+        sessions.remember(&path, session("alice"));
+        sessions.forget(&path);
+        assert!(sessions.is_empty());
+    }
+}
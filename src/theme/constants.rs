@@ -6,6 +6,7 @@ pub const SPACING_MEDIUM: f32 = 2.0 * GRID_UNIT; // 8px
 pub const SPACING_LARGE: f32 = 4.0 * GRID_UNIT; // 16px
 pub const TREE_INDENT: f32 = SPACING_LARGE;
 pub const ROW_HEIGHT: f32 = 22.0;
+pub const ROW_TEXT_PADDING: f32 = 8.0; // space around the text of a tree row (22px at the default 14pt font)
 
 pub const ROW_PADDING_H: f32 = 24.0; // outer left/right margin for section header and group title
 pub const ROW_INNER_H: f32 = 16.0; // horizontal padding INSIDE card rows (matches design 16px)
@@ -49,6 +50,7 @@ impl Theme {
             sidebar_hover: "#6c708633".into(),
             sidebar_header: "#9399b2".into(),
             indent_guide: "#45475a".into(),
+            search_highlight: String::new(),
         }
     }
 
@@ -84,6 +86,7 @@ impl Theme {
             sidebar_hover: "#9ca0b033".into(),
             sidebar_header: "#7c7f93".into(),
             indent_guide: "#bcc0cc".into(),
+            search_highlight: String::new(),
         }
     }
 
@@ -120,6 +123,7 @@ impl Theme {
             sidebar_hover: "#62688033".into(),
             sidebar_header: "#737994".into(), // Overlay2
             indent_guide: "#51576d".into(),   // Surface1
+            search_highlight: String::new(),
         }
     }
 
@@ -156,6 +160,7 @@ impl Theme {
             sidebar_hover: "#6e738d33".into(),
             sidebar_header: "#939ab7".into(),
             indent_guide: "#494d64".into(),
+            search_highlight: String::new(),
         }
     }
 
@@ -185,6 +190,7 @@ impl Theme {
             sidebar_hover: "#44475a33".into(),
             sidebar_header: "#6272a4".into(),
             indent_guide: "#44475a".into(),
+            search_highlight: String::new(),
         }
     }
 
@@ -214,6 +220,7 @@ impl Theme {
             sidebar_hover: "#3b425233".into(),
             sidebar_header: "#7b88a1".into(),
             indent_guide: "#3b4252".into(),
+            search_highlight: String::new(),
         }
     }
 
@@ -243,6 +250,7 @@ impl Theme {
             sidebar_hover: "#3c383633".into(),
             sidebar_header: "#928374".into(),
             indent_guide: "#3c3836".into(),
+            search_highlight: String::new(),
         }
     }
 
@@ -272,6 +280,7 @@ impl Theme {
             sidebar_hover: "#292e4233".into(),
             sidebar_header: "#565f89".into(),
             indent_guide: "#292e42".into(),
+            search_highlight: String::new(),
         }
     }
 
@@ -301,6 +310,7 @@ impl Theme {
             sidebar_hover: "#26233a33".into(),
             sidebar_header: "#6e6a86".into(),
             indent_guide: "#26233a".into(),
+            search_highlight: String::new(),
         }
     }
 
@@ -330,6 +340,7 @@ impl Theme {
             sidebar_hover: "#d0d7de33".into(),
             sidebar_header: "#656d76".into(),
             indent_guide: "#d0d7de".into(),
+            search_highlight: String::new(),
         }
     }
 
@@ -359,6 +370,7 @@ impl Theme {
             sidebar_hover: "#07364233".into(),
             sidebar_header: "#657b83".into(),
             indent_guide: "#073642".into(),
+            search_highlight: String::new(),
         }
     }
 
@@ -388,6 +400,101 @@ impl Theme {
             sidebar_hover: "#93a1a133".into(),
             sidebar_header: "#839496".into(),
             indent_guide: "#93a1a1".into(),
+            search_highlight: String::new(),
+        }
+    }
+
+    /// Maximum-contrast dark theme: white text on black with saturated accents.
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "High Contrast".into(),
+            dark_mode: true,
+            bg: "#000000".into(),
+            bg_panel: "#0a0a0a".into(),
+            bg_sunken: "#000000".into(),
+            surface: "#1a1a1a".into(),
+            surface_raised: "#333333".into(),
+            surface_active: "#4d4d4d".into(),
+            fg: "#ffffff".into(),
+            fg_muted: "#c8c8c8".into(),
+            syntax_key: "#6cb6ff".into(),
+            syntax_string: "#ffd75f".into(),
+            syntax_number: "#ff9e3d".into(),
+            syntax_bool: "#ff87ff".into(),
+            syntax_punctuation: "#e0e0e0".into(),
+            success: "#3fffa2".into(),
+            warning: "#ffd700".into(),
+            error: "#ff6b6b".into(),
+            info: "#6cb6ff".into(),
+            accent: "#ffd700".into(),
+            accent_secondary: "#00e5ff".into(),
+            sidebar_hover: "#ffffff33".into(),
+            sidebar_header: "#ffffff".into(),
+            indent_guide: "#808080".into(),
+            search_highlight: "#ffd70066".into(),
+        }
+    }
+
+    /// Okabe–Ito based dark theme with no red/green pairs: status colours and
+    /// highlights use blue for "ok" and orange for "problem".
+    pub fn colorblind_dark() -> Self {
+        Self {
+            name: "Colorblind Safe Dark".into(),
+            dark_mode: true,
+            bg: "#1b1d23".into(),
+            bg_panel: "#15171c".into(),
+            bg_sunken: "#0f1115".into(),
+            surface: "#2a2d35".into(),
+            surface_raised: "#3a3e48".into(),
+            surface_active: "#4b505c".into(),
+            fg: "#e8e8e8".into(),
+            fg_muted: "#9da3ae".into(),
+            syntax_key: "#56b4e9".into(),
+            syntax_string: "#e69f00".into(),
+            syntax_number: "#f0e442".into(),
+            syntax_bool: "#cc79a7".into(),
+            syntax_punctuation: "#9da3ae".into(),
+            success: "#56b4e9".into(),
+            warning: "#f0e442".into(),
+            error: "#e69f00".into(),
+            info: "#9ecae1".into(),
+            accent: "#56b4e9".into(),
+            accent_secondary: "#e69f00".into(),
+            sidebar_hover: "#9da3ae33".into(),
+            sidebar_header: "#9da3ae".into(),
+            indent_guide: "#3a3e48".into(),
+            search_highlight: "#e69f0066".into(),
+        }
+    }
+
+    /// Light counterpart of [`Theme::colorblind_dark`].
+    pub fn colorblind_light() -> Self {
+        Self {
+            name: "Colorblind Safe Light".into(),
+            dark_mode: false,
+            bg: "#ffffff".into(),
+            bg_panel: "#f3f4f6".into(),
+            bg_sunken: "#e8eaee".into(),
+            surface: "#e1e4e8".into(),
+            surface_raised: "#cfd4da".into(),
+            surface_active: "#bcc2ca".into(),
+            fg: "#1f2328".into(),
+            fg_muted: "#59636e".into(),
+            syntax_key: "#0072b2".into(),
+            syntax_string: "#d55e00".into(),
+            syntax_number: "#8a5a00".into(),
+            syntax_bool: "#a8467f".into(),
+            syntax_punctuation: "#59636e".into(),
+            success: "#0072b2".into(),
+            warning: "#8a5a00".into(),
+            error: "#d55e00".into(),
+            info: "#2f80c0".into(),
+            accent: "#0072b2".into(),
+            accent_secondary: "#d55e00".into(),
+            sidebar_hover: "#59636e22".into(),
+            sidebar_header: "#59636e".into(),
+            indent_guide: "#d0d7de".into(),
+            search_highlight: "#e69f0055".into(),
         }
     }
 }
@@ -5,8 +5,10 @@ use eframe::egui::{self, Color32};
 use egui_code_editor::ColorTheme;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::{fs, path::Path};
 
 use crate::{
+    error::{Result, ThothError},
     plugin::theme_plugin::{get_plugin_theme_by_name, get_plugin_theme_catalog},
     settings::Settings,
 };
@@ -86,6 +88,10 @@ pub struct Theme {
     pub sidebar_header: String,
     /// Tree-view indent guide lines.
     pub indent_guide: String,
+    /// Search-match highlight background (may include alpha); empty derives
+    /// it from `accent`.
+    #[serde(default)]
+    pub search_highlight: String,
 }
 
 impl Default for Theme {
@@ -103,6 +109,28 @@ impl Theme {
         }
     }
 
+    /// This theme if it is already `dark` (or light), otherwise a theme of the
+    /// other mode from the same catalog family, falling back to Catppuccin
+    /// Mocha / Latte for families (and custom themes) with only one mode
+    pub fn for_mode(&self, dark: bool) -> Self {
+        if self.dark_mode == dark {
+            return self.clone();
+        }
+        let catalog = Self::catalog();
+        catalog
+            .iter()
+            .find(|(name, _, _)| *name == self.name)
+            .and_then(|(_, _, family)| {
+                catalog
+                    .iter()
+                    .find(|(_, is_dark, f)| *is_dark == dark && f == family)
+            })
+            .map_or_else(
+                || Self::for_dark_mode(dark),
+                |(name, _, _)| Self::from_name(name),
+            )
+    }
+
     /// All themes as `(display_name, is_dark, family)` tuples.
     pub fn catalog() -> Vec<(String, bool, String)> {
         let theme_plugins_catalog = get_plugin_theme_catalog();
@@ -140,6 +168,21 @@ impl Theme {
                     false,
                     "Solarized".to_string(),
                 ),
+                (
+                    "High Contrast".to_string(),
+                    true,
+                    "Accessibility".to_string(),
+                ),
+                (
+                    "Colorblind Safe Dark".to_string(),
+                    true,
+                    "Accessibility".to_string(),
+                ),
+                (
+                    "Colorblind Safe Light".to_string(),
+                    false,
+                    "Accessibility".to_string(),
+                ),
             ],
             theme_plugins_catalog,
         ]
@@ -160,6 +203,9 @@ impl Theme {
             "GitHub Light" => Self::github_light(),
             "Solarized Dark" => Self::solarized_dark(),
             "Solarized Light" => Self::solarized_light(),
+            "High Contrast" => Self::high_contrast(),
+            "Colorblind Safe Dark" => Self::colorblind_dark(),
+            "Colorblind Safe Light" => Self::colorblind_light(),
             _ => get_plugin_theme_by_name(name).unwrap_or_default(),
         }
     }
@@ -195,6 +241,12 @@ impl Theme {
             sidebar_hover: Self::parse_color_with_alpha(&self.sidebar_hover),
             sidebar_header: Self::parse_color(&self.sidebar_header),
             indent_guide: Self::parse_color(&self.indent_guide),
+            search_highlight: if self.search_highlight.is_empty() {
+                let accent = Self::parse_color(&self.accent);
+                Color32::from_rgba_unmultiplied(accent.r(), accent.g(), accent.b(), 60)
+            } else {
+                Self::parse_color_with_alpha(&self.search_highlight)
+            },
         }
     }
 
@@ -223,6 +275,106 @@ impl Theme {
     }
 }
 
+// ── Theme files ───────────────────────────────────────────────────────────────
+
+/// A theme read from a standalone file by [`Theme::from_file`].
+#[derive(Debug, Clone)]
+pub struct LoadedTheme {
+    pub theme: Theme,
+    /// Colour fields the file left out, filled from the default theme.
+    pub defaulted: Vec<String>,
+}
+
+impl Theme {
+    /// Load a theme from a standalone `.toml` or `.json` file.
+    ///
+    /// Colour fields the file leaves out are filled from the default theme for
+    /// its `dark_mode` and reported in [`LoadedTheme::defaulted`]; a colour that
+    /// isn't a `#rrggbb` (or `#rrggbbaa`) string is an error. Without a `name`
+    /// the theme is named after the file.
+    pub fn from_file(path: &Path) -> Result<LoadedTheme> {
+        let read_error = |reason: String| ThothError::FileReadError {
+            path: path.to_path_buf(),
+            reason,
+        };
+
+        let text = fs::read_to_string(path)
+            .map_err(|e| read_error(format!("Failed to read theme: {}", e)))?;
+        let fields: serde_json::Map<String, Value> = if is_json_path(path) {
+            serde_json::from_str(&text)
+                .map_err(|e| read_error(format!("Invalid theme JSON: {}", e)))?
+        } else {
+            toml::from_str(&text).map_err(|e| read_error(format!("Invalid theme TOML: {}", e)))?
+        };
+
+        let dark_mode = match fields.get("dark_mode") {
+            None => true,
+            Some(Value::Bool(dark_mode)) => *dark_mode,
+            Some(_) => return Err(read_error("`dark_mode` must be true or false".to_string())),
+        };
+        let Ok(Value::Object(mut merged)) = serde_json::to_value(Self::for_dark_mode(dark_mode))
+        else {
+            return Err(read_error("Failed to build the default theme".to_string()));
+        };
+
+        let mut defaulted = Vec::new();
+        for (key, value) in merged.iter_mut() {
+            match (key.as_str(), fields.get(key)) {
+                ("name" | "dark_mode", Some(field)) => *value = field.clone(),
+                ("name" | "dark_mode", None) => {}
+                (_, Some(Value::String(hex))) if is_hex_color(hex) => {
+                    *value = Value::from(hex.as_str())
+                }
+                // An empty search highlight means "derive it from the accent"
+                ("search_highlight", Some(Value::String(hex))) if hex.is_empty() => {
+                    *value = Value::from("")
+                }
+                (_, Some(_)) => {
+                    return Err(read_error(format!("`{}` is not a #rrggbb colour", key)));
+                }
+                (_, None) => defaulted.push(key.clone()),
+            }
+        }
+
+        let mut theme: Theme = serde_json::from_value(Value::Object(merged))
+            .map_err(|e| read_error(format!("Invalid theme: {}", e)))?;
+        if theme.name.trim().is_empty() || !fields.contains_key("name") {
+            theme.name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "Custom".to_string());
+        }
+
+        Ok(LoadedTheme { theme, defaulted })
+    }
+
+    /// Write this theme to a `.toml` or `.json` file that [`Theme::from_file`] reads back.
+    pub fn to_file(&self, path: &Path) -> Result<()> {
+        let write_error = |reason: String| ThothError::FileWriteError {
+            path: path.to_path_buf(),
+            reason,
+        };
+
+        let text = if is_json_path(path) {
+            serde_json::to_string_pretty(self).map_err(|e| write_error(e.to_string()))?
+        } else {
+            toml::to_string_pretty(self).map_err(|e| write_error(e.to_string()))?
+        };
+        fs::write(path, text).map_err(|e| write_error(format!("Failed to write theme: {}", e)))
+    }
+}
+
+fn is_json_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+/// `#rrggbb`, or `#rrggbbaa` for slots that carry alpha.
+fn is_hex_color(s: &str) -> bool {
+    s.strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
 // ── ThemeColors (parsed Color32 form) ────────────────────────────────────────
 
 // The canonical parsed palette type lives in the plugin SDK so that exactly one
@@ -446,7 +598,11 @@ pub fn apply_theme(ctx: &egui::Context, settings: &Settings) {
 
     ctx.set_visuals(build_visuals(is_dark, &colors));
 
-    let system_theme = if is_dark {
+    // Pinning the window's appearance would hide OS changes from
+    // `RawInput::system_theme`, so leave it to the OS when following it
+    let system_theme = if settings.follow_system_theme {
+        egui::viewport::SystemTheme::SystemDefault
+    } else if is_dark {
         egui::viewport::SystemTheme::Dark
     } else {
         egui::viewport::SystemTheme::Light
@@ -563,6 +719,22 @@ pub fn row_fill(i: usize, ui: &egui::Ui) -> Color32 {
     }
 }
 
+/// Height of a tree row for text of `font_size`
+pub fn row_height_for_font(font_size: f32) -> f32 {
+    font_size + ROW_TEXT_PADDING
+}
+
+/// Height of a tree row at the body font size currently set on `ui`
+///
+/// Follows the Appearance font size (and presentation mode scaling), so rows
+/// don't get cramped at large sizes.
+pub fn tree_row_height(ui: &egui::Ui) -> f32 {
+    ui.style()
+        .text_styles
+        .get(&egui::TextStyle::Body)
+        .map_or(ROW_HEIGHT, |font| row_height_for_font(font.size))
+}
+
 pub fn selected_row_bg(ui: &egui::Ui) -> Color32 {
     ui.visuals().widgets.active.bg_fill
 }
@@ -695,3 +867,104 @@ impl BgColorOptions {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_mode_stays_in_family() {
+        let mocha = Theme::from_name("Catppuccin Mocha");
+        assert_eq!(mocha.for_mode(true).name, "Catppuccin Mocha");
+        assert_eq!(mocha.for_mode(false).name, "Catppuccin Latte");
+        assert_eq!(
+            Theme::from_name("Solarized Light").for_mode(true).name,
+            "Solarized Dark"
+        );
+        assert_eq!(
+            Theme::from_name("Tokyo Night").for_mode(false).name,
+            "GitHub Light"
+        );
+        // Families without the other mode fall back to Catppuccin
+        assert_eq!(
+            Theme::from_name("Dracula").for_mode(false).name,
+            "Catppuccin Latte"
+        );
+    }
+
+    #[test]
+    fn test_row_height_follows_font_size() {
+        // The default 14pt font keeps the original row height
+        assert_eq!(row_height_for_font(14.0), ROW_HEIGHT);
+        assert!(row_height_for_font(24.0) > ROW_HEIGHT);
+        assert!(row_height_for_font(10.0) < ROW_HEIGHT);
+    }
+
+    #[test]
+    fn test_theme_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        for file_name in ["theme.toml", "theme.json"] {
+            let path = dir.path().join(file_name);
+            let theme = Theme::nord();
+            theme.to_file(&path).unwrap();
+
+            let loaded = Theme::from_file(&path).unwrap();
+            assert_eq!(loaded.theme, theme);
+            assert!(loaded.defaulted.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_theme_file_fills_missing_colors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("paper.toml");
+        fs::write(
+            &path,
+            "dark_mode = false\nbg = \"#fafafa\"\naccent = \"#AA3366\"\n",
+        )
+        .unwrap();
+
+        let loaded = Theme::from_file(&path).unwrap();
+        assert_eq!(loaded.theme.name, "paper");
+        assert_eq!(loaded.theme.bg, "#fafafa");
+        assert_eq!(loaded.theme.accent, "#AA3366");
+        assert_eq!(loaded.theme.fg, Theme::latte().fg);
+        assert!(loaded.defaulted.contains(&"fg".to_string()));
+        assert!(!loaded.defaulted.contains(&"bg".to_string()));
+        assert!(!loaded.defaulted.contains(&"name".to_string()));
+    }
+
+    #[test]
+    fn test_accessibility_presets_set_search_highlight() {
+        for name in [
+            "High Contrast",
+            "Colorblind Safe Dark",
+            "Colorblind Safe Light",
+        ] {
+            let theme = Theme::from_name(name);
+            assert_eq!(theme.name, name);
+            assert!(Theme::catalog().iter().any(|(n, _, _)| n == name));
+            assert!(is_hex_color(&theme.search_highlight));
+        }
+
+        // Themes without an explicit highlight derive it from the accent
+        let colors = Theme::mocha().colors();
+        assert_eq!(
+            colors.search_highlight,
+            Color32::from_rgba_unmultiplied(
+                colors.accent.r(),
+                colors.accent.g(),
+                colors.accent.b(),
+                60
+            )
+        );
+    }
+
+    #[test]
+    fn test_theme_file_rejects_bad_color() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad.json");
+        fs::write(&path, r#"{"bg": "blue"}"#).unwrap();
+        assert!(Theme::from_file(&path).is_err());
+    }
+}
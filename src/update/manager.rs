@@ -3,6 +3,7 @@ use crate::error::{Result, ThothError};
 use crate::platform::{get_extractor_for_file, get_fs_ops};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::thread;
+use std::time::Duration;
 
 const GITHUB_REPO: &str = "anitnilay20/thoth";
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -36,10 +37,12 @@ impl UpdateManager {
         &mut self.rx
     }
 
-    pub fn check_for_updates(&self) {
+    /// Fetch the release list on a background thread, failing once `timeout`
+    /// passes without an answer
+    pub fn check_for_updates(&self, timeout: Duration) {
         let tx = self.tx.clone();
         thread::spawn(move || {
-            let result = Self::fetch_releases();
+            let result = Self::fetch_releases(timeout);
             let msg = match result {
                 Ok(releases) => UpdateMessage::UpdateCheckComplete(Ok(releases)),
                 Err(e) => UpdateMessage::UpdateCheckComplete(Err(e)),
@@ -48,19 +51,27 @@ impl UpdateManager {
         });
     }
 
-    fn fetch_releases() -> Result<Vec<ReleaseInfo>> {
+    fn fetch_releases(timeout: Duration) -> Result<Vec<ReleaseInfo>> {
         let url = format!("https://api.github.com/repos/{}/releases", GITHUB_REPO);
 
         let client = reqwest::blocking::Client::builder()
             .user_agent("thoth-updater")
+            .connect_timeout(timeout)
+            .timeout(timeout)
             .build()?;
+        let timed_out = || ThothError::UpdateCheckError {
+            reason: format!("GitHub didn't respond within {} seconds", timeout.as_secs()),
+        };
 
-        let response = client
-            .get(&url)
-            .send()
-            .map_err(|e| ThothError::UpdateCheckError {
-                reason: format!("Failed to fetch releases from GitHub: {}", e),
-            })?;
+        let response = client.get(&url).send().map_err(|e| {
+            if e.is_timeout() {
+                timed_out()
+            } else {
+                ThothError::UpdateCheckError {
+                    reason: format!("Failed to fetch releases from GitHub: {}", e),
+                }
+            }
+        })?;
 
         if !response.status().is_success() {
             return Err(ThothError::UpdateCheckError {
@@ -68,68 +79,57 @@ impl UpdateManager {
             });
         }
 
-        let releases: Vec<ReleaseInfo> =
-            response.json().map_err(|e| ThothError::UpdateCheckError {
-                reason: format!("Failed to parse GitHub releases: {}", e),
-            })?;
+        let releases: Vec<ReleaseInfo> = response.json().map_err(|e| {
+            if e.is_timeout() {
+                timed_out()
+            } else {
+                ThothError::UpdateCheckError {
+                    reason: format!("Failed to parse GitHub releases: {}", e),
+                }
+            }
+        })?;
 
         Ok(releases)
     }
 
-    pub fn has_newer_version(releases: &[ReleaseInfo]) -> bool {
-        if let Some(latest) = releases.first() {
-            let latest_version = Self::parse_version(&latest.tag_name);
-            let current_version = Self::parse_version(CURRENT_VERSION);
-
-            if let (Some(latest), Some(current)) = (latest_version, current_version) {
-                return Self::compare_versions(&latest, &current) > 0;
-            }
-        }
-        false
+    pub fn has_newer_version(releases: &[ReleaseInfo], include_prereleases: bool) -> bool {
+        !Self::get_newer_releases(releases, include_prereleases).is_empty()
     }
 
-    pub fn get_newer_releases(releases: &[ReleaseInfo]) -> Vec<ReleaseInfo> {
-        let current_version = Self::parse_version(CURRENT_VERSION);
+    /// Releases newer than the running version, newest first
+    ///
+    /// Pre-releases (flagged as such on GitHub or tagged like `v0.3.0-beta.2`)
+    /// are only considered with `include_prereleases`.
+    pub fn get_newer_releases(
+        releases: &[ReleaseInfo],
+        include_prereleases: bool,
+    ) -> Vec<ReleaseInfo> {
+        Self::newer_than(releases, CURRENT_VERSION, include_prereleases)
+    }
 
-        releases
+    fn newer_than(
+        releases: &[ReleaseInfo],
+        current: &str,
+        include_prereleases: bool,
+    ) -> Vec<ReleaseInfo> {
+        let Some(current) = Version::parse(current) else {
+            return Vec::new();
+        };
+        let mut newer: Vec<(Version, &ReleaseInfo)> = releases
             .iter()
-            .filter(|release| {
-                let release_version = Self::parse_version(&release.tag_name);
-                if let (Some(rv), Some(cv)) = (release_version, current_version.as_ref()) {
-                    Self::compare_versions(&rv, cv) > 0
-                } else {
-                    false
-                }
+            .filter_map(|release| Some((Version::parse(&release.tag_name)?, release)))
+            .filter(|(version, release)| {
+                include_prereleases || !(release.prerelease || version.is_prerelease())
             })
-            .cloned()
+            .filter(|(version, _)| *version > current)
+            .collect();
+        newer.sort_by(|a, b| b.0.cmp(&a.0));
+        newer
+            .into_iter()
+            .map(|(_, release)| release.clone())
             .collect()
     }
 
-    fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
-        let version = version.trim_start_matches('v');
-        let parts: Vec<&str> = version.split('.').collect();
-
-        if parts.len() != 3 {
-            return None;
-        }
-
-        let major = parts[0].parse::<u32>().ok()?;
-        let minor = parts[1].parse::<u32>().ok()?;
-        let patch = parts[2].parse::<u32>().ok()?;
-
-        Some((major, minor, patch))
-    }
-
-    fn compare_versions(a: &(u32, u32, u32), b: &(u32, u32, u32)) -> i32 {
-        if a.0 != b.0 {
-            return (a.0 as i32) - (b.0 as i32);
-        }
-        if a.1 != b.1 {
-            return (a.1 as i32) - (b.1 as i32);
-        }
-        (a.2 as i32) - (b.2 as i32)
-    }
-
     pub fn download_update(&self, release: &ReleaseInfo) {
         let tx = self.tx.clone();
         let release = release.clone();
@@ -452,3 +452,176 @@ impl UpdateManager {
         CURRENT_VERSION
     }
 }
+
+/// A release version, `major.minor.patch` with an optional pre-release such
+/// as `-beta.2`, ordered by semver precedence (`0.3.0-beta.2 < 0.3.0`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Version {
+    core: (u64, u64, u64),
+    pre: Vec<PreRelease>,
+}
+
+/// One dot-separated identifier of a pre-release; numeric identifiers sort
+/// numerically and below alphanumeric ones
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum PreRelease {
+    Numeric(u64),
+    Alpha(String),
+}
+
+impl Version {
+    /// Parse a tag like `v0.3.0`, `0.3.0-rc.1` or `0.3.0+build.5`
+    fn parse(tag: &str) -> Option<Self> {
+        let version = tag.trim().trim_start_matches('v');
+        // Build metadata plays no part in ordering
+        let version = version.split_once('+').map_or(version, |(v, _)| v);
+        let (core, pre) = match version.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (version, None),
+        };
+
+        let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+        let core = (parts.next()??, parts.next()??, parts.next()??);
+        if parts.next().is_some() {
+            return None;
+        }
+
+        let pre = match pre {
+            None => Vec::new(),
+            Some(pre) => pre
+                .split('.')
+                .map(|id| {
+                    if id.is_empty() {
+                        None
+                    } else if id.bytes().all(|b| b.is_ascii_digit()) {
+                        id.parse().ok().map(PreRelease::Numeric)
+                    } else {
+                        Some(PreRelease::Alpha(id.to_string()))
+                    }
+                })
+                .collect::<Option<_>>()?,
+        };
+        Some(Self { core, pre })
+    }
+
+    fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.core.cmp(&other.core).then_with(|| {
+            match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => std::cmp::Ordering::Equal,
+                // A pre-release comes before the release it leads up to
+                (true, false) => std::cmp::Ordering::Greater,
+                (false, true) => std::cmp::Ordering::Less,
+                // Identifier by identifier; a shorter list that is a prefix
+                // of the longer one comes first
+                (false, false) => self.pre.cmp(&other.pre),
+            }
+        })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(tag: &str) -> Version {
+        Version::parse(tag).unwrap()
+    }
+
+    fn release(tag: &str, prerelease: bool) -> ReleaseInfo {
+        ReleaseInfo {
+            tag_name: tag.to_string(),
+            name: tag.to_string(),
+            body: String::new(),
+            published_at: String::new(),
+            html_url: String::new(),
+            prerelease,
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_version_parse() {
+        assert_eq!(v("v1.2.3").core, (1, 2, 3));
+        assert_eq!(
+            v("0.3.0-beta.2+build.7").pre,
+            [
+                PreRelease::Alpha("beta".to_string()),
+                PreRelease::Numeric(2)
+            ]
+        );
+        assert!(Version::parse("1.2").is_none());
+        assert!(Version::parse("1.2.3.4").is_none());
+        assert!(Version::parse("1.2.x").is_none());
+        assert!(Version::parse("1.2.3-").is_none());
+        assert!(Version::parse("1.2.3-beta..1").is_none());
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        // The semver spec's own example, each lower than the next
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.1.0-beta.1",
+            "1.1.0",
+            "2.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+        assert!(v("0.3.0-beta.2") < v("v0.3.0"));
+        assert!(v("0.10.0") > v("0.9.9"));
+        assert_eq!(v("1.0.0+a").cmp(&v("v1.0.0+b")), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn test_newer_releases_stable_only() {
+        let releases = [
+            release("v0.4.0-beta.1", true),
+            release("v0.3.1", false),
+            release("v0.3.0", false),
+            // Tagged as a pre-release even though GitHub wasn't told
+            release("v0.3.2-rc.1", false),
+        ];
+        let newer = UpdateManager::newer_than(&releases, "0.3.0", false);
+        let tags: Vec<&str> = newer.iter().map(|r| r.tag_name.as_str()).collect();
+        assert_eq!(tags, ["v0.3.1"]);
+        assert!(UpdateManager::newer_than(&releases, "0.3.1", false).is_empty());
+    }
+
+    #[test]
+    fn test_newer_releases_with_prereleases() {
+        let releases = [
+            release("v0.3.0", false),
+            release("v0.4.0-beta.1", true),
+            release("v0.4.0-beta.2", true),
+            release("v0.3.0-beta.3", true),
+        ];
+        let newer = UpdateManager::newer_than(&releases, "0.3.0-beta.3", true);
+        let tags: Vec<&str> = newer.iter().map(|r| r.tag_name.as_str()).collect();
+        assert_eq!(tags, ["v0.4.0-beta.2", "v0.4.0-beta.1", "v0.3.0"]);
+        // Running a beta, the matching stable release is still offered without opting in
+        let newer = UpdateManager::newer_than(&releases, "0.3.0-beta.3", false);
+        assert_eq!(newer.len(), 1);
+        assert_eq!(newer[0].tag_name, "v0.3.0");
+    }
+}
@@ -1,4 +1,6 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+
+use crate::constants::UPDATE_RETRY_BASE_SECS;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -44,6 +46,8 @@ pub enum UpdateState {
 pub struct UpdateStatus {
     pub state: UpdateState,
     pub last_check: Option<DateTime<Utc>>,
+    /// Checks that failed in a row since the last one that succeeded
+    pub failures: u32,
 }
 
 impl Default for UpdateStatus {
@@ -51,6 +55,7 @@ impl Default for UpdateStatus {
         Self {
             state: UpdateState::Idle,
             last_check: None,
+            failures: 0,
         }
     }
 }
@@ -68,8 +73,57 @@ impl UpdateStatus {
             Some(last_check) => {
                 let now = Utc::now();
                 let duration = now.signed_duration_since(last_check);
-                duration.num_hours() >= check_interval_hours as i64
+                duration >= next_check_delay(check_interval_hours, self.failures)
             }
         }
     }
 }
+
+/// Time from the last check to the next automatic one
+///
+/// After `failures` failed checks in a row the next one comes sooner, backing
+/// off exponentially from [`UPDATE_RETRY_BASE_SECS`] up to the regular interval
+/// so an unreachable endpoint isn't asked again every frame.
+pub fn next_check_delay(check_interval_hours: u64, failures: u32) -> Duration {
+    let interval = Duration::hours(check_interval_hours as i64);
+    if failures == 0 {
+        return interval;
+    }
+    let backoff = UPDATE_RETRY_BASE_SECS.saturating_mul(1 << (failures - 1).min(30));
+    Duration::seconds(backoff).min(interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_check_delay_backs_off() {
+        assert_eq!(next_check_delay(24, 0), Duration::hours(24));
+        assert_eq!(
+            next_check_delay(24, 1),
+            Duration::seconds(UPDATE_RETRY_BASE_SECS)
+        );
+        assert_eq!(
+            next_check_delay(24, 3),
+            Duration::seconds(UPDATE_RETRY_BASE_SECS * 4)
+        );
+        // Never waits longer than the regular interval
+        assert_eq!(next_check_delay(1, 20), Duration::hours(1));
+        assert_eq!(next_check_delay(24, u32::MAX), Duration::hours(24));
+    }
+
+    #[test]
+    fn test_should_check_after_failure() {
+        let mut status = UpdateStatus {
+            last_check: Some(Utc::now() - Duration::seconds(UPDATE_RETRY_BASE_SECS + 1)),
+            ..Default::default()
+        };
+        assert!(!status.should_check(24, true));
+        status.failures = 1;
+        assert!(status.should_check(24, true));
+        assert!(!status.should_check(24, false));
+        status.failures = 2;
+        assert!(!status.should_check(24, true));
+    }
+}
@@ -12,6 +12,7 @@
 //! - `ThothApp::poll_os_open_requests()` draining the queue into `window_state`
 //! - Existing argv path still works (regression guard)
 //! - Second file replaces current file, error state is cleared
+//! - Several dispatched files all open, and an already-open file reuses its tab
 //!
 //! ## What is NOT tested (requires a real macOS `.app` bundle)
 //!
@@ -155,7 +156,7 @@ fn thoth_app_picks_up_os_dispatched_file() {
     // Launch app with no file (simulates Finder-launched empty window).
     // Session restore may reopen previously-open tabs, so we don't assert a blank
     // initial state — that is legitimate behaviour.
-    let mut app = ThothApp::new(Settings::default(), None);
+    let mut app = ThothApp::new(Settings::default(), Vec::new());
 
     // Simulate macOS dispatching a file via Apple Event
     let path = make_temp_json_file("os_dispatch.json");
@@ -179,14 +180,14 @@ fn argv_path_still_loads_on_construction() {
     let _guard = test_guard();
     // This guards against regressions: the existing CLI flow must keep working.
     let path = make_temp_json_file("argv_test.json");
-    let mut app = ThothApp::new(Settings::default(), Some(path.clone()));
+    let mut app = ThothApp::new(Settings::default(), vec![path.clone()]);
     assert_eq!(
         app.window_state
             .tab_manager
             .active_tab_mut()
             .and_then(|t| t.file_path.as_deref()),
         Some(path.as_path()),
-        "file_to_open passed via argv must set the active tab's file_path in constructor"
+        "files passed via argv must set the active tab's file_path in constructor"
     );
 }
 
@@ -199,7 +200,7 @@ fn second_os_dispatch_replaces_current_file() {
     let p2 = make_temp_json_file("second_file.json");
 
     // App launched with first file via argv
-    let mut app = ThothApp::new(Settings::default(), Some(p1.clone()));
+    let mut app = ThothApp::new(Settings::default(), vec![p1.clone()]);
     assert!(
         app.window_state
             .tab_manager
@@ -227,7 +228,7 @@ fn os_dispatch_clears_previous_error() {
     let _guard = test_guard();
     reset();
 
-    let mut app = ThothApp::new(Settings::default(), None);
+    let mut app = ThothApp::new(Settings::default(), Vec::new());
     // Simulate an error state on the active tab
     if let Some(tab) = app.window_state.tab_manager.active_tab_mut() {
         tab.error = Some(thoth::error::ThothError::Unknown {
@@ -254,3 +255,51 @@ fn os_dispatch_clears_previous_error() {
         Some(path.as_path())
     );
 }
+
+#[test]
+fn os_dispatch_opens_every_file_and_reuses_open_tabs() {
+    let _guard = test_guard();
+    reset();
+
+    let p1 = make_temp_json_file("multi_first.json");
+    let p2 = make_temp_json_file("multi_second.json");
+    let mut app = ThothApp::new(Settings::default(), Vec::new());
+
+    // Finder "Open With" on a multi-selection dispatches every file at once
+    file_open_channel::enqueue_open_request(p1.clone());
+    file_open_channel::enqueue_open_request(p2.clone());
+    assert!(app.poll_os_open_requests());
+
+    let tabs_with = |app: &ThothApp, path: &PathBuf| {
+        app.window_state
+            .tab_manager
+            .tabs
+            .values()
+            .filter(|t| t.file_path.as_deref() == Some(path.as_path()))
+            .count()
+    };
+    assert_eq!(tabs_with(&app, &p1), 1);
+    assert_eq!(tabs_with(&app, &p2), 1);
+    assert_eq!(
+        app.window_state
+            .tab_manager
+            .active_tab_mut()
+            .and_then(|t| t.file_path.as_deref()),
+        Some(p1.as_path()),
+        "the first dispatched file must be focused"
+    );
+
+    // Opening an already-open file focuses its tab instead of duplicating it
+    file_open_channel::enqueue_open_request(p2.clone());
+    app.poll_os_open_requests();
+    assert_eq!(tabs_with(&app, &p2), 1);
+    assert_eq!(
+        app.window_state
+            .tab_manager
+            .active_tab_mut()
+            .and_then(|t| t.file_path.as_deref()),
+        Some(p2.as_path())
+    );
+
+    assert!(!app.poll_os_open_requests(), "nothing left to open");
+}
@@ -1,6 +1,7 @@
 use std::path::Path;
+use thoth::error::ThothError;
 use thoth::file::detect_file_type::{DetectedFileType, sniff_file_type};
-use thoth::file::loaders::{FileLoader, FileType};
+use thoth::file::loaders::{FileLoader, FileType, NdjsonFile};
 
 // Convenience alias matching the old name used throughout these tests.
 type LazyJsonFile = FileType;
@@ -53,6 +54,51 @@ fn test_load_ndjson_single_line_fixture() {
     assert_eq!(val["id"], 1);
 }
 
+#[test]
+fn test_ndjson_malformed_line_fails_alone() {
+    let path = Path::new("tests/fixtures/ndjson/malformed.ndjson");
+    let mut loader = NdjsonFile::open(path).unwrap();
+
+    assert_eq!(loader.len(), 3);
+    assert_eq!(loader.get(0).unwrap()["name"], "Valid");
+    assert!(matches!(
+        loader.get(1),
+        Err(ThothError::JsonParseError { line: Some(2), .. })
+    ));
+    assert_eq!(loader.get(2).unwrap()["name"], "AlsoValid");
+    assert_eq!(loader.raw_line(1).unwrap(), b"{invalid json}");
+}
+
+#[test]
+fn test_ndjson_line_numbers_match_fixture() {
+    let path = Path::new("tests/fixtures/ndjson/simple.ndjson");
+    let mut loader = LazyJsonFile::open(path).unwrap();
+    let text = std::fs::read_to_string(path).unwrap();
+
+    for idx in 0..loader.len() {
+        let line = loader.line_number(idx).unwrap().unwrap();
+        let source_line = text.lines().nth(line - 1).unwrap();
+        let at_line: serde_json::Value = serde_json::from_str(source_line).unwrap();
+        assert_eq!(
+            at_line,
+            loader.get(idx).unwrap(),
+            "record {idx} reported line {line}"
+        );
+    }
+    assert_eq!(loader.line_number(10).unwrap(), None);
+}
+
+#[test]
+fn test_json_array_line_numbers_match_fixture() {
+    let path = Path::new("tests/fixtures/json_array/simple.json");
+    let loader = LazyJsonFile::open(path).unwrap();
+
+    // Elements start on lines 2-4, after the opening bracket
+    for idx in 0..3 {
+        assert_eq!(loader.line_number(idx).unwrap(), Some(idx + 2));
+    }
+}
+
 #[test]
 fn test_load_json_array_simple_fixture() {
     let path = Path::new("tests/fixtures/json_array/simple.json");
@@ -1107,6 +1107,7 @@ fn dark_palette() -> ThemeColors {
         sidebar_hover: rgb(0x45, 0x47, 0x5a),
         sidebar_header: rgb(0xa6, 0xad, 0xc8),
         indent_guide: rgb(0x45, 0x47, 0x5a),
+        search_highlight: Color32::from_rgba_unmultiplied(0xcb, 0xa6, 0xf7, 60),
     }
 }
 
@@ -1135,5 +1136,6 @@ fn light_palette() -> ThemeColors {
         sidebar_hover: rgb(0xbc, 0xc0, 0xcc),
         sidebar_header: rgb(0x6c, 0x6f, 0x85),
         indent_guide: rgb(0xbc, 0xc0, 0xcc),
+        search_highlight: Color32::from_rgba_unmultiplied(0x88, 0x39, 0xef, 60),
     }
 }
@@ -20,6 +20,12 @@ pub struct RowHighlights {
     pub value_ranges: Vec<std::ops::Range<usize>>,
 }
 
+/// Default indentation step per tree depth level, in logical pixels.
+pub const DEFAULT_INDENT_STEP: f32 = 16.0;
+
+/// Default row height, in logical pixels (matches `theme::ROW_HEIGHT`).
+pub const DEFAULT_ROW_HEIGHT: f32 = 22.0;
+
 /// An optional leading icon for a [`DataRow`].
 #[derive(Clone, Debug, Serialize, Deserialize, Builder)]
 #[builder(on(String, into))]
@@ -77,10 +83,19 @@ pub struct DataRow {
     #[builder(default)]
     #[serde(default)]
     pub syntax_highlighting: bool,
-    /// Indentation depth (multiplied by a fixed step).
+    /// Indentation depth (multiplied by [`indent_step`](DataRow::indent_step)).
     #[builder(default)]
     #[serde(default)]
     pub indent: usize,
+    /// Width of one indentation level, in logical pixels.
+    #[builder(default = DEFAULT_INDENT_STEP)]
+    #[serde(default = "default_indent_step")]
+    pub indent_step: f32,
+    /// Minimum row height, in logical pixels. Virtualised lists must pass the
+    /// same height they give `show_rows`.
+    #[builder(default = DEFAULT_ROW_HEIGHT)]
+    #[serde(default = "default_height")]
+    pub height: f32,
     /// `Some(expanded)` renders an expand/collapse caret; `None` renders an
     /// aligned spacer (leaf row).
     #[serde(default)]
@@ -91,6 +106,14 @@ pub struct DataRow {
     /// Optional right-aligned muted text (e.g. a count or type).
     #[serde(default)]
     pub trailing: Option<String>,
+    /// Token class colouring [`trailing`](DataRow::trailing) when syntax
+    /// highlighting is on; muted otherwise.
+    #[serde(default)]
+    pub trailing_token: Option<TextToken>,
+    /// Optional muted note shown right after the value (e.g. a readable form
+    /// of a timestamp). It isn't selectable, so copying the value leaves it out.
+    #[serde(default)]
+    pub annotation: Option<String>,
     /// Optional right-aligned action icon (a Phosphor glyph). Clicking it reports
     /// [`DataRowOutput::action_clicked`] instead of a row click — e.g. an
     /// "open structure" affordance on a table row.
@@ -99,6 +122,14 @@ pub struct DataRow {
     /// Tooltip for [`action_icon`](DataRow::action_icon).
     #[serde(default)]
     pub action_tooltip: Option<String>,
+    /// Optional right-aligned pill (e.g. a search match count) in the
+    /// search-highlight colour. Clicking it reports
+    /// [`DataRowOutput::badge_clicked`] instead of a row click.
+    #[serde(default)]
+    pub count_badge: Option<String>,
+    /// Tooltip for [`count_badge`](DataRow::count_badge).
+    #[serde(default)]
+    pub count_badge_tooltip: Option<String>,
     /// Truncate the key/value text with an ellipsis and pin the trailing/action
     /// to the right edge (for a full-width, non-horizontally-scrolling list).
     /// Defaults to `false`: the text extends and stays readable inside a
@@ -115,3 +146,98 @@ pub struct DataRow {
 fn default_key_token() -> TextToken {
     TextToken::Key
 }
+
+fn default_indent_step() -> f32 {
+    DEFAULT_INDENT_STEP
+}
+
+fn default_height() -> f32 {
+    DEFAULT_ROW_HEIGHT
+}
+
+impl DataRow {
+    /// Horizontal space reserved before the caret for this row's depth.
+    pub fn indent_offset(&self) -> f32 {
+        self.indent as f32 * self.indent_step
+    }
+}
+
+/// Split a row's display text into its key part and the value part after the
+/// separating colon (`""` for a key-only row).
+///
+/// The separator is the first `": "`, so keys containing a bare `:` (URNs,
+/// times) stay whole; text without one falls back to the first `:`.
+pub fn split_display_text(display_text: &str) -> (&str, &str) {
+    let colon = display_text.find(": ").or_else(|| display_text.find(':'));
+    match colon {
+        Some(idx) => (&display_text[..idx], &display_text[idx + 1..]),
+        None => (display_text, ""),
+    }
+}
+
+/// Sort `ranges`, clamp them to `text`, widen them to whole characters and
+/// merge any that overlap or touch, so each can be sliced and drawn once.
+#[cfg_attr(not(feature = "egui"), allow(dead_code))]
+pub(crate) fn normalize_ranges(
+    text: &str,
+    ranges: &[std::ops::Range<usize>],
+) -> Vec<std::ops::Range<usize>> {
+    let mut snapped: Vec<std::ops::Range<usize>> = ranges
+        .iter()
+        .filter_map(|range| {
+            let mut start = range.start.min(text.len());
+            let mut end = range.end.min(text.len());
+            while !text.is_char_boundary(start) {
+                start -= 1;
+            }
+            while !text.is_char_boundary(end) {
+                end += 1;
+            }
+            (start < end).then_some(start..end)
+        })
+        .collect();
+    snapped.sort_unstable_by_key(|range| range.start);
+
+    let mut merged: Vec<std::ops::Range<usize>> = Vec::with_capacity(snapped.len());
+    for range in snapped {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_keeps_colons_inside_keys() {
+        assert_eq!(
+            split_display_text("\"urn:id\": \"a: b\""),
+            ("\"urn:id\"", " \"a: b\"")
+        );
+        assert_eq!(split_display_text("[0]: {"), ("[0]", " {"));
+        assert_eq!(split_display_text("\"a\":1"), ("\"a\"", "1"));
+        assert_eq!(split_display_text("}"), ("}", ""));
+    }
+
+    #[test]
+    fn normalize_sorts_and_merges_overlaps() {
+        let text = "abcdefghij";
+        assert_eq!(
+            normalize_ranges(text, &[6..8, 0..2, 1..3, 8..9, 20..30]),
+            vec![0..3, 6..9]
+        );
+    }
+
+    #[test]
+    fn normalize_widens_to_char_boundaries() {
+        // "é" is two bytes, "日" three
+        let text = "xé日y";
+        assert_eq!(normalize_ranges(text, &[2..3]), vec![1..3]);
+        assert_eq!(normalize_ranges(text, &[3..4]), vec![3..6]);
+        assert_eq!(normalize_ranges(text, &[0..text.len() + 5]), vec![0..7]);
+    }
+}
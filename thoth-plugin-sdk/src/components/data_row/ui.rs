@@ -2,15 +2,12 @@ use std::sync::Arc;
 
 use egui::{Color32, RichText, Ui, WidgetText, text::LayoutJob};
 
-use crate::components::IconButton;
+use crate::components::{Badge, IconButton};
 use crate::theme::{
-    ROW_HEIGHT, TextPalette, ThemeColors, hover_row_bg, phosphor_font_id, resolve_color,
+    TextPalette, ThemeColors, color_to_hex, hover_row_bg, phosphor_font_id, resolve_color,
 };
 
-use super::DataRow;
-
-/// Indentation step per tree depth level, in logical pixels.
-const INDENT_STEP: f32 = 16.0;
+use super::{DataRow, normalize_ranges, split_display_text};
 
 /// Outcome of rendering a [`DataRow`].
 pub struct DataRowOutput {
@@ -22,6 +19,8 @@ pub struct DataRowOutput {
     pub caret_clicked: bool,
     /// The trailing action icon was clicked (takes precedence over `clicked`).
     pub action_clicked: bool,
+    /// The count badge was clicked (takes precedence over `clicked`).
+    pub badge_clicked: bool,
     /// The row's interaction response.
     pub response: egui::Response,
 }
@@ -31,16 +30,14 @@ impl DataRow {
     pub fn show(&self, ui: &mut Ui) -> DataRowOutput {
         let palette = TextPalette::from_ctx(ui.ctx());
 
-        let mut parts = self.display_text.splitn(2, ':');
-        let key_part = parts.next().unwrap_or("");
-        let value_part = parts.next().unwrap_or("");
+        let (key_part, value_part) = split_display_text(&self.display_text);
         let has_colon = !value_part.is_empty() && self.value_token.is_some();
 
         let id = ui.id().with(&self.row_id);
         let available_rect = ui.available_rect_before_wrap();
         let interact_rect = egui::Rect::from_min_size(
             available_rect.min,
-            egui::vec2(ui.available_width(), ROW_HEIGHT),
+            egui::vec2(ui.available_width(), self.height),
         );
         let resp = ui.interact(interact_rect, id, egui::Sense::click());
 
@@ -62,21 +59,26 @@ impl DataRow {
             base_bg
         };
 
-        let highlight_bg = ui.visuals().selection.bg_fill;
+        // Palettes without a search-highlight slot fall back to the selection fill
+        let highlight_bg = Some(ThemeColors::from_ctx(ui.ctx()).search_highlight)
+            .filter(|color| *color != Color32::TRANSPARENT)
+            .unwrap_or(ui.visuals().selection.bg_fill);
         let highlight_fg = ui.visuals().strong_text_color();
         let base_text_color = ui.visuals().text_color();
         let muted = ui.visuals().weak_text_color();
 
         let mut caret_clicked = false;
         let mut action_clicked = false;
+        let mut badge_clicked = false;
         let mut body_clicked = false;
         let mut body_secondary = false;
 
         egui::Frame::new().fill(background).show(ui, |ui| {
             ui.set_min_width(ui.available_width());
             ui.horizontal(|ui| {
+                ui.set_min_height(self.height);
                 if self.indent > 0 {
-                    ui.add_space(self.indent as f32 * INDENT_STEP);
+                    ui.add_space(self.indent_offset());
                 }
 
                 match self.caret {
@@ -158,14 +160,21 @@ impl DataRow {
                     )
                 });
 
-                // Trailing count + action, added right-to-left so they pin to the
-                // right edge. Flags are passed in (not captured) so the label/
-                // truncate code below can still borrow them.
+                // Trailing action + badge + count, added right-to-left so they pin
+                // to the right edge. Flags are passed in (not captured) so the
+                // label/truncate code below can still borrow them.
                 let action_icon = self.action_icon.clone();
                 let action_tooltip = self.action_tooltip.clone();
+                let count_badge = self.count_badge.clone();
+                let count_badge_tooltip = self.count_badge_tooltip.clone();
+                let badge_id = id.with("count_badge");
                 let trailing_text = self.trailing.clone();
-                let render_trailing = |ui: &mut Ui, bc: &mut bool, bs: &mut bool| -> bool {
+                let trailing_color = self.trailing_token.map_or(muted, |token| {
+                    palette.color_with_highlighting(token, self.syntax_highlighting, muted)
+                });
+                let render_trailing = |ui: &mut Ui, bc: &mut bool, bs: &mut bool| -> (bool, bool) {
                     let mut clicked = false;
+                    let mut badge_clicked = false;
                     if let Some(glyph) = &action_icon {
                         clicked = ui
                             .add(
@@ -176,29 +185,52 @@ impl DataRow {
                             )
                             .clicked();
                     }
+                    if let Some(label) = &count_badge {
+                        let rect = ui
+                            .add(
+                                Badge::builder()
+                                    .label(label.as_str())
+                                    .color(color_to_hex(highlight_bg))
+                                    .build(),
+                            )
+                            .rect;
+                        let mut resp = ui
+                            .interact(rect, badge_id, egui::Sense::click())
+                            .on_hover_cursor(egui::CursorIcon::PointingHand);
+                        if let Some(tooltip) = &count_badge_tooltip {
+                            resp = resp.on_hover_text(tooltip);
+                        }
+                        badge_clicked = resp.clicked();
+                    }
                     if let Some(t) = &trailing_text {
                         body_label(
                             ui,
-                            RichText::new(t).color(muted).size(11.0).into(),
+                            RichText::new(t).color(trailing_color).size(11.0).into(),
                             false,
                             bc,
                             bs,
                         );
                     }
-                    clicked
+                    (clicked, badge_clicked)
                 };
 
+                let annotation = self
+                    .annotation
+                    .as_ref()
+                    .map(|note| WidgetText::from(RichText::new(note).color(muted).italics()));
+
                 if self.truncate {
                     // Full-width row: pin trailing/action right, and truncate the
                     // key/value in the middle with an ellipsis so nothing bleeds.
-                    let remaining = egui::vec2(ui.available_width(), ROW_HEIGHT);
+                    let remaining = egui::vec2(ui.available_width(), self.height);
                     ui.allocate_ui_with_layout(
                         remaining,
                         egui::Layout::right_to_left(egui::Align::Center),
                         |ui| {
-                            if render_trailing(ui, &mut body_clicked, &mut body_secondary) {
-                                action_clicked = true;
-                            }
+                            let (action, badge) =
+                                render_trailing(ui, &mut body_clicked, &mut body_secondary);
+                            action_clicked |= action;
+                            badge_clicked |= badge;
                             ui.with_layout(
                                 egui::Layout::left_to_right(egui::Align::Center),
                                 |ui| {
@@ -219,6 +251,15 @@ impl DataRow {
                                             &mut body_secondary,
                                         );
                                     }
+                                    if let Some(annotation) = annotation {
+                                        body_label(
+                                            ui,
+                                            annotation,
+                                            false,
+                                            &mut body_clicked,
+                                            &mut body_secondary,
+                                        );
+                                    }
                                 },
                             );
                         },
@@ -236,11 +277,21 @@ impl DataRow {
                             &mut body_secondary,
                         );
                     }
-                    if action_icon.is_some() || trailing_text.is_some() {
+                    if let Some(annotation) = annotation {
+                        body_label(
+                            ui,
+                            annotation,
+                            false,
+                            &mut body_clicked,
+                            &mut body_secondary,
+                        );
+                    }
+                    if action_icon.is_some() || count_badge.is_some() || trailing_text.is_some() {
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            if render_trailing(ui, &mut body_clicked, &mut body_secondary) {
-                                action_clicked = true;
-                            }
+                            let (action, badge) =
+                                render_trailing(ui, &mut body_clicked, &mut body_secondary);
+                            action_clicked |= action;
+                            badge_clicked |= badge;
                         });
                     }
                 }
@@ -252,12 +303,16 @@ impl DataRow {
         }
 
         DataRowOutput {
-            // A caret or action click takes precedence and must not surface as a
-            // row click.
-            clicked: !caret_clicked && !action_clicked && (resp.clicked() || body_clicked),
+            // A caret, action or badge click takes precedence and must not
+            // surface as a row click.
+            clicked: !caret_clicked
+                && !action_clicked
+                && !badge_clicked
+                && (resp.clicked() || body_clicked),
             right_clicked: resp.secondary_clicked() || body_secondary,
             caret_clicked,
             action_clicked,
+            badge_clicked,
             response: resp,
         }
     }
@@ -335,16 +390,12 @@ fn highlighted_text(
     };
 
     let mut cursor = 0;
-    for range in ranges {
-        let start = range.start.min(text.len());
-        let end = range.end.min(text.len());
-        if start > cursor {
-            job.append(&text[cursor..start], 0.0, base_format.clone());
-        }
-        if start < end {
-            job.append(&text[start..end], 0.0, highlight_format.clone());
+    for range in normalize_ranges(text, ranges) {
+        if range.start > cursor {
+            job.append(&text[cursor..range.start], 0.0, base_format.clone());
         }
-        cursor = end;
+        job.append(&text[range.clone()], 0.0, highlight_format.clone());
+        cursor = range.end;
     }
     if cursor < text.len() {
         job.append(&text[cursor..], 0.0, base_format);
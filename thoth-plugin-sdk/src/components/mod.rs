@@ -53,7 +53,10 @@ pub use code::Code;
 pub use code_editor::{CodeEditor, CodeEditorOutput, CustomSyntax, RunRequest};
 #[cfg(feature = "egui")]
 pub use data_row::DataRowOutput;
-pub use data_row::{DataRow, DataRowIcon, RowHighlights};
+pub use data_row::{
+    DEFAULT_INDENT_STEP, DEFAULT_ROW_HEIGHT, DataRow, DataRowIcon, RowHighlights,
+    split_display_text,
+};
 pub use icon::Icon;
 pub use icon_button::IconButton;
 pub use input::Input;
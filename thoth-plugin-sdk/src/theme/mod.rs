@@ -90,6 +90,8 @@ pub struct ThemeColors {
     pub sidebar_header: Color32,
     /// Tree-view indent guide lines.
     pub indent_guide: Color32,
+    /// Background behind search-match highlights (may include alpha).
+    pub search_highlight: Color32,
 }
 
 impl ThemeColors {